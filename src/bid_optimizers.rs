@@ -5,6 +5,7 @@
 /// impression's competition and floor data.
 
 use crate::impressions::Impression;
+use crate::competition::ImpressionCompetition;
 use crate::sigmoid::Sigmoid;
 
 /// Trait for bid optimization strategies
@@ -14,34 +15,45 @@ pub trait BidOptimizerTrait {
     /// # Arguments
     /// * `value` - The base value to optimize (e.g., boosted price, max affordable bid)
     /// * `impression` - The impression being bid on
+    /// * `competition` - Competition data of the impression as seen by the campaign
+    ///   (win rate prediction may be miscalibrated compared to impression.competition)
     /// 
     /// # Returns
     /// The optimized bid value, or None if no bid should be made
-    fn get_optimized_bid(&self, value: f64, impression: &Impression) -> Option<f64>;
+    fn get_optimized_bid(&self, value: f64, impression: &Impression, competition: Option<&ImpressionCompetition>) -> Option<f64>;
     
     /// Get the name/type of this optimizer
     fn get_optimizer_type(&self) -> String;
+    
+    /// Whether the optimizer returns the value unchanged (allows batch bidding without calling it)
+    fn is_truthful(&self) -> bool {
+        false
+    }
 }
 
 /// Truthful bid optimizer that returns the value as-is without optimization
 pub struct BidOptimizerTrutful;
 
 impl BidOptimizerTrait for BidOptimizerTrutful {
-    fn get_optimized_bid(&self, value: f64, _impression: &Impression) -> Option<f64> {
+    fn get_optimized_bid(&self, value: f64, _impression: &Impression, _competition: Option<&ImpressionCompetition>) -> Option<f64> {
         Some(value)
     }
     
     fn get_optimizer_type(&self) -> String {
         "Truthful".to_string()
     }
+    
+    fn is_truthful(&self) -> bool {
+        true
+    }
 }
 
 /// Maximum margin bid optimizer that uses sigmoid-based optimization
 pub struct BidOptimizerMaximumMargin;
 
 impl BidOptimizerTrait for BidOptimizerMaximumMargin {
-    fn get_optimized_bid(&self, value: f64, impression: &Impression) -> Option<f64> {
-        let competition = impression.competition.as_ref()
+    fn get_optimized_bid(&self, value: f64, impression: &Impression, competition: Option<&ImpressionCompetition>) -> Option<f64> {
+        let competition = competition
             .expect("Maximum margin optimizer requires competition data. This impression has no competition data.");
         
         let sigmoid = Sigmoid::new(
@@ -58,16 +70,128 @@ impl BidOptimizerTrait for BidOptimizerMaximumMargin {
     }
 }
 
+/// Maximum margin bid optimizer with a floor-aware prediction sigmoid
+/// 
+/// The predicted win rate is truncated at the floor: zero below it and renormalized above it.
+/// Untruncated sigmoids assume bids just above the floor still win with the full sigmoid probability,
+/// which makes max margin bidding systematically bid too low near floors.
+pub struct BidOptimizerMaximumMarginFloorAware;
+
+impl BidOptimizerTrait for BidOptimizerMaximumMarginFloorAware {
+    fn get_optimized_bid(&self, value: f64, impression: &Impression, competition: Option<&ImpressionCompetition>) -> Option<f64> {
+        let competition = competition
+            .expect("Maximum margin optimizer requires competition data. This impression has no competition data.");
+        
+        let sigmoid = Sigmoid::new(
+            competition.win_rate_prediction_sigmoid_offset,
+            competition.win_rate_prediction_sigmoid_scale,
+            1.0,  // Using normalized value of 1.0
+        );
+        
+        sigmoid.max_margin_bid_bisection_floor_truncated(value, impression.floor_cpm)
+    }
+    
+    fn get_optimizer_type(&self) -> String {
+        "MaxMarginFloorAware".to_string()
+    }
+}
+
+/// Maximum margin bid optimizer aware of the soft floor price rule
+/// 
+/// Winning bids below the soft floor are charged second price and bids above it first price.
+/// The optimizer picks whichever regime gives the higher expected margin: truthful bidding capped
+/// just below the soft floor, or max margin bidding at or above the soft floor.
+/// Without a soft floor it bids like BidOptimizerMaximumMargin.
+pub struct BidOptimizerMaximumMarginSoftFloor;
+
+impl BidOptimizerTrait for BidOptimizerMaximumMarginSoftFloor {
+    fn get_optimized_bid(&self, value: f64, impression: &Impression, competition: Option<&ImpressionCompetition>) -> Option<f64> {
+        let competition = competition
+            .expect("Maximum margin optimizer requires competition data. This impression has no competition data.");
+        
+        let sigmoid = Sigmoid::new(
+            competition.win_rate_prediction_sigmoid_offset,
+            competition.win_rate_prediction_sigmoid_scale,
+            1.0,  // Using normalized value of 1.0
+        );
+        
+        match impression.soft_floor_cpm {
+            Some(soft_floor_cpm) => sigmoid.max_margin_bid_soft_floor(value, impression.floor_cpm, soft_floor_cpm),
+            None => sigmoid.max_margin_bid_bisection(value, impression.floor_cpm),
+        }
+    }
+    
+    fn get_optimizer_type(&self) -> String {
+        "MaxMarginSoftFloor".to_string()
+    }
+}
+
+/// Minimum margin required per impression by BidOptimizerMinimumMargin
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MinimumMargin {
+    /// Margin in CPM: value - price >= margin
+    ABSOLUTE(f64),
+    /// Margin as a fraction of value: value - price >= fraction * value
+    RELATIVE(f64),
+}
+
+/// Bid optimizer wrapper that abstains when the bid of the wrapped optimizer leaves too little margin
+/// 
+/// The price is the bid itself (first price), margin is taken against the value given to the optimizer
+/// (paced value). Meant for arbitrage-style campaigns, wraps any optimizer (e.g. MaxMargin).
+pub struct BidOptimizerMinimumMargin {
+    pub bid_optimizer: Box<dyn BidOptimizerTrait>,
+    pub minimum_margin: MinimumMargin,
+}
+
+impl BidOptimizerTrait for BidOptimizerMinimumMargin {
+    fn get_optimized_bid(&self, value: f64, impression: &Impression, competition: Option<&ImpressionCompetition>) -> Option<f64> {
+        let bid = self.bid_optimizer.get_optimized_bid(value, impression, competition)?;
+        let required_margin = match self.minimum_margin {
+            MinimumMargin::ABSOLUTE(margin) => margin,
+            MinimumMargin::RELATIVE(fraction) => fraction * value,
+        };
+        if value - bid >= required_margin {
+            Some(bid)
+        } else {
+            None
+        }
+    }
+    
+    fn get_optimizer_type(&self) -> String {
+        match self.minimum_margin {
+            MinimumMargin::ABSOLUTE(margin) => format!("{} (minimum margin {:.2} CPM)", self.bid_optimizer.get_optimizer_type(), margin),
+            MinimumMargin::RELATIVE(fraction) => format!("{} (minimum margin {:.0}% of value)", self.bid_optimizer.get_optimizer_type(), fraction * 100.0),
+        }
+    }
+}
+
 /// Cheater bid optimizer that bids just above the minimum winning bid
-pub struct BidOptimizerCheater;
+/// 
+/// Last look abuse can be enforced against: with `detection_probability` the cheater is detected
+/// in an auction and excluded from it (no bid). Detection is drawn per impression, so the same
+/// impression is detected in every iteration of convergence.
+pub struct BidOptimizerCheater {
+    /// Probability of being detected and excluded from an auction (0.0 = never detected)
+    pub detection_probability: f64,
+}
 
 impl BidOptimizerTrait for BidOptimizerCheater {
-    fn get_optimized_bid(&self, value: f64, impression: &Impression) -> Option<f64> {
+    fn get_optimized_bid(&self, value: f64, impression: &Impression, competition: Option<&ImpressionCompetition>) -> Option<f64> {
         // value is the max_affordable_bid
         
+        // Detected cheaters are excluded from the auction
+        if self.detection_probability > 0.0 {
+            let detection_draw = (impression.seed(7997) >> 11) as f64 / (1u64 << 53) as f64;
+            if detection_draw < self.detection_probability {
+                return None;
+            }
+        }
+        
         // Calculate minimum winning bid as maximum of floor and competing bid, plus 0.00001
         let mut minimum_winning_bid = impression.floor_cpm;
-        if let Some(competition) = &impression.competition {
+        if let Some(competition) = competition {
             minimum_winning_bid = minimum_winning_bid.max(competition.bid_cpm);
         }
         
@@ -82,7 +206,11 @@ impl BidOptimizerTrait for BidOptimizerCheater {
     }
     
     fn get_optimizer_type(&self) -> String {
-        "Cheater".to_string()
+        if self.detection_probability > 0.0 {
+            format!("Cheater (detection probability {:.2})", self.detection_probability)
+        } else {
+            "Cheater".to_string()
+        }
     }
 }
 
@@ -90,10 +218,10 @@ impl BidOptimizerTrait for BidOptimizerCheater {
 pub struct BidOptimizerMedian;
 
 impl BidOptimizerTrait for BidOptimizerMedian {
-    fn get_optimized_bid(&self, value: f64, impression: &Impression) -> Option<f64> {
+    fn get_optimized_bid(&self, value: f64, impression: &Impression, competition: Option<&ImpressionCompetition>) -> Option<f64> {
         // value is the campaign_control_bid
         
-        let competition = impression.competition.as_ref()
+        let competition = competition
             .expect("Median optimizer requires competition data. This impression has no competition data.");
         
         let predicted_offset = competition.win_rate_prediction_sigmoid_offset;
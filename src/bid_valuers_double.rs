@@ -39,3 +39,91 @@ impl BidValuerTrait for BidValuerDualTarget {
     }
 }
 
+/// Bid valuer for dual-knob pacing with a multiplier and an additive boost
+/// bid = multiplier * value_to_campaign * seller_control_factor + additive_boost
+/// The multiplier converges to the first target and the additive boost to the second one.
+/// The additive boost shifts bids relatively more on low value impressions, so the two knobs
+/// together can hit e.g. both a budget and an impressions target.
+pub struct BidValuerMultiplicativeAdditive;
+
+impl BidValuerTrait for BidValuerMultiplicativeAdditive {
+    fn get_bid(&self, value_to_campaign: f64, _impression: &Impression, control_variables: &[f64], _converge_targets: &Vec<Box<dyn CampaignTargetTrait>>, seller_control_factor: f64, _logger: &mut Logger) -> Option<f64> {
+        assert_eq!(control_variables.len(), 2, "BidValuerMultiplicativeAdditive requires exactly 2 control variables");
+        let multiplier = control_variables[0];
+        let additive_boost = control_variables[1];
+        
+        Some(multiplier * value_to_campaign * seller_control_factor + additive_boost)
+    }
+    
+    fn get_valuer_type(&self) -> String {
+        "Multiplicative + additive dual pacing".to_string()
+    }
+}
+
+/// Bid valuer for multiplicative pacing constrained to a minimum average viewability
+/// bid = pacing * value_to_campaign * measured_viewability^(penalty - 1) * seller_control_factor
+/// Pacing converges to the first target and the viewability penalty (>= 1.0) to the viewability
+/// constraint. Penalty of 1.0 bids on plain value, higher penalties down-weight low viewability
+/// impressions more than highly viewable ones, raising the average viewability of won impressions.
+pub struct BidValuerViewabilityConstrained;
+
+impl BidValuerTrait for BidValuerViewabilityConstrained {
+    fn get_bid(&self, value_to_campaign: f64, impression: &Impression, control_variables: &[f64], _converge_targets: &Vec<Box<dyn CampaignTargetTrait>>, seller_control_factor: f64, _logger: &mut Logger) -> Option<f64> {
+        assert_eq!(control_variables.len(), 2, "BidValuerViewabilityConstrained requires exactly 2 control variables");
+        let pacing = control_variables[0];
+        let viewability_penalty = control_variables[1];
+        
+        Some(pacing * value_to_campaign * impression.measured_viewability.powf(viewability_penalty - 1.0) * seller_control_factor)
+    }
+    
+    fn get_valuer_type(&self) -> String {
+        "Multiplicative with viewability constraint".to_string()
+    }
+}
+
+/// Bid valuer for multiplicative pacing constrained to a maximum average CPM
+/// bid = (pacing * value_to_campaign * seller_control_factor)^exponent * max_avg_cpm^(1 - exponent)
+/// Pacing converges to the first target and the exponent (<= 1.0) to the CPM ceiling (the second
+/// target). Exponent of 1.0 bids on plain value, lower exponents pull bids geometrically towards the
+/// ceiling, skewing them away from high value impressions, which are the expensive ones, towards
+/// cheaper ones. At exponent 0.0 every bid is the ceiling itself.
+pub struct BidValuerCpmCeiling;
+
+impl BidValuerTrait for BidValuerCpmCeiling {
+    fn get_bid(&self, value_to_campaign: f64, _impression: &Impression, control_variables: &[f64], converge_targets: &Vec<Box<dyn CampaignTargetTrait>>, seller_control_factor: f64, _logger: &mut Logger) -> Option<f64> {
+        assert_eq!(control_variables.len(), 2, "BidValuerCpmCeiling requires exactly 2 control variables");
+        let pacing = control_variables[0];
+        let exponent = control_variables[1];
+        let max_avg_cpm = converge_targets[1].get_target_value();
+        
+        Some((pacing * value_to_campaign * seller_control_factor).powf(exponent) * max_avg_cpm.powf(1.0 - exponent))
+    }
+    
+    fn get_valuer_type(&self) -> String {
+        "Multiplicative with CPM ceiling".to_string()
+    }
+}
+
+/// Multiplicative bidder with a per-seller bid multiplier, keeping the share of spend on one seller
+/// below a maximum (second converge target)
+///
+/// Uses two control variables: pacing (first target) and the multiplier of bids on the seller's impressions
+/// (spend share target). Multiplier of 1.0 bids on plain paced value, lower multipliers shift spend
+/// from the seller to the others.
+pub struct BidValuerSellerSpendShare {
+    pub seller_id: usize,
+}
+
+impl BidValuerTrait for BidValuerSellerSpendShare {
+    fn get_bid(&self, value_to_campaign: f64, impression: &Impression, control_variables: &[f64], _converge_targets: &Vec<Box<dyn CampaignTargetTrait>>, seller_control_factor: f64, _logger: &mut Logger) -> Option<f64> {
+        assert_eq!(control_variables.len(), 2, "BidValuerSellerSpendShare requires exactly 2 control variables");
+        let pacing = control_variables[0];
+        let seller_multiplier = if impression.seller_id == self.seller_id { control_variables[1] } else { 1.0 };
+        
+        Some(pacing * seller_multiplier * value_to_campaign * seller_control_factor)
+    }
+    
+    fn get_valuer_type(&self) -> String {
+        format!("Multiplicative with spend share of seller {}", self.seller_id)
+    }
+}
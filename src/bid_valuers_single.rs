@@ -7,7 +7,7 @@
 /// A single controlling variable is usually seen simply as a pacing parameter, but it can be used for other purposes as well.
 
 
-use crate::impressions::Impression;
+use crate::impressions::{Impression, NUM_VALUE_COMPONENTS};
 use crate::logger::Logger;
 use crate::campaign_targets::CampaignTargetTrait;
 
@@ -31,6 +31,10 @@ impl BidValuerTrait for BidValuerMultiplicative {
     fn get_valuer_type(&self) -> String {
         "Multiplicative pacing".to_string()
     }
+    
+    fn linear_multiplier(&self, control_variables: &[f64]) -> Option<f64> {
+        Some(control_variables[0])
+    }
 }
 
 /// Bid valuer for multiplicative pacing with additive seller control factor
@@ -72,3 +76,46 @@ impl BidValuerTrait for BidValuerMultiplicative_ExponentialSupply {
 
 
 
+
+/// Bid valuer that discounts value by the impression's viewability probability, as measured
+/// Uses viewable value: campaign_control_factor * value_to_campaign * measured_viewability * seller_control_factor
+/// A viewability-blind bidder overpays for impressions that are unlikely to be seen, this one does not
+pub struct BidValuerViewability;
+
+impl BidValuerTrait for BidValuerViewability {
+    fn get_bid(&self, value_to_campaign: f64, impression: &Impression, control_variables: &[f64], _converge_targets: &Vec<Box<dyn CampaignTargetTrait>>, seller_control_factor: f64, _logger: &mut Logger) -> Option<f64> {
+        assert_eq!(control_variables.len(), 1, "BidValuerViewability requires exactly 1 control variable");
+        let campaign_control_factor = control_variables[0];
+        let bid = campaign_control_factor * value_to_campaign * impression.measured_viewability * seller_control_factor;
+        
+        Some(bid)
+    }
+    
+    fn get_valuer_type(&self) -> String {
+        "Viewability-weighted pacing".to_string()
+    }
+}
+
+/// Bid valuer for multiplicative pacing on a weighted sum of impression value components
+/// Effective value is Σ weights[i] × component[i] over Impression::value_components (value, viewable value, quality value)
+pub struct BidValuerWeightedValue {
+    pub weights: [f64; NUM_VALUE_COMPONENTS],
+}
+
+impl BidValuerTrait for BidValuerWeightedValue {
+    fn get_bid(&self, value_to_campaign: f64, impression: &Impression, control_variables: &[f64], _converge_targets: &Vec<Box<dyn CampaignTargetTrait>>, seller_control_factor: f64, _logger: &mut Logger) -> Option<f64> {
+        assert_eq!(control_variables.len(), 1, "BidValuerWeightedValue requires exactly 1 control variable");
+        let campaign_control_factor = control_variables[0];
+        let weighted_value: f64 = self.weights.iter()
+            .zip(impression.value_components(value_to_campaign))
+            .map(|(weight, component)| weight * component)
+            .sum();
+        let bid = campaign_control_factor * weighted_value * seller_control_factor;
+        
+        Some(bid)
+    }
+    
+    fn get_valuer_type(&self) -> String {
+        format!("Weighted value pacing (weights: value {:.2}, viewable {:.2}, quality {:.2})", self.weights[0], self.weights[1], self.weights[2])
+    }
+}
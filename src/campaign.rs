@@ -3,11 +3,17 @@ use crate::campaign_targets::CampaignTargetTrait;
 use crate::controllers::ControllerTrait;
 use crate::logger::Logger;
 use crate::bid_optimizers::BidOptimizerTrait;
+use crate::competition::{ImpressionCompetition, WinRateMiscalibration, win_rate_logistic_sigmoid};
+use crate::controller_state::ControllerStateCompetitionModel;
+use crate::campaign_learned_competition::learn_competition_model;
 use std::any::Any;
 
 /// Maximum number of controllers supported by campaigns
 const MAX_CONTROLLERS: usize = 10;
 
+/// Number of bids the linear batch path computes together, as SIMD lanes (one AVX or two SSE2 vectors of f64)
+const BID_LANES: usize = 4;
+
 /// Trait for campaigns participating in auctions
 pub trait CampaignTrait: Any {
     /// Get the campaign ID
@@ -21,6 +27,15 @@ pub trait CampaignTrait: Any {
     /// Returns None if bid cannot be calculated (logs warning via logger)
     fn get_bid(&self, impression: &Impression, controller_states: &[&dyn crate::controllers::ControllerStateTrait], seller_control_factor: f64, value_to_campaign: f64, logger: &mut crate::logger::Logger) -> Option<f64>;
     
+    /// Calculate bids for a batch of impressions, writing the bid for impressions[i] into bids[i]
+    /// `seller_control_factors` and `values_to_campaign` are given per impression, like the arguments of get_bid
+    /// Default falls back to calling get_bid for every impression, campaigns can override it with a faster batch path
+    fn get_bids(&self, impressions: &[Impression], controller_states: &[&dyn crate::controllers::ControllerStateTrait], seller_control_factors: &[f64], values_to_campaign: &[f64], bids: &mut [Option<f64>], logger: &mut crate::logger::Logger) {
+        for (index, impression) in impressions.iter().enumerate() {
+            bids[index] = self.get_bid(impression, controller_states, seller_control_factors[index], values_to_campaign[index], logger);
+        }
+    }
+    
     /// Create a new convergence parameter for this campaign type
     fn create_controller_state(&self) -> Vec<Box<dyn crate::controllers::ControllerStateTrait>>;
 
@@ -42,6 +57,48 @@ pub trait CampaignTrait: Any {
     /// * `controller_states` - Controller states to include pacing information
     fn type_target_and_controller_state_string(&self, controller_states: &[&dyn crate::controllers::ControllerStateTrait]) -> String;
     
+    /// Get the control variables (e.g., pacing) the campaign's bids depend on, in a fixed order
+    /// Used by convergence diagnostics to follow each control variable across iterations
+    fn get_control_variables(&self, controller_states: &[&dyn crate::controllers::ControllerStateTrait]) -> Vec<f64>;
+    
+    /// Get (target description, actual, target) of each convergence target from the statistics of an iteration
+    /// Targets are in the order of the control variables of get_control_variables that converge to them
+    fn get_targets_actual_and_target(&self, controller_states: &[&dyn crate::controllers::ControllerStateTrait], campaign_stat: &crate::simulationrun::CampaignStat) -> Vec<(String, f64, f64)>;
+    
+    /// Multiply the control variables converged between iterations (e.g., pacing) by a factor
+    /// Used by the convergence loop to perturb a converged campaign (see SimulationConverge::with_perturbation)
+    fn perturb_controller_states(&self, controller_states: &mut [Box<dyn crate::controllers::ControllerStateTrait>], factor: f64);
+    
+    /// Get (target index, bound) of each convergence target whose control variable is held at a hard bound
+    /// Only campaigns with per-target configured controllers have bounds
+    fn binding_bounds(&self, _controller_states: &[&dyn crate::controllers::ControllerStateTrait]) -> Vec<(usize, f64)> {
+        Vec::new()
+    }
+    
+    /// Get the penalty of the campaign's violated soft targets in the objective, in units of spend
+    /// (None = the campaign has no soft targets)
+    fn soft_target_penalty(&self, _campaign_stat: &crate::simulationrun::CampaignStat) -> Option<f64> {
+        None
+    }
+    
+    /// Get the maximum CPM bid of the campaign (None = no cap)
+    /// Bids returned by get_bid never exceed this value
+    fn max_bid_cpm(&self) -> Option<f64>;
+    
+    /// Whether the simulation should record the outcomes of this campaign's bids in CampaignStat::bid_history
+    fn wants_bid_history(&self) -> bool;
+    
+    /// Called after every auction, in order of impression timestamps, with what the campaign was charged (0.0 if it did not win),
+    /// weighted by Impression::weight
+    /// Lets campaigns adapt within a simulation run through interior mutability of their controller states
+    fn intra_run_update(&self, impression: &Impression, controller_states: &[&dyn crate::controllers::ControllerStateTrait], buyer_charge: f64);
+    
+    /// Whether intra_run_update changes the campaign's bids within a run
+    /// Bids of such campaigns are computed per impression right before its auction, instead of in batches with get_bids
+    fn updates_within_run(&self) -> bool {
+        false
+    }
+    
     /// Get a reference to the underlying Any type for downcasting
     fn as_any(&self) -> &dyn Any;
     
@@ -58,6 +115,32 @@ pub trait BidValuerTrait {
     
     /// Get a string representation of the valuer type
     fn get_valuer_type(&self) -> String;
+    
+    /// Multiplier of value_to_campaign × seller_control_factor when the bid is linear in them, None otherwise
+    /// Lets campaigns compute bids for a batch of impressions in a single loop
+    fn linear_multiplier(&self, _control_variables: &[f64]) -> Option<f64> {
+        None
+    }
+}
+
+/// Compute bids linear in the value, multiplier × value_to_campaign × seller_control_factor capped at max_bid_cpm
+/// Bids are computed BID_LANES at a time on fixed-size arrays, which the compiler turns into SIMD instructions
+/// (e.g. mulpd and minpd on x86-64), the impressions left over are computed one by one
+fn linear_bids(multiplier: f64, max_bid_cpm: f64, seller_control_factors: &[f64], values_to_campaign: &[f64], bids: &mut [Option<f64>]) {
+    let mut factor_chunks = seller_control_factors.chunks_exact(BID_LANES);
+    let mut value_chunks = values_to_campaign.chunks_exact(BID_LANES);
+    let mut bid_chunks = bids.chunks_exact_mut(BID_LANES);
+    for ((bids, values), factors) in (&mut bid_chunks).zip(&mut value_chunks).zip(&mut factor_chunks) {
+        let values: &[f64; BID_LANES] = values.try_into().unwrap();
+        let factors: &[f64; BID_LANES] = factors.try_into().unwrap();
+        let lanes: [f64; BID_LANES] = std::array::from_fn(|lane| (multiplier * values[lane] * factors[lane]).min(max_bid_cpm));
+        for (bid, lane) in bids.iter_mut().zip(lanes) {
+            *bid = Some(lane);
+        }
+    }
+    for ((bid, value_to_campaign), seller_control_factor) in bid_chunks.into_remainder().iter_mut().zip(value_chunks.remainder()).zip(factor_chunks.remainder()) {
+        *bid = Some((multiplier * value_to_campaign * seller_control_factor).min(max_bid_cpm));
+    }
 }
 
 /// While in theory one can write any kind of campaign, in practice it is possible to break it down to key elements
@@ -75,6 +158,28 @@ pub struct CampaignGeneral {
     pub converge_controllers: Vec<Box<dyn ControllerTrait>>,
     pub bid_valuer: Box<dyn BidValuerTrait>,
     pub bid_optimizer: Box<dyn BidOptimizerTrait>,
+    /// Optional CPM cap, enforced after the bid optimizer computed the bid
+    pub max_bid_cpm: Option<f64>,
+    /// Optional miscalibration of the win rate prediction as seen by the bid optimizer
+    pub win_rate_miscalibration: Option<WinRateMiscalibration>,
+    /// Whether the campaign only observes the competition through the wins and losses of its own bids
+    /// The provided win rate prediction is then replaced by one learned from the campaign's bid history across
+    /// iterations, held in an extra controller state after those of the converge controllers. Until the first
+    /// model is fitted the campaign bids without optimizing (see Campaigns::set_censored_competition)
+    pub censored_competition: bool,
+    /// Optional priority of each target (0 = highest), None converges all targets symmetrically
+    /// A target's controller only adjusts in iterations where the controllers of all higher priority
+    /// targets are settled (did not change), so lower priority targets converge within the slack left
+    /// by higher priority ones instead of fighting them (lexicographic convergence)
+    pub target_priorities: Option<Vec<usize>>,
+}
+
+impl CampaignGeneral {
+    /// Seed for miscalibration noise, derived from the impression and campaign
+    /// The same impression gets the same noise in every iteration, so convergence is not disturbed
+    fn miscalibration_noise_seed(&self, impression: &Impression) -> u64 {
+        impression.seed(6996 ^ (self.campaign_id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
 }
 
 impl CampaignTrait for CampaignGeneral {
@@ -96,16 +201,112 @@ impl CampaignTrait for CampaignGeneral {
         // Get initial bid from the bid valuer
         let initial_bid = self.bid_valuer.get_bid(value_to_campaign, impression, &control_variables[..self.converge_controllers.len()], &self.converge_targets, seller_control_factor, logger)?;
         
+        // With censored competition, the win rate prediction comes from the learned model
+        let learned_competition;
+        let impression_competition = if self.censored_competition {
+            let model = controller_states[self.converge_controllers.len()].as_any().downcast_ref::<ControllerStateCompetitionModel>().unwrap();
+            let (offset, scale) = match model.coefficients.as_ref().and_then(|coefficients| win_rate_logistic_sigmoid(coefficients, impression.base_impression_value)) {
+                Some(sigmoid) => sigmoid,
+                // Without a model yet, bid without optimizing to gather observations
+                None => return Some(self.max_bid_cpm.map_or(initial_bid, |max_bid_cpm| initial_bid.min(max_bid_cpm))),
+            };
+            learned_competition = match &impression.competition {
+                Some(competition) => ImpressionCompetition {
+                    win_rate_prediction_sigmoid_offset: offset,
+                    win_rate_prediction_sigmoid_scale: scale,
+                    ..competition.clone()
+                },
+                // The campaign doesn't know there is no competing bid, actual parameters mirror the prediction
+                None => ImpressionCompetition {
+                    bid_cpm: 0.0,
+                    win_rate_actual_sigmoid_offset: offset,
+                    win_rate_actual_sigmoid_scale: scale,
+                    win_rate_prediction_sigmoid_offset: offset,
+                    win_rate_prediction_sigmoid_scale: scale,
+                },
+            };
+            Some(&learned_competition)
+        } else {
+            impression.competition.as_ref()
+        };
+        
+        // Competition as seen by this campaign (miscalibrated on the stack, no allocation)
+        let miscalibrated_competition;
+        let competition = match (&self.win_rate_miscalibration, impression_competition) {
+            (Some(miscalibration), Some(impression_competition)) => {
+                miscalibrated_competition = miscalibration.apply(impression_competition, self.miscalibration_noise_seed(impression));
+                Some(&miscalibrated_competition)
+            }
+            _ => impression_competition,
+        };
+        
         // Optimize the bid using the optimizer
-        self.bid_optimizer.get_optimized_bid(initial_bid, impression)
+        let optimized_bid = self.bid_optimizer.get_optimized_bid(initial_bid, impression, competition)?;
+        
+        // Enforce the CPM cap
+        match self.max_bid_cpm {
+            Some(max_bid_cpm) => Some(optimized_bid.min(max_bid_cpm)),
+            None => Some(optimized_bid),
+        }
+    }
+    
+    fn get_bids(&self, impressions: &[Impression], controller_states: &[&dyn crate::controllers::ControllerStateTrait], seller_control_factors: &[f64], values_to_campaign: &[f64], bids: &mut [Option<f64>], logger: &mut crate::logger::Logger) {
+        let mut control_variables = [0.0; MAX_CONTROLLERS];
+        for (i, (converge_controller, controller_state)) in self.converge_controllers.iter().zip(controller_states.iter()).enumerate() {
+            control_variables[i] = converge_controller.get_control_variable(*controller_state);
+        }
+        
+        // Truthful bids of linear valuers are a product of per-impression inputs, computed in vectorized lanes
+        // without per-impression dispatch to the valuer and optimizer
+        let linear_multiplier = if self.bid_optimizer.is_truthful() {
+            self.bid_valuer.linear_multiplier(&control_variables[..self.converge_controllers.len()])
+        } else {
+            None
+        };
+        match linear_multiplier {
+            Some(multiplier) => linear_bids(multiplier, self.max_bid_cpm.unwrap_or(f64::INFINITY), seller_control_factors, values_to_campaign, bids),
+            None => {
+                for (index, impression) in impressions.iter().enumerate() {
+                    bids[index] = self.get_bid(impression, controller_states, seller_control_factors[index], values_to_campaign[index], logger);
+                }
+            }
+        }
     }
     
     fn next_controller_state(&self, previous_states: &[Box<dyn crate::controllers::ControllerStateTrait>], next_states: &mut [Box<dyn crate::controllers::ControllerStateTrait>], campaign_stat: &crate::simulationrun::CampaignStat) -> bool {
+        let priorities = match &self.target_priorities {
+            Some(priorities) => priorities.clone(),
+            None => vec![0; self.converge_targets.len()],
+        };
+        
+        // Targets are converged level by level, from the highest priority (lowest number) down
+        let mut levels = priorities.clone();
+        levels.sort_unstable();
+        levels.dedup();
+        
         let mut any_changed = false;
-        for (index, (converge_target, converge_controller)) in self.converge_targets.iter().zip(self.converge_controllers.iter()).enumerate() {
-            let (actual, target) = converge_target.get_actual_and_target(campaign_stat);
-            let changed = converge_controller.next_controller_state(previous_states[index].as_ref(), next_states[index].as_mut(), actual, target);
-            any_changed = any_changed || changed;
+        let mut higher_priority_changed = false;
+        for level in levels {
+            let mut level_changed = false;
+            for index in (0..self.converge_targets.len()).filter(|index| priorities[*index] == level) {
+                if higher_priority_changed {
+                    // Hold the control variable until all higher priority targets are settled
+                    next_states[index] = previous_states[index].clone_box();
+                    continue;
+                }
+                let (actual, target) = self.converge_targets[index].get_actual_and_target(campaign_stat);
+                let changed = self.converge_controllers[index].next_controller_state(previous_states[index].as_ref(), next_states[index].as_mut(), actual, target);
+                level_changed = level_changed || changed;
+            }
+            higher_priority_changed = higher_priority_changed || level_changed;
+            any_changed = any_changed || level_changed;
+        }
+        
+        if self.censored_competition {
+            let model_index = self.converge_targets.len();
+            let previous_model = previous_states[model_index].as_any().downcast_ref::<ControllerStateCompetitionModel>().unwrap();
+            let next_model = next_states[model_index].as_any_mut().downcast_mut::<ControllerStateCompetitionModel>().unwrap();
+            any_changed |= learn_competition_model(previous_model, next_model, &campaign_stat.bid_history);
         }
         any_changed
     }
@@ -113,17 +314,85 @@ impl CampaignTrait for CampaignGeneral {
     fn type_target_and_controller_state_string(&self, controller_states: &[&dyn crate::controllers::ControllerStateTrait]) -> String {
         let mut parts = Vec::new();
         for (index, (converge_target, converge_controller)) in self.converge_targets.iter().zip(self.converge_controllers.iter()).enumerate() {
-            parts.push(format!("T{}: {} ({})", 
+            let priority_string = match &self.target_priorities {
+                Some(priorities) => format!(" [priority {}]", priorities[index]),
+                None => String::new(),
+            };
+            parts.push(format!("T{}{}: {} ({})", 
                 index + 1,
+                priority_string,
                 converge_target.converge_target_string(),
                 converge_controller.controller_string(controller_states[index])
             ));
         }
-        format!("{} / {} ({})", self.bid_optimizer.get_optimizer_type(), self.bid_valuer.get_valuer_type(), parts.join(", "))
+        let max_bid_string = match self.max_bid_cpm {
+            Some(max_bid_cpm) => format!(", max bid {:.2} CPM", max_bid_cpm),
+            None => String::new(),
+        };
+        let miscalibration_string = match &self.win_rate_miscalibration {
+            Some(miscalibration) => format!(", miscalibrated win rate (offset x{:.2}, scale x{:.2})", miscalibration.offset_bias, miscalibration.scale_bias),
+            None => String::new(),
+        };
+        let censored_string = if self.censored_competition {
+            let model = controller_states[self.converge_controllers.len()].as_any().downcast_ref::<ControllerStateCompetitionModel>().unwrap();
+            match model.coefficients {
+                Some(_) => format!(", censored competition (learned win rate, {} observations)", model.observations.len()),
+                None => format!(", censored competition (no learned win rate yet, {} observations)", model.observations.len()),
+            }
+        } else {
+            String::new()
+        };
+        format!("{} / {} ({}){}{}{}", self.bid_optimizer.get_optimizer_type(), self.bid_valuer.get_valuer_type(), parts.join(", "), max_bid_string, miscalibration_string, censored_string)
+    }
+    
+    fn get_control_variables(&self, controller_states: &[&dyn crate::controllers::ControllerStateTrait]) -> Vec<f64> {
+        self.converge_controllers.iter()
+            .zip(controller_states.iter())
+            .map(|(converge_controller, controller_state)| converge_controller.get_control_variable(*controller_state))
+            .collect()
+    }
+    
+    fn get_targets_actual_and_target(&self, _controller_states: &[&dyn crate::controllers::ControllerStateTrait], campaign_stat: &crate::simulationrun::CampaignStat) -> Vec<(String, f64, f64)> {
+        self.converge_targets.iter()
+            .map(|converge_target| {
+                let (actual, target) = converge_target.get_actual_and_target(campaign_stat);
+                (converge_target.converge_target_string(), actual, target)
+            })
+            .collect()
+    }
+    
+    fn perturb_controller_states(&self, controller_states: &mut [Box<dyn crate::controllers::ControllerStateTrait>], factor: f64) {
+        for (converge_controller, controller_state) in self.converge_controllers.iter().zip(controller_states.iter_mut()) {
+            let control_variable = converge_controller.get_control_variable(controller_state.as_ref());
+            converge_controller.set_control_variable(controller_state.as_mut(), control_variable * factor);
+        }
+    }
+    
+    fn binding_bounds(&self, controller_states: &[&dyn crate::controllers::ControllerStateTrait]) -> Vec<(usize, f64)> {
+        self.converge_controllers.iter()
+            .zip(controller_states.iter())
+            .enumerate()
+            .filter_map(|(index, (converge_controller, controller_state))| converge_controller.binding_bound(*controller_state).map(|bound| (index, bound)))
+            .collect()
+    }
+    
+    fn max_bid_cpm(&self) -> Option<f64> {
+        self.max_bid_cpm
+    }
+    
+    fn wants_bid_history(&self) -> bool {
+        self.censored_competition
+    }
+    
+    fn intra_run_update(&self, _impression: &Impression, _controller_states: &[&dyn crate::controllers::ControllerStateTrait], _buyer_charge: f64) {
     }
     
     fn create_controller_state(&self) -> Vec<Box<dyn crate::controllers::ControllerStateTrait>> {
-        self.converge_controllers.iter().map(|c| c.create_controller_state()).collect()
+        let mut controller_states: Vec<Box<dyn crate::controllers::ControllerStateTrait>> = self.converge_controllers.iter().map(|c| c.create_controller_state()).collect();
+        if self.censored_competition {
+            controller_states.push(Box::new(ControllerStateCompetitionModel { coefficients: None, observations: Vec::new() }));
+        }
+        controller_states
     }
     
     fn as_any(&self) -> &dyn Any {
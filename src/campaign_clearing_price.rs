@@ -0,0 +1,153 @@
+//! Campaign that bids slightly above the average clearing price it observed on each seller
+//!
+//! A common naive strategy: the campaign keeps an exponentially weighted moving average of the
+//! clearing prices (minimum winning CPM) reported to it after its bids, separately per seller, and
+//! bids a markup above that average. Bids never exceed the paced value, and pacing converges the
+//! campaign to its target. The bid ignores how the clearing price varies between impressions of
+//! the same seller, which is what the comparison against max margin bidding shows.
+//!
+//! Averages are updated between convergence iterations from the bid history, in order of the bids.
+//! Until a seller has been observed, the campaign bids the paced value there.
+
+use crate::impressions::Impression;
+use crate::campaign::CampaignTrait;
+use crate::campaign_targets::CampaignTargetTrait;
+use crate::controllers::{ControllerTrait, ControllerProportionalDerivative, ControllerStateTrait, ControllerStateClearingPrice};
+use crate::simulationrun::CampaignStat;
+use std::any::Any;
+
+/// Weight of each new observation in the moving average of clearing prices
+const AVERAGE_SMOOTHING: f64 = 0.01;
+/// Relative change of an average clearing price above which the campaign is considered changed
+const AVERAGE_CHANGE_TOLERANCE: f64 = 0.01;
+
+pub struct CampaignClearingPrice {
+    pub campaign_id: usize,
+    pub campaign_name: String,
+    pub converge_target: Box<dyn CampaignTargetTrait>,
+    /// Controller for the pacing
+    pub converge_controller: Box<dyn ControllerTrait>,
+    /// Bid is the average clearing price times (1 + markup)
+    pub markup: f64,
+}
+
+impl CampaignClearingPrice {
+    /// Create a new campaign bidding above observed clearing prices
+    ///
+    /// # Arguments
+    /// * `campaign_name` - Name of the campaign
+    /// * `converge_target` - Target the pacing converges to
+    /// * `markup` - Fraction by which bids exceed the average clearing price (e.g. 0.05)
+    pub fn new(campaign_name: String, converge_target: Box<dyn CampaignTargetTrait>, markup: f64) -> Self {
+        Self {
+            campaign_id: 0,  // Set by Campaigns::add_advanced
+            campaign_name,
+            converge_target,
+            converge_controller: Box::new(ControllerProportionalDerivative::new()),
+            markup,
+        }
+    }
+}
+
+impl CampaignTrait for CampaignClearingPrice {
+    fn campaign_id(&self) -> usize {
+        self.campaign_id
+    }
+
+    fn campaign_name(&self) -> &str {
+        &self.campaign_name
+    }
+
+    fn get_bid(&self, impression: &Impression, controller_states: &[&dyn ControllerStateTrait], seller_control_factor: f64, value_to_campaign: f64, _logger: &mut crate::logger::Logger) -> Option<f64> {
+        let pacing = self.converge_controller.get_control_variable(controller_states[0]);
+        let clearing_price = controller_states[1].as_any().downcast_ref::<ControllerStateClearingPrice>().unwrap();
+
+        let paced_value = pacing * value_to_campaign * seller_control_factor;
+        match clearing_price.average_clearing_price.get(impression.seller_id).copied().flatten() {
+            Some(average_clearing_price) => Some((average_clearing_price * (1.0 + self.markup)).min(paced_value)),
+            None => Some(paced_value),
+        }
+    }
+
+    fn create_controller_state(&self) -> Vec<Box<dyn ControllerStateTrait>> {
+        vec![
+            self.converge_controller.create_controller_state(),
+            Box::new(ControllerStateClearingPrice { average_clearing_price: Vec::new() }),
+        ]
+    }
+
+    fn next_controller_state(&self, previous_states: &[Box<dyn ControllerStateTrait>], next_states: &mut [Box<dyn ControllerStateTrait>], campaign_stat: &CampaignStat) -> bool {
+        let (actual, target) = self.converge_target.get_actual_and_target(campaign_stat);
+        let mut any_changed = self.converge_controller.next_controller_state(previous_states[0].as_ref(), next_states[0].as_mut(), actual, target);
+
+        let previous = previous_states[1].as_any().downcast_ref::<ControllerStateClearingPrice>().unwrap();
+        let next = next_states[1].as_any_mut().downcast_mut::<ControllerStateClearingPrice>().unwrap();
+
+        next.average_clearing_price = previous.average_clearing_price.clone();
+        for observation in &campaign_stat.bid_history {
+            if next.average_clearing_price.len() <= observation.seller_id {
+                next.average_clearing_price.resize(observation.seller_id + 1, None);
+            }
+            let average = &mut next.average_clearing_price[observation.seller_id];
+            *average = Some(match *average {
+                Some(average) => average + AVERAGE_SMOOTHING * (observation.clearing_price_cpm - average),
+                None => observation.clearing_price_cpm,
+            });
+        }
+
+        for (seller_id, average) in next.average_clearing_price.iter().enumerate() {
+            let previous_average = previous.average_clearing_price.get(seller_id).copied().flatten();
+            any_changed |= match (previous_average, *average) {
+                (Some(previous_average), Some(average)) => (average - previous_average).abs() > AVERAGE_CHANGE_TOLERANCE * previous_average.abs().max(1e-6),
+                (None, Some(_)) => true,
+                _ => false,
+            };
+        }
+        any_changed
+    }
+
+    fn type_target_and_controller_state_string(&self, controller_states: &[&dyn ControllerStateTrait]) -> String {
+        let clearing_price = controller_states[1].as_any().downcast_ref::<ControllerStateClearingPrice>().unwrap();
+        let averages: Vec<String> = clearing_price.average_clearing_price.iter()
+            .map(|average| average.map_or("-".to_string(), |average| format!("{:.4}", average)))
+            .collect();
+        format!("ClearingPrice / Clearing price feedback (T1: {} ({}), markup {:.2}, average clearing price per seller [{}])",
+            self.converge_target.converge_target_string(),
+            self.converge_controller.controller_string(controller_states[0]),
+            self.markup,
+            averages.join(", "))
+    }
+
+    fn get_control_variables(&self, controller_states: &[&dyn ControllerStateTrait]) -> Vec<f64> {
+        vec![self.converge_controller.get_control_variable(controller_states[0])]
+    }
+
+    fn get_targets_actual_and_target(&self, _controller_states: &[&dyn ControllerStateTrait], campaign_stat: &CampaignStat) -> Vec<(String, f64, f64)> {
+        let (actual, target) = self.converge_target.get_actual_and_target(campaign_stat);
+        vec![(self.converge_target.converge_target_string(), actual, target)]
+    }
+
+    fn perturb_controller_states(&self, controller_states: &mut [Box<dyn ControllerStateTrait>], factor: f64) {
+        let control_variable = self.converge_controller.get_control_variable(controller_states[0].as_ref());
+        self.converge_controller.set_control_variable(controller_states[0].as_mut(), control_variable * factor);
+    }
+
+    fn max_bid_cpm(&self) -> Option<f64> {
+        None
+    }
+
+    fn wants_bid_history(&self) -> bool {
+        true
+    }
+
+    fn intra_run_update(&self, _impression: &Impression, _controller_states: &[&dyn ControllerStateTrait], _buyer_charge: f64) {
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
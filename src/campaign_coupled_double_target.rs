@@ -0,0 +1,114 @@
+//! Max margin campaign converging to two targets with a jointly-coupled controller
+//!
+//! Bids like MAX_MARGIN_DOUBLE_TARGET (BidValuerDualTarget with max margin optimization), but instead of
+//! one independent controller per target, a ControllerCoupled updates both control variables (lambda
+//! and mu) from the errors of both targets, so the controllers don't fight each other.
+
+use crate::impressions::Impression;
+use crate::campaign::{CampaignTrait, BidValuerTrait};
+use crate::campaign_targets::CampaignTargetTrait;
+use crate::bid_valuers_double::BidValuerDualTarget;
+use crate::bid_optimizers::{BidOptimizerTrait, BidOptimizerMaximumMargin};
+use crate::controllers::{ControllerCoupled, ControllerStateTrait, ControllerStateMultiVariable};
+use crate::simulationrun::CampaignStat;
+use std::any::Any;
+
+pub struct CampaignCoupledDoubleTarget {
+    pub campaign_id: usize,
+    pub campaign_name: String,
+    /// Primary target (converged mainly by lambda) and secondary target (converged mainly by mu)
+    pub converge_targets: Vec<Box<dyn CampaignTargetTrait>>,
+    pub converge_controller: ControllerCoupled,
+}
+
+impl CampaignCoupledDoubleTarget {
+    /// Create a new max margin campaign with two targets and a coupled controller
+    ///
+    /// # Arguments
+    /// * `campaign_name` - Name of the campaign
+    /// * `primary_target` - First target, e.g. total impressions
+    /// * `secondary_target` - Second target, e.g. average value (its target value is used by the bid valuer)
+    /// * `gain_matrix` - Gains of the coupled controller (see ControllerCoupled)
+    pub fn new(campaign_name: String, primary_target: Box<dyn CampaignTargetTrait>, secondary_target: Box<dyn CampaignTargetTrait>, gain_matrix: [[f64; 2]; 2]) -> Self {
+        Self {
+            campaign_id: 0,  // Set by Campaigns::add_advanced
+            campaign_name,
+            converge_targets: vec![primary_target, secondary_target],
+            converge_controller: ControllerCoupled::new(gain_matrix),
+        }
+    }
+}
+
+impl CampaignTrait for CampaignCoupledDoubleTarget {
+    fn campaign_id(&self) -> usize {
+        self.campaign_id
+    }
+
+    fn campaign_name(&self) -> &str {
+        &self.campaign_name
+    }
+
+    fn get_bid(&self, impression: &Impression, controller_states: &[&dyn ControllerStateTrait], seller_control_factor: f64, value_to_campaign: f64, logger: &mut crate::logger::Logger) -> Option<f64> {
+        let control_variables = self.converge_controller.get_control_variables(controller_states[0]);
+        let initial_bid = BidValuerDualTarget.get_bid(value_to_campaign, impression, &control_variables, &self.converge_targets, seller_control_factor, logger)?;
+        BidOptimizerMaximumMargin.get_optimized_bid(initial_bid, impression, impression.competition.as_ref())
+    }
+
+    fn create_controller_state(&self) -> Vec<Box<dyn ControllerStateTrait>> {
+        vec![self.converge_controller.create_controller_state()]
+    }
+
+    fn next_controller_state(&self, previous_states: &[Box<dyn ControllerStateTrait>], next_states: &mut [Box<dyn ControllerStateTrait>], campaign_stat: &CampaignStat) -> bool {
+        let (actual_primary, target_primary) = self.converge_targets[0].get_actual_and_target(campaign_stat);
+        let (actual_secondary, target_secondary) = self.converge_targets[1].get_actual_and_target(campaign_stat);
+        self.converge_controller.next_controller_state(previous_states[0].as_ref(), next_states[0].as_mut(), [actual_primary, actual_secondary], [target_primary, target_secondary])
+    }
+
+    fn type_target_and_controller_state_string(&self, controller_states: &[&dyn ControllerStateTrait]) -> String {
+        format!("{} / {} (T1: {}, T2: {} ({}))",
+            BidValuerDualTarget.get_valuer_type(),
+            BidOptimizerMaximumMargin.get_optimizer_type(),
+            self.converge_targets[0].converge_target_string(),
+            self.converge_targets[1].converge_target_string(),
+            self.converge_controller.controller_string(controller_states[0]))
+    }
+
+    fn get_control_variables(&self, controller_states: &[&dyn ControllerStateTrait]) -> Vec<f64> {
+        self.converge_controller.get_control_variables(controller_states[0]).to_vec()
+    }
+
+    fn get_targets_actual_and_target(&self, _controller_states: &[&dyn ControllerStateTrait], campaign_stat: &CampaignStat) -> Vec<(String, f64, f64)> {
+        self.converge_targets.iter()
+            .map(|converge_target| {
+                let (actual, target) = converge_target.get_actual_and_target(campaign_stat);
+                (converge_target.converge_target_string(), actual, target)
+            })
+            .collect()
+    }
+
+    fn perturb_controller_states(&self, controller_states: &mut [Box<dyn ControllerStateTrait>], factor: f64) {
+        let state = controller_states[0].as_any_mut().downcast_mut::<ControllerStateMultiVariable>().unwrap();
+        for variable in state.variables.iter_mut() {
+            *variable *= factor;
+        }
+    }
+
+    fn max_bid_cpm(&self) -> Option<f64> {
+        None
+    }
+
+    fn wants_bid_history(&self) -> bool {
+        false
+    }
+
+    fn intra_run_update(&self, _impression: &Impression, _controller_states: &[&dyn ControllerStateTrait], _buyer_charge: f64) {
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,134 @@
+//! Campaign with a daily budget, persisting across the days of a multi-day simulation
+//!
+//! Impressions of a multi-day simulation are spread over several days, with different traffic on
+//! each day. A total budget lets spend follow the traffic, so busy days take most of the budget.
+//! This campaign instead spends the same budget on every day.
+//!
+//! Each day has its own pacing with its own PD controller state. An impression is bid on with the
+//! pacing of its day, and between convergence iterations each day's pacing is converged on that
+//! day's spend (from CampaignStat::day_breakdown). The campaign itself persists across days.
+
+use crate::impressions::Impression;
+use crate::campaign::CampaignTrait;
+use crate::controllers::{ControllerTrait, ControllerProportionalDerivative, ControllerStateTrait, ControllerConfig};
+use crate::bid_optimizers::BidOptimizerTrait;
+use crate::simulationrun::CampaignStat;
+use std::any::Any;
+
+pub struct CampaignDailyBudget {
+    pub campaign_id: usize,
+    pub campaign_name: String,
+    /// Budget to be spent on each day
+    pub daily_budget: f64,
+    /// Number of days of the simulation, each with its own pacing
+    pub num_days: usize,
+    /// Controller for the pacing of each day
+    pub converge_controller: Box<dyn ControllerTrait>,
+    pub bid_optimizer: Box<dyn BidOptimizerTrait>,
+}
+
+impl CampaignDailyBudget {
+    /// Create a new daily budget campaign
+    ///
+    /// # Arguments
+    /// * `campaign_name` - Name of the campaign
+    /// * `daily_budget` - Budget to spend on each day
+    /// * `num_days` - Number of days of the simulation (must match ImpressionsParam::with_days)
+    /// * `bid_optimizer` - Optimizer applied to the paced value
+    /// * `controller_config` - Parameters of the per-day pacing controller
+    pub fn new(campaign_name: String, daily_budget: f64, num_days: usize, bid_optimizer: Box<dyn BidOptimizerTrait>, controller_config: &ControllerConfig) -> Self {
+        assert!(num_days > 0, "Daily budget campaign needs at least one day");
+        Self {
+            campaign_id: 0,  // Set by Campaigns::add_advanced
+            campaign_name,
+            daily_budget,
+            num_days,
+            converge_controller: Box::new(ControllerProportionalDerivative::new().with_config(controller_config)),
+            bid_optimizer,
+        }
+    }
+}
+
+impl CampaignTrait for CampaignDailyBudget {
+    fn campaign_id(&self) -> usize {
+        self.campaign_id
+    }
+
+    fn campaign_name(&self) -> &str {
+        &self.campaign_name
+    }
+
+    fn get_bid(&self, impression: &Impression, controller_states: &[&dyn ControllerStateTrait], seller_control_factor: f64, value_to_campaign: f64, _logger: &mut crate::logger::Logger) -> Option<f64> {
+        let pacing = self.converge_controller.get_control_variable(controller_states[impression.day()]);
+        let paced_value = pacing * value_to_campaign * seller_control_factor;
+        self.bid_optimizer.get_optimized_bid(paced_value, impression, impression.competition.as_ref())
+    }
+
+    fn create_controller_state(&self) -> Vec<Box<dyn ControllerStateTrait>> {
+        (0..self.num_days).map(|_| self.converge_controller.create_controller_state()).collect()
+    }
+
+    fn next_controller_state(&self, previous_states: &[Box<dyn ControllerStateTrait>], next_states: &mut [Box<dyn ControllerStateTrait>], campaign_stat: &CampaignStat) -> bool {
+        assert_eq!(campaign_stat.day_breakdown.len(), self.num_days, "Campaign '{}' has a daily budget for {} days, but the simulation has {} days", self.campaign_name, self.num_days, campaign_stat.day_breakdown.len());
+        let mut all_converged = true;
+        for (day, day_stat) in campaign_stat.day_breakdown.iter().enumerate() {
+            let converged = self.converge_controller.next_controller_state(previous_states[day].as_ref(), next_states[day].as_mut(), day_stat.total_buyer_charge, self.daily_budget);
+            all_converged = all_converged && converged;
+        }
+        all_converged
+    }
+
+    fn type_target_and_controller_state_string(&self, controller_states: &[&dyn ControllerStateTrait]) -> String {
+        let day_controllers: Vec<String> = controller_states.iter()
+            .map(|controller_state| self.converge_controller.controller_string(*controller_state))
+            .collect();
+        format!("{} / Daily budget (Budget: {:.2} on each of {} days, {})",
+            self.bid_optimizer.get_optimizer_type(),
+            self.daily_budget,
+            self.num_days,
+            day_controllers.join(", "))
+    }
+
+    fn get_control_variables(&self, controller_states: &[&dyn ControllerStateTrait]) -> Vec<f64> {
+        controller_states.iter().map(|controller_state| self.converge_controller.get_control_variable(*controller_state)).collect()
+    }
+
+    fn get_targets_actual_and_target(&self, _controller_states: &[&dyn ControllerStateTrait], campaign_stat: &CampaignStat) -> Vec<(String, f64, f64)> {
+        campaign_stat.day_breakdown.iter().enumerate()
+            .map(|(day, day_stat)| (format!("Day {} budget: {:.2}", day, self.daily_budget), day_stat.total_buyer_charge, self.daily_budget))
+            .collect()
+    }
+
+    fn perturb_controller_states(&self, controller_states: &mut [Box<dyn ControllerStateTrait>], factor: f64) {
+        for controller_state in controller_states.iter_mut() {
+            let control_variable = self.converge_controller.get_control_variable(controller_state.as_ref());
+            self.converge_controller.set_control_variable(controller_state.as_mut(), control_variable * factor);
+        }
+    }
+
+    fn binding_bounds(&self, controller_states: &[&dyn ControllerStateTrait]) -> Vec<(usize, f64)> {
+        controller_states.iter()
+            .enumerate()
+            .filter_map(|(day, controller_state)| self.converge_controller.binding_bound(*controller_state).map(|bound| (day, bound)))
+            .collect()
+    }
+
+    fn max_bid_cpm(&self) -> Option<f64> {
+        None
+    }
+
+    fn wants_bid_history(&self) -> bool {
+        false
+    }
+
+    fn intra_run_update(&self, _impression: &Impression, _controller_states: &[&dyn ControllerStateTrait], _buyer_charge: f64) {
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
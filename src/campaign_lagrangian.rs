@@ -0,0 +1,315 @@
+//! Max margin campaign with multiple constraints handled by dual decomposition (Lagrangian relaxation)
+//!
+//! The campaign maximizes margin (value − cost) subject to any combination of constraints:
+//! a maximum budget, a minimum number of impressions and a minimum ROAS (value / spend).
+//! Each constraint gets a dual variable (Lagrange multiplier) from a ControllerDual. Relaxing the
+//! constraints into the objective, an impression with value v bought at cost c contributes
+//!
+//!   (1 + μ_roas) × v + μ_impressions − (1 + μ_budget + μ_roas × roas) × c
+//!
+//! so the campaign bids max margin on the effective value
+//! (v × (1 + μ_roas) + μ_impressions) / (1 + μ_budget + μ_roas × roas).
+//! With all duals at zero this is plain max margin bidding on the value. Between iterations the duals
+//! are updated by subgradient steps on the constraint violations, so only binding constraints
+//! end up with non-zero duals.
+//!
+//! A soft constraint is one the campaign prefers but doesn't require to meet. Its violation is
+//! penalized in the objective by a PenaltyFunction, which bounds the price the campaign pays to meet
+//! it: the dual can't exceed the marginal penalty, so the constraint is violated once meeting it
+//! costs more than the penalty of missing it.
+
+use crate::impressions::Impression;
+use crate::campaign::CampaignTrait;
+use crate::bid_optimizers::{BidOptimizerTrait, BidOptimizerMaximumMargin};
+use crate::controllers::{ControllerDual, ControllerStateTrait, ControllerStateDual};
+use crate::simulationrun::CampaignStat;
+use std::any::Any;
+
+/// Penalty of violating a soft constraint, as a function of the violation in the units priced by the
+/// constraint's dual: overspend for budget, thousands of missed impressions, missing value for ROAS
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PenaltyFunction {
+    /// weight × violation: the dual is bounded by weight, so the campaign stops meeting the constraint
+    /// where it would pay more than weight per unit of violation
+    LINEAR { weight: f64 },
+    /// weight × violation² / 2: the dual settles at the marginal penalty weight × violation, so the
+    /// campaign trades a small violation against a smaller price
+    QUADRATIC { weight: f64 },
+}
+
+impl PenaltyFunction {
+    /// Penalty of a violation (no penalty when the constraint is met)
+    pub fn penalty(&self, violation: f64) -> f64 {
+        let violation = violation.max(0.0);
+        match self {
+            PenaltyFunction::LINEAR { weight } => weight * violation,
+            PenaltyFunction::QUADRATIC { weight } => weight * violation * violation / 2.0,
+        }
+    }
+
+    fn weight(&self) -> f64 {
+        match self {
+            PenaltyFunction::LINEAR { weight } | PenaltyFunction::QUADRATIC { weight } => *weight,
+        }
+    }
+
+    fn penalty_string(&self) -> String {
+        match self {
+            PenaltyFunction::LINEAR { weight } => format!("linear penalty {:.2}", weight),
+            PenaltyFunction::QUADRATIC { weight } => format!("quadratic penalty {:.2}", weight),
+        }
+    }
+}
+
+/// Constraint of a Lagrangian campaign
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LagrangianConstraint {
+    /// Spend at most the budget
+    MAX_BUDGET { budget: f64 },
+    /// Obtain at least this many impressions
+    MIN_IMPRESSIONS { impressions: i32 },
+    /// Obtain at least this much value per unit of spend (value is in CPM, so ROAS = total_value / 1000 / spend)
+    MIN_ROAS { roas: f64 },
+    /// Preferred but not required constraint, whose violation is penalized in the objective
+    SOFT { constraint: Box<LagrangianConstraint>, penalty: PenaltyFunction },
+}
+
+impl LagrangianConstraint {
+    /// The constraint itself, without softening
+    fn hard_constraint(&self) -> &LagrangianConstraint {
+        match self {
+            LagrangianConstraint::SOFT { constraint, .. } => constraint,
+            _ => self,
+        }
+    }
+
+    /// Violation in the units priced by the constraint's dual: positive when violated, negative when there is slack
+    fn violation(&self, campaign_stat: &CampaignStat) -> f64 {
+        match self {
+            LagrangianConstraint::MAX_BUDGET { budget } => campaign_stat.total_buyer_charge - budget,
+            // Dual is in CPM, so impressions are priced per thousand
+            LagrangianConstraint::MIN_IMPRESSIONS { impressions } => (*impressions as f64 - campaign_stat.impressions_obtained) / 1000.0,
+            LagrangianConstraint::MIN_ROAS { roas } => roas * campaign_stat.total_buyer_charge - campaign_stat.total_value / 1000.0,
+            LagrangianConstraint::SOFT { constraint, .. } => constraint.violation(campaign_stat),
+        }
+    }
+
+    /// Scale normalizing the violation (a violation of the whole scale is a subgradient of 1.0)
+    fn scale(&self, campaign_stat: &CampaignStat) -> f64 {
+        match self {
+            LagrangianConstraint::MAX_BUDGET { budget } => *budget,
+            LagrangianConstraint::MIN_IMPRESSIONS { impressions } => *impressions as f64 / 1000.0,
+            LagrangianConstraint::MIN_ROAS { roas } => (roas * campaign_stat.total_buyer_charge).max(campaign_stat.total_value / 1000.0),
+            LagrangianConstraint::SOFT { constraint, .. } => constraint.scale(campaign_stat),
+        }
+    }
+
+    /// Normalized subgradient of the constraint's dual: positive when the dual should grow, negative when it should shrink
+    ///
+    /// For hard (and linearly penalized) constraints this is the normalized violation. A quadratic penalty
+    /// prices the violation at its marginal penalty, so the dual is pulled towards weight × violation.
+    fn subgradient(&self, campaign_stat: &CampaignStat, dual: f64) -> f64 {
+        let scale = self.scale(campaign_stat);
+        if scale <= 0.0 {
+            return 0.0;
+        }
+        match self {
+            LagrangianConstraint::SOFT { penalty: PenaltyFunction::QUADRATIC { weight }, .. } => {
+                (weight * self.violation(campaign_stat).max(0.0) - dual) / (weight * scale)
+            }
+            _ => self.violation(campaign_stat) / scale,
+        }
+    }
+
+    /// Penalty of the constraint's violation in the objective (zero for hard constraints, which are met)
+    fn penalty(&self, campaign_stat: &CampaignStat) -> f64 {
+        match self {
+            LagrangianConstraint::SOFT { penalty, .. } => penalty.penalty(self.violation(campaign_stat)),
+            _ => 0.0,
+        }
+    }
+
+    /// Upper bound of the constraint's dual: the penalty weight for linearly penalized constraints
+    fn max_dual(&self) -> f64 {
+        match self {
+            LagrangianConstraint::SOFT { penalty: PenaltyFunction::LINEAR { weight }, .. } => *weight,
+            _ => f64::INFINITY,
+        }
+    }
+
+    /// Actual value and bound of the constraint
+    fn actual_and_target(&self, campaign_stat: &CampaignStat) -> (f64, f64) {
+        match self {
+            LagrangianConstraint::MAX_BUDGET { budget } => (campaign_stat.total_buyer_charge, *budget),
+            LagrangianConstraint::MIN_IMPRESSIONS { impressions } => (campaign_stat.impressions_obtained, *impressions as f64),
+            LagrangianConstraint::MIN_ROAS { roas } => {
+                let actual_roas = if campaign_stat.total_buyer_charge > 0.0 { campaign_stat.total_value / 1000.0 / campaign_stat.total_buyer_charge } else { 0.0 };
+                (actual_roas, *roas)
+            }
+            LagrangianConstraint::SOFT { constraint, .. } => constraint.actual_and_target(campaign_stat),
+        }
+    }
+
+    /// Initial subgradient step size of the constraint's dual
+    fn initial_step_size(&self) -> f64 {
+        match self {
+            LagrangianConstraint::MAX_BUDGET { .. } => 1.0,
+            // Dual is in CPM, added to the value of every impression
+            LagrangianConstraint::MIN_IMPRESSIONS { .. } => 5.0,
+            LagrangianConstraint::MIN_ROAS { .. } => 1.0,
+            LagrangianConstraint::SOFT { constraint, .. } => constraint.initial_step_size(),
+        }
+    }
+
+    fn constraint_string(&self) -> String {
+        match self {
+            LagrangianConstraint::MAX_BUDGET { budget } => format!("budget <= {:.2}", budget),
+            LagrangianConstraint::MIN_IMPRESSIONS { impressions } => format!("impressions >= {}", impressions),
+            LagrangianConstraint::MIN_ROAS { roas } => format!("ROAS >= {:.2}", roas),
+            LagrangianConstraint::SOFT { constraint, penalty } => format!("soft {} ({})", constraint.constraint_string(), penalty.penalty_string()),
+        }
+    }
+}
+
+pub struct CampaignLagrangian {
+    pub campaign_id: usize,
+    pub campaign_name: String,
+    pub constraints: Vec<LagrangianConstraint>,
+    pub converge_controller: ControllerDual,
+}
+
+impl CampaignLagrangian {
+    /// Create a new max margin campaign with the given constraints
+    ///
+    /// # Arguments
+    /// * `campaign_name` - Name of the campaign
+    /// * `constraints` - Constraints of the campaign, each at most once, hard or SOFT
+    pub fn new(campaign_name: String, constraints: Vec<LagrangianConstraint>) -> Self {
+        for constraint in &constraints {
+            if let LagrangianConstraint::SOFT { constraint: soft_constraint, penalty } = constraint {
+                assert!(!matches!(**soft_constraint, LagrangianConstraint::SOFT { .. }), "Soft constraint can't soften another soft constraint");
+                assert!(penalty.weight() > 0.0, "Penalty weight of a soft constraint must be positive, got {}", penalty.weight());
+            }
+        }
+        let initial_step_sizes = constraints.iter().map(|constraint| constraint.initial_step_size()).collect();
+        let max_duals = constraints.iter().map(|constraint| constraint.max_dual()).collect();
+        Self {
+            campaign_id: 0,  // Set by Campaigns::add_advanced
+            campaign_name,
+            constraints,
+            converge_controller: ControllerDual::new(initial_step_sizes).with_max_duals(max_duals),
+        }
+    }
+}
+
+impl CampaignTrait for CampaignLagrangian {
+    fn campaign_id(&self) -> usize {
+        self.campaign_id
+    }
+
+    fn campaign_name(&self) -> &str {
+        &self.campaign_name
+    }
+
+    fn get_bid(&self, impression: &Impression, controller_states: &[&dyn ControllerStateTrait], seller_control_factor: f64, value_to_campaign: f64, _logger: &mut crate::logger::Logger) -> Option<f64> {
+        let duals = self.converge_controller.get_duals(controller_states[0]);
+        let mut value_weight = 1.0;
+        let mut value_offset = 0.0;
+        let mut cost_weight = 1.0;
+        for (constraint, dual) in self.constraints.iter().zip(duals) {
+            match constraint.hard_constraint() {
+                LagrangianConstraint::MAX_BUDGET { .. } => cost_weight += dual,
+                LagrangianConstraint::MIN_IMPRESSIONS { .. } => value_offset += dual,
+                LagrangianConstraint::MIN_ROAS { roas } => {
+                    value_weight += dual;
+                    cost_weight += dual * roas;
+                }
+                LagrangianConstraint::SOFT { .. } => unreachable!("Soft constraints are bid on as their hard constraint"),
+            }
+        }
+        let effective_value = (value_to_campaign * seller_control_factor * value_weight + value_offset) / cost_weight;
+        BidOptimizerMaximumMargin.get_optimized_bid(effective_value, impression, impression.competition.as_ref())
+    }
+
+    fn create_controller_state(&self) -> Vec<Box<dyn ControllerStateTrait>> {
+        vec![self.converge_controller.create_controller_state()]
+    }
+
+    fn next_controller_state(&self, previous_states: &[Box<dyn ControllerStateTrait>], next_states: &mut [Box<dyn ControllerStateTrait>], campaign_stat: &CampaignStat) -> bool {
+        let duals = self.converge_controller.get_duals(previous_states[0].as_ref());
+        let subgradients: Vec<f64> = self.constraints.iter().zip(duals).map(|(constraint, dual)| constraint.subgradient(campaign_stat, *dual)).collect();
+        self.converge_controller.next_controller_state(previous_states[0].as_ref(), next_states[0].as_mut(), &subgradients)
+    }
+
+    fn type_target_and_controller_state_string(&self, controller_states: &[&dyn ControllerStateTrait]) -> String {
+        let constraints: Vec<String> = self.constraints.iter().map(|constraint| constraint.constraint_string()).collect();
+        format!("Lagrangian / {} (constraints: {} ({}))",
+            BidOptimizerMaximumMargin.get_optimizer_type(),
+            constraints.join(", "),
+            self.converge_controller.controller_string(controller_states[0]))
+    }
+
+    fn get_control_variables(&self, controller_states: &[&dyn ControllerStateTrait]) -> Vec<f64> {
+        self.converge_controller.get_duals(controller_states[0]).to_vec()
+    }
+
+    fn get_targets_actual_and_target(&self, _controller_states: &[&dyn ControllerStateTrait], campaign_stat: &CampaignStat) -> Vec<(String, f64, f64)> {
+        self.constraints.iter()
+            .map(|constraint| {
+                let (actual, target) = constraint.actual_and_target(campaign_stat);
+                (constraint.constraint_string(), actual, target)
+            })
+            .collect()
+    }
+
+    fn perturb_controller_states(&self, controller_states: &mut [Box<dyn ControllerStateTrait>], factor: f64) {
+        let state = controller_states[0].as_any_mut().downcast_mut::<ControllerStateDual>().unwrap();
+        for dual in state.duals.iter_mut() {
+            *dual *= factor;
+        }
+    }
+
+    fn soft_target_penalty(&self, campaign_stat: &CampaignStat) -> Option<f64> {
+        let soft_constraints: Vec<&LagrangianConstraint> = self.constraints.iter().filter(|constraint| matches!(constraint, LagrangianConstraint::SOFT { .. })).collect();
+        if soft_constraints.is_empty() {
+            None
+        } else {
+            Some(soft_constraints.iter().map(|constraint| constraint.penalty(campaign_stat)).sum())
+        }
+    }
+
+    fn max_bid_cpm(&self) -> Option<f64> {
+        None
+    }
+
+    fn wants_bid_history(&self) -> bool {
+        false
+    }
+
+    fn intra_run_update(&self, _impression: &Impression, _controller_states: &[&dyn ControllerStateTrait], _buyer_charge: f64) {
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_penalty_functions() {
+        assert_eq!(PenaltyFunction::LINEAR { weight: 2.0 }.penalty(0.5), 1.0);
+        assert_eq!(PenaltyFunction::QUADRATIC { weight: 2.0 }.penalty(0.5), 0.25);
+        // Met constraints (negative violation) are not penalized
+        assert_eq!(PenaltyFunction::LINEAR { weight: 2.0 }.penalty(-0.5), 0.0);
+        assert_eq!(PenaltyFunction::QUADRATIC { weight: 2.0 }.penalty(-0.5), 0.0);
+    }
+}
@@ -0,0 +1,181 @@
+//! Campaign that ignores the provided win rate prediction and learns its own from bid outcomes
+//!
+//! The provided prediction sigmoid is not used at all. Instead the campaign accumulates the outcomes
+//! of its own bids (win or loss) across convergence iterations and fits a logistic regression of
+//! win/loss on the bid and on the base impression value. Only win/loss is observed, never the price
+//! of the competition, so this is a censored fit of the competition.
+//!
+//! The fitted model gives a win rate sigmoid for each impression, which is then used to bid for
+//! maximum margin on the paced value. Until the first model is fitted the campaign bids truthfully.
+//! Since the campaign learns from its own bids, the model and the bids evolve together
+//! (endogenous learning), which is what this campaign is meant to explore.
+
+use crate::impressions::Impression;
+use crate::campaign::CampaignTrait;
+use crate::campaign_targets::CampaignTargetTrait;
+use crate::controllers::{ControllerTrait, ControllerProportionalDerivative, ControllerStateTrait, ControllerStateCompetitionModel};
+use crate::simulationrun::{CampaignStat, BidObservation};
+use crate::competition::{fit_win_rate_logistic, win_rate_logistic_sigmoid};
+use crate::sigmoid::Sigmoid;
+use std::any::Any;
+
+/// Maximum number of observations kept, older observations are dropped first
+const MAX_OBSERVATIONS: usize = 50000;
+/// Minimum number of observations (and of both wins and losses) before the model is fitted
+const MIN_OBSERVATIONS: usize = 100;
+/// Relative change of coefficients above which the model is considered changed
+const MODEL_CHANGE_TOLERANCE: f64 = 0.01;
+
+pub struct CampaignLearnedCompetition {
+    pub campaign_id: usize,
+    pub campaign_name: String,
+    pub converge_target: Box<dyn CampaignTargetTrait>,
+    /// Controller for the pacing
+    pub converge_controller: Box<dyn ControllerTrait>,
+}
+
+impl CampaignLearnedCompetition {
+    /// Create a new campaign learning its own win rate model
+    ///
+    /// # Arguments
+    /// * `campaign_name` - Name of the campaign
+    /// * `converge_target` - Target the pacing converges to
+    pub fn new(campaign_name: String, converge_target: Box<dyn CampaignTargetTrait>) -> Self {
+        Self {
+            campaign_id: 0,  // Set by Campaigns::add_advanced
+            campaign_name,
+            converge_target,
+            converge_controller: Box::new(ControllerProportionalDerivative::new()),
+        }
+    }
+
+    /// Win rate sigmoid predicted by the learned coefficients for an impression
+    /// Returns None when the model does not increase win rate with the bid
+    fn predicted_sigmoid(coefficients: &[f64; 3], base_impression_value: f64) -> Option<Sigmoid> {
+        win_rate_logistic_sigmoid(coefficients, base_impression_value).map(|(offset, scale)| Sigmoid::new(offset, scale, 1.0))
+    }
+}
+
+/// Add the observations of a bid history to a learned win rate model and refit it
+/// Shared by campaigns learning the competition from censored outcomes of their own bids
+/// 
+/// # Returns
+/// Whether the model changed
+pub(crate) fn learn_competition_model(previous_model: &ControllerStateCompetitionModel, next_model: &mut ControllerStateCompetitionModel, bid_history: &[BidObservation]) -> bool {
+    // Bids below the floor lose regardless of competition, so they say nothing about it
+    next_model.observations.extend(bid_history.iter()
+        .filter(|observation| observation.bid_cpm >= observation.floor_cpm)
+        .map(|observation| (observation.bid_cpm, observation.base_impression_value, observation.won)));
+    if next_model.observations.len() > MAX_OBSERVATIONS {
+        let excess = next_model.observations.len() - MAX_OBSERVATIONS;
+        next_model.observations.drain(0..excess);
+    }
+
+    let wins = next_model.observations.iter().filter(|(_, _, won)| *won).count();
+    let losses = next_model.observations.len() - wins;
+    if wins < MIN_OBSERVATIONS || losses < MIN_OBSERVATIONS {
+        return false;
+    }
+
+    let fitted = fit_win_rate_logistic(&next_model.observations, previous_model.coefficients.unwrap_or([0.0; 3]));
+    // A model where win rate does not increase with the bid is unusable, keep the previous one
+    if !(fitted.iter().all(|c| c.is_finite()) && fitted[1] > 0.0) {
+        return false;
+    }
+    let model_changed = match previous_model.coefficients {
+        Some(previous) => (0..3).any(|i| (fitted[i] - previous[i]).abs() > MODEL_CHANGE_TOLERANCE * previous[i].abs().max(1e-6)),
+        None => true,
+    };
+    next_model.coefficients = Some(fitted);
+    model_changed
+}
+
+impl CampaignTrait for CampaignLearnedCompetition {
+    fn campaign_id(&self) -> usize {
+        self.campaign_id
+    }
+
+    fn campaign_name(&self) -> &str {
+        &self.campaign_name
+    }
+
+    fn get_bid(&self, impression: &Impression, controller_states: &[&dyn ControllerStateTrait], seller_control_factor: f64, value_to_campaign: f64, _logger: &mut crate::logger::Logger) -> Option<f64> {
+        let pacing = self.converge_controller.get_control_variable(controller_states[0]);
+        let model = controller_states[1].as_any().downcast_ref::<ControllerStateCompetitionModel>().unwrap();
+
+        let paced_value = pacing * value_to_campaign * seller_control_factor;
+        if paced_value < impression.floor_cpm {
+            return None;
+        }
+        // Without a model yet, bid truthfully to gather observations
+        match model.coefficients.as_ref().and_then(|c| Self::predicted_sigmoid(c, impression.base_impression_value)) {
+            Some(sigmoid) => sigmoid.max_margin_bid_bisection(paced_value, impression.floor_cpm),
+            None => Some(paced_value),
+        }
+    }
+
+    fn create_controller_state(&self) -> Vec<Box<dyn ControllerStateTrait>> {
+        vec![
+            self.converge_controller.create_controller_state(),
+            Box::new(ControllerStateCompetitionModel { coefficients: None, observations: Vec::new() }),
+        ]
+    }
+
+    fn next_controller_state(&self, previous_states: &[Box<dyn ControllerStateTrait>], next_states: &mut [Box<dyn ControllerStateTrait>], campaign_stat: &CampaignStat) -> bool {
+        let (actual, target) = self.converge_target.get_actual_and_target(campaign_stat);
+        let mut any_changed = self.converge_controller.next_controller_state(previous_states[0].as_ref(), next_states[0].as_mut(), actual, target);
+
+        let previous_model = previous_states[1].as_any().downcast_ref::<ControllerStateCompetitionModel>().unwrap();
+        let next_model = next_states[1].as_any_mut().downcast_mut::<ControllerStateCompetitionModel>().unwrap();
+
+        any_changed |= learn_competition_model(previous_model, next_model, &campaign_stat.bid_history);
+        any_changed
+    }
+
+    fn type_target_and_controller_state_string(&self, controller_states: &[&dyn ControllerStateTrait]) -> String {
+        let model = controller_states[1].as_any().downcast_ref::<ControllerStateCompetitionModel>().unwrap();
+        let model_string = match model.coefficients {
+            Some([intercept, bid_coefficient, value_coefficient]) => format!(
+                "learned win rate: intercept {:.4}, bid {:.4}, value {:.4}, {} observations",
+                intercept, bid_coefficient, value_coefficient, model.observations.len()),
+            None => format!("no learned win rate yet, {} observations", model.observations.len()),
+        };
+        format!("MaxMarginLearned / Learned competition (T1: {} ({}), {})",
+            self.converge_target.converge_target_string(),
+            self.converge_controller.controller_string(controller_states[0]),
+            model_string)
+    }
+
+    fn get_control_variables(&self, controller_states: &[&dyn ControllerStateTrait]) -> Vec<f64> {
+        vec![self.converge_controller.get_control_variable(controller_states[0])]
+    }
+
+    fn get_targets_actual_and_target(&self, _controller_states: &[&dyn ControllerStateTrait], campaign_stat: &CampaignStat) -> Vec<(String, f64, f64)> {
+        let (actual, target) = self.converge_target.get_actual_and_target(campaign_stat);
+        vec![(self.converge_target.converge_target_string(), actual, target)]
+    }
+
+    fn perturb_controller_states(&self, controller_states: &mut [Box<dyn ControllerStateTrait>], factor: f64) {
+        let control_variable = self.converge_controller.get_control_variable(controller_states[0].as_ref());
+        self.converge_controller.set_control_variable(controller_states[0].as_mut(), control_variable * factor);
+    }
+
+    fn max_bid_cpm(&self) -> Option<f64> {
+        None
+    }
+
+    fn wants_bid_history(&self) -> bool {
+        true
+    }
+
+    fn intra_run_update(&self, _impression: &Impression, _controller_states: &[&dyn ControllerStateTrait], _buyer_charge: f64) {
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,183 @@
+//! Portfolio campaign that allocates its bidding threshold differently per seller
+//!
+//! On top of the campaign-wide pacing (converged to the campaign target), the campaign keeps one
+//! pacing multiplier per seller in a single ControllerStateMultiVariable keyed by seller_id.
+//! 
+//! Spend is allocated optimally when the marginal value per marginal spend is the same on all sellers.
+//! Average value per spend is not a usable signal (with truthful first price bidding it is the same
+//! on all sellers), so the marginal value per spend of each seller is estimated from the change in
+//! spend and value between consecutive iterations. Multipliers increase on sellers with above average
+//! marginal value per spend, so spend shifts to sellers where supply is cheapest.
+
+use crate::impressions::Impression;
+use crate::campaign::CampaignTrait;
+use crate::campaign_targets::CampaignTargetTrait;
+use crate::controllers::{ControllerTrait, ControllerProportionalDerivative, ControllerProportionalDerivativeCore, ControllerStateTrait, ControllerStateMultiVariable};
+use crate::bid_optimizers::BidOptimizerTrait;
+use crate::simulationrun::CampaignStat;
+use std::any::Any;
+
+/// Minimum change in per-seller spend between iterations to estimate marginal value per spend
+const MIN_SPEND_CHANGE: f64 = 0.01;
+
+pub struct CampaignPortfolio {
+    pub campaign_id: usize,
+    pub campaign_name: String,
+    pub converge_target: Box<dyn CampaignTargetTrait>,
+    /// Controller for the campaign-wide pacing
+    pub converge_controller: Box<dyn ControllerTrait>,
+    /// Controller core used for each per-seller multiplier
+    pub seller_controller: ControllerProportionalDerivativeCore,
+    pub bid_optimizer: Box<dyn BidOptimizerTrait>,
+    pub num_sellers: usize,
+}
+
+impl CampaignPortfolio {
+    /// Create a new portfolio campaign
+    ///
+    /// # Arguments
+    /// * `campaign_name` - Name of the campaign
+    /// * `converge_target` - Target the campaign-wide pacing converges to
+    /// * `bid_optimizer` - Optimizer applied to the per-seller paced value
+    /// * `num_sellers` - Number of sellers in the marketplace (one multiplier per seller)
+    pub fn new(campaign_name: String, converge_target: Box<dyn CampaignTargetTrait>, bid_optimizer: Box<dyn BidOptimizerTrait>, num_sellers: usize) -> Self {
+        Self {
+            campaign_id: 0,  // Set by Campaigns::add_advanced
+            campaign_name,
+            converge_target,
+            converge_controller: Box::new(ControllerProportionalDerivative::new()),
+            // Marginal estimates are noisy, so multipliers use a wider tolerance and no rescaling
+            seller_controller: ControllerProportionalDerivativeCore::new_advanced(
+                0.01,  // tolerance_fraction
+                0.1,   // max_adjustment_factor
+                0.1,   // proportional_gain
+                0.05,  // derivative_gain
+                false, // rescaling
+            ),
+            bid_optimizer,
+            num_sellers,
+        }
+    }
+}
+
+impl CampaignTrait for CampaignPortfolio {
+    fn campaign_id(&self) -> usize {
+        self.campaign_id
+    }
+
+    fn campaign_name(&self) -> &str {
+        &self.campaign_name
+    }
+
+    fn get_bid(&self, impression: &Impression, controller_states: &[&dyn ControllerStateTrait], seller_control_factor: f64, value_to_campaign: f64, _logger: &mut crate::logger::Logger) -> Option<f64> {
+        let pacing = self.converge_controller.get_control_variable(controller_states[0]);
+        let seller_multipliers = controller_states[1].as_any().downcast_ref::<ControllerStateMultiVariable>().unwrap();
+        let seller_multiplier = seller_multipliers.variables[impression.seller_id];
+
+        let paced_value = pacing * seller_multiplier * value_to_campaign * seller_control_factor;
+        self.bid_optimizer.get_optimized_bid(paced_value, impression, impression.competition.as_ref())
+    }
+
+    fn create_controller_state(&self) -> Vec<Box<dyn ControllerStateTrait>> {
+        vec![
+            self.converge_controller.create_controller_state(),
+            Box::new(ControllerStateMultiVariable::new(self.num_sellers, 1.0)),
+        ]
+    }
+
+    fn next_controller_state(&self, previous_states: &[Box<dyn ControllerStateTrait>], next_states: &mut [Box<dyn ControllerStateTrait>], campaign_stat: &CampaignStat) -> bool {
+        // Campaign-wide pacing converges to the campaign target
+        let (actual, target) = self.converge_target.get_actual_and_target(campaign_stat);
+        let mut any_changed = self.converge_controller.next_controller_state(previous_states[0].as_ref(), next_states[0].as_mut(), actual, target);
+
+        // Per-seller multipliers converge so that marginal value per spend is equal on all sellers
+        let previous_multipliers = previous_states[1].as_any().downcast_ref::<ControllerStateMultiVariable>().unwrap();
+        let next_multipliers = next_states[1].as_any_mut().downcast_mut::<ControllerStateMultiVariable>().unwrap();
+
+        // Estimate marginal value per spend from the change since the previous iteration
+        let mut marginal_value_per_spend: Vec<Option<f64>> = vec![None; self.num_sellers];
+        for (seller_id, seller_stat) in campaign_stat.seller_breakdown.iter().enumerate() {
+            if let Some((previous_spend, previous_value)) = previous_multipliers.previous_observations[seller_id] {
+                let spend_change = seller_stat.total_buyer_charge - previous_spend;
+                let value_change = seller_stat.total_value - previous_value;
+                // Too small changes in spend give unreliable estimates
+                if spend_change.abs() > MIN_SPEND_CHANGE && value_change / spend_change > 0.0 {
+                    marginal_value_per_spend[seller_id] = Some(value_change / spend_change);
+                }
+            }
+            next_multipliers.previous_observations[seller_id] = Some((seller_stat.total_buyer_charge, seller_stat.total_value));
+        }
+
+        // Multipliers can only be compared when at least two sellers have an estimate
+        let estimates: Vec<f64> = marginal_value_per_spend.iter().flatten().cloned().collect();
+        if estimates.len() < 2 {
+            return any_changed;
+        }
+        let average_marginal_value_per_spend = estimates.iter().sum::<f64>() / estimates.len() as f64;
+
+        for (seller_id, estimate) in marginal_value_per_spend.iter().enumerate() {
+            if let Some(seller_marginal_value_per_spend) = estimate {
+                // Seller estimate acts as target: when it is above average, the multiplier increases
+                let (changed, next_multiplier, next_error) = self.seller_controller.controller_next_state(
+                    *seller_marginal_value_per_spend,
+                    average_marginal_value_per_spend,
+                    previous_multipliers.variables[seller_id],
+                    previous_multipliers.previous_errors[seller_id],
+                );
+                next_multipliers.variables[seller_id] = next_multiplier;
+                next_multipliers.previous_errors[seller_id] = Some(next_error);
+                any_changed |= changed;
+            }
+        }
+        any_changed
+    }
+
+    fn type_target_and_controller_state_string(&self, controller_states: &[&dyn ControllerStateTrait]) -> String {
+        let seller_multipliers = controller_states[1].as_any().downcast_ref::<ControllerStateMultiVariable>().unwrap();
+        let multipliers: Vec<String> = seller_multipliers.variables.iter()
+            .enumerate()
+            .map(|(seller_id, multiplier)| format!("S{}: {:.4}", seller_id, multiplier))
+            .collect();
+        format!("{} / Portfolio (T1: {} ({}), seller multipliers: {})",
+            self.bid_optimizer.get_optimizer_type(),
+            self.converge_target.converge_target_string(),
+            self.converge_controller.controller_string(controller_states[0]),
+            multipliers.join(", "))
+    }
+
+    fn get_control_variables(&self, controller_states: &[&dyn ControllerStateTrait]) -> Vec<f64> {
+        let seller_multipliers = controller_states[1].as_any().downcast_ref::<ControllerStateMultiVariable>().unwrap();
+        let mut control_variables = vec![self.converge_controller.get_control_variable(controller_states[0])];
+        control_variables.extend_from_slice(&seller_multipliers.variables);
+        control_variables
+    }
+
+    fn get_targets_actual_and_target(&self, _controller_states: &[&dyn ControllerStateTrait], campaign_stat: &CampaignStat) -> Vec<(String, f64, f64)> {
+        let (actual, target) = self.converge_target.get_actual_and_target(campaign_stat);
+        vec![(self.converge_target.converge_target_string(), actual, target)]
+    }
+
+    fn perturb_controller_states(&self, controller_states: &mut [Box<dyn ControllerStateTrait>], factor: f64) {
+        let control_variable = self.converge_controller.get_control_variable(controller_states[0].as_ref());
+        self.converge_controller.set_control_variable(controller_states[0].as_mut(), control_variable * factor);
+    }
+
+    fn max_bid_cpm(&self) -> Option<f64> {
+        None
+    }
+
+    fn wants_bid_history(&self) -> bool {
+        false
+    }
+
+    fn intra_run_update(&self, _impression: &Impression, _controller_states: &[&dyn ControllerStateTrait], _buyer_charge: f64) {
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -21,6 +21,42 @@ pub trait CampaignTargetTrait {
     fn converge_target_string(&self) -> String;
 }
 
+/// Band around a target within which the target counts as met when validating results
+/// (controllers converge with their own, usually tighter, tolerance)
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TargetTolerance {
+    /// Fraction of the target, e.g. 0.01 = within 1% of the target
+    RELATIVE(f64),
+    /// Absolute difference from the target, in the target's units
+    ABSOLUTE(f64),
+}
+
+impl TargetTolerance {
+    /// Whether the actual value is within the band around the target
+    pub fn is_met(&self, actual: f64, target: f64) -> bool {
+        match self {
+            TargetTolerance::RELATIVE(fraction) => (actual - target).abs() <= fraction * target.abs(),
+            TargetTolerance::ABSOLUTE(difference) => (actual - target).abs() <= *difference,
+        }
+    }
+    
+    /// Get a string representation of the band, e.g. "±1.0%"
+    pub fn tolerance_string(&self) -> String {
+        match self {
+            TargetTolerance::RELATIVE(fraction) => format!("±{:.1}%", fraction * 100.0),
+            TargetTolerance::ABSOLUTE(difference) => format!("±{}", difference),
+        }
+    }
+}
+
+impl Default for TargetTolerance {
+    /// Targets are met within 1% by default
+    fn default() -> Self {
+        TargetTolerance::RELATIVE(0.01)
+    }
+}
+
 /// Convergence strategy for total impressions target
 pub struct CampaignTargetTotalImpressions {
     pub total_impressions_target: i32,
@@ -28,7 +64,12 @@ pub struct CampaignTargetTotalImpressions {
 
 impl CampaignTargetTrait for CampaignTargetTotalImpressions {
     fn get_actual_and_target(&self, campaign_stat: &crate::simulationrun::CampaignStat) -> (f64, f64) {
-        (campaign_stat.impressions_obtained, self.total_impressions_target as f64)
+        // A frequency cap reduces the supply the campaign can win, the target can't exceed it
+        let target = match campaign_stat.eligible_impressions {
+            Some(eligible_impressions) => (self.total_impressions_target as f64).min(eligible_impressions),
+            None => self.total_impressions_target as f64,
+        };
+        (campaign_stat.impressions_obtained, target)
     }
     
     fn get_target_value(&self) -> f64 {
@@ -86,6 +127,217 @@ impl CampaignTargetTrait for CampaignTargetAvgValue {
     }
 }
 
+/// Convergence strategy for cost per acquisition target (spend per conversion)
+/// Needs conversions to be modeled, see ImpressionsParam::with_conversions
+pub struct CampaignTargetCpa {
+    pub cpa_target: f64,
+}
+
+impl CampaignTargetTrait for CampaignTargetCpa {
+    fn get_actual_and_target(&self, campaign_stat: &crate::simulationrun::CampaignStat) -> (f64, f64) {
+        // Without conversions CPA is undefined, 0.0 as actual lets pacing increase until there are some
+        let actual = if campaign_stat.total_conversions > 0.0 {
+            campaign_stat.total_buyer_charge / campaign_stat.total_conversions
+        } else {
+            0.0
+        };
+        (actual, self.cpa_target)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        self.cpa_target
+    }
+    
+    fn converge_target_string(&self) -> String {
+        format!("CPA: {:.4}", self.cpa_target)
+    }
+}
+
+/// Convergence strategy for a minimum average viewability of won impressions, as measured
+/// Converges like an equality target, the controller's lower bound makes it a minimum (see MULTIPLICATIVE_VIEWABILITY_CONSTRAINED)
+pub struct CampaignTargetMinAvgViewability {
+    pub min_avg_viewability: f64,
+}
+
+impl CampaignTargetTrait for CampaignTargetMinAvgViewability {
+    fn get_actual_and_target(&self, campaign_stat: &crate::simulationrun::CampaignStat) -> (f64, f64) {
+        // If no impressions were obtained, return 0.0 as actual
+        let actual = if campaign_stat.impressions_obtained > 0.0 {
+            campaign_stat.total_measured_viewability / campaign_stat.impressions_obtained
+        } else {
+            0.0
+        };
+        (actual, self.min_avg_viewability)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        self.min_avg_viewability
+    }
+    
+    fn converge_target_string(&self) -> String {
+        format!("Min avg viewability: {:.4}", self.min_avg_viewability)
+    }
+}
+
+/// Convergence strategy for a maximum average CPM of won impressions: total buyer charge * 1000 / impressions
+/// Converges like an equality target, the controller's upper bound makes it a maximum (see MULTIPLICATIVE_CPM_CEILING)
+pub struct CampaignTargetMaxAvgCpm {
+    pub max_avg_cpm: f64,
+}
+
+impl CampaignTargetTrait for CampaignTargetMaxAvgCpm {
+    fn get_actual_and_target(&self, campaign_stat: &crate::simulationrun::CampaignStat) -> (f64, f64) {
+        // If no impressions were obtained, return 0.0 as actual
+        let actual = if campaign_stat.impressions_obtained > 0.0 {
+            campaign_stat.total_buyer_charge * 1000.0 / campaign_stat.impressions_obtained
+        } else {
+            0.0
+        };
+        (actual, self.max_avg_cpm)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        self.max_avg_cpm
+    }
+    
+    fn converge_target_string(&self) -> String {
+        format!("Max avg CPM: {:.4}", self.max_avg_cpm)
+    }
+}
+
+/// Convergence strategy for a maximum share of spend going to one seller: the seller's buyer charge / total buyer charge
+/// Converges like an equality target, the controller's upper bound makes it a maximum (see MULTIPLICATIVE_SELLER_SPEND_SHARE)
+pub struct CampaignTargetMaxSellerSpendShare {
+    pub seller_id: usize,
+    pub max_spend_share: f64,
+}
+
+impl CampaignTargetTrait for CampaignTargetMaxSellerSpendShare {
+    fn get_actual_and_target(&self, campaign_stat: &crate::simulationrun::CampaignStat) -> (f64, f64) {
+        // If nothing was spent, return 0.0 as actual
+        let actual = if campaign_stat.total_buyer_charge > 0.0 {
+            campaign_stat.seller_breakdown[self.seller_id].total_buyer_charge / campaign_stat.total_buyer_charge
+        } else {
+            0.0
+        };
+        (actual, self.max_spend_share)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        self.max_spend_share
+    }
+    
+    fn converge_target_string(&self) -> String {
+        format!("Max spend share of seller {}: {:.4}", self.seller_id, self.max_spend_share)
+    }
+}
+
+/// Convergence strategy for win rate target: share of the auctions the campaign bid in that it won
+pub struct CampaignTargetWinRate {
+    pub win_rate_target: f64,
+}
+
+impl CampaignTargetTrait for CampaignTargetWinRate {
+    fn get_actual_and_target(&self, campaign_stat: &crate::simulationrun::CampaignStat) -> (f64, f64) {
+        // If the campaign didn't bid in any auction, return 0.0 as actual
+        let actual = if campaign_stat.auctions_participated > 0 {
+            campaign_stat.impressions_obtained / campaign_stat.weighted_auctions_participated
+        } else {
+            0.0
+        };
+        (actual, self.win_rate_target)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        self.win_rate_target
+    }
+    
+    fn converge_target_string(&self) -> String {
+        format!("Win rate: {:.4}", self.win_rate_target)
+    }
+}
+
+/// Convergence strategy for value efficiency target: value per spend (total_value / total_buyer_charge)
+/// Value per spend drops as pacing rises, so the controller converges its inverse, spend per value,
+/// which rises with pacing like spend does
+pub struct CampaignTargetValuePerSpend {
+    pub value_per_spend_target: f64,
+}
+
+impl CampaignTargetValuePerSpend {
+    /// Value per spend of the campaign, 0.0 if it spent nothing
+    pub fn value_per_spend(campaign_stat: &crate::simulationrun::CampaignStat) -> f64 {
+        if campaign_stat.total_buyer_charge > 0.0 {
+            campaign_stat.total_value / campaign_stat.total_buyer_charge
+        } else {
+            0.0
+        }
+    }
+}
+
+impl CampaignTargetTrait for CampaignTargetValuePerSpend {
+    fn get_actual_and_target(&self, campaign_stat: &crate::simulationrun::CampaignStat) -> (f64, f64) {
+        // Without value the spend per value is 0.0, which lets pacing increase until there is some
+        let spend_per_value = if campaign_stat.total_value > 0.0 {
+            campaign_stat.total_buyer_charge / campaign_stat.total_value
+        } else {
+            0.0
+        };
+        (spend_per_value, 1.0 / self.value_per_spend_target)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        1.0 / self.value_per_spend_target
+    }
+    
+    fn converge_target_string(&self) -> String {
+        format!("Value per spend: {:.2}", self.value_per_spend_target)
+    }
+}
+
+/// Convergence strategy for margin target: (value - buyer charge) / value
+/// Value is accumulated in CPM (total_value) while buyer charge is per impression, so value is divided by 1000
+/// Margin drops as pacing rises, so the controller converges the cost to value ratio (1 - margin)
+/// instead, which rises with pacing like spend does
+pub struct CampaignTargetMargin {
+    pub margin_target: f64,
+}
+
+impl CampaignTargetMargin {
+    /// Cost to value ratio of the campaign (1 - margin), 0.0 if it obtained no value
+    fn cost_to_value(campaign_stat: &crate::simulationrun::CampaignStat) -> f64 {
+        if campaign_stat.total_value > 0.0 {
+            campaign_stat.total_buyer_charge / (campaign_stat.total_value / 1000.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Margin of the campaign, 0.0 if it obtained no value
+    pub fn margin(campaign_stat: &crate::simulationrun::CampaignStat) -> f64 {
+        if campaign_stat.total_value > 0.0 {
+            1.0 - Self::cost_to_value(campaign_stat)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl CampaignTargetTrait for CampaignTargetMargin {
+    fn get_actual_and_target(&self, campaign_stat: &crate::simulationrun::CampaignStat) -> (f64, f64) {
+        // Without value the ratio is 0.0, which lets pacing increase until there is some
+        (Self::cost_to_value(campaign_stat), 1.0 - self.margin_target)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        1.0 - self.margin_target
+    }
+    
+    fn converge_target_string(&self) -> String {
+        format!("Margin: {:.4}", self.margin_target)
+    }
+}
+
 /// Convergence strategy for no convergence (fixed pacing)
 pub struct CampaignTargetNone;
 
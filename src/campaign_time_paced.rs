@@ -0,0 +1,176 @@
+//! Campaign that paces its budget within the simulation run, per time bucket of the day
+//!
+//! Other campaigns only update pacing between convergence iterations, so their spend follows the
+//! traffic: when most impressions arrive in the evening, most of the budget is spent in the evening.
+//! This campaign aims for smooth delivery, spending the budget evenly across the day.
+//!
+//! Two controls are combined:
+//! - Intra-run multiplier, updated at every time bucket boundary by a PID controller on the difference
+//!   between planned spend (linear in time of day) and actual spend so far
+//! - Base pacing, updated between convergence iterations by a PD controller, so that the intra-run
+//!   multiplier is 1.0 on average over the day
+//!
+//! The integral term keeps cumulative spend on the plan, so total spend reaches the budget within the run.
+//! Base pacing doesn't target the budget (the PID would cancel its effect), instead it learns the
+//! level of bidding so that every run starts with the right pacing and the PID only shapes delivery.
+//!
+//! The intra-run state lives in a ControllerStateIntraRunPacing, which is updated through
+//! CampaignTrait::intra_run_update while auctions run and reset between iterations.
+
+use crate::impressions::{Impression, HOURS_PER_DAY};
+use crate::campaign::CampaignTrait;
+use crate::controllers::{ControllerTrait, ControllerProportionalDerivative, ControllerStateTrait, ControllerStateIntraRunPacing};
+use crate::bid_optimizers::BidOptimizerTrait;
+use crate::simulationrun::CampaignStat;
+use std::any::Any;
+
+/// Proportional gain of the intra-run PID controller (on error normalized by per-bucket budget)
+const PID_PROPORTIONAL_GAIN: f64 = 0.3;
+/// Integral gain of the intra-run PID controller
+const PID_INTEGRAL_GAIN: f64 = 0.05;
+/// Derivative gain of the intra-run PID controller
+const PID_DERIVATIVE_GAIN: f64 = 0.1;
+/// Limit of the accumulated error, prevents windup in hours where the plan can't be met
+const PID_INTEGRAL_LIMIT: f64 = 20.0;
+/// Intra-run multiplier is kept within [1 / MAX_MULTIPLIER, MAX_MULTIPLIER]
+const MAX_MULTIPLIER: f64 = 5.0;
+
+pub struct CampaignTimePaced {
+    pub campaign_id: usize,
+    pub campaign_name: String,
+    /// Budget to be delivered evenly over the day
+    pub total_budget: f64,
+    /// Controller for the base pacing (between iterations)
+    pub converge_controller: Box<dyn ControllerTrait>,
+    pub bid_optimizer: Box<dyn BidOptimizerTrait>,
+    /// Number of equal time buckets the day is divided into for intra-run updates
+    pub num_time_buckets: usize,
+}
+
+impl CampaignTimePaced {
+    /// Create a new time paced campaign
+    ///
+    /// # Arguments
+    /// * `campaign_name` - Name of the campaign
+    /// * `total_budget` - Budget to spend evenly over the day
+    /// * `bid_optimizer` - Optimizer applied to the paced value
+    /// * `num_time_buckets` - Number of intra-run pacing updates per day
+    pub fn new(campaign_name: String, total_budget: f64, bid_optimizer: Box<dyn BidOptimizerTrait>, num_time_buckets: usize) -> Self {
+        Self {
+            campaign_id: 0,  // Set by Campaigns::add_advanced
+            campaign_name,
+            total_budget,
+            converge_controller: Box::new(ControllerProportionalDerivative::new()),
+            bid_optimizer,
+            num_time_buckets,
+        }
+    }
+
+    /// Time bucket the timestamp falls into
+    fn time_bucket(&self, timestamp: f64) -> usize {
+        ((timestamp / HOURS_PER_DAY * self.num_time_buckets as f64) as usize).min(self.num_time_buckets - 1)
+    }
+}
+
+impl CampaignTrait for CampaignTimePaced {
+    fn campaign_id(&self) -> usize {
+        self.campaign_id
+    }
+
+    fn campaign_name(&self) -> &str {
+        &self.campaign_name
+    }
+
+    fn get_bid(&self, impression: &Impression, controller_states: &[&dyn ControllerStateTrait], seller_control_factor: f64, value_to_campaign: f64, _logger: &mut crate::logger::Logger) -> Option<f64> {
+        let pacing = self.converge_controller.get_control_variable(controller_states[0]);
+        let intra_run = controller_states[1].as_any().downcast_ref::<ControllerStateIntraRunPacing>().unwrap();
+
+        let paced_value = pacing * intra_run.multiplier.get() * value_to_campaign * seller_control_factor;
+        self.bid_optimizer.get_optimized_bid(paced_value, impression, impression.competition.as_ref())
+    }
+
+    fn create_controller_state(&self) -> Vec<Box<dyn ControllerStateTrait>> {
+        vec![
+            self.converge_controller.create_controller_state(),
+            Box::new(ControllerStateIntraRunPacing::new()),
+        ]
+    }
+
+    fn next_controller_state(&self, previous_states: &[Box<dyn ControllerStateTrait>], next_states: &mut [Box<dyn ControllerStateTrait>], _campaign_stat: &CampaignStat) -> bool {
+        let intra_run = previous_states[1].as_any().downcast_ref::<ControllerStateIntraRunPacing>().unwrap();
+        // Average multiplier acts as target: when above 1.0, base pacing increases
+        let average_multiplier = intra_run.average_multiplier();
+        // Next run starts the day from scratch
+        next_states[1] = Box::new(ControllerStateIntraRunPacing::new());
+        self.converge_controller.next_controller_state(previous_states[0].as_ref(), next_states[0].as_mut(), 1.0, average_multiplier)
+    }
+
+    fn intra_run_update(&self, impression: &Impression, controller_states: &[&dyn ControllerStateTrait], buyer_charge: f64) {
+        let intra_run = controller_states[1].as_any().downcast_ref::<ControllerStateIntraRunPacing>().unwrap();
+        intra_run.spend.set(intra_run.spend.get() + buyer_charge);
+
+        // PID update at every bucket boundary passed since the last update
+        let bucket = self.time_bucket(impression.timestamp);
+        let bucket_budget = self.total_budget / self.num_time_buckets as f64;
+        while intra_run.bucket.get() < bucket {
+            let completed_buckets = intra_run.bucket.get() + 1;
+            let planned_spend = bucket_budget * completed_buckets as f64;
+            // Positive error means delivery is behind the plan, so the multiplier increases
+            let error = (planned_spend - intra_run.spend.get()) / bucket_budget;
+            let integral = (intra_run.integral.get() + error).clamp(-PID_INTEGRAL_LIMIT, PID_INTEGRAL_LIMIT);
+            let derivative = intra_run.previous_error.get().map_or(0.0, |previous_error| error - previous_error);
+            let log_multiplier = PID_PROPORTIONAL_GAIN * error + PID_INTEGRAL_GAIN * integral + PID_DERIVATIVE_GAIN * derivative;
+
+            intra_run.multiplier_sum.set(intra_run.multiplier_sum.get() + intra_run.multiplier.get());
+            intra_run.multiplier.set(log_multiplier.exp().clamp(1.0 / MAX_MULTIPLIER, MAX_MULTIPLIER));
+            intra_run.integral.set(integral);
+            intra_run.previous_error.set(Some(error));
+            intra_run.bucket.set(completed_buckets);
+        }
+    }
+
+    fn updates_within_run(&self) -> bool {
+        true
+    }
+
+    fn type_target_and_controller_state_string(&self, controller_states: &[&dyn ControllerStateTrait]) -> String {
+        let intra_run = controller_states[1].as_any().downcast_ref::<ControllerStateIntraRunPacing>().unwrap();
+        format!("{} / Time paced (Budget: {:.2} evenly in {} time buckets (base {}), average intra-run multiplier {:.4})",
+            self.bid_optimizer.get_optimizer_type(),
+            self.total_budget,
+            self.num_time_buckets,
+            self.converge_controller.controller_string(controller_states[0]),
+            intra_run.average_multiplier())
+    }
+
+    fn get_control_variables(&self, controller_states: &[&dyn ControllerStateTrait]) -> Vec<f64> {
+        vec![self.converge_controller.get_control_variable(controller_states[0])]
+    }
+
+    fn get_targets_actual_and_target(&self, controller_states: &[&dyn ControllerStateTrait], _campaign_stat: &CampaignStat) -> Vec<(String, f64, f64)> {
+        // Base pacing converges the average intra-run multiplier to 1.0
+        let intra_run = controller_states[1].as_any().downcast_ref::<ControllerStateIntraRunPacing>().unwrap();
+        vec![("Average intra-run multiplier".to_string(), intra_run.average_multiplier(), 1.0)]
+    }
+
+    fn perturb_controller_states(&self, controller_states: &mut [Box<dyn ControllerStateTrait>], factor: f64) {
+        let control_variable = self.converge_controller.get_control_variable(controller_states[0].as_ref());
+        self.converge_controller.set_control_variable(controller_states[0].as_mut(), control_variable * factor);
+    }
+
+    fn max_bid_cpm(&self) -> Option<f64> {
+        None
+    }
+
+    fn wants_bid_history(&self) -> bool {
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
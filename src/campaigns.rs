@@ -2,38 +2,104 @@ pub use crate::campaign_targets::CampaignTargetTrait;
 pub use crate::controller_state::ControllerStateTrait;
 pub use crate::campaign::CampaignTrait;
 pub use crate::campaign::CampaignGeneral;
+pub use crate::campaign_portfolio::CampaignPortfolio;
+pub use crate::campaign_learned_competition::CampaignLearnedCompetition;
+pub use crate::campaign_time_paced::CampaignTimePaced;
+pub use crate::campaign_daily_budget::CampaignDailyBudget;
+pub use crate::campaign_clearing_price::CampaignClearingPrice;
+pub use crate::campaign_coupled_double_target::CampaignCoupledDoubleTarget;
+pub use crate::campaign_lagrangian::{CampaignLagrangian, LagrangianConstraint, PenaltyFunction};
 pub use crate::campaign::BidValuerTrait;
-pub use crate::bid_valuers_double::BidValuerDualTarget;
-pub use crate::bid_optimizers::{BidOptimizerTrait, BidOptimizerTrutful, BidOptimizerMaximumMargin, BidOptimizerCheater, BidOptimizerMedian};
-pub use crate::bid_valuers_single::{BidValuerMultiplicative, BidValuerMultiplicative_AdditiveSupply, BidValuerMultiplicative_ExponentialSupply};
+pub use crate::bid_valuers_double::{BidValuerDualTarget, BidValuerMultiplicativeAdditive, BidValuerViewabilityConstrained, BidValuerCpmCeiling, BidValuerSellerSpendShare};
+pub use crate::bid_optimizers::{BidOptimizerTrait, BidOptimizerTrutful, BidOptimizerMaximumMargin, BidOptimizerMaximumMarginFloorAware, BidOptimizerMaximumMarginSoftFloor, BidOptimizerCheater, BidOptimizerMedian, BidOptimizerMinimumMargin, MinimumMargin};
+pub use crate::competition::WinRateMiscalibration;
+pub use crate::bid_valuers_single::{BidValuerMultiplicative, BidValuerMultiplicative_AdditiveSupply, BidValuerMultiplicative_ExponentialSupply, BidValuerViewability, BidValuerWeightedValue};
+pub use crate::impressions::NUM_VALUE_COMPONENTS;
+pub use crate::controllers::ControllerConfig;
+pub use crate::experiments::{Experiment, ExperimentSplit, ExperimentArm};
+use std::ops::Range;
 
 /// Campaign type determining the bidding strategy
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub enum CampaignType {
     MULTIPLICATIVE_PACING,
     MULTIPLICATIVE_ADDITIVE,
     CHEATER,
+    /// Cheater that is detected and excluded from an auction with detection_probability
+    CHEATER_DETECTABLE { detection_probability: f64 },
     MAX_MARGIN,
     MAX_MARGIN_ADDITIVE_SUPPLY,
     MAX_MARGIN_EXPONENTIAL_SUPPLY,
     MAX_MARGIN_DOUBLE_TARGET,
+    MULTIPLICATIVE_ADDITIVE_DOUBLE_TARGET,
+    MAX_MARGIN_VIEWABILITY,
+    /// Multiplicative pacing towards the first target, with a MIN_AVG_VIEWABILITY second target
+    /// kept by down-weighting low viewability impressions
+    MULTIPLICATIVE_VIEWABILITY_CONSTRAINED,
+    /// Multiplicative pacing towards the first target, with a MAX_AVG_CPM second target
+    /// kept by pulling bids towards the ceiling, away from high value (expensive) impressions
+    MULTIPLICATIVE_CPM_CEILING,
+    /// Multiplicative pacing towards the first target, with a MAX_SELLER_SPEND_SHARE second target
+    /// kept by a bid multiplier on the seller's impressions
+    MULTIPLICATIVE_SELLER_SPEND_SHARE,
+    /// Max margin on a weighted sum of value components (value, viewable value, quality value)
+    MAX_MARGIN_WEIGHTED_VALUE { weights: [f64; NUM_VALUE_COMPONENTS] },
+    MAX_MARGIN_FLOOR_AWARE,
+    MAX_MARGIN_SOFT_FLOOR,
     MEDIAN,
 }
 
 /// Convergence target determining what the campaign converges on
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub enum ConvergeTarget {
     TOTAL_BUDGET { target_total_budget: f64 },
     TOTAL_IMPRESSIONS { target_total_impressions: i32 },
     AVG_VALUE { avg_impression_value_to_campaign: f64 },
     NONE { default_pacing: f64 },
+    /// Same budget on each day of a multi-day simulation (see ImpressionsParam::with_days)
+    /// Each day is paced separately by a CampaignDailyBudget
+    DAILY_BUDGET { target_daily_budget: f64, num_days: usize },
+    /// Cost per conversion (spend / conversions), needs ImpressionsParam::with_conversions
+    CPA { target_cpa: f64 },
+    /// Minimum average viewability of won impressions, see MULTIPLICATIVE_VIEWABILITY_CONSTRAINED
+    MIN_AVG_VIEWABILITY { min_avg_viewability: f64 },
+    /// Margin on obtained value: (total_value - total_buyer_charge) / total_value, below 1.0
+    MARGIN { target_margin: f64 },
+    /// Value efficiency: total_value / total_buyer_charge (value is in CPM, so 1000.0 is value equal to spend)
+    VALUE_PER_SPEND { target_value_per_spend: f64 },
+    /// Maximum average CPM of won impressions (total_buyer_charge * 1000 / impressions), see MULTIPLICATIVE_CPM_CEILING
+    MAX_AVG_CPM { max_avg_cpm: f64 },
+    /// Share of the auctions the campaign bid in that it won: impressions_obtained / auctions_participated
+    WIN_RATE { target_win_rate: f64 },
+    /// Maximum share of spend going to one seller (from CampaignStat::seller_breakdown), see MULTIPLICATIVE_SELLER_SPEND_SHARE
+    MAX_SELLER_SPEND_SHARE { seller_id: usize, max_spend_share: f64 },
+}
+
+/// Rule for splitting the group value among campaigns of a value group when they bid
+///
+/// Campaigns in a value group value impressions the same, but the group can win each impression only
+/// once. The value of any non-empty coalition of campaigns is thus the full group value, and each
+/// campaign's Shapley value (its average marginal contribution) is an equal share of it.
+/// Weighted Shapley values give each campaign a share proportional to its weight.
+///
+/// Split only changes the value campaigns bid on, obtained value is still the full group value.
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueSplitRule {
+    /// Every campaign bids on the full group value
+    FULL,
+    /// Every campaign bids on its weighted Shapley share of the group value: value × weight / sum of weights
+    /// Weights are given in the order of campaign IDs of the group
+    SHAPLEY { weights: Vec<f64> },
 }
 
 
 // Re-export convergence target types for convenience
-pub use crate::campaign_targets::{CampaignTargetTotalImpressions, CampaignTargetTotalBudget, CampaignTargetAvgValue, CampaignTargetNone};
+pub use crate::campaign_targets::{CampaignTargetTotalImpressions, CampaignTargetTotalBudget, CampaignTargetAvgValue, CampaignTargetCpa, CampaignTargetMinAvgViewability, CampaignTargetMaxAvgCpm, CampaignTargetMaxSellerSpendShare, CampaignTargetWinRate, TargetTolerance, CampaignTargetMargin, CampaignTargetValuePerSpend, CampaignTargetNone};
 
 // Re-export bidder types for convenience
 
@@ -42,7 +108,29 @@ pub use crate::campaign_targets::{CampaignTargetTotalImpressions, CampaignTarget
 pub struct Campaigns {
     pub campaigns: Vec<Box<dyn CampaignTrait>>,
     pub value_groups: Vec<Vec<usize>>,
+    /// Value split rule of each value group (indexed like value_groups)
+    pub value_group_split_rules: Vec<ValueSplitRule>,
     pub campaign_to_value_group_mapping: Vec<usize>,
+    /// Share of the group value each campaign bids on (indexed by campaign_id), set by finalize_groups
+    pub campaign_value_share: Vec<f64>,
+    /// Maximum number of wins per user and day of each campaign (indexed by campaign_id, missing or None = no cap)
+    pub frequency_caps: Vec<Option<usize>>,
+    /// Audience segments each campaign bids on (indexed by campaign_id, missing or None = all segments)
+    pub segment_targets: Vec<Option<Vec<usize>>>,
+    /// Tolerance of each campaign's targets when validating results (indexed by campaign_id, then by target; missing = default)
+    pub target_tolerances: Vec<Vec<TargetTolerance>>,
+    /// A/B experiments splitting impressions into control and treatment buckets (indexed by experiment_id)
+    pub experiments: Vec<Experiment>,
+    /// Experiment and arm each campaign is assigned to (indexed by campaign_id, missing or None = not in an experiment)
+    pub experiment_arms: Vec<Option<(usize, ExperimentArm)>>,
+    /// Whether each campaign is paused, placing no bids (indexed by campaign_id, missing = not paused)
+    pub paused: Vec<bool>,
+    /// Budget each campaign can spend within a simulation run before it stops bidding (indexed by campaign_id,
+    /// missing or None = no cap)
+    pub budget_caps: Vec<Option<f64>>,
+    /// Days each campaign bids on, from its start day up to its end day (indexed by campaign_id, missing or
+    /// None = all days)
+    pub active_days: Vec<Option<Range<usize>>>,
 }
 
 impl Campaigns {
@@ -50,7 +138,17 @@ impl Campaigns {
         Self {
             campaigns: Vec::new(),
             value_groups: Vec::new(),
+            value_group_split_rules: Vec::new(),
             campaign_to_value_group_mapping: Vec::new(),
+            campaign_value_share: Vec::new(),
+            frequency_caps: Vec::new(),
+            segment_targets: Vec::new(),
+            target_tolerances: Vec::new(),
+            experiments: Vec::new(),
+            experiment_arms: Vec::new(),
+            paused: Vec::new(),
+            budget_caps: Vec::new(),
+            active_days: Vec::new(),
         }
     }
 
@@ -58,11 +156,13 @@ impl Campaigns {
     /// 
     /// # Arguments
     /// * `converge_target` - The convergence target to convert
+    /// * `controller_config` - Parameters of the target's controller (ignored by NONE targets)
     /// 
     /// # Returns
     /// A tuple of (converge_target_box, converge_controller)
     fn convert_converge_target(
         converge_target: ConvergeTarget,
+        controller_config: &ControllerConfig,
     ) -> (
         Box<dyn CampaignTargetTrait>,
         Box<dyn crate::controllers::ControllerTrait>,
@@ -73,7 +173,7 @@ impl Campaigns {
                     Box::new(CampaignTargetTotalImpressions {
                         total_impressions_target: target_total_impressions,
                     }),
-                    Box::new(crate::controllers::ControllerProportionalDerivative::new())
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(controller_config))
                 )
             }
             ConvergeTarget::TOTAL_BUDGET { target_total_budget } => {
@@ -81,7 +181,7 @@ impl Campaigns {
                     Box::new(CampaignTargetTotalBudget {
                         total_budget_target: target_total_budget,
                     }),
-                    Box::new(crate::controllers::ControllerProportionalDerivative::new())
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(controller_config))
                 )
             }
             ConvergeTarget::AVG_VALUE { avg_impression_value_to_campaign } => {
@@ -89,7 +189,68 @@ impl Campaigns {
                     Box::new(CampaignTargetAvgValue {
                         avg_impression_value_to_campaign: avg_impression_value_to_campaign,
                     }),
-                    Box::new(crate::controllers::ControllerProportionalDerivative::new())
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(controller_config))
+                )
+            }
+            ConvergeTarget::CPA { target_cpa } => {
+                (
+                    Box::new(CampaignTargetCpa {
+                        cpa_target: target_cpa,
+                    }),
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(controller_config))
+                )
+            }
+            ConvergeTarget::MIN_AVG_VIEWABILITY { min_avg_viewability } => {
+                (
+                    Box::new(CampaignTargetMinAvgViewability {
+                        min_avg_viewability,
+                    }),
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(controller_config))
+                )
+            }
+            ConvergeTarget::MAX_AVG_CPM { max_avg_cpm } => {
+                (
+                    Box::new(CampaignTargetMaxAvgCpm {
+                        max_avg_cpm,
+                    }),
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(controller_config))
+                )
+            }
+            ConvergeTarget::MAX_SELLER_SPEND_SHARE { seller_id, max_spend_share } => {
+                assert!(max_spend_share > 0.0 && max_spend_share <= 1.0, "Max seller spend share must be in (0, 1], got {}", max_spend_share);
+                (
+                    Box::new(CampaignTargetMaxSellerSpendShare {
+                        seller_id,
+                        max_spend_share,
+                    }),
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(controller_config))
+                )
+            }
+            ConvergeTarget::WIN_RATE { target_win_rate } => {
+                assert!(target_win_rate > 0.0 && target_win_rate <= 1.0, "Win rate target must be in (0, 1], got {}", target_win_rate);
+                (
+                    Box::new(CampaignTargetWinRate {
+                        win_rate_target: target_win_rate,
+                    }),
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(controller_config))
+                )
+            }
+            ConvergeTarget::MARGIN { target_margin } => {
+                assert!(target_margin < 1.0, "Margin target must be below 1.0, got {}", target_margin);
+                (
+                    Box::new(CampaignTargetMargin {
+                        margin_target: target_margin,
+                    }),
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(controller_config))
+                )
+            }
+            ConvergeTarget::VALUE_PER_SPEND { target_value_per_spend } => {
+                assert!(target_value_per_spend > 0.0, "Value per spend target must be positive, got {}", target_value_per_spend);
+                (
+                    Box::new(CampaignTargetValuePerSpend {
+                        value_per_spend_target: target_value_per_spend,
+                    }),
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(controller_config))
                 )
             }
             ConvergeTarget::NONE { default_pacing } => {
@@ -98,6 +259,9 @@ impl Campaigns {
                     Box::new(crate::controllers::ControllerConstant::new(default_pacing))
                 )
             }
+            ConvergeTarget::DAILY_BUDGET { .. } => {
+                panic!("DAILY_BUDGET target paces each day separately and can't be combined with other targets or converged by a single controller");
+            }
         }
     }
 
@@ -111,14 +275,41 @@ impl Campaigns {
     /// # Returns
     /// The campaign_id of the just added campaign
     pub fn add(&mut self, campaign_name: String, campaign_type: CampaignType, converge_targets: Vec<ConvergeTarget>) -> usize {
+        self.add_configured(campaign_name, campaign_type, converge_targets, Vec::new())
+    }
+
+    /// Add a campaign to the collection, configuring the controllers of its convergence targets
+    /// 
+    /// # Arguments
+    /// * `campaign_name` - Name of the campaign
+    /// * `campaign_type` - Type of campaign (bidding strategy)
+    /// * `converge_targets` - Vector of targets for convergence
+    /// * `controller_configs` - Controller parameters for each target, in the order of converge_targets
+    ///   (missing entries keep the campaign type's defaults)
+    /// 
+    /// # Returns
+    /// The campaign_id of the just added campaign
+    pub fn add_configured(&mut self, campaign_name: String, campaign_type: CampaignType, converge_targets: Vec<ConvergeTarget>, mut controller_configs: Vec<ControllerConfig>) -> usize {
         // No limit on number of campaigns
         let campaign_id = self.campaigns.len();
+        assert!(controller_configs.len() <= converge_targets.len(), "Got {} controller configs for {} converge targets", controller_configs.len(), converge_targets.len());
+        controller_configs.resize(converge_targets.len(), ControllerConfig::default());
+        
+        // Daily budget is paced per day by its own campaign implementation
+        if let [ConvergeTarget::DAILY_BUDGET { target_daily_budget, num_days }] = converge_targets.as_slice() {
+            let bid_optimizer = match campaign_type {
+                CampaignType::MULTIPLICATIVE_PACING => Box::new(BidOptimizerTrutful) as Box<dyn BidOptimizerTrait>,
+                CampaignType::MAX_MARGIN => Box::new(BidOptimizerMaximumMargin) as Box<dyn BidOptimizerTrait>,
+                _ => panic!("DAILY_BUDGET target is supported by MULTIPLICATIVE_PACING and MAX_MARGIN campaigns, got {:?}", campaign_type),
+            };
+            return self.add_advanced(Box::new(CampaignDailyBudget::new(campaign_name, *target_daily_budget, *num_days, bid_optimizer, &controller_configs[0])));
+        }
         
         // Create campaign based on campaign_type
         match campaign_type {
             CampaignType::MULTIPLICATIVE_PACING => {
                 assert_eq!(converge_targets.len(), 1, "MULTIPLICATIVE_PACING requires exactly one converge target");
-                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone());
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
                 let bid_valuer = Box::new(BidValuerMultiplicative) as Box<dyn BidValuerTrait>;
                 let bid_optimizer = Box::new(BidOptimizerTrutful) as Box<dyn BidOptimizerTrait>;
                 self.campaigns.push(Box::new(CampaignGeneral {
@@ -128,11 +319,15 @@ impl Campaigns {
                     converge_controllers: vec![converge_controller],
                     bid_valuer,
                     bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
                 }));
             }
             CampaignType::MULTIPLICATIVE_ADDITIVE => {
                 assert_eq!(converge_targets.len(), 1, "MULTIPLICATIVE_ADDITIVE requires exactly one converge target");
-                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone());
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
                 let bid_valuer = Box::new(BidValuerMultiplicative_AdditiveSupply) as Box<dyn BidValuerTrait>;
                 let bid_optimizer = Box::new(BidOptimizerTrutful) as Box<dyn BidOptimizerTrait>;
                 self.campaigns.push(Box::new(CampaignGeneral {
@@ -142,13 +337,35 @@ impl Campaigns {
                     converge_controllers: vec![converge_controller],
                     bid_valuer,
                     bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
                 }));
             }
             CampaignType::CHEATER => {
                 assert_eq!(converge_targets.len(), 1, "CHEATER requires exactly one converge target");
-                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone());
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
+                let bid_valuer = Box::new(BidValuerMultiplicative) as Box<dyn BidValuerTrait>;
+                let bid_optimizer = Box::new(BidOptimizerCheater { detection_probability: 0.0 }) as Box<dyn BidOptimizerTrait>;
+                self.campaigns.push(Box::new(CampaignGeneral {
+                    campaign_id,
+                    campaign_name,
+                    converge_targets: vec![converge_target_box],
+                    converge_controllers: vec![converge_controller],
+                    bid_valuer,
+                    bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
+                }));
+            }
+            CampaignType::CHEATER_DETECTABLE { detection_probability } => {
+                assert_eq!(converge_targets.len(), 1, "CHEATER_DETECTABLE requires exactly one converge target");
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
                 let bid_valuer = Box::new(BidValuerMultiplicative) as Box<dyn BidValuerTrait>;
-                let bid_optimizer = Box::new(BidOptimizerCheater) as Box<dyn BidOptimizerTrait>;
+                let bid_optimizer = Box::new(BidOptimizerCheater { detection_probability }) as Box<dyn BidOptimizerTrait>;
                 self.campaigns.push(Box::new(CampaignGeneral {
                     campaign_id,
                     campaign_name,
@@ -156,11 +373,15 @@ impl Campaigns {
                     converge_controllers: vec![converge_controller],
                     bid_valuer,
                     bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
                 }));
             }
             CampaignType::MAX_MARGIN => {
                 assert_eq!(converge_targets.len(), 1, "MAX_MARGIN requires exactly one converge target");
-                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone());
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
                 let bid_valuer = Box::new(BidValuerMultiplicative) as Box<dyn BidValuerTrait>;
                 let bid_optimizer = Box::new(BidOptimizerMaximumMargin) as Box<dyn BidOptimizerTrait>;
                 self.campaigns.push(Box::new(CampaignGeneral {
@@ -170,11 +391,15 @@ impl Campaigns {
                     converge_controllers: vec![converge_controller],
                     bid_valuer,
                     bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
                 }));
             }
             CampaignType::MAX_MARGIN_ADDITIVE_SUPPLY => {
                 assert_eq!(converge_targets.len(), 1, "MAX_MARGIN_ADDITIVE_SUPPLY requires exactly one converge target");
-                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone());
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
                 let bid_valuer = Box::new(BidValuerMultiplicative_AdditiveSupply) as Box<dyn BidValuerTrait>;
                 let bid_optimizer = Box::new(BidOptimizerMaximumMargin) as Box<dyn BidOptimizerTrait>;
                 self.campaigns.push(Box::new(CampaignGeneral {
@@ -184,11 +409,15 @@ impl Campaigns {
                     converge_controllers: vec![converge_controller],
                     bid_valuer,
                     bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
                 }));
             }
             CampaignType::MAX_MARGIN_EXPONENTIAL_SUPPLY => {
                 assert_eq!(converge_targets.len(), 1, "MAX_MARGIN_EXPONENTIAL_SUPPLY requires exactly one converge target");
-                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone());
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
                 let bid_valuer = Box::new(BidValuerMultiplicative_ExponentialSupply) as Box<dyn BidValuerTrait>;
                 let bid_optimizer = Box::new(BidOptimizerMaximumMargin) as Box<dyn BidOptimizerTrait>;
                 self.campaigns.push(Box::new(CampaignGeneral {
@@ -198,24 +427,28 @@ impl Campaigns {
                     converge_controllers: vec![converge_controller],
                     bid_valuer,
                     bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
                 }));
             }
             CampaignType::MAX_MARGIN_DOUBLE_TARGET => {
                 assert_eq!(converge_targets.len(), 2, "MAX_MARGIN_DOUBLE_TARGET requires exactly two converge targets");
                 let converge_targets_vec: Vec<Box<dyn CampaignTargetTrait>> = converge_targets.iter()
-                    .map(|ct| Self::convert_converge_target(ct.clone()).0)
+                    .map(|ct| Self::convert_converge_target(ct.clone(), &ControllerConfig::default()).0)
                     .collect();
                 let bid_valuer = Box::new(BidValuerDualTarget) as Box<dyn BidValuerTrait>;
                 let bid_optimizer = Box::new(BidOptimizerMaximumMargin) as Box<dyn BidOptimizerTrait>;
                 let converge_controllers = vec![
-                    Box::new(crate::controllers::ControllerProportionalDerivative::new()) as Box<dyn crate::controllers::ControllerTrait>,
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(&controller_configs[0])) as Box<dyn crate::controllers::ControllerTrait>,
                     Box::new(crate::controllers::ControllerProportionalDerivative::new_advanced(
                         0.005, // tolerance_fraction
                         0.03,   // max_adjustment_factor
                         0.03,   // proportional_gain
                         0.015,  // derivative_gain (half of proportional_gain)
                         true,   // rescaling (default)
-                    )) as Box<dyn crate::controllers::ControllerTrait>,
+                    ).with_config(&controller_configs[1])) as Box<dyn crate::controllers::ControllerTrait>,
                 ];
                 self.campaigns.push(Box::new(CampaignGeneral {
                     campaign_id,
@@ -224,12 +457,216 @@ impl Campaigns {
                     converge_controllers,
                     bid_valuer,
                     bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
+                }));
+            }
+            CampaignType::MULTIPLICATIVE_ADDITIVE_DOUBLE_TARGET => {
+                assert_eq!(converge_targets.len(), 2, "MULTIPLICATIVE_ADDITIVE_DOUBLE_TARGET requires exactly two converge targets");
+                // First target is converged by the multiplier, second by the additive boost
+                let (converge_target_multiplier, converge_controller_multiplier) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
+                let (converge_target_additive, _) = Self::convert_converge_target(converge_targets[1].clone(), &controller_configs[1]);
+                let bid_valuer = Box::new(BidValuerMultiplicativeAdditive) as Box<dyn BidValuerTrait>;
+                let bid_optimizer = Box::new(BidOptimizerTrutful) as Box<dyn BidOptimizerTrait>;
+                let converge_controllers = vec![
+                    converge_controller_multiplier,
+                    // The multiplier partly cancels out changes of the additive boost, so the boost needs a higher gain
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new_advanced(
+                        0.005, // tolerance_fraction
+                        0.2,   // max_adjustment_factor
+                        1.0,   // proportional_gain
+                        0.5,   // derivative_gain (half of proportional_gain)
+                        false, // rescaling
+                    ).with_config(&controller_configs[1])) as Box<dyn crate::controllers::ControllerTrait>,
+                ];
+                self.campaigns.push(Box::new(CampaignGeneral {
+                    campaign_id,
+                    campaign_name,
+                    converge_targets: vec![converge_target_multiplier, converge_target_additive],
+                    converge_controllers,
+                    bid_valuer,
+                    bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
+                }));
+            }
+            CampaignType::MULTIPLICATIVE_VIEWABILITY_CONSTRAINED => {
+                assert_eq!(converge_targets.len(), 2, "MULTIPLICATIVE_VIEWABILITY_CONSTRAINED requires exactly two converge targets");
+                assert!(matches!(converge_targets[1], ConvergeTarget::MIN_AVG_VIEWABILITY { .. }), "MULTIPLICATIVE_VIEWABILITY_CONSTRAINED requires MIN_AVG_VIEWABILITY as the second converge target");
+                // First target is converged by pacing, the viewability constraint by the viewability penalty
+                let (converge_target_pacing, converge_controller_pacing) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
+                // Penalty of 1.0 doesn't down-weight anything, so it rests there while the constraint has slack
+                let mut penalty_config = controller_configs[1].clone();
+                penalty_config.min_control_variable = penalty_config.min_control_variable.or(Some(1.0));
+                let (converge_target_viewability, _) = Self::convert_converge_target(converge_targets[1].clone(), &penalty_config);
+                let bid_valuer = Box::new(BidValuerViewabilityConstrained) as Box<dyn BidValuerTrait>;
+                let bid_optimizer = Box::new(BidOptimizerTrutful) as Box<dyn BidOptimizerTrait>;
+                let converge_controllers = vec![
+                    converge_controller_pacing,
+                    // Average viewability responds weakly to the penalty, so the penalty needs a higher gain
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new_advanced(
+                        0.005, // tolerance_fraction
+                        0.2,   // max_adjustment_factor
+                        1.0,   // proportional_gain
+                        0.5,   // derivative_gain (half of proportional_gain)
+                        true,  // rescaling (default)
+                    ).with_config(&penalty_config)) as Box<dyn crate::controllers::ControllerTrait>,
+                ];
+                self.campaigns.push(Box::new(CampaignGeneral {
+                    campaign_id,
+                    campaign_name,
+                    converge_targets: vec![converge_target_pacing, converge_target_viewability],
+                    converge_controllers,
+                    bid_valuer,
+                    bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
+                }));
+            }
+            CampaignType::MULTIPLICATIVE_CPM_CEILING => {
+                assert_eq!(converge_targets.len(), 2, "MULTIPLICATIVE_CPM_CEILING requires exactly two converge targets");
+                assert!(matches!(converge_targets[1], ConvergeTarget::MAX_AVG_CPM { .. }), "MULTIPLICATIVE_CPM_CEILING requires MAX_AVG_CPM as the second converge target");
+                // First target is converged by pacing, the CPM ceiling by the exponent
+                let (converge_target_pacing, converge_controller_pacing) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
+                // Exponent of 1.0 bids on plain paced value, so it rests there while the ceiling has slack
+                let mut exponent_config = controller_configs[1].clone();
+                exponent_config.max_control_variable = exponent_config.max_control_variable.or(Some(1.0));
+                let (converge_target_cpm, _) = Self::convert_converge_target(converge_targets[1].clone(), &exponent_config);
+                let bid_valuer = Box::new(BidValuerCpmCeiling) as Box<dyn BidValuerTrait>;
+                let bid_optimizer = Box::new(BidOptimizerTrutful) as Box<dyn BidOptimizerTrait>;
+                let converge_controllers = vec![
+                    converge_controller_pacing,
+                    // Average CPM responds weakly to the exponent, so the exponent needs a higher gain
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new_advanced(
+                        0.005, // tolerance_fraction
+                        0.2,   // max_adjustment_factor
+                        1.0,   // proportional_gain
+                        0.5,   // derivative_gain (half of proportional_gain)
+                        true,  // rescaling (default)
+                    ).with_config(&exponent_config)) as Box<dyn crate::controllers::ControllerTrait>,
+                ];
+                self.campaigns.push(Box::new(CampaignGeneral {
+                    campaign_id,
+                    campaign_name,
+                    converge_targets: vec![converge_target_pacing, converge_target_cpm],
+                    converge_controllers,
+                    bid_valuer,
+                    bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
+                }));
+            }
+            CampaignType::MULTIPLICATIVE_SELLER_SPEND_SHARE => {
+                assert_eq!(converge_targets.len(), 2, "MULTIPLICATIVE_SELLER_SPEND_SHARE requires exactly two converge targets");
+                let seller_id = match converge_targets[1] {
+                    ConvergeTarget::MAX_SELLER_SPEND_SHARE { seller_id, .. } => seller_id,
+                    _ => panic!("MULTIPLICATIVE_SELLER_SPEND_SHARE requires MAX_SELLER_SPEND_SHARE as the second converge target"),
+                };
+                // First target is converged by pacing, the spend share by the seller's bid multiplier
+                let (converge_target_pacing, converge_controller_pacing) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
+                // Multiplier of 1.0 bids on plain paced value, so it rests there while the share has slack
+                let mut multiplier_config = controller_configs[1].clone();
+                multiplier_config.max_control_variable = multiplier_config.max_control_variable.or(Some(1.0));
+                let (converge_target_share, converge_controller_share) = Self::convert_converge_target(converge_targets[1].clone(), &multiplier_config);
+                let bid_valuer = Box::new(BidValuerSellerSpendShare { seller_id }) as Box<dyn BidValuerTrait>;
+                let bid_optimizer = Box::new(BidOptimizerTrutful) as Box<dyn BidOptimizerTrait>;
+                self.campaigns.push(Box::new(CampaignGeneral {
+                    campaign_id,
+                    campaign_name,
+                    converge_targets: vec![converge_target_pacing, converge_target_share],
+                    converge_controllers: vec![converge_controller_pacing, converge_controller_share],
+                    bid_valuer,
+                    bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
+                }));
+            }
+            CampaignType::MAX_MARGIN_VIEWABILITY => {
+                assert_eq!(converge_targets.len(), 1, "MAX_MARGIN_VIEWABILITY requires exactly one converge target");
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
+                let bid_valuer = Box::new(BidValuerViewability) as Box<dyn BidValuerTrait>;
+                let bid_optimizer = Box::new(BidOptimizerMaximumMargin) as Box<dyn BidOptimizerTrait>;
+                self.campaigns.push(Box::new(CampaignGeneral {
+                    campaign_id,
+                    campaign_name,
+                    converge_targets: vec![converge_target_box],
+                    converge_controllers: vec![converge_controller],
+                    bid_valuer,
+                    bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
+                }));
+            }
+            CampaignType::MAX_MARGIN_WEIGHTED_VALUE { weights } => {
+                assert_eq!(converge_targets.len(), 1, "MAX_MARGIN_WEIGHTED_VALUE requires exactly one converge target");
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
+                let bid_valuer = Box::new(BidValuerWeightedValue { weights }) as Box<dyn BidValuerTrait>;
+                let bid_optimizer = Box::new(BidOptimizerMaximumMargin) as Box<dyn BidOptimizerTrait>;
+                self.campaigns.push(Box::new(CampaignGeneral {
+                    campaign_id,
+                    campaign_name,
+                    converge_targets: vec![converge_target_box],
+                    converge_controllers: vec![converge_controller],
+                    bid_valuer,
+                    bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
+                }));
+            }
+            CampaignType::MAX_MARGIN_FLOOR_AWARE => {
+                assert_eq!(converge_targets.len(), 1, "MAX_MARGIN_FLOOR_AWARE requires exactly one converge target");
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
+                let bid_valuer = Box::new(BidValuerMultiplicative) as Box<dyn BidValuerTrait>;
+                let bid_optimizer = Box::new(BidOptimizerMaximumMarginFloorAware) as Box<dyn BidOptimizerTrait>;
+                self.campaigns.push(Box::new(CampaignGeneral {
+                    campaign_id,
+                    campaign_name,
+                    converge_targets: vec![converge_target_box],
+                    converge_controllers: vec![converge_controller],
+                    bid_valuer,
+                    bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
+                }));
+            }
+            CampaignType::MAX_MARGIN_SOFT_FLOOR => {
+                assert_eq!(converge_targets.len(), 1, "MAX_MARGIN_SOFT_FLOOR requires exactly one converge target");
+                let (converge_target_box, converge_controller) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
+                let bid_valuer = Box::new(BidValuerMultiplicative) as Box<dyn BidValuerTrait>;
+                let bid_optimizer = Box::new(BidOptimizerMaximumMarginSoftFloor) as Box<dyn BidOptimizerTrait>;
+                self.campaigns.push(Box::new(CampaignGeneral {
+                    campaign_id,
+                    campaign_name,
+                    converge_targets: vec![converge_target_box],
+                    converge_controllers: vec![converge_controller],
+                    bid_valuer,
+                    bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
                 }));
             }
             CampaignType::MEDIAN => {
                 assert_eq!(converge_targets.len(), 1, "MEDIAN requires exactly one converge target");
-                let (converge_target_box, _) = Self::convert_converge_target(converge_targets[0].clone());
-                let converge_controller = Box::new(crate::controllers::ControllerProportionalDerivative::new()) as Box<dyn crate::controllers::ControllerTrait>;
+                let (converge_target_box, _) = Self::convert_converge_target(converge_targets[0].clone(), &controller_configs[0]);
+                let converge_controller = Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(&controller_configs[0])) as Box<dyn crate::controllers::ControllerTrait>;
                 let bid_valuer = Box::new(BidValuerMultiplicative) as Box<dyn BidValuerTrait>;
                 let bid_optimizer = Box::new(BidOptimizerMedian) as Box<dyn BidOptimizerTrait>;
                 self.campaigns.push(Box::new(CampaignGeneral {
@@ -239,6 +676,10 @@ impl Campaigns {
                     converge_controllers: vec![converge_controller],
                     bid_valuer,
                     bid_optimizer,
+                    max_bid_cpm: None,
+                    win_rate_miscalibration: None,
+                    censored_competition: false,
+                    target_priorities: None,
                 }));
             }
         }
@@ -255,8 +696,33 @@ impl Campaigns {
     /// Panics if any campaign_id is invalid (not between 0 and num_campaigns) or
     /// if any campaign is already in another group
     pub fn create_value_group(&mut self, campaign_ids: Vec<usize>) {
+        self.create_value_group_with_split(campaign_ids, ValueSplitRule::FULL);
+    }
+    
+    /// Create a value group whose campaigns bid on a split of the group value
+    /// 
+    /// # Arguments
+    /// * `campaign_ids` - Vector of campaign IDs to add as a group
+    /// * `split_rule` - How the group value is split among the campaigns when bidding
+    /// 
+    /// # Panics
+    /// Panics in the same cases as create_value_group, or if Shapley weights don't match
+    /// the campaigns of the group or are not positive
+    pub fn create_value_group_with_split(&mut self, campaign_ids: Vec<usize>, split_rule: ValueSplitRule) {
         let num_campaigns = self.campaigns.len();
         
+        if let ValueSplitRule::SHAPLEY { weights } = &split_rule {
+            if weights.len() != campaign_ids.len() {
+                panic!(
+                    "Shapley split needs one weight per campaign: got {} weights for {} campaigns",
+                    weights.len(), campaign_ids.len()
+                );
+            }
+            if weights.iter().any(|&weight| weight <= 0.0) {
+                panic!("Shapley split weights must be positive: {:?}", weights);
+            }
+        }
+        
         // Check that all campaign IDs are valid
         for &campaign_id in &campaign_ids {
             if campaign_id >= num_campaigns {
@@ -280,6 +746,7 @@ impl Campaigns {
         }
         
         self.value_groups.push(campaign_ids);
+        self.value_group_split_rules.push(split_rule);
     }
     
     /// Finalize group mappings for all campaigns
@@ -306,10 +773,271 @@ impl Campaigns {
             if self.campaign_to_value_group_mapping[campaign_id] == usize::MAX {
                 // Create a new group containing just this campaign
                 self.value_groups.push(vec![campaign_id]);
+                self.value_group_split_rules.push(ValueSplitRule::FULL);
                 // The group index is the last index in value_groups (length - 1)
                 self.campaign_to_value_group_mapping[campaign_id] = self.value_groups.len() - 1;
             }
         }
+        
+        // Third pass: resolve the share of the group value each campaign bids on
+        self.campaign_value_share = vec![1.0; num_campaigns];
+        for (group, split_rule) in self.value_groups.iter().zip(&self.value_group_split_rules) {
+            if let ValueSplitRule::SHAPLEY { weights } = split_rule {
+                let total_weight: f64 = weights.iter().sum();
+                for (&campaign_id, weight) in group.iter().zip(weights) {
+                    self.campaign_value_share[campaign_id] = weight / total_weight;
+                }
+            }
+        }
+    }
+    
+    /// Set a maximum CPM bid cap on a campaign
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign to cap
+    /// * `max_bid_cpm` - Maximum CPM the campaign will bid, enforced after the bid optimizer
+    /// 
+    /// # Panics
+    /// Panics if the campaign is not a CampaignGeneral
+    pub fn set_max_bid_cpm(&mut self, campaign_id: usize, max_bid_cpm: f64) {
+        let campaign_general = self.campaigns[campaign_id].as_mut().as_any_mut().downcast_mut::<CampaignGeneral>()
+            .expect("Max bid CPM can only be set on CampaignGeneral campaigns");
+        campaign_general.max_bid_cpm = Some(max_bid_cpm);
+    }
+    
+    /// Set a frequency cap on a campaign
+    /// Once the campaign has won max_wins_per_user impressions of a user on a day, its bids on further
+    /// impressions of that user on that day are dropped. Impressions need users (ImpressionsParam::with_users)
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign to cap
+    /// * `max_wins_per_user` - Maximum number of impressions the campaign wins per user and day
+    pub fn set_frequency_cap(&mut self, campaign_id: usize, max_wins_per_user: usize) {
+        assert!(campaign_id < self.campaigns.len(), "No campaign with ID {}", campaign_id);
+        assert!(max_wins_per_user > 0, "Frequency cap must allow at least one win per user");
+        if self.frequency_caps.len() <= campaign_id {
+            self.frequency_caps.resize(campaign_id + 1, None);
+        }
+        self.frequency_caps[campaign_id] = Some(max_wins_per_user);
+    }
+    
+    /// Get the frequency cap of a campaign (None = no cap)
+    pub fn frequency_cap(&self, campaign_id: usize) -> Option<usize> {
+        self.frequency_caps.get(campaign_id).copied().flatten()
+    }
+    
+    /// Target a campaign at audience segments
+    /// The campaign is only eligible for impressions in one of the segments, its bids on impressions of other
+    /// segments are dropped before the auction. Impressions need segments (ImpressionsParam::with_segments)
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign to target
+    /// * `segments` - Segments the campaign bids on
+    pub fn set_segment_targeting(&mut self, campaign_id: usize, segments: Vec<usize>) {
+        assert!(campaign_id < self.campaigns.len(), "No campaign with ID {}", campaign_id);
+        assert!(!segments.is_empty(), "Segment targeting must include at least one segment");
+        if self.segment_targets.len() <= campaign_id {
+            self.segment_targets.resize(campaign_id + 1, None);
+        }
+        self.segment_targets[campaign_id] = Some(segments);
+    }
+    
+    /// Get the segments a campaign is targeted at (None = all segments)
+    pub fn segment_targeting(&self, campaign_id: usize) -> Option<&[usize]> {
+        self.segment_targets.get(campaign_id).and_then(|segments| segments.as_deref())
+    }
+    
+    /// Whether a campaign is eligible for an impression of the given segment (None = segments are not modeled)
+    pub fn is_eligible(&self, campaign_id: usize, segment: Option<usize>) -> bool {
+        match (self.segment_targeting(campaign_id), segment) {
+            (None, _) => true,
+            (Some(segments), Some(segment)) => segments.contains(&segment),
+            (Some(_), None) => panic!("Segment targeting needs segments, see ImpressionsParam::with_segments"),
+        }
+    }
+    
+    /// Add an A/B experiment splitting impressions into a control and a treatment bucket
+    /// 
+    /// # Arguments
+    /// * `name` - Name of the experiment
+    /// * `split` - Whether impressions are assigned to buckets by their user or individually
+    /// * `treatment_share` - Share of the users or impressions in the treatment bucket
+    /// 
+    /// # Returns
+    /// The experiment_id, to assign campaigns to its arms with set_experiment_arm
+    pub fn add_experiment(&mut self, name: &str, split: ExperimentSplit, treatment_share: f64) -> usize {
+        let experiment_id = self.experiments.len();
+        self.experiments.push(Experiment::new(name, split, treatment_share, experiment_id));
+        experiment_id
+    }
+    
+    /// Assign a campaign to an arm of an experiment
+    /// The campaign is only eligible for impressions in its arm's bucket, its bids on impressions of the other
+    /// bucket are dropped before the auction. Campaigns of both arms run simultaneously, their results per arm are
+    /// collected in SimulationStat::experiment_stats
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign to assign
+    /// * `experiment_id` - ID of the experiment (see add_experiment)
+    /// * `arm` - Arm the campaign runs in
+    pub fn set_experiment_arm(&mut self, campaign_id: usize, experiment_id: usize, arm: ExperimentArm) {
+        assert!(campaign_id < self.campaigns.len(), "No campaign with ID {}", campaign_id);
+        assert!(experiment_id < self.experiments.len(), "No experiment with ID {}", experiment_id);
+        if self.experiment_arms.len() <= campaign_id {
+            self.experiment_arms.resize(campaign_id + 1, None);
+        }
+        self.experiment_arms[campaign_id] = Some((experiment_id, arm));
+    }
+    
+    /// Get the experiment and arm a campaign is assigned to (None = not in an experiment)
+    pub fn experiment_arm(&self, campaign_id: usize) -> Option<(usize, ExperimentArm)> {
+        self.experiment_arms.get(campaign_id).copied().flatten()
+    }
+    
+    /// Pause or resume a campaign
+    /// A paused campaign keeps its ID and controller states but its bids are dropped before the auction, as if it
+    /// weren't in the marketplace (e.g. to replay the marketplace without it, see counterfactual.rs)
+    pub fn set_paused(&mut self, campaign_id: usize, paused: bool) {
+        assert!(campaign_id < self.campaigns.len(), "No campaign with ID {}", campaign_id);
+        if self.paused.len() <= campaign_id {
+            self.paused.resize(campaign_id + 1, false);
+        }
+        self.paused[campaign_id] = paused;
+    }
+    
+    /// Whether a campaign is paused
+    pub fn is_paused(&self, campaign_id: usize) -> bool {
+        self.paused.get(campaign_id).copied().unwrap_or(false)
+    }
+    
+    /// Set a hard budget cap on a campaign
+    /// Impressions are auctioned in timestamp order, and once the campaign's spend within the run has reached
+    /// the budget its bids on the remaining impressions are dropped. The impression that exhausts the budget is
+    /// charged in full, so the spend can exceed the budget by at most one impression's charge
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign to cap
+    /// * `budget` - Budget the campaign spends at most within a run (in the units of the buyer charge)
+    pub fn set_budget_cap(&mut self, campaign_id: usize, budget: f64) {
+        assert!(campaign_id < self.campaigns.len(), "No campaign with ID {}", campaign_id);
+        assert!(budget > 0.0, "Budget cap must be positive");
+        if self.budget_caps.len() <= campaign_id {
+            self.budget_caps.resize(campaign_id + 1, None);
+        }
+        self.budget_caps[campaign_id] = Some(budget);
+    }
+    
+    /// Get the budget cap of a campaign (None = no cap)
+    pub fn budget_cap(&self, campaign_id: usize) -> Option<f64> {
+        self.budget_caps.get(campaign_id).copied().flatten()
+    }
+    
+    /// Set the days a campaign is active, launching it mid-flight and/or ending it before the flight ends
+    /// Its bids on impressions of other days are dropped before the auction. In a multi-period run
+    /// (SimulationConverge::run_periods) its targets are shared between its active periods only, and its
+    /// controllers are left as they are while it is inactive
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign
+    /// * `start_day` - First day the campaign bids on
+    /// * `end_day` - Day the campaign stops bidding on (exclusive, None = it runs until the end of the flight)
+    pub fn set_active_days(&mut self, campaign_id: usize, start_day: usize, end_day: Option<usize>) {
+        assert!(campaign_id < self.campaigns.len(), "No campaign with ID {}", campaign_id);
+        let end_day = end_day.unwrap_or(usize::MAX);
+        assert!(start_day < end_day, "Campaign must be active on at least one day");
+        if self.active_days.len() <= campaign_id {
+            self.active_days.resize(campaign_id + 1, None);
+        }
+        self.active_days[campaign_id] = Some(start_day..end_day);
+    }
+    
+    /// Get the days a campaign is active (None = all days)
+    pub fn active_days(&self, campaign_id: usize) -> Option<Range<usize>> {
+        self.active_days.get(campaign_id).cloned().flatten()
+    }
+    
+    /// Whether a campaign bids on impressions of the given day
+    pub fn is_active(&self, campaign_id: usize, day: usize) -> bool {
+        self.active_days(campaign_id).is_none_or(|active_days| active_days.contains(&day))
+    }
+    
+    /// Set the tolerance bands within which a campaign's targets count as met (see SimulationStat::campaign_targets)
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign
+    /// * `target_tolerances` - Tolerance of each target, in order of the targets (further targets keep the default)
+    pub fn set_target_tolerances(&mut self, campaign_id: usize, target_tolerances: Vec<TargetTolerance>) {
+        assert!(campaign_id < self.campaigns.len(), "No campaign with ID {}", campaign_id);
+        if self.target_tolerances.len() <= campaign_id {
+            self.target_tolerances.resize(campaign_id + 1, Vec::new());
+        }
+        self.target_tolerances[campaign_id] = target_tolerances;
+    }
+    
+    /// Get the tolerance band of a campaign's target (TargetTolerance::default() unless set)
+    pub fn target_tolerance(&self, campaign_id: usize, target_index: usize) -> TargetTolerance {
+        self.target_tolerances.get(campaign_id).and_then(|tolerances| tolerances.get(target_index)).copied().unwrap_or_default()
+    }
+    
+    /// Set a miscalibration of the win rate prediction on a campaign
+    /// Only the campaign's bid optimizer sees the miscalibrated prediction, actual competition is unchanged
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign to miscalibrate
+    /// * `win_rate_miscalibration` - Bias and noise applied to predicted sigmoid offset and scale
+    /// 
+    /// # Panics
+    /// Panics if the campaign is not a CampaignGeneral
+    pub fn set_win_rate_miscalibration(&mut self, campaign_id: usize, win_rate_miscalibration: WinRateMiscalibration) {
+        let campaign_general = self.campaigns[campaign_id].as_mut().as_any_mut().downcast_mut::<CampaignGeneral>()
+            .expect("Win rate miscalibration can only be set on CampaignGeneral campaigns");
+        campaign_general.win_rate_miscalibration = Some(win_rate_miscalibration);
+    }
+    
+    /// Make a campaign observe the competition only through the wins and losses of its own bids
+    /// The provided win rate prediction is ignored; instead a logistic regression of win/loss on the bid and the
+    /// base impression value is fitted on the campaign's bid history accumulated across iterations, and its
+    /// sigmoid is used as the prediction seen by the bid optimizer
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign learning the competition
+    /// 
+    /// # Panics
+    /// Panics if the campaign is not a CampaignGeneral
+    pub fn set_censored_competition(&mut self, campaign_id: usize) {
+        let campaign_general = self.campaigns[campaign_id].as_mut().as_any_mut().downcast_mut::<CampaignGeneral>()
+            .expect("Censored competition can only be set on CampaignGeneral campaigns");
+        campaign_general.censored_competition = true;
+    }
+    
+    /// Converge a campaign's targets lexicographically by priority
+    /// A target's controller only adjusts once the controllers of all higher priority targets settled,
+    /// so lower priority targets converge within the slack left by higher priority ones
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign
+    /// * `target_priorities` - Priority of each converge target, in order of the targets (0 = highest)
+    pub fn set_target_priorities(&mut self, campaign_id: usize, target_priorities: Vec<usize>) {
+        let campaign_general = self.campaigns[campaign_id].as_mut().as_any_mut().downcast_mut::<CampaignGeneral>()
+            .expect("Target priorities can only be set on CampaignGeneral campaigns");
+        assert_eq!(target_priorities.len(), campaign_general.converge_targets.len(), "Campaign '{}' needs a priority for each of its {} targets", campaign_general.campaign_name, campaign_general.converge_targets.len());
+        campaign_general.target_priorities = Some(target_priorities);
+    }
+    
+    /// Require a minimum margin per impression from a campaign
+    /// The campaign's bid optimizer is wrapped, so the constraint composes with any optimizer
+    /// 
+    /// # Arguments
+    /// * `campaign_id` - ID of the campaign to constrain
+    /// * `minimum_margin` - Margin below which the campaign abstains from bidding
+    /// 
+    /// # Panics
+    /// Panics if the campaign is not a CampaignGeneral
+    pub fn set_minimum_margin(&mut self, campaign_id: usize, minimum_margin: MinimumMargin) {
+        let campaign_general = self.campaigns[campaign_id].as_mut().as_any_mut().downcast_mut::<CampaignGeneral>()
+            .expect("Minimum margin can only be set on CampaignGeneral campaigns");
+        let bid_optimizer = std::mem::replace(&mut campaign_general.bid_optimizer, Box::new(BidOptimizerTrutful));
+        campaign_general.bid_optimizer = Box::new(BidOptimizerMinimumMargin { bid_optimizer, minimum_margin });
     }
     
     /// Add a campaign using an advanced method that accepts a pre-constructed CampaignTrait
@@ -322,9 +1050,23 @@ impl Campaigns {
     pub fn add_advanced(&mut self, mut campaign: Box<dyn CampaignTrait>) -> usize {
         let campaign_id = self.campaigns.len();
         
-        // Try to downcast to CampaignGeneral to set the campaign_id
+        // Try to downcast to known campaign implementations to set the campaign_id
         if let Some(campaign_general) = campaign.as_mut().as_any_mut().downcast_mut::<CampaignGeneral>() {
             campaign_general.campaign_id = campaign_id;
+        } else if let Some(campaign_portfolio) = campaign.as_mut().as_any_mut().downcast_mut::<CampaignPortfolio>() {
+            campaign_portfolio.campaign_id = campaign_id;
+        } else if let Some(campaign_learned) = campaign.as_mut().as_any_mut().downcast_mut::<CampaignLearnedCompetition>() {
+            campaign_learned.campaign_id = campaign_id;
+        } else if let Some(campaign_time_paced) = campaign.as_mut().as_any_mut().downcast_mut::<CampaignTimePaced>() {
+            campaign_time_paced.campaign_id = campaign_id;
+        } else if let Some(campaign_daily_budget) = campaign.as_mut().as_any_mut().downcast_mut::<CampaignDailyBudget>() {
+            campaign_daily_budget.campaign_id = campaign_id;
+        } else if let Some(campaign_clearing_price) = campaign.as_mut().as_any_mut().downcast_mut::<CampaignClearingPrice>() {
+            campaign_clearing_price.campaign_id = campaign_id;
+        } else if let Some(campaign_coupled) = campaign.as_mut().as_any_mut().downcast_mut::<CampaignCoupledDoubleTarget>() {
+            campaign_coupled.campaign_id = campaign_id;
+        } else if let Some(campaign_lagrangian) = campaign.as_mut().as_any_mut().downcast_mut::<CampaignLagrangian>() {
+            campaign_lagrangian.campaign_id = campaign_id;
         }
         
         self.campaigns.push(campaign);
@@ -336,6 +1078,65 @@ impl Campaigns {
 mod tests {
     use super::*;
     use crate::controllers::ControllerStateSingleVariable;
+    use crate::impressions::Impression;
+    use crate::competition::ImpressionCompetition;
+    use crate::simulationrun::CampaignStat;
+
+    /// Campaign statistics with the given impressions and buyer charge, everything else zero
+    fn campaign_stat(impressions_obtained: f64, total_buyer_charge: f64) -> CampaignStat {
+        CampaignStat {
+            impressions_obtained,
+            total_supply_cost: 0.0,
+            total_virtual_cost: 0.0,
+            total_buyer_charge,
+            total_value: 0.0,
+            total_viewable_value: 0.0,
+            total_viewability: 0.0,
+            total_measured_viewability: 0.0,
+            total_conversions: 0.0,
+            total_conversion_value: 0.0,
+            capped_bids: 0,
+            stale_bids: 0,
+            frequency_capped_auctions: 0,
+            budget_capped_auctions: 0,
+            budget_exhausted_at: None,
+            eligible_impressions: None,
+            auctions_participated: 0,
+            weighted_auctions_participated: 0.0,
+            reach: 0,
+            soft_target_penalty: None,
+            seller_breakdown: Vec::new(),
+            day_breakdown: Vec::new(),
+            hour_breakdown: Vec::new(),
+            bid_history: Vec::new(),
+        }
+    }
+
+    /// Impression of seller 0 with the given value to the campaign, without competition, floors or the other
+    /// attributes modeled
+    fn impression(value_to_campaign: f64) -> Impression {
+        Impression {
+            seller_id: 0,
+            competition: None,
+            floor_cpm: 0.0,
+            soft_floor_cpm: None,
+            value_to_campaign_group: vec![value_to_campaign],
+            base_impression_value: value_to_campaign,
+            viewability: 1.0,
+            measured_viewability: 1.0,
+            timestamp: 0.0,
+            quality_score: 1.0,
+            conversion_probability: 0.0,
+            conversion_value: 0.0,
+            converted: false,
+            user_id: None,
+            segment: None,
+            device_type: None,
+            geo_tier: None,
+            weight: 1.0,
+            supply_id: None,
+        }
+    }
 
     #[test]
     fn test_get_bid() {
@@ -348,9 +1149,13 @@ mod tests {
             converge_targets: vec![Box::new(CampaignTargetTotalImpressions {
                 total_impressions_target: 1000,
             })],
-            converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(1.0))],
+            converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(0.5))],
             bid_valuer,
             bid_optimizer,
+            max_bid_cpm: None,
+            win_rate_miscalibration: None,
+            censored_competition: false,
+            target_priorities: None,
         };
 
         // Create a campaign converge with pacing = 0.5
@@ -359,21 +1164,7 @@ mod tests {
         });
 
         // Create an impression with value_to_campaign_group[0] = 20.0
-        let value_to_campaign_group = vec![20.0];
-
-        let impression = Impression {
-            seller_id: 0,
-            competition: Some(crate::competition::ImpressionCompetition {
-                bid_cpm: 0.0,
-                win_rate_actual_sigmoid_offset: 0.0,
-                win_rate_actual_sigmoid_scale: 0.0,
-                win_rate_prediction_sigmoid_offset: 0.0,
-                win_rate_prediction_sigmoid_scale: 0.0,
-            }),
-            floor_cpm: 0.0,
-            value_to_campaign_group,
-            base_impression_value: 10.0,
-        };
+        let impression = impression(20.0);
 
         // Expected bid = 0.5 * 20.0 * 1.0 = 10.0
         let mut logger = crate::logger::Logger::new();
@@ -396,6 +1187,10 @@ mod tests {
             converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(1.0))],
             bid_valuer,
             bid_optimizer,
+            max_bid_cpm: None,
+            win_rate_miscalibration: None,
+            censored_competition: false,
+            target_priorities: None,
         };
 
         // Create a campaign converge with pacing = 1.0
@@ -404,25 +1199,11 @@ mod tests {
         });
 
         // Create an impression with value_to_campaign_group[0] = 15.0
-        let value_to_campaign_group = vec![15.0];
-
-        let impression = Impression {
-            seller_id: 1,
-            competition: Some(crate::competition::ImpressionCompetition {
-                bid_cpm: 0.0,
-                win_rate_actual_sigmoid_offset: 0.0,
-                win_rate_actual_sigmoid_scale: 0.0,
-                win_rate_prediction_sigmoid_offset: 0.0,
-                win_rate_prediction_sigmoid_scale: 0.0,
-            }),
-            floor_cpm: 0.0,
-            value_to_campaign_group,
-            base_impression_value: 10.0,
-        };
+        let impression = Impression { seller_id: 1, ..impression(15.0) };
 
         // Expected bid = 1.0 * 15.0 * 1.0 = 15.0
         let mut logger = crate::logger::Logger::new();
-        let bid = campaign.get_bid(&impression, campaign_converge.as_ref(), 1.0, 15.0, &mut logger);
+        let bid = campaign.get_bid(&impression, &[campaign_converge.as_ref()], 1.0, 15.0, &mut logger);
         assert_eq!(bid, Some(15.0));
     }
 
@@ -437,9 +1218,13 @@ mod tests {
             converge_targets: vec![Box::new(CampaignTargetTotalImpressions {
                 total_impressions_target: 1000,
             })],
-            converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(1.0))],
+            converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(0.0))],
             bid_valuer,
             bid_optimizer,
+            max_bid_cpm: None,
+            win_rate_miscalibration: None,
+            censored_competition: false,
+            target_priorities: None,
         };
 
         // Create a campaign converge with pacing = 0.0
@@ -448,28 +1233,246 @@ mod tests {
         });
 
         // Create an impression with value_to_campaign_group[0] = 100.0
-        let value_to_campaign_group = vec![100.0];
-
-        let impression = Impression {
-            seller_id: 0,
-            competition: Some(crate::competition::ImpressionCompetition {
-                bid_cpm: 0.0,
-                win_rate_actual_sigmoid_offset: 0.0,
-                win_rate_actual_sigmoid_scale: 0.0,
-                win_rate_prediction_sigmoid_offset: 0.0,
-                win_rate_prediction_sigmoid_scale: 0.0,
-            }),
-            floor_cpm: 0.0,
-            value_to_campaign_group,
-            base_impression_value: 10.0,
-        };
+        let impression = impression(100.0);
 
         // Expected bid = 0.0 * 100.0 * 1.0 = 0.0
         let mut logger = crate::logger::Logger::new();
-        let bid = campaign.get_bid(&impression, campaign_converge.as_ref(), 1.0, 100.0, &mut logger);
+        let bid = campaign.get_bid(&impression, &[campaign_converge.as_ref()], 1.0, 100.0, &mut logger);
         assert_eq!(bid, Some(0.0));
     }
 
+    #[test]
+    fn test_get_bid_viewability_weighted() {
+        let campaign = CampaignGeneral {
+            campaign_id: 0,
+            campaign_name: "Test Campaign".to_string(),
+            converge_targets: vec![Box::new(CampaignTargetNone)],
+            converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(0.5))],
+            bid_valuer: Box::new(BidValuerViewability),
+            bid_optimizer: Box::new(BidOptimizerTrutful),
+            max_bid_cpm: None,
+            win_rate_miscalibration: None,
+            censored_competition: false,
+            target_priorities: None,
+        };
+        let controller_states = campaign.create_controller_state();
+        let controller_states: Vec<&dyn crate::controllers::ControllerStateTrait> = controller_states.iter().map(|cs| cs.as_ref()).collect();
+
+        let impression = Impression { viewability: 0.25, measured_viewability: 0.25, ..impression(20.0) };
+
+        // Expected bid = 0.5 * 20.0 * 0.25 * 1.0 = 2.5
+        let mut logger = crate::logger::Logger::new();
+        let bid = campaign.get_bid(&impression, &controller_states, 1.0, 20.0, &mut logger);
+        assert_eq!(bid, Some(2.5));
+    }
+
+    #[test]
+    fn test_get_bid_viewability_constrained() {
+        let campaign = CampaignGeneral {
+            campaign_id: 0,
+            campaign_name: "Test Campaign".to_string(),
+            converge_targets: vec![Box::new(CampaignTargetNone), Box::new(CampaignTargetMinAvgViewability { min_avg_viewability: 0.7 })],
+            converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(0.5)), Box::new(crate::controllers::ControllerConstant::new(3.0))],
+            bid_valuer: Box::new(BidValuerViewabilityConstrained),
+            bid_optimizer: Box::new(BidOptimizerTrutful),
+            max_bid_cpm: None,
+            win_rate_miscalibration: None,
+            censored_competition: false,
+            target_priorities: None,
+        };
+        let controller_states = campaign.create_controller_state();
+        let controller_states: Vec<&dyn crate::controllers::ControllerStateTrait> = controller_states.iter().map(|cs| cs.as_ref()).collect();
+
+        let impression = Impression { viewability: 0.5, measured_viewability: 0.5, ..impression(20.0) };
+
+        // Expected bid = 0.5 * 20.0 * 0.5^(3.0 - 1.0) * 1.0 = 2.5
+        let mut logger = crate::logger::Logger::new();
+        let bid = campaign.get_bid(&impression, &controller_states, 1.0, 20.0, &mut logger);
+        assert_eq!(bid, Some(2.5));
+    }
+
+    #[test]
+    fn test_get_bid_cpm_ceiling() {
+        let campaign = CampaignGeneral {
+            campaign_id: 0,
+            campaign_name: "Test Campaign".to_string(),
+            converge_targets: vec![Box::new(CampaignTargetNone), Box::new(CampaignTargetMaxAvgCpm { max_avg_cpm: 4.0 })],
+            converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(0.8)), Box::new(crate::controllers::ControllerConstant::new(0.5))],
+            bid_valuer: Box::new(BidValuerCpmCeiling),
+            bid_optimizer: Box::new(BidOptimizerTrutful),
+            max_bid_cpm: None,
+            win_rate_miscalibration: None,
+            censored_competition: false,
+            target_priorities: None,
+        };
+        let controller_states = campaign.create_controller_state();
+        let controller_states: Vec<&dyn crate::controllers::ControllerStateTrait> = controller_states.iter().map(|cs| cs.as_ref()).collect();
+
+        let impression = impression(20.0);
+
+        // Expected bid = (0.8 * 20.0 * 1.0)^0.5 * 4.0^(1.0 - 0.5) = 4.0 * 2.0 = 8.0
+        let mut logger = crate::logger::Logger::new();
+        let bid = campaign.get_bid(&impression, &controller_states, 1.0, 20.0, &mut logger);
+        assert_eq!(bid, Some(8.0));
+    }
+
+    #[test]
+    fn test_get_bid_seller_spend_share() {
+        let campaign = CampaignGeneral {
+            campaign_id: 0,
+            campaign_name: "Test Campaign".to_string(),
+            converge_targets: vec![Box::new(CampaignTargetNone), Box::new(CampaignTargetMaxSellerSpendShare { seller_id: 1, max_spend_share: 0.4 })],
+            converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(0.5)), Box::new(crate::controllers::ControllerConstant::new(0.5))],
+            bid_valuer: Box::new(BidValuerSellerSpendShare { seller_id: 1 }),
+            bid_optimizer: Box::new(BidOptimizerTrutful),
+            max_bid_cpm: None,
+            win_rate_miscalibration: None,
+            censored_competition: false,
+            target_priorities: None,
+        };
+        let controller_states = campaign.create_controller_state();
+        let controller_states: Vec<&dyn crate::controllers::ControllerStateTrait> = controller_states.iter().map(|cs| cs.as_ref()).collect();
+
+        let mut impression = impression(20.0);
+
+        // Other sellers' impressions are bid on with pacing only: 0.5 * 20.0 = 10.0
+        let mut logger = crate::logger::Logger::new();
+        assert_eq!(campaign.get_bid(&impression, &controller_states, 1.0, 20.0, &mut logger), Some(10.0));
+
+        // The capped seller's impressions get the multiplier too: 0.5 * 0.5 * 20.0 = 5.0
+        impression.seller_id = 1;
+        assert_eq!(campaign.get_bid(&impression, &controller_states, 1.0, 20.0, &mut logger), Some(5.0));
+    }
+
+    #[test]
+    fn test_get_bid_weighted_value() {
+        let campaign = CampaignGeneral {
+            campaign_id: 0,
+            campaign_name: "Test Campaign".to_string(),
+            converge_targets: vec![Box::new(CampaignTargetNone)],
+            converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(0.5))],
+            bid_valuer: Box::new(BidValuerWeightedValue { weights: [0.2, 0.3, 0.5] }),
+            bid_optimizer: Box::new(BidOptimizerTrutful),
+            max_bid_cpm: None,
+            win_rate_miscalibration: None,
+            censored_competition: false,
+            target_priorities: None,
+        };
+        let controller_states = campaign.create_controller_state();
+        let controller_states: Vec<&dyn crate::controllers::ControllerStateTrait> = controller_states.iter().map(|cs| cs.as_ref()).collect();
+
+        let impression = Impression { viewability: 0.5, measured_viewability: 0.5, quality_score: 0.25, ..impression(20.0) };
+
+        // Weighted value = 0.2 * 20.0 + 0.3 * 20.0 * 0.5 + 0.5 * 20.0 * 0.25 = 9.5
+        // Expected bid = 0.5 * 9.5 * 1.0 = 4.75
+        let mut logger = crate::logger::Logger::new();
+        let bid = campaign.get_bid(&impression, &controller_states, 1.0, 20.0, &mut logger);
+        assert_eq!(bid, Some(4.75));
+    }
+
+    #[test]
+    fn test_get_bid_max_bid_cpm() {
+        let mut campaigns = Campaigns::new();
+        campaigns.add(
+            "Capped Campaign".to_string(),
+            CampaignType::MULTIPLICATIVE_PACING,
+            vec![ConvergeTarget::NONE { default_pacing: 1.0 }],
+        );
+        campaigns.set_max_bid_cpm(0, 8.0);
+        let campaign = &campaigns.campaigns[0];
+        assert_eq!(campaign.max_bid_cpm(), Some(8.0));
+
+        let controller_states = campaign.create_controller_state();
+        let controller_states: Vec<&dyn crate::controllers::ControllerStateTrait> = controller_states.iter().map(|cs| cs.as_ref()).collect();
+        let impression = impression(20.0);
+        let mut logger = crate::logger::Logger::new();
+
+        // Bid above the cap is capped, bid below the cap is left as is
+        assert_eq!(campaign.get_bid(&impression, &controller_states, 1.0, 20.0, &mut logger), Some(8.0));
+        assert_eq!(campaign.get_bid(&impression, &controller_states, 1.0, 5.0, &mut logger), Some(5.0));
+    }
+
+    #[test]
+    fn test_set_minimum_margin() {
+        let mut campaigns = Campaigns::new();
+        campaigns.add(
+            "Absolute Margin".to_string(),
+            CampaignType::MULTIPLICATIVE_PACING,
+            vec![ConvergeTarget::NONE { default_pacing: 1.0 }],
+        );
+        campaigns.add(
+            "Relative Margin".to_string(),
+            CampaignType::MULTIPLICATIVE_PACING,
+            vec![ConvergeTarget::NONE { default_pacing: 1.0 }],
+        );
+        campaigns.set_minimum_margin(0, MinimumMargin::ABSOLUTE(0.0));
+        campaigns.set_minimum_margin(1, MinimumMargin::RELATIVE(0.1));
+        let impression = impression(10.0);
+        let mut logger = crate::logger::Logger::new();
+
+        // Truthful bids leave zero margin: accepted without a required margin, abstained with 10% of value required
+        for (campaign_id, expected_bid) in [(0, Some(10.0)), (1, None)] {
+            let campaign = &campaigns.campaigns[campaign_id];
+            let controller_states = campaign.create_controller_state();
+            let controller_states: Vec<&dyn crate::controllers::ControllerStateTrait> = controller_states.iter().map(|cs| cs.as_ref()).collect();
+            assert_eq!(campaign.get_bid(&impression, &controller_states, 1.0, 10.0, &mut logger), expected_bid);
+        }
+    }
+
+    #[test]
+    fn test_get_bids_matches_get_bid() {
+        let mut campaigns = Campaigns::new();
+        campaigns.add(
+            "Capped Campaign".to_string(),
+            CampaignType::MULTIPLICATIVE_PACING,
+            vec![ConvergeTarget::NONE { default_pacing: 0.7 }],
+        );
+        campaigns.set_max_bid_cpm(0, 8.0);
+        let campaign = &campaigns.campaigns[0];
+
+        let controller_states = campaign.create_controller_state();
+        let controller_states: Vec<&dyn crate::controllers::ControllerStateTrait> = controller_states.iter().map(|cs| cs.as_ref()).collect();
+        let values_to_campaign = [2.0, 5.0, 20.0, 3.0, 9.0, 12.0, 0.5];
+        let seller_control_factors = [1.0, 1.5, 0.8, 1.2, 1.0, 0.7, 2.0];
+        let impressions: Vec<Impression> = values_to_campaign.iter().map(|&value| impression(value)).collect();
+        let mut logger = crate::logger::Logger::new();
+
+        // Batch bids (fast path for truthful multiplicative bidding) equal per-impression bids, both in full lanes
+        // and in the impressions left over
+        let mut bids = [None; 7];
+        campaign.get_bids(&impressions, &controller_states, &seller_control_factors, &values_to_campaign, &mut bids, &mut logger);
+        for (index, impression) in impressions.iter().enumerate() {
+            let bid = campaign.get_bid(impression, &controller_states, seller_control_factors[index], values_to_campaign[index], &mut logger);
+            assert_eq!(bids[index], bid);
+        }
+        assert_eq!(bids[2], Some(8.0));
+    }
+
+    #[test]
+    fn test_win_rate_miscalibration() {
+        let competition = ImpressionCompetition {
+            bid_cpm: 10.0,
+            win_rate_actual_sigmoid_offset: 10.0,
+            win_rate_actual_sigmoid_scale: 1.0,
+            win_rate_prediction_sigmoid_offset: 10.0,
+            win_rate_prediction_sigmoid_scale: 1.0,
+        };
+
+        // Bias only scales predicted parameters, actual competition is unchanged
+        let biased = WinRateMiscalibration::new(1.5, 0.5, 0.0, 0.0).apply(&competition, 42);
+        assert_eq!(biased.win_rate_prediction_sigmoid_offset, 15.0);
+        assert_eq!(biased.win_rate_prediction_sigmoid_scale, 0.5);
+        assert_eq!(biased.bid_cpm, 10.0);
+        assert_eq!(biased.win_rate_actual_sigmoid_offset, 10.0);
+
+        // Noise is deterministic for the same seed
+        let noisy = WinRateMiscalibration::new(1.0, 1.0, 0.3, 0.3);
+        let first = noisy.apply(&competition, 42);
+        let second = noisy.apply(&competition, 42);
+        assert_eq!(first.win_rate_prediction_sigmoid_offset, second.win_rate_prediction_sigmoid_offset);
+        assert_ne!(first.win_rate_prediction_sigmoid_offset, 10.0);
+    }
+
     #[test]
     fn test_converge_target_none() {
         // Test creating a campaign with ConvergeTarget::NONE
@@ -484,52 +1487,18 @@ mod tests {
         let campaign = &campaigns.campaigns[0];
 
         // Test that CampaignTargetNone works correctly
-        // Test create_controller_state returns the default pacing
+        // The constant controller keeps the default pacing itself, so its state is empty
         let converge_vars = campaign.create_controller_state();
-        // Use ControllerStateSingleVariable to extract the pacing value
-        if let Some(state) = converge_vars[0].as_any().downcast_ref::<crate::controllers::ControllerStateSingleVariable>() {
-            assert_eq!(state.converging_variable, 0.75);
-        } else {
-            panic!("Expected ControllerStateSingleVariable");
-        }
+        assert!(converge_vars[0].as_any().downcast_ref::<crate::controllers::ControllerStateEmpty>().is_some());
 
         // Test that next_controller_state always returns false (no convergence)
-        let campaign_stat = crate::simulationrun::CampaignStat {
-            impressions_obtained: 100.0,
-            total_supply_cost: 0.0,
-            total_virtual_cost: 0.0,
-            total_buyer_charge: 50.0,
-            total_value: 200.0,
-        };
+        let campaign_stat = campaign_stat(100.0, 50.0);
         let mut next_state = campaign.create_controller_state();
-        let previous_states: Vec<&dyn crate::controllers::ControllerStateTrait> = converge_vars.iter().map(|cs| cs.as_ref()).collect();
-        let next_states: Vec<&mut dyn crate::controllers::ControllerStateTrait> = next_state.iter_mut().map(|cs| cs.as_mut()).collect();
-        let converged = campaign.next_controller_state(&previous_states, &mut next_states, &campaign_stat);
-        assert_eq!(converged, false);
-
-        // Test that pacing remains unchanged after next_controller_state
-        if let Some(state) = next_state[0].as_any().downcast_ref::<crate::controllers::ControllerStateSingleVariable>() {
-            assert_eq!(state.converging_variable, 0.75);
-        } else {
-            panic!("Expected ControllerStateSingleVariable");
-        }
+        let changed = campaign.next_controller_state(&converge_vars, &mut next_state, &campaign_stat);
+        assert!(!changed);
 
         // Test that bidding works correctly with fixed pacing
-        let value_to_campaign_group = vec![30.0];
-
-        let impression = Impression {
-            seller_id: 0,
-            competition: Some(crate::competition::ImpressionCompetition {
-                bid_cpm: 0.0,
-                win_rate_actual_sigmoid_offset: 0.0,
-                win_rate_actual_sigmoid_scale: 0.0,
-                win_rate_prediction_sigmoid_offset: 0.0,
-                win_rate_prediction_sigmoid_scale: 0.0,
-            }),
-            floor_cpm: 0.0,
-            value_to_campaign_group,
-            base_impression_value: 10.0,
-        };
+        let impression = impression(30.0);
 
         // Expected bid = 0.75 * 30.0 * 1.0 = 22.5
         let mut logger = crate::logger::Logger::new();
@@ -666,6 +1635,40 @@ mod tests {
         assert_eq!(campaigns.value_groups[2], vec![2]);
     }
 
+    #[test]
+    fn test_finalize_groups_value_split() {
+        let mut campaigns = Campaigns::new();
+        
+        // Add 5 campaigns
+        for i in 0..5 {
+            campaigns.add(
+                format!("Campaign {}", i),
+                CampaignType::MULTIPLICATIVE_PACING,
+                vec![ConvergeTarget::NONE { default_pacing: 1.0 }]
+            );
+        }
+        
+        // Create groups: [0, 1] with equal Shapley split, [2, 3] with weighted split, 4 stays alone
+        campaigns.create_value_group_with_split(vec![0, 1], ValueSplitRule::SHAPLEY { weights: vec![1.0, 1.0] });
+        campaigns.create_value_group_with_split(vec![2, 3], ValueSplitRule::SHAPLEY { weights: vec![3.0, 1.0] });
+        campaigns.finalize_groups();
+        
+        assert_eq!(campaigns.campaign_value_share, vec![0.5, 0.5, 0.75, 0.25, 1.0]);
+        assert_eq!(campaigns.value_group_split_rules.len(), campaigns.value_groups.len());
+        assert_eq!(campaigns.value_group_split_rules[2], ValueSplitRule::FULL);
+    }
+
+    #[test]
+    #[should_panic(expected = "one weight per campaign")]
+    fn test_create_value_group_with_split_wrong_weights() {
+        let mut campaigns = Campaigns::new();
+        
+        campaigns.add("Campaign 0".to_string(), CampaignType::MULTIPLICATIVE_PACING, vec![ConvergeTarget::NONE { default_pacing: 1.0 }]);
+        campaigns.add("Campaign 1".to_string(), CampaignType::MULTIPLICATIVE_PACING, vec![ConvergeTarget::NONE { default_pacing: 1.0 }]);
+        
+        campaigns.create_value_group_with_split(vec![0, 1], ValueSplitRule::SHAPLEY { weights: vec![1.0] });
+    }
+
     #[test]
     fn test_finalize_groups_mixed() {
         let mut campaigns = Campaigns::new();
@@ -757,6 +1760,142 @@ mod tests {
         assert_eq!(campaigns.value_groups[1], vec![0]);
         assert_eq!(campaigns.value_groups[2], vec![2]);
     }
+
+    #[test]
+    fn test_add_configured_controller_tolerance() {
+        let mut campaigns = Campaigns::new();
+        campaigns.add(
+            "Default".to_string(),
+            CampaignType::MAX_MARGIN,
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 10.0 }],
+        );
+        campaigns.add_configured(
+            "Configured".to_string(),
+            CampaignType::MAX_MARGIN,
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 10.0 }],
+            vec![ControllerConfig::new().with_tolerance_fraction(0.1)],
+        );
+
+        // 5% below the target is outside the default tolerance, but within the configured 10%
+        let changed: Vec<bool> = campaigns.campaigns.iter().map(|campaign| {
+            let campaign_general = campaign.as_any().downcast_ref::<CampaignGeneral>().unwrap();
+            let controller = &campaign_general.converge_controllers[0];
+            let previous_state = controller.create_controller_state();
+            let mut next_state = controller.create_controller_state();
+            controller.next_controller_state(previous_state.as_ref(), next_state.as_mut(), 9.5, 10.0)
+        }).collect();
+        assert_eq!(changed, vec![true, false]);
+    }
+
+    #[test]
+    fn test_add_configured_controller_bounds() {
+        let mut campaigns = Campaigns::new();
+        campaigns.add_configured(
+            "Bounded".to_string(),
+            CampaignType::MAX_MARGIN,
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 10.0 }],
+            vec![ControllerConfig::new().with_bounds(0.5, 1.05)],
+        );
+        let campaign = &campaigns.campaigns[0];
+        let campaign_general = campaign.as_any().downcast_ref::<CampaignGeneral>().unwrap();
+        let controller = &campaign_general.converge_controllers[0];
+
+        // Far below the target, pacing would rise by 9%, but is held at the upper bound
+        let previous_state = controller.create_controller_state();
+        let mut next_state = controller.create_controller_state();
+        controller.next_controller_state(previous_state.as_ref(), next_state.as_mut(), 1.0, 10.0);
+        assert_eq!(controller.get_control_variable(next_state.as_ref()), 1.05);
+        assert_eq!(campaign.binding_bounds(&[previous_state.as_ref()]), vec![]);
+        assert_eq!(campaign.binding_bounds(&[next_state.as_ref()]), vec![(0, 1.05)]);
+    }
+
+    #[test]
+    fn test_target_priorities() {
+        let mut campaigns = Campaigns::new();
+        campaigns.add(
+            "Prioritized".to_string(),
+            CampaignType::MULTIPLICATIVE_ADDITIVE_DOUBLE_TARGET,
+            vec![
+                ConvergeTarget::TOTAL_BUDGET { target_total_budget: 10.0 },
+                ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 100 },
+            ],
+        );
+        campaigns.set_target_priorities(0, vec![0, 1]);
+        let campaign = &campaigns.campaigns[0];
+        let control_variables = |states: &[Box<dyn crate::controllers::ControllerStateTrait>]| {
+            let states: Vec<&dyn crate::controllers::ControllerStateTrait> = states.iter().map(|cs| cs.as_ref()).collect();
+            campaign.get_control_variables(&states)
+        };
+
+        // Budget is missed, so the multiplier adjusts and the additive boost is held
+        let previous_states = campaign.create_controller_state();
+        let mut next_states = campaign.create_controller_state();
+        assert!(campaign.next_controller_state(&previous_states, &mut next_states, &campaign_stat(50.0, 20.0)));
+        let next_control_variables = control_variables(&next_states);
+        assert!(next_control_variables[0] < 1.0);
+        assert_eq!(next_control_variables[1], 1.0);
+
+        // Budget is met, so the additive boost adjusts towards the impressions target
+        let mut next_states = campaign.create_controller_state();
+        assert!(campaign.next_controller_state(&previous_states, &mut next_states, &campaign_stat(50.0, 10.0)));
+        let next_control_variables = control_variables(&next_states);
+        assert_eq!(next_control_variables[0], 1.0);
+        assert!(next_control_variables[1] > 1.0);
+    }
+
+    #[test]
+    fn test_target_tolerances() {
+        let mut campaigns = Campaigns::new();
+        campaigns.add(
+            "Tolerant".to_string(),
+            CampaignType::MULTIPLICATIVE_PACING,
+            vec![ConvergeTarget::WIN_RATE { target_win_rate: 0.2 }],
+        );
+        assert_eq!(campaigns.target_tolerance(0, 0), TargetTolerance::RELATIVE(0.01));
+        assert!(campaigns.target_tolerance(0, 0).is_met(0.201, 0.2));
+        assert!(!campaigns.target_tolerance(0, 0).is_met(0.205, 0.2));
+
+        campaigns.set_target_tolerances(0, vec![TargetTolerance::ABSOLUTE(0.01)]);
+        assert!(campaigns.target_tolerance(0, 0).is_met(0.205, 0.2));
+        assert!(!campaigns.target_tolerance(0, 0).is_met(0.185, 0.2));
+    }
+
+    #[test]
+    fn test_paused_campaign_places_no_bids() {
+        use crate::simulationrun::{Marketplace, SimulationRun, SimulationType};
+        use crate::sellers::{SellerType, Sellers, SellerBuilder};
+        use crate::converge::{CampaignControllerStates, SellerControllerStates};
+        use crate::hooks::SimulationHooks;
+        use crate::impressions::{ImpressionsParam, Winner};
+
+        let mut campaigns = Campaigns::new();
+        for name in ["Campaign 0", "Campaign 1"] {
+            campaigns.add(
+                name.to_string(),
+                CampaignType::MULTIPLICATIVE_PACING,
+                vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 10.0 }],
+            );
+        }
+        campaigns.set_paused(1, true);
+        assert!(!campaigns.is_paused(0) && campaigns.is_paused(1));
+        let mut sellers = Sellers::new();
+        SellerBuilder::new("HB", SellerType::FIRST_PRICE, 1000).add_to(&mut sellers);
+        let impressions_params = ImpressionsParam::new(crate::utils::lognormal_dist(10.0, 3.0), crate::utils::lognormal_dist(1.0, 0.2));
+        let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+        let campaign_controller_states = CampaignControllerStates::new(&marketplace.campaigns);
+        let seller_controller_states = SellerControllerStates::new(&marketplace.sellers);
+        let mut hooks = SimulationHooks::new();
+        let mut logger = crate::logger::Logger::new();
+        let run = SimulationRun::new(&marketplace, &campaign_controller_states, &seller_controller_states, &[], &mut hooks, None, 1, &mut logger);
+
+        // The paused campaign takes part in no auction and wins nothing, the other one bids as usual
+        assert_eq!(run.auctions_participated[1], 0);
+        assert!(run.auctions_participated[0] > 0);
+        let wins = |campaign_id: usize| run.results.iter().filter(|result| matches!(result.winner, Winner::Campaign { campaign_id: winner_id, .. } if winner_id == campaign_id)).count();
+        assert_eq!(wins(1), 0);
+        assert!(wins(0) > 0);
+    }
 }
 
 
@@ -42,8 +42,22 @@ fn generate_all_impressions() -> Vec<Impression> {
             seller_id: 0,
             competition,
             floor_cpm,
+            soft_floor_cpm: None,
             value_to_campaign_group,
             base_impression_value,
+            viewability: 1.0,
+            measured_viewability: 1.0,
+            timestamp: 0.0,
+            quality_score: 1.0,
+            conversion_probability: 0.0,
+            conversion_value: 0.0,
+            converted: false,
+            user_id: None,
+            segment: None,
+            device_type: None,
+            geo_tier: None,
+            weight: 1.0,
+            supply_id: None,
         });
     }
     
@@ -0,0 +1,139 @@
+//! Checkpoints of the convergence loop
+//!
+//! A long convergence run periodically writes what it needs to continue to a checkpoint file: the next
+//! iteration, the controller states, the bids cached for the next iteration and the progress of a
+//! perturbation. Random draws within the loop are seeded from RAND_SEED and the iteration (stale bids) or
+//! the impression, so the seed and the iteration fix the RNG positions. A run interrupted and started
+//! again with the same seed resumes from its last checkpoint and continues as it would have.
+
+use crate::converge::{CampaignControllerStates, SellerControllerStates};
+use crate::simulationrun::Marketplace;
+use crate::utils::RAND_SEED;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Field used in checkpoints for a missing (None) value
+const CHECKPOINT_NONE: &str = "-";
+
+/// State of the convergence loop between two iterations
+pub struct Checkpoint {
+    /// RAND_SEED of the run
+    pub seed: u64,
+    /// Fingerprint of the marketplace the run converges on (see marketplace_fingerprint)
+    pub marketplace_fingerprint: u64,
+    /// Next iteration to run (0-indexed)
+    pub iteration: usize,
+    /// Iteration at which the perturbation was applied, None while it's pending or without perturbation
+    pub perturbed_at_iteration: Option<usize>,
+    pub campaign_controller_states: CampaignControllerStates,
+    pub seller_controller_states: SellerControllerStates,
+    /// Bids cached for sellers serving stale bids in the next iteration (see SimulationRun::cached_bids)
+    pub previous_bids: Vec<Vec<Option<f64>>>,
+}
+
+/// Fingerprint of a marketplace's campaigns, sellers and impressions, so a checkpoint isn't resumed on
+/// a different marketplace
+pub fn marketplace_fingerprint(marketplace: &Marketplace) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    marketplace.campaigns.campaigns.len().hash(&mut hasher);
+    marketplace.sellers.sellers.len().hash(&mut hasher);
+    for impression in &marketplace.impressions.impressions {
+        impression.seller_id.hash(&mut hasher);
+        impression.base_impression_value.to_bits().hash(&mut hasher);
+        impression.floor_cpm.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Next line of a checkpoint, which must start with `name:`, with the prefix stripped
+fn next_field<'a>(lines: &mut std::str::Lines<'a>, name: &str) -> Result<&'a str, Box<dyn std::error::Error>> {
+    let line = lines.next().ok_or_else(|| format!("Checkpoint ends before '{}'", name))?;
+    line.strip_prefix(&format!("{}:", name))
+        .map(|value| value.trim())
+        .ok_or_else(|| format!("Checkpoint line '{}' doesn't start with '{}:'", line, name).into())
+}
+
+/// Next `count` lines of a checkpoint
+fn next_lines<'a>(lines: &mut std::str::Lines<'a>, count: usize) -> Result<Vec<&'a str>, Box<dyn std::error::Error>> {
+    let section: Vec<&str> = lines.by_ref().take(count).collect();
+    if section.len() != count {
+        return Err("Checkpoint ends within a section".into());
+    }
+    Ok(section)
+}
+
+impl Checkpoint {
+    /// Save the checkpoint as text
+    pub fn save(&self) -> String {
+        let campaign_states = self.campaign_controller_states.save();
+        let seller_states = self.seller_controller_states.save();
+        let previous_bids: Vec<String> = self.previous_bids.iter()
+            .map(|bids| bids.iter().map(|bid| bid.map_or(CHECKPOINT_NONE.to_string(), |bid| bid.to_string())).collect::<Vec<_>>().join(" "))
+            .collect();
+        format!(
+            "seed: {}\nmarketplace: {}\niteration: {}\nperturbed_at_iteration: {}\ncampaign_controller_states: {}\n{}\nseller_controller_states: {}\n{}\nprevious_bids: {}\n{}",
+            self.seed,
+            self.marketplace_fingerprint,
+            self.iteration,
+            self.perturbed_at_iteration.map_or(CHECKPOINT_NONE.to_string(), |iteration| iteration.to_string()),
+            self.campaign_controller_states.campaign_controller_states.len(),
+            campaign_states,
+            self.seller_controller_states.seller_controller_states.len(),
+            seller_states,
+            previous_bids.len(),
+            previous_bids.join("\n"),
+        )
+    }
+
+    /// Load a checkpoint saved by `save` for a run on the given marketplace
+    ///
+    /// # Returns
+    /// Returns an error if the checkpoint is malformed, or was saved with a different RAND_SEED or marketplace
+    pub fn load(marketplace: &Marketplace, saved: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut lines = saved.lines();
+        let seed = next_field(&mut lines, "seed")?.parse::<u64>()?;
+        if seed != RAND_SEED.get() {
+            return Err(format!("Checkpoint was saved with seed {}, the run uses seed {}", seed, RAND_SEED.get()).into());
+        }
+        let fingerprint = next_field(&mut lines, "marketplace")?.parse::<u64>()?;
+        if fingerprint != marketplace_fingerprint(marketplace) {
+            return Err("Checkpoint was saved for a different marketplace".into());
+        }
+        let iteration = next_field(&mut lines, "iteration")?.parse::<usize>()?;
+        let perturbed_at_iteration = match next_field(&mut lines, "perturbed_at_iteration")? {
+            CHECKPOINT_NONE => None,
+            field => Some(field.parse::<usize>()?),
+        };
+        let campaign_lines = next_field(&mut lines, "campaign_controller_states")?.parse::<usize>()?;
+        let campaign_controller_states = CampaignControllerStates::load(&marketplace.campaigns, &next_lines(&mut lines, campaign_lines)?.join("\n"))?;
+        let seller_lines = next_field(&mut lines, "seller_controller_states")?.parse::<usize>()?;
+        let seller_controller_states = SellerControllerStates::load(&marketplace.sellers, &next_lines(&mut lines, seller_lines)?.join("\n"))?;
+        let bid_lines = next_field(&mut lines, "previous_bids")?.parse::<usize>()?;
+        let previous_bids = next_lines(&mut lines, bid_lines)?.into_iter()
+            .map(|line| line.split_whitespace()
+                .map(|field| if field == CHECKPOINT_NONE { Ok(None) } else { field.parse::<f64>().map(Some) })
+                .collect::<Result<Vec<_>, _>>())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { seed, marketplace_fingerprint: fingerprint, iteration, perturbed_at_iteration, campaign_controller_states, seller_controller_states, previous_bids })
+    }
+
+    /// Write the checkpoint to a file, replacing the previous one only once it's completely written
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let partial_path = path.with_extension("partial");
+        std::fs::write(&partial_path, self.save())?;
+        std::fs::rename(partial_path, path)
+    }
+
+    /// Read a checkpoint written by `write` for a run on the given marketplace, None if there's no checkpoint file
+    pub fn read(marketplace: &Marketplace, path: &Path) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        match std::fs::read_to_string(path) {
+            Ok(saved) => Self::load(marketplace, &saved).map(Some),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use rand::rngs::StdRng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use rand_distr::Distribution;
-use crate::utils::lognormal_dist;
+use crate::utils::{lognormal_dist, lognormal_from_mean_stddev};
+use crate::impressions::HOURS_PER_DAY;
 
 // Generating realistic competition parameters is a complex problem.
 // Using simple sampling of logistic parameters leads to a lot of impressions high value of win probability at near-zero bid, which is not realistic
@@ -18,6 +19,101 @@ pub struct ImpressionCompetition {
     pub win_rate_prediction_sigmoid_scale: f64,
 }
 
+impl ImpressionCompetition {
+    /// Competition with the competing bid multiplied by factor, sigmoid offsets multiplied and scales divided
+    /// by it so that the win rate curves move with the bid
+    pub fn scaled(&self, factor: f64) -> Self {
+        Self {
+            bid_cpm: self.bid_cpm * factor,
+            win_rate_actual_sigmoid_offset: self.win_rate_actual_sigmoid_offset * factor,
+            win_rate_actual_sigmoid_scale: self.win_rate_actual_sigmoid_scale / factor,
+            win_rate_prediction_sigmoid_offset: self.win_rate_prediction_sigmoid_offset * factor,
+            win_rate_prediction_sigmoid_scale: self.win_rate_prediction_sigmoid_scale / factor,
+        }
+    }
+}
+
+/// Number of steps of the numerical integration in CompetingBidDistribution::expected_competing_bid_below
+const EXPECTATION_STEPS: usize = 1000;
+
+/// Distribution the competing bid of an impression is drawn from, as returned by
+/// CompetitionGeneratorTrait::competing_bid_distribution
+/// 
+/// Gives exact win probabilities (the CDF at the bid) and expected clearing prices, instead of the
+/// approximation by the win rate sigmoid of ImpressionCompetition.
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone)]
+pub enum CompetingBidDistribution<'a> {
+    /// Logistic with the given location and scale, negative bids clipped to zero
+    LOGISTIC { location: f64, scale: f64 },
+    /// Lognormal, with (μ, σ) of the underlying normal
+    LOG_NORMAL { mu: f64, sigma: f64 },
+    /// Uniform over bid samples, sorted in increasing order
+    EMPIRICAL { sorted_bids: &'a [f64] },
+    /// Another distribution with its bids multiplied by a positive factor
+    SCALED { distribution: Box<CompetingBidDistribution<'a>>, factor: f64 },
+}
+
+impl CompetingBidDistribution<'_> {
+    /// Probability that the competing bid is at most bid_cpm, i.e. that a bid of bid_cpm wins against it
+    pub fn cdf(&self, bid_cpm: f64) -> f64 {
+        match self {
+            CompetingBidDistribution::LOGISTIC { location, scale } => {
+                if bid_cpm < 0.0 {
+                    0.0
+                } else {
+                    1.0 / (1.0 + (-(bid_cpm - location) / scale).exp())
+                }
+            }
+            CompetingBidDistribution::LOG_NORMAL { mu, sigma } => {
+                if bid_cpm <= 0.0 {
+                    0.0
+                } else {
+                    crate::utils::standard_normal_cdf((bid_cpm.ln() - mu) / sigma)
+                }
+            }
+            CompetingBidDistribution::EMPIRICAL { sorted_bids } => {
+                sorted_bids.partition_point(|bid| *bid <= bid_cpm) as f64 / sorted_bids.len() as f64
+            }
+            CompetingBidDistribution::SCALED { distribution, factor } => distribution.cdf(bid_cpm / factor),
+        }
+    }
+
+    /// Competing bid at the given probability of the CDF (quantile function)
+    pub fn quantile(&self, probability: f64) -> f64 {
+        let probability = probability.clamp(0.0, 1.0);
+        match self {
+            CompetingBidDistribution::LOGISTIC { location, scale } => {
+                (location + scale * (probability / (1.0 - probability)).ln()).max(0.0)
+            }
+            CompetingBidDistribution::LOG_NORMAL { mu, sigma } => {
+                (mu + sigma * crate::utils::standard_normal_quantile(probability)).exp()
+            }
+            CompetingBidDistribution::EMPIRICAL { sorted_bids } => {
+                let index = (probability * sorted_bids.len() as f64) as usize;
+                sorted_bids[index.min(sorted_bids.len() - 1)]
+            }
+            CompetingBidDistribution::SCALED { distribution, factor } => distribution.quantile(probability) * factor,
+        }
+    }
+
+    /// Expected competing bid given it is at most bid_cpm, i.e. the expected clearing price of a winning
+    /// bid of bid_cpm under second price (without floor)
+    /// Returns 0.0 when bid_cpm can't win
+    pub fn expected_competing_bid_below(&self, bid_cpm: f64) -> f64 {
+        let win_probability = self.cdf(bid_cpm);
+        if win_probability <= 0.0 {
+            return 0.0;
+        }
+        // E[B | B <= bid] = ∫ quantile(u) du over [0, cdf(bid)] / cdf(bid), integrated with the midpoint rule
+        let step = win_probability / EXPECTATION_STEPS as f64;
+        let integral: f64 = (0..EXPECTATION_STEPS)
+            .map(|index| self.quantile((index as f64 + 0.5) * step).min(bid_cpm))
+            .sum::<f64>() * step;
+        integral / win_probability
+    }
+}
+
 /// Trait for generating impression competition information
 pub trait CompetitionGeneratorTrait {
     /// Generate competition information for an impression
@@ -29,6 +125,25 @@ pub trait CompetitionGeneratorTrait {
     /// # Returns
     /// `Some(ImpressionCompetition)` if competition should be generated, `None` otherwise
     fn generate_competition(&self, base_impression_value: f64, rng: &mut StdRng) -> Option<ImpressionCompetition>;
+
+    /// Generate competition information for an impression offered at the given time of day in hours
+    /// Defaults to generate_competition, for generators that don't depend on the time of day
+    fn generate_competition_at(&self, base_impression_value: f64, _time_of_day: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        self.generate_competition(base_impression_value, rng)
+    }
+
+    /// Distribution the competing bid of an impression was drawn from
+    /// 
+    /// # Arguments
+    /// * `base_impression_value` - The base value of the impression
+    /// * `time_of_day` - Time of day in hours the impression is offered at
+    /// * `competition` - Competition this generator generated for the impression
+    /// 
+    /// # Returns
+    /// The distribution, or None if the generator doesn't expose it
+    fn competing_bid_distribution(&self, _base_impression_value: f64, _time_of_day: f64, _competition: &ImpressionCompetition) -> Option<CompetingBidDistribution<'_>> {
+        None
+    }
 }
 
 /// Competition generator that always returns None (no competition)
@@ -47,6 +162,59 @@ impl CompetitionGeneratorTrait for CompetitionGeneratorNone {
     }
 }
 
+/// Deviation of the predicted win rate sigmoid from the actual one, in the lognormal competition generators
+/// 
+/// Bias and noise are multiplicative on the sigmoid offset and scale, the noise drawn per impression.
+/// The default is the prediction error the generators have always had: unbiased, with lognormal noise of
+/// stddev 0.1 on the offset and 0.05 on the scale.
+#[derive(Debug, Clone)]
+pub struct PredictionError {
+    /// Multiplicative bias of predicted offset (1.0 = unbiased, > 1.0 = competition predicted to be more expensive)
+    pub offset_bias: f64,
+    /// Multiplicative bias of predicted scale (1.0 = unbiased, > 1.0 = win rate curve predicted to be steeper)
+    pub scale_bias: f64,
+    /// Distribution of offset lognormal noise (mean=1.0), None for no noise
+    offset_noise_dist: Option<rand_distr::LogNormal<f64>>,
+    /// Distribution of scale lognormal noise (mean=1.0), None for no noise
+    scale_noise_dist: Option<rand_distr::LogNormal<f64>>,
+}
+
+impl PredictionError {
+    /// Create a new prediction error
+    /// 
+    /// # Arguments
+    /// * `offset_bias` - Multiplicative bias of predicted offset (1.0 = unbiased)
+    /// * `scale_bias` - Multiplicative bias of predicted scale (1.0 = unbiased)
+    /// * `offset_noise_stddev` - Stddev of multiplicative lognormal noise (mean=1.0) on predicted offset (0.0 = no noise)
+    /// * `scale_noise_stddev` - Stddev of multiplicative lognormal noise (mean=1.0) on predicted scale (0.0 = no noise)
+    pub fn new(offset_bias: f64, scale_bias: f64, offset_noise_stddev: f64, scale_noise_stddev: f64) -> Self {
+        Self {
+            offset_bias,
+            scale_bias,
+            offset_noise_dist: if offset_noise_stddev > 0.0 { Some(lognormal_dist(1.0, offset_noise_stddev)) } else { None },
+            scale_noise_dist: if scale_noise_stddev > 0.0 { Some(lognormal_dist(1.0, scale_noise_stddev)) } else { None },
+        }
+    }
+
+    /// Prediction equal to the actual sigmoid
+    pub fn exact() -> Self {
+        Self::new(1.0, 1.0, 0.0, 0.0)
+    }
+
+    /// Predicted (offset, scale) for the actual sigmoid offset and scale
+    fn predict(&self, actual_offset: f64, actual_scale: f64, rng: &mut StdRng) -> (f64, f64) {
+        let offset_noise = self.offset_noise_dist.as_ref().map_or(1.0, |dist| Distribution::sample(dist, rng));
+        let scale_noise = self.scale_noise_dist.as_ref().map_or(1.0, |dist| Distribution::sample(dist, rng));
+        (actual_offset * self.offset_bias * offset_noise, actual_scale * self.scale_bias * scale_noise)
+    }
+}
+
+impl Default for PredictionError {
+    fn default() -> Self {
+        Self::new(1.0, 1.0, 0.1, 0.05)
+    }
+}
+
 /// Generator for impression competition information using lognormal distributions
 /// 
 /// Holds distributions used to generatively build competition that resembles real world.
@@ -55,10 +223,8 @@ pub struct CompetitionGeneratorLogNormal {
     // actual_offset_dist: rand_distr::LogNormal<f64>,
     /// Distribution for actual sigmoid scale (mean=2.0, stddev=1.0)
     actual_scale_dist: rand_distr::LogNormal<f64>,
-    /// Distribution for offset lognormal noise (mean=1.0, stddev=0.5)
-    noise_offset_dist: rand_distr::LogNormal<f64>,
-    /// Distribution for scale lognormal noise (mean=1.0, stddev=0.5)
-    noise_scale_dist: rand_distr::LogNormal<f64>,
+    /// Deviation of the predicted sigmoid from the actual one
+    prediction_error: PredictionError,
 }
 
 impl CompetitionGeneratorLogNormal {
@@ -71,10 +237,15 @@ impl CompetitionGeneratorLogNormal {
             // With current parameters, we end up with 0.3% of impressions at zero bid
             // actual_offset_dist: lognormal_dist(value_base, 3.0),
             actual_scale_dist: lognormal_dist(1.5, 1.0),
-            noise_offset_dist: lognormal_dist(1.0, 0.1),
-            noise_scale_dist: lognormal_dist(1.0, 0.05),
+            prediction_error: PredictionError::default(),
         })
     }
+
+    /// Set the deviation of the predicted sigmoid from the actual one (PredictionError::default() unless set)
+    pub fn with_prediction_error(mut self: Box<Self>, prediction_error: PredictionError) -> Box<Self> {
+        self.prediction_error = prediction_error;
+        self
+    }
 }
 
 /// Implementation considerations for building a realistic competitive environment:
@@ -94,7 +265,7 @@ impl CompetitionGeneratorLogNormal {
 ///    in the auction.
 ///
 /// 4. **Perturbation for imperfect modeling**: We prepare a perturbation of the actual parameters
-///    (by multiplying with lognormal noise) to simulate imperfect modeling of the win rate
+///    (by multiplying with bias and lognormal noise, see PredictionError) to simulate imperfect modeling of the win rate
 ///    prediction curve. This creates `win_rate_prediction_sigmoid_offset` and
 ///    `win_rate_prediction_sigmoid_scale` that differ from the actual parameters.
 ///
@@ -145,13 +316,95 @@ impl CompetitionGeneratorTrait for CompetitionGeneratorLogNormal {
         // Clip the competing bid (bid_cpm) to be above zero
         bid_cpm = bid_cpm.max(0.0); 
 
-        // Add multiplicative lognormal noise to get win_rate_prediction_sigmoid_offset
-        let noise_offset = Distribution::sample(&self.noise_offset_dist, rng);
-        let win_rate_prediction_sigmoid_offset = win_rate_actual_sigmoid_offset * noise_offset;
+        // Apply multiplicative bias and lognormal noise to get the predicted sigmoid
+        let (win_rate_prediction_sigmoid_offset, win_rate_prediction_sigmoid_scale) =
+            self.prediction_error.predict(win_rate_actual_sigmoid_offset, win_rate_actual_sigmoid_scale, rng);
+        Some(ImpressionCompetition {
+            bid_cpm,
+            win_rate_actual_sigmoid_offset,
+            win_rate_actual_sigmoid_scale,
+            win_rate_prediction_sigmoid_offset,
+            win_rate_prediction_sigmoid_scale,
+        })
+    }
+
+    /// Competing bids are drawn from the logistic of the actual sigmoid parameters, clipped to zero
+    fn competing_bid_distribution(&self, _base_impression_value: f64, _time_of_day: f64, competition: &ImpressionCompetition) -> Option<CompetingBidDistribution<'_>> {
+        Some(CompetingBidDistribution::LOGISTIC {
+            location: competition.win_rate_actual_sigmoid_offset,
+            scale: competition.win_rate_actual_sigmoid_scale,
+        })
+    }
+}
+
+
+/// Competition generator whose competing bids are correlated with the impression's base value
+/// 
+/// Base values and competing bids both follow lognormal distributions, joined by a Gaussian copula:
+/// the base value's standard normal score z is taken from its lognormal, and the competing bid is the
+/// bid lognormal's value at score correlation × z + √(1 − correlation²) × ε, with ε standard normal.
+/// With correlation 0 competing bids are independent of the base value, with higher correlation valuable
+/// impressions draw proportionally higher competing bids, while the bid distribution over all impressions
+/// stays the same.
+/// 
+/// The actual win rate sigmoid is the logistic matching the conditional bid distribution given the base
+/// value at its median, and the predicted sigmoid deviates from it by the generator's PredictionError.
+pub struct CompetitionGeneratorCorrelated {
+    /// (μ, σ) of the base value lognormal
+    value_log_params: (f64, f64),
+    /// (μ, σ) of the competing bid lognormal
+    bid_log_params: (f64, f64),
+    correlation: f64,
+    /// Deviation of the predicted sigmoid from the actual one
+    prediction_error: PredictionError,
+}
+
+impl CompetitionGeneratorCorrelated {
+    /// Create a new generator
+    /// 
+    /// # Arguments
+    /// * `value_mean`, `value_stddev` - Mean and stddev of the base impression value lognormal (as in ImpressionsParam)
+    /// * `bid_mean`, `bid_stddev` - Mean and stddev of the competing bid lognormal
+    /// * `correlation` - Correlation coefficient of the copula, in (-1, 1)
+    pub fn new(value_mean: f64, value_stddev: f64, bid_mean: f64, bid_stddev: f64, correlation: f64) -> Box<Self> {
+        assert!(correlation > -1.0 && correlation < 1.0, "Correlation must be in (-1, 1), got {}", correlation);
+        Box::new(Self {
+            value_log_params: lognormal_from_mean_stddev(value_mean, value_stddev),
+            bid_log_params: lognormal_from_mean_stddev(bid_mean, bid_stddev),
+            correlation,
+            prediction_error: PredictionError::default(),
+        })
+    }
+
+    /// Set the deviation of the predicted sigmoid from the actual one (PredictionError::default() unless set)
+    pub fn with_prediction_error(mut self: Box<Self>, prediction_error: PredictionError) -> Box<Self> {
+        self.prediction_error = prediction_error;
+        self
+    }
+
+    /// (μ, σ) of the lognormal distribution of the competing bid given the base value
+    fn conditional_log_params(&self, base_impression_value: f64) -> (f64, f64) {
+        let (value_mu, value_sigma) = self.value_log_params;
+        let (bid_mu, bid_sigma) = self.bid_log_params;
+        let value_score = (base_impression_value.max(f64::MIN_POSITIVE).ln() - value_mu) / value_sigma;
+        (bid_mu + bid_sigma * self.correlation * value_score, bid_sigma * (1.0 - self.correlation * self.correlation).sqrt())
+    }
+}
+
+impl CompetitionGeneratorTrait for CompetitionGeneratorCorrelated {
+    fn generate_competition(&self, base_impression_value: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        let (conditional_mu, conditional_sigma) = self.conditional_log_params(base_impression_value);
+        
+        let noise: f64 = rng.sample(rand_distr::StandardNormal);
+        let bid_cpm = (conditional_mu + conditional_sigma * noise).exp();
+        
+        // Logistic with the conditional median as offset and the same slope there
+        // (slope of the lognormal CDF at its median m is 1 / (√(2π) σ m), of the logistic it is scale / 4)
+        let win_rate_actual_sigmoid_offset = conditional_mu.exp();
+        let win_rate_actual_sigmoid_scale = 4.0 / ((2.0 * std::f64::consts::PI).sqrt() * conditional_sigma * win_rate_actual_sigmoid_offset);
         
-        // Add multiplicative lognormal noise to get win_rate_prediction_sigmoid_scale
-        let noise_scale = Distribution::sample(&self.noise_scale_dist, rng);
-        let win_rate_prediction_sigmoid_scale = win_rate_actual_sigmoid_scale * noise_scale;
+        let (win_rate_prediction_sigmoid_offset, win_rate_prediction_sigmoid_scale) =
+            self.prediction_error.predict(win_rate_actual_sigmoid_offset, win_rate_actual_sigmoid_scale, rng);
         Some(ImpressionCompetition {
             bid_cpm,
             win_rate_actual_sigmoid_offset,
@@ -160,5 +413,572 @@ impl CompetitionGeneratorTrait for CompetitionGeneratorLogNormal {
             win_rate_prediction_sigmoid_scale,
         })
     }
+
+    fn competing_bid_distribution(&self, base_impression_value: f64, _time_of_day: f64, _competition: &ImpressionCompetition) -> Option<CompetingBidDistribution<'_>> {
+        let (mu, sigma) = self.conditional_log_params(base_impression_value);
+        Some(CompetingBidDistribution::LOG_NORMAL { mu, sigma })
+    }
+}
+
+/// Period of a piecewise competition intensity curve, lasting from from_hour until the next period starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntensityPeriod {
+    pub from_hour: f64,
+    pub intensity: f64,
+}
+
+/// Intra-day curve of competition intensity, multiplying the competing bids at each time of day
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompetitionIntensityCurve {
+    /// Constant intensity within each period, periods ordered by from_hour and the first starting at hour 0
+    PIECEWISE { periods: Vec<IntensityPeriod> },
+    /// 1 + amplitude × cos(2π × (time_of_day − peak_hour) / HOURS_PER_DAY), amplitude in [0, 1)
+    SINUSOIDAL { amplitude: f64, peak_hour: f64 },
+}
+
+impl CompetitionIntensityCurve {
+    /// Intensity at the given time of day in hours
+    pub fn intensity(&self, time_of_day: f64) -> f64 {
+        match self {
+            CompetitionIntensityCurve::PIECEWISE { periods } => periods.iter().rev()
+                .find(|period| period.from_hour <= time_of_day)
+                .map_or(1.0, |period| period.intensity),
+            CompetitionIntensityCurve::SINUSOIDAL { amplitude, peak_hour } =>
+                1.0 + amplitude * (2.0 * std::f64::consts::PI * (time_of_day - peak_hour) / HOURS_PER_DAY).cos(),
+        }
+    }
+}
+
+/// Competition generator whose competing bids follow an intra-day intensity curve
+/// 
+/// Wraps another generator and multiplies its competing bids by the curve's intensity at the impression's
+/// time of day, so competition is stronger at some hours than at others. The win rate sigmoids are stretched
+/// the same way (offsets multiplied, scales divided by the intensity), keeping predictions as accurate as
+/// the wrapped generator's.
+pub struct CompetitionGeneratorTimeVarying {
+    competition_generator: Box<dyn CompetitionGeneratorTrait>,
+    curve: CompetitionIntensityCurve,
+}
+
+impl CompetitionGeneratorTimeVarying {
+    /// Create a new generator
+    /// 
+    /// # Arguments
+    /// * `competition_generator` - Generator of the competition at intensity 1.0
+    /// * `curve` - Intensity over the day
+    pub fn new(competition_generator: Box<dyn CompetitionGeneratorTrait>, curve: CompetitionIntensityCurve) -> Box<Self> {
+        match &curve {
+            CompetitionIntensityCurve::PIECEWISE { periods } => {
+                assert!(periods.first().is_some_and(|period| period.from_hour == 0.0), "Intensity curve must start at hour 0");
+                assert!(periods.windows(2).all(|pair| pair[0].from_hour < pair[1].from_hour), "Intensity curve must be ordered by increasing hour");
+                assert!(periods.iter().all(|period| period.from_hour < HOURS_PER_DAY && period.intensity > 0.0), "Intensity curve must have periods within the day and positive intensities");
+            }
+            CompetitionIntensityCurve::SINUSOIDAL { amplitude, .. } => {
+                assert!((0.0..1.0).contains(amplitude), "Intensity amplitude must be in [0, 1), got {}", amplitude);
+            }
+        }
+        Box::new(Self { competition_generator, curve })
+    }
+}
+
+impl CompetitionGeneratorTrait for CompetitionGeneratorTimeVarying {
+    /// Without a time of day, competition is generated at intensity 1.0
+    fn generate_competition(&self, base_impression_value: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        self.competition_generator.generate_competition(base_impression_value, rng)
+    }
+
+    fn generate_competition_at(&self, base_impression_value: f64, time_of_day: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        let intensity = self.curve.intensity(time_of_day);
+        self.competition_generator.generate_competition_at(base_impression_value, time_of_day, rng).map(|competition| competition.scaled(intensity))
+    }
+
+    /// The wrapped generator's distribution for the competition before scaling, scaled by the intensity
+    fn competing_bid_distribution(&self, base_impression_value: f64, time_of_day: f64, competition: &ImpressionCompetition) -> Option<CompetingBidDistribution<'_>> {
+        let intensity = self.curve.intensity(time_of_day);
+        let unscaled_competition = competition.scaled(1.0 / intensity);
+        self.competition_generator.competing_bid_distribution(base_impression_value, time_of_day, &unscaled_competition)
+            .map(|distribution| CompetingBidDistribution::SCALED { distribution: Box::new(distribution), factor: intensity })
+    }
+}
+
+/// Logistic (offset, scale) matching a win probability curve at its median
+/// 
+/// The offset is where the curve crosses 0.5 (found by bisection within [lower, upper]), and the scale gives
+/// the logistic the curve's slope there (the slope of a logistic at its offset is scale / 4).
+fn logistic_at_median(win_probability: impl Fn(f64) -> f64, slope: impl Fn(f64) -> f64, mut lower: f64, mut upper: f64) -> (f64, f64) {
+    for _ in 0..100 {
+        let middle = 0.5 * (lower + upper);
+        if win_probability(middle) < 0.5 {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+    let offset = 0.5 * (lower + upper);
+    (offset, 4.0 * slope(offset))
+}
+
+/// Range of bids within which all the sigmoids go from near 0 to near 1, for logistic_at_median
+fn sigmoids_range(sigmoids: &[(f64, f64)]) -> (f64, f64) {
+    let lower = sigmoids.iter().map(|(offset, scale)| offset - 50.0 / scale).fold(f64::INFINITY, f64::min);
+    let upper = sigmoids.iter().map(|(offset, scale)| offset + 50.0 / scale).fold(f64::NEG_INFINITY, f64::max);
+    (lower, upper)
+}
+
+/// Win rate sigmoid of the highest of independent competing bids with the given sigmoids
+/// Win probability is the product of the sigmoids, fitted by a logistic at its median
+fn max_sigmoid(sigmoids: &[(f64, f64)]) -> (f64, f64) {
+    let probability = |bid: f64| -> f64 {
+        sigmoids.iter().map(|&(offset, scale)| crate::sigmoid::Sigmoid::new(offset, scale, 1.0).get_probability(bid)).product()
+    };
+    // d/dx Π σ_i = Π σ_i × Σ scale_i × (1 − σ_i)
+    let slope = |bid: f64| -> f64 {
+        probability(bid) * sigmoids.iter().map(|&(offset, scale)| scale * (1.0 - crate::sigmoid::Sigmoid::new(offset, scale, 1.0).get_probability(bid))).sum::<f64>()
+    };
+    let (lower, upper) = sigmoids_range(sigmoids);
+    logistic_at_median(probability, slope, lower, upper)
+}
+
+/// Win rate sigmoid of a competing bid drawn from one of the given sigmoids with the given weights
+/// Win probability is the weighted average of the sigmoids, fitted by a logistic at its median
+fn mixture_sigmoid(weighted_sigmoids: &[(f64, (f64, f64))]) -> (f64, f64) {
+    let total_weight: f64 = weighted_sigmoids.iter().map(|(weight, _)| weight).sum();
+    let probability = |bid: f64| -> f64 {
+        weighted_sigmoids.iter().map(|&(weight, (offset, scale))| weight * crate::sigmoid::Sigmoid::new(offset, scale, 1.0).get_probability(bid)).sum::<f64>() / total_weight
+    };
+    let slope = |bid: f64| -> f64 {
+        weighted_sigmoids.iter().map(|&(weight, (offset, scale))| {
+            let probability = crate::sigmoid::Sigmoid::new(offset, scale, 1.0).get_probability(bid);
+            weight * scale * probability * (1.0 - probability)
+        }).sum::<f64>() / total_weight
+    };
+    let sigmoids: Vec<(f64, f64)> = weighted_sigmoids.iter().map(|(_, sigmoid)| *sigmoid).collect();
+    let (lower, upper) = sigmoids_range(&sigmoids);
+    logistic_at_median(probability, slope, lower, upper)
+}
+
+/// Actual and predicted (offset, scale) of a competition
+fn competition_sigmoids(competition: &ImpressionCompetition) -> ((f64, f64), (f64, f64)) {
+    (
+        (competition.win_rate_actual_sigmoid_offset, competition.win_rate_actual_sigmoid_scale),
+        (competition.win_rate_prediction_sigmoid_offset, competition.win_rate_prediction_sigmoid_scale),
+    )
+}
+
+/// Generate the competition of all generators for an impression, skipping those that generate none
+fn generate_all(competition_generators: &[Box<dyn CompetitionGeneratorTrait>], base_impression_value: f64, time_of_day: f64, rng: &mut StdRng) -> Vec<ImpressionCompetition> {
+    competition_generators.iter()
+        .filter_map(|competition_generator| competition_generator.generate_competition_at(base_impression_value, time_of_day, rng))
+        .collect()
+}
+
+/// Competition generator taking the highest competing bid of several independent bidder populations
+/// 
+/// Each impression gets competition from every population, the highest competing bid competes with the
+/// campaigns. Win rate sigmoids are the logistics matching the product of the populations' sigmoids at its
+/// median, separately for actual and predicted sigmoids. Populations generating no competition are skipped.
+/// The populations' competing bid distributions aren't kept on the impression, so no distribution is exposed.
+pub struct CompetitionGeneratorMax {
+    competition_generators: Vec<Box<dyn CompetitionGeneratorTrait>>,
+}
+
+impl CompetitionGeneratorMax {
+    /// Create a new generator over the given populations
+    pub fn new(competition_generators: Vec<Box<dyn CompetitionGeneratorTrait>>) -> Box<Self> {
+        assert!(!competition_generators.is_empty(), "At least one competition generator is needed");
+        Box::new(Self { competition_generators })
+    }
+}
+
+impl CompetitionGeneratorTrait for CompetitionGeneratorMax {
+    fn generate_competition(&self, base_impression_value: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        self.generate_competition_at(base_impression_value, 0.0, rng)
+    }
+
+    fn generate_competition_at(&self, base_impression_value: f64, time_of_day: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        let competitions = generate_all(&self.competition_generators, base_impression_value, time_of_day, rng);
+        if competitions.len() <= 1 {
+            return competitions.into_iter().next();
+        }
+        let (actual_sigmoids, prediction_sigmoids): (Vec<_>, Vec<_>) = competitions.iter().map(competition_sigmoids).unzip();
+        let (win_rate_actual_sigmoid_offset, win_rate_actual_sigmoid_scale) = max_sigmoid(&actual_sigmoids);
+        let (win_rate_prediction_sigmoid_offset, win_rate_prediction_sigmoid_scale) = max_sigmoid(&prediction_sigmoids);
+        Some(ImpressionCompetition {
+            bid_cpm: competitions.iter().map(|competition| competition.bid_cpm).fold(f64::NEG_INFINITY, f64::max),
+            win_rate_actual_sigmoid_offset,
+            win_rate_actual_sigmoid_scale,
+            win_rate_prediction_sigmoid_offset,
+            win_rate_prediction_sigmoid_scale,
+        })
+    }
+}
+
+/// Competition generator adding up the competing bids of several generators
+/// 
+/// Models competing bids made of independent components, e.g. a base price and a premium. Sigmoid offsets
+/// add up, and scales combine like the standard deviations of independent logistics (1 / scale² adds up).
+/// Generators generating no competition are skipped. No competing bid distribution is exposed.
+pub struct CompetitionGeneratorSum {
+    competition_generators: Vec<Box<dyn CompetitionGeneratorTrait>>,
+}
+
+impl CompetitionGeneratorSum {
+    /// Create a new generator over the given components
+    pub fn new(competition_generators: Vec<Box<dyn CompetitionGeneratorTrait>>) -> Box<Self> {
+        assert!(!competition_generators.is_empty(), "At least one competition generator is needed");
+        Box::new(Self { competition_generators })
+    }
+}
+
+impl CompetitionGeneratorTrait for CompetitionGeneratorSum {
+    fn generate_competition(&self, base_impression_value: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        self.generate_competition_at(base_impression_value, 0.0, rng)
+    }
+
+    fn generate_competition_at(&self, base_impression_value: f64, time_of_day: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        let competitions = generate_all(&self.competition_generators, base_impression_value, time_of_day, rng);
+        if competitions.is_empty() {
+            return None;
+        }
+        let combined_scale = |scale: fn(&ImpressionCompetition) -> f64| -> f64 {
+            1.0 / competitions.iter().map(|competition| scale(competition).powi(-2)).sum::<f64>().sqrt()
+        };
+        Some(ImpressionCompetition {
+            bid_cpm: competitions.iter().map(|competition| competition.bid_cpm).sum(),
+            win_rate_actual_sigmoid_offset: competitions.iter().map(|competition| competition.win_rate_actual_sigmoid_offset).sum(),
+            win_rate_actual_sigmoid_scale: combined_scale(|competition| competition.win_rate_actual_sigmoid_scale),
+            win_rate_prediction_sigmoid_offset: competitions.iter().map(|competition| competition.win_rate_prediction_sigmoid_offset).sum(),
+            win_rate_prediction_sigmoid_scale: combined_scale(|competition| competition.win_rate_prediction_sigmoid_scale),
+        })
+    }
+}
+
+/// Competition generator drawing each impression's competing bid from one of several bidder populations
+/// 
+/// The population is chosen per impression with probability proportional to its weight. The campaigns don't
+/// know which population an impression's competing bid comes from, so win rate sigmoids are the logistics
+/// matching the weighted average of the populations' sigmoids at its median. If the chosen population
+/// generates no competition, the impression has none; populations generating none are left out of the
+/// sigmoids. No competing bid distribution is exposed.
+pub struct CompetitionGeneratorWeighted {
+    weighted_generators: Vec<(f64, Box<dyn CompetitionGeneratorTrait>)>,
+}
+
+impl CompetitionGeneratorWeighted {
+    /// Create a new generator over the given (weight, population) pairs, weights being positive
+    pub fn new(weighted_generators: Vec<(f64, Box<dyn CompetitionGeneratorTrait>)>) -> Box<Self> {
+        assert!(!weighted_generators.is_empty(), "At least one competition generator is needed");
+        assert!(weighted_generators.iter().all(|(weight, _)| *weight > 0.0), "Competition generator weights must be positive");
+        Box::new(Self { weighted_generators })
+    }
+}
+
+impl CompetitionGeneratorTrait for CompetitionGeneratorWeighted {
+    fn generate_competition(&self, base_impression_value: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        self.generate_competition_at(base_impression_value, 0.0, rng)
+    }
+
+    fn generate_competition_at(&self, base_impression_value: f64, time_of_day: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        // All populations generate, so the sigmoids can cover them all
+        let competitions: Vec<(f64, Option<ImpressionCompetition>)> = self.weighted_generators.iter()
+            .map(|(weight, competition_generator)| (*weight, competition_generator.generate_competition_at(base_impression_value, time_of_day, rng)))
+            .collect();
+        let total_weight: f64 = competitions.iter().map(|(weight, _)| weight).sum();
+        let mut pick = rng.gen_range(0.0..total_weight);
+        let chosen_index = competitions.iter().position(|(weight, _)| {
+            pick -= weight;
+            pick < 0.0
+        }).unwrap_or(competitions.len() - 1);
+        let chosen = competitions[chosen_index].1.as_ref()?;
+
+        let (actual_sigmoids, prediction_sigmoids): (Vec<_>, Vec<_>) = competitions.iter()
+            .filter_map(|(weight, competition)| competition.as_ref().map(|competition| {
+                let (actual, prediction) = competition_sigmoids(competition);
+                ((*weight, actual), (*weight, prediction))
+            }))
+            .unzip();
+        let (win_rate_actual_sigmoid_offset, win_rate_actual_sigmoid_scale) = mixture_sigmoid(&actual_sigmoids);
+        let (win_rate_prediction_sigmoid_offset, win_rate_prediction_sigmoid_scale) = mixture_sigmoid(&prediction_sigmoids);
+        Some(ImpressionCompetition {
+            bid_cpm: chosen.bid_cpm,
+            win_rate_actual_sigmoid_offset,
+            win_rate_actual_sigmoid_scale,
+            win_rate_prediction_sigmoid_offset,
+            win_rate_prediction_sigmoid_scale,
+        })
+    }
+}
+
+/// A competing bid sample of CompetitionGeneratorEmpirical, with the win rate sigmoid fitted for its auction if known
+struct EmpiricalCompetitionSample {
+    bid_cpm: f64,
+    /// (offset, scale) of the fitted win rate sigmoid
+    sigmoid: Option<(f64, f64)>,
 }
 
+/// Competition generator replaying empirical highest competing bids loaded from a CSV file
+/// 
+/// The CSV file has a header row naming its columns: `bid_cpm` (required), and optionally `sigmoid_offset` and
+/// `sigmoid_scale` with the win rate sigmoid fitted for the auction. Blank lines and lines starting with `#` are skipped.
+/// Values must be finite, bids non-negative and sigmoid scales positive.
+/// Each impression gets a sample drawn uniformly, independent of its base value. Its sigmoid is used as both the
+/// actual and the predicted win rate sigmoid; samples without one use a logistic fitted to all bid samples by moments.
+pub struct CompetitionGeneratorEmpirical {
+    samples: Vec<EmpiricalCompetitionSample>,
+    /// Competing bids of the samples in increasing order
+    sorted_bids: Vec<f64>,
+    /// (offset, scale) of the logistic fitted to all bid samples
+    fitted_sigmoid: (f64, f64),
+}
+
+impl CompetitionGeneratorEmpirical {
+    /// Load the samples from a CSV file
+    pub fn from_csv(path: &str) -> Result<Box<Self>, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path).map_err(|error| format!("Can't read competition samples from '{}': {}", path, error))?;
+        Self::from_csv_str(&contents).map_err(|error| format!("Invalid competition samples in '{}': {}", path, error).into())
+    }
+
+    /// Load the samples from the contents of a CSV file
+    pub fn from_csv_str(contents: &str) -> Result<Box<Self>, Box<dyn std::error::Error>> {
+        let mut lines = contents.lines().enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+        
+        let (_, header) = lines.next().ok_or("No header row")?;
+        let columns: Vec<&str> = header.split(',').map(|column| column.trim()).collect();
+        let column_index = |name: &str| columns.iter().position(|column| *column == name);
+        let bid_column = column_index("bid_cpm").ok_or("No bid_cpm column")?;
+        let sigmoid_columns = match (column_index("sigmoid_offset"), column_index("sigmoid_scale")) {
+            (Some(offset_column), Some(scale_column)) => Some((offset_column, scale_column)),
+            (None, None) => None,
+            _ => return Err("sigmoid_offset and sigmoid_scale columns must be given together".into()),
+        };
+        
+        let mut samples = Vec::new();
+        for (line_number, line) in lines {
+            let fields: Vec<&str> = line.split(',').map(|field| field.trim()).collect();
+            let field = |column: usize| -> Result<f64, Box<dyn std::error::Error>> {
+                let text = fields.get(column).ok_or_else(|| format!("Line {}: missing column {}", line_number, columns[column]))?;
+                text.parse::<f64>().ok().filter(|value| value.is_finite())
+                    .ok_or_else(|| format!("Line {}: invalid {} '{}'", line_number, columns[column], text).into())
+            };
+            let bid_cpm = field(bid_column)?;
+            if bid_cpm < 0.0 {
+                return Err(format!("Line {}: negative bid_cpm {}", line_number, bid_cpm).into());
+            }
+            let sigmoid = match sigmoid_columns {
+                Some((offset_column, scale_column)) => {
+                    let sigmoid_scale = field(scale_column)?;
+                    if sigmoid_scale <= 0.0 {
+                        return Err(format!("Line {}: non-positive sigmoid_scale {}", line_number, sigmoid_scale).into());
+                    }
+                    Some((field(offset_column)?, sigmoid_scale))
+                }
+                None => None,
+            };
+            samples.push(EmpiricalCompetitionSample { bid_cpm, sigmoid });
+        }
+        
+        // Logistic with the samples' mean and standard deviation (the standard deviation of a logistic is π / (scale × √3))
+        if samples.len() < 2 {
+            return Err("At least two bid samples are needed".into());
+        }
+        let count = samples.len() as f64;
+        let mean = samples.iter().map(|sample| sample.bid_cpm).sum::<f64>() / count;
+        let variance = samples.iter().map(|sample| (sample.bid_cpm - mean).powi(2)).sum::<f64>() / count;
+        if variance == 0.0 {
+            return Err("At least two distinct bid samples are needed".into());
+        }
+        let fitted_sigmoid = (mean, std::f64::consts::PI / (variance.sqrt() * 3.0_f64.sqrt()));
+        
+        let mut sorted_bids: Vec<f64> = samples.iter().map(|sample| sample.bid_cpm).collect();
+        sorted_bids.sort_by(f64::total_cmp);
+        
+        Ok(Box::new(Self { samples, sorted_bids, fitted_sigmoid }))
+    }
+
+    /// Competing bids of the samples, in file order
+    pub fn bid_samples(&self) -> impl Iterator<Item = f64> + '_ {
+        self.samples.iter().map(|sample| sample.bid_cpm)
+    }
+}
+
+impl CompetitionGeneratorTrait for CompetitionGeneratorEmpirical {
+    fn generate_competition(&self, _base_impression_value: f64, rng: &mut StdRng) -> Option<ImpressionCompetition> {
+        let sample = &self.samples[rng.gen_range(0..self.samples.len())];
+        let (sigmoid_offset, sigmoid_scale) = sample.sigmoid.unwrap_or(self.fitted_sigmoid);
+        Some(ImpressionCompetition {
+            bid_cpm: sample.bid_cpm,
+            win_rate_actual_sigmoid_offset: sigmoid_offset,
+            win_rate_actual_sigmoid_scale: sigmoid_scale,
+            win_rate_prediction_sigmoid_offset: sigmoid_offset,
+            win_rate_prediction_sigmoid_scale: sigmoid_scale,
+        })
+    }
+
+    /// Competing bids are drawn uniformly from all samples, whatever the impression
+    fn competing_bid_distribution(&self, _base_impression_value: f64, _time_of_day: f64, _competition: &ImpressionCompetition) -> Option<CompetingBidDistribution<'_>> {
+        Some(CompetingBidDistribution::EMPIRICAL { sorted_bids: &self.sorted_bids })
+    }
+}
+
+/// Miscalibration of the win rate prediction as seen by a single campaign
+/// 
+/// Applies bias and noise to the predicted sigmoid offset and scale of ImpressionCompetition,
+/// on top of the prediction error already introduced by the competition generator.
+/// Both bias and noise are multiplicative, like the PredictionError of the lognormal competition generators.
+pub struct WinRateMiscalibration {
+    /// Multiplicative bias of predicted offset (1.0 = unbiased, > 1.0 = competition predicted to be more expensive)
+    pub offset_bias: f64,
+    /// Multiplicative bias of predicted scale (1.0 = unbiased, > 1.0 = win rate curve predicted to be steeper)
+    pub scale_bias: f64,
+    /// Distribution of offset lognormal noise (mean=1.0), None for no noise
+    offset_noise_dist: Option<rand_distr::LogNormal<f64>>,
+    /// Distribution of scale lognormal noise (mean=1.0), None for no noise
+    scale_noise_dist: Option<rand_distr::LogNormal<f64>>,
+}
+
+impl WinRateMiscalibration {
+    /// Create a new miscalibration
+    /// 
+    /// # Arguments
+    /// * `offset_bias` - Multiplicative bias of predicted offset (1.0 = unbiased)
+    /// * `scale_bias` - Multiplicative bias of predicted scale (1.0 = unbiased)
+    /// * `offset_noise_stddev` - Stddev of multiplicative lognormal noise (mean=1.0) on predicted offset (0.0 = no noise)
+    /// * `scale_noise_stddev` - Stddev of multiplicative lognormal noise (mean=1.0) on predicted scale (0.0 = no noise)
+    pub fn new(offset_bias: f64, scale_bias: f64, offset_noise_stddev: f64, scale_noise_stddev: f64) -> Self {
+        Self {
+            offset_bias,
+            scale_bias,
+            offset_noise_dist: if offset_noise_stddev > 0.0 { Some(lognormal_dist(1.0, offset_noise_stddev)) } else { None },
+            scale_noise_dist: if scale_noise_stddev > 0.0 { Some(lognormal_dist(1.0, scale_noise_stddev)) } else { None },
+        }
+    }
+
+    /// Get competition data with miscalibrated win rate prediction
+    /// 
+    /// Noise is drawn from an RNG seeded with `noise_seed`, so the same impression seen by the same
+    /// campaign gets the same miscalibration in every iteration of convergence.
+    /// 
+    /// # Arguments
+    /// * `competition` - Competition data of the impression
+    /// * `noise_seed` - Seed identifying the impression and campaign
+    pub fn apply(&self, competition: &ImpressionCompetition, noise_seed: u64) -> ImpressionCompetition {
+        let mut rng = StdRng::seed_from_u64(noise_seed);
+        let offset_noise = self.offset_noise_dist.as_ref().map_or(1.0, |dist| Distribution::sample(dist, &mut rng));
+        let scale_noise = self.scale_noise_dist.as_ref().map_or(1.0, |dist| Distribution::sample(dist, &mut rng));
+        ImpressionCompetition {
+            win_rate_prediction_sigmoid_offset: competition.win_rate_prediction_sigmoid_offset * self.offset_bias * offset_noise,
+            win_rate_prediction_sigmoid_scale: competition.win_rate_prediction_sigmoid_scale * self.scale_bias * scale_noise,
+            ..competition.clone()
+        }
+    }
+}
+
+/// Ridge regularization of the win rate logistic regression, keeps the fit stable when wins or losses are rare
+const WIN_RATE_RIDGE: f64 = 0.001;
+/// Newton iterations per fit of the win rate logistic regression (the fit is warm started from a previous model)
+const WIN_RATE_NEWTON_ITERATIONS: usize = 25;
+
+/// Fit a logistic regression of win/loss on (1, bid_cpm, base_impression_value) with Newton's method
+/// 
+/// Estimates the win rate from censored observations: only whether each bid won is known, never the
+/// competing bid. The fit starts from `initial`, so it can be warm started from a previous model.
+/// 
+/// # Arguments
+/// * `observations` - Observations as (bid_cpm, base_impression_value, won)
+/// * `initial` - Initial coefficients (intercept, bid_cpm, base_impression_value)
+/// 
+/// # Returns
+/// Fitted coefficients (intercept, bid_cpm, base_impression_value)
+pub fn fit_win_rate_logistic(observations: &[(f64, f64, bool)], initial: [f64; 3]) -> [f64; 3] {
+    let mut coefficients = initial;
+    for _ in 0..WIN_RATE_NEWTON_ITERATIONS {
+        // Gradient and Hessian of the penalized log-likelihood
+        let mut gradient = [0.0; 3];
+        let mut hessian = [[0.0; 3]; 3];
+        for &(bid_cpm, base_impression_value, won) in observations {
+            let features = [1.0, bid_cpm, base_impression_value];
+            let linear: f64 = (0..3).map(|i| coefficients[i] * features[i]).sum();
+            let probability = 1.0 / (1.0 + (-linear).exp());
+            let residual = if won { 1.0 } else { 0.0 } - probability;
+            let weight = probability * (1.0 - probability);
+            for i in 0..3 {
+                gradient[i] += residual * features[i];
+                for j in 0..3 {
+                    hessian[i][j] += weight * features[i] * features[j];
+                }
+            }
+        }
+        for i in 0..3 {
+            gradient[i] -= WIN_RATE_RIDGE * coefficients[i];
+            hessian[i][i] += WIN_RATE_RIDGE;
+        }
+
+        let step = match solve_3x3(hessian, gradient) {
+            Some(step) => step,
+            None => break,
+        };
+        for i in 0..3 {
+            coefficients[i] += step[i];
+        }
+        if step.iter().map(|s| s.abs()).fold(0.0, f64::max) < 1e-9 {
+            break;
+        }
+    }
+    coefficients
+}
+
+/// Win rate sigmoid (offset, scale) of fitted win rate logistic regression coefficients for an impression
+/// Returns None when the model does not increase win rate with the bid
+pub fn win_rate_logistic_sigmoid(coefficients: &[f64; 3], base_impression_value: f64) -> Option<(f64, f64)> {
+    let [intercept, bid_coefficient, value_coefficient] = *coefficients;
+    if bid_coefficient <= 0.0 {
+        return None;
+    }
+    // intercept + bid_coefficient * bid + value_coefficient * value = bid_coefficient * (bid - offset)
+    let offset = -(intercept + value_coefficient * base_impression_value) / bid_coefficient;
+    Some((offset, bid_coefficient))
+}
+
+/// Solve a 3x3 linear system with Gaussian elimination and partial pivoting
+/// Returns None when the matrix is singular
+fn solve_3x3(mut matrix: [[f64; 3]; 3], mut rhs: [f64; 3]) -> Option<[f64; 3]> {
+    for column in 0..3 {
+        let pivot = (column..3).max_by(|&a, &b| matrix[a][column].abs().partial_cmp(&matrix[b][column].abs()).unwrap())?;
+        if matrix[pivot][column].abs() < 1e-12 {
+            return None;
+        }
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+        let pivot_row = matrix[column];
+        for row in (column + 1)..3 {
+            let factor = matrix[row][column] / pivot_row[column];
+            for (k, entry) in matrix[row].iter_mut().enumerate().skip(column) {
+                *entry -= factor * pivot_row[k];
+            }
+            rhs[row] -= factor * rhs[column];
+        }
+    }
+    let mut solution = [0.0; 3];
+    for row in (0..3).rev() {
+        let sum: f64 = ((row + 1)..3).map(|k| matrix[row][k] * solution[k]).sum();
+        solution[row] = (rhs[row] - sum) / matrix[row][row];
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empirical_from_csv_str_rejects_invalid_values() {
+        assert!(CompetitionGeneratorEmpirical::from_csv_str("bid_cpm,sigmoid_offset,sigmoid_scale\n5.0,5.0,0.5\n7.0,7.0,0.4\n").is_ok());
+        for invalid_row in ["NaN,5.0,0.5", "inf,5.0,0.5", "5.0,NaN,0.5", "5.0,-inf,0.5", "5.0,5.0,inf", "5.0,5.0,0.0", "5.0,5.0,-0.5", "-1.0,5.0,0.5"] {
+            let contents = format!("bid_cpm,sigmoid_offset,sigmoid_scale\n{}\n7.0,7.0,0.4\n", invalid_row);
+            assert!(CompetitionGeneratorEmpirical::from_csv_str(&contents).is_err(), "Row '{}' should be rejected", invalid_row);
+        }
+    }
+}
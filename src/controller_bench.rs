@@ -0,0 +1,143 @@
+//! Controller benchmark harness
+//!
+//! Runs the same marketplace once per controller, with the controller pacing a single max margin
+//! campaign towards a convergence target, and reports iterations to converge, overshoot and final
+//! error of every controller in a comparison table. Invoked with the `bench` subcommand, and usable
+//! from scenarios comparing their own selection of controllers with `run_benchmark`.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignGeneral};
+use crate::controllers::{ControllerTrait, ControllerProportionalDerivative, ControllerAdaptiveGain, ControllerBisection, ControllerRobbinsMonro, ControllerResponseCurve, ControllerDeadBand, ControllerSlewRateLimited, ControllerErrorSmoothing};
+use crate::converge::SimulationConverge;
+use crate::trajectory::Trajectory;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+
+/// A controller taking part in the benchmark
+pub struct BenchController {
+    pub name: &'static str,
+    /// Creates a fresh controller for each run
+    pub create: fn() -> Box<dyn ControllerTrait>,
+}
+
+/// Result of a single controller's benchmark run
+pub struct BenchResult {
+    pub name: &'static str,
+    pub converged: bool,
+    /// Iterations run (iterations to converge when converged)
+    pub iterations: usize,
+    /// Largest relative excursion past the target, on the opposite side of where the first iteration was
+    pub overshoot: f64,
+    /// Relative error of the last iteration
+    pub final_error: f64,
+}
+
+/// All single-target controllers with their default parameters (wrappers wrap the default PD controller)
+pub fn available_controllers() -> Vec<BenchController> {
+    vec![
+        BenchController { name: "PD", create: || Box::new(ControllerProportionalDerivative::new()) },
+        BenchController { name: "AdaptiveGain", create: || Box::new(ControllerAdaptiveGain::new()) },
+        BenchController { name: "Bisection", create: || Box::new(ControllerBisection::new()) },
+        BenchController { name: "RobbinsMonro", create: || Box::new(ControllerRobbinsMonro::new()) },
+        BenchController { name: "ResponseCurve", create: || Box::new(ControllerResponseCurve::new()) },
+        BenchController { name: "PD + DeadBand 1%", create: || Box::new(ControllerDeadBand::new(Box::new(ControllerProportionalDerivative::new()), 0.01)) },
+        BenchController { name: "PD + SlewRateLimited 10%", create: || Box::new(ControllerSlewRateLimited::new(Box::new(ControllerProportionalDerivative::new()), 0.1)) },
+        BenchController { name: "PD + ErrorSmoothing 0.5", create: || Box::new(ControllerErrorSmoothing::new(Box::new(ControllerProportionalDerivative::new()), 0.5)) },
+    ]
+}
+
+/// Prepare the benchmark marketplace with a single max margin campaign paced by `converge_controller`
+fn prepare_simulationconverge(converge_target: ConvergeTarget, converge_controller: Box<dyn ControllerTrait>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![converge_target],  // converge_target
+    );
+    let campaign_general = campaigns.campaigns[campaign_id].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+    campaign_general.converge_controllers[0] = converge_controller;
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally), recording the
+    // statistics of every iteration
+    SimulationConverge::new(marketplace).with_history()
+}
+
+/// Run the benchmark marketplace once per controller and collect the results
+///
+/// # Arguments
+/// * `scenario_name` - Name used for the log directory of the runs
+/// * `converge_target` - Convergence target of the campaign (e.g. TOTAL_BUDGET or TOTAL_IMPRESSIONS)
+/// * `controllers` - Controllers to compare
+/// * `max_iterations` - Maximum number of iterations per run, controllers are allowed not to converge
+/// * `logger` - Logger for event-based logging
+pub fn run_benchmark(scenario_name: &str, converge_target: ConvergeTarget, controllers: &[BenchController], max_iterations: usize, logger: &mut Logger) -> Vec<BenchResult> {
+    controllers.iter().map(|controller| {
+        let simulation_converge = prepare_simulationconverge(converge_target.clone(), (controller.create)());
+        let converged = simulation_converge.run_variant(&format!("Running benchmark with {} controller", controller.name), scenario_name, controller.name, max_iterations, logger).is_ok();
+
+        // The campaign's target in every iteration, whether the run converged or not
+        let trajectory = Trajectory::campaign_target(&simulation_converge.history(), 0, 0);
+        BenchResult {
+            name: controller.name,
+            converged,
+            iterations: trajectory.values.len(),
+            overshoot: trajectory.overshoot(),
+            final_error: trajectory.final_error(),
+        }
+    }).collect()
+}
+
+/// Output the benchmark results as a comparison table
+pub fn printout_results(title: &str, results: &[BenchResult], logger: &mut Logger, event: LogEvent) {
+    logln!(logger, event, "\n=== {} ===", title);
+    logln!(logger, event, "{:<26} {:>9} {:>10} {:>10} {:>12}", "Controller", "Converged", "Iterations", "Overshoot", "Final error");
+    for result in results {
+        logln!(logger, event, "{:<26} {:>9} {:>10} {:>9.2}% {:>11.3}%",
+            result.name,
+            if result.converged { "yes" } else { "no" },
+            result.iterations,
+            result.overshoot * 100.0,
+            result.final_error * 100.0);
+    }
+}
+
+/// Benchmark all available controllers on a budget and on an impressions target
+pub fn run(logger: &mut Logger) {
+    let controllers = available_controllers();
+    let benchmarks = [
+        ("Budget target (40.0)", ConvergeTarget::TOTAL_BUDGET { target_total_budget: 40.0 }),
+        ("Impressions target (1500)", ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1500 }),
+    ];
+    for (title, converge_target) in benchmarks {
+        let results = run_benchmark("controller_bench", converge_target, &controllers, 200, logger);
+        printout_results(title, &results, logger, LogEvent::Scenario);
+    }
+}
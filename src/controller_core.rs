@@ -1,3 +1,54 @@
+/// Parameters of the proportional-derivative controller created for a convergence target
+/// by Campaigns::add_configured and Sellers::add_configured
+/// 
+/// Parameters left as None keep the default the campaign or seller type uses for that target.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct ControllerConfig {
+    pub tolerance_fraction: Option<f64>,
+    pub max_adjustment_factor: Option<f64>,
+    pub proportional_gain: Option<f64>,
+    pub derivative_gain: Option<f64>,
+    /// Lower bound of the control variable (e.g., minimum seller boost)
+    pub min_control_variable: Option<f64>,
+    /// Upper bound of the control variable (e.g., maximum seller boost)
+    pub max_control_variable: Option<f64>,
+}
+
+impl ControllerConfig {
+    /// Create a configuration keeping all defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tolerance as a fraction of target (e.g., 0.005 = 0.5%)
+    pub fn with_tolerance_fraction(mut self, tolerance_fraction: f64) -> Self {
+        self.tolerance_fraction = Some(tolerance_fraction);
+        self
+    }
+
+    /// Set the maximum adjustment factor (e.g., 0.2 = 20%)
+    pub fn with_max_adjustment_factor(mut self, max_adjustment_factor: f64) -> Self {
+        self.max_adjustment_factor = Some(max_adjustment_factor);
+        self
+    }
+
+    /// Set the proportional and derivative gains
+    pub fn with_gains(mut self, proportional_gain: f64, derivative_gain: f64) -> Self {
+        self.proportional_gain = Some(proportional_gain);
+        self.derivative_gain = Some(derivative_gain);
+        self
+    }
+
+    /// Keep the control variable within [min_control_variable, max_control_variable]
+    /// (see ControllerProportionalDerivativeCore::with_output_bounds)
+    pub fn with_bounds(mut self, min_control_variable: f64, max_control_variable: f64) -> Self {
+        self.min_control_variable = Some(min_control_variable);
+        self.max_control_variable = Some(max_control_variable);
+        self
+    }
+}
+
 /// Proportional-Derivative controller for adjusting campaign pacing based on target vs actual performance
 /// Adds derivative term to reduce overshoot and improve stability
 /// 
@@ -9,6 +60,8 @@ pub struct ControllerProportionalDerivativeCore {
     proportional_gain: f64,       // Proportional gain (e.g., 0.1 = 10% of error)
     derivative_gain: f64,         // Derivative gain (e.g., 0.05 = 5% of error rate)
     rescaling: bool,              // Whether to apply rescaling (reversal of proportions) based on previous_state
+    min_output: f64,              // Lower bound of the control variable (default: unbounded)
+    max_output: f64,              // Upper bound of the control variable (default: unbounded)
 }
 
 impl ControllerProportionalDerivativeCore {
@@ -22,6 +75,8 @@ impl ControllerProportionalDerivativeCore {
             proportional_gain: 0.1,      // 10% of error
             derivative_gain: 0.05,       // 5% of error rate
             rescaling: true,             // Enable rescaling by default
+            min_output: f64::NEG_INFINITY,
+            max_output: f64::INFINITY,
         }
     }
 
@@ -40,6 +95,46 @@ impl ControllerProportionalDerivativeCore {
             proportional_gain,
             derivative_gain,
             rescaling,
+            min_output: f64::NEG_INFINITY,
+            max_output: f64::INFINITY,
+        }
+    }
+
+    /// Override the parameters set in the configuration, keeping the others
+    pub fn with_config(mut self, config: &ControllerConfig) -> Self {
+        self.tolerance_fraction = config.tolerance_fraction.unwrap_or(self.tolerance_fraction);
+        self.max_adjustment_factor = config.max_adjustment_factor.unwrap_or(self.max_adjustment_factor);
+        self.proportional_gain = config.proportional_gain.unwrap_or(self.proportional_gain);
+        self.derivative_gain = config.derivative_gain.unwrap_or(self.derivative_gain);
+        if config.min_control_variable.is_some() || config.max_control_variable.is_some() {
+            let min_output = config.min_control_variable.unwrap_or(self.min_output);
+            let max_output = config.max_control_variable.unwrap_or(self.max_output);
+            self = self.with_output_bounds(min_output, max_output);
+        }
+        self
+    }
+
+    /// Bound the control variable to [min_output, max_output]
+    /// 
+    /// While the output is saturated at a bound, the stored error is frozen (anti-windup), so the
+    /// derivative term doesn't build up against the bound and the controller recovers as soon as
+    /// the error turns around. A saturated output that stays at its bound counts as unchanged.
+    /// 
+    /// # Panics
+    /// Panics if min_output is not below max_output
+    pub fn with_output_bounds(mut self, min_output: f64, max_output: f64) -> Self {
+        assert!(min_output < max_output, "Controller output bounds must satisfy min_output < max_output, got [{}, {}]", min_output, max_output);
+        self.min_output = min_output;
+        self.max_output = max_output;
+        self
+    }
+
+    /// Get the output bound the control variable is held at, or None when it is within the bounds
+    pub fn binding_bound(&self, control_variable: f64) -> Option<f64> {
+        if control_variable == self.min_output || control_variable == self.max_output {
+            Some(control_variable)
+        } else {
+            None
         }
     }
 
@@ -56,7 +151,13 @@ impl ControllerProportionalDerivativeCore {
     /// - `changed` is `true` if pacing was changed, `false` if it remained the same
     /// - `next_state` is the new controller state value (pacing)
     /// - `next_error` is the new error value to store for next iteration
-    pub fn controller_next_state(&self, mut target: f64, mut actual: f64, mut previous_state: f64, previous_error: Option<f64>) -> (bool, f64, f64) {
+    pub fn controller_next_state(&self, target: f64, actual: f64, previous_state: f64, previous_error: Option<f64>) -> (bool, f64, f64) {
+        self.controller_next_state_scaled(target, actual, previous_state, previous_error, 1.0)
+    }
+
+    /// Same as controller_next_state, with proportional and derivative gains multiplied by `gain_scale`
+    /// Used by controllers that schedule their gains (e.g., ControllerAdaptiveGain)
+    pub fn controller_next_state_scaled(&self, mut target: f64, mut actual: f64, mut previous_state: f64, previous_error: Option<f64>, gain_scale: f64) -> (bool, f64, f64) {
         
         let tolerance = target * self.tolerance_fraction;
         // target is never zero
@@ -75,7 +176,7 @@ impl ControllerProportionalDerivativeCore {
             // When error is decreasing (negative change), derivative_term is negative, reducing adjustment
             // When error is increasing (positive change), derivative_term is positive, increasing adjustment
             let error_change = current_error - prev_error;
-            error_change * self.derivative_gain
+            error_change * self.derivative_gain * gain_scale
         } else {
             // First iteration - no derivative term
             0.0
@@ -84,14 +185,14 @@ impl ControllerProportionalDerivativeCore {
         // Calculate change in pacing
         let mut change_in_pacing = if actual < target - tolerance {
             // Below target - increase pacing
-            let proportional_term = current_error * self.proportional_gain;
+            let proportional_term = current_error * self.proportional_gain * gain_scale;
             // Derivative term: negative when error decreasing (reduces adjustment), positive when error increasing (increases adjustment)
             let adjustment_factor = (proportional_term + derivative_term).min(self.max_adjustment_factor).min(self.max_adjustment_factor);
     //        println!("Below target - increase pacing: {}", adjustment_factor);
             previous_state * adjustment_factor
         } else if actual > target + tolerance {
             // Above target - decrease pacing
-            let proportional_term = current_error * self.proportional_gain;
+            let proportional_term = current_error * self.proportional_gain * gain_scale;
   //          println!("proportional_term: {}, current_error: {}, derivative_term: {}", proportional_term, current_error, derivative_term);
             // Derivative term: negative when error decreasing (reduces adjustment), positive when error increasing (increases adjustment)
             let adjustment_factor = (proportional_term + derivative_term).min(self.max_adjustment_factor).min(self.max_adjustment_factor);
@@ -113,10 +214,16 @@ impl ControllerProportionalDerivativeCore {
                 change_in_pacing /= previous_state;
             }
         }
-        // Calculate next state by adding change
-        let mut next_state = previous_state + change_in_pacing;
-        let changed = change_in_pacing != 0.0;
-        let next_error = current_error;
+        // Calculate next state by adding change, kept within the output bounds
+        let unbounded_next_state = previous_state + change_in_pacing;
+        let next_state = unbounded_next_state.clamp(self.min_output, self.max_output);
+        let changed = next_state != previous_state;
+        // Anti-windup: while saturated, keep the previous error instead of accumulating error against the bound
+        let next_error = if next_state != unbounded_next_state {
+            previous_error.unwrap_or(current_error)
+        } else {
+            current_error
+        };
             
         // Return the calculated values
         (changed, next_state, next_error)
@@ -4,6 +4,53 @@ pub trait ControllerStateTrait: std::any::Any {
     fn clone_box(&self) -> Box<dyn ControllerStateTrait>;
     fn as_any(&self) -> &dyn std::any::Any;
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+    
+    /// Save the state as a single line of whitespace separated fields (see `load`)
+    fn save(&self) -> String;
+    
+    /// Load a state saved by `save` into this state
+    /// The state must be of the same type as the saved one, e.g. created by the same controller
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Field used in saved states for a missing (None) value
+const SAVED_NONE: &str = "-";
+
+/// Format an optional value for a saved state
+fn save_option(value: Option<f64>) -> String {
+    value.map_or(SAVED_NONE.to_string(), |value| value.to_string())
+}
+
+/// Parse a value of a saved state
+fn load_f64(field: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    field.parse::<f64>().map_err(|error| format!("Invalid value '{}' in saved controller state: {}", field, error).into())
+}
+
+/// Parse an optional value of a saved state
+fn load_option(field: &str) -> Result<Option<f64>, Box<dyn std::error::Error>> {
+    if field == SAVED_NONE {
+        Ok(None)
+    } else {
+        load_f64(field).map(Some)
+    }
+}
+
+/// Split a saved state into its fields, checking the number of fields
+fn load_fields(saved: &str, expected: usize) -> Result<Vec<&str>, Box<dyn std::error::Error>> {
+    let fields: Vec<&str> = saved.split_whitespace().collect();
+    if fields.len() != expected {
+        return Err(format!("Saved controller state '{}' has {} fields, expected {}", saved, fields.len(), expected).into());
+    }
+    Ok(fields)
+}
+
+/// Split a saved state that starts with a count of entries, each taking `fields_per_entry` fields
+/// Returns the count and the remaining fields
+fn load_counted_fields(saved: &str, fields_per_entry: usize, extra_fields: usize) -> Result<(usize, Vec<&str>), Box<dyn std::error::Error>> {
+    let count_field = saved.split_whitespace().next().ok_or("Saved controller state is empty")?;
+    let count = count_field.parse::<usize>().map_err(|error| format!("Invalid count '{}' in saved controller state: {}", count_field, error))?;
+    let fields = load_fields(saved, 1 + extra_fields + count * fields_per_entry)?;
+    Ok((count, fields[1..].to_vec()))
 }
 
 /// Empty controller state with no data
@@ -15,6 +62,13 @@ impl ControllerStateTrait for ControllerStateEmpty {
     fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(ControllerStateEmpty) }
     fn as_any(&self) -> &dyn std::any::Any { self }
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        String::new()
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        load_fields(saved, 0)?;
+        Ok(())
+    }
 }
 
 
@@ -28,6 +82,14 @@ impl ControllerStateTrait for ControllerStateSingleVariable {
     fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
     fn as_any(&self) -> &dyn std::any::Any { self }
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        self.converging_variable.to_string()
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let fields = load_fields(saved, 1)?;
+        self.converging_variable = load_f64(fields[0])?;
+        Ok(())
+    }
 }
 
 /// Controller state with two variables (e.g., for PD controller: pacing and previous error)
@@ -41,5 +103,430 @@ impl ControllerStateTrait for ControllerStateDoubleVariable {
     fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
     fn as_any(&self) -> &dyn std::any::Any { self }
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        format!("{} {}", self.variable1, save_option(self.variable2))
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let fields = load_fields(saved, 2)?;
+        self.variable1 = load_f64(fields[0])?;
+        self.variable2 = load_option(fields[1])?;
+        Ok(())
+    }
 }
 
+
+/// Controller state with one variable per key (e.g., per seller_id for per-seller pacing multipliers)
+/// All vectors are indexed by key
+#[derive(Clone)]
+pub struct ControllerStateMultiVariable {
+    pub variables: Vec<f64>,
+    pub previous_errors: Vec<Option<f64>>,
+    /// Observations (e.g., spend and value) from the previous iteration, for controllers estimating marginal effects
+    pub previous_observations: Vec<Option<(f64, f64)>>,
+}
+
+impl ControllerStateMultiVariable {
+    /// Create a state with `num_keys` variables set to `initial_value`, no previous errors and no observations
+    pub fn new(num_keys: usize, initial_value: f64) -> Self {
+        Self {
+            variables: vec![initial_value; num_keys],
+            previous_errors: vec![None; num_keys],
+            previous_observations: vec![None; num_keys],
+        }
+    }
+}
+
+impl ControllerStateTrait for ControllerStateMultiVariable {
+    fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        // Count, then per key: variable, previous error, previous observation (spend and value)
+        let mut fields = vec![self.variables.len().to_string()];
+        for key in 0..self.variables.len() {
+            let (spend, value) = match self.previous_observations[key] {
+                Some((spend, value)) => (Some(spend), Some(value)),
+                None => (None, None),
+            };
+            fields.push(self.variables[key].to_string());
+            fields.push(save_option(self.previous_errors[key]));
+            fields.push(save_option(spend));
+            fields.push(save_option(value));
+        }
+        fields.join(" ")
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let (count, fields) = load_counted_fields(saved, 4, 0)?;
+        let mut state = Self::new(count, 0.0);
+        for (key, entry) in fields.chunks(4).enumerate() {
+            state.variables[key] = load_f64(entry[0])?;
+            state.previous_errors[key] = load_option(entry[1])?;
+            state.previous_observations[key] = match (load_option(entry[2])?, load_option(entry[3])?) {
+                (Some(spend), Some(value)) => Some((spend, value)),
+                _ => None,
+            };
+        }
+        *self = state;
+        Ok(())
+    }
+}
+
+/// Controller state of a bisection controller: current control variable and the interval bracketing the solution
+#[derive(Clone)]
+pub struct ControllerStateBisection {
+    pub control_variable: f64,
+    /// Largest control variable known to undershoot the target (None until one is observed)
+    pub lower: Option<f64>,
+    /// Smallest control variable known to overshoot the target (None until one is observed)
+    pub upper: Option<f64>,
+}
+
+impl ControllerStateTrait for ControllerStateBisection {
+    fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        format!("{} {} {}", self.control_variable, save_option(self.lower), save_option(self.upper))
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let fields = load_fields(saved, 3)?;
+        self.control_variable = load_f64(fields[0])?;
+        self.lower = load_option(fields[1])?;
+        self.upper = load_option(fields[2])?;
+        Ok(())
+    }
+}
+
+/// Controller state of a line search controller: current control variable, the step and direction of the
+/// search, and the objective observed at the previous control variable (None before the first observation)
+#[derive(Clone)]
+pub struct ControllerStateLineSearch {
+    pub control_variable: f64,
+    pub step: f64,
+    /// +1.0 while searching upwards, -1.0 while searching downwards
+    pub direction: f64,
+    pub previous_objective: Option<f64>,
+}
+
+impl ControllerStateTrait for ControllerStateLineSearch {
+    fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        format!("{} {} {} {}", self.control_variable, self.step, self.direction, save_option(self.previous_objective))
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let fields = load_fields(saved, 4)?;
+        self.control_variable = load_f64(fields[0])?;
+        self.step = load_f64(fields[1])?;
+        self.direction = load_f64(fields[2])?;
+        self.previous_objective = load_option(fields[3])?;
+        Ok(())
+    }
+}
+
+/// Controller state of a stochastic approximation controller: current control variable and number of updates made
+#[derive(Clone)]
+pub struct ControllerStateStochasticApproximation {
+    pub control_variable: f64,
+    pub step: usize,
+}
+
+impl ControllerStateTrait for ControllerStateStochasticApproximation {
+    fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        format!("{} {}", self.control_variable, self.step)
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let fields = load_fields(saved, 2)?;
+        self.control_variable = load_f64(fields[0])?;
+        self.step = fields[1].parse::<usize>().map_err(|error| format!("Invalid step '{}' in saved controller state: {}", fields[1], error))?;
+        Ok(())
+    }
+}
+
+/// Controller state of a response curve controller: current control variable and the observed
+/// (control variable, actual) pairs of previous iterations, oldest first
+#[derive(Clone)]
+pub struct ControllerStateResponseCurve {
+    pub control_variable: f64,
+    pub history: Vec<(f64, f64)>,
+}
+
+impl ControllerStateTrait for ControllerStateResponseCurve {
+    fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        // Count, control variable, then per observation: control variable, actual
+        let mut fields = vec![self.history.len().to_string(), self.control_variable.to_string()];
+        for (control_variable, actual) in &self.history {
+            fields.push(control_variable.to_string());
+            fields.push(actual.to_string());
+        }
+        fields.join(" ")
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let (_count, fields) = load_counted_fields(saved, 2, 1)?;
+        self.control_variable = load_f64(fields[0])?;
+        self.history = fields[1..].chunks(2)
+            .map(|entry| Ok((load_f64(entry[0])?, load_f64(entry[1])?)))
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+        Ok(())
+    }
+}
+
+/// Controller state of a dual (Lagrangian) controller: one dual variable per constraint
+/// All vectors are indexed by constraint
+#[derive(Clone)]
+pub struct ControllerStateDual {
+    pub duals: Vec<f64>,
+    /// Current subgradient step size, adapted to the signs of consecutive subgradients
+    pub step_sizes: Vec<f64>,
+    pub previous_subgradients: Vec<Option<f64>>,
+}
+
+impl ControllerStateTrait for ControllerStateDual {
+    fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        // Count, then per constraint: dual, step size, previous subgradient
+        let mut fields = vec![self.duals.len().to_string()];
+        for index in 0..self.duals.len() {
+            fields.push(self.duals[index].to_string());
+            fields.push(self.step_sizes[index].to_string());
+            fields.push(save_option(self.previous_subgradients[index]));
+        }
+        fields.join(" ")
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let (_count, fields) = load_counted_fields(saved, 3, 0)?;
+        let entries = fields.chunks(3)
+            .map(|entry| Ok((load_f64(entry[0])?, load_f64(entry[1])?, load_option(entry[2])?)))
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+        self.duals = entries.iter().map(|entry| entry.0).collect();
+        self.step_sizes = entries.iter().map(|entry| entry.1).collect();
+        self.previous_subgradients = entries.iter().map(|entry| entry.2).collect();
+        Ok(())
+    }
+}
+
+/// Controller state of an error smoothing wrapper: exponentially-smoothed error and the wrapped controller's state
+pub struct ControllerStateSmoothedError {
+    /// Smoothed error (actual - target), None before the first observation
+    pub smoothed_error: Option<f64>,
+    pub inner: Box<dyn ControllerStateTrait>,
+}
+
+impl Clone for ControllerStateSmoothedError {
+    fn clone(&self) -> Self {
+        Self {
+            smoothed_error: self.smoothed_error,
+            inner: self.inner.clone_box(),
+        }
+    }
+}
+
+impl ControllerStateTrait for ControllerStateSmoothedError {
+    fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        // Smoothed error, then the fields of the wrapped state
+        format!("{} {}", save_option(self.smoothed_error), self.inner.save()).trim_end().to_string()
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let saved = saved.trim_start();
+        let (smoothed_error_field, inner_saved) = saved.split_once(char::is_whitespace).unwrap_or((saved, ""));
+        self.smoothed_error = load_option(smoothed_error_field)?;
+        self.inner.load(inner_saved)
+    }
+}
+
+/// Controller state holding a win rate model learned from the campaign's own bid outcomes
+/// The model is a logistic regression of win/loss on (1, bid_cpm, base_impression_value)
+#[derive(Clone)]
+pub struct ControllerStateCompetitionModel {
+    /// Coefficients (intercept, bid_cpm, base_impression_value), None until the first fit
+    pub coefficients: Option<[f64; 3]>,
+    /// Accumulated observations as (bid_cpm, base_impression_value, won), newest last
+    pub observations: Vec<(f64, f64, bool)>,
+}
+
+impl ControllerStateTrait for ControllerStateCompetitionModel {
+    fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        // Count of observations, coefficients, then per observation: bid_cpm, base_impression_value, won (1 or 0)
+        let mut fields = vec![self.observations.len().to_string()];
+        for index in 0..3 {
+            fields.push(save_option(self.coefficients.map(|coefficients| coefficients[index])));
+        }
+        for (bid_cpm, base_impression_value, won) in &self.observations {
+            fields.push(bid_cpm.to_string());
+            fields.push(base_impression_value.to_string());
+            fields.push(if *won { "1" } else { "0" }.to_string());
+        }
+        fields.join(" ")
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let (_count, fields) = load_counted_fields(saved, 3, 3)?;
+        self.coefficients = match (load_option(fields[0])?, load_option(fields[1])?, load_option(fields[2])?) {
+            (Some(intercept), Some(bid_cpm), Some(base_impression_value)) => Some([intercept, bid_cpm, base_impression_value]),
+            _ => None,
+        };
+        self.observations = fields[3..].chunks(3)
+            .map(|entry| Ok((load_f64(entry[0])?, load_f64(entry[1])?, entry[2] == "1")))
+            .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+        Ok(())
+    }
+}
+
+/// Controller state holding exponentially weighted moving averages of observed clearing prices
+#[derive(Clone)]
+pub struct ControllerStateClearingPrice {
+    /// Average clearing price CPM per seller (indexed by seller_id), None until the first observation
+    pub average_clearing_price: Vec<Option<f64>>,
+}
+
+impl ControllerStateTrait for ControllerStateClearingPrice {
+    fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        // Count of sellers, then the average clearing price per seller
+        let mut fields = vec![self.average_clearing_price.len().to_string()];
+        fields.extend(self.average_clearing_price.iter().map(|average| save_option(*average)));
+        fields.join(" ")
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let (_count, fields) = load_counted_fields(saved, 1, 0)?;
+        self.average_clearing_price = fields.iter().map(|field| load_option(field)).collect::<Result<Vec<_>, _>>()?;
+        Ok(())
+    }
+}
+
+/// Controller state for pacing that is updated within a simulation run (e.g., per time bucket)
+/// Uses interior mutability, since controller states are shared immutably while auctions run
+#[derive(Clone)]
+pub struct ControllerStateIntraRunPacing {
+    /// Current intra-run pacing multiplier
+    pub multiplier: std::cell::Cell<f64>,
+    /// Spend since the start of the run
+    pub spend: std::cell::Cell<f64>,
+    /// Index of the time bucket currently being delivered
+    pub bucket: std::cell::Cell<usize>,
+    /// Accumulated error for the integral term
+    pub integral: std::cell::Cell<f64>,
+    /// Error at the previous bucket boundary for the derivative term
+    pub previous_error: std::cell::Cell<Option<f64>>,
+    /// Sum of multipliers in effect during the completed buckets
+    pub multiplier_sum: std::cell::Cell<f64>,
+}
+
+impl ControllerStateIntraRunPacing {
+    /// Create a state at the start of a run
+    pub fn new() -> Self {
+        Self {
+            multiplier: std::cell::Cell::new(1.0),
+            spend: std::cell::Cell::new(0.0),
+            bucket: std::cell::Cell::new(0),
+            integral: std::cell::Cell::new(0.0),
+            previous_error: std::cell::Cell::new(None),
+            multiplier_sum: std::cell::Cell::new(0.0),
+        }
+    }
+}
+
+impl ControllerStateIntraRunPacing {
+    /// Average multiplier over the buckets delivered so far (including the current one)
+    pub fn average_multiplier(&self) -> f64 {
+        (self.multiplier_sum.get() + self.multiplier.get()) / (self.bucket.get() + 1) as f64
+    }
+}
+
+impl ControllerStateTrait for ControllerStateIntraRunPacing {
+    fn clone_box(&self) -> Box<dyn ControllerStateTrait> { Box::new(self.clone()) }
+    fn as_any(&self) -> &dyn std::any::Any { self }
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    fn save(&self) -> String {
+        format!("{} {} {} {} {} {}",
+            self.multiplier.get(),
+            self.spend.get(),
+            self.bucket.get(),
+            self.integral.get(),
+            save_option(self.previous_error.get()),
+            self.multiplier_sum.get())
+    }
+    fn load(&mut self, saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let fields = load_fields(saved, 6)?;
+        self.multiplier.set(load_f64(fields[0])?);
+        self.spend.set(load_f64(fields[1])?);
+        self.bucket.set(fields[2].parse::<usize>().map_err(|error| format!("Invalid bucket '{}' in saved controller state: {}", fields[2], error))?);
+        self.integral.set(load_f64(fields[3])?);
+        self.previous_error.set(load_option(fields[4])?);
+        self.multiplier_sum.set(load_f64(fields[5])?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::campaigns::{Campaigns, CampaignType, ConvergeTarget};
+    use crate::converge::CampaignControllerStates;
+    use crate::controllers::ControllerStateSmoothedError;
+
+    #[test]
+    fn test_controller_states_save_load() {
+        let mut campaigns = Campaigns::new();
+        campaigns.add(
+            "Budget".to_string(),
+            CampaignType::MAX_MARGIN,
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 10.0 }],
+        );
+        campaigns.add(
+            "Fixed".to_string(),
+            CampaignType::MULTIPLICATIVE_PACING,
+            vec![ConvergeTarget::NONE { default_pacing: 1.0 }],
+        );
+        let mut campaign_controller_states = CampaignControllerStates::new(&campaigns);
+        let state = campaign_controller_states.campaign_controller_states[0][0].as_any_mut().downcast_mut::<ControllerStateDoubleVariable>().unwrap();
+        state.variable1 = 0.1 + 0.2;
+        state.variable2 = Some(-1.0 / 3.0);
+
+        // Values survive the round trip exactly, None included
+        let saved = campaign_controller_states.save();
+        let loaded = CampaignControllerStates::load(&campaigns, &saved).unwrap();
+        assert_eq!(loaded.save(), saved);
+        let loaded_state = loaded.campaign_controller_states[0][0].as_any().downcast_ref::<ControllerStateDoubleVariable>().unwrap();
+        assert_eq!(loaded_state.variable1, 0.1 + 0.2);
+        assert_eq!(loaded_state.variable2, Some(-1.0 / 3.0));
+
+        // Saved states of a different setup are rejected
+        assert!(CampaignControllerStates::load(&campaigns, "0: 1.0 -").is_err());
+        assert!(CampaignControllerStates::load(&campaigns, "0: 1.0\n1:").is_err());
+
+        // Wrapper states save the wrapped state after their own fields
+        let mut smoothed_state = ControllerStateSmoothedError {
+            smoothed_error: Some(0.25),
+            inner: Box::new(ControllerStateDoubleVariable { variable1: 0.5, variable2: None }),
+        };
+        let saved = smoothed_state.save();
+        smoothed_state.smoothed_error = None;
+        smoothed_state.load(&saved).unwrap();
+        assert_eq!(smoothed_state.smoothed_error, Some(0.25));
+        assert_eq!(smoothed_state.save(), saved);
+
+        // Counted states round trip their history
+        let mut response_curve_state = ControllerStateResponseCurve { control_variable: 0.8, history: vec![(1.0, 52.5), (0.8, 41.25)] };
+        let saved = response_curve_state.save();
+        response_curve_state.history.clear();
+        response_curve_state.load(&saved).unwrap();
+        assert_eq!(response_curve_state.history, vec![(1.0, 52.5), (0.8, 41.25)]);
+    }
+}
@@ -2,7 +2,7 @@
 pub use crate::controller_state::*;
 
 // Re-export ControllerProportionalDerivativeCore from controller_core module
-pub use crate::controller_core::ControllerProportionalDerivativeCore;
+pub use crate::controller_core::{ControllerProportionalDerivativeCore, ControllerConfig};
 
 /// Trait for controlling convergence behavior in campaigns
 pub trait ControllerTrait {
@@ -24,6 +24,14 @@ pub trait ControllerTrait {
     /// * `converge` - Controller state to extract the pacing value from
     fn get_control_variable(&self, converge: &dyn ControllerStateTrait) -> f64;
     
+    /// Overwrite the control variable (pacing value) in a controller state
+    /// Used by wrapper controllers that post-process the wrapped controller's output
+    /// 
+    /// # Arguments
+    /// * `converge` - Controller state to update
+    /// * `control_variable` - New control variable
+    fn set_control_variable(&self, converge: &mut dyn ControllerStateTrait, control_variable: f64);
+    
     /// Create initial controller state
     fn create_controller_state(&self) -> Box<dyn ControllerStateTrait>;
     
@@ -32,6 +40,15 @@ pub trait ControllerTrait {
     /// # Arguments
     /// * `converge` - Controller state to include pacing information
     fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String;
+    
+    /// Get the bound the control variable is held at, or None when it is within its bounds
+    /// Used by convergence diagnostics to warn when a hard bound binds
+    /// 
+    /// # Arguments
+    /// * `converge` - Controller state to check
+    fn binding_bound(&self, _converge: &dyn ControllerStateTrait) -> Option<f64> {
+        None
+    }
 }
 
 /// Constant implementation of ControllerTrait
@@ -57,6 +74,10 @@ impl ControllerTrait for ControllerConstant {
         self.default_value
     }
     
+    fn set_control_variable(&self, _converge: &mut dyn ControllerStateTrait, _control_variable: f64) {
+        // Constant controller - the control variable is not part of the state
+    }
+    
     fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
         Box::new(ControllerStateEmpty)
     }
@@ -97,6 +118,19 @@ impl ControllerProportionalDerivative {
             controller: ControllerProportionalDerivativeCore::new_advanced(tolerance_fraction, max_adjustment_factor, proportional_gain, derivative_gain, rescaling),
         }
     }
+
+    /// Override the parameters set in the configuration, keeping the others
+    pub fn with_config(mut self, config: &ControllerConfig) -> Self {
+        self.controller = self.controller.with_config(config);
+        self
+    }
+
+    /// Bound the control variable to [min_output, max_output], with anti-windup while saturated
+    /// (see ControllerProportionalDerivativeCore::with_output_bounds)
+    pub fn with_output_bounds(mut self, min_output: f64, max_output: f64) -> Self {
+        self.controller = self.controller.with_output_bounds(min_output, max_output);
+        self
+    }
 }
 
 impl ControllerTrait for ControllerProportionalDerivative {
@@ -121,6 +155,10 @@ impl ControllerTrait for ControllerProportionalDerivative {
         converge.as_any().downcast_ref::<ControllerStateDoubleVariable>().unwrap().variable1
     }
     
+    fn set_control_variable(&self, converge: &mut dyn ControllerStateTrait, control_variable: f64) {
+        converge.as_any_mut().downcast_mut::<ControllerStateDoubleVariable>().unwrap().variable1 = control_variable;
+    }
+    
     fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
         Box::new(ControllerStateDoubleVariable { 
             variable1: 1.0,  // Initial pacing value
@@ -135,6 +173,870 @@ impl ControllerTrait for ControllerProportionalDerivative {
             Some(prev_err) => format!("PD cntrl: {:.4} (prev_err: {:.4})", state.variable1, prev_err),
         }
     }
+    
+    fn binding_bound(&self, converge: &dyn ControllerStateTrait) -> Option<f64> {
+        self.controller.binding_bound(self.get_control_variable(converge))
+    }
+}
+
+
+/// Proportional-Derivative controller with gain scheduling (coarse-to-fine)
+/// 
+/// Gains are scaled by the relative error |actual - target| / target: full gains at or above
+/// `coarse_error`, shrinking linearly below it down to `min_gain_fraction` of the full gains.
+/// Far from the target the controller takes large steps, near the target small ones, which avoids
+/// the oscillation of fixed gains around the target. Uses the same controller state as
+/// ControllerProportionalDerivative, so it can replace it anywhere.
+pub struct ControllerAdaptiveGain {
+    pub controller: ControllerProportionalDerivativeCore,
+    /// Relative error at and above which full gains apply
+    pub coarse_error: f64,
+    /// Fraction of full gains applied as the relative error approaches zero
+    pub min_gain_fraction: f64,
+}
+
+impl ControllerAdaptiveGain {
+    /// Create a new ControllerAdaptiveGain with gains higher than ControllerProportionalDerivative far from
+    /// the target and lower near it
+    pub fn new() -> Self {
+        Self::new_advanced(
+            ControllerProportionalDerivativeCore::new_advanced(
+                0.002, // tolerance_fraction
+                0.3,   // max_adjustment_factor
+                0.8,   // proportional_gain (full, far from target)
+                0.4,   // derivative_gain (full, far from target)
+                true,  // rescaling
+            ),
+            0.3,   // coarse_error
+            0.15,  // min_gain_fraction (gains near the target similar to ControllerProportionalDerivative::new)
+        )
+    }
+
+    /// Create a new ControllerAdaptiveGain with custom parameters
+    /// 
+    /// # Arguments
+    /// * `controller` - PD core with the full (coarse) gains
+    /// * `coarse_error` - Relative error at and above which full gains apply (e.g., 0.2 = 20%)
+    /// * `min_gain_fraction` - Fraction of full gains used as the error approaches zero (e.g., 0.2)
+    pub fn new_advanced(controller: ControllerProportionalDerivativeCore, coarse_error: f64, min_gain_fraction: f64) -> Self {
+        Self {
+            controller,
+            coarse_error,
+            min_gain_fraction,
+        }
+    }
+
+    /// Fraction of full gains applied at the given actual and target (full gains for a zero target, where the
+    /// relative error is undefined)
+    fn gain_scale(&self, actual: f64, target: f64) -> f64 {
+        if target == 0.0 {
+            return 1.0;
+        }
+        let relative_error = (actual - target).abs() / target.abs();
+        (relative_error / self.coarse_error).clamp(self.min_gain_fraction, 1.0)
+    }
+}
+
+impl ControllerTrait for ControllerAdaptiveGain {
+    fn next_controller_state(&self, previous_state: &dyn ControllerStateTrait, next_state: &mut dyn ControllerStateTrait, actual: f64, target: f64) -> bool {
+        let previous_state_double = previous_state.as_any().downcast_ref::<ControllerStateDoubleVariable>().unwrap();
+        let gain_scale = self.gain_scale(actual, target);
+        
+        let (changed, next_state_value, next_error) = self.controller.controller_next_state_scaled(target, actual, previous_state_double.variable1, previous_state_double.variable2, gain_scale);
+        
+        let next_state_mut = next_state.as_any_mut().downcast_mut::<ControllerStateDoubleVariable>().unwrap();
+        next_state_mut.variable1 = next_state_value;
+        next_state_mut.variable2 = Some(next_error);
+        
+        changed
+    }
+    
+    fn get_control_variable(&self, converge: &dyn ControllerStateTrait) -> f64 {
+        converge.as_any().downcast_ref::<ControllerStateDoubleVariable>().unwrap().variable1
+    }
+    
+    fn set_control_variable(&self, converge: &mut dyn ControllerStateTrait, control_variable: f64) {
+        converge.as_any_mut().downcast_mut::<ControllerStateDoubleVariable>().unwrap().variable1 = control_variable;
+    }
+    
+    fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
+        Box::new(ControllerStateDoubleVariable { 
+            variable1: 1.0,  // Initial pacing value
+            variable2: None,  // No previous error on first iteration
+        })
+    }
+    
+    fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String {
+        let state = converge.as_any().downcast_ref::<ControllerStateDoubleVariable>().unwrap();
+        match state.variable2 {
+            None => format!("Adaptive PD cntrl: {:.4} (no prev err)", state.variable1),
+            Some(prev_err) => format!("Adaptive PD cntrl: {:.4} (prev_err: {:.4})", state.variable1, prev_err),
+        }
+    }
+}
+
+
+/// Bisection (binary search) controller for targets that grow monotonically with the control variable
+/// 
+/// Keeps an interval [lower, upper] bracketing the control variable that meets the target. Until both
+/// ends are known, the control variable is multiplied (or divided) by `expansion_factor` to find them,
+/// afterwards every iteration bisects the interval. Converges in a logarithmic number of iterations
+/// for monotone targets like TOTAL_IMPRESSIONS or TOTAL_BUDGET, but assumes the relation is monotone
+/// and nearly deterministic: it does not recover if noise moves the solution out of the interval.
+pub struct ControllerBisection {
+    /// Tolerance as a fraction of target (e.g., 0.002 = 0.2%)
+    pub tolerance_fraction: f64,
+    /// Factor by which the control variable grows (or shrinks) while searching for the interval
+    pub expansion_factor: f64,
+    /// Relative interval width below which the search stops (the target can't be met more closely)
+    pub min_interval_fraction: f64,
+}
+
+impl ControllerBisection {
+    /// Create a new ControllerBisection with default parameters
+    pub fn new() -> Self {
+        Self {
+            tolerance_fraction: 0.002,
+            expansion_factor: 2.0,
+            min_interval_fraction: 1e-6,
+        }
+    }
+}
+
+impl ControllerTrait for ControllerBisection {
+    fn next_controller_state(&self, previous_state: &dyn ControllerStateTrait, next_state: &mut dyn ControllerStateTrait, actual: f64, target: f64) -> bool {
+        let previous = previous_state.as_any().downcast_ref::<ControllerStateBisection>().unwrap();
+        let mut lower = previous.lower;
+        let mut upper = previous.upper;
+        let tolerance = target * self.tolerance_fraction;
+        
+        let control_variable = if actual < target - tolerance {
+            lower = Some(previous.control_variable);
+            match upper {
+                Some(upper) => (previous.control_variable + upper) / 2.0,
+                None => previous.control_variable * self.expansion_factor,
+            }
+        } else if actual > target + tolerance {
+            upper = Some(previous.control_variable);
+            match lower {
+                Some(lower) => (lower + previous.control_variable) / 2.0,
+                None => previous.control_variable / self.expansion_factor,
+            }
+        } else {
+            // Within tolerance - no change
+            previous.control_variable
+        };
+        
+        // Stop when the interval is too narrow to make progress
+        let interval_exhausted = match (lower, upper) {
+            (Some(lower), Some(upper)) => upper - lower <= self.min_interval_fraction * upper.abs(),
+            _ => false,
+        };
+        
+        let next = next_state.as_any_mut().downcast_mut::<ControllerStateBisection>().unwrap();
+        next.lower = lower;
+        next.upper = upper;
+        if interval_exhausted {
+            next.control_variable = previous.control_variable;
+            return false;
+        }
+        next.control_variable = control_variable;
+        control_variable != previous.control_variable
+    }
+    
+    fn get_control_variable(&self, converge: &dyn ControllerStateTrait) -> f64 {
+        converge.as_any().downcast_ref::<ControllerStateBisection>().unwrap().control_variable
+    }
+    
+    fn set_control_variable(&self, converge: &mut dyn ControllerStateTrait, control_variable: f64) {
+        converge.as_any_mut().downcast_mut::<ControllerStateBisection>().unwrap().control_variable = control_variable;
+    }
+    
+    fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
+        Box::new(ControllerStateBisection {
+            control_variable: 1.0,  // Initial pacing value
+            lower: None,
+            upper: None,
+        })
+    }
+    
+    fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String {
+        let state = converge.as_any().downcast_ref::<ControllerStateBisection>().unwrap();
+        let bound_string = |bound: Option<f64>| bound.map_or("?".to_string(), |bound| format!("{:.4}", bound));
+        format!("Bisection cntrl: {:.4} (interval [{}, {}])", state.control_variable, bound_string(state.lower), bound_string(state.upper))
+    }
+}
+
+
+/// Line search controller maximizing the actual value, using the controller framework as a univariate optimizer
+/// 
+/// The target is ignored. Each iteration the control variable moves by `step` in the current direction while
+/// the actual value keeps improving; when it gets worse the search turns around and halves the step. A search
+/// held at a bound also turns around. Stops (converges) when the step falls below `min_step`, within `min_step`
+/// of a local maximum for unimodal objectives. Like ControllerBisection it assumes nearly deterministic
+/// observations: noise can turn the search around too early.
+pub struct ControllerLineSearch {
+    /// Control variable the search starts from
+    pub initial_control_variable: f64,
+    /// Step of the first iteration, halved every time the search turns around
+    pub initial_step: f64,
+    /// Step below which the search stops
+    pub min_step: f64,
+    pub min_control_variable: f64,
+    pub max_control_variable: f64,
+}
+
+impl ControllerLineSearch {
+    /// Create a new ControllerLineSearch starting at `initial_control_variable` with steps of `initial_step`
+    pub fn new(initial_control_variable: f64, initial_step: f64) -> Self {
+        assert!(initial_step > 0.0, "Line search step must be positive, got {}", initial_step);
+        Self {
+            initial_control_variable,
+            initial_step,
+            min_step: initial_step / 128.0,
+            min_control_variable: f64::NEG_INFINITY,
+            max_control_variable: f64::INFINITY,
+        }
+    }
+    
+    /// Bound the control variable to [min_control_variable, max_control_variable] (bounds not set in the configuration are kept)
+    pub fn with_config(mut self, config: &ControllerConfig) -> Self {
+        self.min_control_variable = config.min_control_variable.unwrap_or(self.min_control_variable);
+        self.max_control_variable = config.max_control_variable.unwrap_or(self.max_control_variable);
+        assert!(self.min_control_variable < self.max_control_variable, "Line search bounds must satisfy min < max, got [{}, {}]", self.min_control_variable, self.max_control_variable);
+        self
+    }
+}
+
+impl ControllerTrait for ControllerLineSearch {
+    fn next_controller_state(&self, previous_state: &dyn ControllerStateTrait, next_state: &mut dyn ControllerStateTrait, actual: f64, _target: f64) -> bool {
+        let previous = previous_state.as_any().downcast_ref::<ControllerStateLineSearch>().unwrap();
+        let mut step = previous.step;
+        let mut direction = previous.direction;
+        
+        // Turn around and refine when the last move made the objective worse
+        if previous.previous_objective.is_some_and(|previous_objective| actual < previous_objective) {
+            direction = -direction;
+            step /= 2.0;
+        }
+        
+        let mut control_variable = (previous.control_variable + direction * step).clamp(self.min_control_variable, self.max_control_variable);
+        if control_variable == previous.control_variable {
+            // Held at a bound - search back from it
+            direction = -direction;
+            step /= 2.0;
+            control_variable = (previous.control_variable + direction * step).clamp(self.min_control_variable, self.max_control_variable);
+        }
+        
+        let next = next_state.as_any_mut().downcast_mut::<ControllerStateLineSearch>().unwrap();
+        next.step = step;
+        next.direction = direction;
+        next.previous_objective = Some(actual);
+        if step < self.min_step {
+            next.control_variable = previous.control_variable;
+            return false;
+        }
+        next.control_variable = control_variable;
+        control_variable != previous.control_variable
+    }
+    
+    fn get_control_variable(&self, converge: &dyn ControllerStateTrait) -> f64 {
+        converge.as_any().downcast_ref::<ControllerStateLineSearch>().unwrap().control_variable
+    }
+    
+    fn set_control_variable(&self, converge: &mut dyn ControllerStateTrait, control_variable: f64) {
+        converge.as_any_mut().downcast_mut::<ControllerStateLineSearch>().unwrap().control_variable = control_variable;
+    }
+    
+    fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
+        Box::new(ControllerStateLineSearch {
+            control_variable: self.initial_control_variable,
+            step: self.initial_step,
+            direction: 1.0,
+            previous_objective: None,
+        })
+    }
+    
+    fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String {
+        let state = converge.as_any().downcast_ref::<ControllerStateLineSearch>().unwrap();
+        format!("Line search cntrl: {:.4} (step {:+.4})", state.control_variable, state.direction * state.step)
+    }
+}
+
+/// Robbins-Monro stochastic approximation controller
+/// 
+/// Moves the control variable in log space by step_size(n) * relative error, where the step size
+/// a / (n + b) decays with the number of updates n made so far. The decaying step averages out noise in
+/// the observed actual values (e.g., from different RAND_SEED iterations or mini-batches), so the control
+/// variable settles even when the actual never stays within tolerance, unlike PD controllers that keep
+/// reacting to every noisy observation. Started far from the target it can be slower than PD, as the steps
+/// shrink before it gets there.
+pub struct ControllerRobbinsMonro {
+    /// Tolerance as a fraction of target (e.g., 0.002 = 0.2%)
+    pub tolerance_fraction: f64,
+    /// Numerator `a` of the step size a / (n + b)
+    pub step_scale: f64,
+    /// Offset `b` of the step size a / (n + b), delaying the decay of the first steps
+    pub step_offset: f64,
+    /// Relative error is clamped to [-max_relative_error, max_relative_error] before each step
+    pub max_relative_error: f64,
+}
+
+impl ControllerRobbinsMonro {
+    /// Create a new ControllerRobbinsMonro with default parameters
+    pub fn new() -> Self {
+        Self::new_advanced(0.002, 2.0, 4.0, 1.0)
+    }
+
+    /// Create a new ControllerRobbinsMonro with custom parameters
+    /// 
+    /// # Arguments
+    /// * `tolerance_fraction` - Tolerance as a fraction of target (e.g., 0.002 = 0.2%)
+    /// * `step_scale` - Numerator `a` of the step size a / (n + b)
+    /// * `step_offset` - Offset `b` of the step size a / (n + b), must be positive
+    /// * `max_relative_error` - Clamp on the relative error used in each step (e.g., 1.0 = 100%)
+    pub fn new_advanced(tolerance_fraction: f64, step_scale: f64, step_offset: f64, max_relative_error: f64) -> Self {
+        Self {
+            tolerance_fraction,
+            step_scale,
+            step_offset,
+            max_relative_error,
+        }
+    }
+
+    /// Step size for the n-th update
+    fn step_size(&self, step: usize) -> f64 {
+        self.step_scale / (step as f64 + self.step_offset)
+    }
+}
+
+impl ControllerTrait for ControllerRobbinsMonro {
+    fn next_controller_state(&self, previous_state: &dyn ControllerStateTrait, next_state: &mut dyn ControllerStateTrait, actual: f64, target: f64) -> bool {
+        let previous = previous_state.as_any().downcast_ref::<ControllerStateStochasticApproximation>().unwrap();
+        let next = next_state.as_any_mut().downcast_mut::<ControllerStateStochasticApproximation>().unwrap();
+        
+        // Within tolerance - no change
+        if (actual - target).abs() <= target * self.tolerance_fraction {
+            next.control_variable = previous.control_variable;
+            next.step = previous.step;
+            return false;
+        }
+        
+        // Positive when below target - increase control variable
+        let relative_error = ((target - actual) / target).clamp(-self.max_relative_error, self.max_relative_error);
+        next.control_variable = previous.control_variable * (self.step_size(previous.step) * relative_error).exp();
+        next.step = previous.step + 1;
+        true
+    }
+    
+    fn get_control_variable(&self, converge: &dyn ControllerStateTrait) -> f64 {
+        converge.as_any().downcast_ref::<ControllerStateStochasticApproximation>().unwrap().control_variable
+    }
+    
+    fn set_control_variable(&self, converge: &mut dyn ControllerStateTrait, control_variable: f64) {
+        converge.as_any_mut().downcast_mut::<ControllerStateStochasticApproximation>().unwrap().control_variable = control_variable;
+    }
+    
+    fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
+        Box::new(ControllerStateStochasticApproximation {
+            control_variable: 1.0,  // Initial pacing value
+            step: 0,
+        })
+    }
+    
+    fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String {
+        let state = converge.as_any().downcast_ref::<ControllerStateStochasticApproximation>().unwrap();
+        format!("Robbins-Monro cntrl: {:.4} (step: {}, step size: {:.4})", state.control_variable, state.step, self.step_size(state.step))
+    }
+}
+
+
+/// Model-based controller that fits a response curve of the target to the control variable
+/// 
+/// Instead of small proportional steps, the controller models actual = c * control_variable^elasticity
+/// (a straight line in log-log space) and jumps directly to the control variable where the curve
+/// reaches the target. The elasticity is the least squares slope over the last `history_length`
+/// (control variable, actual) observations, the curve goes through the latest observation.
+/// Until two distinct observations are available (or when the fit is degenerate) `initial_elasticity`
+/// is used. Steps are limited to a factor of `max_step_factor` up or down.
+/// 
+/// Works best for smooth monotone targets like spend or impressions as a function of pacing.
+pub struct ControllerResponseCurve {
+    /// Tolerance as a fraction of target (e.g., 0.002 = 0.2%)
+    pub tolerance_fraction: f64,
+    /// Number of most recent observations the response curve is fitted to
+    pub history_length: usize,
+    /// Maximum factor by which the control variable changes in one step (e.g., 4.0)
+    pub max_step_factor: f64,
+    /// Elasticity assumed before it can be fitted (1.0 = actual proportional to the control variable)
+    pub initial_elasticity: f64,
+}
+
+impl ControllerResponseCurve {
+    /// Smallest absolute fitted elasticity that is trusted, flatter fits fall back to initial_elasticity
+    const MIN_ELASTICITY: f64 = 0.05;
+
+    /// Create a new ControllerResponseCurve with default parameters
+    pub fn new() -> Self {
+        Self::new_advanced(0.002, 4, 4.0, 1.0)
+    }
+
+    /// Create a new ControllerResponseCurve with custom parameters
+    /// 
+    /// # Arguments
+    /// * `tolerance_fraction` - Tolerance as a fraction of target (e.g., 0.002 = 0.2%)
+    /// * `history_length` - Number of most recent observations to fit, at least 2
+    /// * `max_step_factor` - Maximum factor of change per step, above 1.0
+    /// * `initial_elasticity` - Elasticity assumed before it can be fitted, non-zero
+    pub fn new_advanced(tolerance_fraction: f64, history_length: usize, max_step_factor: f64, initial_elasticity: f64) -> Self {
+        assert!(history_length >= 2, "Response curve needs a history of at least 2 observations, got {}", history_length);
+        assert!(max_step_factor > 1.0, "Maximum step factor must be above 1.0, got {}", max_step_factor);
+        assert!(initial_elasticity != 0.0, "Initial elasticity must be non-zero");
+        Self {
+            tolerance_fraction,
+            history_length,
+            max_step_factor,
+            initial_elasticity,
+        }
+    }
+
+    /// Least squares slope of ln(actual) over ln(control variable), None if it can't be fitted
+    fn fitted_elasticity(history: &[(f64, f64)]) -> Option<f64> {
+        let points: Vec<(f64, f64)> = history.iter()
+            .filter(|(control_variable, actual)| *control_variable > 0.0 && *actual > 0.0)
+            .map(|(control_variable, actual)| (control_variable.ln(), actual.ln()))
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+        let count = points.len() as f64;
+        let mean_x = points.iter().map(|point| point.0).sum::<f64>() / count;
+        let mean_y = points.iter().map(|point| point.1).sum::<f64>() / count;
+        let covariance: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        if variance < 1e-12 {
+            return None;
+        }
+        let elasticity = covariance / variance;
+        if elasticity.is_finite() && elasticity.abs() >= Self::MIN_ELASTICITY {
+            Some(elasticity)
+        } else {
+            None
+        }
+    }
+}
+
+impl ControllerTrait for ControllerResponseCurve {
+    fn next_controller_state(&self, previous_state: &dyn ControllerStateTrait, next_state: &mut dyn ControllerStateTrait, actual: f64, target: f64) -> bool {
+        let previous = previous_state.as_any().downcast_ref::<ControllerStateResponseCurve>().unwrap();
+        let next = next_state.as_any_mut().downcast_mut::<ControllerStateResponseCurve>().unwrap();
+        
+        // Record the observation, keeping the last history_length
+        next.history = previous.history.clone();
+        next.history.push((previous.control_variable, actual));
+        if next.history.len() > self.history_length {
+            next.history.remove(0);
+        }
+        
+        // Within tolerance - no change
+        if (actual - target).abs() <= target * self.tolerance_fraction {
+            next.control_variable = previous.control_variable;
+            return false;
+        }
+        
+        // Jump to the root of the response curve through the latest observation
+        let elasticity = Self::fitted_elasticity(&next.history).unwrap_or(self.initial_elasticity);
+        let step_factor = if actual > 0.0 {
+            (target / actual).powf(1.0 / elasticity)
+        } else if elasticity > 0.0 {
+            // Nothing obtained yet, increasing the control variable is the only way to learn more
+            self.max_step_factor
+        } else {
+            1.0 / self.max_step_factor
+        };
+        let step_factor = if step_factor.is_finite() { step_factor.clamp(1.0 / self.max_step_factor, self.max_step_factor) } else { self.max_step_factor };
+        next.control_variable = previous.control_variable * step_factor;
+        true
+    }
+    
+    fn get_control_variable(&self, converge: &dyn ControllerStateTrait) -> f64 {
+        converge.as_any().downcast_ref::<ControllerStateResponseCurve>().unwrap().control_variable
+    }
+    
+    fn set_control_variable(&self, converge: &mut dyn ControllerStateTrait, control_variable: f64) {
+        converge.as_any_mut().downcast_mut::<ControllerStateResponseCurve>().unwrap().control_variable = control_variable;
+    }
+    
+    fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
+        Box::new(ControllerStateResponseCurve {
+            control_variable: 1.0,  // Initial pacing value
+            history: Vec::new(),
+        })
+    }
+    
+    fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String {
+        let state = converge.as_any().downcast_ref::<ControllerStateResponseCurve>().unwrap();
+        let elasticity = Self::fitted_elasticity(&state.history).map_or("-".to_string(), |elasticity| format!("{:.3}", elasticity));
+        format!("Response curve cntrl: {:.4} (observations: {}, elasticity: {})", state.control_variable, state.history.len(), elasticity)
+    }
+}
+
+
+/// Dead band wrapper usable around any controller
+/// 
+/// While the relative error |actual - target| / target is below `dead_band_fraction`, no adjustment is made
+/// and the control variable counts as unchanged (converged). Outside the dead band the wrapped controller
+/// runs as usual. Prevents the endless tiny adjustments that keep a run from converging when the wrapped
+/// controller's own tolerance is tighter than the precision the simulation can reach.
+/// 
+/// Relies on the simulation passing a copy of the previous state as the next state, so leaving next_state
+/// untouched keeps the previous state.
+pub struct ControllerDeadBand {
+    pub controller: Box<dyn ControllerTrait>,
+    /// Relative error below which no adjustment is made (e.g., 0.01 = 1%)
+    pub dead_band_fraction: f64,
+}
+
+impl ControllerDeadBand {
+    /// Wrap `controller` with a dead band of `dead_band_fraction` of the target
+    pub fn new(controller: Box<dyn ControllerTrait>, dead_band_fraction: f64) -> Self {
+        Self {
+            controller,
+            dead_band_fraction,
+        }
+    }
+}
+
+impl ControllerTrait for ControllerDeadBand {
+    fn next_controller_state(&self, previous_state: &dyn ControllerStateTrait, next_state: &mut dyn ControllerStateTrait, actual: f64, target: f64) -> bool {
+        if (actual - target).abs() < target * self.dead_band_fraction {
+            // Within dead band - no change
+            return false;
+        }
+        self.controller.next_controller_state(previous_state, next_state, actual, target)
+    }
+    
+    fn get_control_variable(&self, converge: &dyn ControllerStateTrait) -> f64 {
+        self.controller.get_control_variable(converge)
+    }
+    
+    fn set_control_variable(&self, converge: &mut dyn ControllerStateTrait, control_variable: f64) {
+        self.controller.set_control_variable(converge, control_variable);
+    }
+    
+    fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
+        self.controller.create_controller_state()
+    }
+    
+    fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String {
+        format!("{} (dead band: {:.1}%)", self.controller.controller_string(converge), self.dead_band_fraction * 100.0)
+    }
+}
+
+
+/// Slew rate limiting wrapper usable around any controller
+/// 
+/// Limits the per-iteration change of the control variable to `max_change_fraction` of its previous value,
+/// so a wrapped controller reacting to wildly-off stats (e.g., in the first iteration) can't overshoot far.
+pub struct ControllerSlewRateLimited {
+    pub controller: Box<dyn ControllerTrait>,
+    /// Maximum change of the control variable per iteration, as a fraction of its previous value (e.g., 0.1 = 10%)
+    pub max_change_fraction: f64,
+}
+
+impl ControllerSlewRateLimited {
+    /// Wrap `controller`, limiting its per-iteration change to `max_change_fraction` of the previous control variable
+    pub fn new(controller: Box<dyn ControllerTrait>, max_change_fraction: f64) -> Self {
+        Self {
+            controller,
+            max_change_fraction,
+        }
+    }
+}
+
+impl ControllerTrait for ControllerSlewRateLimited {
+    fn next_controller_state(&self, previous_state: &dyn ControllerStateTrait, next_state: &mut dyn ControllerStateTrait, actual: f64, target: f64) -> bool {
+        let changed = self.controller.next_controller_state(previous_state, next_state, actual, target);
+        
+        let previous_control_variable = self.controller.get_control_variable(previous_state);
+        let next_control_variable = self.controller.get_control_variable(next_state);
+        let max_change = previous_control_variable.abs() * self.max_change_fraction;
+        let limited_control_variable = next_control_variable.clamp(previous_control_variable - max_change, previous_control_variable + max_change);
+        if limited_control_variable != next_control_variable {
+            self.controller.set_control_variable(next_state, limited_control_variable);
+        }
+        
+        changed
+    }
+    
+    fn get_control_variable(&self, converge: &dyn ControllerStateTrait) -> f64 {
+        self.controller.get_control_variable(converge)
+    }
+    
+    fn set_control_variable(&self, converge: &mut dyn ControllerStateTrait, control_variable: f64) {
+        self.controller.set_control_variable(converge, control_variable);
+    }
+    
+    fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
+        self.controller.create_controller_state()
+    }
+    
+    fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String {
+        format!("{} (slew limit: {:.1}%)", self.controller.controller_string(converge), self.max_change_fraction * 100.0)
+    }
+}
+
+
+/// Error smoothing wrapper usable around any controller
+/// 
+/// Feeds the wrapped controller an exponential moving average (EMA) of the error instead of the raw error:
+/// smoothed = smoothing_factor * error + (1 - smoothing_factor) * previous smoothed, with error = actual - target.
+/// Damps the iteration-to-iteration noise of actuals coming from stochastic impression generation, at the
+/// cost of reacting later to real changes. A smoothing factor of 1.0 disables smoothing.
+/// 
+/// The wrapped controller's state is kept inside ControllerStateSmoothedError.
+pub struct ControllerErrorSmoothing {
+    pub controller: Box<dyn ControllerTrait>,
+    /// Weight of the newest error in the moving average (0.0 < smoothing_factor <= 1.0)
+    pub smoothing_factor: f64,
+}
+
+impl ControllerErrorSmoothing {
+    /// Wrap `controller`, smoothing its error with an EMA of weight `smoothing_factor` for the newest error
+    /// 
+    /// # Panics
+    /// Panics if smoothing_factor is not in (0, 1]
+    pub fn new(controller: Box<dyn ControllerTrait>, smoothing_factor: f64) -> Self {
+        assert!(smoothing_factor > 0.0 && smoothing_factor <= 1.0, "Smoothing factor must be in (0, 1], got {}", smoothing_factor);
+        Self {
+            controller,
+            smoothing_factor,
+        }
+    }
 }
 
+impl ControllerTrait for ControllerErrorSmoothing {
+    fn next_controller_state(&self, previous_state: &dyn ControllerStateTrait, next_state: &mut dyn ControllerStateTrait, actual: f64, target: f64) -> bool {
+        let previous_state = previous_state.as_any().downcast_ref::<ControllerStateSmoothedError>().unwrap();
+        let next_state = next_state.as_any_mut().downcast_mut::<ControllerStateSmoothedError>().unwrap();
+        
+        let error = actual - target;
+        let smoothed_error = match previous_state.smoothed_error {
+            Some(previous_smoothed_error) => self.smoothing_factor * error + (1.0 - self.smoothing_factor) * previous_smoothed_error,
+            None => error,
+        };
+        next_state.smoothed_error = Some(smoothed_error);
+        
+        self.controller.next_controller_state(previous_state.inner.as_ref(), next_state.inner.as_mut(), target + smoothed_error, target)
+    }
+    
+    fn get_control_variable(&self, converge: &dyn ControllerStateTrait) -> f64 {
+        let state = converge.as_any().downcast_ref::<ControllerStateSmoothedError>().unwrap();
+        self.controller.get_control_variable(state.inner.as_ref())
+    }
+    
+    fn set_control_variable(&self, converge: &mut dyn ControllerStateTrait, control_variable: f64) {
+        let state = converge.as_any_mut().downcast_mut::<ControllerStateSmoothedError>().unwrap();
+        self.controller.set_control_variable(state.inner.as_mut(), control_variable);
+    }
+    
+    fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
+        Box::new(ControllerStateSmoothedError {
+            smoothed_error: None,
+            inner: self.controller.create_controller_state(),
+        })
+    }
+    
+    fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String {
+        let state = converge.as_any().downcast_ref::<ControllerStateSmoothedError>().unwrap();
+        let smoothed_error = state.smoothed_error.map_or("-".to_string(), |smoothed_error| format!("{:.4}", smoothed_error));
+        format!("{} (error EMA: factor {:.2}, smoothed error {})", self.controller.controller_string(state.inner.as_ref()), self.smoothing_factor, smoothed_error)
+    }
+}
 
+
+/// Jointly-coupled controller for two control variables converging to two targets
+/// 
+/// Independent controllers for two targets fight each other when each control variable also moves the
+/// other target. This controller updates both control variables from both relative errors at once:
+/// the relative change of control variable i is sum over j of gain_matrix[i][j] * error_j, where error_j
+/// is positive when target j is not reached. Off-diagonal gains compensate for the cross effects.
+/// Not a ControllerTrait, as it needs the actual and target values of both targets.
+pub struct ControllerCoupled {
+    /// Tolerance as a fraction of each target (e.g., 0.005 = 0.5%)
+    pub tolerance_fraction: f64,
+    /// Maximum relative change of a control variable per iteration (e.g., 0.2 = 20%)
+    pub max_adjustment_factor: f64,
+    /// gain_matrix[i][j] is the gain of control variable i on the relative error of target j
+    pub gain_matrix: [[f64; 2]; 2],
+}
+
+impl ControllerCoupled {
+    /// Create a new ControllerCoupled with the given gain matrix and default tolerance and maximum adjustment
+    pub fn new(gain_matrix: [[f64; 2]; 2]) -> Self {
+        Self {
+            tolerance_fraction: 0.005,
+            max_adjustment_factor: 0.2,
+            gain_matrix,
+        }
+    }
+    
+    /// Calculate the next controller state from actual and target values of both targets
+    /// 
+    /// # Returns
+    /// `true` if the control variables changed, `false` if both targets are within tolerance
+    pub fn next_controller_state(&self, previous_state: &dyn ControllerStateTrait, next_state: &mut dyn ControllerStateTrait, actuals: [f64; 2], targets: [f64; 2]) -> bool {
+        let previous = previous_state.as_any().downcast_ref::<ControllerStateMultiVariable>().unwrap();
+        let next = next_state.as_any_mut().downcast_mut::<ControllerStateMultiVariable>().unwrap();
+        
+        let errors = [
+            (targets[0] - actuals[0]) / targets[0],
+            (targets[1] - actuals[1]) / targets[1],
+        ];
+        next.previous_errors = errors.iter().map(|error| Some(*error)).collect();
+        
+        // Within tolerance on both targets - no change
+        if errors.iter().all(|error| error.abs() <= self.tolerance_fraction) {
+            next.variables = previous.variables.clone();
+            return false;
+        }
+        
+        for i in 0..2 {
+            let adjustment = (self.gain_matrix[i][0] * errors[0] + self.gain_matrix[i][1] * errors[1])
+                .clamp(-self.max_adjustment_factor, self.max_adjustment_factor);
+            next.variables[i] = previous.variables[i] * (1.0 + adjustment);
+        }
+        true
+    }
+    
+    /// Get both control variables
+    pub fn get_control_variables(&self, converge: &dyn ControllerStateTrait) -> [f64; 2] {
+        let state = converge.as_any().downcast_ref::<ControllerStateMultiVariable>().unwrap();
+        [state.variables[0], state.variables[1]]
+    }
+    
+    /// Create initial controller state with both control variables at 1.0
+    pub fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
+        Box::new(ControllerStateMultiVariable::new(2, 1.0))
+    }
+    
+    /// Get a string representation of the controller state
+    pub fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String {
+        let [variable1, variable2] = self.get_control_variables(converge);
+        format!("Coupled cntrl: {:.4}, {:.4}", variable1, variable2)
+    }
+}
+
+
+/// Dual (Lagrangian) controller treating targets as constraints
+/// 
+/// Keeps one non-negative dual variable (Lagrange multiplier) per constraint and updates it by a projected
+/// subgradient step: dual = max(0, dual + step_size * subgradient), where the subgradient is the normalized
+/// constraint violation (positive when violated, negative when there is slack). A dual grows while its
+/// constraint is violated and shrinks back to zero when the constraint has slack, so constraints that
+/// don't bind end up with a zero dual. The step size of a constraint is halved whenever its subgradient
+/// changes sign, which dampens oscillation around the optimum, and grows by 20% while the sign stays
+/// the same, which speeds up the approach.
+/// Not a ControllerTrait, as the subgradients of all constraints are needed together. The campaign
+/// computes the subgradients and turns the duals into bids (see CampaignLagrangian).
+pub struct ControllerDual {
+    /// Tolerance on the normalized violation (e.g., 0.005 = 0.5%)
+    pub tolerance_fraction: f64,
+    /// Initial subgradient step size per constraint
+    pub initial_step_sizes: Vec<f64>,
+    /// Upper bound of each dual (infinite unless set with with_max_duals)
+    pub max_duals: Vec<f64>,
+}
+
+impl ControllerDual {
+    /// Create a new ControllerDual with the given initial step size per constraint
+    pub fn new(initial_step_sizes: Vec<f64>) -> Self {
+        let num_constraints = initial_step_sizes.len();
+        Self {
+            tolerance_fraction: 0.005,
+            initial_step_sizes,
+            max_duals: vec![f64::INFINITY; num_constraints],
+        }
+    }
+    
+    /// Bound each dual from above, e.g. by the price of violating a soft constraint
+    pub fn with_max_duals(mut self, max_duals: Vec<f64>) -> Self {
+        assert_eq!(max_duals.len(), self.initial_step_sizes.len(), "Got {} max duals for {} constraints", max_duals.len(), self.initial_step_sizes.len());
+        self.max_duals = max_duals;
+        self
+    }
+    
+    /// Calculate the next controller state from the subgradient of every constraint
+    /// 
+    /// # Returns
+    /// `true` if any dual changed, `false` if every constraint is within tolerance, has slack with a zero dual
+    /// or is violated with the dual at its upper bound
+    pub fn next_controller_state(&self, previous_state: &dyn ControllerStateTrait, next_state: &mut dyn ControllerStateTrait, subgradients: &[f64]) -> bool {
+        let previous = previous_state.as_any().downcast_ref::<ControllerStateDual>().unwrap();
+        let next = next_state.as_any_mut().downcast_mut::<ControllerStateDual>().unwrap();
+        
+        let mut changed = false;
+        for (index, subgradient) in subgradients.iter().copied().enumerate() {
+            let dual = previous.duals[index];
+            // Complementary slackness: satisfied when within tolerance, or with slack and a zero dual
+            // (or violated with the dual at its bound, when the violation is cheaper than meeting the constraint)
+            let max_dual = self.max_duals[index];
+            let satisfied = subgradient.abs() <= self.tolerance_fraction || (subgradient < 0.0 && dual == 0.0) || (subgradient > 0.0 && dual == max_dual);
+            
+            let mut step_size = previous.step_sizes[index];
+            if let Some(previous_subgradient) = previous.previous_subgradients[index] {
+                if previous_subgradient * subgradient < 0.0 {
+                    step_size /= 2.0;
+                } else if !satisfied {
+                    step_size *= 1.2;
+                }
+            }
+            
+            next.step_sizes[index] = step_size;
+            next.previous_subgradients[index] = Some(subgradient);
+            next.duals[index] = if satisfied { dual } else { (dual + step_size * subgradient).clamp(0.0, max_dual) };
+            changed |= next.duals[index] != dual;
+        }
+        changed
+    }
+    
+    /// Get the dual variables
+    pub fn get_duals<'a>(&self, converge: &'a dyn ControllerStateTrait) -> &'a [f64] {
+        &converge.as_any().downcast_ref::<ControllerStateDual>().unwrap().duals
+    }
+    
+    /// Create initial controller state with all duals at zero
+    pub fn create_controller_state(&self) -> Box<dyn ControllerStateTrait> {
+        let num_constraints = self.initial_step_sizes.len();
+        Box::new(ControllerStateDual {
+            duals: vec![0.0; num_constraints],
+            step_sizes: self.initial_step_sizes.clone(),
+            previous_subgradients: vec![None; num_constraints],
+        })
+    }
+    
+    /// Get a string representation of the controller state
+    pub fn controller_string(&self, converge: &dyn ControllerStateTrait) -> String {
+        let duals: Vec<String> = self.get_duals(converge).iter().map(|dual| format!("{:.4}", dual)).collect();
+        format!("Dual cntrl: [{}]", duals.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adaptive_gain_scale() {
+        let controller = ControllerAdaptiveGain::new();
+        // Full gains far from the target, the minimum fraction at it
+        assert_eq!(controller.gain_scale(2.0, 1.0), 1.0);
+        assert_eq!(controller.gain_scale(1.0, 1.0), controller.min_gain_fraction);
+        // Negative targets scale by the error relative to their magnitude, like positive ones
+        assert_eq!(controller.gain_scale(-2.0, -1.0), controller.gain_scale(2.0, 1.0));
+        assert!((controller.gain_scale(-1.15, -1.0) - 0.5).abs() < 1e-9);
+        // A zero target has no relative error, full gains apply
+        assert_eq!(controller.gain_scale(0.0, 0.0), 1.0);
+        assert_eq!(controller.gain_scale(0.5, 0.0), 1.0);
+    }
+}
@@ -1,13 +1,81 @@
-use crate::simulationrun::{Marketplace, SimulationRun, SimulationStat};
+use crate::simulationrun::{Marketplace, SimulationRun, SimulationStat, CampaignStat, TargetStat, OnlineUpdates};
 use crate::campaigns::Campaigns;
 use crate::sellers::Sellers;
 use crate::logger::{Logger, LogEvent, FileReceiver, sanitize_filename};
 use crate::logln;
-use std::path::PathBuf;
-use crate::utils::VERBOSE_AUCTION;
+use crate::warnln;
+use crate::utils::{VERBOSE_AUCTION, CHECKPOINT_INTERVAL, RAND_SEED};
+use crate::checkpoint::{Checkpoint, marketplace_fingerprint};
+use crate::convergence_criteria::{ConvergenceCriterionTrait, ConvergenceCriterionControllersUnchanged, ConvergenceObservation, ConvergenceDecision};
+use crate::hooks::{SimulationHooks, SimulationObserverTrait, auction_csv_hook};
+use crate::oscillation::{OscillationDetector, DEFAULT_OSCILLATION_WINDOW};
+use crate::timeouts::{TimeoutError, check_timeouts, variant_timeout};
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 pub use crate::controller_state::ControllerStateTrait;
 
+/// Save controller states, one line per campaign or seller: its index, then its states separated by ';'
+fn save_controller_states(controller_states: &[Vec<Box<dyn ControllerStateTrait>>]) -> String {
+    controller_states.iter()
+        .enumerate()
+        .map(|(index, states)| format!("{}: {}", index, states.iter().map(|state| state.save()).collect::<Vec<_>>().join(" ; ")))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Load controller states saved by save_controller_states into existing states of the same layout
+fn load_controller_states(controller_states: &mut [Vec<Box<dyn ControllerStateTrait>>], saved: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let lines: Vec<&str> = saved.lines().collect();
+    if lines.len() != controller_states.len() {
+        return Err(format!("Saved controller states have {} lines, expected {}", lines.len(), controller_states.len()).into());
+    }
+    for (index, (states, line)) in controller_states.iter_mut().zip(lines).enumerate() {
+        let saved_states = line.strip_prefix(&format!("{}:", index))
+            .ok_or_else(|| format!("Saved controller states on line {} don't start with index {}", index + 1, index))?;
+        let saved_states: Vec<&str> = saved_states.split(';').collect();
+        if saved_states.len() != states.len() {
+            return Err(format!("Saved controller states on line {} have {} states, expected {}", index + 1, saved_states.len(), states.len()).into());
+        }
+        for (state, saved_state) in states.iter_mut().zip(saved_states) {
+            state.load(saved_state)?;
+        }
+    }
+    Ok(())
+}
+
+/// Take over the controller states of every campaign or seller whose states match the ones given in number and
+/// type (by index), keeping the others
+fn warm_start_controller_states(controller_states: &mut [Vec<Box<dyn ControllerStateTrait>>], from: &[Vec<Box<dyn ControllerStateTrait>>]) {
+    for (states, from_states) in controller_states.iter_mut().zip(from) {
+        let same_layout = states.len() == from_states.len()
+            && states.iter().zip(from_states).all(|(state, from_state)| state.as_any().type_id() == from_state.as_any().type_id());
+        if same_layout {
+            *states = from_states.iter().map(|state| state.clone_box()).collect();
+        }
+    }
+}
+
+/// Log one controller trajectory CSV row per convergence target of a campaign or seller
+/// Each target is paired with the control variable at the same position (extra control variables aren't logged)
+fn log_controller_trajectory(iteration: usize, kind: &str, id: usize, targets: &[(String, f64, f64)], control_variables: &[f64], logger: &mut Logger) {
+    for ((target_name, actual, target), control_variable) in targets.iter().zip(control_variables) {
+        logln!(logger, LogEvent::Controller, "{},{},{},\"{}\",{},{},{}", iteration, kind, id, target_name, actual, target, control_variable);
+    }
+}
+
+/// Warn about the hard bounds of a campaign or seller that started binding in the next iteration's states
+fn warn_binding_bounds(iteration: usize, kind: &str, id: usize, name: &str, previous_bounds: &[(usize, f64)], next_bounds: &[(usize, f64)], logger: &mut Logger) {
+    for (target_index, bound) in next_bounds {
+        if !previous_bounds.contains(&(*target_index, *bound)) {
+            warnln!(logger, LogEvent::Convergence,
+                "Control variable bound binding: kind={} id={} name=\"{}\" target={} iteration={} bound={:.4}",
+                kind, id, name, target_index, iteration, bound);
+        }
+    }
+}
+
 /// Container for campaign controller states
 /// Uses dynamic dispatch to support different campaign types
 /// Each campaign can have multiple controller states (e.g., CampaignGeneral can have 1 or more)
@@ -38,6 +106,19 @@ impl CampaignControllerStates {
         let converged = vec![false; campaigns.campaigns.len()];
         Self { campaign_controller_states, converged }
     }
+    
+    /// Save the controller states (e.g., converged pacing) as text, one line per campaign
+    pub fn save(&self) -> String {
+        save_controller_states(&self.campaign_controller_states)
+    }
+    
+    /// Create campaign controller states from campaigns and load text saved by `save` into them
+    /// The campaigns must be set up the same way as when the states were saved
+    pub fn load(campaigns: &Campaigns, saved: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut campaign_controller_states = Self::new(campaigns);
+        load_controller_states(&mut campaign_controller_states.campaign_controller_states, saved)?;
+        Ok(campaign_controller_states)
+    }
 }
 
 /// Container for seller controller states
@@ -70,6 +151,72 @@ impl SellerControllerStates {
         let converged = vec![false; sellers.sellers.len()];
         Self { seller_controller_states, converged }
     }
+    
+    /// Save the controller states (e.g., converged boosts) as text, one line per seller
+    pub fn save(&self) -> String {
+        save_controller_states(&self.seller_controller_states)
+    }
+    
+    /// Create seller controller states from sellers and load text saved by `save` into them
+    /// The sellers must be set up the same way as when the states were saved
+    pub fn load(sellers: &Sellers, saved: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut seller_controller_states = Self::new(sellers);
+        load_controller_states(&mut seller_controller_states.seller_controller_states, saved)?;
+        Ok(seller_controller_states)
+    }
+}
+
+/// Perturbation of a campaign's converged controller state, for robustness experiments
+/// (see SimulationConverge::with_perturbation)
+#[derive(Debug, Clone)]
+pub struct Perturbation {
+    /// Campaign whose control variables are perturbed
+    pub campaign_id: usize,
+    /// Factor the campaign's control variables are multiplied by (e.g., 2.0 doubles pacing)
+    pub factor: f64,
+}
+
+/// Smallest share of a campaign's target left to a period of a multi-period run, as a fraction of the target
+/// (periods after the target is met are paced to this share)
+const MIN_PERIOD_SHARE: f64 = 0.01;
+
+/// Statistics of a multi-period run (see SimulationConverge::run_periods)
+pub struct PeriodsStat {
+    /// Statistics of each period's simulation run, with the campaign targets compared with the period extrapolated
+    /// over the flight
+    pub period_stats: Vec<SimulationStat>,
+    /// Statistics of each campaign summed over all periods (indexed by campaign_id)
+    pub campaign_stats: Vec<CampaignStat>,
+    /// Actual value over all periods, target value and tolerance of every convergence target, per campaign
+    pub campaign_targets: Vec<Vec<TargetStat>>,
+}
+
+impl PeriodsStat {
+    /// Output each period's campaign results and the campaigns' results over all periods
+    pub fn printout(&self, campaigns: &Campaigns, logger: &mut Logger) {
+        logln!(logger, LogEvent::Variant, "\n=== Period Statistics ===");
+        for (period, stats) in self.period_stats.iter().enumerate() {
+            let campaign_results: Vec<String> = stats.campaign_stats.iter().enumerate()
+                .map(|(index, campaign_stat)| if campaigns.is_active(index, period) {
+                    format!("Campaign {}: impressions {:.2}, spend {:.2}, value {:.2}", index, campaign_stat.impressions_obtained, campaign_stat.total_buyer_charge, campaign_stat.total_value)
+                } else {
+                    format!("Campaign {}: inactive", index)
+                })
+                .collect();
+            logln!(logger, LogEvent::Variant, "Period {}: {}", period + 1, campaign_results.join(" | "));
+        }
+        
+        logln!(logger, LogEvent::Variant, "\n=== Statistics over {} Periods ===", self.period_stats.len());
+        for (index, campaign_stat) in self.campaign_stats.iter().enumerate() {
+            logln!(logger, LogEvent::Variant, "\nCampaign {} ({})", index, campaigns.campaigns[index].campaign_name());
+            logln!(logger, LogEvent::Variant, "  Impressions Obtained: {:.2}", campaign_stat.impressions_obtained);
+            logln!(logger, LogEvent::Variant, "  Costs (supply/virtual/buyer): {:.2} / {:.2} / {:.2}", campaign_stat.total_supply_cost, campaign_stat.total_virtual_cost, campaign_stat.total_buyer_charge);
+            logln!(logger, LogEvent::Variant, "  Obtained Value: {:.2}", campaign_stat.total_value);
+            for target in &self.campaign_targets[index] {
+                logln!(logger, LogEvent::Variant, "  Target [{}]: {:.4} vs. {:.4} ({})", target.name, target.actual, target.target, target.tolerance.tolerance_string());
+            }
+        }
+    }
 }
 
 /// Object for running simulation convergence with pacing adjustments
@@ -77,6 +224,30 @@ pub struct SimulationConverge {
     pub marketplace: Marketplace,
     pub initial_campaign_controller_states: CampaignControllerStates,
     pub initial_seller_controller_states: SellerControllerStates,
+    /// Hooks subscribed to lifecycle events of the convergence loop
+    /// Interior mutability allows registering and dispatching hooks through a shared reference
+    pub hooks: RefCell<SimulationHooks>,
+    /// Number of consecutive sign-alternating control variable changes reported as sustained oscillation
+    pub oscillation_window: usize,
+    /// Perturbation applied once after the first convergence, None to stop at the first convergence
+    pub perturbation: Option<Perturbation>,
+    /// Whether what a period leaves of the campaigns' targets rolls over to the remaining periods (see run_periods)
+    pub period_carryover: bool,
+    /// Number of impressions between online updates of the campaign controller states within each run, None to
+    /// update them only between runs (see with_online_updates)
+    pub online_batch_size: Option<usize>,
+    /// Number of iterations between checkpoints of convergence runs, 0 for no checkpoints (see with_checkpoints)
+    pub checkpoint_interval: usize,
+    /// Criterion deciding when a convergence run stops (see with_convergence_criterion)
+    /// Interior mutability lets criteria keep state across the iterations of a run
+    pub convergence_criterion: RefCell<Box<dyn ConvergenceCriterionTrait>>,
+    /// Maximum wall-clock duration of a convergence run, None for no limit (see with_timeout)
+    pub timeout: Option<Duration>,
+    /// Whether the statistics of every iteration are recorded into history (see with_history)
+    pub record_history: bool,
+    /// Statistics of every iteration of the last convergence run (see history)
+    /// Interior mutability allows recording them through a shared reference
+    pub history: RefCell<Vec<SimulationStat>>,
 }
 
 impl SimulationConverge {
@@ -94,9 +265,166 @@ impl SimulationConverge {
             marketplace,
             initial_campaign_controller_states,
             initial_seller_controller_states,
+            hooks: RefCell::new(SimulationHooks::new()),
+            oscillation_window: DEFAULT_OSCILLATION_WINDOW,
+            perturbation: None,
+            period_carryover: true,
+            online_batch_size: None,
+            checkpoint_interval: CHECKPOINT_INTERVAL.load(Ordering::Relaxed),
+            convergence_criterion: RefCell::new(ConvergenceCriterionControllersUnchanged::new()),
+            timeout: variant_timeout(),
+            record_history: false,
+            history: RefCell::new(Vec::new()),
         }
     }
     
+    /// Attach an observer of the simulation lifecycle (see SimulationObserverTrait), keeping a clone of the
+    /// observer to read its measurements after the run
+    pub fn with_observer<O: SimulationObserverTrait + 'static>(self, observer: Rc<RefCell<O>>) -> Self {
+        self.hooks.borrow_mut().add_observer(observer);
+        self
+    }
+    
+    /// Robustness mode: after the first convergence, multiply the control variables of a campaign
+    /// by `factor` and continue until convergence is reached again
+    /// 
+    /// The iterations needed to recover are reported in SimulationStat::perturbation_recovery_iterations.
+    /// Convergence iterations count both the initial convergence and the recovery.
+    /// 
+    /// # Panics
+    /// Panics if the campaign doesn't exist or the factor is not positive
+    pub fn with_perturbation(mut self, campaign_id: usize, factor: f64) -> Self {
+        assert!(campaign_id < self.marketplace.campaigns.campaigns.len(), "Perturbed campaign {} doesn't exist", campaign_id);
+        assert!(factor > 0.0, "Perturbation factor must be positive, got {}", factor);
+        self.perturbation = Some(Perturbation { campaign_id, factor });
+        self
+    }
+    
+    /// Start convergence from the given controller states instead of the controllers' initial states
+    /// (warm start), e.g. converged states of a previous variant or states loaded from a file
+    /// 
+    /// Convergence flags are reset, so the first iteration is evaluated like any other.
+    pub fn with_initial_states(mut self, campaign_controller_states: CampaignControllerStates, seller_controller_states: SellerControllerStates) -> Self {
+        assert_eq!(campaign_controller_states.campaign_controller_states.len(), self.marketplace.campaigns.campaigns.len(), "Initial campaign controller states must match the campaigns");
+        assert_eq!(seller_controller_states.seller_controller_states.len(), self.marketplace.sellers.sellers.len(), "Initial seller controller states must match the sellers");
+        self.initial_campaign_controller_states = campaign_controller_states;
+        self.initial_campaign_controller_states.converged.fill(false);
+        self.initial_seller_controller_states = seller_controller_states;
+        self.initial_seller_controller_states.converged.fill(false);
+        self
+    }
+    
+    /// Warm start convergence from the final controller states of a previous run (see run_variant_with_states),
+    /// e.g. of a near-identical variant
+    /// 
+    /// Unlike with_initial_states, the campaigns and sellers don't need to be set up the same way: each campaign
+    /// and seller takes over the states at its index if they match its own in number and type, and otherwise
+    /// starts from its controllers' initial states. Convergence flags are reset.
+    pub fn with_warm_start(mut self, campaign_controller_states: &CampaignControllerStates, seller_controller_states: &SellerControllerStates) -> Self {
+        warm_start_controller_states(&mut self.initial_campaign_controller_states.campaign_controller_states, &campaign_controller_states.campaign_controller_states);
+        self.initial_campaign_controller_states.converged.fill(false);
+        warm_start_controller_states(&mut self.initial_seller_controller_states.seller_controller_states, &seller_controller_states.seller_controller_states);
+        self.initial_seller_controller_states.converged.fill(false);
+        self
+    }
+    
+    /// Set whether what a period leaves of the campaigns' targets rolls over to the remaining periods of a
+    /// multi-period run (see run_periods, carryover is on by default)
+    pub fn with_period_carryover(mut self, period_carryover: bool) -> Self {
+        self.period_carryover = period_carryover;
+        self
+    }
+    
+    /// Online mini-batch mode: update the campaign controller states every `batch_size` impressions within each run,
+    /// as real-time pacing systems do, besides the update between runs
+    /// 
+    /// Every run starts from the controller states of the convergence loop, and each online update takes one
+    /// controller step from them on the campaigns' statistics of the run so far, extrapolated to all impressions by
+    /// their weight. Pacing thus reacts to delivery within the run, while the states of the convergence loop are
+    /// still updated, and convergence decided, between runs. Seller controller states are only updated between runs.
+    /// 
+    /// # Panics
+    /// Panics if the batch size is zero
+    pub fn with_online_updates(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "Online update batch size must be positive");
+        self.online_batch_size = Some(batch_size);
+        self
+    }
+    
+    /// Select the criterion deciding when a convergence run stops, instead of stopping once no campaign or seller
+    /// controller changes its state (see convergence_criteria.rs)
+    /// 
+    /// Runs still stop without converging at maximum iterations. With perturbation, the first convergence
+    /// by the criterion triggers the perturbation.
+    pub fn with_convergence_criterion(self, convergence_criterion: Box<dyn ConvergenceCriterionTrait>) -> Self {
+        *self.convergence_criterion.borrow_mut() = convergence_criterion;
+        self
+    }
+    
+    /// Checkpoint convergence runs every `interval` iterations, so an interrupted run resumes where it left off
+    /// (defaults to the --checkpoint command line option, 0 turns checkpoints off)
+    /// 
+    /// Checkpoints are written to the scenario's log directory, one file per variant and RAND_SEED, and a run
+    /// finding a checkpoint of the same variant, seed and marketplace resumes from it (see checkpoint.rs).
+    /// A run that converges removes its checkpoint, one that reaches maximum iterations or is stopped by its
+    /// convergence criterion keeps it, so it can be continued later. Oscillation diagnostics and convergence
+    /// criteria restart on resume, and hooks only see the iterations run after it.
+    pub fn with_checkpoints(mut self, interval: usize) -> Self {
+        self.checkpoint_interval = interval;
+        self
+    }
+    
+    /// Record the statistics of every iteration of convergence runs, read with history after the run
+    /// 
+    /// Off by default, since every iteration's statistics are kept in full, including the bid history of
+    /// campaigns recording one.
+    pub fn with_history(mut self) -> Self {
+        self.record_history = true;
+        self
+    }
+    
+    /// Statistics of every iteration of the last convergence run, in order (the last one being the final
+    /// statistics), e.g. to check the trajectory of a target (see trajectory.rs)
+    /// 
+    /// Only recorded when enabled with with_history, empty otherwise. Kept after run_variant returns, whether
+    /// the run converged or not. A run resumed from a checkpoint only has the iterations run after it.
+    pub fn history(&self) -> Ref<'_, Vec<SimulationStat>> {
+        self.history.borrow()
+    }
+    
+    /// Limit convergence runs to a maximum wall-clock duration (defaults to the --variant-timeout command line
+    /// option, no limit without it)
+    /// 
+    /// The limit, and the limit of the scenario running (--scenario-timeout), are checked after every iteration
+    /// that didn't converge. A run exceeding either stops, and run_variant returns a TimeoutError instead of
+    /// running on to maximum iterations (see timeouts.rs).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    
+    /// Online update within a run (see with_online_updates): set the controller states campaigns bid with to one
+    /// controller step from the run's starting states, given the run so far
+    fn online_update(&self, simulation_run: &SimulationRun, run_campaign_controller_states: &CampaignControllerStates, campaign_controller_states: &mut CampaignControllerStates, logger: &mut Logger) {
+        let impressions = &self.marketplace.impressions.impressions;
+        let impressions_auctioned = simulation_run.impressions_auctioned();
+        let auctioned_share = impressions[..impressions_auctioned].iter().map(|impression| impression.weight).sum::<f64>()
+            / impressions.iter().map(|impression| impression.weight).sum::<f64>();
+        let stats = SimulationStat::new(&self.marketplace, simulation_run, 0);
+        
+        let mut control_variables = Vec::with_capacity(self.marketplace.campaigns.campaigns.len());
+        for (index, campaign) in self.marketplace.campaigns.campaigns.iter().enumerate() {
+            let mut campaign_stat = stats.campaign_stats[index].clone();
+            campaign_stat.scale(1.0 / auctioned_share);
+            let next_states = &mut campaign_controller_states.campaign_controller_states[index];
+            *next_states = run_campaign_controller_states.campaign_controller_states[index].iter().map(|state| state.clone_box()).collect();
+            campaign.next_controller_state(&run_campaign_controller_states.campaign_controller_states[index], next_states, &campaign_stat);
+            let next_state_refs: Vec<&dyn ControllerStateTrait> = next_states.iter().map(|state| state.as_ref()).collect();
+            control_variables.push(format!("Campaign {}: control variables {:?}", index, campaign.get_control_variables(&next_state_refs)));
+        }
+        logln!(logger, LogEvent::Simulation, "Online update after {} impressions: {}", impressions_auctioned, control_variables.join(" | "));
+    }
+    
     /// Run simulation loop with pacing adjustments (maximum max_iterations iterations)
     /// 
     /// # Arguments
@@ -106,32 +434,87 @@ impl SimulationConverge {
     /// * `logger` - Logger for event-based logging
     /// 
     /// # Returns
-    /// Returns a tuple of (final SimulationRun, final SimulationStat, final CampaignControllerStates, final SellerControllerStates, converged)
+    /// Returns a tuple of (final SimulationRun, final SimulationStat, final CampaignControllerStates, final SellerControllerStates, converged,
+    /// TimeoutError if the run was stopped by a wall-clock limit)
     pub fn run(
         &self,
         max_iterations: usize,
         scenario_name: &str,
         variant_name: &str,
         logger: &mut Logger,
-    ) -> (SimulationRun, SimulationStat, CampaignControllerStates, SellerControllerStates, bool) {
+    ) -> (SimulationRun, SimulationStat, CampaignControllerStates, SellerControllerStates, bool, Option<TimeoutError>) {
         
+        let started = Instant::now();
         let mut final_simulation_run = None;
         let mut final_stats = None;
         let mut final_campaign_controller_states = None;
         let mut final_seller_controller_states = None;
         let mut converged = false;
+        let mut timed_out: Option<TimeoutError> = None;
+        
+        // Iteration at which the perturbation was applied (None while it's pending or without perturbation)
+        let mut perturbed_at_iteration: Option<usize> = None;
         
         // Initialize current campaign controller states from input for the first iteration
         let mut current_campaign_controller_states = self.initial_campaign_controller_states.clone();
         // Initialize current seller controller states from input for the first iteration
         let mut current_seller_controller_states = self.initial_seller_controller_states.clone();
         
-        for iteration in 0..max_iterations {
+        let mut hooks = self.hooks.borrow_mut();
+        let mut convergence_criterion = self.convergence_criterion.borrow_mut();
+        convergence_criterion.reset();
+        let mut history = self.history.borrow_mut();
+        history.clear();
+        logln!(logger, LogEvent::Convergence, "{}: Converging until {}", variant_name, convergence_criterion.criterion_string());
+        // Reason the convergence criterion gave for stopping the run without converging
+        let mut stopped: Option<String> = None;
+        
+        // Fresh bids of the previous iteration, served as stale bids by sellers caching bids
+        let mut previous_bids: Vec<Vec<Option<f64>>> = Vec::new();
+        
+        // With checkpoints, resume from the last checkpoint of an interrupted run of this variant and seed
+        let checkpoint_interval = self.checkpoint_interval;
+        let checkpoint_path = logger.log_path(&format!("{}/checkpoint-{}-seed{}.txt", sanitize_filename(scenario_name), sanitize_filename(variant_name), RAND_SEED.get()));
+        let mut start_iteration = 0;
+        if checkpoint_interval > 0 {
+            match Checkpoint::read(&self.marketplace, &checkpoint_path) {
+                Ok(Some(checkpoint)) if checkpoint.iteration < max_iterations => {
+                    logln!(logger, LogEvent::Convergence, "{}: Resuming from checkpoint at iteration {}", variant_name, checkpoint.iteration + 1);
+                    start_iteration = checkpoint.iteration;
+                    perturbed_at_iteration = checkpoint.perturbed_at_iteration;
+                    current_campaign_controller_states = checkpoint.campaign_controller_states;
+                    current_seller_controller_states = checkpoint.seller_controller_states;
+                    previous_bids = checkpoint.previous_bids;
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    warnln!(logger, LogEvent::Convergence, "{}: Ignoring checkpoint {}: {}", variant_name, checkpoint_path.display(), error);
+                }
+            }
+        }
+        
+        // Oscillation diagnostics follow the control variables used in each iteration
+        let mut campaign_oscillation_detector = OscillationDetector::new("campaign", self.marketplace.campaigns.campaigns.len(), self.oscillation_window);
+        let mut seller_oscillation_detector = OscillationDetector::new("seller", self.marketplace.sellers.sellers.len(), self.oscillation_window);
+        
+        // Auction CSV logging is implemented as a hook, registered only for the duration of this run
+        let auction_csv_hook_id = if VERBOSE_AUCTION.load(Ordering::Relaxed) {
+            Some(hooks.on_auction_result(auction_csv_hook(self.marketplace.campaigns.campaign_to_value_group_mapping.clone())))
+        } else {
+            None
+        };
+        
+        // Controller trajectory CSV header
+        logln!(logger, LogEvent::Controller, "iteration,kind,id,target_name,actual,target,control_variable");
+        
+        for iteration in start_iteration..max_iterations {
             logln!(logger, LogEvent::Simulation, "\n=== {} - Iteration {} ===", variant_name, iteration + 1);
             
+            hooks.iteration_start(iteration + 1, logger);
+            
             // Create auction receiver for this iteration
             let auctions_receiver_id = if VERBOSE_AUCTION.load(Ordering::Relaxed) {
-                let receiver_id = logger.add_receiver(FileReceiver::new(&PathBuf::from(format!("log/{}/auctions-{}-iter{}.csv", sanitize_filename(scenario_name), sanitize_filename(variant_name), iteration + 1)), vec![LogEvent::Auction]));
+                let receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/auctions-{}-iter{}.csv", sanitize_filename(scenario_name), sanitize_filename(variant_name), iteration + 1)), vec![LogEvent::Auction]));
                 
                 // Write CSV header
                 let mut header_fields = vec![
@@ -158,8 +541,13 @@ impl SimulationConverge {
                 None
             };
             
-            // Run auctions for all impressions
-            let simulation_run = SimulationRun::new(&self.marketplace, &current_campaign_controller_states, &current_seller_controller_states, logger);
+            // Run auctions for all impressions, with online updates of the campaign controller states if enabled
+            let mut online_update = |simulation_run: &SimulationRun, campaign_controller_states: &mut CampaignControllerStates, logger: &mut Logger| {
+                self.online_update(simulation_run, &current_campaign_controller_states, campaign_controller_states, logger);
+            };
+            let online_updates = self.online_batch_size.map(|batch_size| OnlineUpdates { batch_size, update: &mut online_update });
+            let simulation_run = SimulationRun::new(&self.marketplace, &current_campaign_controller_states, &current_seller_controller_states, &previous_bids, &mut hooks, online_updates, iteration + 1, logger);
+            previous_bids.clone_from(&simulation_run.cached_bids);
             
             // Remove auction receiver after this iteration
             if let Some(id) = auctions_receiver_id {
@@ -167,7 +555,33 @@ impl SimulationConverge {
             }
 
             // Generate statistics (use iteration + 1 for 1-indexed iteration count)
-            let stats = SimulationStat::new(&self.marketplace, &simulation_run, iteration + 1);
+            let mut stats = SimulationStat::new(&self.marketplace, &simulation_run, iteration + 1);
+            
+            // Log the controller trajectory and detect sustained oscillation of the control variables used in this iteration
+            let mut campaign_control_variables = Vec::with_capacity(self.marketplace.campaigns.campaigns.len());
+            let mut seller_control_variables = Vec::with_capacity(self.marketplace.sellers.sellers.len());
+            let mut seller_targets = Vec::with_capacity(self.marketplace.sellers.sellers.len());
+            for (index, campaign) in self.marketplace.campaigns.campaigns.iter().enumerate() {
+                let controller_states: Vec<&dyn ControllerStateTrait> = current_campaign_controller_states.campaign_controller_states[index].iter().map(|state| state.as_ref()).collect();
+                let control_variables = campaign.get_control_variables(&controller_states);
+                let targets = campaign.get_targets_actual_and_target(&controller_states, &stats.campaign_stats[index]);
+                log_controller_trajectory(iteration + 1, "campaign", index, &targets, &control_variables, logger);
+                campaign_oscillation_detector.observe(iteration + 1, index, campaign.campaign_name(), &control_variables, logger);
+                stats.campaign_targets.push(targets.into_iter().enumerate()
+                    .map(|(target_index, (name, actual, target))| TargetStat { name, actual, target, tolerance: self.marketplace.campaigns.target_tolerance(index, target_index) })
+                    .collect());
+                campaign_control_variables.push(control_variables);
+            }
+            for (index, seller) in self.marketplace.sellers.sellers.iter().enumerate() {
+                let control_variable = seller.get_control_variable(current_seller_controller_states.seller_controller_states[index][0].as_ref());
+                let targets = seller.get_targets_actual_and_target(&stats.seller_stats[index]);
+                log_controller_trajectory(iteration + 1, "seller", index, &targets, &[control_variable], logger);
+                seller_oscillation_detector.observe(iteration + 1, index, seller.seller_name(), &[control_variable], logger);
+                seller_control_variables.push(vec![control_variable]);
+                seller_targets.push(targets);
+            }
+            stats.campaign_oscillations = campaign_oscillation_detector.oscillations().clone();
+            stats.seller_oscillations = seller_oscillation_detector.oscillations().clone();
             
             // Calculate next iteration's campaign controller states based on current results
             let mut next_campaign_controller_states = current_campaign_controller_states.clone();
@@ -179,6 +593,9 @@ impl SimulationConverge {
                 
                 // Use the campaign's next_controller_state method (now part of CampaignTrait)
                 let campaign_pacing_changed = campaign.next_controller_state(previous_states, next_states, campaign_stat);
+                let previous_state_refs: Vec<&dyn ControllerStateTrait> = previous_states.iter().map(|state| state.as_ref()).collect();
+                let next_state_refs: Vec<&dyn ControllerStateTrait> = next_states.iter().map(|state| state.as_ref()).collect();
+                warn_binding_bounds(iteration + 1, "campaign", index, campaign.campaign_name(), &campaign.binding_bounds(&previous_state_refs), &campaign.binding_bounds(&next_state_refs), logger);
                 pacing_changed |= campaign_pacing_changed;
                 // Track convergence: if pacing didn't change, this campaign converged
                 // Update in current states for printing, and also in next states for next iteration
@@ -196,6 +613,9 @@ impl SimulationConverge {
                 
                 // Use the seller's next_controller_state method
                 let seller_boost_changed = seller.next_controller_state(previous_states, next_states, seller_stat);
+                let previous_state_refs: Vec<&dyn ControllerStateTrait> = previous_states.iter().map(|state| state.as_ref()).collect();
+                let next_state_refs: Vec<&dyn ControllerStateTrait> = next_states.iter().map(|state| state.as_ref()).collect();
+                warn_binding_bounds(iteration + 1, "seller", index, seller.seller_name(), &seller.binding_bounds(&previous_state_refs), &seller.binding_bounds(&next_state_refs), logger);
                 boost_changed |= seller_boost_changed;
                 // Track convergence: if boost didn't change, this seller converged
                 // Update in current states for printing, and also in next states for next iteration
@@ -209,26 +629,93 @@ impl SimulationConverge {
             // Output seller statistics for each iteration (using the controller states that were actually used)
             stats.printout_sellers(&self.marketplace.sellers, &current_seller_controller_states, logger, LogEvent::Simulation);
             
+            hooks.iteration_end(iteration + 1, &stats, logger);
+            
             // Keep track of final simulation run and stats
+            let decision = convergence_criterion.check(&ConvergenceObservation {
+                iteration: iteration + 1,
+                controllers_changed: pacing_changed || boost_changed,
+                campaign_targets: &stats.campaign_targets,
+                seller_targets: &seller_targets,
+                campaign_control_variables: &campaign_control_variables,
+                seller_control_variables: &seller_control_variables,
+            });
+            let iteration_converged = decision == ConvergenceDecision::Converged;
+            // Robustness mode perturbs the first converged iteration's campaign once and continues until it recovers
+            let perturbing = iteration_converged && perturbed_at_iteration.is_none() && self.perturbation.is_some();
+            if let Some(perturbed_at_iteration) = perturbed_at_iteration {
+                stats.perturbation_recovery_iterations = Some(iteration + 1 - perturbed_at_iteration);
+            }
+            if iteration_converged && !perturbing {
+                hooks.converged(&stats, logger);
+            }
+            if self.record_history {
+                history.push(stats.clone());
+            }
                 final_simulation_run = Some(simulation_run);
                 final_stats = Some(stats);
             final_campaign_controller_states = Some(current_campaign_controller_states.clone());
             final_seller_controller_states = Some(current_seller_controller_states.clone());
             
-            // Break early if no pacing or boost changes were made (converged)
-            if !pacing_changed && !boost_changed {
+            if perturbing {
+                let perturbation = self.perturbation.as_ref().unwrap();
+                let campaign = &self.marketplace.campaigns.campaigns[perturbation.campaign_id];
+                logln!(logger, LogEvent::Convergence, "{}: Converged after {} iterations, perturbing control variables of campaign {} by factor {:.4}",
+                    variant_name, iteration + 1, campaign.campaign_name(), perturbation.factor);
+                campaign.perturb_controller_states(&mut next_campaign_controller_states.campaign_controller_states[perturbation.campaign_id], perturbation.factor);
+                next_campaign_controller_states.converged.fill(false);
+                next_seller_controller_states.converged.fill(false);
+                perturbed_at_iteration = Some(iteration + 1);
+            } else if iteration_converged {
+                // Break early if no pacing or boost changes were made (converged)
                 converged = true;
                 logln!(logger, LogEvent::Convergence, "{}: Converged after {} iterations", variant_name, iteration + 1);
                 break;
+            } else if let ConvergenceDecision::Stop(reason) = decision {
+                logln!(logger, LogEvent::Convergence, "{}: Stopped after {} iterations ({})", variant_name, iteration + 1, reason);
+                stopped = Some(reason);
+            } else if let Some(timeout_error) = check_timeouts(variant_name, started, self.timeout, iteration + 1) {
+                logln!(logger, LogEvent::Convergence, "{}: Stopped after {} iterations ({})", variant_name, iteration + 1, timeout_error);
+                stopped = Some(timeout_error.to_string());
+                timed_out = Some(timeout_error);
             }
             
             // Prepare for next iteration
             current_campaign_controller_states = next_campaign_controller_states;
             current_seller_controller_states = next_seller_controller_states;
+            
+            // Checkpoint what the next iteration starts from
+            if checkpoint_interval > 0 && ((iteration + 1) % checkpoint_interval == 0 || iteration + 1 == max_iterations || stopped.is_some()) {
+                let checkpoint = Checkpoint {
+                    seed: RAND_SEED.get(),
+                    marketplace_fingerprint: marketplace_fingerprint(&self.marketplace),
+                    iteration: iteration + 1,
+                    perturbed_at_iteration,
+                    campaign_controller_states: current_campaign_controller_states.clone(),
+                    seller_controller_states: current_seller_controller_states.clone(),
+                    previous_bids: previous_bids.clone(),
+                };
+                if let Err(error) = checkpoint.write(&checkpoint_path) {
+                    warnln!(logger, LogEvent::Convergence, "{}: Failed to write checkpoint {}: {}", variant_name, checkpoint_path.display(), error);
+                }
+            }
+            
+            if stopped.is_some() {
+                break;
+            }
+        }
+        
+        // A converged run is complete, a further run of this variant starts from scratch
+        if checkpoint_interval > 0 && converged {
+            let _ = std::fs::remove_file(&checkpoint_path);
+        }
+        
+        if let Some(id) = auction_csv_hook_id {
+            hooks.remove(id);
         }
         
         // Log if we reached max iterations
-        if !converged {
+        if !converged && stopped.is_none() {
             logln!(logger, LogEvent::Convergence, "{}: Reached maximum iterations ({})", variant_name, max_iterations);
         }
         
@@ -240,6 +727,7 @@ impl SimulationConverge {
             final_campaign_controller_states.expect("Should have at least one iteration"),
             final_seller_controller_states.expect("Should have at least one iteration"),
             converged,
+            timed_out,
         )
     }
     
@@ -253,7 +741,8 @@ impl SimulationConverge {
     /// * `logger` - Logger for event-based logging
     /// 
     /// # Returns
-    /// Returns a Result with the final SimulationStat, or an error if convergence failed
+    /// Returns a Result with the final SimulationStat, or an error if convergence failed (a TimeoutError if
+    /// the variant or its scenario exceeded its wall-clock limit)
     pub fn run_variant(
         &self,
         variant_description: &str,
@@ -262,27 +751,53 @@ impl SimulationConverge {
         max_iterations: usize,
         logger: &mut Logger,
     ) -> Result<SimulationStat, Box<dyn std::error::Error>> {
+        let (stats, _final_campaign_controller_states, _final_seller_controller_states) = self.run_variant_with_states(variant_description, scenario_name, variant_name, max_iterations, logger)?;
+        Ok(stats)
+    }
+    
+    /// Same as run_variant, additionally returning the converged controller states
+    /// (e.g., to save them or warm start a follow-up variant with `with_initial_states`)
+    pub fn run_variant_with_states(
+        &self,
+        variant_description: &str,
+        scenario_name: &str,
+        variant_name: &str,
+        max_iterations: usize,
+        logger: &mut Logger,
+    ) -> Result<(SimulationStat, CampaignControllerStates, SellerControllerStates), Box<dyn std::error::Error>> {
+        // A scenario that has run out of time doesn't start further variants
+        if let Some(timeout_error) = check_timeouts(variant_name, Instant::now(), None, 0) {
+            return Err(Box::new(timeout_error));
+        }
+        
         // Add variant iterations receiver (for simulation and convergence events)
-        let iterations_receiver_id = logger.add_receiver(FileReceiver::new(&PathBuf::from(format!("log/{}/iterations-{}.log", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Simulation, LogEvent::Convergence]));
+        let iterations_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/iterations-{}.log", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Simulation, LogEvent::Convergence]));
         
         // Add variant receiver (for variant events)
-        let variant_receiver_id = logger.add_receiver(FileReceiver::new(&PathBuf::from(format!("log/{}/variant-{}.log", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Variant]));
+        let variant_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/variant-{}.log", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Variant]));
+        
+        // Add controller trajectory receiver (CSV for plotting convergence)
+        let controller_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/controller-{}.csv", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Controller]));
         
         // Add impressions receiver (for logging impression data)
-    //    let impressions_receiver_id = logger.add_receiver(FileReceiver::new(&PathBuf::from(format!("log/{}/imps-{}.log", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Impression]));
+    //    let impressions_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/imps-{}.log", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Impression]));
         
         logln!(logger, LogEvent::Variant, "\n=== {} ===", variant_description);
         
         self.marketplace.printout(logger);
         
         // Run simulation loop with pacing adjustments
-        let (_final_simulation_run, stats, final_campaign_controller_states, final_seller_controller_states, converged) = self.run(max_iterations, scenario_name, variant_name, logger);
+        let (_final_simulation_run, stats, final_campaign_controller_states, final_seller_controller_states, converged, timed_out) = self.run(max_iterations, scenario_name, variant_name, logger);
         
         // Check for convergence failure
         if !converged {
             // Remove variant-specific receivers before returning error
+            logger.remove_receiver(controller_receiver_id);
             logger.remove_receiver(variant_receiver_id);
             logger.remove_receiver(iterations_receiver_id);
+            if let Some(timeout_error) = timed_out {
+                return Err(Box::new(timeout_error));
+            }
             return Err(format!("Variant '{}' failed to converge within {} iterations", variant_name, max_iterations).into());
         }
         
@@ -291,10 +806,231 @@ impl SimulationConverge {
         
         // Remove variant-specific receivers
 //        logger.remove_receiver(impressions_receiver_id);
+        logger.remove_receiver(controller_receiver_id);
         logger.remove_receiver(variant_receiver_id);
         logger.remove_receiver(iterations_receiver_id);
         
-        Ok(stats)
+        Ok((stats, final_campaign_controller_states, final_seller_controller_states))
+    }
+    
+    /// Run the auctions once with the given controller states, without updating them (no convergence), e.g. to
+    /// replay converged states on a modified marketplace (see counterfactual.rs)
+    /// 
+    /// Random draws of the run are seeded as in iteration `iteration` of a convergence run, so replaying the
+    /// converged states with the iteration they converged in reproduces the converged statistics (unless sellers
+    /// cache bids, which no bids are cached for). Hooks don't see the run.
+    pub fn replay(&self, campaign_controller_states: &CampaignControllerStates, seller_controller_states: &SellerControllerStates, iteration: usize, logger: &mut Logger) -> SimulationStat {
+        let simulation_run = SimulationRun::new(&self.marketplace, campaign_controller_states, seller_controller_states, &[], &mut SimulationHooks::new(), None, iteration, logger);
+        SimulationStat::new(&self.marketplace, &simulation_run, iteration)
+    }
+    
+    /// Multi-period mode: run the days of the impressions one after another as the periods of a flight,
+    /// instead of converging on all impressions at once
+    /// 
+    /// Each period is a single simulation run on the impressions of its day, after which the controllers are
+    /// updated once (day-by-day pacing), so a period starts from the controller states the previous one left.
+    /// Campaign targets are targets for the whole flight, and campaign controllers compare them with the period
+    /// extrapolated over the flight, so each period is paced to its share of the targets, which follows its traffic
+    /// (the impressions it offers). With carryover, the share is taken of what the past periods left of the target,
+    /// so what a period leaves unspent rolls over to the remaining periods (e.g. with even traffic, a budget target
+    /// paces each period to the remaining budget divided by the remaining periods). Without carryover, the share
+    /// is taken of the whole target. Seller controllers are updated on each period's own statistics.
+    /// 
+    /// Campaigns active on some days only (Campaigns::set_active_days) share their targets between their active
+    /// periods, and their controllers aren't updated in the periods they are inactive, so a campaign launched
+    /// mid-flight starts from its initial controller states and one that ended keeps its last ones.
+    /// 
+    /// # Returns
+    /// Returns a tuple of (PeriodsStat, CampaignControllerStates, SellerControllerStates), the controller states
+    /// being those a further period would start from
+    pub fn run_periods(&mut self, variant_name: &str, logger: &mut Logger) -> (PeriodsStat, CampaignControllerStates, SellerControllerStates) {
+        let all_impressions = self.marketplace.shared_impressions();
+        let num_periods = all_impressions.num_days;
+        // Traffic of each period, by which the campaigns' targets are shared between periods
+        let period_impressions: Vec<usize> = (0..num_periods)
+            .map(|period| all_impressions.impressions.iter().filter(|impression| impression.day() == period).count())
+            .collect();
+        // Traffic of each period as seen by each campaign, none in the periods it is inactive
+        let campaign_period_impressions: Vec<Vec<usize>> = (0..self.marketplace.campaigns.campaigns.len())
+            .map(|campaign_id| (0..num_periods)
+                .map(|period| if self.marketplace.campaigns.is_active(campaign_id, period) { period_impressions[period] } else { 0 })
+                .collect())
+            .collect();
+        
+        let mut current_campaign_controller_states = self.initial_campaign_controller_states.clone();
+        let mut current_seller_controller_states = self.initial_seller_controller_states.clone();
+        let mut hooks = self.hooks.borrow_mut();
+        
+        let mut period_stats: Vec<SimulationStat> = Vec::with_capacity(num_periods);
+        // Statistics of each campaign over the periods so far
+        let mut flight_campaign_stats: Option<Vec<CampaignStat>> = None;
+        
+        // Controller trajectory CSV header (with a row per period)
+        logln!(logger, LogEvent::Controller, "iteration,kind,id,target_name,actual,target,control_variable");
+        
+        for period in 0..num_periods {
+            logln!(logger, LogEvent::Simulation, "\n=== {} - Period {} of {} ===", variant_name, period + 1, num_periods);
+            
+            hooks.iteration_start(period + 1, logger);
+            
+            // Run auctions for the impressions of this period (bids aren't cached across periods)
+            self.marketplace.replace_impressions(Rc::new(all_impressions.day_impressions(period)));
+            let simulation_run = SimulationRun::new(&self.marketplace, &current_campaign_controller_states, &current_seller_controller_states, &[], &mut hooks, None, period + 1, logger);
+            let mut stats = SimulationStat::new(&self.marketplace, &simulation_run, period + 1);
+            
+            // Extrapolate the period over the flight by the factor between the flight's target and the period's
+            // share of it (of the campaign's first target, e.g. its budget), so that targets compare the period with
+            // its share: sums scale with the factor, while averages stay the same
+            // Shares are of the campaign's traffic, in the periods it is active
+            let projected_campaign_stats: Vec<CampaignStat> = stats.campaign_stats.iter().enumerate()
+                .map(|(index, campaign_stat)| {
+                    let campaign = &self.marketplace.campaigns.campaigns[index];
+                    let period_impressions = &campaign_period_impressions[index];
+                    if period_impressions[period] == 0 {
+                        return campaign_stat.clone();
+                    }
+                    let traffic_share = period_impressions[period] as f64 / period_impressions.iter().sum::<usize>() as f64;
+                    let remaining_traffic_share = period_impressions[period] as f64 / period_impressions[period..].iter().sum::<usize>() as f64;
+                    let controller_states: Vec<&dyn ControllerStateTrait> = current_campaign_controller_states.campaign_controller_states[index].iter().map(|state| state.as_ref()).collect();
+                    let factor = match (&flight_campaign_stats, self.period_carryover) {
+                        // With carryover, the period's share is the part of what the past periods left of the target
+                        // that falls on it by traffic
+                        (Some(flight_campaign_stats), true) => match campaign.get_targets_actual_and_target(&controller_states, &flight_campaign_stats[index]).first() {
+                            Some(&(_, actual, target)) if target > 0.0 => target / ((target - actual).max(target * MIN_PERIOD_SHARE) * remaining_traffic_share),
+                            _ => 1.0 / remaining_traffic_share,
+                        },
+                        // In the first period and without carryover, the period's share is its share of the traffic
+                        _ => 1.0 / traffic_share,
+                    };
+                    let mut projected = campaign_stat.clone();
+                    projected.scale(factor);
+                    projected
+                })
+                .collect();
+            match &mut flight_campaign_stats {
+                Some(flight_campaign_stats) => {
+                    for (flight_campaign_stat, campaign_stat) in flight_campaign_stats.iter_mut().zip(&stats.campaign_stats) {
+                        flight_campaign_stat.add(campaign_stat);
+                    }
+                }
+                None => flight_campaign_stats = Some(stats.campaign_stats.clone()),
+            }
+            
+            // Log the controller trajectory on the extrapolated period
+            for (index, campaign) in self.marketplace.campaigns.campaigns.iter().enumerate() {
+                let controller_states: Vec<&dyn ControllerStateTrait> = current_campaign_controller_states.campaign_controller_states[index].iter().map(|state| state.as_ref()).collect();
+                let targets = campaign.get_targets_actual_and_target(&controller_states, &projected_campaign_stats[index]);
+                log_controller_trajectory(period + 1, "campaign", index, &targets, &campaign.get_control_variables(&controller_states), logger);
+                stats.campaign_targets.push(targets.into_iter().enumerate()
+                    .map(|(target_index, (name, actual, target))| TargetStat { name, actual, target, tolerance: self.marketplace.campaigns.target_tolerance(index, target_index) })
+                    .collect());
+            }
+            for (index, seller) in self.marketplace.sellers.sellers.iter().enumerate() {
+                let control_variable = seller.get_control_variable(current_seller_controller_states.seller_controller_states[index][0].as_ref());
+                let targets = seller.get_targets_actual_and_target(&stats.seller_stats[index]);
+                log_controller_trajectory(period + 1, "seller", index, &targets, &[control_variable], logger);
+            }
+            
+            // Update the campaign controllers once, on the extrapolated period (of the campaigns active in it)
+            let mut next_campaign_controller_states = current_campaign_controller_states.clone();
+            for (index, campaign) in self.marketplace.campaigns.campaigns.iter().enumerate() {
+                if campaign_period_impressions[index][period] == 0 {
+                    continue;
+                }
+                let previous_states = &current_campaign_controller_states.campaign_controller_states[index];
+                let next_states = &mut next_campaign_controller_states.campaign_controller_states[index];
+                let campaign_pacing_changed = campaign.next_controller_state(previous_states, next_states, &projected_campaign_stats[index]);
+                let previous_state_refs: Vec<&dyn ControllerStateTrait> = previous_states.iter().map(|state| state.as_ref()).collect();
+                let next_state_refs: Vec<&dyn ControllerStateTrait> = next_states.iter().map(|state| state.as_ref()).collect();
+                warn_binding_bounds(period + 1, "campaign", index, campaign.campaign_name(), &campaign.binding_bounds(&previous_state_refs), &campaign.binding_bounds(&next_state_refs), logger);
+                current_campaign_controller_states.converged[index] = !campaign_pacing_changed;
+                next_campaign_controller_states.converged[index] = !campaign_pacing_changed;
+            }
+            
+            // Update the seller controllers once, on the period's statistics
+            let mut next_seller_controller_states = current_seller_controller_states.clone();
+            for (index, seller) in self.marketplace.sellers.sellers.iter().enumerate() {
+                let previous_states = &current_seller_controller_states.seller_controller_states[index];
+                let next_states = &mut next_seller_controller_states.seller_controller_states[index];
+                let seller_boost_changed = seller.next_controller_state(previous_states, next_states, &stats.seller_stats[index]);
+                let previous_state_refs: Vec<&dyn ControllerStateTrait> = previous_states.iter().map(|state| state.as_ref()).collect();
+                let next_state_refs: Vec<&dyn ControllerStateTrait> = next_states.iter().map(|state| state.as_ref()).collect();
+                warn_binding_bounds(period + 1, "seller", index, seller.seller_name(), &seller.binding_bounds(&previous_state_refs), &seller.binding_bounds(&next_state_refs), logger);
+                current_seller_controller_states.converged[index] = !seller_boost_changed;
+                next_seller_controller_states.converged[index] = !seller_boost_changed;
+            }
+            
+            // Output campaign and seller statistics of the period (using the controller states that were actually used)
+            stats.printout_campaigns(&self.marketplace.campaigns, &current_campaign_controller_states, logger, LogEvent::Simulation);
+            stats.printout_sellers(&self.marketplace.sellers, &current_seller_controller_states, logger, LogEvent::Simulation);
+            
+            hooks.iteration_end(period + 1, &stats, logger);
+            
+            period_stats.push(stats);
+            
+            // Prepare for next period
+            current_campaign_controller_states = next_campaign_controller_states;
+            current_seller_controller_states = next_seller_controller_states;
+        }
+        
+        // Offer all impressions again
+        self.marketplace.replace_impressions(all_impressions);
+        
+        // Targets over the whole flight
+        let campaign_stats = flight_campaign_stats.expect("Impressions are spread over at least one day");
+        let campaign_targets = self.marketplace.campaigns.campaigns.iter().enumerate()
+            .map(|(index, campaign)| {
+                let controller_states: Vec<&dyn ControllerStateTrait> = current_campaign_controller_states.campaign_controller_states[index].iter().map(|state| state.as_ref()).collect();
+                campaign.get_targets_actual_and_target(&controller_states, &campaign_stats[index]).into_iter().enumerate()
+                    .map(|(target_index, (name, actual, target))| TargetStat { name, actual, target, tolerance: self.marketplace.campaigns.target_tolerance(index, target_index) })
+                    .collect()
+            })
+            .collect();
+        
+        logln!(logger, LogEvent::Convergence, "{}: Ran {} periods", variant_name, num_periods);
+        
+        (PeriodsStat { period_stats, campaign_stats, campaign_targets }, current_campaign_controller_states, current_seller_controller_states)
     }
+    
+    /// Run a multi-period variant (see run_periods) with logging setup and cleanup
+    /// 
+    /// # Arguments
+    /// * `variant_description` - Description of the variant being run
+    /// * `scenario_name` - Name of the scenario (for log file paths)
+    /// * `variant_name` - Name of the variant (for log file paths)
+    /// * `logger` - Logger for event-based logging
+    pub fn run_periods_variant(
+        &mut self,
+        variant_description: &str,
+        scenario_name: &str,
+        variant_name: &str,
+        logger: &mut Logger,
+    ) -> PeriodsStat {
+        // Add variant periods receiver (for simulation and convergence events)
+        let iterations_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/iterations-{}.log", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Simulation, LogEvent::Convergence]));
+        
+        // Add variant receiver (for variant events)
+        let variant_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/variant-{}.log", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Variant]));
+        
+        // Add controller trajectory receiver (CSV for plotting pacing over the periods)
+        let controller_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/controller-{}.csv", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Controller]));
+        
+        logln!(logger, LogEvent::Variant, "\n=== {} ===", variant_description);
+        
+        self.marketplace.printout(logger);
+        
+        let (stats, _final_campaign_controller_states, _final_seller_controller_states) = self.run_periods(variant_name, logger);
+        
+        // Print the period and flight statistics (variant-level output)
+        stats.printout(&self.marketplace.campaigns, logger);
+        
+        // Remove variant-specific receivers
+        logger.remove_receiver(controller_receiver_id);
+        logger.remove_receiver(variant_receiver_id);
+        logger.remove_receiver(iterations_receiver_id);
+        
+        stats
+    }
+
 }
 
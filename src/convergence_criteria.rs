@@ -0,0 +1,249 @@
+//! Convergence criteria of the convergence loop
+//!
+//! After every iteration, the convergence loop asks its convergence criterion whether to continue, stop as
+//! converged or stop without converging. By default a run converges when no campaign or seller controller
+//! changes its state, but some controllers never report their state unchanged (e.g. with a tolerance tighter
+//! than the simulation can resolve) while they are effectively converged, so runs can select other criteria
+//! (see SimulationConverge::with_convergence_criterion).
+
+use crate::simulationrun::TargetStat;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Decision of a convergence criterion after an iteration
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConvergenceDecision {
+    /// Run another iteration
+    Continue,
+    /// Stop, the run converged
+    Converged,
+    /// Stop without converging, for the given reason
+    Stop(String),
+}
+
+/// What a convergence criterion observes of an iteration
+pub struct ConvergenceObservation<'a> {
+    /// Iteration (1-indexed)
+    pub iteration: usize,
+    /// Whether any campaign or seller controller changed its state after the iteration
+    pub controllers_changed: bool,
+    /// Actual value, target value and tolerance of every convergence target, per campaign
+    pub campaign_targets: &'a [Vec<TargetStat>],
+    /// (description, actual, target) of every convergence target, per seller
+    pub seller_targets: &'a [Vec<(String, f64, f64)>],
+    /// Control variables used in the iteration, per campaign
+    pub campaign_control_variables: &'a [Vec<f64>],
+    /// Control variables used in the iteration, per seller
+    pub seller_control_variables: &'a [Vec<f64>],
+}
+
+impl ConvergenceObservation<'_> {
+    /// Largest relative error |actual - target| / |target| over all campaign and seller targets
+    /// Targets of 0.0 (campaigns and sellers without a target) are skipped
+    pub fn max_relative_error(&self) -> f64 {
+        let campaign_targets = self.campaign_targets.iter().flatten().map(|target| (target.actual, target.target));
+        let seller_targets = self.seller_targets.iter().flatten().map(|(_, actual, target)| (*actual, *target));
+        campaign_targets.chain(seller_targets)
+            .filter(|(_, target)| *target != 0.0)
+            .map(|(actual, target)| ((actual - target) / target).abs())
+            .fold(0.0, f64::max)
+    }
+
+    /// All control variables of campaigns and sellers, in order
+    fn control_variables(&self) -> Vec<f64> {
+        self.campaign_control_variables.iter().chain(self.seller_control_variables).flatten().copied().collect()
+    }
+}
+
+/// Trait for deciding when the convergence loop stops
+pub trait ConvergenceCriterionTrait {
+    /// Forget what was observed, called at the start of every convergence run
+    fn reset(&mut self) {}
+
+    /// Observe an iteration and decide whether the run continues
+    fn check(&mut self, observation: &ConvergenceObservation) -> ConvergenceDecision;
+
+    /// Get a string representation of the criterion, for logs
+    fn criterion_string(&self) -> String;
+}
+
+/// Converged when no campaign or seller controller changes its state (the default criterion)
+pub struct ConvergenceCriterionControllersUnchanged;
+
+impl ConvergenceCriterionControllersUnchanged {
+    /// Create a new ConvergenceCriterionControllersUnchanged
+    pub fn new() -> Box<Self> {
+        Box::new(Self)
+    }
+}
+
+impl ConvergenceCriterionTrait for ConvergenceCriterionControllersUnchanged {
+    fn check(&mut self, observation: &ConvergenceObservation) -> ConvergenceDecision {
+        if observation.controllers_changed {
+            ConvergenceDecision::Continue
+        } else {
+            ConvergenceDecision::Converged
+        }
+    }
+
+    fn criterion_string(&self) -> String {
+        "controllers unchanged".to_string()
+    }
+}
+
+/// Converged when the relative error of every campaign and seller target stays within a tolerance for a
+/// number of consecutive iterations, whether or not controllers keep changing their states
+pub struct ConvergenceCriterionRelativeError {
+    /// Largest relative error counted as within tolerance (e.g., 0.01 = 1%)
+    pub tolerance: f64,
+    /// Consecutive iterations within tolerance needed to converge
+    pub consecutive_iterations: usize,
+    /// Consecutive iterations within tolerance so far
+    iterations_within_tolerance: usize,
+}
+
+impl ConvergenceCriterionRelativeError {
+    /// Create a new ConvergenceCriterionRelativeError
+    pub fn new(tolerance: f64, consecutive_iterations: usize) -> Box<Self> {
+        assert!(consecutive_iterations > 0, "Consecutive iterations within tolerance must be positive");
+        Box::new(Self { tolerance, consecutive_iterations, iterations_within_tolerance: 0 })
+    }
+}
+
+impl ConvergenceCriterionTrait for ConvergenceCriterionRelativeError {
+    fn reset(&mut self) {
+        self.iterations_within_tolerance = 0;
+    }
+
+    fn check(&mut self, observation: &ConvergenceObservation) -> ConvergenceDecision {
+        if observation.max_relative_error() <= self.tolerance {
+            self.iterations_within_tolerance += 1;
+        } else {
+            self.iterations_within_tolerance = 0;
+        }
+        if self.iterations_within_tolerance >= self.consecutive_iterations {
+            ConvergenceDecision::Converged
+        } else {
+            ConvergenceDecision::Continue
+        }
+    }
+
+    fn criterion_string(&self) -> String {
+        format!("relative error within ±{:.2}% for {} iterations", self.tolerance * 100.0, self.consecutive_iterations)
+    }
+}
+
+/// Stops the run without converging once it has taken longer than a maximum (wall-clock) runtime
+/// Usually combined with another criterion deciding convergence (see ConvergenceCriterionAny)
+pub struct ConvergenceCriterionMaxRuntime {
+    pub max_runtime: Duration,
+    /// Start of the current run
+    started: Instant,
+}
+
+impl ConvergenceCriterionMaxRuntime {
+    /// Create a new ConvergenceCriterionMaxRuntime
+    pub fn new(max_runtime: Duration) -> Box<Self> {
+        Box::new(Self { max_runtime, started: Instant::now() })
+    }
+}
+
+impl ConvergenceCriterionTrait for ConvergenceCriterionMaxRuntime {
+    fn reset(&mut self) {
+        self.started = Instant::now();
+    }
+
+    fn check(&mut self, observation: &ConvergenceObservation) -> ConvergenceDecision {
+        if self.started.elapsed() >= self.max_runtime {
+            ConvergenceDecision::Stop(format!("maximum runtime of {:.1}s reached after {} iterations", self.max_runtime.as_secs_f64(), observation.iteration))
+        } else {
+            ConvergenceDecision::Continue
+        }
+    }
+
+    fn criterion_string(&self) -> String {
+        format!("maximum runtime {:.1}s", self.max_runtime.as_secs_f64())
+    }
+}
+
+/// Converged when the control variables stagnate: over the last `window` iterations, none of them moved by
+/// more than `threshold` relative to its latest value
+pub struct ConvergenceCriterionStagnation {
+    /// Number of consecutive iterations over which the control variables are compared
+    pub window: usize,
+    /// Largest relative range (max - min) / |latest| of a control variable counted as stagnant (e.g., 0.005 = 0.5%)
+    pub threshold: f64,
+    /// Control variables of the last `window` iterations
+    history: VecDeque<Vec<f64>>,
+}
+
+impl ConvergenceCriterionStagnation {
+    /// Create a new ConvergenceCriterionStagnation
+    pub fn new(window: usize, threshold: f64) -> Box<Self> {
+        assert!(window >= 2, "Stagnation window must span at least 2 iterations");
+        Box::new(Self { window, threshold, history: VecDeque::with_capacity(window) })
+    }
+}
+
+impl ConvergenceCriterionTrait for ConvergenceCriterionStagnation {
+    fn reset(&mut self) {
+        self.history.clear();
+    }
+
+    fn check(&mut self, observation: &ConvergenceObservation) -> ConvergenceDecision {
+        if self.history.len() == self.window {
+            self.history.pop_front();
+        }
+        self.history.push_back(observation.control_variables());
+        if self.history.len() < self.window {
+            return ConvergenceDecision::Continue;
+        }
+
+        let latest = self.history.back().unwrap();
+        let stagnant = latest.iter().enumerate().all(|(index, &latest_value)| {
+            let (min, max) = self.history.iter()
+                .map(|control_variables| control_variables[index])
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+            max - min <= self.threshold * latest_value.abs()
+        });
+        if stagnant {
+            ConvergenceDecision::Converged
+        } else {
+            ConvergenceDecision::Continue
+        }
+    }
+
+    fn criterion_string(&self) -> String {
+        format!("control variables within ±{:.2}% over {} iterations", self.threshold * 100.0, self.window)
+    }
+}
+
+/// Combines criteria, stopping on the first decision to stop among them (in order)
+/// All criteria observe every iteration, so those keeping state stay up to date
+pub struct ConvergenceCriterionAny {
+    pub criteria: Vec<Box<dyn ConvergenceCriterionTrait>>,
+}
+
+impl ConvergenceCriterionAny {
+    /// Create a new ConvergenceCriterionAny
+    pub fn new(criteria: Vec<Box<dyn ConvergenceCriterionTrait>>) -> Box<Self> {
+        Box::new(Self { criteria })
+    }
+}
+
+impl ConvergenceCriterionTrait for ConvergenceCriterionAny {
+    fn reset(&mut self) {
+        for criterion in &mut self.criteria {
+            criterion.reset();
+        }
+    }
+
+    fn check(&mut self, observation: &ConvergenceObservation) -> ConvergenceDecision {
+        let decisions: Vec<ConvergenceDecision> = self.criteria.iter_mut().map(|criterion| criterion.check(observation)).collect();
+        decisions.into_iter().find(|decision| *decision != ConvergenceDecision::Continue).unwrap_or(ConvergenceDecision::Continue)
+    }
+
+    fn criterion_string(&self) -> String {
+        self.criteria.iter().map(|criterion| criterion.criterion_string()).collect::<Vec<_>>().join(" or ")
+    }
+}
@@ -0,0 +1,160 @@
+//! Counterfactual replay of converged controller states
+//!
+//! A counterfactual is a set of changes to the marketplace (e.g. removing a campaign or raising a seller's
+//! floors). Replaying converged controller states on the same impressions with and without the changes, without
+//! converging again, shows what the changes do while all participants keep bidding as they converged: removing
+//! a campaign measures its incrementality, what it adds to the sellers' revenue and takes from the other
+//! campaigns. The baseline and every counterfactual are reported side by side.
+
+use crate::simulationrun::SimulationStat;
+use crate::converge::{SimulationConverge, CampaignControllerStates, SellerControllerStates};
+use crate::impressions::Impressions;
+use crate::variants::printout_comparison;
+use crate::logger::{Logger, LogEvent};
+use std::rc::Rc;
+
+/// A change of the marketplace in a counterfactual
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone)]
+pub enum CounterfactualChange {
+    /// The campaign doesn't bid (it is paused, keeping its ID and controller states)
+    REMOVE_CAMPAIGN { campaign_id: usize },
+    /// The floors of the seller's impressions are multiplied by the factor
+    SCALE_FLOORS { seller_id: usize, factor: f64 },
+}
+
+/// What applying a counterfactual changed, to restore the marketplace
+struct CounterfactualUndo {
+    /// Campaigns paused, with whether they were paused before
+    paused_before: Vec<(usize, bool)>,
+    /// Impressions offered before floors were changed (None if they weren't)
+    impressions_before: Option<Rc<Impressions>>,
+}
+
+/// Named set of changes of the marketplace
+pub struct Counterfactual {
+    pub name: String,
+    pub changes: Vec<CounterfactualChange>,
+}
+
+impl Counterfactual {
+    /// Create a new counterfactual without changes
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), changes: Vec::new() }
+    }
+
+    /// Add a change of the marketplace
+    pub fn with_change(mut self, change: CounterfactualChange) -> Self {
+        self.changes.push(change);
+        self
+    }
+
+    /// Apply the changes to the marketplace of `simulation_converge`
+    /// Returns what undo needs to restore it
+    fn apply(&self, simulation_converge: &mut SimulationConverge) -> CounterfactualUndo {
+        let marketplace = &mut simulation_converge.marketplace;
+        let mut paused_before = Vec::new();
+        let mut floor_factors = vec![1.0; marketplace.sellers.sellers.len()];
+        for change in &self.changes {
+            match *change {
+                CounterfactualChange::REMOVE_CAMPAIGN { campaign_id } => {
+                    paused_before.push((campaign_id, marketplace.campaigns.is_paused(campaign_id)));
+                    marketplace.campaigns.set_paused(campaign_id, true);
+                }
+                CounterfactualChange::SCALE_FLOORS { seller_id, factor } => {
+                    assert!(seller_id < floor_factors.len(), "Counterfactual '{}' changes floors of seller {}, which doesn't exist", self.name, seller_id);
+                    floor_factors[seller_id] *= factor;
+                }
+            }
+        }
+
+        // Floors are properties of the impressions, so the impressions are copied with the changed floors
+        let impressions_before = if floor_factors.iter().any(|&factor| factor != 1.0) {
+            let impressions = &marketplace.impressions;
+            let changed_impressions = Impressions {
+                impressions: impressions.impressions.iter()
+                    .map(|impression| {
+                        let mut impression = impression.clone();
+                        impression.floor_cpm *= floor_factors[impression.seller_id];
+                        impression
+                    })
+                    .collect(),
+                num_days: impressions.num_days,
+                num_segments: impressions.num_segments,
+            };
+            Some(marketplace.replace_impressions(Rc::new(changed_impressions)))
+        } else {
+            None
+        };
+        CounterfactualUndo { paused_before, impressions_before }
+    }
+
+    /// Restore the marketplace changed by apply
+    fn undo(simulation_converge: &mut SimulationConverge, undo: CounterfactualUndo) {
+        let marketplace = &mut simulation_converge.marketplace;
+        for &(campaign_id, paused) in undo.paused_before.iter().rev() {
+            marketplace.campaigns.set_paused(campaign_id, paused);
+        }
+        if let Some(impressions) = undo.impressions_before {
+            marketplace.replace_impressions(impressions);
+        }
+    }
+}
+
+/// Statistics of the baseline and every counterfactual replayed with the same controller states
+pub struct CounterfactualResult {
+    /// Statistics of the unchanged marketplace
+    pub baseline: SimulationStat,
+    /// Name and statistics of every counterfactual, in order
+    pub counterfactuals: Vec<(String, SimulationStat)>,
+}
+
+impl CounterfactualResult {
+    /// Statistics of a counterfactual
+    pub fn counterfactual(&self, name: &str) -> &SimulationStat {
+        self.counterfactuals.iter()
+            .find(|(counterfactual_name, _)| counterfactual_name == name)
+            .map(|(_, stats)| stats)
+            .unwrap_or_else(|| panic!("No counterfactual '{}'", name))
+    }
+
+    /// Incrementality of what a counterfactual changes on a metric: the metric of the baseline minus the metric
+    /// of the counterfactual (e.g. the seller revenue a removed campaign brings)
+    pub fn incrementality(&self, name: &str, metric: impl Fn(&SimulationStat) -> f64) -> f64 {
+        metric(&self.baseline) - metric(self.counterfactual(name))
+    }
+
+    /// Output the baseline and every counterfactual side by side, with the change from the baseline
+    pub fn printout(&self, simulation_converge: &SimulationConverge, logger: &mut Logger, event: LogEvent) {
+        let marketplace = &simulation_converge.marketplace;
+        let campaign_names: Vec<String> = marketplace.campaigns.campaigns.iter().map(|campaign| campaign.campaign_name().to_string()).collect();
+        let seller_names: Vec<String> = marketplace.sellers.sellers.iter().map(|seller| seller.seller_name().to_string()).collect();
+        printout_comparison("Counterfactuals", &campaign_names, &seller_names, &self.baseline, &self.counterfactuals, logger, event);
+    }
+}
+
+/// Replay controller states (e.g. the converged states of a variant) on the marketplace of `simulation_converge`
+/// unchanged and with the changes of every counterfactual, on the same impressions and without converging
+///
+/// Every replay is seeded as iteration `iteration` of the convergence run (see SimulationConverge::replay),
+/// usually the iteration the states converged in, so the baseline reproduces the converged statistics and the
+/// counterfactuals differ from it only by their changes. The marketplace is restored after each counterfactual.
+pub fn replay_counterfactuals(
+    simulation_converge: &mut SimulationConverge,
+    counterfactuals: &[Counterfactual],
+    campaign_controller_states: &CampaignControllerStates,
+    seller_controller_states: &SellerControllerStates,
+    iteration: usize,
+    logger: &mut Logger,
+) -> CounterfactualResult {
+    let baseline = simulation_converge.replay(campaign_controller_states, seller_controller_states, iteration, logger);
+    let counterfactuals = counterfactuals.iter()
+        .map(|counterfactual| {
+            let undo = counterfactual.apply(simulation_converge);
+            let stats = simulation_converge.replay(campaign_controller_states, seller_controller_states, iteration, logger);
+            Counterfactual::undo(simulation_converge, undo);
+            (counterfactual.name.clone(), stats)
+        })
+        .collect();
+    CounterfactualResult { baseline, counterfactuals }
+}
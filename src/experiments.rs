@@ -0,0 +1,259 @@
+//! A/B experiments over impressions
+//!
+//! An experiment splits impressions into a control and a treatment bucket by a hash of their user (all
+//! impressions of a user fall in the same bucket) or of the impression itself. Campaigns assigned to an arm
+//! of the experiment (see Campaigns::set_experiment_arm) only bid on impressions of their arm's bucket, so two
+//! campaign configurations run simultaneously on disjoint traffic of the same marketplace. ExperimentStat
+//! collects the results of each arm per replicate (a further hash split of the experiment's units), from which
+//! the lift of a metric and its variance are estimated by the jackknife.
+
+use crate::impressions::{Impression, Winner, FractionalWinners};
+use crate::simulationrun::{Marketplace, SimulationRun, SimulationType};
+use crate::seed_aggregation::CONFIDENCE_LEVEL;
+use crate::utils::get_seed;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+
+/// Number of replicates the units of an experiment are split into for variance estimates
+pub const EXPERIMENT_REPLICATES: usize = 20;
+/// Two-sided standard normal quantile of CONFIDENCE_LEVEL (95%)
+const CONFIDENCE_Z: f64 = 1.959964;
+
+/// Unit an experiment assigns to buckets
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExperimentSplit {
+    /// Split by user, all impressions of a user in the same bucket (needs ImpressionsParam::with_users)
+    USER,
+    /// Split by impression
+    IMPRESSION,
+}
+
+/// Arm of an experiment
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExperimentArm {
+    CONTROL,
+    TREATMENT,
+}
+
+/// An experiment splitting impressions into a control and a treatment bucket
+pub struct Experiment {
+    pub name: String,
+    pub split: ExperimentSplit,
+    /// Share of the units in the treatment bucket
+    pub treatment_share: f64,
+    /// Local seed of the experiment's hash, so experiments split independently of each other
+    local_seed: u64,
+}
+
+impl Experiment {
+    /// Create a new experiment, `experiment_id` makes its split independent of other experiments
+    pub fn new(name: &str, split: ExperimentSplit, treatment_share: f64, experiment_id: usize) -> Self {
+        assert!(treatment_share > 0.0 && treatment_share < 1.0, "Treatment share of experiment '{}' must be within (0, 1)", name);
+        Self {
+            name: name.to_string(),
+            split,
+            treatment_share,
+            local_seed: 17171 ^ (experiment_id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15),
+        }
+    }
+
+    /// Hash of the impression's unit (its user or itself), the same in every iteration
+    fn unit_hash(&self, impression: &Impression) -> u64 {
+        match self.split {
+            ExperimentSplit::USER => {
+                let user_id = impression.user_id.unwrap_or_else(|| panic!("Experiment '{}' splits by user, which needs users (see ImpressionsParam::with_users)", self.name));
+                let mut hash = (get_seed(self.local_seed) ^ user_id as u64).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                hash ^= hash >> 31;
+                hash = hash.wrapping_mul(0x94D0_49BB_1331_11EB);
+                hash ^ (hash >> 29)
+            }
+            ExperimentSplit::IMPRESSION => impression.seed(self.local_seed),
+        }
+    }
+
+    /// Arm of the bucket the impression falls in, and the replicate of its unit
+    pub fn bucket(&self, impression: &Impression) -> (ExperimentArm, usize) {
+        let hash = self.unit_hash(impression);
+        let draw = (hash >> 11) as f64 / (1u64 << 53) as f64;
+        let arm = if draw < self.treatment_share { ExperimentArm::TREATMENT } else { ExperimentArm::CONTROL };
+        (arm, (hash % EXPERIMENT_REPLICATES as u64) as usize)
+    }
+}
+
+/// Results of the campaigns of an experiment arm on the impressions of a replicate of its bucket
+/// (sums weighted by Impression::weight)
+#[derive(Debug, Clone, Default)]
+pub struct ExperimentBucketStat {
+    /// Impressions in the bucket, the base of per impression metrics
+    pub impressions_offered: f64,
+    pub impressions_obtained: f64,
+    pub total_buyer_charge: f64,
+    pub total_value: f64,
+    pub total_conversions: f64,
+}
+
+impl ExperimentBucketStat {
+    fn add(&mut self, other: &ExperimentBucketStat) {
+        self.impressions_offered += other.impressions_offered;
+        self.impressions_obtained += other.impressions_obtained;
+        self.total_buyer_charge += other.total_buyer_charge;
+        self.total_value += other.total_value;
+        self.total_conversions += other.total_conversions;
+    }
+}
+
+/// Lift of a metric in the treatment arm over the control arm
+#[derive(Debug, Clone)]
+pub struct LiftEstimate {
+    pub control: f64,
+    pub treatment: f64,
+    /// Relative lift, treatment / control - 1
+    pub lift: f64,
+    /// Jackknife standard error of the lift over the replicates
+    pub std_error: f64,
+    /// Bounds of the normal confidence interval of the lift (CONFIDENCE_LEVEL)
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+impl LiftEstimate {
+    /// Get a string representation, e.g. "+5.21% ± 1.02% (95% CI [+3.21%, +7.21%])"
+    pub fn lift_string(&self) -> String {
+        format!("{:+.2}% ± {:.2}% ({:.0}% CI [{:+.2}%, {:+.2}%])", self.lift * 100.0, self.std_error * 100.0, CONFIDENCE_LEVEL * 100.0, self.ci_low * 100.0, self.ci_high * 100.0)
+    }
+}
+
+/// Results of an experiment's arms, per replicate
+#[derive(Debug, Clone)]
+pub struct ExperimentStat {
+    pub name: String,
+    /// Results of the control arm (indexed by replicate)
+    pub control: Vec<ExperimentBucketStat>,
+    /// Results of the treatment arm (indexed by replicate)
+    pub treatment: Vec<ExperimentBucketStat>,
+}
+
+impl ExperimentStat {
+    /// Collect the results of the marketplace's experiments from a simulation run
+    /// (covering the impressions auctioned so far, for a run in progress)
+    pub fn from_run(marketplace: &Marketplace, simulation_run: &SimulationRun) -> Vec<Self> {
+        let experiments = &marketplace.campaigns.experiments;
+        if experiments.is_empty() {
+            return Vec::new();
+        }
+        let mut experiment_stats: Vec<Self> = experiments.iter()
+            .map(|experiment| Self {
+                name: experiment.name.clone(),
+                control: vec![ExperimentBucketStat::default(); EXPERIMENT_REPLICATES],
+                treatment: vec![ExperimentBucketStat::default(); EXPERIMENT_REPLICATES],
+            })
+            .collect();
+
+        let impressions = &marketplace.impressions.impressions[..simulation_run.impressions_auctioned()];
+        let mut results = simulation_run.results.iter();
+        let mut results_fractional = simulation_run.results_fractional.iter();
+        for impression in impressions {
+            // Fraction won, buyer charge and campaign of every winner of the impression
+            let winners: Vec<(usize, f64, f64)> = match marketplace.seller_simulation_type(impression.seller_id) {
+                SimulationType::Standard => match results.next().expect("Standard auction result for every impression auctioned with Standard auctions").winner {
+                    Winner::Campaign { campaign_id, buyer_charge, .. } => vec![(campaign_id, 1.0, buyer_charge)],
+                    _ => Vec::new(),
+                },
+                SimulationType::FractionalInternalAuction { .. } => match &results_fractional.next().expect("Fractional auction result for every impression auctioned with fractional auctions").winner {
+                    FractionalWinners::Campaigns { winners } => winners.iter().map(|winner| (winner.campaign_id, winner.win_fraction, winner.buyer_charge)).collect(),
+                    _ => Vec::new(),
+                },
+            };
+
+            let weight = impression.weight;
+            for (experiment_id, (experiment, experiment_stat)) in experiments.iter().zip(experiment_stats.iter_mut()).enumerate() {
+                let (arm, replicate) = experiment.bucket(impression);
+                let bucket_stat = match arm {
+                    ExperimentArm::CONTROL => &mut experiment_stat.control[replicate],
+                    ExperimentArm::TREATMENT => &mut experiment_stat.treatment[replicate],
+                };
+                bucket_stat.impressions_offered += weight;
+                for &(campaign_id, win_fraction, buyer_charge) in &winners {
+                    if marketplace.campaigns.experiment_arm(campaign_id) != Some((experiment_id, arm)) {
+                        continue;
+                    }
+                    let win_fraction = win_fraction * weight;
+                    let group_id = marketplace.campaigns.campaign_to_value_group_mapping[campaign_id];
+                    bucket_stat.impressions_obtained += win_fraction;
+                    bucket_stat.total_buyer_charge += buyer_charge * win_fraction;
+                    bucket_stat.total_value += impression.value_to_campaign_group[group_id] * win_fraction;
+                    if impression.converted {
+                        bucket_stat.total_conversions += win_fraction;
+                    }
+                }
+            }
+        }
+        experiment_stats
+    }
+
+    /// Results of an arm over all its replicates
+    pub fn arm_total(&self, arm: ExperimentArm) -> ExperimentBucketStat {
+        let buckets = match arm {
+            ExperimentArm::CONTROL => &self.control,
+            ExperimentArm::TREATMENT => &self.treatment,
+        };
+        let mut total = ExperimentBucketStat::default();
+        for bucket in buckets {
+            total.add(bucket);
+        }
+        total
+    }
+
+    /// Estimate the lift of a ratio metric in the treatment arm over the control arm, e.g. value per impression
+    /// offered (`|bucket| bucket.total_value`, `|bucket| bucket.impressions_offered`) or value per buyer charge
+    ///
+    /// Each arm's metric is the ratio of the sums over its replicates. The standard error comes from the
+    /// delete-one-replicate jackknife, leaving out a replicate of both arms at a time, so it accounts for the
+    /// split's units (e.g. users) being the independent observations.
+    pub fn lift<N: Fn(&ExperimentBucketStat) -> f64, D: Fn(&ExperimentBucketStat) -> f64>(&self, numerator: N, denominator: D) -> LiftEstimate {
+        let sums = |buckets: &[ExperimentBucketStat], skip: Option<usize>| buckets.iter().enumerate()
+            .filter(|(replicate, _)| Some(*replicate) != skip)
+            .fold((0.0, 0.0), |(numerator_sum, denominator_sum), (_, bucket)| (numerator_sum + numerator(bucket), denominator_sum + denominator(bucket)));
+        let metrics = |skip: Option<usize>| {
+            let (control_numerator, control_denominator) = sums(&self.control, skip);
+            let (treatment_numerator, treatment_denominator) = sums(&self.treatment, skip);
+            (control_numerator / control_denominator, treatment_numerator / treatment_denominator)
+        };
+
+        let (control, treatment) = metrics(None);
+        let lift = treatment / control - 1.0;
+        let replicate_lifts: Vec<f64> = (0..EXPERIMENT_REPLICATES)
+            .map(|replicate| {
+                let (control, treatment) = metrics(Some(replicate));
+                treatment / control - 1.0
+            })
+            .collect();
+        let replicates = EXPERIMENT_REPLICATES as f64;
+        let mean_replicate_lift = replicate_lifts.iter().sum::<f64>() / replicates;
+        let variance = (replicates - 1.0) / replicates * replicate_lifts.iter().map(|replicate_lift| (replicate_lift - mean_replicate_lift).powi(2)).sum::<f64>();
+        let std_error = variance.sqrt();
+
+        LiftEstimate {
+            control,
+            treatment,
+            lift,
+            std_error,
+            ci_low: lift - CONFIDENCE_Z * std_error,
+            ci_high: lift + CONFIDENCE_Z * std_error,
+        }
+    }
+
+    /// Output the results of both arms and the lift of value per impression offered
+    pub fn printout(&self, logger: &mut Logger, event: LogEvent) {
+        logln!(logger, event, "\nExperiment {}", self.name);
+        for (arm_name, arm) in [("Control", ExperimentArm::CONTROL), ("Treatment", ExperimentArm::TREATMENT)] {
+            let total = self.arm_total(arm);
+            logln!(logger, event, "  {}: Impressions Offered: {:.0}, Obtained: {:.2}, Buyer Charge: {:.4}, Value: {:.4}",
+                arm_name, total.impressions_offered, total.impressions_obtained, total.total_buyer_charge, total.total_value);
+        }
+        let value_lift = self.lift(|bucket| bucket.total_value, |bucket| bucket.impressions_offered);
+        logln!(logger, event, "  Lift of Value per Impression Offered: {}", value_lift.lift_string());
+    }
+}
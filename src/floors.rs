@@ -13,6 +13,12 @@ pub trait FloorGeneratorTrait {
     /// # Returns
     /// Generated floor CPM value
     fn generate_floor(&self, base_impression_value: f64, rng: &mut StdRng) -> f64;
+    
+    /// Whether generated floors are scaled by the seller's control variable in each simulation run
+    /// (instead of the control variable boosting bids on the seller)
+    fn is_controlled(&self) -> bool {
+        false
+    }
 }
 
 /// Floor generator that always returns a fixed value
@@ -59,3 +65,26 @@ impl FloorGeneratorTrait for FloorGeneratorLogNormal {
     }
 }
 
+/// Floor generator whose floors adapt to demand: floors are generated like FloorGeneratorLogNormal,
+/// and in each simulation run scaled by the seller's control variable (its first controller)
+/// The seller's converge strategy then moves floors across convergence iterations, e.g. SELL_THROUGH
+pub struct FloorGeneratorControlled {
+    floor_generator: FloorGeneratorLogNormal,
+}
+
+impl FloorGeneratorControlled {
+    /// Create a new FloorGeneratorControlled, generating floors at scale 1.0 like FloorGeneratorLogNormal
+    pub fn new(relative_to_impression_value: f64, stddev: f64) -> Box<Self> {
+        Box::new(Self { floor_generator: *FloorGeneratorLogNormal::new(relative_to_impression_value, stddev) })
+    }
+}
+
+impl FloorGeneratorTrait for FloorGeneratorControlled {
+    fn generate_floor(&self, base_impression_value: f64, rng: &mut StdRng) -> f64 {
+        self.floor_generator.generate_floor(base_impression_value, rng)
+    }
+    
+    fn is_controlled(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,288 @@
+//! Hook registry for simulation lifecycle events
+//!
+//! Scenarios can subscribe closures to events of the convergence loop to do custom measurement
+//! without modifying engine code. Hooks are registered on SimulationConverge and receive the
+//! logger, so they can write their measurements into the regular log streams.
+//!
+//! Supported events:
+//! - on_iteration_start: called before auctions of each convergence iteration are run
+//! - on_auction_result: called after every auction with the impression, all bids and the result
+//! - on_iteration_end: called after each convergence iteration with its statistics
+//! - on_converged: called once when the convergence loop converges, with the final statistics
+//!
+//! Measurements that follow several events and keep state across them implement SimulationObserverTrait
+//! instead, and are registered as a whole with SimulationHooks::add_observer (or
+//! SimulationConverge::with_observer), the scenario keeping a handle to read their results.
+
+use crate::impressions::{AuctionResult, FractionalAuctionResult, Impression, Winner};
+use crate::logger::{Logger, LogEvent};
+use crate::simulationrun::SimulationStat;
+use crate::logln;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Result of a single auction as seen by hooks
+/// Depending on the simulation type either a standard or a fractional result is reported
+pub enum AuctionOutcome<'a> {
+    Standard(&'a AuctionResult),
+    Fractional(&'a FractionalAuctionResult),
+}
+
+/// Data passed to on_auction_result hooks
+pub struct AuctionEvent<'a> {
+    /// Convergence iteration (1-indexed)
+    pub iteration: usize,
+    pub impression: &'a Impression,
+    /// Non-negative bids submitted by campaigns as (campaign_id, bid_cpm)
+    pub bids: &'a [(usize, f64)],
+    pub outcome: AuctionOutcome<'a>,
+}
+
+/// Unique identifier for a registered hook
+pub type HookId = usize;
+
+type IterationStartHook = Box<dyn FnMut(usize, &mut Logger)>;
+type AuctionResultHook = Box<dyn FnMut(&AuctionEvent, &mut Logger)>;
+type IterationEndHook = Box<dyn FnMut(usize, &SimulationStat, &mut Logger)>;
+type ConvergedHook = Box<dyn FnMut(&SimulationStat, &mut Logger)>;
+
+/// Observer of the simulation lifecycle, with a callback per event (all doing nothing unless implemented)
+///
+/// Unlike closures subscribed to single events, an observer keeps its state across all events, e.g. a recorder
+/// counting auction results during an iteration and comparing them with the iteration's statistics at its end.
+pub trait SimulationObserverTrait {
+    /// Called before auctions of each convergence iteration are run, with the 1-indexed iteration number
+    fn on_iteration_start(&mut self, _iteration: usize, _logger: &mut Logger) {}
+
+    /// Called after every auction
+    fn on_auction_result(&mut self, _event: &AuctionEvent, _logger: &mut Logger) {}
+
+    /// Called after each convergence iteration with its statistics (including the targets' actual values)
+    fn on_iteration_end(&mut self, _iteration: usize, _stats: &SimulationStat, _logger: &mut Logger) {}
+
+    /// Called once when the convergence loop converges, with the statistics of the final iteration
+    fn on_converged(&mut self, _stats: &SimulationStat, _logger: &mut Logger) {}
+
+    /// Whether the observer uses on_auction_result
+    /// Observers that don't should return false, so the auction loop can skip collecting bids for them
+    fn wants_auction_results(&self) -> bool {
+        true
+    }
+}
+
+/// Registry of closures and observers subscribed to simulation lifecycle events
+pub struct SimulationHooks {
+    next_id: HookId,
+    iteration_start: Vec<(HookId, IterationStartHook)>,
+    auction_result: Vec<(HookId, AuctionResultHook)>,
+    iteration_end: Vec<(HookId, IterationEndHook)>,
+    converged: Vec<(HookId, ConvergedHook)>,
+    observers: Vec<(HookId, Rc<RefCell<dyn SimulationObserverTrait>>)>,
+}
+
+impl SimulationHooks {
+    /// Create an empty hook registry
+    pub fn new() -> Self {
+        Self {
+            next_id: 1,
+            iteration_start: Vec::new(),
+            auction_result: Vec::new(),
+            iteration_end: Vec::new(),
+            converged: Vec::new(),
+            observers: Vec::new(),
+        }
+    }
+
+    fn allocate_id(&mut self) -> HookId {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Subscribe to the start of each convergence iteration
+    /// The closure receives the 1-indexed iteration number
+    pub fn on_iteration_start<F>(&mut self, hook: F) -> HookId
+    where
+        F: FnMut(usize, &mut Logger) + 'static,
+    {
+        let id = self.allocate_id();
+        self.iteration_start.push((id, Box::new(hook)));
+        id
+    }
+
+    /// Subscribe to the result of every auction
+    pub fn on_auction_result<F>(&mut self, hook: F) -> HookId
+    where
+        F: FnMut(&AuctionEvent, &mut Logger) + 'static,
+    {
+        let id = self.allocate_id();
+        self.auction_result.push((id, Box::new(hook)));
+        id
+    }
+
+    /// Subscribe to the end of each convergence iteration
+    /// The closure receives the 1-indexed iteration number and the iteration's statistics
+    pub fn on_iteration_end<F>(&mut self, hook: F) -> HookId
+    where
+        F: FnMut(usize, &SimulationStat, &mut Logger) + 'static,
+    {
+        let id = self.allocate_id();
+        self.iteration_end.push((id, Box::new(hook)));
+        id
+    }
+
+    /// Subscribe to convergence of the simulation
+    /// The closure receives the statistics of the final (converged) iteration
+    pub fn on_converged<F>(&mut self, hook: F) -> HookId
+    where
+        F: FnMut(&SimulationStat, &mut Logger) + 'static,
+    {
+        let id = self.allocate_id();
+        self.converged.push((id, Box::new(hook)));
+        id
+    }
+
+    /// Subscribe an observer to all events
+    /// The caller keeps a clone of the observer to read its measurements; it must not be borrowed while the
+    /// simulation runs
+    pub fn add_observer<O>(&mut self, observer: Rc<RefCell<O>>) -> HookId
+    where
+        O: SimulationObserverTrait + 'static,
+    {
+        let id = self.allocate_id();
+        self.observers.push((id, observer));
+        id
+    }
+
+    /// Remove a hook or observer by its ID, regardless of which event it is subscribed to
+    pub fn remove(&mut self, id: HookId) {
+        self.iteration_start.retain(|(hook_id, _)| *hook_id != id);
+        self.auction_result.retain(|(hook_id, _)| *hook_id != id);
+        self.iteration_end.retain(|(hook_id, _)| *hook_id != id);
+        self.converged.retain(|(hook_id, _)| *hook_id != id);
+        self.observers.retain(|(hook_id, _)| *hook_id != id);
+    }
+
+    /// Whether any auction result hooks (or observers using auction results) are registered
+    /// Used by the auction loop to skip collecting bids when nobody listens
+    pub fn wants_auction_results(&self) -> bool {
+        !self.auction_result.is_empty() || self.observers.iter().any(|(_, observer)| observer.borrow().wants_auction_results())
+    }
+
+    /// Dispatch iteration start event to all subscribed hooks
+    pub fn iteration_start(&mut self, iteration: usize, logger: &mut Logger) {
+        for (_, hook) in &mut self.iteration_start {
+            hook(iteration, logger);
+        }
+        for (_, observer) in &self.observers {
+            observer.borrow_mut().on_iteration_start(iteration, logger);
+        }
+    }
+
+    /// Dispatch auction result event to all subscribed hooks
+    pub fn auction_result(&mut self, event: &AuctionEvent, logger: &mut Logger) {
+        for (_, hook) in &mut self.auction_result {
+            hook(event, logger);
+        }
+        for (_, observer) in &self.observers {
+            let mut observer = observer.borrow_mut();
+            if observer.wants_auction_results() {
+                observer.on_auction_result(event, logger);
+            }
+        }
+    }
+
+    /// Dispatch iteration end event to all subscribed hooks
+    pub fn iteration_end(&mut self, iteration: usize, stats: &SimulationStat, logger: &mut Logger) {
+        for (_, hook) in &mut self.iteration_end {
+            hook(iteration, stats, logger);
+        }
+        for (_, observer) in &self.observers {
+            observer.borrow_mut().on_iteration_end(iteration, stats, logger);
+        }
+    }
+
+    /// Dispatch converged event to all subscribed hooks
+    pub fn converged(&mut self, stats: &SimulationStat, logger: &mut Logger) {
+        for (_, hook) in &mut self.converged {
+            hook(stats, logger);
+        }
+        for (_, observer) in &self.observers {
+            observer.borrow_mut().on_converged(stats, logger);
+        }
+    }
+}
+
+impl Default for SimulationHooks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create a hook writing one CSV row per standard auction to LogEvent::Auction
+/// Columns match the header written by SimulationConverge when verbose auction logging is enabled
+/// 
+/// # Arguments
+/// * `campaign_to_value_group_mapping` - Value group of each campaign, used to resolve per-campaign values
+pub fn auction_csv_hook(campaign_to_value_group_mapping: Vec<usize>) -> impl FnMut(&AuctionEvent, &mut Logger) {
+    move |event: &AuctionEvent, logger: &mut Logger| {
+        let result = match event.outcome {
+            AuctionOutcome::Standard(result) => result,
+            AuctionOutcome::Fractional(_) => return,
+        };
+        let impression = event.impression;
+
+        // Build CSV row
+        let mut csv_fields = Vec::new();
+
+        // seller_id
+        csv_fields.push(format!("{}", impression.seller_id));
+
+        // demand_id (winner identifier)
+        let demand_id = match &result.winner {
+            Winner::Campaign { campaign_id, .. } => format!("{}", campaign_id),
+            Winner::LOST => "LOST".to_string(),
+            Winner::NO_DEMAND => "NO_DEMAND".to_string(),
+            Winner::WITHDRAWN => "WITHDRAWN".to_string(),
+        };
+        csv_fields.push(demand_id);
+
+        // winning_bid (highest submitted bid, even when it lost to competition)
+        let winning_bid_cpm = event.bids.iter().fold(0.0, |acc: f64, (_, bid)| acc.max(*bid));
+        csv_fields.push(format!("{:.4}", winning_bid_cpm));
+
+        // floor_cpm
+        csv_fields.push(format!("{:.4}", impression.floor_cpm));
+
+        // impression_base_value
+        csv_fields.push(format!("{:.4}", impression.base_impression_value));
+
+        // competing_bid, competing_offset, competing_scale
+        if let Some(comp) = &impression.competition {
+            csv_fields.push(format!("{:.4}", comp.bid_cpm));
+            csv_fields.push(format!("{:.4}", comp.win_rate_actual_sigmoid_offset));
+            csv_fields.push(format!("{:.4}", comp.win_rate_actual_sigmoid_scale));
+        } else {
+            csv_fields.push("".to_string());
+            csv_fields.push("".to_string());
+            csv_fields.push("".to_string());
+        }
+
+        // For each campaign: value and bid
+        // Create a map of campaign_id to bid for quick lookup
+        let bid_map: std::collections::HashMap<usize, f64> = event.bids.iter().cloned().collect();
+        for (campaign_id, &group_index) in campaign_to_value_group_mapping.iter().enumerate() {
+            // campaign value - get from campaign's group
+            csv_fields.push(format!("{:.4}", impression.value_to_campaign_group[group_index]));
+
+            // campaign bid (empty if no bid)
+            if let Some(bid) = bid_map.get(&campaign_id) {
+                csv_fields.push(format!("{:.4}", bid));
+            } else {
+                csv_fields.push("".to_string());
+            }
+        }
+
+        logln!(logger, LogEvent::Auction, "{}", csv_fields.join(","));
+    }
+}
@@ -1,18 +1,20 @@
-use rand::{rngs::StdRng, SeedableRng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand::seq::SliceRandom;
+use rand::distributions::WeightedIndex;
 use rand_distr::Distribution;
 use crate::sellers::Sellers;
 use crate::seller::SellerTrait;
 use crate::campaigns::Campaigns;
 use crate::competition::ImpressionCompetition;
+use crate::viewability::{ViewabilityGeneratorTrait, ViewabilityGeneratorDistribution};
 use crate::logger::LogEvent;
 use crate::errln;
-use crate::logln;
-use crate::utils::get_seed;
-use crate::utils::VERBOSE_AUCTION;
-use std::sync::atomic::Ordering;
+use crate::utils::{get_seed, CorrelatedLogNormal};
+use std::collections::HashMap;
+use std::io::{Read, Write};
 
 /// Represents the winner of an auction
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum Winner {
     Campaign { 
@@ -22,6 +24,8 @@ pub enum Winner {
     },
     LOST,
     NO_DEMAND,
+    /// Impression was sold through another of its supply paths, so this path held no auction
+    WITHDRAWN,
 }
 
 /// Represents a fractional winner in a fractional auction
@@ -36,7 +40,7 @@ pub struct FractionalWinner {
 }
 
 /// Represents the winners of a fractional auction (can have multiple campaigns winning fractions)
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum FractionalWinners {
     Campaigns {
@@ -44,6 +48,8 @@ pub enum FractionalWinners {
     },
     LOST,
     NO_DEMAND,
+    /// Impression was sold through another of its supply paths, so this path held no auction
+    WITHDRAWN,
 }
 
 /// Represents the result of an auction, subsuming the winner with cost information
@@ -60,6 +66,31 @@ pub struct FractionalAuctionResult {
     pub supply_cost: f64,
 }
 
+impl AuctionResult {
+    /// Scale the winner's costs of an auction run on bids net of an adapter fee back up to the bids the campaigns made
+    /// (the supply cost stays based on the net charge the seller receives)
+    pub fn gross_up_adapter_fee(&mut self, adapter_fee: f64) {
+        if let Winner::Campaign { virtual_cost, buyer_charge, .. } = &mut self.winner {
+            *virtual_cost /= 1.0 - adapter_fee;
+            *buyer_charge /= 1.0 - adapter_fee;
+        }
+    }
+}
+
+impl FractionalAuctionResult {
+    /// Scale the winners' costs and bids of an auction run on bids net of an adapter fee back up to the bids the
+    /// campaigns made (the supply cost stays based on the net charges the seller receives)
+    pub fn gross_up_adapter_fee(&mut self, adapter_fee: f64) {
+        if let FractionalWinners::Campaigns { winners } = &mut self.winner {
+            for winner in winners {
+                winner.virtual_cost /= 1.0 - adapter_fee;
+                winner.buyer_charge /= 1.0 - adapter_fee;
+                winner.bid_cpm /= 1.0 - adapter_fee;
+            }
+        }
+    }
+}
+
 /// Object-safe wrapper for Distribution<f64> that works with StdRng
 /// This is needed because Distribution<f64> cannot be made into a trait object
 /// due to its generic sample method
@@ -73,11 +104,178 @@ impl<D: Distribution<f64>> DistributionF64 for D {
     }
 }
 
+/// How active users are, i.e. how impressions are spread over the users (see ImpressionsParam::with_user_activity)
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserActivity {
+    /// Every user is equally likely to be shown each impression
+    UNIFORM,
+    /// The user with ID k is shown impressions with probability proportional to 1 / (k + 1)^exponent, so
+    /// activity is heavy-tailed: a few users see most impressions while most users see few
+    ZIPF { exponent: f64 },
+}
+
+/// Picks the user each impression is shown to, according to the users' activity
+struct UserGenerator {
+    num_users: usize,
+    /// Activity weights of the users (None = uniform)
+    activity_weights: Option<WeightedIndex<f64>>,
+}
+
+impl UserGenerator {
+    fn new(num_users: usize, user_activity: &UserActivity) -> Self {
+        let activity_weights = match user_activity {
+            UserActivity::UNIFORM => None,
+            UserActivity::ZIPF { exponent } => Some(WeightedIndex::new((1..=num_users).map(|rank| (rank as f64).powf(-exponent)))
+                .expect("Zipf weights of users are positive")),
+        };
+        Self { num_users, activity_weights }
+    }
+
+    fn generate_user(&self, rng: &mut StdRng) -> usize {
+        match &self.activity_weights {
+            Some(activity_weights) => activity_weights.sample(rng),
+            None => rng.gen_range(0..self.num_users),
+        }
+    }
+}
+
+/// Category of a categorical impression attribute, e.g. a device type or a geo tier
+/// (see ImpressionsParam::with_device_types and ImpressionsParam::with_geo_tiers)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeCategory {
+    pub name: String,
+    /// Relative share of impressions in the category
+    pub weight: f64,
+    /// Factor on the values of the category's impressions to campaigns
+    pub value_multiplier: f64,
+    /// Factor on the competing bids of the category's impressions (the category's competition level)
+    pub competition_multiplier: f64,
+}
+
+impl AttributeCategory {
+    /// Create a category with the given share of impressions, valued and competed for like all impressions
+    pub fn new(name: &str, weight: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            weight,
+            value_multiplier: 1.0,
+            competition_multiplier: 1.0,
+        }
+    }
+
+    /// Multiply the values of the category's impressions to campaigns
+    pub fn with_value_multiplier(mut self, value_multiplier: f64) -> Self {
+        self.value_multiplier = value_multiplier;
+        self
+    }
+
+    /// Multiply the competing bids of the category's impressions
+    pub fn with_competition_multiplier(mut self, competition_multiplier: f64) -> Self {
+        self.competition_multiplier = competition_multiplier;
+        self
+    }
+}
+
+/// Check the categories of an attribute and build the distribution its categories are drawn from
+/// (None when the attribute has no categories, i.e. is not modeled)
+fn attribute_dist(attribute: &str, categories: &[AttributeCategory]) -> Option<WeightedIndex<f64>> {
+    if categories.is_empty() {
+        return None;
+    }
+    for category in categories {
+        assert!(category.weight > 0.0, "Weight of {} '{}' must be positive, got {}", attribute, category.name, category.weight);
+        assert!(category.value_multiplier >= 0.0, "Value multiplier of {} '{}' must not be negative, got {}", attribute, category.name, category.value_multiplier);
+        assert!(category.competition_multiplier > 0.0, "Competition multiplier of {} '{}' must be positive, got {}", attribute, category.name, category.competition_multiplier);
+    }
+    Some(WeightedIndex::new(categories.iter().map(|category| category.weight)).expect("Category weights are positive"))
+}
+
+/// Oversampling of rare high-value impressions, with weights correcting for it (see ImpressionsParam::with_value_oversampling)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueOversampling {
+    /// Share of impressions with the highest base values that is oversampled (e.g. 0.01 for the top 1%)
+    pub tail_share: f64,
+    /// How many times more often tail impressions are sampled than they occur
+    pub oversampling_factor: f64,
+}
+
+/// Number of base values drawn to locate the tail of the base value distribution
+const OVERSAMPLING_PILOT_SAMPLES: usize = 100_000;
+
+/// Stratified sampler of base values: the tail above threshold is drawn with probability tail_probability
+/// instead of tail_share, each stratum by rejection sampling from the base value distribution
+struct ValueOversampler {
+    threshold: f64,
+    tail_probability: f64,
+    /// Weights correcting the tail and the body for their sampling probability
+    tail_weight: f64,
+    body_weight: f64,
+}
+
+impl ValueOversampler {
+    /// Locate the tail of the distribution on a pilot sample drawn with rng_pilot
+    fn new(base_impression_value_dist: &dyn DistributionF64, value_oversampling: &ValueOversampling, rng_pilot: &mut StdRng) -> Self {
+        let mut pilot: Vec<f64> = (0..OVERSAMPLING_PILOT_SAMPLES).map(|_| base_impression_value_dist.sample(rng_pilot)).collect();
+        pilot.sort_by(|a, b| a.total_cmp(b));
+        let threshold = pilot[((1.0 - value_oversampling.tail_share) * OVERSAMPLING_PILOT_SAMPLES as f64) as usize];
+        let tail_probability = value_oversampling.tail_share * value_oversampling.oversampling_factor;
+        Self {
+            threshold,
+            tail_probability,
+            tail_weight: value_oversampling.tail_share / tail_probability,
+            body_weight: (1.0 - value_oversampling.tail_share) / (1.0 - tail_probability),
+        }
+    }
+
+    /// Sample a base value and its weight
+    fn sample(&self, base_impression_value_dist: &dyn DistributionF64, rng: &mut StdRng) -> (f64, f64) {
+        let in_tail = rng.gen_bool(self.tail_probability);
+        loop {
+            let base_impression_value = base_impression_value_dist.sample(rng);
+            if (base_impression_value > self.threshold) == in_tail {
+                return (base_impression_value, if in_tail { self.tail_weight } else { self.body_weight });
+            }
+        }
+    }
+}
+
 /// Struct for providing distribution parameters for impression generation
 /// Contains pre-initialized distribution boxes
 pub struct ImpressionsParam {
     pub base_impression_value_dist: Box<dyn DistributionF64>,
     pub value_to_campaign_multiplier_dist: Box<dyn DistributionF64>,
+    /// Correlated sampler of the value group multipliers, replacing the i.i.d. draws from
+    /// value_to_campaign_multiplier_dist (None = independent multipliers)
+    pub correlated_value_multipliers: Option<CorrelatedLogNormal>,
+    /// Generator of per-impression viewability (None = every impression is fully viewable)
+    pub viewability_generator: Option<Box<dyn ViewabilityGeneratorTrait>>,
+    /// Distribution of impression time of day in hours (None = uniform over the day)
+    pub time_of_day_dist: Option<Box<dyn DistributionF64>>,
+    /// Distribution of per-impression quality score (None = every impression has quality score 1.0)
+    pub quality_score_dist: Option<Box<dyn DistributionF64>>,
+    /// Relative traffic of each simulated day (a single day by default)
+    pub day_weights: Vec<f64>,
+    /// Distribution of per-impression conversion rate (None = conversions are not modeled)
+    pub conversion_rate_dist: Option<Box<dyn DistributionF64>>,
+    /// Elasticity of conversion probability to base impression value (0.0 = independent of value)
+    pub conversion_value_elasticity: f64,
+    /// Distribution of the value of a conversion (None = every conversion is worth 1.0)
+    pub conversion_value_dist: Option<Box<dyn DistributionF64>>,
+    /// Number of users impressions are shown to (None = users are not modeled)
+    pub num_users: Option<usize>,
+    /// How impressions are spread over the users
+    pub user_activity: UserActivity,
+    /// Relative share of impressions in each audience segment (None = segments are not modeled)
+    pub segment_weights: Option<Vec<f64>>,
+    /// Device types of impressions (empty = device types are not modeled)
+    pub device_types: Vec<AttributeCategory>,
+    /// Geo tiers of impressions (empty = geo tiers are not modeled)
+    pub geo_tiers: Vec<AttributeCategory>,
+    /// Oversampling of high-value impressions (None = impressions are sampled as they occur, with weight 1.0)
+    pub value_oversampling: Option<ValueOversampling>,
+    /// Per-seller overrides of the distributions above (indexed by seller_id), see with_seller_params
+    pub seller_params: HashMap<usize, SellerImpressionsParam>,
 }
 
 impl ImpressionsParam {
@@ -94,7 +292,293 @@ impl ImpressionsParam {
         Self {
             base_impression_value_dist: Box::new(base_impression_value_dist),
             value_to_campaign_multiplier_dist: Box::new(value_to_campaign_multiplier_dist),
+            correlated_value_multipliers: None,
+            viewability_generator: None,
+            time_of_day_dist: None,
+            quality_score_dist: None,
+            day_weights: vec![1.0],
+            conversion_rate_dist: None,
+            conversion_value_elasticity: 0.0,
+            conversion_value_dist: None,
+            num_users: None,
+            user_activity: UserActivity::UNIFORM,
+            segment_weights: None,
+            device_types: Vec::new(),
+            geo_tiers: Vec::new(),
+            value_oversampling: None,
+            seller_params: HashMap::new(),
+        }
+    }
+
+    /// Draw the value multipliers of the value groups from correlated log-normal distributions instead of
+    /// i.i.d. from value_to_campaign_multiplier_dist, so that campaigns with similar targeting value the same
+    /// impressions alike
+    /// 
+    /// # Arguments
+    /// * `mean` - Mean of every group's multiplier
+    /// * `stddev` - Standard deviation of every group's multiplier
+    /// * `correlation` - Correlation matrix of the log multipliers, indexed by value group (groups created with
+    ///   Campaigns::create_value_group first, then one group per ungrouped campaign in order of campaign IDs)
+    pub fn with_correlated_value_multipliers(mut self, mean: f64, stddev: f64, correlation: Vec<Vec<f64>>) -> Self {
+        self.correlated_value_multipliers = Some(CorrelatedLogNormal::new(mean, stddev, correlation));
+        self
+    }
+
+    /// Set the distribution used to sample per-impression viewability probability, measured exactly
+    /// Samples are clamped to [0, 1]
+    pub fn with_viewability<D>(self, viewability_dist: D) -> Self
+    where
+        D: Distribution<f64> + 'static,
+    {
+        self.with_viewability_generator(ViewabilityGeneratorDistribution::new(viewability_dist))
+    }
+
+    /// Set the generator of per-impression viewability and its measurement
+    pub fn with_viewability_generator(mut self, viewability_generator: Box<dyn ViewabilityGeneratorTrait>) -> Self {
+        self.viewability_generator = Some(viewability_generator);
+        self
+    }
+
+    /// Set the distribution used to sample impression time of day in hours (e.g., to model daily traffic peaks)
+    /// Samples are wrapped around to [0, HOURS_PER_DAY)
+    pub fn with_time_of_day<D>(mut self, time_of_day_dist: D) -> Self
+    where
+        D: Distribution<f64> + 'static,
+    {
+        self.time_of_day_dist = Some(Box::new(time_of_day_dist));
+        self
+    }
+
+    /// Make impressions arrive over the day as a Poisson process with the given hourly arrival rates,
+    /// instead of uniformly (see HourlyArrivalRates)
+    /// 
+    /// # Arguments
+    /// * `hourly_rates` - Relative arrival rate of each hour of the day (HOURS_PER_DAY entries)
+    pub fn with_hourly_arrival_rates(self, hourly_rates: Vec<f64>) -> Self {
+        self.with_time_of_day(HourlyArrivalRates::new(hourly_rates))
+    }
+
+    /// Spread impressions over multiple days, each day getting a share of every seller's impressions
+    /// proportional to its weight (e.g., [1.0, 2.0] doubles traffic on the second day)
+    /// 
+    /// # Panics
+    /// Panics if there are no days or the weights are not positive
+    pub fn with_days(mut self, day_weights: Vec<f64>) -> Self {
+        assert!(!day_weights.is_empty(), "At least one day has to be simulated");
+        assert!(day_weights.iter().all(|&weight| weight > 0.0), "Day weights must be positive: {:?}", day_weights);
+        self.day_weights = day_weights;
+        self
+    }
+
+    /// Model conversions: every impression converts with a probability of
+    /// conversion_rate × (base_impression_value / mean base_impression_value)^value_elasticity, clamped to [0, 1]
+    /// where conversion_rate is sampled from conversion_rate_dist
+    /// 
+    /// # Arguments
+    /// * `conversion_rate_dist` - Distribution of per-impression conversion rate
+    /// * `value_elasticity` - Correlation of conversions with value (0.0 = independent, 1.0 = proportional to value)
+    pub fn with_conversions<D>(mut self, conversion_rate_dist: D, value_elasticity: f64) -> Self
+    where
+        D: Distribution<f64> + 'static,
+    {
+        self.conversion_rate_dist = Some(Box::new(conversion_rate_dist));
+        self.conversion_value_elasticity = value_elasticity;
+        self
+    }
+
+    /// Sample the value of every impression's conversion from conversion_value_dist, instead of every conversion
+    /// being worth 1.0; only has an effect when conversions are modeled (see with_conversions)
+    pub fn with_conversion_values<D>(mut self, conversion_value_dist: D) -> Self
+    where
+        D: Distribution<f64> + 'static,
+    {
+        self.conversion_value_dist = Some(Box::new(conversion_value_dist));
+        self
+    }
+
+    /// Show every impression to one of num_users users, picked uniformly at random
+    /// Needed by campaign frequency caps (see Campaigns::set_frequency_cap)
+    pub fn with_users(self, num_users: usize) -> Self {
+        self.with_user_activity(num_users, UserActivity::UNIFORM)
+    }
+
+    /// Show every impression to one of num_users users, picked according to their activity
+    /// (e.g. UserActivity::ZIPF for a few heavy users seeing most impressions)
+    pub fn with_user_activity(mut self, num_users: usize, user_activity: UserActivity) -> Self {
+        assert!(num_users > 0, "At least one user is needed");
+        if let UserActivity::ZIPF { exponent } = user_activity {
+            assert!(exponent >= 0.0, "Zipf exponent of user activity must not be negative, got {}", exponent);
         }
+        self.num_users = Some(num_users);
+        self.user_activity = user_activity;
+        self
+    }
+
+    /// Label every impression with an audience segment, segment i drawn with probability proportional to
+    /// segment_weights[i] (e.g., [3.0, 1.0] puts a quarter of the impressions in segment 1)
+    /// Needed by campaign segment targeting (see Campaigns::set_segment_targeting)
+    /// 
+    /// # Panics
+    /// Panics if there are no segments or the weights are not positive
+    pub fn with_segments(mut self, segment_weights: Vec<f64>) -> Self {
+        assert!(!segment_weights.is_empty(), "At least one segment is needed");
+        assert!(segment_weights.iter().all(|&weight| weight > 0.0), "Segment weights must be positive: {:?}", segment_weights);
+        self.segment_weights = Some(segment_weights);
+        self
+    }
+
+    /// Give every impression a device type, drawn with probability proportional to the categories' weights
+    /// The impression's values to campaigns and competing bid are multiplied by the device type's multipliers
+    pub fn with_device_types(mut self, device_types: Vec<AttributeCategory>) -> Self {
+        self.device_types = device_types;
+        self
+    }
+
+    /// Give every impression a geo tier, drawn with probability proportional to the categories' weights
+    /// The impression's values to campaigns and competing bid are multiplied by the geo tier's multipliers
+    /// (on top of the device type's)
+    pub fn with_geo_tiers(mut self, geo_tiers: Vec<AttributeCategory>) -> Self {
+        self.geo_tiers = geo_tiers;
+        self
+    }
+
+    /// Oversample the impressions with the highest base values, giving every impression a weight that corrects
+    /// for its sampling probability (Impression::weight), so that statistics weighted by it estimate the
+    /// statistics of impressions sampled as they occur, with less noise on the tail
+    /// 
+    /// # Arguments
+    /// * `tail_share` - Share of impressions with the highest base values to oversample (e.g. 0.01 for the top 1%)
+    /// * `oversampling_factor` - How many times more often the tail is sampled (its impressions get weight 1 / factor)
+    /// 
+    /// # Panics
+    /// Panics unless 0 < tail_share < 1, oversampling_factor ≥ 1 and the oversampled tail is below all impressions
+    pub fn with_value_oversampling(mut self, tail_share: f64, oversampling_factor: f64) -> Self {
+        assert!(tail_share > 0.0 && tail_share < 1.0, "Oversampled tail share must be in (0, 1), got {}", tail_share);
+        assert!(oversampling_factor >= 1.0, "Oversampling factor must be at least 1, got {}", oversampling_factor);
+        assert!(tail_share * oversampling_factor < 1.0, "Oversampled tail must be less than all impressions, got {} × {}", tail_share, oversampling_factor);
+        self.value_oversampling = Some(ValueOversampling { tail_share, oversampling_factor });
+        self
+    }
+
+    /// Value and competition multipliers of an impression's device type and geo tier, multiplied together
+    fn attribute_multipliers(&self, device_type: Option<usize>, geo_tier: Option<usize>) -> (f64, f64) {
+        let categories = [device_type.map(|index| &self.device_types[index]), geo_tier.map(|index| &self.geo_tiers[index])];
+        categories.iter().flatten().fold((1.0, 1.0), |(value_multiplier, competition_multiplier), category| {
+            (value_multiplier * category.value_multiplier, competition_multiplier * category.competition_multiplier)
+        })
+    }
+
+    /// Set the distribution used to sample per-impression quality score
+    /// Samples are clamped to [0, 1]
+    pub fn with_quality_score<D>(mut self, quality_score_dist: D) -> Self
+    where
+        D: Distribution<f64> + 'static,
+    {
+        self.quality_score_dist = Some(Box::new(quality_score_dist));
+        self
+    }
+
+    /// Override distributions of one seller's impressions (e.g., premium vs remnant sellers), the distributions
+    /// not set in `seller_params` fall back to the global ones
+    /// Floors are per seller already, set by the seller's floor generator
+    pub fn with_seller_params(mut self, seller_id: usize, seller_params: SellerImpressionsParam) -> Self {
+        self.seller_params.insert(seller_id, seller_params);
+        self
+    }
+}
+
+/// Per-seller overrides of ImpressionsParam distributions (None = use the global distribution)
+#[derive(Default)]
+pub struct SellerImpressionsParam {
+    pub base_impression_value_dist: Option<Box<dyn DistributionF64>>,
+    /// Generator of per-impression viewability, also when globally every impression is fully viewable
+    pub viewability_generator: Option<Box<dyn ViewabilityGeneratorTrait>>,
+}
+
+impl SellerImpressionsParam {
+    /// Create overrides that keep all global distributions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the distribution of the seller's base impression values
+    pub fn with_base_impression_value<D>(mut self, base_impression_value_dist: D) -> Self
+    where
+        D: Distribution<f64> + 'static,
+    {
+        self.base_impression_value_dist = Some(Box::new(base_impression_value_dist));
+        self
+    }
+
+    /// Set the distribution of the seller's per-impression viewability probability, measured exactly
+    /// Samples are clamped to [0, 1]
+    pub fn with_viewability<D>(self, viewability_dist: D) -> Self
+    where
+        D: Distribution<f64> + 'static,
+    {
+        self.with_viewability_generator(ViewabilityGeneratorDistribution::new(viewability_dist))
+    }
+
+    /// Set the generator of the seller's per-impression viewability and its measurement
+    pub fn with_viewability_generator(mut self, viewability_generator: Box<dyn ViewabilityGeneratorTrait>) -> Self {
+        self.viewability_generator = Some(viewability_generator);
+        self
+    }
+}
+
+/// Length of the simulated day, impression timestamps are hours in [0, num_days × HOURS_PER_DAY)
+pub const HOURS_PER_DAY: f64 = 24.0;
+
+/// Identifies files written by Impressions::write_to
+const IMPRESSIONS_FILE_MAGIC: &[u8; 8] = b"MRKIMPR\0";
+
+/// Version of the impressions file format, bumped on incompatible changes
+const IMPRESSIONS_FILE_VERSION: u32 = 3;
+
+/// Number of value components of an impression, see Impression::value_components
+pub const NUM_VALUE_COMPONENTS: usize = 3;
+
+/// Distribution of impression time of day following hourly arrival rates
+/// 
+/// Given the number of impressions, the arrival times of a Poisson process with piecewise constant hourly
+/// rates are independent, each in an hour with probability proportional to the hour's rate and uniform
+/// within the hour. Sampling the time of day from this distribution therefore models Poisson arrivals
+/// (equal rates give the default uniform arrivals).
+pub struct HourlyArrivalRates {
+    /// Rates of the hours up to and including each hour
+    cumulative_rates: Vec<f64>,
+}
+
+impl HourlyArrivalRates {
+    /// Create the distribution from the relative arrival rate of each hour of the day
+    /// 
+    /// # Panics
+    /// Panics if there aren't HOURS_PER_DAY rates, any is negative or all are zero
+    pub fn new(hourly_rates: Vec<f64>) -> Self {
+        assert_eq!(hourly_rates.len(), HOURS_PER_DAY as usize, "Hourly arrival rates need one rate per hour of the day");
+        assert!(hourly_rates.iter().all(|&rate| rate >= 0.0), "Hourly arrival rates must not be negative: {:?}", hourly_rates);
+        let cumulative_rates: Vec<f64> = hourly_rates.iter()
+            .scan(0.0, |cumulative_rate, &rate| {
+                *cumulative_rate += rate;
+                Some(*cumulative_rate)
+            })
+            .collect();
+        assert!(cumulative_rates[cumulative_rates.len() - 1] > 0.0, "Some hourly arrival rate must be positive");
+        Self { cumulative_rates }
+    }
+
+    /// Share of the impressions arriving in the given hour of the day
+    pub fn hour_share(&self, hour: usize) -> f64 {
+        let previous = if hour == 0 { 0.0 } else { self.cumulative_rates[hour - 1] };
+        (self.cumulative_rates[hour] - previous) / self.cumulative_rates[self.cumulative_rates.len() - 1]
+    }
+}
+
+impl Distribution<f64> for HourlyArrivalRates {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let draw = rng.gen_range(0.0..self.cumulative_rates[self.cumulative_rates.len() - 1]);
+        let hour = self.cumulative_rates.partition_point(|&cumulative_rate| cumulative_rate <= draw);
+        hour as f64 + rng.gen::<f64>()
     }
 }
 
@@ -105,50 +589,115 @@ pub struct Impression {
     pub seller_id: usize,
     pub competition: Option<ImpressionCompetition>,
     pub floor_cpm: f64,
+    /// Soft floor of the seller (None = no soft floor), winning bids below it are charged second price
+    pub soft_floor_cpm: Option<f64>,
     pub value_to_campaign_group: Vec<f64>,
     pub base_impression_value: f64,  // Store base value for logging
+    /// Probability that the impression will be viewable (1.0 when viewability is not modeled)
+    pub viewability: f64,
+    /// Viewability measured for the impression, what bidders observe (equals viewability unless it is
+    /// measured with error, see ViewabilityGeneratorTrait::measure_viewability)
+    pub measured_viewability: f64,
+    /// Time in hours since the start of the simulation when the impression is offered,
+    /// in [0, num_days × HOURS_PER_DAY) (time of day on single-day simulations)
+    pub timestamp: f64,
+    /// Quality score of the impression in [0, 1] (1.0 when quality is not modeled)
+    pub quality_score: f64,
+    /// Probability that the impression leads to a conversion (0.0 when conversions are not modeled)
+    pub conversion_probability: f64,
+    /// Whether the impression converts when won, sampled once from conversion_probability
+    pub converted: bool,
+    /// Value of the impression's conversion, counted when it converts (0.0 when conversions are not modeled)
+    pub conversion_value: f64,
+    /// User the impression is shown to (None when users are not modeled)
+    pub user_id: Option<usize>,
+    /// Audience segment of the impression (None when segments are not modeled)
+    pub segment: Option<usize>,
+    /// Device type of the impression, indexing ImpressionsParam::device_types (None when device types are not modeled)
+    pub device_type: Option<usize>,
+    /// Geo tier of the impression, indexing ImpressionsParam::geo_tiers (None when geo tiers are not modeled)
+    pub geo_tier: Option<usize>,
+    /// Importance sampling weight the impression counts with in all statistics (1.0 unless impressions are
+    /// oversampled, see ImpressionsParam::with_value_oversampling)
+    pub weight: f64,
+    /// Underlying impression offered through several supply paths, shared by all its paths
+    /// (None when the impression is offered by a single seller)
+    pub supply_id: Option<usize>,
 }
 
 impl Impression {
+    /// Components of the impression's value to a campaign as bidders observe them, all in CPM:
+    /// [value, viewable value (value × measured_viewability), quality value (value × quality_score)]
+    pub fn value_components(&self, value_to_campaign: f64) -> [f64; NUM_VALUE_COMPONENTS] {
+        [
+            value_to_campaign,
+            value_to_campaign * self.measured_viewability,
+            value_to_campaign * self.quality_score,
+        ]
+    }
 
-    /// Run an auction for this impression with the given campaigns, campaign converges, seller, and seller convergence parameters
+    /// Day of the simulation (0-indexed) the impression is offered on
+    pub fn day(&self) -> usize {
+        (self.timestamp / HOURS_PER_DAY) as usize
+    }
+
+    /// Time of day in hours [0, HOURS_PER_DAY) the impression is offered at
+    pub fn time_of_day(&self) -> f64 {
+        self.timestamp.rem_euclid(HOURS_PER_DAY)
+    }
+
+    /// Hour of the day (0-indexed) the impression is offered in
+    pub fn hour_of_day(&self) -> usize {
+        self.time_of_day() as usize
+    }
+
+    /// Deterministic seed identifying this impression, mixed with get_seed(local_seed)
+    /// Used for per-impression randomness that must be the same in every iteration of convergence
+    pub fn seed(&self, local_seed: u64) -> u64 {
+        let mut seed = get_seed(local_seed);
+        for bits in [self.base_impression_value.to_bits(), self.floor_cpm.to_bits(), self.seller_id as u64] {
+            seed = (seed ^ bits).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            seed ^= seed >> 31;
+        }
+        seed
+    }
+
+    /// Run an auction for this impression with the given campaign bids and seller
     /// Returns the auction result
-    pub fn run_auction(&self, campaigns: &Campaigns, campaign_converges: &[Vec<&dyn crate::controllers::ControllerStateTrait>], seller: &dyn SellerTrait, seller_converge: &dyn crate::controllers::ControllerStateTrait, logger: &mut crate::logger::Logger) -> AuctionResult {
-        // Get bids from all campaigns
+    /// 
+    /// `campaign_bids`: Bid of every campaign (indexed by campaign_id), None when the campaign doesn't bid
+    /// 
+    /// `seller_control_variable`: Control variable of the seller, used for its supply cost (see SellerTrait::get_supply_cost_cpm)
+    /// 
+    /// `seller_volume`: Number of impressions the seller sold earlier in the run, used for its supply cost
+    /// 
+    /// `bids`: When provided, all non-negative bids are collected into it as (campaign_id, bid_cpm)
+    pub fn run_auction(&self, campaign_bids: &[Option<f64>], seller: &dyn SellerTrait, seller_control_variable: f64, seller_volume: usize, mut bids: Option<&mut Vec<(usize, f64)>>, logger: &mut crate::logger::Logger) -> AuctionResult {
+        // Find the highest and second highest campaign bids
         let mut winning_bid_cpm = 0.0;
+        let mut second_bid_cpm = 0.0;
         let mut winning_campaign_id: Option<usize> = None;
-        let mut all_bids = if VERBOSE_AUCTION.load(Ordering::Relaxed) {
-            Some(Vec::new())
-        } else {
-            None
-        };
 
-        // Get seller_control_factor from seller using get_control_variable
-        let seller_control_factor = seller.get_control_variable(seller_converge);
-
-        for campaign in &campaigns.campaigns {
-            let campaign_id = campaign.campaign_id();
-            let campaign_converge = &campaign_converges[campaign_id];
-            // Resolve value_to_campaign at call site using campaign's group ID
-            let group_id = campaigns.campaign_to_value_group_mapping[campaign_id];
-            let value_to_campaign = self.value_to_campaign_group[group_id];
-            // Use the trait method for get_bid
-            if let Some(bid) = campaign.get_bid(self, &campaign_converge, seller_control_factor, value_to_campaign, logger) {
+        for (campaign_id, campaign_bid) in campaign_bids.iter().enumerate() {
+            if let Some(bid) = *campaign_bid {
                 // Check if bid is below zero - skip negative bids
                 if bid < 0.0 {
                     errln!(logger, LogEvent::Simulation, "Bid below zero: {:.4} from campaign_id: {}, skipping", bid, campaign_id);
                     continue;
                 }
-                if let Some(bids) = &mut all_bids {
+                if let Some(bids) = bids.as_deref_mut() {
                     bids.push((campaign_id, bid));
                 }
                 if bid > winning_bid_cpm {
+                    second_bid_cpm = winning_bid_cpm;
                     winning_bid_cpm = bid;
                     winning_campaign_id = Some(campaign_id);
                     //println!("Winning bid: {:.4}, campaign_id: {}", bid, campaign_id);
+                } else if bid > second_bid_cpm {
+                    second_bid_cpm = bid;
                 }
             }
-            // If get_bid returns None, skip this campaign (warning already logged)
+            // Campaigns without a bid are skipped
         }
 
         // Determine the result based on winning bid
@@ -157,7 +706,7 @@ impl Impression {
             // No campaigns participated
             let campaign_id = match winning_campaign_id {
                 Some(id) => id,
-                None => break 'result (Winner::NO_DEMAND, seller.get_supply_cost_cpm(0.0) / 1000.0),
+                None => break 'result (Winner::NO_DEMAND, seller.get_unsold_supply_cost_cpm() / 1000.0),
             };
             
             // Winning bid is below z or below competition - no winner (LOST)
@@ -168,14 +717,22 @@ impl Impression {
             };
             
             if winning_bid_cpm < minimum_cpm_to_win {
-                break 'result (Winner::LOST, seller.get_supply_cost_cpm(0.0) / 1000.0);
+                break 'result (Winner::LOST, seller.get_unsold_supply_cost_cpm() / 1000.0);
             }
             
             // Valid winner - bid passes all checks (floor and competition if present)
-            // Set cost values - virtual_cost and buyer_charge are always the winning bid
-            let supply_cost = seller.get_supply_cost_cpm(winning_bid_cpm) / 1000.0;
-            let virtual_cost = winning_bid_cpm / 1000.0;
-            let buyer_charge = winning_bid_cpm / 1000.0;
+            // Set cost values - virtual_cost and buyer_charge are the winning bid, unless the bid is below
+            // the soft floor, in which case the winner pays second price (next campaign bid, competition or floor)
+            // Second price sellers charge the second price on every win, recording the winning bid as virtual cost
+            let second_price_cpm = second_bid_cpm.max(minimum_cpm_to_win);
+            let (charge_cpm, virtual_cost_cpm) = match self.soft_floor_cpm {
+                _ if seller.is_second_price() => (second_price_cpm, winning_bid_cpm),
+                Some(soft_floor_cpm) if winning_bid_cpm < soft_floor_cpm => (second_price_cpm, second_price_cpm),
+                _ => (winning_bid_cpm, winning_bid_cpm),
+            };
+            let supply_cost = seller.get_supply_cost_cpm(charge_cpm, seller_control_variable, seller_volume) / 1000.0;
+            let virtual_cost = virtual_cost_cpm / 1000.0;
+            let buyer_charge = charge_cpm / 1000.0;
             
             // Convert from CPM to actual cost by dividing by 1000
             (Winner::Campaign {
@@ -185,78 +742,29 @@ impl Impression {
             }, supply_cost)
         };
 
-        // Log auction data in CSV format
-        if VERBOSE_AUCTION.load(Ordering::Relaxed) {
-            let all_bids = all_bids.as_ref().unwrap();
-            
-            // Build CSV row
-            let mut csv_fields = Vec::new();
-            
-            // seller_id
-            csv_fields.push(format!("{}", self.seller_id));
-            
-            // demand_id (winner identifier)
-            let demand_id = match &winner {
-                Winner::Campaign { campaign_id, .. } => format!("{}", campaign_id),
-                Winner::LOST => "LOST".to_string(),
-                Winner::NO_DEMAND => "NO_DEMAND".to_string(),
-            };
-            csv_fields.push(demand_id);
-            
-            // winning_bid
-            csv_fields.push(format!("{:.4}", winning_bid_cpm));
-            
-            // floor_cpm
-            csv_fields.push(format!("{:.4}", self.floor_cpm));
-            
-            // impression_base_value
-            csv_fields.push(format!("{:.4}", self.base_impression_value));
-            
-            // competing_bid, competing_offset, competing_scale
-            if let Some(comp) = &self.competition {
-                csv_fields.push(format!("{:.4}", comp.bid_cpm));
-                csv_fields.push(format!("{:.4}", comp.win_rate_actual_sigmoid_offset));
-                csv_fields.push(format!("{:.4}", comp.win_rate_actual_sigmoid_scale));
-            } else {
-                csv_fields.push("".to_string());
-                csv_fields.push("".to_string());
-                csv_fields.push("".to_string());
-            }
-            
-            // For each campaign: value and bid
-            // Create a map of campaign_id to bid for quick lookup
-            let bid_map: std::collections::HashMap<usize, f64> = all_bids.iter().cloned().collect();
-            
-            for campaign_id in 0..campaigns.campaigns.len() {
-                // campaign value - get from campaign's group
-                let group_index = campaigns.campaign_to_value_group_mapping[campaign_id];
-                csv_fields.push(format!("{:.4}", self.value_to_campaign_group[group_index]));
-                
-                // campaign bid (empty if no bid)
-                if let Some(bid) = bid_map.get(&campaign_id) {
-                    csv_fields.push(format!("{:.4}", bid));
-                } else {
-                    csv_fields.push("".to_string());
-                }
-            }
-            
-            logln!(logger, LogEvent::Auction, "{}", csv_fields.join(","));
-        }
-
         AuctionResult {
             winner,
             supply_cost,
         }
     }
 
-    /// Run a fractional auction for this impression with the given campaigns, campaign converges, seller, and seller convergence parameters
+    /// Run a fractional auction for this impression with the given campaign bids and seller
     /// Returns the fractional auction result
     /// 
+    /// `campaign_bids`: Bid of every campaign (indexed by campaign_id), None when the campaign doesn't bid
+    /// 
     /// `softmax_temperature`: Temperature parameter for softmax calculation
     /// - Lower values (< 1.0) make the distribution sharper (more concentrated on highest bid)
     /// - Higher values (> 1.0) make the distribution smoother (more uniform)
     /// - Default: 1.0 (standard softmax)
-    pub fn run_fractional_auction(&self, campaigns: &Campaigns, campaign_converges: &[Vec<&dyn crate::controllers::ControllerStateTrait>], seller: &dyn SellerTrait, seller_converge: &dyn crate::controllers::ControllerStateTrait, softmax_temperature: f64, logger: &mut crate::logger::Logger) -> FractionalAuctionResult {
+    /// 
+    /// `seller_control_variable`: Control variable of the seller, used for its supply cost (see SellerTrait::get_supply_cost_cpm)
+    /// 
+    /// `seller_volume`: Number of impressions the seller sold earlier in the run, used for its supply cost
+    /// 
+    /// `bids`: When provided, all non-negative bids are collected into it as (campaign_id, bid_cpm)
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_fractional_auction(&self, campaign_bids: &[Option<f64>], seller: &dyn SellerTrait, softmax_temperature: f64, seller_control_variable: f64, seller_volume: usize, mut bids: Option<&mut Vec<(usize, f64)>>, logger: &mut crate::logger::Logger) -> FractionalAuctionResult {
         // Calculate minimum CPM needed to win this impression
         // Must be at least the floor, and if competition exists, must beat the competing bid
         let minimum_cpm_to_win = if let Some(competition) = &self.competition {
@@ -269,29 +777,30 @@ impl Impression {
         let mut fractional_winners: Vec<FractionalWinner> = Vec::new();
         let mut any_bids_made = false;
 
-        // Get seller_control_factor from seller using get_control_variable
-        let seller_control_factor = seller.get_control_variable(seller_converge);
-
-        for campaign in &campaigns.campaigns {
-            let campaign_id = campaign.campaign_id();
-            let campaign_converge = &campaign_converges[campaign_id];
-            // Resolve value_to_campaign at call site using campaign's group ID
-            let group_id = campaigns.campaign_to_value_group_mapping[campaign_id];
-            let value_to_campaign = self.value_to_campaign_group[group_id];
-            // Use the trait method for get_bid
-            if let Some(bid) = campaign.get_bid(self, &campaign_converge, seller_control_factor, value_to_campaign, logger) {
+        for (campaign_id, campaign_bid) in campaign_bids.iter().enumerate() {
+            if let Some(bid) = *campaign_bid {
                 
                 any_bids_made = true;
                 // Check if bid is below zero - skip negative bids
                 if bid < 0.0 {
                     errln!(logger, LogEvent::Simulation, "Bid below zero: {:.4} from campaign_id: {}, skipping", bid, campaign_id);
                     continue;
-                }                
+                }
+                if let Some(bids) = bids.as_deref_mut() {
+                    bids.push((campaign_id, bid));
+                }
                 // If bid is above minimum_cpm_to_win, add to winners list
                 if bid >= minimum_cpm_to_win {
-                    let virtual_cost = bid / 1000.0;
-                    let buyer_charge = bid / 1000.0;
-                    let supply_cost = seller.get_supply_cost_cpm(bid) / 1000.0;
+                    // Bids below the soft floor, and all bids on second price sellers, pay the minimum winning price
+                    // (there is no single runner-up among fractional winners)
+                    let (charge_cpm, virtual_cost_cpm) = match self.soft_floor_cpm {
+                        _ if seller.is_second_price() => (minimum_cpm_to_win, bid),
+                        Some(soft_floor_cpm) if bid < soft_floor_cpm => (minimum_cpm_to_win, minimum_cpm_to_win),
+                        _ => (bid, bid),
+                    };
+                    let virtual_cost = virtual_cost_cpm / 1000.0;
+                    let buyer_charge = charge_cpm / 1000.0;
+                    let supply_cost = seller.get_supply_cost_cpm(charge_cpm, seller_control_variable, seller_volume) / 1000.0;
                     fractional_winners.push(FractionalWinner {
                         campaign_id,
                         virtual_cost,
@@ -302,7 +811,7 @@ impl Impression {
                     });
                 }
             }
-            // If get_bid returns None, skip this campaign (warning already logged)
+            // Campaigns without a bid are skipped
         }
 
         // Calculate win_fraction using softmax based on bid_cpm with temperature
@@ -332,7 +841,7 @@ impl Impression {
         let (winner, supply_cost) = if fractional_winners.is_empty() {
             // Distinguish between no bids (NO_DEMAND) and bids below threshold (LOST)
             // Even when impression is not sold, calculate supply cost (0.0 for first price, fixed_cost_cpm for fixed price)
-            let supply_cost = seller.get_supply_cost_cpm(0.0) / 1000.0;
+            let supply_cost = seller.get_unsold_supply_cost_cpm() / 1000.0;
             let winner = if any_bids_made {
                 FractionalWinners::LOST
             } else {
@@ -358,12 +867,21 @@ impl Impression {
 /// Container for impressions with methods to create impressions
 pub struct Impressions {
     pub impressions: Vec<Impression>,
+    /// Number of simulated days the impressions are spread over
+    pub num_days: usize,
+    /// Number of audience segments the impressions are labeled with (0 when segments are not modeled)
+    pub num_segments: usize,
 }
 
 impl Impressions {
     /// Create a new Impressions container and populate it from sellers
+    /// Impressions of sellers with overrides in params.seller_params are sampled from the overriding distributions
     /// Note: campaign groups must be finalized before calling this function
     pub fn new(sellers: &Sellers, params: &ImpressionsParam, campaigns: &Campaigns) -> Self {
+        if let Some(seller_id) = params.seller_params.keys().find(|&&seller_id| seller_id >= sellers.sellers.len()) {
+            panic!("Impressions parameters override seller {}, but there are only {} sellers", seller_id, sellers.sellers.len());
+        }
+        
         // Calculate total number of impressions ahead of time
         let total_impressions: usize = sellers.sellers.iter()
             .map(|seller| seller.get_impressions_on_offer())
@@ -376,6 +894,10 @@ impl Impressions {
         if num_campaign_groups == 0 {
             panic!("Campaigns have to be finalized before calling impressions::new()");
         }
+        if let Some(correlated_value_multipliers) = &params.correlated_value_multipliers {
+            assert_eq!(correlated_value_multipliers.dimension(), num_campaign_groups,
+                "Correlation matrix of the value multipliers needs one row per value group");
+        }
         
         // Pre-allocate impressions vector with calculated capacity
         let mut impressions = Vec::with_capacity(total_impressions);
@@ -385,41 +907,371 @@ impl Impressions {
         let mut rng_competition = StdRng::seed_from_u64(get_seed(2992));
         let mut rng_floor = StdRng::seed_from_u64(get_seed(3993));
         let mut rng_campaigns_multiplier = StdRng::seed_from_u64(get_seed(4994));
+        let mut rng_viewability = StdRng::seed_from_u64(get_seed(5995));
+        let mut rng_viewability_measurement = StdRng::seed_from_u64(get_seed(22222));
+        let mut rng_timestamp = StdRng::seed_from_u64(get_seed(8998));
+        let mut rng_quality_score = StdRng::seed_from_u64(get_seed(9999));
+        let mut rng_day = StdRng::seed_from_u64(get_seed(10101));
+        let mut rng_conversion_rate = StdRng::seed_from_u64(get_seed(11111));
+        let mut rng_converted = StdRng::seed_from_u64(get_seed(12121));
+        let mut rng_conversion_value = StdRng::seed_from_u64(get_seed(21212));
+        let mut rng_user = StdRng::seed_from_u64(get_seed(13131));
+        let user_generator = params.num_users.map(|num_users| UserGenerator::new(num_users, &params.user_activity));
+        let mut rng_supply_path = StdRng::seed_from_u64(get_seed(14141));
+        let mut rng_segment = StdRng::seed_from_u64(get_seed(17171));
+        let segment_dist = params.segment_weights.as_ref().map(|segment_weights| WeightedIndex::new(segment_weights).expect("Segment weights must be positive"));
+        let mut rng_device_type = StdRng::seed_from_u64(get_seed(18181));
+        let device_type_dist = attribute_dist("device type", &params.device_types);
+        let mut rng_geo_tier = StdRng::seed_from_u64(get_seed(19191));
+        let geo_tier_dist = attribute_dist("geo tier", &params.geo_tiers);
+        let mut rng_oversampling_pilot = StdRng::seed_from_u64(get_seed(20202));
+        let day_dist = WeightedIndex::new(&params.day_weights).expect("Day weights must be positive");
+        // Index of the first impression of each seller
+        let mut seller_offsets = Vec::with_capacity(sellers.sellers.len());
         for seller in &sellers.sellers {
+            seller_offsets.push(impressions.len());
+
+            // Supply paths re-offer the source seller's impressions, with their own competition and floors
+            if let Some(source_seller_id) = sellers.supply_path_source(seller.seller_id()) {
+                for source_index in seller_offsets[source_seller_id]..seller_offsets[source_seller_id] + seller.get_impressions_on_offer() {
+                    let source_impression: &mut Impression = &mut impressions[source_index];
+                    source_impression.supply_id = Some(source_index);
+                    let (competition, floor_cpm) = seller.generate_impression(
+                        source_impression.base_impression_value,
+                        source_impression.time_of_day(),
+                        &mut rng_competition,
+                        &mut rng_floor,
+                    );
+                    // The path's competition is at the competition level of the impression's attributes as well
+                    let (_, attribute_competition_multiplier) = params.attribute_multipliers(source_impression.device_type, source_impression.geo_tier);
+                    let competition = if attribute_competition_multiplier != 1.0 {
+                        competition.map(|competition| competition.scaled(attribute_competition_multiplier))
+                    } else {
+                        competition
+                    };
+                    let impression = Impression {
+                        seller_id: seller.seller_id(),
+                        competition,
+                        floor_cpm,
+                        soft_floor_cpm: seller.soft_floor_cpm(),
+                        ..source_impression.clone()
+                    };
+                    impressions.push(impression);
+                }
+                continue;
+            }
+
+            // Distributions of the seller, its overrides falling back to the global ones
+            let seller_params = params.seller_params.get(&seller.seller_id());
+            let base_impression_value_dist = seller_params.and_then(|seller_params| seller_params.base_impression_value_dist.as_deref()).unwrap_or(params.base_impression_value_dist.as_ref());
+            let viewability_generator = seller_params.and_then(|seller_params| seller_params.viewability_generator.as_deref()).or(params.viewability_generator.as_deref());
+            let value_oversampler = params.value_oversampling.as_ref()
+                .map(|value_oversampling| ValueOversampler::new(base_impression_value_dist, value_oversampling, &mut rng_oversampling_pilot));
+            
             for _ in 0..seller.get_impressions_on_offer() {
                 // First calculate base impression value (needed for floor generation)
-                let base_impression_value = params.base_impression_value_dist.sample(&mut rng_base_value);
+                let (base_impression_value, weight) = match &value_oversampler {
+                    Some(value_oversampler) => value_oversampler.sample(base_impression_value_dist, &mut rng_base_value),
+                    None => (base_impression_value_dist.sample(&mut rng_base_value), 1.0),
+                };
                // println!("base_impression_value: {:.4}", base_impression_value);
+
+                // Time is drawn before competition, which may vary over the day
+                let time_of_day = match &params.time_of_day_dist {
+                    Some(dist) => dist.sample(&mut rng_timestamp).rem_euclid(HOURS_PER_DAY),
+                    None => rng_timestamp.gen_range(0.0..HOURS_PER_DAY),
+                };
+                let day = day_dist.sample(&mut rng_day);
+                let timestamp = day as f64 * HOURS_PER_DAY + time_of_day;
+
                 let (competition, floor_cpm) = seller.generate_impression(
                     base_impression_value,
+                    time_of_day,
                     &mut rng_competition,
                     &mut rng_floor,
                 );
 
+                // Attributes multiply the values to campaigns and the competition level
+                let device_type = device_type_dist.as_ref().map(|dist| dist.sample(&mut rng_device_type));
+                let geo_tier = geo_tier_dist.as_ref().map(|dist| dist.sample(&mut rng_geo_tier));
+                let (attribute_value_multiplier, attribute_competition_multiplier) = params.attribute_multipliers(device_type, geo_tier);
+                let competition = if attribute_competition_multiplier != 1.0 {
+                    competition.map(|competition| competition.scaled(attribute_competition_multiplier))
+                } else {
+                    competition
+                };
+
                 // Generate values for each campaign group by multiplying base value with campaign-specific multiplier
                 let mut value_to_campaign_group = Vec::with_capacity(num_campaign_groups);
 
-                for _ in 0..num_campaign_groups {
-                    let multiplier = params.value_to_campaign_multiplier_dist.sample(&mut rng_campaigns_multiplier);
-//                    println!("multiplier: {:.4}", multiplier);
-                    // println!("base_impression_value: {:.4}", base_impression_value)
-                    //let multiplier = 1.0;
-                    let value = base_impression_value * multiplier;
-                    value_to_campaign_group.push(value);
+                if let Some(correlated_value_multipliers) = &params.correlated_value_multipliers {
+                    value_to_campaign_group.resize(num_campaign_groups, 0.0);
+                    correlated_value_multipliers.sample_into(&mut rng_campaigns_multiplier, &mut value_to_campaign_group);
+                    for value in value_to_campaign_group.iter_mut() {
+                        *value *= base_impression_value * attribute_value_multiplier;
+                    }
+                } else {
+                    for _ in 0..num_campaign_groups {
+                        let multiplier = params.value_to_campaign_multiplier_dist.sample(&mut rng_campaigns_multiplier);
+//                        println!("multiplier: {:.4}", multiplier);
+                        // println!("base_impression_value: {:.4}", base_impression_value)
+                        //let multiplier = 1.0;
+                        let value = base_impression_value * multiplier * attribute_value_multiplier;
+                        value_to_campaign_group.push(value);
+                    }
                 }
+
+                let (viewability, measured_viewability) = match viewability_generator {
+                    Some(viewability_generator) => {
+                        let viewability = viewability_generator.generate_viewability(&mut rng_viewability);
+                        (viewability, viewability_generator.measure_viewability(viewability, &mut rng_viewability_measurement))
+                    }
+                    None => (1.0, 1.0),
+                };
+
+                let quality_score = match &params.quality_score_dist {
+                    Some(dist) => dist.sample(&mut rng_quality_score).clamp(0.0, 1.0),
+                    None => 1.0,
+                };
+
+                // Conversion rate is scaled by the value correlation once all impressions are generated
+                let conversion_rate = match &params.conversion_rate_dist {
+                    Some(dist) => dist.sample(&mut rng_conversion_rate),
+                    None => 0.0,
+                };
+
+                let user_id = user_generator.as_ref().map(|user_generator| user_generator.generate_user(&mut rng_user));
+                let segment = segment_dist.as_ref().map(|segment_dist| segment_dist.sample(&mut rng_segment));
+
                 impressions.push(Impression {
                     seller_id: seller.seller_id(),
                     competition,
                     floor_cpm,
+                    soft_floor_cpm: seller.soft_floor_cpm(),
                     value_to_campaign_group,
                     base_impression_value,
+                    viewability,
+                    measured_viewability,
+                    timestamp,
+                    quality_score,
+                    conversion_probability: conversion_rate,
+                    converted: false,
+                    conversion_value: 0.0,
+                    user_id,
+                    segment,
+                    device_type,
+                    geo_tier,
+                    weight,
+                    supply_id: None,
                 });
             }
         }
 
+        // Correlate conversion probability with value relative to the mean, then sample the conversions
+        if params.conversion_rate_dist.is_some() {
+            // Supply paths re-offer their source impression, so only source impressions are sampled
+            let is_supply_path = |index: usize, impression: &Impression| impression.supply_id.is_some_and(|supply_id| supply_id != index);
+            let source_values: Vec<f64> = impressions.iter().enumerate()
+                .filter(|(index, impression)| !is_supply_path(*index, impression))
+                .map(|(_, impression)| impression.base_impression_value)
+                .collect();
+            let mean_base_impression_value = source_values.iter().sum::<f64>() / source_values.len() as f64;
+            for index in 0..impressions.len() {
+                if let Some(supply_id) = impressions[index].supply_id.filter(|&supply_id| supply_id != index) {
+                    impressions[index].conversion_probability = impressions[supply_id].conversion_probability;
+                    impressions[index].converted = impressions[supply_id].converted;
+                    impressions[index].conversion_value = impressions[supply_id].conversion_value;
+                    continue;
+                }
+                let impression = &mut impressions[index];
+                let value_factor = (impression.base_impression_value / mean_base_impression_value).powf(params.conversion_value_elasticity);
+                impression.conversion_probability = (impression.conversion_probability * value_factor).clamp(0.0, 1.0);
+                impression.converted = rng_converted.gen_bool(impression.conversion_probability);
+                impression.conversion_value = params.conversion_value_dist.as_ref()
+                    .map_or(1.0, |conversion_value_dist| conversion_value_dist.sample(&mut rng_conversion_value));
+            }
+        }
+
+        // Auctions run in order of the impressions, so they are ordered by time
+        impressions.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+
+        // Paths of an impression share its timestamp, so they are adjacent; they are auctioned in random order
+        for supply_paths in impressions.chunk_by_mut(|a, b| a.supply_id.is_some() && a.supply_id == b.supply_id) {
+            supply_paths.shuffle(&mut rng_supply_path);
+        }
+
         Self { 
             impressions,
+            num_days: params.day_weights.len(),
+            num_segments: params.segment_weights.as_ref().map_or(0, |segment_weights| segment_weights.len()),
+        }
+    }
+
+    /// Impressions offered on one day (see Impression::day), e.g. as one period of a multi-period run
+    /// The impressions keep their timestamps, so the number of days stays the same
+    pub fn day_impressions(&self, day: usize) -> Self {
+        assert!(day < self.num_days, "Impressions are spread over {} days, there is no day {}", self.num_days, day);
+        Self {
+            impressions: self.impressions.iter().filter(|impression| impression.day() == day).cloned().collect(),
+            num_days: self.num_days,
+            num_segments: self.num_segments,
+        }
+    }
+
+    /// Save the impressions to a binary file (see write_to), to be loaded with load
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let file = std::fs::File::create(path).map_err(|error| format!("Can't create impressions file '{}': {}", path, error))?;
+        let mut writer = std::io::BufWriter::new(file);
+        self.write_to(&mut writer).and_then(|_| writer.flush()).map_err(|error| format!("Can't write impressions to '{}': {}", path, error))?;
+        Ok(())
+    }
+
+    /// Load impressions saved with save
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let file = std::fs::File::open(path).map_err(|error| format!("Can't read impressions from '{}': {}", path, error))?;
+        Self::read_from(&mut std::io::BufReader::new(file)).map_err(|error| format!("Invalid impressions file '{}': {}", path, error).into())
+    }
+
+    /// Write the impressions in a compact binary format, all numbers little-endian:
+    /// a header (IMPRESSIONS_FILE_MAGIC, IMPRESSIONS_FILE_VERSION as u32, num_days, num_segments, the number of
+    /// impressions and of value groups as u64), then every impression as its seller_id (u64), a byte flagging
+    /// its optional fields, floor_cpm, the value of every value group, base_impression_value, viewability,
+    /// measured_viewability, timestamp, quality_score, conversion_probability, conversion_value and weight (f64), followed by the optional fields present:
+    /// competition (5 × f64), soft_floor_cpm (f64), user_id, segment, device_type, geo_tier and supply_id (u64)
+    pub fn write_to(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        let num_value_groups = self.impressions.first().map_or(0, |impression| impression.value_to_campaign_group.len());
+        writer.write_all(IMPRESSIONS_FILE_MAGIC)?;
+        writer.write_all(&IMPRESSIONS_FILE_VERSION.to_le_bytes())?;
+        for count in [self.num_days, self.num_segments, self.impressions.len(), num_value_groups] {
+            writer.write_all(&(count as u64).to_le_bytes())?;
+        }
+        for impression in &self.impressions {
+            assert_eq!(impression.value_to_campaign_group.len(), num_value_groups, "All impressions need a value for every value group");
+            let optional_ids = [impression.user_id, impression.segment, impression.device_type, impression.geo_tier, impression.supply_id];
+            let mut flags = 0u8;
+            for (bit, present) in [impression.competition.is_some(), impression.soft_floor_cpm.is_some(), impression.converted].into_iter()
+                .chain(optional_ids.iter().map(|id| id.is_some()))
+                .enumerate() {
+                flags |= (present as u8) << bit;
+            }
+            writer.write_all(&(impression.seller_id as u64).to_le_bytes())?;
+            writer.write_all(&[flags])?;
+            let values = [impression.floor_cpm].into_iter()
+                .chain(impression.value_to_campaign_group.iter().copied())
+                .chain([impression.base_impression_value, impression.viewability, impression.measured_viewability, impression.timestamp, impression.quality_score, impression.conversion_probability, impression.conversion_value, impression.weight]);
+            for value in values {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+            if let Some(competition) = &impression.competition {
+                for value in [competition.bid_cpm, competition.win_rate_actual_sigmoid_offset, competition.win_rate_actual_sigmoid_scale,
+                    competition.win_rate_prediction_sigmoid_offset, competition.win_rate_prediction_sigmoid_scale] {
+                    writer.write_all(&value.to_le_bytes())?;
+                }
+            }
+            if let Some(soft_floor_cpm) = impression.soft_floor_cpm {
+                writer.write_all(&soft_floor_cpm.to_le_bytes())?;
+            }
+            for id in optional_ids.into_iter().flatten() {
+                writer.write_all(&(id as u64).to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read impressions written with write_to
+    pub fn read_from(reader: &mut impl Read) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut magic = [0u8; IMPRESSIONS_FILE_MAGIC.len()];
+        reader.read_exact(&mut magic).map_err(|_| "Not an impressions file")?;
+        if magic != *IMPRESSIONS_FILE_MAGIC {
+            return Err("Not an impressions file".into());
         }
+        let mut version = [0u8; 4];
+        reader.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != IMPRESSIONS_FILE_VERSION {
+            return Err(format!("Unsupported version {} (expected {})", version, IMPRESSIONS_FILE_VERSION).into());
+        }
+        let read_u64 = |reader: &mut dyn Read| -> std::io::Result<u64> {
+            let mut bytes = [0u8; 8];
+            reader.read_exact(&mut bytes)?;
+            Ok(u64::from_le_bytes(bytes))
+        };
+        let num_days = read_u64(reader)? as usize;
+        let num_segments = read_u64(reader)? as usize;
+        let num_impressions = read_u64(reader)? as usize;
+        let num_value_groups = read_u64(reader)? as usize;
+        let read_f64 = |reader: &mut dyn Read| -> std::io::Result<f64> {
+            let mut bytes = [0u8; 8];
+            reader.read_exact(&mut bytes)?;
+            Ok(f64::from_le_bytes(bytes))
+        };
+
+        // The count is only trusted as far as impressions can be read
+        let mut impressions = Vec::with_capacity(num_impressions.min(1 << 20));
+        for index in 0..num_impressions {
+            let truncated = |error: std::io::Error| format!("Impression {}: {}", index, error);
+            let seller_id = read_u64(reader).map_err(truncated)? as usize;
+            let mut flags = [0u8; 1];
+            reader.read_exact(&mut flags).map_err(truncated)?;
+            let flag = |bit: usize| flags[0] & (1 << bit) != 0;
+            let floor_cpm = read_f64(reader).map_err(truncated)?;
+            let value_to_campaign_group = (0..num_value_groups).map(|_| read_f64(reader)).collect::<std::io::Result<Vec<f64>>>().map_err(truncated)?;
+            let mut values = [0.0; 8];
+            for value in values.iter_mut() {
+                *value = read_f64(reader).map_err(truncated)?;
+            }
+            let [base_impression_value, viewability, measured_viewability, timestamp, quality_score, conversion_probability, conversion_value, weight] = values;
+            let competition = if flag(0) {
+                let mut parameters = [0.0; 5];
+                for parameter in parameters.iter_mut() {
+                    *parameter = read_f64(reader).map_err(truncated)?;
+                }
+                let [bid_cpm, win_rate_actual_sigmoid_offset, win_rate_actual_sigmoid_scale, win_rate_prediction_sigmoid_offset, win_rate_prediction_sigmoid_scale] = parameters;
+                Some(ImpressionCompetition { bid_cpm, win_rate_actual_sigmoid_offset, win_rate_actual_sigmoid_scale, win_rate_prediction_sigmoid_offset, win_rate_prediction_sigmoid_scale })
+            } else {
+                None
+            };
+            let soft_floor_cpm = if flag(1) { Some(read_f64(reader).map_err(truncated)?) } else { None };
+            let mut optional_ids = [None; 5];
+            for (bit, id) in optional_ids.iter_mut().enumerate() {
+                if flag(3 + bit) {
+                    *id = Some(read_u64(reader).map_err(truncated)? as usize);
+                }
+            }
+            let [user_id, segment, device_type, geo_tier, supply_id] = optional_ids;
+            if let Some(segment) = segment.filter(|&segment| segment >= num_segments) {
+                return Err(format!("Impression {}: segment {} of only {} segments", index, segment, num_segments).into());
+            }
+            if let Some(supply_id) = supply_id.filter(|&supply_id| supply_id >= num_impressions) {
+                return Err(format!("Impression {}: supply_id {} of only {} impressions", index, supply_id, num_impressions).into());
+            }
+            impressions.push(Impression {
+                seller_id,
+                competition,
+                floor_cpm,
+                soft_floor_cpm,
+                value_to_campaign_group,
+                base_impression_value,
+                viewability,
+                measured_viewability,
+                timestamp,
+                quality_score,
+                conversion_probability,
+                converted: flag(2),
+                conversion_value,
+                user_id,
+                segment,
+                device_type,
+                geo_tier,
+                weight,
+                supply_id,
+            });
+        }
+        if reader.read(&mut [0u8; 1])? != 0 {
+            return Err(format!("Unexpected data after {} impressions", num_impressions).into());
+        }
+
+        Ok(Self { impressions, num_days, num_segments })
     }
 }
 
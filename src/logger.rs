@@ -1,6 +1,8 @@
+use std::cell::RefCell;
 use std::fs::{File, create_dir_all};
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Log event types that determine which receivers should log the message
@@ -10,6 +12,10 @@ pub enum LogEvent {
     Auction,
     /// Simulation iteration data (detailed per-iteration info)
     Simulation,
+    /// Controller trajectory data (CSV row per campaign and seller target in every convergence iteration)
+    Controller,
+    /// Parameter sweep, tuning and sensitivity results (CSV row per evaluated combination of parameters)
+    Sweep,
     /// Convergence information (iteration counts, convergence messages)
     Convergence,
     /// Variant-level data (final converged simulation results for a variant)
@@ -95,6 +101,37 @@ impl LogReceiver for FileReceiver {
     }
 }
 
+/// Buffer log receiver (collects messages in memory)
+/// Used to replay the log of a scenario run in parallel once its turn comes
+pub struct BufferReceiver {
+    buffer: Rc<RefCell<String>>,
+    enabled_events: Vec<LogEvent>,
+}
+
+impl BufferReceiver {
+    /// Create a new buffer receiver that appends to the given buffer
+    /// Returns a boxed receiver ready to be added to a logger
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(buffer: Rc<RefCell<String>>, enabled_events: Vec<LogEvent>) -> Box<dyn LogReceiver> {
+        Box::new(Self { buffer, enabled_events })
+    }
+}
+
+impl LogReceiver for BufferReceiver {
+    fn should_log(&self, event: LogEvent) -> bool {
+        self.enabled_events.contains(&event)
+    }
+    
+    fn write(&mut self, s: &str) -> io::Result<()> {
+        self.buffer.borrow_mut().push_str(s);
+        Ok(())
+    }
+    
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Unique identifier for a receiver
 pub type ReceiverId = usize;
 
@@ -104,16 +141,30 @@ static RECEIVER_ID_COUNTER: AtomicUsize = AtomicUsize::new(1);
 /// Main logger that manages multiple receivers
 pub struct Logger {
     receivers: Vec<(ReceiverId, Box<dyn LogReceiver>)>,
+    /// Directory that log files of scenarios are written to
+    log_dir: PathBuf,
 }
 
 impl Logger {
-    /// Create a new logger with no receivers
+    /// Create a new logger with no receivers, writing log files to `log/`
     pub fn new() -> Self {
         Self {
             receivers: Vec::new(),
+            log_dir: PathBuf::from("log"),
         }
     }
     
+    /// Write log files to the given directory instead of `log/`
+    pub fn with_log_dir(mut self, log_dir: PathBuf) -> Self {
+        self.log_dir = log_dir;
+        self
+    }
+    
+    /// Path of a log file relative to the log directory
+    pub fn log_path(&self, relative_path: &str) -> PathBuf {
+        self.log_dir.join(relative_path)
+    }
+    
     /// Add a receiver to the logger and return its unique ID
     pub fn add_receiver(&mut self, receiver: Box<dyn LogReceiver>) -> ReceiverId {
         let id = RECEIVER_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
@@ -162,6 +213,18 @@ impl Logger {
                 LogEvent::Scenario,
                 LogEvent::Validation,
             ],
+            LogEvent::Controller => vec![
+                LogEvent::Controller,
+                LogEvent::Convergence,
+                LogEvent::Variant,
+                LogEvent::Scenario,
+                LogEvent::Validation,
+            ],
+            LogEvent::Sweep => vec![
+                LogEvent::Sweep,
+                LogEvent::Scenario,
+                LogEvent::Validation,
+            ],
             LogEvent::Convergence => vec![
                 LogEvent::Convergence,
                 LogEvent::Variant,
@@ -2,9 +2,30 @@
 #[allow(unused_imports)]
 mod simulationrun;
 mod converge;
+mod oscillation;
+mod checkpoint;
+mod timeouts;
+mod convergence_criteria;
+mod seed_aggregation;
+mod sweep;
+mod tuning;
+mod experiments;
+mod sensitivity;
+mod trajectory;
+mod counterfactual;
+mod variants;
+#[cfg(feature = "config")]
+mod scenario_config;
 mod utils;
 mod impressions;
 mod campaign;
+mod campaign_portfolio;
+mod campaign_learned_competition;
+mod campaign_time_paced;
+mod campaign_daily_budget;
+mod campaign_clearing_price;
+mod campaign_coupled_double_target;
+mod campaign_lagrangian;
 mod campaigns;
 mod campaign_targets;
 mod bid_valuers_single;
@@ -13,16 +34,20 @@ mod seller;
 mod sellers;
 mod seller_targets;
 mod seller_chargers;
+mod seller_builder;
 mod scenarios;
 mod logger;
 mod charts;
 mod floors;
+mod viewability;
 mod competition;
 mod sigmoid;
 mod controller_state;
 mod controller_core;
 mod controllers;
+mod controller_bench;
 mod bid_optimizers;
+mod hooks;
 
 
 use sellers::{SellerType, SellerConvergeStrategy, Sellers};
@@ -30,20 +55,28 @@ use campaigns::{CampaignType, ConvergeTarget, Campaigns};
 use converge::SimulationConverge;
 use competition::{CompetitionGeneratorLogNormal, CompetitionGeneratorNone};
 use floors::{FloorGeneratorFixed, FloorGeneratorLogNormal};
-use logger::{Logger, LogEvent, ConsoleReceiver, FileReceiver, sanitize_filename};
+use logger::{Logger, LogEvent, ConsoleReceiver, FileReceiver, BufferReceiver, sanitize_filename};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::mpsc;
 
-use scenarios::get_scenario_catalog;
+use scenarios::{get_scenario_catalog, ScenarioEntry};
 use utils::{RAND_SEED, TOTAL_SIMULATION_RUNS};
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 fn main() {
     let raw_args: Vec<String> = std::env::args().collect();
     
-    // Parse and filter out --verbose and --fastbreak arguments
+    // Parse and filter out --verbose, --fastbreak, --jobs, --checkpoint, --variant-timeout, --scenario-timeout, --tag and
+    // --exclude arguments
     let mut args = Vec::new();
     let mut skip_next = false;
     let mut fastbreak = false;
+    let mut jobs = 1;
+    let mut tags: Vec<String> = Vec::new();
+    let mut excluded_tags: Vec<String> = Vec::new();
     for (i, arg) in raw_args.iter().enumerate() {
         if skip_next {
             skip_next = false;
@@ -60,6 +93,56 @@ fn main() {
             fastbreak = true;
             continue;
         }
+        if arg == "--jobs" {
+            jobs = match raw_args.get(i + 1).map(|n| n.parse::<usize>()) {
+                Some(Ok(n)) if n > 0 => n,
+                _ => {
+                    eprintln!("Error: --jobs expects a positive number of worker threads.");
+                    std::process::exit(1);
+                }
+            };
+            skip_next = true;
+            continue;
+        }
+        if arg == "--checkpoint" {
+            match raw_args.get(i + 1).map(|n| n.parse::<usize>()) {
+                Some(Ok(n)) if n > 0 => utils::CHECKPOINT_INTERVAL.store(n, Ordering::Relaxed),
+                _ => {
+                    eprintln!("Error: --checkpoint expects a positive number of iterations between checkpoints.");
+                    std::process::exit(1);
+                }
+            }
+            skip_next = true;
+            continue;
+        }
+        if arg == "--variant-timeout" || arg == "--scenario-timeout" {
+            let timeout_ms = if arg == "--variant-timeout" { &utils::VARIANT_TIMEOUT_MS } else { &utils::SCENARIO_TIMEOUT_MS };
+            match raw_args.get(i + 1).map(|n| n.parse::<f64>()) {
+                Some(Ok(seconds)) if seconds > 0.0 && seconds.is_finite() => timeout_ms.store(((seconds * 1000.0).ceil() as u64).max(1), Ordering::Relaxed),
+                _ => {
+                    eprintln!("Error: {} expects a positive number of seconds.", arg);
+                    std::process::exit(1);
+                }
+            }
+            skip_next = true;
+            continue;
+        }
+        if arg == "--tag" || arg == "--exclude" {
+            match raw_args.get(i + 1) {
+                Some(tag) if scenarios::is_known_tag(tag) => {
+                    if arg == "--tag" { tags.push(tag.clone()) } else { excluded_tags.push(tag.clone()) }
+                }
+                _ => {
+                    eprintln!("Error: {} expects one of the scenario tags:", arg);
+                    for (tag, description) in scenarios::SCENARIO_TAGS {
+                        eprintln!("  - {}: {}", tag, description);
+                    }
+                    std::process::exit(1);
+                }
+            }
+            skip_next = true;
+            continue;
+        }
         args.push(arg.clone());
     }
     
@@ -91,6 +174,15 @@ fn main() {
         return;
     }
     
+    // Check if "bench" argument is provided
+    if args.len() > 1 && args[1] == "bench" {
+        let mut logger = Logger::new();
+        logger.add_receiver(ConsoleReceiver::new(vec![LogEvent::Scenario]));
+        logger.add_receiver(FileReceiver::new(&PathBuf::from("log/controller_bench/bench.log"), vec![LogEvent::Scenario]));
+        controller_bench::run(&mut logger);
+        return;
+    }
+    
     // Check if "test" argument is provided
     if args.len() > 1 && args[1] == "test" {
         use campaigns::{CampaignGeneral, CampaignTargetNone, CampaignTrait};
@@ -109,6 +201,10 @@ fn main() {
             converge_controllers: vec![Box::new(crate::controllers::ControllerConstant::new(0.8298))],
             bid_valuer,
             bid_optimizer,
+            max_bid_cpm: None,
+            win_rate_miscalibration: None,
+            censored_competition: false,
+            target_priorities: None,
         };
         
         let converge_vars = campaign_max_margin.create_controller_state();
@@ -157,8 +253,22 @@ fn main() {
                     win_rate_prediction_sigmoid_scale: test_case.sigmoid_scale,
                 }),
                 floor_cpm: test_case.floor_cpm,
+                soft_floor_cpm: None,
                 value_to_campaign_group,
                 base_impression_value: test_case.value,
+                viewability: 1.0,
+                measured_viewability: 1.0,
+                timestamp: 0.0,
+                quality_score: 1.0,
+                conversion_probability: 0.0,
+                conversion_value: 0.0,
+                converted: false,
+                user_id: None,
+                segment: None,
+                device_type: None,
+                geo_tier: None,
+                weight: 1.0,
+                supply_id: None,
             };
             
             println!("{}: {:#?}", test_case.name, impression);
@@ -171,6 +281,16 @@ fn main() {
         
         return;
     }
+    // Check if "run-config" argument is provided
+    if args.len() > 1 && args[1] == "run-config" {
+        let Some(config_path) = args.get(2) else {
+            eprintln!("Error: run-config expects the path of a scenario config file.");
+            std::process::exit(1);
+        };
+        run_config(config_path);
+        return;
+    }
+    
     if args.len() > 1 {
         let scenario_arg = &args[1];
         
@@ -203,9 +323,17 @@ fn main() {
         // Get all scenarios from the catalog
         let all_scenarios = get_scenario_catalog();
         
-        // Filter scenarios: if "all", use all scenarios; otherwise filter to the named scenario
+        // Filter scenarios: if "all", use all scenarios with the selected tags; otherwise filter to the named scenario
         let scenarios: Vec<_> = if scenario_arg == "all" {
-            all_scenarios.clone()
+            let selected = scenarios::filter_by_tags(all_scenarios.clone(), &tags, &excluded_tags);
+            if selected.is_empty() {
+                eprintln!("Error: No scenarios have any of the tags [{}] without the excluded tags [{}].", tags.join(", "), excluded_tags.join(", "));
+                std::process::exit(1);
+            }
+            selected
+        } else if !tags.is_empty() || !excluded_tags.is_empty() {
+            eprintln!("Error: --tag and --exclude select scenarios of \"all\", not of a named scenario.");
+            std::process::exit(1);
         } else {
             // Find the requested scenario
             let found = all_scenarios.iter().find(|s| s.short_name == scenario_arg);
@@ -215,7 +343,7 @@ fn main() {
                     eprintln!("Error: Scenario '{}' not found.", scenario_arg);
                     eprintln!("Available scenarios:");
                     for s in &all_scenarios {
-                        eprintln!("  - {}", s.short_name);
+                        eprintln!("  - {} [{}]", s.short_name, s.tags.join(", "));
                     }
                     std::process::exit(1);
                 }
@@ -246,10 +374,17 @@ fn main() {
         
         // Log appropriate message
         if scenario_arg == "all" {
+            let mut scenarios_description = if tags.is_empty() { "all scenarios".to_string() } else { format!("scenarios tagged {}", tags.join(" or ")) };
+            if !excluded_tags.is_empty() {
+                scenarios_description.push_str(&format!(" except {}", excluded_tags.join(" or ")));
+            }
+            if !tags.is_empty() || !excluded_tags.is_empty() {
+                scenarios_description.push_str(&format!(" ({})", scenarios.len()));
+            }
             if iterations > 1 {
-                logln!(&mut logger, LogEvent::Validation, "Running all scenarios {} times... (Total simulation runs: {})\n", iterations, initial_count);
+                logln!(&mut logger, LogEvent::Validation, "Running {} {} times... (Total simulation runs: {})\n", scenarios_description, iterations, initial_count);
             } else {
-                logln!(&mut logger, LogEvent::Validation, "Running all scenarios... (Total simulation runs: {})\n", initial_count);
+                logln!(&mut logger, LogEvent::Validation, "Running {}... (Total simulation runs: {})\n", scenarios_description, initial_count);
             }
         } else {
             if iterations > 1 {
@@ -259,61 +394,74 @@ fn main() {
             }
         }
         
-        // Outer loop for scenarios
-        'scenarios: for scenario in &scenarios {
-            log!(&mut logger, LogEvent::Validation, "{}: ", scenario.short_name);
-            
-            // Add scenario-level receiver
-            let scenario_receiver_id = logger.add_receiver(FileReceiver::new(&PathBuf::from(format!("log/{}/scenario.log", sanitize_filename(scenario.short_name))), vec![LogEvent::Scenario]));
-            
-            // Inner loop for iterations
-            for i in start_iteration..(start_iteration + iterations) {
-                if iterations > 1 {
-                    let iteration_num = i - start_iteration + 1;
-                    log!(&mut logger, LogEvent::Validation, "[{}/{}] ", iteration_num, iterations);
-                }
+        // Run scenarios and iterations on a pool of worker threads, or one after another
+        if jobs > 1 && scenarios.len() as u64 * iterations > 1 {
+            run_scenarios_parallel(&scenarios, start_iteration, iterations, jobs, fastbreak, &mut logger);
+        } else {
+            // Outer loop for scenarios
+            'scenarios: for scenario in &scenarios {
+                log!(&mut logger, LogEvent::Validation, "{}: ", scenario.short_name);
                 
-                // Set RAND_SEED to iteration number
-                RAND_SEED.store(i, Ordering::Relaxed);
+                // Add scenario-level receiver
+                let scenario_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/scenario.log", sanitize_filename(scenario.short_name))), vec![LogEvent::Scenario]));
                 
-                match (scenario.run)(scenario.short_name, &mut logger) {
-                    Ok(()) => {
-                        if iterations > 1 {
-                            logln!(&mut logger, LogEvent::Validation, "✓");
-                        } else {
-                            logln!(&mut logger, LogEvent::Validation, "✓ PASSED");
-                        }
-                    },
-                    Err(e) => {
-                        if iterations > 1 {
-                            logln!(&mut logger, LogEvent::Validation, "✗");
-                        } else {
-                            logln!(&mut logger, LogEvent::Validation, "✗ FAILED: {}", e);
-                        }
-                        
-                        // If fastbreak is enabled, stop immediately on first failure
-                        if fastbreak {
-                            // Remove scenario-level receiver before breaking
-                            logger.remove_receiver(scenario_receiver_id);
-                            logln!(&mut logger, LogEvent::Validation, "\nStopping scenario execution due to failure (--fastbreak enabled)");
-                            // Always log the full error message when fastbreak stops execution
+                // Inner loop for iterations
+                for i in start_iteration..(start_iteration + iterations) {
+                    if iterations > 1 {
+                        let iteration_num = i - start_iteration + 1;
+                        log!(&mut logger, LogEvent::Validation, "[{}/{}] ", iteration_num, iterations);
+                    }
+                    
+                    // Set RAND_SEED to iteration number
+                    RAND_SEED.set(i);
+                    
+                    // Time the scenario against --scenario-timeout
+                    let previous_clock = timeouts::start_scenario_clock(scenario.short_name, timeouts::scenario_timeout());
+                    let result = (scenario.run)(scenario.short_name, &mut logger);
+                    timeouts::restore_scenario_clock(previous_clock);
+                    
+                    match result {
+                        Ok(()) => {
+                            if iterations > 1 {
+                                logln!(&mut logger, LogEvent::Validation, "✓");
+                            } else {
+                                logln!(&mut logger, LogEvent::Validation, "✓ PASSED");
+                            }
+                        },
+                        Err(e) => {
+                            let timed_out = timeouts::is_timeout(e.as_ref());
                             if iterations > 1 {
-                                let iteration_num = i - start_iteration + 1;
-                                logln!(&mut logger, LogEvent::Validation, "Error at iteration {}/{} (seed {}): {}", iteration_num, iterations, i, e);
+                                logln!(&mut logger, LogEvent::Validation, "{}", if timed_out { "✗ TIMEOUT" } else { "✗" });
+                            } else if timed_out {
+                                logln!(&mut logger, LogEvent::Validation, "✗ TIMEOUT: {}", e);
                             } else {
-                                logln!(&mut logger, LogEvent::Validation, "Error: {}", e);
+                                logln!(&mut logger, LogEvent::Validation, "✗ FAILED: {}", e);
+                            }
+                            
+                            // If fastbreak is enabled, stop immediately on first failure
+                            if fastbreak {
+                                // Remove scenario-level receiver before breaking
+                                logger.remove_receiver(scenario_receiver_id);
+                                logln!(&mut logger, LogEvent::Validation, "\nStopping scenario execution due to failure (--fastbreak enabled)");
+                                // Always log the full error message when fastbreak stops execution
+                                if iterations > 1 {
+                                    let iteration_num = i - start_iteration + 1;
+                                    logln!(&mut logger, LogEvent::Validation, "Error at iteration {}/{} (seed {}): {}", iteration_num, iterations, i, e);
+                                } else {
+                                    logln!(&mut logger, LogEvent::Validation, "Error: {}", e);
+                                }
+                                break 'scenarios;
                             }
-                            break 'scenarios;
                         }
                     }
+                    
+                    // Flush to ensure validation is written to summary.log
+                    let _ = logger.flush();
                 }
                 
-                // Flush to ensure validation is written to summary.log
-                let _ = logger.flush();
+                // Remove scenario-level receiver
+                logger.remove_receiver(scenario_receiver_id);
             }
-            
-            // Remove scenario-level receiver
-            logger.remove_receiver(scenario_receiver_id);
         }
         
         // Log final simulation run count
@@ -334,3 +482,191 @@ fn main() {
     }
     
 }
+
+/// Outcome of running a scenario with one seed on a worker thread
+struct TaskOutcome {
+    /// Error message if the scenario failed
+    error: Option<String>,
+    /// Whether the scenario failed by exceeding a wall-clock limit (see timeouts.rs)
+    timed_out: bool,
+    /// Validation events logged by the scenario, replayed to the main logger in order
+    validation_log: String,
+    /// Scenario events logged by the scenario, collected into the scenario's scenario.log
+    scenario_log: String,
+}
+
+/// Load a scenario config file and run it, logging like a single scenario run
+#[cfg(feature = "config")]
+fn run_config(config_path: &str) {
+    let config = match scenario_config::ScenarioConfig::load(std::path::Path::new(config_path)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    
+    let mut logger = Logger::new();
+    logger.add_receiver(ConsoleReceiver::new(vec![LogEvent::Validation, LogEvent::Scenario]));
+    logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/scenario.log", sanitize_filename(&config.name))), vec![LogEvent::Scenario]));
+    
+    logln!(&mut logger, LogEvent::Validation, "Running config '{}' from {}...\n", config.name, config_path);
+    log!(&mut logger, LogEvent::Validation, "{}: ", config.name);
+    match config.scenario().run(&mut logger) {
+        Ok(()) => logln!(&mut logger, LogEvent::Validation, "✓ PASSED"),
+        Err(e) => {
+            logln!(&mut logger, LogEvent::Validation, "✗ FAILED: {}", e);
+            let _ = logger.flush();
+            std::process::exit(1);
+        }
+    }
+    let _ = logger.flush();
+}
+
+/// Scenario config files need the `config` feature, which adds the TOML and YAML parsers
+#[cfg(not(feature = "config"))]
+fn run_config(_config_path: &str) {
+    eprintln!("Error: run-config needs the config feature, build with `cargo build --release --features config`.");
+    std::process::exit(1);
+}
+
+/// Run a scenario with the given seed on its own logger, capturing what it logs
+/// Only the last iteration of a scenario writes its log files to `log/`, as in a serial run where each
+/// iteration overwrites them; other iterations write to a scratch directory removed when they finish
+fn run_task(scenario: &ScenarioEntry, seed: u64, last_iteration: bool) -> TaskOutcome {
+    // RAND_SEED is thread-local, so this only affects the scenario running on this thread
+    RAND_SEED.set(seed);
+    
+    let scratch_dir = PathBuf::from(format!("log/.jobs/{}-{}", sanitize_filename(scenario.short_name), seed));
+    let mut logger = if last_iteration { Logger::new() } else { Logger::new().with_log_dir(scratch_dir.clone()) };
+    let validation_log = Rc::new(RefCell::new(String::new()));
+    let scenario_log = Rc::new(RefCell::new(String::new()));
+    logger.add_receiver(BufferReceiver::new(Rc::clone(&validation_log), vec![LogEvent::Validation]));
+    logger.add_receiver(BufferReceiver::new(Rc::clone(&scenario_log), vec![LogEvent::Scenario]));
+    
+    // The scenario clock is thread-local too, timing only this scenario against --scenario-timeout
+    let previous_clock = timeouts::start_scenario_clock(scenario.short_name, timeouts::scenario_timeout());
+    let result = (scenario.run)(scenario.short_name, &mut logger);
+    timeouts::restore_scenario_clock(previous_clock);
+    if !last_iteration {
+        let _ = std::fs::remove_dir_all(&scratch_dir);
+    }
+    
+    TaskOutcome {
+        timed_out: result.as_ref().is_err_and(|e| timeouts::is_timeout(e.as_ref())),
+        error: result.err().map(|e| e.to_string()),
+        validation_log: validation_log.take(),
+        scenario_log: scenario_log.take(),
+    }
+}
+
+/// Run scenarios with all iterations as tasks on a pool of worker threads
+/// Outcomes are reported in the same order and format as in a serial run, each as soon as all tasks before
+/// it are done. With fastbreak, workers stop taking tasks after a failure and reporting stops at the first one.
+fn run_scenarios_parallel(scenarios: &[ScenarioEntry], start_iteration: u64, iterations: u64, jobs: usize, fastbreak: bool, logger: &mut Logger) {
+    let tasks: Vec<(&ScenarioEntry, u64)> = scenarios.iter()
+        .flat_map(|scenario| (start_iteration..(start_iteration + iterations)).map(move |i| (scenario, i)))
+        .collect();
+    let next_task = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    
+    std::thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..jobs.min(tasks.len()) {
+            let sender = sender.clone();
+            let (tasks, next_task, stop) = (&tasks, &next_task, &stop);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let task_index = next_task.fetch_add(1, Ordering::Relaxed);
+                    let Some(&(scenario, i)) = tasks.get(task_index) else { break };
+                    let outcome = run_task(scenario, i, i + 1 == start_iteration + iterations);
+                    if fastbreak && outcome.error.is_some() {
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                    if sender.send((task_index, outcome)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+        
+        // Report outcomes in task order, holding back those that finish early
+        let mut finished = HashMap::new();
+        let mut scenario_log = String::new();
+        let mut task_index = 0;
+        while task_index < tasks.len() {
+            let Some(outcome) = finished.remove(&task_index) else {
+                match receiver.recv() {
+                    Ok((index, outcome)) => {
+                        finished.insert(index, outcome);
+                        continue;
+                    }
+                    Err(_) => break,
+                }
+            };
+            let (scenario, i) = tasks[task_index];
+            let iteration_num = i - start_iteration + 1;
+            
+            if iteration_num == 1 {
+                log!(logger, LogEvent::Validation, "{}: ", scenario.short_name);
+                scenario_log.clear();
+            }
+            if iterations > 1 {
+                log!(logger, LogEvent::Validation, "[{}/{}] ", iteration_num, iterations);
+            }
+            let _ = logger.log(LogEvent::Validation, &outcome.validation_log);
+            scenario_log.push_str(&outcome.scenario_log);
+            
+            match &outcome.error {
+                None => {
+                    if iterations > 1 {
+                        logln!(logger, LogEvent::Validation, "✓");
+                    } else {
+                        logln!(logger, LogEvent::Validation, "✓ PASSED");
+                    }
+                },
+                Some(e) => {
+                    if iterations > 1 {
+                        logln!(logger, LogEvent::Validation, "{}", if outcome.timed_out { "✗ TIMEOUT" } else { "✗" });
+                    } else if outcome.timed_out {
+                        logln!(logger, LogEvent::Validation, "✗ TIMEOUT: {}", e);
+                    } else {
+                        logln!(logger, LogEvent::Validation, "✗ FAILED: {}", e);
+                    }
+                    
+                    // If fastbreak is enabled, stop reporting at the first failure
+                    if fastbreak {
+                        write_scenario_log(logger, scenario.short_name, &scenario_log);
+                        logln!(logger, LogEvent::Validation, "\nStopping scenario execution due to failure (--fastbreak enabled)");
+                        // Always log the full error message when fastbreak stops execution
+                        if iterations > 1 {
+                            logln!(logger, LogEvent::Validation, "Error at iteration {}/{} (seed {}): {}", iteration_num, iterations, i, e);
+                        } else {
+                            logln!(logger, LogEvent::Validation, "Error: {}", e);
+                        }
+                        break;
+                    }
+                }
+            }
+            
+            if iteration_num == iterations {
+                write_scenario_log(logger, scenario.short_name, &scenario_log);
+            }
+            
+            // Flush to ensure validation is written to summary.log
+            let _ = logger.flush();
+            task_index += 1;
+        }
+    });
+    
+    // Remove the scratch directory of non-final iterations once it is empty
+    let _ = std::fs::remove_dir("log/.jobs");
+}
+
+/// Write the collected scenario events of a scenario to its scenario.log
+fn write_scenario_log(logger: &mut Logger, scenario_name: &str, scenario_log: &str) {
+    let scenario_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/scenario.log", sanitize_filename(scenario_name))), vec![LogEvent::Scenario]));
+    let _ = logger.log(LogEvent::Scenario, scenario_log);
+    logger.remove_receiver(scenario_receiver_id);
+}
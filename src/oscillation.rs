@@ -0,0 +1,126 @@
+//! Oscillation diagnostics for the convergence loop
+//!
+//! A control variable oscillates when its changes between consecutive convergence iterations
+//! keep alternating in sign (up, down, up, ...), the pattern of a controller that overshoots its
+//! target on every iteration. Sustained oscillation is reported as a Convergence warning and
+//! collected in SimulationStat, so scenarios can assert that their controllers are stable.
+
+use crate::logger::{Logger, LogEvent};
+use crate::warnln;
+
+/// Default number of consecutive sign-alternating changes that count as sustained oscillation
+pub const DEFAULT_OSCILLATION_WINDOW: usize = 6;
+
+/// Sustained oscillation of a single control variable of a campaign or seller
+#[derive(Debug, Clone)]
+pub struct Oscillation {
+    /// Index of the control variable (order of CampaignTrait::get_control_variables, always 0 for sellers)
+    pub control_variable_index: usize,
+    /// Convergence iteration (1-indexed) at which the oscillation was detected
+    pub detected_at_iteration: usize,
+    /// Number of consecutive sign-alternating changes (keeps growing while the oscillation lasts)
+    pub alternating_changes: usize,
+    /// Half of the peak-to-peak range of the control variable over the alternating changes
+    pub amplitude: f64,
+}
+
+/// Follows the control variables of campaigns or sellers across convergence iterations
+/// and detects sustained oscillation of each of them
+pub struct OscillationDetector {
+    /// "campaign" or "seller", used in warnings
+    kind: &'static str,
+    /// Number of consecutive sign-alternating changes that count as sustained oscillation
+    window: usize,
+    /// Control variable history, indexed by [entity][control variable][iteration]
+    history: Vec<Vec<Vec<f64>>>,
+    /// Detected oscillations per campaign or seller
+    oscillations: Vec<Vec<Oscillation>>,
+    /// Index into oscillations of the oscillation still in progress, per entity and control variable
+    ongoing: Vec<Vec<Option<usize>>>,
+}
+
+impl OscillationDetector {
+    pub fn new(kind: &'static str, num_entities: usize, window: usize) -> Self {
+        assert!(window >= 2, "Oscillation window must be at least 2 changes");
+        Self {
+            kind,
+            window,
+            history: vec![Vec::new(); num_entities],
+            oscillations: vec![Vec::new(); num_entities],
+            ongoing: vec![Vec::new(); num_entities],
+        }
+    }
+
+    /// Record the control variables a campaign or seller used in an iteration
+    /// Logs a Convergence warning when an oscillation is first detected
+    ///
+    /// # Arguments
+    /// * `iteration` - Convergence iteration (1-indexed)
+    /// * `entity_id` - Campaign or seller index
+    /// * `entity_name` - Campaign or seller name, used in warnings
+    /// * `control_variables` - Control variables used in this iteration
+    /// * `logger` - Logger for the warning
+    pub fn observe(&mut self, iteration: usize, entity_id: usize, entity_name: &str, control_variables: &[f64], logger: &mut Logger) {
+        let history = &mut self.history[entity_id];
+        let ongoing = &mut self.ongoing[entity_id];
+        if history.len() < control_variables.len() {
+            history.resize(control_variables.len(), Vec::new());
+            ongoing.resize(control_variables.len(), None);
+        }
+
+        for (index, &control_variable) in control_variables.iter().enumerate() {
+            history[index].push(control_variable);
+            let alternating_changes = trailing_alternating_changes(&history[index]);
+            if alternating_changes < self.window {
+                ongoing[index] = None;
+                continue;
+            }
+
+            let window_values = &history[index][history[index].len() - alternating_changes - 1..];
+            let min = window_values.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = window_values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let amplitude = (max - min) / 2.0;
+
+            match ongoing[index] {
+                Some(oscillation_index) => {
+                    let oscillation = &mut self.oscillations[entity_id][oscillation_index];
+                    oscillation.alternating_changes = alternating_changes;
+                    oscillation.amplitude = amplitude;
+                }
+                None => {
+                    warnln!(logger, LogEvent::Convergence,
+                        "Oscillation detected: kind={} id={} name=\"{}\" control_variable={} iteration={} alternating_changes={} amplitude={:.4} range=[{:.4}, {:.4}]",
+                        self.kind, entity_id, entity_name, index, iteration, alternating_changes, amplitude, min, max);
+                    ongoing[index] = Some(self.oscillations[entity_id].len());
+                    self.oscillations[entity_id].push(Oscillation {
+                        control_variable_index: index,
+                        detected_at_iteration: iteration,
+                        alternating_changes,
+                        amplitude,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Oscillations detected so far, per campaign or seller
+    pub fn oscillations(&self) -> &Vec<Vec<Oscillation>> {
+        &self.oscillations
+    }
+}
+
+/// Number of consecutive sign-alternating changes at the end of a control variable history
+/// A zero change (the variable held still) ends the sequence
+fn trailing_alternating_changes(history: &[f64]) -> usize {
+    let mut count = 0;
+    let mut later_change: f64 = 0.0;
+    for values in history.windows(2).rev() {
+        let change = values[1] - values[0];
+        if change == 0.0 || (count > 0 && change.signum() == later_change.signum()) {
+            break;
+        }
+        count += 1;
+        later_change = change;
+    }
+    count
+}
@@ -0,0 +1,255 @@
+//! Declarative scenario configuration files
+//!
+//! A scenario config describes a marketplace (campaigns, sellers, impressions and simulation type) and variants
+//! of it in a TOML or YAML file, so experiments can be defined without writing a scenario module. The config is
+//! run with `marrakesh run-config <path>` (needs the `config` feature) as a Scenario (see scenarios/builder.rs):
+//! the base marketplace and every variant converge on the same impressions, are reported in a comparison table
+//! and are checked for their campaigns meeting their convergence targets.
+//!
+//! Enums are written like in Rust, unit variants as strings and struct variants as a table keyed by the variant
+//! (in YAML, tagged with the variant, e.g. `!TOTAL_BUDGET { target_total_budget: 20.0 }`):
+//!
+//! ```toml
+//! name = "bid_cap_experiment"
+//!
+//! [[campaigns]]
+//! campaign_name = "Campaign 0"
+//! campaign_type = "MULTIPLICATIVE_PACING"
+//! converge_targets = [{ TOTAL_BUDGET = { target_total_budget = 20.0 } }]
+//!
+//! [[sellers]]
+//! seller_name = "HB"
+//! seller_type = "FIRST_PRICE"
+//! seller_converge = { NONE = { default_value = 1.0 } }
+//! impressions_on_offer = 10000
+//!
+//! [[variants]]
+//! name = "capped"
+//! campaigns = [{ campaign_id = 0, max_bid_cpm = 9.0 }]
+//! ```
+
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::seller_builder::SellerConfig;
+use crate::simulationrun::SimulationType;
+use crate::impressions::ImpressionsParam;
+use crate::variants::BASELINE_NAME;
+use crate::scenarios::builder::Scenario;
+use crate::utils;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Maximum number of iterations to converge each variant, when the config doesn't set it
+const DEFAULT_MAX_ITERATIONS: usize = 100;
+
+/// Lognormal distribution given by its mean and standard deviation (see utils::lognormal_dist)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LogNormalConfig {
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+/// Settings of a campaign that can be set on the base marketplace and changed by variants
+/// Unset settings are left as they are
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct CampaignSettingsConfig {
+    /// See Campaigns::set_max_bid_cpm
+    #[serde(default)]
+    pub max_bid_cpm: Option<f64>,
+    /// See Campaigns::set_frequency_cap, needs `num_users` in the impressions config
+    #[serde(default)]
+    pub frequency_cap: Option<usize>,
+    /// See Campaigns::set_budget_cap
+    #[serde(default)]
+    pub budget_cap: Option<f64>,
+    /// First day the campaign is active, see Campaigns::set_active_days
+    #[serde(default)]
+    pub start_day: Option<usize>,
+    /// Day the campaign ends (exclusive), runs until the end of the simulation when unset
+    #[serde(default)]
+    pub end_day: Option<usize>,
+    /// See Campaigns::set_paused
+    #[serde(default)]
+    pub paused: Option<bool>,
+}
+
+impl CampaignSettingsConfig {
+    /// Apply the set settings to a campaign
+    fn apply(&self, campaigns: &mut Campaigns, campaign_id: usize) {
+        if let Some(max_bid_cpm) = self.max_bid_cpm {
+            campaigns.set_max_bid_cpm(campaign_id, max_bid_cpm);
+        }
+        if let Some(frequency_cap) = self.frequency_cap {
+            campaigns.set_frequency_cap(campaign_id, frequency_cap);
+        }
+        if let Some(budget_cap) = self.budget_cap {
+            campaigns.set_budget_cap(campaign_id, budget_cap);
+        }
+        if self.start_day.is_some() || self.end_day.is_some() {
+            campaigns.set_active_days(campaign_id, self.start_day.unwrap_or(0), self.end_day);
+        }
+        if let Some(paused) = self.paused {
+            campaigns.set_paused(campaign_id, paused);
+        }
+    }
+}
+
+/// Declarative description of a campaign, with the arguments of Campaigns::add and its settings
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CampaignConfig {
+    pub campaign_name: String,
+    pub campaign_type: CampaignType,
+    pub converge_targets: Vec<ConvergeTarget>,
+    #[serde(flatten)]
+    pub settings: CampaignSettingsConfig,
+}
+
+/// Impressions parameters, defaulting to the value distributions most scenarios use
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ImpressionsConfig {
+    pub base_impression_value: LogNormalConfig,
+    pub value_to_campaign_multiplier: LogNormalConfig,
+    /// Relative traffic of each day, see ImpressionsParam::with_days
+    pub days: Option<Vec<f64>>,
+    /// Relative traffic of each hour of the day, see ImpressionsParam::with_hourly_arrival_rates
+    pub hourly_arrival_rates: Option<Vec<f64>>,
+    /// Number of users impressions are spread over, see ImpressionsParam::with_users
+    pub num_users: Option<usize>,
+}
+
+impl Default for ImpressionsConfig {
+    fn default() -> Self {
+        Self {
+            base_impression_value: LogNormalConfig { mean: 10.0, stddev: 3.0 },
+            value_to_campaign_multiplier: LogNormalConfig { mean: 1.0, stddev: 0.2 },
+            days: None,
+            hourly_arrival_rates: None,
+            num_users: None,
+        }
+    }
+}
+
+impl ImpressionsConfig {
+    /// Create the impressions parameters
+    pub fn build(&self) -> ImpressionsParam {
+        let mut impressions_params = ImpressionsParam::new(
+            utils::lognormal_dist(self.base_impression_value.mean, self.base_impression_value.stddev),
+            utils::lognormal_dist(self.value_to_campaign_multiplier.mean, self.value_to_campaign_multiplier.stddev),
+        );
+        if let Some(hourly_arrival_rates) = &self.hourly_arrival_rates {
+            impressions_params = impressions_params.with_hourly_arrival_rates(hourly_arrival_rates.clone());
+        }
+        if let Some(days) = &self.days {
+            impressions_params = impressions_params.with_days(days.clone());
+        }
+        if let Some(num_users) = self.num_users {
+            impressions_params = impressions_params.with_users(num_users);
+        }
+        impressions_params
+    }
+}
+
+/// Change of a campaign's settings in a variant
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CampaignChangeConfig {
+    pub campaign_id: usize,
+    #[serde(flatten)]
+    pub settings: CampaignSettingsConfig,
+}
+
+/// Named variant of the base marketplace, changing the settings of some of its campaigns
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct VariantConfig {
+    pub name: String,
+    #[serde(default)]
+    pub campaigns: Vec<CampaignChangeConfig>,
+}
+
+/// Declarative description of a scenario: the base marketplace and its variants
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ScenarioConfig {
+    /// Name of the scenario, used for its log directory
+    pub name: String,
+    #[serde(default = "default_simulation_type")]
+    pub simulation_type: SimulationType,
+    #[serde(default = "default_max_iterations")]
+    pub max_iterations: usize,
+    #[serde(default)]
+    pub impressions: ImpressionsConfig,
+    pub campaigns: Vec<CampaignConfig>,
+    pub sellers: Vec<SellerConfig>,
+    #[serde(default)]
+    pub variants: Vec<VariantConfig>,
+}
+
+fn default_simulation_type() -> SimulationType {
+    SimulationType::Standard
+}
+
+fn default_max_iterations() -> usize {
+    DEFAULT_MAX_ITERATIONS
+}
+
+impl ScenarioConfig {
+    /// Read a config from a TOML file, or a YAML file when the extension is .yaml or .yml
+    ///
+    /// # Returns
+    /// Returns a Result with the config, or an error if the file can't be read, parsed or is inconsistent
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path).map_err(|e| format!("Can't read config file '{}': {}", path.display(), e))?;
+        let config: ScenarioConfig = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&contents).map_err(|e| format!("Invalid config file '{}': {}", path.display(), e))?,
+            _ => toml::from_str(&contents).map_err(|e| format!("Invalid config file '{}': {}", path.display(), e))?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check what deserialization can't: the marketplace isn't empty and variants refer to existing campaigns
+    fn validate(&self) -> Result<(), String> {
+        if self.campaigns.is_empty() || self.sellers.is_empty() {
+            return Err(format!("Config '{}' needs at least one campaign and one seller", self.name));
+        }
+        if self.max_iterations == 0 {
+            return Err(format!("Config '{}' needs max_iterations of at least 1", self.name));
+        }
+        for variant in &self.variants {
+            if variant.name == BASELINE_NAME {
+                return Err(format!("Config '{}': variant name '{}' is reserved for the base marketplace", self.name, BASELINE_NAME));
+            }
+            for change in &variant.campaigns {
+                if change.campaign_id >= self.campaigns.len() {
+                    return Err(format!("Config '{}': variant '{}' changes campaign {}, but there are only {} campaigns", self.name, variant.name, change.campaign_id, self.campaigns.len()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Create the scenario running the base marketplace and every variant, validated by every campaign meeting its
+    /// targets (command `marrakesh run-config <path>`)
+    pub fn scenario(&self) -> Scenario {
+        let mut scenario = Scenario::new(&self.name)
+            .impressions(self.impressions.build())
+            .simulation_type(self.simulation_type.clone())
+            .max_iterations(self.max_iterations)
+            .validate_targets_met();
+        for (campaign_id, campaign) in self.campaigns.iter().enumerate() {
+            scenario = scenario.campaign(&campaign.campaign_name, campaign.campaign_type.clone(), campaign.converge_targets.clone());
+            let settings = campaign.settings.clone();
+            scenario = scenario.setup(move |campaigns, _| settings.apply(campaigns, campaign_id));
+        }
+        for seller in &self.sellers {
+            scenario = scenario.seller(seller.clone());
+        }
+        for variant in &self.variants {
+            let changes = variant.campaigns.clone();
+            scenario = scenario.variant(&variant.name, move |campaigns, _| {
+                for change in &changes {
+                    change.settings.apply(campaigns, change.campaign_id);
+                }
+            });
+        }
+        scenario
+    }
+}
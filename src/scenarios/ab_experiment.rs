@@ -0,0 +1,153 @@
+//! This scenario demonstrates A/B experiments splitting impressions into control and treatment buckets.
+//!
+//! Impressions are shown to 10000 users. An experiment assigns every user (or every impression) to the
+//! control or the treatment bucket, and two campaigns, one per arm, each bid only on the impressions of
+//! their arm's bucket, with budgets in proportion to the bucket's share. Both arms run simultaneously in
+//! the same marketplace, and the lift of the treatment over the control is estimated with its variance.
+//!
+//! - Variant A: A/A test split by user, both arms pacing with the same bidder
+//!
+//! - Variant B: A/B test split by user, the treatment arm bidding with the maximum margin bidder
+//!
+//! - Variant C: A/A test split by impression, 20% of the impressions in the treatment bucket
+//!
+//! The lift is measured on value obtained per impression offered in the arm's bucket. The A/A tests should show
+//! no lift, within three standard errors of 0 (a 95% confidence interval misses 0 in one of twenty A/A tests by
+//! design). The maximum margin bidder spends its budget on the impressions with the best margin, so it obtains
+//! clearly more value from its bucket than the control.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, ExperimentSplit, ExperimentArm};
+use crate::converge::SimulationConverge;
+use crate::experiments::{ExperimentStat, LiftEstimate};
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "ab_experiment",
+    run,
+    tags: &["analysis"],
+});
+
+/// Number of users the impressions are shown to
+const NUM_USERS: usize = 10000;
+/// Budget of both arms together
+const TOTAL_BUDGET: f64 = 100.0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(split: ExperimentSplit, treatment_share: f64, treatment_campaign_type: CampaignType) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add the experiment and a campaign per arm (IDs are automatically set to match Vec index), the arms'
+    // budgets in proportion to their bucket's share of the impressions
+    let experiment_id = campaigns.add_experiment("bidder", split, treatment_share);
+    let control_id = campaigns.add(
+        "Control".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TOTAL_BUDGET * (1.0 - treatment_share) }],  // converge_target
+    );
+    campaigns.set_experiment_arm(control_id, experiment_id, ExperimentArm::CONTROL);
+    let treatment_id = campaigns.add(
+        "Treatment".to_string(),  // campaign_name
+        treatment_campaign_type,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TOTAL_BUDGET * treatment_share }],  // converge_target
+    );
+    campaigns.set_experiment_arm(treatment_id, experiment_id, ExperimentArm::TREATMENT);
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        50000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(1.0, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, showing impressions to users
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 1.0),   // value_to_campaign_multiplier_dist
+    ).with_users(NUM_USERS);
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Lift of value obtained per impression offered in the bucket of the experiment's treatment over its control
+fn value_lift(experiment_stat: &ExperimentStat) -> LiftEstimate {
+    experiment_stat.lift(|bucket| bucket.total_value, |bucket| bucket.impressions_offered)
+}
+
+/// Check that the treatment bucket's share of the impressions is close to the experiment's treatment share
+fn check_bucket_share(label: &str, stats: &SimulationStat, treatment_share: f64, logger: &mut Logger, errors: &mut Vec<String>) {
+    let experiment_stat = &stats.experiment_stats[0];
+    let treatment_offered = experiment_stat.arm_total(ExperimentArm::TREATMENT).impressions_offered;
+    let share = treatment_offered / (treatment_offered + experiment_stat.arm_total(ExperimentArm::CONTROL).impressions_offered);
+    assertions::assert_between(&format!("{}: Treatment bucket's share of the impressions", label), share, treatment_share - 0.05, treatment_share + 0.05, logger, errors);
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A, an A/A test split by user
+    let simulation_converge_a = prepare_simulationconverge(ExperimentSplit::USER, 0.5, CampaignType::MULTIPLICATIVE_PACING);
+    let stats_a = simulation_converge_a.run_variant("Running A/A test split by user", scenario_name, "aa-user", 100, logger)?;
+
+    // Run variant B, an A/B test split by user with the maximum margin bidder in the treatment arm
+    let simulation_converge_b = prepare_simulationconverge(ExperimentSplit::USER, 0.5, CampaignType::MAX_MARGIN);
+    let stats_b = simulation_converge_b.run_variant("Running A/B test split by user, maximum margin treatment", scenario_name, "ab-user", 100, logger)?;
+
+    // Run variant C, an A/A test split by impression with 20% of the impressions in the treatment bucket
+    let simulation_converge_c = prepare_simulationconverge(ExperimentSplit::IMPRESSION, 0.2, CampaignType::MULTIPLICATIVE_PACING);
+    let stats_c = simulation_converge_c.run_variant("Running A/A test split by impression, 20% treatment", scenario_name, "aa-impression", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Both arms of every variant meet their budgets, each in its own bucket
+    validation::assert_targets_met("Variant A", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C", &stats_c, logger, &mut errors);
+
+    // Check: The buckets' shares of the impressions follow the experiments' treatment shares
+    check_bucket_share("Variant A", &stats_a, 0.5, logger, &mut errors);
+    check_bucket_share("Variant C", &stats_c, 0.2, logger, &mut errors);
+
+    // Check: The A/A tests show no lift of value per impression
+    for (label, stats) in [("Variant A", &stats_a), ("Variant C", &stats_c)] {
+        let lift = value_lift(&stats.experiment_stats[0]);
+        assertions::assert_between(
+            &format!("{} (A/A): Lift of value per impression is within 3 standard errors of 0", label),
+            lift.lift,
+            -3.0 * lift.std_error,
+            3.0 * lift.std_error,
+            logger,
+            &mut errors,
+        );
+    }
+
+    // Check: The maximum margin treatment obtains clearly more value from its bucket than the control
+    let lift_b = value_lift(&stats_b.experiment_stats[0]);
+    assertions::assert_greater(
+        &format!("Variant B (A/B): Treatment obtains more value per impression than control ({:.4} vs. {:.4}, lift {}), lower bound of the lift", lift_b.treatment, lift_b.control, lift_b.lift_string()),
+        lift_b.ci_low,
+        0.0,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,123 @@
+//! This scenario demonstrates a seller reached through an expensive header bidding adapter.
+//!
+//! Two campaigns (impressions and budget targets) buy from two identical sellers.
+//!
+//! - Variant A: Neither seller charges an adapter fee
+//!
+//! - Variant B: The second seller's adapter takes 20% of the bids, deducted before the auction compares
+//!   them with the floor and the competing bid
+//!
+//! Bids routed through the expensive seller are less competitive, so in variant B the campaigns buy fewer
+//! impressions there and more on the other seller, while the adapter keeps 20% of what they pay on it.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, Sellers, SellerBuilder};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "adapter_fee",
+    run,
+    tags: &["fast", "supply"],
+});
+
+/// Seller IDs
+const CHEAP_SELLER: usize = 0;
+const EXPENSIVE_SELLER: usize = 1;
+/// Adapter fee of the expensive seller in variant B
+const ADAPTER_FEE: f64 = 0.2;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(adapter_fee: Option<f64>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 2000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 40.0 }],  // converge_target
+    );
+
+    // Add sellers (IDs are automatically set to match Vec index)
+    SellerBuilder::new("Cheap", SellerType::FIRST_PRICE, 10000).add_to(&mut sellers);
+    let mut expensive_seller = SellerBuilder::new("Expensive", SellerType::FIRST_PRICE, 10000);
+    if let Some(adapter_fee) = adapter_fee {
+        expensive_seller = expensive_seller.with_adapter_fee(adapter_fee);
+    }
+    expensive_seller.add_to(&mut sellers);
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A without adapter fees
+    let simulation_converge_a = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_variant("Running without adapter fees", scenario_name, "no-fee", 100, logger)?;
+
+    // Run variant B with an adapter fee on the second seller
+    let simulation_converge_b = prepare_simulationconverge(Some(ADAPTER_FEE));
+    let stats_b = simulation_converge_b.run_variant("Running with an adapter fee on the second seller", scenario_name, "fee", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (no fee)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (fee)", &stats_b, logger, &mut errors);
+
+    // Check: Demand shifts away from the seller with the adapter fee
+    let cheap_a = &stats_a.seller_stats[CHEAP_SELLER];
+    let cheap_b = &stats_b.seller_stats[CHEAP_SELLER];
+    let expensive_a = &stats_a.seller_stats[EXPENSIVE_SELLER];
+    let expensive_b = &stats_b.seller_stats[EXPENSIVE_SELLER];
+    assertions::assert_greater(
+        "Variant A (no fee) sells more impressions on the expensive seller than variant B (fee)",
+        expensive_a.impressions_sold as f64,
+        expensive_b.impressions_sold as f64,
+        logger,
+        &mut errors,
+    );
+    assertions::assert_greater(
+        "Variant B (fee) sells more impressions on the cheap seller than variant A (no fee)",
+        cheap_b.impressions_sold as f64,
+        cheap_a.impressions_sold as f64,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Adapter keeps its fee of what buyers pay on the expensive seller, and no fees are charged elsewhere
+    let expected_fees = expensive_b.total_buyer_charge * ADAPTER_FEE;
+    validation::check(
+        expensive_b.total_adapter_fees > 0.0 && (expensive_b.total_adapter_fees - expected_fees).abs() < 1e-9
+            && cheap_b.total_adapter_fees == 0.0 && expensive_a.total_adapter_fees == 0.0,
+        format!("Variant B (fee) adapter fees are {:.0}% of the expensive seller's buyer charge: {:.4} == {:.4}", ADAPTER_FEE * 100.0, expensive_b.total_adapter_fees, expected_fees),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,126 @@
+//! This scenario compares a fixed-gain PD controller with the adaptive gain (coarse-to-fine) controller.
+//!
+//! - Variant A: Max margin campaign paced by ControllerProportionalDerivative with high gains
+//!
+//! - Variant B: Max margin campaign paced by ControllerAdaptiveGain with the same gains far from the target
+//!
+//! Both campaigns converge to the same budget. High fixed gains overshoot near the budget and oscillate
+//! around it. The adaptive controller takes equally large steps while far from the budget but shrinks
+//! them near it, so it should converge in fewer iterations (the fixed-gain controller may not converge at all).
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignGeneral};
+use crate::controllers::{ControllerAdaptiveGain, ControllerProportionalDerivative, ControllerTrait};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "adaptive_gain",
+    run,
+    tags: &["slow", "controllers"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the number of iterations run
+fn prepare_simulationconverge(adaptive_gain: bool) -> (SimulationConverge, Rc<Cell<usize>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 40.0 }],  // converge_target
+    );
+    let converge_controller: Box<dyn ControllerTrait> = if adaptive_gain {
+        Box::new(ControllerAdaptiveGain::new())
+    } else {
+        // Same gains as the adaptive controller uses far from the target
+        Box::new(ControllerProportionalDerivative::new_advanced(
+            0.002, // tolerance_fraction
+            0.3,   // max_adjustment_factor
+            0.8,   // proportional_gain
+            0.4,   // derivative_gain
+            true,  // rescaling
+        ))
+    };
+    let campaign_general = campaigns.campaigns[campaign_id].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+    campaign_general.converge_controllers[0] = converge_controller;
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Count iterations until convergence
+    let iterations = Rc::new(Cell::new(0usize));
+    {
+        let iterations_count = iterations.clone();
+        simulation_converge.hooks.borrow_mut().on_iteration_start(move |iteration, _logger| {
+            iterations_count.set(iteration);
+        });
+    }
+
+    (simulation_converge, iterations)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with the fixed-gain PD controller, which is allowed not to converge
+    let (simulation_converge_a, iterations_a) = prepare_simulationconverge(false);
+    let result_a = simulation_converge_a.run_variant("Running with high fixed-gain PD controller", scenario_name, "fixed-gain", 200, logger);
+    if let Err(error) = &result_a {
+        logln!(logger, LogEvent::Scenario, "Variant A (fixed gain): {}", error);
+    }
+
+    // Run variant B with the adaptive gain controller
+    let (simulation_converge_b, iterations_b) = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with adaptive gain controller", scenario_name, "adaptive-gain", 200, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Adaptive gain controller converges in fewer iterations
+    assertions::assert_greater(
+        "Variant A (fixed gain) needs more iterations to converge than variant B (adaptive gain)",
+        iterations_a.get() as f64,
+        iterations_b.get() as f64,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Adaptive gain controller converges to the budget
+    let spend_b = stats_b.campaign_stats[0].total_buyer_charge;
+    assertions::assert_within_pct("Variant B (adaptive gain) spends its budget", spend_b, 40.0, 1.0, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -21,12 +21,13 @@ use crate::floors;
 use crate::utils;
 use crate::logger::{Logger, LogEvent};
 use crate::logln;
-use crate::errln;
+use crate::scenarios::{validation, assertions};
 
 // Register this scenario in the catalog
 inventory::submit!(crate::scenarios::ScenarioEntry {
     short_name: "median_bidder",
     run,
+    tags: &["fractional"],
 });
 
 /// Prepare simulation converge instance with campaign and seller setup
@@ -164,61 +165,17 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     let mut errors: Vec<String> = Vec::new();
     
     // Validation 1: Low impressions (5000) - ALB should work worse than multiplicative
-    let msg = format!(
-        "Low impressions (5000): Multiplicative pacing obtained value > ALB obtained value: {:.2} > {:.2}",
-        stats_mult_low.overall_stat.total_value,
-        stats_alb_low.overall_stat.total_value
-    );
-    if stats_mult_low.overall_stat.total_value > stats_alb_low.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
+    assertions::assert_greater("Low impressions (5000): Multiplicative pacing obtains more value than ALB", stats_mult_low.overall_stat.total_value, stats_alb_low.overall_stat.total_value, logger, &mut errors);
     
     // Validation 2: High impressions (50000) - ALB should work better than multiplicative
-    let msg = format!(
-        "High impressions (50000): ALB obtained value > Multiplicative pacing obtained value: {:.2} > {:.2}",
-        stats_alb_high.overall_stat.total_value,
-        stats_mult_high.overall_stat.total_value
-    );
-    if stats_alb_high.overall_stat.total_value > stats_mult_high.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
+    assertions::assert_greater("High impressions (50000): ALB obtains more value than Multiplicative pacing", stats_alb_high.overall_stat.total_value, stats_mult_high.overall_stat.total_value, logger, &mut errors);
     
     // Validation 3: Low impressions - Max margin should capture more value than ALB
-    let msg = format!(
-        "Low impressions (5000): Max margin obtained value > ALB obtained value: {:.2} > {:.2}",
-        stats_maxmargin_low.overall_stat.total_value,
-        stats_alb_low.overall_stat.total_value
-    );
-    if stats_maxmargin_low.overall_stat.total_value > stats_alb_low.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
+    assertions::assert_greater("Low impressions (5000): Max margin obtains more value than ALB", stats_maxmargin_low.overall_stat.total_value, stats_alb_low.overall_stat.total_value, logger, &mut errors);
     
     // Validation 4: High impressions - Max margin should capture more value than ALB
-    let msg = format!(
-        "High impressions (50000): Max margin obtained value > ALB obtained value: {:.2} > {:.2}",
-        stats_maxmargin_high.overall_stat.total_value,
-        stats_alb_high.overall_stat.total_value
-    );
-    if stats_maxmargin_high.overall_stat.total_value > stats_alb_high.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
-    
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(format!("Scenario '{}' validation failed:\n{}", scenario_name, errors.join("\n")).into())
-    }
+    assertions::assert_greater("High impressions (50000): Max margin obtains more value than ALB", stats_maxmargin_high.overall_stat.total_value, stats_alb_high.overall_stat.total_value, logger, &mut errors);
+    
+    validation::scenario_result(scenario_name, errors)
 }
 
@@ -0,0 +1,109 @@
+//! Assertions on the metrics of a scenario, with tolerances
+//!
+//! Each assertion is a check (see validation::check) whose message shows the values compared: the actual value,
+//! the target or bounds, and how far off the actual value is. A failed assertion is logged on its own as ✗ and its
+//! message collected into errors, so scenario_result lists every failed assertion individually.
+
+use crate::logger::Logger;
+use crate::scenarios::validation;
+
+/// Relative difference of a value from a reference in percent, e.g. "+2.50%" ("-" when the reference is 0)
+fn relative_difference_string(value: f64, reference: f64) -> String {
+    if reference != 0.0 { format!("{:+.2}%", (value / reference - 1.0) * 100.0) } else { "-".to_string() }
+}
+
+/// Assert that actual is within tolerance_pct percent of target
+pub fn assert_within_pct(label: &str, actual: f64, target: f64, tolerance_pct: f64, logger: &mut Logger, errors: &mut Vec<String>) {
+    let passed = (actual - target).abs() <= target.abs() * tolerance_pct / 100.0;
+    let msg = if passed {
+        format!("{}: {:.4} ≈ {:.4} (±{}%, off by {})", label, actual, target, tolerance_pct, relative_difference_string(actual, target))
+    } else {
+        format!("{}: expected {:.4} within ±{}% of {:.4}, off by {}", label, actual, tolerance_pct, target, relative_difference_string(actual, target))
+    };
+    validation::check(passed, msg, logger, errors);
+}
+
+/// Assert that actual is between min and max (inclusive)
+pub fn assert_between(label: &str, actual: f64, min: f64, max: f64, logger: &mut Logger, errors: &mut Vec<String>) {
+    let passed = actual >= min && actual <= max;
+    let msg = if passed {
+        format!("{}: {:.4} in [{:.4}, {:.4}]", label, actual, min, max)
+    } else if actual < min {
+        format!("{}: expected {:.4} in [{:.4}, {:.4}], below the minimum by {:.4}", label, actual, min, max, min - actual)
+    } else {
+        format!("{}: expected {:.4} in [{:.4}, {:.4}], above the maximum by {:.4}", label, actual, min, max, actual - max)
+    };
+    validation::check(passed, msg, logger, errors);
+}
+
+/// Assert that actual is greater than other
+pub fn assert_greater(label: &str, actual: f64, other: f64, logger: &mut Logger, errors: &mut Vec<String>) {
+    let passed = actual > other;
+    let msg = if passed {
+        format!("{}: {:.4} > {:.4} ({})", label, actual, other, relative_difference_string(actual, other))
+    } else {
+        format!("{}: expected {:.4} > {:.4}, short by {:.4} ({})", label, actual, other, other - actual, relative_difference_string(actual, other))
+    };
+    validation::check(passed, msg, logger, errors);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run an assertion and return the messages of its failures
+    fn failures(assertion: impl FnOnce(&mut Logger, &mut Vec<String>)) -> Vec<String> {
+        let mut logger = Logger::new();
+        let mut errors = Vec::new();
+        assertion(&mut logger, &mut errors);
+        errors
+    }
+
+    #[test]
+    fn test_assert_within_pct_edges() {
+        // The tolerance is inclusive, on both sides of the target
+        assert!(failures(|logger, errors| assert_within_pct("edge", 110.0, 100.0, 10.0, logger, errors)).is_empty());
+        assert!(failures(|logger, errors| assert_within_pct("edge", 90.0, 100.0, 10.0, logger, errors)).is_empty());
+        assert_eq!(failures(|logger, errors| assert_within_pct("edge", 110.5, 100.0, 10.0, logger, errors)).len(), 1);
+        // Negative targets take the tolerance from their magnitude
+        assert!(failures(|logger, errors| assert_within_pct("negative", -110.0, -100.0, 10.0, logger, errors)).is_empty());
+        assert!(failures(|logger, errors| assert_within_pct("negative", -90.0, -100.0, 10.0, logger, errors)).is_empty());
+        let errors = failures(|logger, errors| assert_within_pct("negative", -111.0, -100.0, 10.0, logger, errors));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("off by +11.00%"), "{}", errors[0]);
+        // A zero target leaves no tolerance, and no relative difference to report
+        assert!(failures(|logger, errors| assert_within_pct("zero", 0.0, 0.0, 10.0, logger, errors)).is_empty());
+        let errors = failures(|logger, errors| assert_within_pct("zero", 0.001, 0.0, 10.0, logger, errors));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("off by -"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn test_assert_between_edges() {
+        // Bounds are inclusive
+        assert!(failures(|logger, errors| assert_between("bounds", 1.0, 1.0, 2.0, logger, errors)).is_empty());
+        assert!(failures(|logger, errors| assert_between("bounds", 2.0, 1.0, 2.0, logger, errors)).is_empty());
+        // Equal bounds only admit their value
+        assert!(failures(|logger, errors| assert_between("equal", 5.0, 5.0, 5.0, logger, errors)).is_empty());
+        let errors = failures(|logger, errors| assert_between("equal", 5.5, 5.0, 5.0, logger, errors));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("above the maximum by 0.5000"), "{}", errors[0]);
+        let errors = failures(|logger, errors| assert_between("equal", 4.5, 5.0, 5.0, logger, errors));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("below the minimum by 0.5000"), "{}", errors[0]);
+        // Negative bounds
+        assert!(failures(|logger, errors| assert_between("negative", -1.5, -2.0, -1.0, logger, errors)).is_empty());
+        assert_eq!(failures(|logger, errors| assert_between("negative", -0.5, -2.0, -1.0, logger, errors)).len(), 1);
+    }
+
+    #[test]
+    fn test_assert_greater_edges() {
+        // Equal values are not greater
+        assert_eq!(failures(|logger, errors| assert_greater("equal", 1.0, 1.0, logger, errors)).len(), 1);
+        assert!(failures(|logger, errors| assert_greater("negative", -1.0, -2.0, logger, errors)).is_empty());
+        // Against zero there is no relative difference to report
+        let errors = failures(|logger, errors| assert_greater("zero", -1.0, 0.0, logger, errors));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("short by 1.0000 (-)"), "{}", errors[0]);
+    }
+}
@@ -0,0 +1,201 @@
+//! This scenario demonstrates attribute-level arbitrage by a max margin bidder.
+//!
+//! Impressions have a device type and a geo tier, each with its own value multiplier and competition level:
+//! mobile impressions are worth 20% less than desktop ones but meet half the competition, tier 1 geos are
+//! worth 30% more than tier 2 ones and meet 30% more competition.
+//!
+//! All variants spend the same budget:
+//!
+//! - Variant A: Multiplicative pacing, bidding the same fraction of value on every impression
+//!
+//! - Variant B: Max margin bidding, its win rate prediction seeing the competition level of every impression
+//!
+//! The max margin bidder shades its bids where competition is weak, buying the underpriced mobile impressions
+//! cheaply, and obtains more value per spend than the pacing bidder.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, AttributeCategory, Winner};
+use crate::hooks::AuctionOutcome;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "attribute_arbitrage",
+    run,
+    tags: &[],
+});
+
+/// Device types, indexed by Impression::device_type
+const DESKTOP: usize = 0;
+const MOBILE: usize = 1;
+
+/// Impressions offered and competing bids, and impressions won and spend of the campaign, per device type
+#[derive(Default, Clone)]
+struct DeviceSplit {
+    offered: f64,
+    competing_bids: f64,
+    won: f64,
+    spend: f64,
+}
+
+impl DeviceSplit {
+    fn win_rate(&self) -> f64 {
+        self.won / self.offered
+    }
+
+    fn average_competing_bid(&self) -> f64 {
+        self.competing_bids / self.offered
+    }
+
+    /// Average CPM paid on the won impressions
+    fn cpm(&self) -> f64 {
+        self.spend / self.won * 1000.0
+    }
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the per device type split of the latest iteration
+fn prepare_simulationconverge(campaign_type: CampaignType) -> (SimulationConverge, Rc<RefCell<Vec<DeviceSplit>>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        campaign_type,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 40.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters with device types and geo tiers
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_device_types(vec![
+        AttributeCategory::new("desktop", 1.0),
+        AttributeCategory::new("mobile", 1.0).with_value_multiplier(0.8).with_competition_multiplier(0.5),
+    ]).with_geo_tiers(vec![
+        AttributeCategory::new("tier 1", 1.0).with_value_multiplier(1.3).with_competition_multiplier(1.3),
+        AttributeCategory::new("tier 2", 2.0),
+    ]);
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Split auctions by device type, reset at the start of every iteration
+    let device_splits = Rc::new(RefCell::new(Vec::new()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let device_splits_reset = device_splits.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            *device_splits_reset.borrow_mut() = vec![DeviceSplit::default(); 2];
+        });
+        let device_splits_sum = device_splits.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let (AuctionOutcome::Standard(result), Some(device_type)) = (&event.outcome, event.impression.device_type) {
+                let device_split = &mut device_splits_sum.borrow_mut()[device_type];
+                device_split.offered += 1.0;
+                device_split.competing_bids += event.impression.competition.as_ref().map_or(0.0, |competition| competition.bid_cpm);
+                if let Winner::Campaign { buyer_charge, .. } = result.winner {
+                    device_split.won += 1.0;
+                    device_split.spend += buyer_charge;
+                }
+            }
+        });
+    }
+
+    (simulation_converge, device_splits)
+}
+
+/// Value obtained per unit of spend by the single campaign
+fn value_per_spend(stats: &SimulationStat) -> f64 {
+    let campaign = &stats.campaign_stats[0];
+    if campaign.total_buyer_charge > 0.0 {
+        campaign.total_value / campaign.total_buyer_charge
+    } else {
+        0.0
+    }
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with multiplicative pacing
+    let (simulation_converge_a, device_splits_a) = prepare_simulationconverge(CampaignType::MULTIPLICATIVE_PACING);
+    let stats_a = simulation_converge_a.run_variant("Running with multiplicative pacing", scenario_name, "pacing", 100, logger)?;
+
+    // Run variant B with max margin bidding
+    let (simulation_converge_b, device_splits_b) = prepare_simulationconverge(CampaignType::MAX_MARGIN);
+    let stats_b = simulation_converge_b.run_variant("Running with max margin bidding", scenario_name, "max_margin", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (pacing)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (max margin)", &stats_b, logger, &mut errors);
+
+    let device_splits_a = device_splits_a.borrow();
+    let device_splits_b = device_splits_b.borrow();
+    for (label, device_splits) in [("Pacing", &device_splits_a), ("Max margin", &device_splits_b)] {
+        logln!(logger, LogEvent::Scenario, "{}: desktop win rate {:.1}% at CPM {:.4}, mobile win rate {:.1}% at CPM {:.4}", label,
+            device_splits[DESKTOP].win_rate() * 100.0, device_splits[DESKTOP].cpm(), device_splits[MOBILE].win_rate() * 100.0, device_splits[MOBILE].cpm());
+    }
+
+    // Check: Mobile impressions meet the configured competition level (geo tiers are spread alike over device types)
+    let competition_ratio = device_splits_a[MOBILE].average_competing_bid() / device_splits_a[DESKTOP].average_competing_bid();
+    assertions::assert_between(
+        "Mobile competing bids relative to desktop ones follow the competition levels",
+        competition_ratio,
+        0.45,
+        0.55,
+        logger,
+        &mut errors,
+    );
+
+    // Check: The max margin bidder shades its bids more on the weakly competed mobile impressions
+    let cpm_ratio_a = device_splits_a[MOBILE].cpm() / device_splits_a[DESKTOP].cpm();
+    let cpm_ratio_b = device_splits_b[MOBILE].cpm() / device_splits_b[DESKTOP].cpm();
+    assertions::assert_greater(
+        "Variant A (pacing) pays more for mobile relative to desktop than variant B (max margin)",
+        cpm_ratio_a,
+        cpm_ratio_b,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Arbitrage across attributes obtains more value per spend
+    let value_per_spend_a = value_per_spend(&stats_a);
+    let value_per_spend_b = value_per_spend(&stats_b);
+    assertions::assert_greater(
+        "Variant B (max margin) has higher value per spend than variant A (pacing)",
+        value_per_spend_b,
+        value_per_spend_a,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,187 @@
+//! This scenario demonstrates campaigns targeted at audience segments.
+//!
+//! Impressions are labeled with one of two audience segments, a quarter of them in segment 0 and the rest in
+//! segment 1. Two campaigns (impressions and budget targets) buy from a single seller.
+//!
+//! - Variant A: No targeting, both campaigns bid on all impressions
+//!
+//! - Variant B: The impressions campaign targeted at segment 0
+//!
+//! - Variant C: The impressions campaign targeted at segment 0 and the budget campaign at segment 1
+//!
+//! Targeted campaigns only win impressions of their segments. Targeting shrinks the impressions campaign's
+//! supply, so it has to pay more per impression, unless the budget campaign leaves its segment to it.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::hooks::AuctionOutcome;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "audience_segments",
+    run,
+    tags: &["fast"],
+});
+
+/// Relative share of impressions in each segment
+const SEGMENT_WEIGHTS: [f64; 2] = [1.0, 3.0];
+
+/// Impressions won by each campaign in each segment (indexed by campaign_id, then by segment)
+type SegmentWins = Vec<Vec<usize>>;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the segment wins of the latest iteration
+fn prepare_simulationconverge(campaign_segments: [Option<Vec<usize>>; 2]) -> (SimulationConverge, Rc<RefCell<SegmentWins>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+    for (campaign_id, segments) in campaign_segments.into_iter().enumerate() {
+        if let Some(segments) = segments {
+            campaigns.set_segment_targeting(campaign_id, segments);
+        }
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, labeling impressions with segments
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_segments(SEGMENT_WEIGHTS.to_vec());
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Count the campaigns' wins per segment, reset at the start of every iteration
+    let segment_wins = Rc::new(RefCell::new(Vec::new()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let segment_wins_reset = segment_wins.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            *segment_wins_reset.borrow_mut() = vec![vec![0; SEGMENT_WEIGHTS.len()]; 2];
+        });
+        let segment_wins_count = segment_wins.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                if let (Winner::Campaign { campaign_id, .. }, Some(segment)) = (&result.winner, event.impression.segment) {
+                    segment_wins_count.borrow_mut()[*campaign_id][segment] += 1;
+                }
+            }
+        });
+    }
+
+    (simulation_converge, segment_wins)
+}
+
+/// Average CPM paid by a campaign
+fn campaign_cpm(stats: &SimulationStat, campaign_id: usize) -> f64 {
+    let campaign = &stats.campaign_stats[campaign_id];
+    campaign.total_buyer_charge / campaign.impressions_obtained * 1000.0
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A without targeting
+    let (simulation_converge_a, segment_wins_a) = prepare_simulationconverge([None, None]);
+    let stats_a = simulation_converge_a.run_variant("Running without targeting", scenario_name, "untargeted", 100, logger)?;
+
+    // Run variant B with the impressions campaign targeted at segment 0
+    let (simulation_converge_b, segment_wins_b) = prepare_simulationconverge([Some(vec![0]), None]);
+    let stats_b = simulation_converge_b.run_variant("Running with the impressions campaign targeted", scenario_name, "targeted", 100, logger)?;
+
+    // Run variant C with the campaigns targeted at disjoint segments
+    let (simulation_converge_c, segment_wins_c) = prepare_simulationconverge([Some(vec![0]), Some(vec![1])]);
+    let stats_c = simulation_converge_c.run_variant("Running with the campaigns targeted at disjoint segments", scenario_name, "disjoint", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (untargeted)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (targeted)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (disjoint)", &stats_c, logger, &mut errors);
+
+    // Check: Impressions are labeled with segments following the weights
+    let segment_0_share = stats_a.segment_impressions_offered[0] as f64 / stats_a.segment_impressions_offered.iter().sum::<usize>() as f64;
+    let expected_share = SEGMENT_WEIGHTS[0] / SEGMENT_WEIGHTS.iter().sum::<f64>();
+    assertions::assert_between(
+        "Share of impressions in segment 0 follows the weights",
+        segment_0_share,
+        expected_share - 0.02,
+        expected_share + 0.02,
+        logger,
+        &mut errors,
+    );
+
+    let segment_wins_a = segment_wins_a.borrow();
+    let segment_wins_b = segment_wins_b.borrow();
+    let segment_wins_c = segment_wins_c.borrow();
+    logln!(logger, LogEvent::Scenario, "Wins per segment (campaign 0, campaign 1): untargeted {:?}, targeted {:?}, disjoint {:?}",
+        *segment_wins_a, *segment_wins_b, *segment_wins_c);
+
+    // Check: Targeted campaigns only win impressions of their segments, untargeted ones win in all segments
+    validation::check(
+        segment_wins_a[0][1] > 0 && segment_wins_b[0][1] == 0 && segment_wins_c[0][1] == 0 && segment_wins_c[1][0] == 0,
+        format!("Targeted campaigns only win impressions of their segments: campaign 0 wins {} (untargeted), {} (targeted) and {} (disjoint) in segment 1, campaign 1 wins {} (disjoint) in segment 0",
+            segment_wins_a[0][1], segment_wins_b[0][1], segment_wins_c[0][1], segment_wins_c[1][0]),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Targeting shrinks the supply, so the impressions campaign pays more per impression
+    let cpm_a = campaign_cpm(&stats_a, 0);
+    let cpm_b = campaign_cpm(&stats_b, 0);
+    let cpm_c = campaign_cpm(&stats_c, 0);
+    assertions::assert_greater(
+        "Variant B (targeted) impressions campaign pays a higher CPM than in variant A (untargeted)",
+        cpm_b,
+        cpm_a,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Without the budget campaign in its segment, the impressions campaign pays less
+    assertions::assert_greater(
+        "Variant B (targeted) impressions campaign pays a higher CPM than in variant C (disjoint)",
+        cpm_b,
+        cpm_c,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -24,12 +24,14 @@ use crate::floors;
 use crate::utils;
 use crate::logger::{Logger, LogEvent};
 use crate::logln;
-use crate::errln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
 
 // Register this scenario in the catalog
 inventory::submit!(crate::scenarios::ScenarioEntry {
     short_name: "basic_bidding_strategies",
     run,
+    tags: &["fast"],
 });
 
 /// Prepare simulation converge instance with campaign and seller setup
@@ -99,35 +101,23 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     
     // Check: Variant B (Median Bidding) obtained value > Variant A (multiplicative pacing) obtained value
     // Note: This validation is true only when operating in regime of low fill rates
-    let msg = format!(
-        "Variant B (Median Bidding) obtained value is greater than Variant A (Multiplicative pacing): {:.2} > {:.2} (Note: true only in low fill rate regime)",
+    assertions::assert_greater(
+        "Variant B (Median Bidding) obtained value is greater than Variant A (Multiplicative pacing) (Note: true only in low fill rate regime)",
         stats_b.overall_stat.total_value,
-        stats_a.overall_stat.total_value
+        stats_a.overall_stat.total_value,
+        logger,
+        &mut errors,
     );
-    if stats_b.overall_stat.total_value > stats_a.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
     
     // Check: Variant D (cheater) obtained value > Variant C (max margin) obtained value
-    let msg = format!(
-        "Variant D (Cheater) obtained value is greater than Variant C (Max margin): {:.2} > {:.2}",
+    assertions::assert_greater(
+        "Variant D (Cheater) obtained value is greater than Variant C (Max margin)",
         stats_d.overall_stat.total_value,
-        stats_c.overall_stat.total_value
+        stats_c.overall_stat.total_value,
+        logger,
+        &mut errors,
     );
-    if stats_d.overall_stat.total_value > stats_c.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
     
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(format!("Scenario '{}' validation failed:\n{}", scenario_name, errors.join("\n")).into())
-    }
+    validation::scenario_result(scenario_name, errors)
 }
 
@@ -0,0 +1,126 @@
+//! This scenario studies the effect of bid caching at a seller on pacing.
+//!
+//! Two campaigns (budget and impressions targets) buy from a single seller.
+//!
+//! - Variant A: Seller auctions fresh bids
+//!
+//! - Variant B: Seller caches bids, serving half of the bids from the previous iteration
+//!
+//! - Variant C: Seller caches bids, serving 90% of the bids from the previous iteration
+//!
+//! Cached bids lag behind the campaigns' pacing, so a pacing change only takes effect on the fresh bids.
+//! With half of the bids cached the campaigns still meet their targets, taking more iterations than with
+//! fresh bids. With 90% of the bids cached the pacing controllers keep overshooting on the delayed
+//! feedback, and the campaigns don't converge.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, Sellers, SellerBuilder};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "bid_caching",
+    run,
+    tags: &["fast", "supply"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup, the seller caching bids with the given probability
+fn prepare_simulationconverge(stale_bid_probability: Option<f64>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    let mut seller = SellerBuilder::new("HB", SellerType::FIRST_PRICE, 10000);
+    if let Some(stale_bid_probability) = stale_bid_probability {
+        seller = seller.with_bid_caching(stale_bid_probability);
+    }
+    seller.add_to(&mut sellers);
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Number of bids served from the seller's cache in the final iteration, over all campaigns
+fn stale_bids(stats: &SimulationStat) -> usize {
+    stats.campaign_stats.iter().map(|campaign_stat| campaign_stat.stale_bids).sum()
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with fresh bids
+    let simulation_converge_a = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_variant("Running with fresh bids", scenario_name, "fresh", 100, logger)?;
+
+    // Run variant B with half of the bids served from the cache
+    let simulation_converge_b = prepare_simulationconverge(Some(0.5));
+    let stats_b = simulation_converge_b.run_variant("Running with half of the bids cached", scenario_name, "cached50", 100, logger)?;
+
+    // Run variant C with 90% of the bids served from the cache, which is expected not to converge
+    let simulation_converge_c = prepare_simulationconverge(Some(0.9));
+    let result_c = simulation_converge_c.run_variant("Running with 90% of the bids cached", scenario_name, "cached90", 100, logger);
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (fresh)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (cached 50%)", &stats_b, logger, &mut errors);
+
+    // Check: Only the caching seller serves stale bids
+    validation::check(
+        stale_bids(&stats_a) == 0 && stale_bids(&stats_b) > 0,
+        format!("Only variant B (cached 50%) serves stale bids: {} (fresh) and {} (cached 50%)", stale_bids(&stats_a), stale_bids(&stats_b)),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Stale bids lag behind pacing changes, slowing down convergence
+    assertions::assert_greater(
+        "Variant B (cached 50%) takes more iterations to converge than variant A (fresh)",
+        stats_b.convergence_iterations as f64,
+        stats_a.convergence_iterations as f64,
+        logger,
+        &mut errors,
+    );
+
+    // Check: With most bids stale, pacing keeps overshooting and doesn't converge
+    validation::check(
+        result_c.is_err(),
+        match &result_c {
+            Err(error) => format!("Variant C (cached 90%) doesn't converge: {}", error),
+            Ok(stats_c) => format!("Variant C (cached 90%) doesn't converge, but it converged in {} iterations", stats_c.convergence_iterations),
+        },
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
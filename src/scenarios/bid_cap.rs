@@ -0,0 +1,49 @@
+//! This scenario shows the interaction of a per-campaign max CPM bid cap with pacing.
+//!
+//! - Variant A: Multiplicative pacing without a bid cap (the baseline)
+//!
+//! - Variant B: Multiplicative pacing with a max CPM bid cap
+//!
+//! Both campaigns need to spend the same budget. The capped campaign cannot win expensive, high value
+//! impressions, so its pacing has to increase bids on cheaper impressions to spend the budget.
+//! It obtains impressions with lower average value than the uncapped campaign.
+
+use crate::simulationrun::CampaignStat;
+use crate::sellers::SellerType;
+use crate::seller_builder::SellerBuilder;
+use crate::campaigns::{CampaignType, ConvergeTarget};
+use crate::scenarios::builder::Scenario;
+use crate::scenarios::assertions;
+use crate::register_scenario;
+
+// Register this scenario in the catalog
+register_scenario!("bid_cap", scenario, &["fast"]);
+
+/// Average value per obtained impression
+fn avg_value(campaign_stat: &CampaignStat) -> f64 {
+    if campaign_stat.impressions_obtained > 0.0 { campaign_stat.total_value / campaign_stat.impressions_obtained } else { 0.0 }
+}
+
+/// Variant A without a bid cap as the baseline, and variant B with a bid cap below the typical impression value
+fn scenario(scenario_name: &str) -> Scenario {
+    Scenario::new(scenario_name)
+        .campaign("Campaign 0", CampaignType::MULTIPLICATIVE_PACING, vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }])
+        .seller(SellerBuilder::new("HB", SellerType::FIRST_PRICE, 10000).build())
+        .variant("capped", |campaigns, _| campaigns.set_max_bid_cpm(0, 9.0))
+        // Check: Only variant B has capped bids
+        .validate(|result| {
+            let (campaign_a, campaign_b) = (&result.baseline.campaign_stats[0], &result.variant("capped").campaign_stats[0]);
+            (
+                campaign_b.capped_bids > 0 && campaign_a.capped_bids == 0,
+                format!("Variant B (capped) has capped bids while variant A (uncapped) has none: {} > 0, {} == 0", campaign_b.capped_bids, campaign_a.capped_bids),
+            )
+        })
+        // Check: Variant A obtains impressions with higher average value than variant B
+        .assert(|result, logger, errors| assertions::assert_greater(
+            "Variant A (uncapped) obtains higher average value per impression than variant B (capped)",
+            avg_value(&result.baseline.campaign_stats[0]),
+            avg_value(&result.variant("capped").campaign_stats[0]),
+            logger,
+            errors,
+        ))
+}
@@ -0,0 +1,118 @@
+//! This scenario compares the default PD controller with the bisection controller on a monotone target.
+//!
+//! - Variant A: Max margin campaign targeting total impressions, paced by ControllerProportionalDerivative
+//!
+//! - Variant B: Max margin campaign targeting total impressions, paced by ControllerBisection
+//!
+//! Impressions obtained grow monotonically with pacing, so the bisection controller can bracket the
+//! correct pacing and halve the interval every iteration. It should converge in fewer iterations than PD.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignGeneral};
+use crate::controllers::{ControllerBisection, ControllerTrait};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "bisection_controller",
+    run,
+    tags: &["controllers"],
+});
+
+const TARGET_TOTAL_IMPRESSIONS: i32 = 1500;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the number of iterations run
+fn prepare_simulationconverge(bisection: bool) -> (SimulationConverge, Rc<Cell<usize>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: TARGET_TOTAL_IMPRESSIONS }],  // converge_target
+    );
+    if bisection {
+        let campaign_general = campaigns.campaigns[campaign_id].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+        let converge_controller: Box<dyn ControllerTrait> = Box::new(ControllerBisection::new());
+        campaign_general.converge_controllers[0] = converge_controller;
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Count iterations until convergence
+    let iterations = Rc::new(Cell::new(0usize));
+    {
+        let iterations_count = iterations.clone();
+        simulation_converge.hooks.borrow_mut().on_iteration_start(move |iteration, _logger| {
+            iterations_count.set(iteration);
+        });
+    }
+
+    (simulation_converge, iterations)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with the default PD controller
+    let (simulation_converge_a, iterations_a) = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running with PD controller", scenario_name, "pd", 200, logger)?;
+
+    // Run variant B with the bisection controller
+    let (simulation_converge_b, iterations_b) = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with bisection controller", scenario_name, "bisection", 200, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+    let target = TARGET_TOTAL_IMPRESSIONS as f64;
+
+    // Check: Bisection controller converges in fewer iterations
+    assertions::assert_greater(
+        "Variant A (PD) needs more iterations to converge than variant B (bisection)",
+        iterations_a.get() as f64,
+        iterations_b.get() as f64,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Both controllers reach the impressions target
+    for (variant, stats) in [("A (PD)", &stats_a), ("B (bisection)", &stats_b)] {
+        let impressions = stats.campaign_stats[0].impressions_obtained;
+        assertions::assert_within_pct(&format!("Variant {} obtains target impressions", variant), impressions, target, 1.0, logger, &mut errors);
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
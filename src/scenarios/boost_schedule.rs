@@ -0,0 +1,172 @@
+//! This scenario demonstrates a seller whose boost follows a schedule over the day.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, with traffic spread evenly
+//! over the day.
+//!
+//! - Variant A: Seller boost is constant over the day
+//!
+//! - Variant B: Seller boost is lowered to 0.6 in the off-peak hours (midnight to 8am), making bids on the
+//!   seller cheaper at night
+//!
+//! In variant B the campaigns bid less at night, so they pay a lower CPM for off-peak impressions relative
+//! to peak ones, and buy fewer of them.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, Sellers, SellerBuilder, BoostPeriod};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::hooks::AuctionOutcome;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "boost_schedule",
+    run,
+    tags: &["fast", "supply"],
+});
+
+/// Off-peak hours end at 8am
+const OFF_PEAK_END_HOUR: f64 = 8.0;
+/// Seller boost in the off-peak hours of variant B
+const OFF_PEAK_BOOST: f64 = 0.6;
+
+/// Impressions won and buyer charge, off-peak and at peak
+#[derive(Default)]
+struct PeakSplit {
+    off_peak_impressions: f64,
+    off_peak_buyer_charge: f64,
+    peak_impressions: f64,
+    peak_buyer_charge: f64,
+}
+
+impl PeakSplit {
+    fn off_peak_cpm(&self) -> f64 {
+        self.off_peak_buyer_charge / self.off_peak_impressions * 1000.0
+    }
+
+    fn peak_cpm(&self) -> f64 {
+        self.peak_buyer_charge / self.peak_impressions * 1000.0
+    }
+
+    fn off_peak_share(&self) -> f64 {
+        self.off_peak_impressions / (self.off_peak_impressions + self.peak_impressions)
+    }
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the off-peak and peak purchases of the campaigns in the latest iteration
+fn prepare_simulationconverge(scheduled: bool) -> (SimulationConverge, Rc<RefCell<PeakSplit>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    let mut seller = SellerBuilder::new("HB", SellerType::FIRST_PRICE, 10000);
+    if scheduled {
+        seller = seller.with_boost_schedule(vec![
+            BoostPeriod { from_hour: 0.0, boost_factor: OFF_PEAK_BOOST },
+            BoostPeriod { from_hour: OFF_PEAK_END_HOUR, boost_factor: 1.0 },
+        ]);
+    }
+    seller.add_to(&mut sellers);
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Measure off-peak and peak purchases of the campaigns, reset at the start of every iteration
+    let peak_split = Rc::new(RefCell::new(PeakSplit::default()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let peak_split_reset = peak_split.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            *peak_split_reset.borrow_mut() = PeakSplit::default();
+        });
+        let peak_split_sum = peak_split.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                if let Winner::Campaign { buyer_charge, .. } = result.winner {
+                    let mut peak_split = peak_split_sum.borrow_mut();
+                    if event.impression.time_of_day() < OFF_PEAK_END_HOUR {
+                        peak_split.off_peak_impressions += 1.0;
+                        peak_split.off_peak_buyer_charge += buyer_charge;
+                    } else {
+                        peak_split.peak_impressions += 1.0;
+                        peak_split.peak_buyer_charge += buyer_charge;
+                    }
+                }
+            }
+        });
+    }
+
+    (simulation_converge, peak_split)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with a constant seller boost
+    let (simulation_converge_a, peak_split_a) = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running with a constant seller boost", scenario_name, "constant", 100, logger)?;
+
+    // Run variant B with the seller boost lowered off-peak
+    let (simulation_converge_b, peak_split_b) = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with the seller boost lowered off-peak", scenario_name, "scheduled", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (constant)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (scheduled)", &stats_b, logger, &mut errors);
+
+    let peak_split_a = peak_split_a.borrow();
+    let peak_split_b = peak_split_b.borrow();
+
+    // Check: Off-peak impressions get cheaper relative to peak ones with the schedule
+    let ratio_a = peak_split_a.off_peak_cpm() / peak_split_a.peak_cpm();
+    let ratio_b = peak_split_b.off_peak_cpm() / peak_split_b.peak_cpm();
+    assertions::assert_greater(
+        "Variant A (constant) off-peak CPM relative to peak is higher than in variant B (scheduled)",
+        ratio_a,
+        ratio_b,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Lower off-peak bids win fewer of the off-peak impressions
+    assertions::assert_greater(
+        "Variant A (constant) buys a higher share of impressions off-peak than variant B (scheduled)",
+        peak_split_a.off_peak_share(),
+        peak_split_b.off_peak_share(),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,163 @@
+//! This scenario demonstrates in-run budget depletion of campaigns with a hard budget cap.
+//!
+//! A campaign with a budget cap of 20 buys from a single seller whose impressions arrive over the day, bidding:
+//!
+//! - Variant A: Unpaced (fixed pacing of 1.0), relying on the budget cap alone
+//!
+//! - Variant B: Paced to a total budget of 19, a margin below the budget cap
+//!
+//! - Variant C: Paced to a total budget of 20, exactly the budget cap
+//!
+//! Impressions are auctioned in timestamp order, so the unpaced campaign spends its budget on the first
+//! impressions of the day and stops bidding early, buying nothing for the rest of the day. The campaign paced
+//! below the cap spreads its budget over the whole day and, buying the impressions that are cheap for their
+//! value instead of the first ones, obtains more value for about the same spend. The campaign paced exactly at
+//! the cap sees its target met by the spend the cap cut off from the first iteration, so its pacing never
+//! leaves 1.0 and it depletes its budget as early as the unpaced one.
+
+use crate::simulationrun::{Marketplace, SimulationType, CampaignStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, HOURS_PER_DAY};
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "budget_depletion",
+    run,
+    tags: &["fast"],
+});
+
+/// Budget cap of the campaign
+const BUDGET: f64 = 20.0;
+/// Total budget the campaign of variant B is paced to, a margin below the budget cap
+const PACED_BUDGET: f64 = 19.0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(converge_target: ConvergeTarget) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index), capped at the budget within every run
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![converge_target],  // converge_target
+    );
+    campaigns.set_budget_cap(campaign_id, BUDGET);
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Share of the campaign's impressions bought in the second half of the day
+fn second_half_share(campaign_stat: &CampaignStat) -> f64 {
+    let half = (HOURS_PER_DAY / 2.0) as usize;
+    let second_half: f64 = campaign_stat.hour_breakdown[half..].iter().map(|hour_stat| hour_stat.impressions_obtained).sum();
+    second_half / campaign_stat.impressions_obtained
+}
+
+/// Format when the campaign's budget was exhausted
+fn exhausted_at_string(campaign_stat: &CampaignStat) -> String {
+    campaign_stat.budget_exhausted_at.map_or("never".to_string(), |hour| format!("at hour {:.2}", hour))
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A without pacing
+    let simulation_converge_a = prepare_simulationconverge(ConvergeTarget::NONE { default_pacing: 1.0 });
+    let stats_a = simulation_converge_a.run_variant("Running unpaced with budget cap", scenario_name, "unpaced", 100, logger)?;
+
+    // Run variant B paced a margin below the budget cap
+    let simulation_converge_b = prepare_simulationconverge(ConvergeTarget::TOTAL_BUDGET { target_total_budget: PACED_BUDGET });
+    let stats_b = simulation_converge_b.run_variant("Running paced below budget cap", scenario_name, "paced", 100, logger)?;
+
+    // Run variant C paced exactly at the budget cap
+    let simulation_converge_c = prepare_simulationconverge(ConvergeTarget::TOTAL_BUDGET { target_total_budget: BUDGET });
+    let stats_c = simulation_converge_c.run_variant("Running paced at budget cap", scenario_name, "paced_at_cap", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant B (paced)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (paced at cap)", &stats_c, logger, &mut errors);
+
+    let campaign_a = &stats_a.campaign_stats[0];
+    let campaign_b = &stats_b.campaign_stats[0];
+    let campaign_c = &stats_c.campaign_stats[0];
+
+    // Check: The unpaced campaign spends its budget (overspending by at most an impression) early in the day
+    validation::check(
+        campaign_a.budget_exhausted_at.is_some_and(|hour| hour < HOURS_PER_DAY / 2.0)
+            && campaign_a.total_buyer_charge >= BUDGET && campaign_a.total_buyer_charge < BUDGET * 1.01,
+        format!("Variant A (unpaced): Budget exhausted before midday, {}, spending {:.4} of {:.2}", exhausted_at_string(campaign_a), campaign_a.total_buyer_charge, BUDGET),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Once its budget is exhausted, the unpaced campaign stops bidding and buys nothing for the rest of the day
+    let last_hour_a = campaign_a.hour_breakdown.iter().rposition(|hour_stat| hour_stat.impressions_obtained > 0.0).unwrap_or(0);
+    validation::check(
+        campaign_a.budget_capped_auctions > 0 && campaign_a.budget_exhausted_at.is_some_and(|hour| last_hour_a <= hour as usize),
+        format!("Variant A (unpaced): No impressions bought after the budget is exhausted (last hour with impressions {}, {} budget capped auctions)", last_hour_a, campaign_a.budget_capped_auctions),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The paced campaign spreads its delivery over the day
+    validation::check(
+        second_half_share(campaign_b) > 0.4 && second_half_share(campaign_a) == 0.0,
+        format!("Variant B (paced): Buys {:.1}% > 40% of its impressions in the second half of the day (variant A: {:.1}%), budget exhausted {}",
+            second_half_share(campaign_b) * 100.0, second_half_share(campaign_a) * 100.0, exhausted_at_string(campaign_b)),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Pacing obtains more value for about the same budget
+    assertions::assert_greater(
+        &format!("Variant B (paced) obtains more value than variant A (unpaced) for about the same budget (spend {:.4} and {:.4})",
+            campaign_b.total_buyer_charge, campaign_a.total_buyer_charge),
+        campaign_b.total_value,
+        campaign_a.total_value,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Paced exactly at the cap, the campaign converges right away and depletes its budget as early as unpaced
+    validation::check(
+        stats_c.convergence_iterations == 1 && campaign_c.budget_exhausted_at == campaign_a.budget_exhausted_at,
+        format!("Variant C (paced at cap): Converged in {} iteration(s), budget exhausted {} like variant A ({})",
+            stats_c.convergence_iterations, exhausted_at_string(campaign_c), exhausted_at_string(campaign_a)),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
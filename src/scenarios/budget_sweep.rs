@@ -0,0 +1,119 @@
+//! This scenario demonstrates chaining near-identical variants with warm starts.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, the budget of campaign 1 swept over
+//! 20, 22, 24, 26 and 28, each budget a variant:
+//!
+//! - Variant A: Every variant converges from the controllers' initial states
+//!
+//! - Variant B: Variants are chained, each warm started from the final controller states of the previous one
+//!
+//! Neighbouring budgets converge to nearby controller states, so the chained sweep needs fewer iterations
+//! in total while meeting the same targets.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use crate::scenarios::variant_chain::VariantChain;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "budget_sweep",
+    run,
+    tags: &["analysis"],
+});
+
+/// Budgets of campaign 1 swept over, one variant each
+const BUDGETS: [f64; 5] = [20.0, 22.0, 24.0, 26.0, 28.0];
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(budget: f64) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: budget }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Total iterations the variants of a sweep took to converge
+fn total_iterations(stats: &[SimulationStat]) -> usize {
+    stats.iter().map(|stat| stat.convergence_iterations).sum()
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A, converging every budget from scratch
+    let mut stats_a = Vec::new();
+    for budget in BUDGETS {
+        let simulation_converge = prepare_simulationconverge(budget);
+        stats_a.push(simulation_converge.run_variant(&format!("Running budget {} from scratch", budget), scenario_name, &format!("scratch-{}", budget), 100, logger)?);
+    }
+
+    // Run variant B, chaining the budgets
+    let mut chain = VariantChain::default();
+    let mut stats_b = Vec::new();
+    for budget in BUDGETS {
+        let simulation_converge = prepare_simulationconverge(budget);
+        stats_b.push(chain.run_variant(simulation_converge, &format!("Running budget {} warm started from the previous budget", budget), scenario_name, &format!("chained-{}", budget), 100, logger)?);
+    }
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    for (budget, (stat_a, stat_b)) in BUDGETS.iter().zip(stats_a.iter().zip(&stats_b)) {
+        validation::assert_targets_met(&format!("Variant A (scratch) budget {}", budget), stat_a, logger, &mut errors);
+        validation::assert_targets_met(&format!("Variant B (chained) budget {}", budget), stat_b, logger, &mut errors);
+    }
+
+    // Check: The chained sweep takes fewer iterations to converge in total
+    let (iterations_a, iterations_b) = (total_iterations(&stats_a), total_iterations(&stats_b));
+    assertions::assert_greater(
+        "Variant A (scratch) takes more iterations to converge the sweep than variant B (chained)",
+        iterations_a as f64,
+        iterations_b as f64,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
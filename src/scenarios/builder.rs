@@ -0,0 +1,197 @@
+//! Fluent builder for scenarios comparing variants of one marketplace
+//!
+//! Most scenarios repeat the same steps: add campaigns and sellers, create the impressions, run variants of the
+//! marketplace, then validate the results check by check. A Scenario describes these declaratively and runs
+//! them with run_variants, logging every validation as a check (see validation::check):
+//!
+//! ```ignore
+//! fn scenario(scenario_name: &str) -> Scenario {
+//!     Scenario::new(scenario_name)
+//!         .campaign("Campaign 0", CampaignType::MULTIPLICATIVE_PACING, vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }])
+//!         .seller(SellerBuilder::new("HB", SellerType::FIRST_PRICE, 10000).build())
+//!         .variant("capped", |campaigns, _| campaigns.set_max_bid_cpm(0, 9.0))
+//!         .validate_targets_met()
+//!         .validate(|result| (result.variant("capped").campaign_stats[0].capped_bids > 0, "Capped variant has capped bids".to_string()))
+//!         .assert(|result, logger, errors| assertions::assert_within_pct("Capped variant spend", result.variant("capped").campaign_stats[0].total_buyer_charge, 20.0, 1.0, logger, errors))
+//! }
+//!
+//! register_scenario!("bid_cap", scenario, &["fast"]);
+//! ```
+
+use crate::simulationrun::SimulationType;
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::sellers::Sellers;
+use crate::seller_builder::SellerConfig;
+use crate::impressions::ImpressionsParam;
+use crate::variants::{Variant, VariantsResult, run_variants, BASELINE_NAME};
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+
+/// Maximum number of iterations to converge each variant, unless set with Scenario::max_iterations
+const DEFAULT_MAX_ITERATIONS: usize = 100;
+
+/// Change of the campaigns and sellers added to the marketplace
+type ScenarioSetup = Box<dyn Fn(&mut Campaigns, &mut Sellers)>;
+
+/// Validation of the results, logging its checks and collecting the messages of failed ones into errors
+type ScenarioValidation = Box<dyn Fn(&VariantsResult, &mut Logger, &mut Vec<String>)>;
+
+/// Register a scenario built by a function `fn(scenario_name: &str) -> Scenario` in the catalog, with its tags
+#[macro_export]
+macro_rules! register_scenario {
+    ($short_name:expr, $scenario:expr, $tags:expr) => {
+        inventory::submit!($crate::scenarios::ScenarioEntry {
+            short_name: $short_name,
+            run: |scenario_name, logger| ($scenario)(scenario_name).run(logger),
+            tags: $tags,
+        });
+    };
+}
+
+/// Scenario running a base marketplace and variants of it, validated by declared checks
+pub struct Scenario {
+    scenario_name: String,
+    campaigns: Vec<(String, CampaignType, Vec<ConvergeTarget>)>,
+    sellers: Vec<SellerConfig>,
+    setups: Vec<ScenarioSetup>,
+    impressions_params: Option<ImpressionsParam>,
+    simulation_type: SimulationType,
+    max_iterations: usize,
+    variants: Vec<Variant>,
+    validate_targets: bool,
+    validations: Vec<ScenarioValidation>,
+}
+
+impl Scenario {
+    /// Create an empty scenario, with standard auctions and the impressions most scenarios use
+    pub fn new(scenario_name: &str) -> Self {
+        Self {
+            scenario_name: scenario_name.to_string(),
+            campaigns: Vec::new(),
+            sellers: Vec::new(),
+            setups: Vec::new(),
+            impressions_params: None,
+            simulation_type: SimulationType::Standard,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            variants: Vec::new(),
+            validate_targets: false,
+            validations: Vec::new(),
+        }
+    }
+
+    /// Add a campaign (see Campaigns::add), IDs follow the order campaigns are added in
+    pub fn campaign(mut self, campaign_name: &str, campaign_type: CampaignType, converge_targets: Vec<ConvergeTarget>) -> Self {
+        self.campaigns.push((campaign_name.to_string(), campaign_type, converge_targets));
+        self
+    }
+
+    /// Add a seller (see Sellers::add_from_config and SellerBuilder), IDs follow the order sellers are added in
+    pub fn seller(mut self, seller_config: SellerConfig) -> Self {
+        self.sellers.push(seller_config);
+        self
+    }
+
+    /// Change the base marketplace once all campaigns and sellers are added, e.g. to set a campaign's bid cap
+    /// Scenario files (see scenario_config) apply the settings of their campaigns with it
+    #[allow(dead_code)]
+    pub fn setup<F: Fn(&mut Campaigns, &mut Sellers) + 'static>(mut self, setup: F) -> Self {
+        self.setups.push(Box::new(setup));
+        self
+    }
+
+    /// Set the impressions parameters, lognormal values around 10.0 by default
+    pub fn impressions(mut self, impressions_params: ImpressionsParam) -> Self {
+        self.impressions_params = Some(impressions_params);
+        self
+    }
+
+    /// Set the simulation type, Standard by default
+    /// Scenario files (see scenario_config) set it from their simulation type
+    #[allow(dead_code)]
+    pub fn simulation_type(mut self, simulation_type: SimulationType) -> Self {
+        self.simulation_type = simulation_type;
+        self
+    }
+
+    /// Set the maximum number of iterations to converge each variant
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Add a variant of the base marketplace (see Variant::new), run after the baseline on its impressions
+    pub fn variant<F: Fn(&mut Campaigns, &mut Sellers) + 'static>(mut self, name: &str, delta: F) -> Self {
+        self.variants.push(Variant::new(name, delta));
+        self
+    }
+
+    /// Validate that every campaign meets its targets in the baseline and every variant (see validation::assert_targets_met)
+    pub fn validate_targets_met(mut self) -> Self {
+        self.validate_targets = true;
+        self
+    }
+
+    /// Add a validation of the results, returning whether it passed and the message of its check
+    /// Validations are checked in the order they are added, after the targets
+    pub fn validate<F: Fn(&VariantsResult) -> (bool, String) + 'static>(mut self, validation: F) -> Self {
+        self.validations.push(Box::new(move |result, logger, errors| {
+            let (passed, msg) = validation(result);
+            validation::check(passed, msg, logger, errors);
+        }));
+        self
+    }
+
+    /// Add a validation of the results made of assertions (see assertions.rs), which log their own checks, e.g.
+    /// `.assert(|result, logger, errors| assertions::assert_greater("...", actual, other, logger, errors))`
+    /// Checked in order with the validations added with validate
+    pub fn assert<F: Fn(&VariantsResult, &mut Logger, &mut Vec<String>) + 'static>(mut self, assertion: F) -> Self {
+        self.validations.push(Box::new(assertion));
+        self
+    }
+
+    /// Create the campaigns and sellers of the base marketplace
+    fn build_base(&self) -> (Campaigns, Sellers) {
+        let mut campaigns = Campaigns::new();
+        for (campaign_name, campaign_type, converge_targets) in &self.campaigns {
+            campaigns.add(campaign_name.clone(), campaign_type.clone(), converge_targets.clone());
+        }
+        let mut sellers = Sellers::new();
+        for seller_config in &self.sellers {
+            sellers.add_from_config(seller_config.clone());
+        }
+        for setup in &self.setups {
+            setup(&mut campaigns, &mut sellers);
+        }
+        (campaigns, sellers)
+    }
+
+    /// Run the baseline and the variants, log the comparison table and check the validations
+    ///
+    /// # Returns
+    /// Returns Ok if every validation passed, or an error with the messages of the failed ones (or the error of a
+    /// variant that failed to run)
+    pub fn run(mut self, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+        let impressions_params = self.impressions_params.take().unwrap_or_else(|| ImpressionsParam::new(
+            utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+            utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+        ));
+        let result = run_variants(|| self.build_base(), &impressions_params, self.simulation_type.clone(), &self.variants, &self.scenario_name, self.max_iterations, logger)?;
+        result.printout(logger, LogEvent::Scenario);
+
+        logln!(logger, LogEvent::Scenario, "");
+
+        let mut errors: Vec<String> = Vec::new();
+        if self.validate_targets {
+            validation::assert_targets_met(BASELINE_NAME, &result.baseline, logger, &mut errors);
+            for (variant_name, stats) in &result.variants {
+                validation::assert_targets_met(variant_name, stats, logger, &mut errors);
+            }
+        }
+        for validate in &self.validations {
+            validate(&result, logger, &mut errors);
+        }
+        validation::scenario_result(&self.scenario_name, errors)
+    }
+}
@@ -0,0 +1,203 @@
+//! This scenario demonstrates campaigns launching and ending mid-flight in a multi-period run.
+//!
+//! Impressions are spread over a 9-day flight paced day by day. A small campaign (budget target for the whole
+//! flight) runs throughout, next to a large campaign with a budget target:
+//!
+//! - Variant A: The large campaign runs the whole flight
+//!
+//! - Variant B: The large campaign launches on day 3 and ends after day 6, with its whole budget in those days
+//!
+//! The launched campaign buys nothing outside its active days. It ramps up from its initial pacing, which
+//! overspends its launch day, and its following days close in on the share of the budget that is left. While it
+//! is active it takes impressions from the small campaign, which raises its pacing to keep spending its share and
+//! buys fewer, more expensive impressions. Once it ends, the small campaign re-equilibrates, buying more
+//! impressions again at a lower CPM.
+
+use crate::simulationrun::{Marketplace, SimulationType, CampaignStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::{SimulationConverge, PeriodsStat};
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::ops::Range;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "campaign_entry_exit",
+    run,
+    tags: &["fast", "multi_day"],
+});
+
+/// Number of days of the flight
+const NUM_DAYS: usize = 10;
+/// Days the large campaign is active in variant B
+const LARGE_CAMPAIGN_DAYS: Range<usize> = 3..7;
+/// Budget of the small campaign over the flight
+const SMALL_BUDGET: f64 = 50.0;
+/// Budget of the large campaign over the flight
+const LARGE_BUDGET: f64 = 48.0;
+/// Tolerance of the flight's targets, pacing isn't converged as within a single period
+const FLIGHT_TOLERANCE: f64 = 0.05;
+/// Tolerance of the launched campaign's flight target, it has fewer days to make up for its launch day
+const LAUNCHED_FLIGHT_TOLERANCE: f64 = 0.1;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(large_campaign_days: Option<Range<usize>>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Small campaign".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: SMALL_BUDGET }],  // converge_target
+    );
+    let large_campaign_id = campaigns.add(
+        "Large campaign".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: LARGE_BUDGET }],  // converge_target
+    );
+    if let Some(large_campaign_days) = large_campaign_days {
+        campaigns.set_active_days(large_campaign_id, large_campaign_days.start, Some(large_campaign_days.end));
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        30000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, spreading impressions evenly over the days of the flight
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_days(vec![1.0; NUM_DAYS]);
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Statistics of a campaign in each period
+fn campaign_period_stats(stats: &PeriodsStat, campaign_id: usize) -> Vec<&CampaignStat> {
+    stats.period_stats.iter().map(|period_stats| &period_stats.campaign_stats[campaign_id]).collect()
+}
+
+/// Average impressions per day and CPM of a campaign over the given days
+fn impressions_and_cpm(period_stats: &[&CampaignStat], days: Range<usize>) -> (f64, f64) {
+    let num_days = days.len() as f64;
+    let (impressions, spend) = period_stats[days].iter()
+        .fold((0.0, 0.0), |(impressions, spend), campaign_stat| (impressions + campaign_stat.impressions_obtained, spend + campaign_stat.total_buyer_charge));
+    (impressions / num_days, spend / impressions * 1000.0)
+}
+
+/// Check that a campaign meets its flight target within the tolerance
+fn check_flight_target(label: &str, stats: &PeriodsStat, campaign_id: usize, tolerance: f64, logger: &mut Logger, errors: &mut Vec<String>) {
+    let target = &stats.campaign_targets[campaign_id][0];
+    assertions::assert_within_pct(&format!("{} campaign {} meets its flight target [{}]", label, campaign_id, target.name), target.actual, target.target, tolerance * 100.0, logger, errors);
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with the large campaign running the whole flight
+    let mut simulation_converge_a = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_periods_variant("Running with the large campaign over the whole flight", scenario_name, "whole_flight", logger);
+
+    // Run variant B with the large campaign launching and ending mid-flight
+    let mut simulation_converge_b = prepare_simulationconverge(Some(LARGE_CAMPAIGN_DAYS));
+    let stats_b = simulation_converge_b.run_periods_variant("Running with the large campaign launching and ending mid-flight", scenario_name, "entry_exit", logger);
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    check_flight_target("Variant A (whole flight)", &stats_a, 0, FLIGHT_TOLERANCE, logger, &mut errors);
+    check_flight_target("Variant A (whole flight)", &stats_a, 1, FLIGHT_TOLERANCE, logger, &mut errors);
+    check_flight_target("Variant B (entry and exit)", &stats_b, 0, FLIGHT_TOLERANCE, logger, &mut errors);
+    check_flight_target("Variant B (entry and exit)", &stats_b, 1, LAUNCHED_FLIGHT_TOLERANCE, logger, &mut errors);
+
+    // Check: The large campaign buys nothing outside its active days
+    let large_b = campaign_period_stats(&stats_b, 1);
+    let inactive_impressions: f64 = (0..NUM_DAYS).filter(|day| !LARGE_CAMPAIGN_DAYS.contains(day)).map(|day| large_b[day].impressions_obtained).sum();
+    validation::check(
+        inactive_impressions == 0.0,
+        format!("Variant B (entry and exit): Large campaign buys {} impressions outside days {}-{}", inactive_impressions, LARGE_CAMPAIGN_DAYS.start, LARGE_CAMPAIGN_DAYS.end - 1),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The large campaign ramps up from its initial pacing, overspending its launch day, and its last day
+    // spends closer to its daily share
+    let daily_share = LARGE_BUDGET / LARGE_CAMPAIGN_DAYS.len() as f64;
+    let launch_spend = large_b[LARGE_CAMPAIGN_DAYS.start].total_buyer_charge;
+    let last_spend = large_b[LARGE_CAMPAIGN_DAYS.end - 1].total_buyer_charge;
+    assertions::assert_greater(
+        &format!("Variant B (entry and exit): Large campaign overspends its daily share of {:.2} by more than 10% on its launch day", daily_share),
+        launch_spend,
+        daily_share * 1.1,
+        logger,
+        &mut errors,
+    );
+    assertions::assert_greater(
+        &format!("Variant B (entry and exit): Large campaign's launch day overspend is larger than its last day's deviation from the daily share (last day spend {:.2})", last_spend),
+        launch_spend - daily_share,
+        (last_spend - daily_share).abs(),
+        logger,
+        &mut errors,
+    );
+
+    // Check: While the large campaign is active, the small campaign buys fewer impressions than before it launched
+    // and after it ended, and more expensive ones than after it ended (the days before include the small
+    // campaign's own ramp-up from its initial pacing)
+    let small_b = campaign_period_stats(&stats_b, 0);
+    let (impressions_before, cpm_before) = impressions_and_cpm(&small_b, 0..LARGE_CAMPAIGN_DAYS.start);
+    let (impressions_during, cpm_during) = impressions_and_cpm(&small_b, LARGE_CAMPAIGN_DAYS);
+    let (impressions_after, cpm_after) = impressions_and_cpm(&small_b, LARGE_CAMPAIGN_DAYS.end..NUM_DAYS);
+    assertions::assert_greater(
+        "Variant B (entry and exit): Small campaign buys more impressions per day before the large campaign launches than while it is active",
+        impressions_before,
+        impressions_during,
+        logger,
+        &mut errors,
+    );
+    assertions::assert_greater(
+        "Variant B (entry and exit): Small campaign buys more impressions per day after the large campaign ends than while it is active",
+        impressions_after,
+        impressions_during,
+        logger,
+        &mut errors,
+    );
+    assertions::assert_greater(
+        &format!("Variant B (entry and exit): Small campaign pays a higher CPM while the large campaign is active than after it ends (before {:.4})", cpm_before),
+        cpm_during,
+        cpm_after,
+        logger,
+        &mut errors,
+    );
+
+    // Check: The small campaign faces more competition during the large campaign's days than with the large
+    // campaign spread over the whole flight
+    let small_a = campaign_period_stats(&stats_a, 0);
+    let (_, cpm_during_a) = impressions_and_cpm(&small_a, LARGE_CAMPAIGN_DAYS);
+    assertions::assert_greater(
+        &format!("Small campaign's CPM on days {}-{} is higher with the large campaign concentrated on them than in variant A", LARGE_CAMPAIGN_DAYS.start, LARGE_CAMPAIGN_DAYS.end - 1),
+        cpm_during,
+        cpm_during_a,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,122 @@
+//! This scenario demonstrates a max margin bidder observing the competition only through its wins and losses.
+//!
+//! All variants spend the same budget:
+//!
+//! - Variant A: Multiplicative pacing, bidding truthfully (no win rate prediction)
+//!
+//! - Variant B: Max margin bidding on the provided win rate prediction
+//!
+//! - Variant C: The same max margin bidder in censored competition mode, its win rate prediction estimated
+//!   from the wins and losses of its own bids accumulated across iterations
+//!
+//! The censored bidder never sees a predicted sigmoid nor the competing bids, yet its estimated prediction
+//! should still capture enough of the competition to obtain more value per spend than truthful bidding.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "censored_competition",
+    run,
+    tags: &[],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(campaign_type: CampaignType, censored_competition: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        campaign_type,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+    if censored_competition {
+        campaigns.set_censored_competition(campaign_id);
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Value obtained per unit of spend by the single campaign
+fn value_per_spend(stats: &SimulationStat) -> f64 {
+    let campaign = &stats.campaign_stats[0];
+    if campaign.total_buyer_charge > 0.0 {
+        campaign.total_value / campaign.total_buyer_charge
+    } else {
+        0.0
+    }
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with truthful bidding
+    let simulation_converge_a = prepare_simulationconverge(CampaignType::MULTIPLICATIVE_PACING, false);
+    let stats_a = simulation_converge_a.run_variant("Running with truthful bidding", scenario_name, "truthful", 100, logger)?;
+
+    // Run variant B with max margin bidding on the provided win rate prediction
+    let simulation_converge_b = prepare_simulationconverge(CampaignType::MAX_MARGIN, false);
+    let stats_b = simulation_converge_b.run_variant("Running with provided win rate prediction", scenario_name, "provided", 100, logger)?;
+
+    // Run variant C with max margin bidding on the win rate estimated from censored outcomes
+    let simulation_converge_c = prepare_simulationconverge(CampaignType::MAX_MARGIN, true);
+    let stats_c = simulation_converge_c.run_variant("Running with censored competition", scenario_name, "censored", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (truthful)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (provided)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (censored)", &stats_c, logger, &mut errors);
+
+    let value_per_spend_a = value_per_spend(&stats_a);
+    let value_per_spend_b = value_per_spend(&stats_b);
+    let value_per_spend_c = value_per_spend(&stats_c);
+
+    logln!(logger, LogEvent::Scenario, "Value per spend: truthful {:.4}, provided prediction {:.4}, censored competition {:.4}",
+        value_per_spend_a, value_per_spend_b, value_per_spend_c);
+
+    // Check: Win rate estimated from censored outcomes obtains more value per spend than truthful bidding
+    assertions::assert_greater(
+        "Variant C (censored) has higher value per spend than variant A (truthful)",
+        value_per_spend_c,
+        value_per_spend_a,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
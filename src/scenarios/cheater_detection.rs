@@ -0,0 +1,126 @@
+//! This scenario quantifies how much last look abuse is worth under enforcement.
+//!
+//! The cheater bids just above the minimum winning bid. With enforcement it is detected in a share of
+//! auctions and excluded from them, so it has to buy more expensive impressions to spend its budget.
+//!
+//! - Variant A: Max margin bidding (honest reference)
+//!
+//! - Variant B: Cheater that is never detected
+//!
+//! - Variant C: Cheater detected in 50% of auctions
+//!
+//! - Variant D: Cheater detected in 90% of auctions
+//!
+//! All campaigns spend the same budget. The value of cheating is the total value obtained on top of
+//! the honest max margin bidder, and it should shrink as the detection probability grows.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "cheater_detection",
+    run,
+    tags: &["fast"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(campaign_type: CampaignType) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        campaign_type,  // campaign_type - max margin or cheater with given detection probability
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 10.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(1.0, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 2.0),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with honest max margin bidding
+    let simulation_converge_a = prepare_simulationconverge(CampaignType::MAX_MARGIN);
+    let stats_a = simulation_converge_a.run_variant("Running with max margin bidding", scenario_name, "max-margin", 100, logger)?;
+
+    // Run variants B, C and D with cheater bidding under increasing enforcement
+    let simulation_converge_b = prepare_simulationconverge(CampaignType::CHEATER);
+    let stats_b = simulation_converge_b.run_variant("Running with undetected cheater bidding", scenario_name, "cheater", 100, logger)?;
+
+    let simulation_converge_c = prepare_simulationconverge(CampaignType::CHEATER_DETECTABLE { detection_probability: 0.5 });
+    let stats_c = simulation_converge_c.run_variant("Running with cheater bidding, detection probability 0.5", scenario_name, "cheater-detection-50", 100, logger)?;
+
+    let simulation_converge_d = prepare_simulationconverge(CampaignType::CHEATER_DETECTABLE { detection_probability: 0.9 });
+    let stats_d = simulation_converge_d.run_variant("Running with cheater bidding, detection probability 0.9", scenario_name, "cheater-detection-90", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Value of cheating is the value obtained on top of the honest bidder with the same budget
+    let honest_value = stats_a.overall_stat.total_value;
+    let cheating_value_b = stats_b.overall_stat.total_value - honest_value;
+    let cheating_value_c = stats_c.overall_stat.total_value - honest_value;
+    let cheating_value_d = stats_d.overall_stat.total_value - honest_value;
+    logln!(logger, LogEvent::Scenario, "Value of cheating over max margin: undetected {:.2}, detection 0.5 {:.2}, detection 0.9 {:.2}",
+        cheating_value_b, cheating_value_c, cheating_value_d);
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Undetected cheater obtains more value than the honest bidder
+    assertions::assert_greater(
+        "Variant B (undetected cheater) obtained value is greater than Variant A (Max margin)",
+        stats_b.overall_stat.total_value,
+        honest_value,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Value of cheating shrinks with detection probability
+    assertions::assert_greater(
+        "Value of cheating is greater undetected (variant B) than with detection probability 0.5 (variant C)",
+        cheating_value_b,
+        cheating_value_c,
+        logger,
+        &mut errors,
+    );
+    assertions::assert_greater(
+        "Value of cheating is greater with detection probability 0.5 (variant C) than 0.9 (variant D)",
+        cheating_value_c,
+        cheating_value_d,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
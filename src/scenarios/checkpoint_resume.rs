@@ -0,0 +1,130 @@
+//! This scenario demonstrates resuming an interrupted convergence run from its last checkpoint.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller:
+//!
+//! - Variant A: Converges uninterrupted, without checkpoints
+//!
+//! - Variant B: Checkpoints every 2 iterations and is interrupted after 5 iterations (it reaches its maximum
+//!   iterations), then run again, resuming from the checkpoint
+//!
+//! The seed and the iteration fix all random draws of the convergence loop, so the resumed variant converges
+//! in the same iterations and to the same results as the uninterrupted one.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "checkpoint_resume",
+    run,
+    tags: &["fast", "controllers"],
+});
+
+/// Iterations between checkpoints in variant B
+const CHECKPOINT_INTERVAL: usize = 2;
+/// Iterations variant B runs before it's interrupted
+const INTERRUPTED_AFTER_ITERATIONS: usize = 5;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(checkpoint_interval: usize) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace).with_checkpoints(checkpoint_interval)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A uninterrupted
+    let simulation_converge_a = prepare_simulationconverge(0);
+    let stats_a = simulation_converge_a.run_variant("Running uninterrupted", scenario_name, "uninterrupted", 100, logger)?;
+
+    // Run variant B until it's interrupted, then again resuming from its checkpoint
+    let simulation_converge_b = prepare_simulationconverge(CHECKPOINT_INTERVAL);
+    let interrupted = simulation_converge_b.run_variant("Running with checkpoints until interrupted", scenario_name, "checkpointed", INTERRUPTED_AFTER_ITERATIONS, logger).is_err();
+    let first_resumed_iteration = Rc::new(Cell::new(None));
+    let first_resumed_iteration_hook = first_resumed_iteration.clone();
+    simulation_converge_b.hooks.borrow_mut().on_iteration_start(move |iteration, _logger| {
+        if first_resumed_iteration_hook.get().is_none() {
+            first_resumed_iteration_hook.set(Some(iteration));
+        }
+    });
+    let stats_b = simulation_converge_b.run_variant("Resuming from the checkpoint", scenario_name, "checkpointed", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (uninterrupted)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (resumed)", &stats_b, logger, &mut errors);
+
+    // Check: Variant B was interrupted before converging and resumed after its last iteration
+    validation::check(
+        interrupted && first_resumed_iteration.get() == Some(INTERRUPTED_AFTER_ITERATIONS + 1),
+        format!("Variant B (resumed) was interrupted after {} iterations and resumed at iteration {}", INTERRUPTED_AFTER_ITERATIONS, first_resumed_iteration.get().map_or("none".to_string(), |iteration| iteration.to_string())),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The resumed variant converges as the uninterrupted one
+    validation::check(
+        stats_b.convergence_iterations == stats_a.convergence_iterations,
+        format!("Variant B (resumed) converges in the iterations of variant A (uninterrupted): {} = {}", stats_b.convergence_iterations, stats_a.convergence_iterations),
+        logger,
+        &mut errors,
+    );
+    for (campaign_id, (campaign_a, campaign_b)) in stats_a.campaign_stats.iter().zip(&stats_b.campaign_stats).enumerate() {
+        validation::check(
+            campaign_b.impressions_obtained == campaign_a.impressions_obtained && campaign_b.total_buyer_charge == campaign_a.total_buyer_charge,
+            format!("Variant B (resumed) campaign {} obtains the results of variant A (uninterrupted): impressions {:.0} = {:.0}, spend {:.4} = {:.4}",
+                campaign_id, campaign_b.impressions_obtained, campaign_a.impressions_obtained, campaign_b.total_buyer_charge, campaign_a.total_buyer_charge),
+            logger,
+            &mut errors,
+        );
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
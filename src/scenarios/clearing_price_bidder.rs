@@ -0,0 +1,125 @@
+//! This scenario benchmarks a naive clearing price feedback bidder against max margin bidding.
+//!
+//! Two sellers with different floors make the clearing prices differ per seller.
+//!
+//! - Variant A: Max margin bidding on the provided win rate prediction (optimal bidding)
+//!
+//! - Variant B: Bidding 5% above the moving average of clearing prices observed per seller,
+//!   never above the paced value
+//!
+//! Both campaigns spend the same budget. The feedback bidder only knows the average clearing price
+//! of each seller, so it overpays on cheap impressions and loses expensive valuable ones, obtaining
+//! less value per spend than max margin bidding.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignClearingPrice, CampaignTargetTotalBudget};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "clearing_price_bidder",
+    run,
+    tags: &["floors"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(clearing_price_feedback: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    if clearing_price_feedback {
+        campaigns.add_advanced(Box::new(CampaignClearingPrice::new(
+            "Campaign 0".to_string(),  // campaign_name
+            Box::new(CampaignTargetTotalBudget { total_budget_target: 20.0 }),  // converge_target
+            0.05,  // markup
+        )));
+    } else {
+        campaigns.add(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MAX_MARGIN,  // campaign_type
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+        );
+    }
+
+    // Add sellers (ID is automatically set to match Vec index)
+    sellers.add(
+        "Low floors".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        5000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+    sellers.add(
+        "High floors".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        5000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.6, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Value obtained per unit of spend by the single campaign
+fn value_per_spend(stats: &SimulationStat) -> f64 {
+    let campaign = &stats.campaign_stats[0];
+    if campaign.total_buyer_charge > 0.0 {
+        campaign.total_value / campaign.total_buyer_charge
+    } else {
+        0.0
+    }
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with max margin bidding
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running with max margin bidding", scenario_name, "max-margin", 100, logger)?;
+
+    // Run variant B with clearing price feedback bidding
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with clearing price feedback bidding", scenario_name, "clearing-price", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+    let value_per_spend_a = value_per_spend(&stats_a);
+    let value_per_spend_b = value_per_spend(&stats_b);
+
+    // Check: Max margin bidding obtains more value per spend than clearing price feedback bidding
+    assertions::assert_greater(
+        "Variant A (max margin) has higher value per spend than variant B (clearing price feedback)",
+        value_per_spend_a,
+        value_per_spend_b,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Clearing price feedback campaign spends its budget like the max margin one
+    let spend_b = stats_b.campaign_stats[0].total_buyer_charge;
+    assertions::assert_within_pct("Variant B (clearing price feedback) spends its budget", spend_b, 20.0, 5.0, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
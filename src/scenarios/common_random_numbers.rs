@@ -0,0 +1,168 @@
+//! This scenario demonstrates common random numbers, variants sharing the same impressions.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, and the budget campaign's budget
+//! is raised from 20 to 22:
+//!
+//! - Variant A: Budget 20, its impressions generated as usual and shared with variant B
+//!
+//! - Variant B: Budget 22 on the impressions of variant A (Marketplace::new_shared)
+//!
+//! - Variant C: Budget 22 on impressions drawn with another seed, as in another iteration of the scenario
+//!
+//! Variant B sees exactly the values, competition and floors of variant A, so the difference between them is
+//! only due to the budget. Raising the budget of one campaign makes the impressions campaign pay more per
+//! impression, while the comparison with variant C is confounded by the different draws.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{Impressions, ImpressionsParam};
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils::{self, RAND_SEED};
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "common_random_numbers",
+    run,
+    tags: &["fast", "analysis"],
+});
+
+/// Mixed into RAND_SEED to draw the impressions of variant C
+const REDRAW_SEED: u64 = 21212;
+
+/// Base value, competing bid and floor of every auction
+type Draws = Vec<(f64, f64, f64)>;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Offers the given impressions, or generates them when there are none
+/// Returns the simulation converge and the draws of the latest iteration
+fn prepare_simulationconverge(target_total_budget: f64, impressions: Option<Rc<Impressions>>) -> (SimulationConverge, Rc<RefCell<Draws>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions (shared or generated)
+    let marketplace = match impressions {
+        Some(impressions) => Marketplace::new_shared(campaigns, sellers, impressions, SimulationType::Standard),
+        None => {
+            let impressions_params = ImpressionsParam::new(
+                utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+                utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+            );
+            Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard)
+        }
+    };
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Record the draws of every auction, reset at the start of every iteration
+    let draws = Rc::new(RefCell::new(Vec::new()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let draws_reset = draws.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            draws_reset.borrow_mut().clear();
+        });
+        let draws_push = draws.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            let competing_bid = event.impression.competition.as_ref().map_or(0.0, |competition| competition.bid_cpm);
+            draws_push.borrow_mut().push((event.impression.base_impression_value, competing_bid, event.impression.floor_cpm));
+        });
+    }
+
+    (simulation_converge, draws)
+}
+
+/// Price per impression paid by the impressions campaign
+fn impressions_campaign_cpm(stats: &SimulationStat) -> f64 {
+    let campaign = &stats.campaign_stats[0];
+    campaign.total_buyer_charge / campaign.impressions_obtained * 1000.0
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with the original budget, generating the impressions
+    let (simulation_converge_a, draws_a) = prepare_simulationconverge(20.0, None);
+    let shared_impressions = simulation_converge_a.marketplace.shared_impressions();
+    let stats_a = simulation_converge_a.run_variant("Running with budget 20", scenario_name, "baseline", 100, logger)?;
+
+    // Run variant B with the raised budget on the impressions of variant A
+    let (simulation_converge_b, draws_b) = prepare_simulationconverge(22.0, Some(Rc::clone(&shared_impressions)));
+    let stats_b = simulation_converge_b.run_variant("Running with budget 22 on shared impressions", scenario_name, "shared", 100, logger)?;
+
+    // Run variant C with the raised budget on impressions drawn with another seed
+    let rand_seed = RAND_SEED.replace(RAND_SEED.get() ^ REDRAW_SEED);
+    let (simulation_converge_c, draws_c) = prepare_simulationconverge(22.0, None);
+    RAND_SEED.set(rand_seed);
+    let stats_c = simulation_converge_c.run_variant("Running with budget 22 on redrawn impressions", scenario_name, "redrawn", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (baseline)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (shared)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (redrawn)", &stats_c, logger, &mut errors);
+
+    // Check: Variant B draws exactly what variant A does, variant C doesn't
+    let draws_a = draws_a.borrow();
+    validation::check(
+        Rc::ptr_eq(&shared_impressions, &simulation_converge_b.marketplace.impressions) && *draws_a == *draws_b.borrow(),
+        format!("Variant B (shared) auctions the same impressions with the same draws as variant A (baseline): {} auctions", draws_a.len()),
+        logger,
+        &mut errors,
+    );
+    validation::check(
+        *draws_a != *draws_c.borrow(),
+        "Variant C (redrawn) auctions impressions with different draws than variant A (baseline)".to_string(),
+        logger,
+        &mut errors,
+    );
+
+    let cpm_a = impressions_campaign_cpm(&stats_a);
+    let cpm_b = impressions_campaign_cpm(&stats_b);
+    let cpm_c = impressions_campaign_cpm(&stats_c);
+    logln!(logger, LogEvent::Scenario, "Effect of the raised budget on the impressions campaign's CPM: {:+.4} on shared impressions, {:+.4} on redrawn impressions",
+        cpm_b - cpm_a, cpm_c - cpm_a);
+
+    // Check: On the same impressions, more budget of the competing campaign makes impressions more expensive
+    assertions::assert_greater(
+        "Variant B (shared) impressions campaign pays a higher CPM than variant A (baseline)",
+        cpm_b,
+        cpm_a,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,186 @@
+//! This scenario checks the competing bid distributions exposed by the competition generators.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, its competition generated by:
+//!
+//! - Variant A: CompetitionGeneratorLogNormal
+//!
+//! - Variant B: CompetitionGeneratorCorrelated (correlation 0.8 with the base value)
+//!
+//! - Variant C: CompetitionGeneratorTimeVarying (sinusoidal intensity around CompetitionGeneratorLogNormal)
+//!
+//! - Variant D: CompetitionGeneratorEmpirical (samples from data/competition_bids.csv)
+//!
+//! For the highest campaign bid of every auction, the CDF of the competing bid distribution gives the exact
+//! probability of beating the competing bid, and expected_competing_bid_below the expected competing bid when
+//! it is beaten. Summed over the auctions, both should match what happened in the final iteration.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::{CompetitionGeneratorTrait, CompetitionGeneratorLogNormal, CompetitionGeneratorCorrelated, CompetitionGeneratorTimeVarying, CompetitionGeneratorEmpirical, CompetitionIntensityCurve, ImpressionCompetition};
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "competing_bid_distribution",
+    run,
+    tags: &[],
+});
+
+/// CSV file with the competing bid samples of variant D
+const COMPETITION_SAMPLES_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/competition_bids.csv");
+
+/// Highest campaign bid of an auction, with what the competition generator was given and generated
+struct AuctionSample {
+    base_impression_value: f64,
+    time_of_day: f64,
+    competition: ImpressionCompetition,
+    highest_bid: f64,
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the auction samples of the latest iteration
+fn prepare_simulationconverge(competition_generator: Box<dyn CompetitionGeneratorTrait>) -> (SimulationConverge, Rc<RefCell<Vec<AuctionSample>>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        competition_generator,  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Record the highest campaign bid of every auction, reset at the start of every iteration
+    let auction_samples = Rc::new(RefCell::new(Vec::new()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let auction_samples_reset = auction_samples.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            auction_samples_reset.borrow_mut().clear();
+        });
+        let auction_samples_push = auction_samples.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            let highest_bid = event.bids.iter().map(|(_, bid)| *bid).fold(f64::NEG_INFINITY, f64::max);
+            if let (Some(competition), true) = (&event.impression.competition, highest_bid.is_finite()) {
+                auction_samples_push.borrow_mut().push(AuctionSample {
+                    base_impression_value: event.impression.base_impression_value,
+                    time_of_day: event.impression.time_of_day(),
+                    competition: competition.clone(),
+                    highest_bid,
+                });
+            }
+        });
+    }
+
+    (simulation_converge, auction_samples)
+}
+
+/// Check that the generator's distributions predict the competing bids beaten by the highest campaign bids
+fn check_distributions(label: &str, competition_generator: &dyn CompetitionGeneratorTrait, auction_samples: &[AuctionSample], logger: &mut Logger, errors: &mut Vec<String>) {
+    let mut expected_beaten = 0.0;
+    let mut expected_beaten_bids = 0.0;
+    let mut beaten = 0.0;
+    let mut beaten_bids = 0.0;
+    for sample in auction_samples {
+        let distribution = match competition_generator.competing_bid_distribution(sample.base_impression_value, sample.time_of_day, &sample.competition) {
+            Some(distribution) => distribution,
+            None => {
+                validation::check(false, format!("{} exposes a competing bid distribution", label), logger, errors);
+                return;
+            }
+        };
+        let win_probability = distribution.cdf(sample.highest_bid);
+        expected_beaten += win_probability;
+        expected_beaten_bids += win_probability * distribution.expected_competing_bid_below(sample.highest_bid);
+        if sample.competition.bid_cpm <= sample.highest_bid {
+            beaten += 1.0;
+            beaten_bids += sample.competition.bid_cpm;
+        }
+    }
+
+    // Check: Win probabilities from the CDF add up to the competing bids beaten
+    assertions::assert_within_pct(&format!("{} competing bids beaten by the highest campaign bids match the CDF", label), beaten, expected_beaten, 5.0, logger, errors);
+
+    // Check: Expected competing bids below the highest campaign bids add up to the beaten competing bids
+    assertions::assert_within_pct(&format!("{} sum of beaten competing bids matches the expectation", label), beaten_bids, expected_beaten_bids, 5.0, logger, errors);
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Each variant's generator, created once for the seller and once more to query the distributions
+    let lognormal = || CompetitionGeneratorLogNormal::new(10.0);
+    let correlated = || CompetitionGeneratorCorrelated::new(10.0, 3.0, 10.0, 3.0, 0.8);
+    let time_varying = || CompetitionGeneratorTimeVarying::new(
+        CompetitionGeneratorLogNormal::new(10.0),
+        CompetitionIntensityCurve::SINUSOIDAL { amplitude: 0.5, peak_hour: 20.0 },
+    );
+
+    // Run variant A with lognormal competition
+    let (simulation_converge_a, samples_a) = prepare_simulationconverge(lognormal());
+    let stats_a = simulation_converge_a.run_variant("Running with lognormal competition", scenario_name, "lognormal", 100, logger)?;
+
+    // Run variant B with value-correlated competition
+    let (simulation_converge_b, samples_b) = prepare_simulationconverge(correlated());
+    let stats_b = simulation_converge_b.run_variant("Running with value-correlated competition", scenario_name, "correlated", 100, logger)?;
+
+    // Run variant C with time-varying competition
+    let (simulation_converge_c, samples_c) = prepare_simulationconverge(time_varying());
+    let stats_c = simulation_converge_c.run_variant("Running with time-varying competition", scenario_name, "time_varying", 100, logger)?;
+
+    // Run variant D with empirical competition
+    let (simulation_converge_d, samples_d) = prepare_simulationconverge(CompetitionGeneratorEmpirical::from_csv(COMPETITION_SAMPLES_PATH)?);
+    let stats_d = simulation_converge_d.run_variant("Running with empirical competition", scenario_name, "empirical", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (lognormal)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (correlated)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (time-varying)", &stats_c, logger, &mut errors);
+    validation::assert_targets_met("Variant D (empirical)", &stats_d, logger, &mut errors);
+
+    check_distributions("Variant A (lognormal)", lognormal().as_ref(), &samples_a.borrow(), logger, &mut errors);
+    check_distributions("Variant B (correlated)", correlated().as_ref(), &samples_b.borrow(), logger, &mut errors);
+    check_distributions("Variant C (time-varying)", time_varying().as_ref(), &samples_c.borrow(), logger, &mut errors);
+    check_distributions("Variant D (empirical)", CompetitionGeneratorEmpirical::from_csv(COMPETITION_SAMPLES_PATH)?.as_ref(), &samples_d.borrow(), logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,175 @@
+//! This scenario demonstrates a seller's competition composed of several bidder populations.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, its competition configured as:
+//!
+//! - Variant A: Lognormal population alone (CompetitionConfig::LOG_NORMAL)
+//!
+//! - Variant B: Value-correlated population alone (CompetitionConfig::CORRELATED)
+//!
+//! - Variant C: Both populations bidding on every impression (CompetitionConfig::MAX)
+//!
+//! - Variant D: One of both populations per impression, chosen with equal weights (CompetitionConfig::WEIGHTED)
+//!
+//! - Variant E: Competing bids made of both populations' bids added up (CompetitionConfig::SUM)
+//!
+//! With both populations bidding, the highest competing bid is above either population's, so the campaigns
+//! pay more for their targets. Choosing one population per impression averages their competing bids, and
+//! adding them up adds their averages.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, Sellers, SellerBuilder};
+use crate::seller_builder::CompetitionConfig;
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "competition_composition",
+    run,
+    tags: &[],
+});
+
+/// Value-correlated bidder population
+const CORRELATED: CompetitionConfig = CompetitionConfig::CORRELATED { value_mean: 10.0, value_stddev: 3.0, bid_mean: 10.0, bid_stddev: 3.0, correlation: 0.8 };
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the sum and count of competing bids in the latest iteration
+fn prepare_simulationconverge(competition: CompetitionConfig) -> (SimulationConverge, Rc<RefCell<(f64, f64)>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    SellerBuilder::new("HB", SellerType::FIRST_PRICE, 10000)
+        .with_competition(competition)
+        .add_to(&mut sellers);
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Sum up the competing bids, reset at the start of every iteration
+    let competing_bids = Rc::new(RefCell::new((0.0, 0.0)));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let competing_bids_reset = competing_bids.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            *competing_bids_reset.borrow_mut() = (0.0, 0.0);
+        });
+        let competing_bids_sum = competing_bids.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let Some(competition) = &event.impression.competition {
+                let mut competing_bids = competing_bids_sum.borrow_mut();
+                competing_bids.0 += competition.bid_cpm;
+                competing_bids.1 += 1.0;
+            }
+        });
+    }
+
+    (simulation_converge, competing_bids)
+}
+
+/// Average of the summed competing bids
+fn average_competing_bid(competing_bids: &Rc<RefCell<(f64, f64)>>) -> f64 {
+    let (sum, count) = *competing_bids.borrow();
+    sum / count
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with the lognormal population alone
+    let (simulation_converge_a, competing_bids_a) = prepare_simulationconverge(CompetitionConfig::LOG_NORMAL);
+    let stats_a = simulation_converge_a.run_variant("Running with the lognormal population", scenario_name, "lognormal", 100, logger)?;
+
+    // Run variant B with the value-correlated population alone
+    let (simulation_converge_b, competing_bids_b) = prepare_simulationconverge(CORRELATED);
+    let stats_b = simulation_converge_b.run_variant("Running with the value-correlated population", scenario_name, "correlated", 100, logger)?;
+
+    // Run variant C with both populations bidding on every impression
+    let (simulation_converge_c, competing_bids_c) = prepare_simulationconverge(CompetitionConfig::MAX { competitions: vec![CompetitionConfig::LOG_NORMAL, CORRELATED] });
+    let stats_c = simulation_converge_c.run_variant("Running with the highest bid of both populations", scenario_name, "max", 100, logger)?;
+
+    // Run variant D with one of both populations per impression
+    let (simulation_converge_d, competing_bids_d) = prepare_simulationconverge(CompetitionConfig::WEIGHTED { competitions: vec![(0.5, CompetitionConfig::LOG_NORMAL), (0.5, CORRELATED)] });
+    let stats_d = simulation_converge_d.run_variant("Running with one population per impression", scenario_name, "weighted", 100, logger)?;
+
+    // Run variant E with both populations' bids added up
+    let (simulation_converge_e, competing_bids_e) = prepare_simulationconverge(CompetitionConfig::SUM { competitions: vec![CompetitionConfig::LOG_NORMAL, CORRELATED] });
+    let stats_e = simulation_converge_e.run_variant("Running with the sum of both populations' bids", scenario_name, "sum", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (lognormal)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (correlated)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (max)", &stats_c, logger, &mut errors);
+    validation::assert_targets_met("Variant D (weighted)", &stats_d, logger, &mut errors);
+    validation::assert_targets_met("Variant E (sum)", &stats_e, logger, &mut errors);
+
+    let average_a = average_competing_bid(&competing_bids_a);
+    let average_b = average_competing_bid(&competing_bids_b);
+    let average_c = average_competing_bid(&competing_bids_c);
+    let average_d = average_competing_bid(&competing_bids_d);
+    let average_e = average_competing_bid(&competing_bids_e);
+
+    // Check: The highest bid of both populations is above either population's
+    for (label, average) in [("A (lognormal)", average_a), ("B (correlated)", average_b)] {
+        assertions::assert_greater(
+            &format!("Variant C (max) has higher average competing bid than variant {} with one population", label),
+            average_c,
+            average,
+            logger,
+            &mut errors,
+        );
+    }
+
+    // Check: Choosing one population per impression averages their competing bids
+    let expected_d = 0.5 * (average_a + average_b);
+    assertions::assert_within_pct("Variant D (weighted) average competing bid is the average of both populations", average_d, expected_d, 5.0, logger, &mut errors);
+
+    // Check: Adding up both populations' bids adds their averages
+    let expected_e = average_a + average_b;
+    assertions::assert_within_pct("Variant E (sum) average competing bid is the sum of both populations'", average_e, expected_e, 5.0, logger, &mut errors);
+
+    // Check: The impressions campaign pays more for its target when both populations bid
+    let spend = |stats: &SimulationStat| stats.campaign_stats[0].total_buyer_charge;
+    for (label, stats) in [("A (lognormal)", &stats_a), ("B (correlated)", &stats_b)] {
+        assertions::assert_greater(
+            &format!("Variant C (max) impressions campaign spends more than variant {} with one population", label),
+            spend(&stats_c),
+            spend(stats),
+            logger,
+            &mut errors,
+        );
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
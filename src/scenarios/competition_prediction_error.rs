@@ -0,0 +1,203 @@
+//! This scenario sweeps the prediction error of the competition generator's win rate sigmoids.
+//!
+//! A max margin bidder spends the same budget in all variants, only the deviation of the predicted win rate
+//! sigmoids from the actual ones, configured on the seller's competition generator, differs:
+//!
+//! - Variant A: Exact prediction
+//!
+//! - Variant B: Default prediction error (unbiased, small noise on offset and scale)
+//!
+//! - Variant C: Biased prediction (competition predicted to be 30% cheaper)
+//!
+//! - Variant D: Noisy prediction (unbiased, stddev 0.3 noise on offset and scale)
+//!
+//! - Variants E and F: Value-correlated competition with exact and with biased prediction
+//!
+//! The bidder should obtain the most value per unit of spend with exact prediction, and less the larger
+//! the prediction error. Biased variants predict competition offsets 30% below the actual ones.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::{CompetitionGeneratorTrait, CompetitionGeneratorLogNormal, CompetitionGeneratorCorrelated, PredictionError};
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "competition_prediction_error",
+    run,
+    tags: &[],
+});
+
+/// Offset bias of the biased variants
+const OFFSET_BIAS: f64 = 0.7;
+
+/// Lognormal competition with the given prediction error
+fn lognormal_competition(prediction_error: PredictionError) -> Box<dyn CompetitionGeneratorTrait> {
+    CompetitionGeneratorLogNormal::new(10.0).with_prediction_error(prediction_error)
+}
+
+/// Value-correlated competition with the given prediction error
+fn correlated_competition(prediction_error: PredictionError) -> Box<dyn CompetitionGeneratorTrait> {
+    CompetitionGeneratorCorrelated::new(10.0, 3.0, 10.0, 3.0, 0.8).with_prediction_error(prediction_error)
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the sum and count of predicted to actual sigmoid offset ratios in the latest iteration
+fn prepare_simulationconverge(competition_generator: Box<dyn CompetitionGeneratorTrait>) -> (SimulationConverge, Rc<RefCell<(f64, f64)>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        competition_generator,  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Sum up the predicted to actual offset ratios, reset at the start of every iteration
+    let offset_ratios = Rc::new(RefCell::new((0.0, 0.0)));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let offset_ratios_reset = offset_ratios.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            *offset_ratios_reset.borrow_mut() = (0.0, 0.0);
+        });
+        let offset_ratios_sum = offset_ratios.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let Some(competition) = &event.impression.competition {
+                let mut offset_ratios = offset_ratios_sum.borrow_mut();
+                offset_ratios.0 += competition.win_rate_prediction_sigmoid_offset / competition.win_rate_actual_sigmoid_offset;
+                offset_ratios.1 += 1.0;
+            }
+        });
+    }
+
+    (simulation_converge, offset_ratios)
+}
+
+/// Average of the summed predicted to actual offset ratios
+fn average_offset_ratio(offset_ratios: &Rc<RefCell<(f64, f64)>>) -> f64 {
+    let (sum, count) = *offset_ratios.borrow();
+    sum / count
+}
+
+/// Value obtained per unit of spend by the single campaign
+fn value_per_spend(stats: &SimulationStat) -> f64 {
+    let campaign = &stats.campaign_stats[0];
+    if campaign.total_buyer_charge > 0.0 {
+        campaign.total_value / campaign.total_buyer_charge
+    } else {
+        0.0
+    }
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with exact prediction
+    let (simulation_converge_a, _) = prepare_simulationconverge(lognormal_competition(PredictionError::exact()));
+    let stats_a = simulation_converge_a.run_variant("Running with exact win rate prediction", scenario_name, "exact", 100, logger)?;
+
+    // Run variant B with the default prediction error
+    let (simulation_converge_b, _) = prepare_simulationconverge(lognormal_competition(PredictionError::default()));
+    let stats_b = simulation_converge_b.run_variant("Running with the default win rate prediction error", scenario_name, "default", 100, logger)?;
+
+    // Run variant C with prediction biased towards cheaper competition
+    let (simulation_converge_c, offset_ratios_c) = prepare_simulationconverge(lognormal_competition(PredictionError::new(OFFSET_BIAS, 1.0, 0.0, 0.0)));
+    let stats_c = simulation_converge_c.run_variant("Running with biased win rate prediction", scenario_name, "biased", 100, logger)?;
+
+    // Run variant D with noisy prediction
+    let (simulation_converge_d, _) = prepare_simulationconverge(lognormal_competition(PredictionError::new(1.0, 1.0, 0.3, 0.3)));
+    let stats_d = simulation_converge_d.run_variant("Running with noisy win rate prediction", scenario_name, "noisy", 100, logger)?;
+
+    // Run variants E and F with value-correlated competition, exact and biased
+    let (simulation_converge_e, offset_ratios_e) = prepare_simulationconverge(correlated_competition(PredictionError::exact()));
+    let stats_e = simulation_converge_e.run_variant("Running with value-correlated competition and exact win rate prediction", scenario_name, "correlated_exact", 100, logger)?;
+    let (simulation_converge_f, offset_ratios_f) = prepare_simulationconverge(correlated_competition(PredictionError::new(OFFSET_BIAS, 1.0, 0.0, 0.0)));
+    let stats_f = simulation_converge_f.run_variant("Running with value-correlated competition and biased win rate prediction", scenario_name, "correlated_biased", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (exact)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (default)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (biased)", &stats_c, logger, &mut errors);
+    validation::assert_targets_met("Variant D (noisy)", &stats_d, logger, &mut errors);
+    validation::assert_targets_met("Variant E (correlated, exact)", &stats_e, logger, &mut errors);
+    validation::assert_targets_met("Variant F (correlated, biased)", &stats_f, logger, &mut errors);
+
+    // Check: Biased prediction offsets are the actual ones scaled by the bias, exact ones equal them
+    for (label, offset_ratios, expected) in [("C (biased)", &offset_ratios_c, OFFSET_BIAS), ("E (correlated, exact)", &offset_ratios_e, 1.0), ("F (correlated, biased)", &offset_ratios_f, OFFSET_BIAS)] {
+        let offset_ratio = average_offset_ratio(offset_ratios);
+        validation::check(
+            (offset_ratio - expected).abs() < 1e-9,
+            format!("Variant {} predicted to actual sigmoid offset ratio: {:.4} ≈ {:.4}", label, offset_ratio, expected),
+            logger,
+            &mut errors,
+        );
+    }
+
+    // Check: Exact prediction obtains more value per spend than each erroneous prediction
+    let value_per_spend_a = value_per_spend(&stats_a);
+    for (label, stats) in [("B (default)", &stats_b), ("C (biased)", &stats_c), ("D (noisy)", &stats_d)] {
+        assertions::assert_greater(
+            &format!("Variant A (exact) has higher value per spend than variant {}", label),
+            value_per_spend_a,
+            value_per_spend(stats),
+            logger,
+            &mut errors,
+        );
+    }
+
+    // Check: Larger noise loses more value per spend
+    assertions::assert_greater(
+        "Variant B (default) has higher value per spend than variant D (noisy)",
+        value_per_spend(&stats_b),
+        value_per_spend(&stats_d),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Biased prediction of value-correlated competition loses value per spend as well
+    assertions::assert_greater(
+        "Variant E (correlated, exact) has higher value per spend than variant F (correlated, biased)",
+        value_per_spend(&stats_e),
+        value_per_spend(&stats_f),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,148 @@
+//! This scenario demonstrates selecting the criterion that stops the convergence loop.
+//!
+//! A campaign targets a total budget, paced by a PD controller with zero tolerance, so the controller keeps
+//! adjusting pacing to ever smaller errors and never reports its state unchanged. It converges with:
+//!
+//! - Variant A: The default criterion, controllers unchanged
+//!
+//! - Variant B: Relative error of the target within 1% for 3 consecutive iterations
+//!
+//! - Variant C: Pacing stagnant within 0.5% over 5 iterations
+//!
+//! - Variant D: The default criterion or a maximum runtime of zero
+//!
+//! The default criterion never stops the run before maximum iterations, while the relative error and stagnation
+//! criteria stop it once the campaign is effectively converged. The maximum runtime stops the run without
+//! converging after its first iteration.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignGeneral};
+use crate::controllers::{ControllerProportionalDerivative, ControllerTrait};
+use crate::convergence_criteria::{ConvergenceCriterionTrait, ConvergenceCriterionControllersUnchanged, ConvergenceCriterionRelativeError, ConvergenceCriterionStagnation, ConvergenceCriterionMaxRuntime, ConvergenceCriterionAny};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "convergence_criteria",
+    run,
+    tags: &["fast", "controllers"],
+});
+
+/// Maximum iterations of every variant
+const MAX_ITERATIONS: usize = 100;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the number of iterations run
+fn prepare_simulationconverge(convergence_criterion: Box<dyn ConvergenceCriterionTrait>) -> (SimulationConverge, Rc<Cell<usize>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index), paced with zero tolerance
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+    let campaign_general = campaigns.campaigns[campaign_id].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+    let converge_controller: Box<dyn ControllerTrait> = Box::new(ControllerProportionalDerivative::new_advanced(0.0, 0.2, 0.1, 0.05, true));
+    campaign_general.converge_controllers[0] = converge_controller;
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace).with_convergence_criterion(convergence_criterion);
+
+    // Count iterations run
+    let iterations = Rc::new(Cell::new(0usize));
+    {
+        let iterations_count = iterations.clone();
+        simulation_converge.hooks.borrow_mut().on_iteration_start(move |iteration, _logger| {
+            iterations_count.set(iteration);
+        });
+    }
+
+    (simulation_converge, iterations)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with the default criterion
+    let (simulation_converge_a, iterations_a) = prepare_simulationconverge(ConvergenceCriterionControllersUnchanged::new());
+    let converged_a = simulation_converge_a.run_variant("Running until controllers are unchanged", scenario_name, "controllers_unchanged", MAX_ITERATIONS, logger).is_ok();
+
+    // Run variant B until the relative error stays within tolerance
+    let (simulation_converge_b, iterations_b) = prepare_simulationconverge(ConvergenceCriterionRelativeError::new(0.01, 3));
+    let stats_b = simulation_converge_b.run_variant("Running until the relative error stays within 1%", scenario_name, "relative_error", MAX_ITERATIONS, logger)?;
+
+    // Run variant C until pacing stagnates
+    let (simulation_converge_c, iterations_c) = prepare_simulationconverge(ConvergenceCriterionStagnation::new(5, 0.005));
+    let stats_c = simulation_converge_c.run_variant("Running until pacing stagnates", scenario_name, "stagnation", MAX_ITERATIONS, logger)?;
+
+    // Run variant D with a maximum runtime of zero
+    let (simulation_converge_d, iterations_d) = prepare_simulationconverge(ConvergenceCriterionAny::new(vec![
+        ConvergenceCriterionControllersUnchanged::new(),
+        ConvergenceCriterionMaxRuntime::new(Duration::ZERO),
+    ]));
+    let converged_d = simulation_converge_d.run_variant("Running with a maximum runtime of zero", scenario_name, "max_runtime", MAX_ITERATIONS, logger).is_ok();
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant B (relative error)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (stagnation)", &stats_c, logger, &mut errors);
+
+    // Check: Controllers keep changing their state, the default criterion never stops the run
+    validation::check(
+        !converged_a && iterations_a.get() == MAX_ITERATIONS,
+        format!("Variant A (controllers unchanged) doesn't converge within {} iterations, ran {}", MAX_ITERATIONS, iterations_a.get()),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The relative error and stagnation criteria stop the run well before maximum iterations
+    validation::check(
+        iterations_b.get() < MAX_ITERATIONS / 2 && iterations_c.get() < MAX_ITERATIONS / 2,
+        format!("Variants B (relative error) and C (stagnation) converge in {} and {} iterations, fewer than {}", iterations_b.get(), iterations_c.get(), MAX_ITERATIONS / 2),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The maximum runtime stops the run without converging after its first iteration
+    validation::check(
+        !converged_d && iterations_d.get() == 1,
+        format!("Variant D (max runtime) stops without converging after {} iteration(s)", iterations_d.get()),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,149 @@
+//! This scenario demonstrates checking convergence trajectories from the statistics of every iteration.
+//!
+//! A campaign targets a total budget, paced by:
+//!
+//! - Variant A: A proportional-only PD controller (no derivative term)
+//!
+//! - Variant B: The bisection controller
+//!
+//! The proportional controller takes steps of a fraction of the error, so the spend should approach the budget
+//! monotonically without overshooting it. The bisection controller jumps to the middle of its bracket, so the
+//! spend should overshoot the budget by a wide margin before it settles. Both trajectories are drawn on a chart.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignGeneral};
+use crate::controllers::{ControllerBisection, ControllerProportionalDerivative, ControllerTrait};
+use crate::converge::SimulationConverge;
+use crate::trajectory::{Trajectory, draw_trajectories};
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent, sanitize_filename};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "convergence_trajectory",
+    run,
+    tags: &["fast", "controllers", "analysis"],
+});
+
+/// Budget of the campaign
+const TARGET_BUDGET: f64 = 20.0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(converge_controller: Box<dyn ControllerTrait>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_BUDGET }],  // converge_target
+    );
+    let campaign_general = campaigns.campaigns[campaign_id].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+    campaign_general.converge_controllers[0] = converge_controller;
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally), recording the
+    // statistics of every iteration
+    SimulationConverge::new(marketplace).with_history()
+}
+
+/// Spend of the campaign in an iteration
+fn campaign_spend(stats: &SimulationStat) -> f64 {
+    stats.campaign_stats[0].total_buyer_charge
+}
+
+/// Check that the history has every iteration of a converged variant, ending with its final statistics
+fn check_history(label: &str, stats: &SimulationStat, history: &[SimulationStat], logger: &mut Logger, errors: &mut Vec<String>) {
+    let final_spend = history.last().map_or(f64::NAN, campaign_spend);
+    validation::check(
+        history.len() == stats.convergence_iterations && final_spend == campaign_spend(stats),
+        format!("{}: History has all {} iterations ({}), ending with the final spend {:.4}", label, stats.convergence_iterations, history.len(), final_spend),
+        logger,
+        errors,
+    );
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with a proportional-only PD controller
+    let simulation_converge_a = prepare_simulationconverge(Box::new(ControllerProportionalDerivative::new_advanced(0.002, 0.2, 0.1, 0.0, false)));
+    let stats_a = simulation_converge_a.run_variant("Running with proportional-only PD controller", scenario_name, "proportional", 100, logger)?;
+    let history_a = simulation_converge_a.history();
+    let trajectory_a = Trajectory::from_history("Proportional", &history_a, campaign_spend).with_target(TARGET_BUDGET);
+
+    // Run variant B with the bisection controller
+    let simulation_converge_b = prepare_simulationconverge(Box::new(ControllerBisection::new()));
+    let stats_b = simulation_converge_b.run_variant("Running with bisection controller", scenario_name, "bisection", 100, logger)?;
+    let history_b = simulation_converge_b.history();
+    let trajectory_b = Trajectory::from_history("Bisection", &history_b, campaign_spend).with_target(TARGET_BUDGET);
+
+    let chart_path = logger.log_path(&format!("{}/trajectory-budget.png", sanitize_filename(scenario_name)));
+    draw_trajectories(&chart_path, "Spend of campaign 0 per iteration", "Spend", &[trajectory_a.clone(), trajectory_b.clone()])?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B", &stats_b, logger, &mut errors);
+
+    // Check: The history has every iteration, the last one being the final statistics
+    check_history("Variant A", &stats_a, &history_a, logger, &mut errors);
+    check_history("Variant B", &stats_b, &history_b, logger, &mut errors);
+
+    // Check: The proportional controller approaches the budget monotonically without overshooting it
+    validation::check(
+        trajectory_a.is_monotone_approach(0.01) && trajectory_a.overshoot() < 0.01,
+        format!("Variant A (proportional): Spend approaches the budget monotonically, overshoot {:.2}% < 1%", trajectory_a.overshoot() * 100.0),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The bisection controller overshoots the budget by a wide margin, then settles
+    assertions::assert_greater("Variant B (bisection): Spend overshoots the budget by more than 20%, overshoot", trajectory_b.overshoot(), 0.2, logger, &mut errors);
+    for (label, trajectory) in [("Variant A (proportional)", &trajectory_a), ("Variant B (bisection)", &trajectory_b)] {
+        let settling_iteration = trajectory.settling_iteration(0.01);
+        validation::check(
+            settling_iteration.is_some(),
+            format!("{}: Spend settles within 1% of the budget from iteration {} of {}", label, settling_iteration.map_or("none".to_string(), |iteration| iteration.to_string()), trajectory.values.len()),
+            logger,
+            &mut errors,
+        );
+    }
+
+    // Check: The trajectories are drawn
+    validation::check(
+        chart_path.exists(),
+        format!("Trajectory chart is written to {}", chart_path.display()),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,129 @@
+//! This scenario demonstrates conversion events carrying a value.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller whose impressions convert with
+//! a probability sampled per impression (conversion rate around 10%):
+//!
+//! - Variant A: Every conversion is worth 1.0
+//!
+//! - Variant B: Conversion values sampled per impression from a log-normal distribution (mean 50, stddev 20)
+//!
+//! Conversion values don't change which impressions convert, so both variants obtain the same conversions,
+//! and in variant B each is worth the mean conversion value on average.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "conversion_values",
+    run,
+    tags: &["fast"],
+});
+
+/// Mean value of a conversion in variant B
+const CONVERSION_VALUE_MEAN: f64 = 50.0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(conversion_values: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters with conversions
+    let mut impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_conversions(utils::beta_dist(2.0, 18.0), 0.0);
+    if conversion_values {
+        impressions_params = impressions_params.with_conversion_values(utils::lognormal_dist(CONVERSION_VALUE_MEAN, 20.0));
+    }
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with every conversion worth 1.0
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running with unit conversion values", scenario_name, "unit", 100, logger)?;
+
+    // Run variant B with sampled conversion values
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with sampled conversion values", scenario_name, "sampled", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (unit)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (sampled)", &stats_b, logger, &mut errors);
+
+    for (campaign_id, (campaign_a, campaign_b)) in stats_a.campaign_stats.iter().zip(&stats_b.campaign_stats).enumerate() {
+        logln!(logger, LogEvent::Scenario, "Campaign {}: ROAS {:.2} (unit) and {:.2} (sampled)", campaign_id,
+            campaign_a.total_conversion_value / campaign_a.total_buyer_charge, campaign_b.total_conversion_value / campaign_b.total_buyer_charge);
+
+        // Check: Conversions are counted, each worth 1.0 without conversion values
+        validation::check(
+            campaign_a.total_conversions > 0.0 && campaign_a.total_conversion_value == campaign_a.total_conversions,
+            format!("Variant A (unit) campaign {} conversion value equals its conversions: {:.0} = {:.0}", campaign_id, campaign_a.total_conversion_value, campaign_a.total_conversions),
+            logger,
+            &mut errors,
+        );
+
+        // Check: Conversion values don't change which impressions convert
+        validation::check(
+            campaign_b.total_conversions == campaign_a.total_conversions,
+            format!("Variant B (sampled) campaign {} obtains the conversions of variant A (unit): {:.0} = {:.0}", campaign_id, campaign_b.total_conversions, campaign_a.total_conversions),
+            logger,
+            &mut errors,
+        );
+
+        // Check: Conversions are worth the mean conversion value on average
+        let average_conversion_value = campaign_b.total_conversion_value / campaign_b.total_conversions;
+        assertions::assert_within_pct(
+            &format!("Variant B (sampled) campaign {} average conversion value matches the mean", campaign_id),
+            average_conversion_value,
+            CONVERSION_VALUE_MEAN,
+            10.0,
+            logger,
+            &mut errors,
+        );
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,155 @@
+//! This scenario demonstrates counterfactual replay of converged controller states.
+//!
+//! Three campaigns (two budget targets and an impressions target) converge on a single seller. Their converged
+//! controller states are then replayed on the same impressions, without converging again:
+//!
+//! - Baseline: The unchanged marketplace
+//!
+//! - Counterfactual "without_campaign_2": Campaign 2 removed
+//!
+//! - Counterfactual "floors_x2": The seller's floors doubled
+//!
+//! The baseline should reproduce the converged statistics. Without campaign 2 the other campaigns, bidding as
+//! before, can only win more impressions, and the seller loses part of campaign 2's spend (its incrementality),
+//! the rest being picked up by the other campaigns. With doubled floors the seller sells fewer impressions.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::counterfactual::{Counterfactual, CounterfactualChange, replay_counterfactuals};
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "counterfactual_replay",
+    run,
+    tags: &["fast", "floors", "analysis"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge() -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 2".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 30.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Largest difference of the campaigns' spend between two statistics
+fn max_spend_difference(stats: &SimulationStat, other: &SimulationStat) -> f64 {
+    stats.campaign_stats.iter().zip(&other.campaign_stats)
+        .map(|(campaign_stat, other_campaign_stat)| (campaign_stat.total_buyer_charge - other_campaign_stat.total_buyer_charge).abs())
+        .fold(0.0, f64::max)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Converge the marketplace
+    let mut simulation_converge = prepare_simulationconverge();
+    let (stats, campaign_controller_states, seller_controller_states) = simulation_converge.run_variant_with_states("Running converging marketplace", scenario_name, "converged", 100, logger)?;
+
+    // Replay the converged states on the unchanged marketplace, without campaign 2 and with doubled floors
+    let counterfactuals = [
+        Counterfactual::new("without_campaign_2").with_change(CounterfactualChange::REMOVE_CAMPAIGN { campaign_id: 2 }),
+        Counterfactual::new("floors_x2").with_change(CounterfactualChange::SCALE_FLOORS { seller_id: 0, factor: 2.0 }),
+    ];
+    let result = replay_counterfactuals(&mut simulation_converge, &counterfactuals, &campaign_controller_states, &seller_controller_states, stats.convergence_iterations, logger);
+    result.printout(&simulation_converge, logger, LogEvent::Scenario);
+
+    // Replay the baseline again, on the marketplace restored after the counterfactuals
+    let restored = simulation_converge.replay(&campaign_controller_states, &seller_controller_states, stats.convergence_iterations, logger);
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Converged", &stats, logger, &mut errors);
+
+    // Check: The baseline reproduces the converged statistics, also after the counterfactuals
+    validation::check(
+        max_spend_difference(&result.baseline, &stats) < 1e-9 && max_spend_difference(&restored, &stats) < 1e-9,
+        format!("Baseline replay reproduces the converged spend (difference {:.2e}, after counterfactuals {:.2e})", max_spend_difference(&result.baseline, &stats), max_spend_difference(&restored, &stats)),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Without campaign 2, it wins nothing and the other campaigns win at least as many impressions
+    let without = result.counterfactual("without_campaign_2");
+    validation::check(
+        without.campaign_stats[2].impressions_obtained == 0.0
+            && (0..2).all(|campaign_id| without.campaign_stats[campaign_id].impressions_obtained >= result.baseline.campaign_stats[campaign_id].impressions_obtained),
+        format!("Without campaign 2, campaigns 0 and 1 win at least as many impressions: {:.0} ≥ {:.0}, {:.0} ≥ {:.0}",
+            without.campaign_stats[0].impressions_obtained, result.baseline.campaign_stats[0].impressions_obtained,
+            without.campaign_stats[1].impressions_obtained, result.baseline.campaign_stats[1].impressions_obtained),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Campaign 2 brings the seller part of its spend, the rest is picked up by the other campaigns
+    let revenue_incrementality = result.incrementality("without_campaign_2", |stats| stats.seller_stats[0].total_buyer_charge);
+    let spend = result.baseline.campaign_stats[2].total_buyer_charge;
+    assertions::assert_between(
+        &format!("Campaign 2's incremental seller revenue is part of its spend ({:.1}%)", revenue_incrementality / spend * 100.0),
+        revenue_incrementality,
+        0.0,
+        spend,
+        logger,
+        &mut errors,
+    );
+
+    // Check: With doubled floors the seller sells fewer impressions
+    let floors_x2 = result.counterfactual("floors_x2");
+    assertions::assert_greater(
+        "Seller sells more impressions in the baseline than with doubled floors",
+        result.baseline.seller_stats[0].impressions_sold as f64,
+        floors_x2.seller_stats[0].impressions_sold as f64,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,133 @@
+//! This scenario compares independent and jointly-coupled controllers on a double target campaign.
+//!
+//! - Variant A: Max margin double target campaign (impressions and average value), each target
+//!   converged by its own PD controller
+//!
+//! - Variant B: The same campaign converged by ControllerCoupled, updating both control variables
+//!   from the errors of both targets
+//!
+//! Both control variables move both targets, so the independent controllers fight each other.
+//! The coupled controller should converge to the same targets in fewer iterations.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, ControllerConfig, CampaignCoupledDoubleTarget, CampaignTargetTotalImpressions, CampaignTargetAvgValue};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "coupled_double_target",
+    run,
+    tags: &["viewability"],
+});
+
+const TARGET_IMPRESSIONS: i32 = 1000;
+const TARGET_AVG_VALUE: f64 = 0.92;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the number of iterations run
+fn prepare_simulationconverge(coupled: bool) -> (SimulationConverge, Rc<Cell<usize>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    if coupled {
+        campaigns.add_advanced(Box::new(CampaignCoupledDoubleTarget::new(
+            "Campaign 0".to_string(),  // campaign_name
+            Box::new(CampaignTargetTotalImpressions { total_impressions_target: TARGET_IMPRESSIONS }),  // primary_target
+            Box::new(CampaignTargetAvgValue { avg_impression_value_to_campaign: TARGET_AVG_VALUE }),  // secondary_target
+            [[0.3, 0.5], [0.0, 1.5]],  // gain_matrix
+        )));
+    } else {
+        campaigns.add_configured(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MAX_MARGIN_DOUBLE_TARGET,  // campaign_type
+            vec![
+                ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: TARGET_IMPRESSIONS },
+                ConvergeTarget::AVG_VALUE { avg_impression_value_to_campaign: TARGET_AVG_VALUE },
+            ],  // converge_target
+            vec![
+                ControllerConfig::new(),
+                // Average value controller tuned as in the viewability scenario
+                ControllerConfig::new().with_tolerance_fraction(0.005).with_max_adjustment_factor(0.5).with_gains(0.35, 0.35),
+            ],  // controller_configs
+        );
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(1.0, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters (beta distributed values resemble viewability, as in the viewability scenario)
+    let impressions_params = ImpressionsParam::new(
+        utils::beta_dist(30.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.01),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Count iterations until convergence
+    let iterations = Rc::new(Cell::new(0usize));
+    {
+        let iterations_count = iterations.clone();
+        simulation_converge.hooks.borrow_mut().on_iteration_start(move |iteration, _logger| {
+            iterations_count.set(iteration);
+        });
+    }
+
+    (simulation_converge, iterations)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with independent controllers
+    let (simulation_converge_a, iterations_a) = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running with independent controllers", scenario_name, "independent", 1000, logger)?;
+
+    // Run variant B with the coupled controller
+    let (simulation_converge_b, iterations_b) = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with coupled controller", scenario_name, "coupled", 1000, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Coupled controller converges in fewer iterations
+    assertions::assert_greater(
+        "Variant A (independent) needs more iterations to converge than variant B (coupled)",
+        iterations_a.get() as f64,
+        iterations_b.get() as f64,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Both variants reach both targets
+    for (variant, stats) in [("A (independent)", &stats_a), ("B (coupled)", &stats_b)] {
+        let impressions = stats.campaign_stats[0].impressions_obtained;
+        let avg_value = stats.campaign_stats[0].total_value / impressions;
+        assertions::assert_within_pct(&format!("Variant {} reaches the impressions target", variant), impressions, TARGET_IMPRESSIONS as f64, 1.0, logger, &mut errors);
+        assertions::assert_within_pct(&format!("Variant {} reaches the average value target", variant), avg_value, TARGET_AVG_VALUE, 1.0, logger, &mut errors);
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
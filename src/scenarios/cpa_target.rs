@@ -0,0 +1,123 @@
+//! This scenario converges a campaign on a cost per acquisition (CPA) target.
+//!
+//! Impressions convert with a probability sampled per impression (conversion rate around 10%),
+//! and the campaign's pacing is converged so that its spend per conversion hits the CPA goal.
+//!
+//! - Variant A: Conversions independent of impression value
+//!
+//! - Variant B: Conversion probability proportional to impression value (value elasticity 1.0)
+//!
+//! Both variants should reach the CPA goal. When conversions follow value, valuable impressions that
+//! cost more are also the ones that convert, so conversions are priced in and cheap conversions from
+//! low value impressions are gone: the same CPA buys fewer conversions.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::controllers::ControllerConfig;
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "cpa_target",
+    run,
+    tags: &["fast"],
+});
+
+/// Cost per conversion the campaign converges to
+const TARGET_CPA: f64 = 0.1;
+/// Each conversion moves the CPA by about 1%, so the controller needs a wider tolerance than the default
+const CPA_TOLERANCE_FRACTION: f64 = 0.01;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(conversion_value_elasticity: f64) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add_configured(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::CPA { target_cpa: TARGET_CPA }],  // converge_target
+        vec![ControllerConfig::new().with_tolerance_fraction(CPA_TOLERANCE_FRACTION)],  // controller_configs
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters with conversions
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_conversions(utils::beta_dist(2.0, 18.0), conversion_value_elasticity);
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Validate that the campaign of a variant converged on the CPA goal, returning its conversions
+fn validate_cpa(variant: &str, stats: &SimulationStat, logger: &mut Logger, errors: &mut Vec<String>) -> f64 {
+    let campaign_stat = &stats.campaign_stats[0];
+    let cpa = campaign_stat.total_buyer_charge / campaign_stat.total_conversions;
+    assertions::assert_within_pct(
+        &format!("{}: CPA hits the goal ({:.0} conversions for {:.2} spend)", variant, campaign_stat.total_conversions, campaign_stat.total_buyer_charge),
+        cpa,
+        TARGET_CPA,
+        CPA_TOLERANCE_FRACTION * 100.0,
+        logger,
+        errors,
+    );
+    campaign_stat.total_conversions
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with conversions independent of value
+    let simulation_converge_a = prepare_simulationconverge(0.0);
+    let stats_a = simulation_converge_a.run_variant("Running with conversions independent of value", scenario_name, "independent", 100, logger)?;
+
+    // Run variant B with conversions correlated with value
+    let simulation_converge_b = prepare_simulationconverge(1.0);
+    let stats_b = simulation_converge_b.run_variant("Running with conversions correlated with value", scenario_name, "correlated", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let conversions_a = validate_cpa("Variant A (independent)", &stats_a, logger, &mut errors);
+    let conversions_b = validate_cpa("Variant B (correlated)", &stats_b, logger, &mut errors);
+
+    // Check: Conversions that follow value are priced in, so the same CPA buys fewer of them
+    assertions::assert_greater(
+        "Variant A (independent) obtains more conversions than variant B (correlated)",
+        conversions_a,
+        conversions_b,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
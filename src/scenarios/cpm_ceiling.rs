@@ -0,0 +1,165 @@
+//! This scenario constrains the average CPM the campaign pays with a second control variable.
+//!
+//! A budget campaign bidding on plain value spends much of its budget on high value impressions,
+//! which are the expensive ones in a first price auction.
+//!
+//! - Variant A: Multiplicative pacing on the budget only
+//!
+//! - Variant B: Budget with a maximum average CPM of 6.5, kept by the exponent pulling bids towards
+//!   the ceiling and away from high value impressions
+//!
+//! - Variant C: Budget with a maximum average CPM of 10.0, which plain value bidding already meets
+//!
+//! Variant B should stay below its ceiling while still spending the budget, buying more (cheaper)
+//! impressions than variant A. In variant C the ceiling has slack, so the exponent rests at 1.0 and
+//! bidding is the same as in variant A.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::{SimulationConverge, CampaignControllerStates};
+use crate::controllers::ControllerStateTrait;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "cpm_ceiling",
+    run,
+    tags: &["fast"],
+});
+
+/// Budget of the campaign in all variants
+const TARGET_BUDGET: f64 = 10.0;
+/// Binding maximum average CPM of variant B
+const MAX_AVG_CPM_BINDING: f64 = 6.5;
+/// Maximum average CPM of variant C, met without any flattening
+const MAX_AVG_CPM_SLACK: f64 = 10.0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// The campaign is constrained to a maximum average CPM when one is given
+fn prepare_simulationconverge(max_avg_cpm: Option<f64>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    match max_avg_cpm {
+        Some(max_avg_cpm) => campaigns.add(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MULTIPLICATIVE_CPM_CEILING,  // campaign_type
+            vec![
+                ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_BUDGET },
+                ConvergeTarget::MAX_AVG_CPM { max_avg_cpm },
+            ],  // converge_targets
+        ),
+        None => campaigns.add(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_BUDGET }],  // converge_target
+        ),
+    };
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Average CPM the campaign paid for the impressions it won
+fn avg_cpm(stats: &SimulationStat) -> f64 {
+    stats.campaign_stats[0].total_buyer_charge * 1000.0 / stats.campaign_stats[0].impressions_obtained
+}
+
+/// Converged exponent (second control variable) of a constrained campaign
+fn exponent(simulation_converge: &SimulationConverge, campaign_controller_states: &CampaignControllerStates) -> f64 {
+    let controller_states: Vec<&dyn ControllerStateTrait> = campaign_controller_states.campaign_controller_states[0].iter().map(|cs| cs.as_ref()).collect();
+    simulation_converge.marketplace.campaigns.campaigns[0].get_control_variables(&controller_states)[1]
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A without a CPM ceiling
+    let simulation_converge_a = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_variant("Running with budget only", scenario_name, "unconstrained", 100, logger)?;
+
+    // Run variant B with a binding CPM ceiling
+    let simulation_converge_b = prepare_simulationconverge(Some(MAX_AVG_CPM_BINDING));
+    let (stats_b, campaign_controller_states_b, _seller_controller_states_b) = simulation_converge_b.run_variant_with_states(&format!("Running with maximum average CPM {:.1}", MAX_AVG_CPM_BINDING), scenario_name, "binding", 100, logger)?;
+
+    // Run variant C with a CPM ceiling that has slack
+    let simulation_converge_c = prepare_simulationconverge(Some(MAX_AVG_CPM_SLACK));
+    let (stats_c, campaign_controller_states_c, _seller_controller_states_c) = simulation_converge_c.run_variant_with_states(&format!("Running with maximum average CPM {:.1}", MAX_AVG_CPM_SLACK), scenario_name, "slack", 100, logger)?;
+
+    let exponent_b = exponent(&simulation_converge_b, &campaign_controller_states_b);
+    let exponent_c = exponent(&simulation_converge_c, &campaign_controller_states_c);
+
+    let cpm_a = avg_cpm(&stats_a);
+    let cpm_b = avg_cpm(&stats_b);
+    let cpm_c = avg_cpm(&stats_c);
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Variant A (unconstrained) pays more than the binding ceiling
+    assertions::assert_greater(
+        "Variant A (unconstrained) average CPM is above the ceiling of variant B",
+        cpm_a,
+        MAX_AVG_CPM_BINDING,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Variant B (binding) stays below its ceiling (within controller tolerance) by flattening bids
+    validation::check(
+        cpm_b <= MAX_AVG_CPM_BINDING * 1.005 && exponent_b < 1.0,
+        format!("Variant B (binding) stays below the maximum average CPM: {:.4} ≤ {:.1} (exponent {:.4})", cpm_b, MAX_AVG_CPM_BINDING, exponent_b),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Variant B (binding) still spends its budget, on more impressions than variant A
+    let spend_b = stats_b.campaign_stats[0].total_buyer_charge;
+    assertions::assert_within_pct("Variant B (binding) spends the budget", spend_b, TARGET_BUDGET, 1.0, logger, &mut errors);
+    assertions::assert_greater(
+        "Variant B (binding) obtains more impressions than variant A (unconstrained)",
+        stats_b.campaign_stats[0].impressions_obtained,
+        stats_a.campaign_stats[0].impressions_obtained,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Variant C (slack) leaves the exponent at rest and bids like variant A
+    validation::check(
+        exponent_c == 1.0,
+        format!("Variant C (slack) doesn't flatten bids: exponent {:.4} == 1.0", exponent_c),
+        logger,
+        &mut errors,
+    );
+    assertions::assert_within_pct("Variant C (slack) average CPM matches variant A (unconstrained)", cpm_c, cpm_a, 1.0, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
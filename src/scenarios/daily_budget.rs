@@ -0,0 +1,122 @@
+//! This scenario compares a total budget with a daily budget in a multi-day simulation.
+//!
+//! Impressions are spread over three days with uneven traffic (weights 1.0, 2.0 and 0.75), and the
+//! campaign persists across the days.
+//!
+//! - Variant A: Total budget of 30.0 over all days
+//!
+//! - Variant B: Daily budget of 10.0 on each day
+//!
+//! With a total budget the single pacing lets spend follow the traffic, so the busy second day takes
+//! most of the budget. With a daily budget each day's pacing is converged separately, and every day
+//! spends its own budget. Both variants spend the same in total.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::{validation, assertions};
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "daily_budget",
+    run,
+    tags: &["fast", "multi_day"],
+});
+
+/// Relative traffic on each simulated day
+const DAY_WEIGHTS: [f64; 3] = [1.0, 2.0, 0.75];
+/// Budget of each day in variant B
+const DAILY_BUDGET: f64 = 10.0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(converge_target: ConvergeTarget) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![converge_target],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, spreading impressions over the days
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_days(DAY_WEIGHTS.to_vec());
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Spend of the campaign on each day
+fn day_spends(stats: &SimulationStat) -> Vec<f64> {
+    stats.campaign_stats[0].day_breakdown.iter().map(|day_stat| day_stat.total_buyer_charge).collect()
+}
+
+/// Format day spends for messages
+fn format_spends(spends: &[f64]) -> String {
+    spends.iter().map(|spend| format!("{:.2}", spend)).collect::<Vec<String>>().join(" / ")
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+    let num_days = DAY_WEIGHTS.len();
+    let total_budget = DAILY_BUDGET * num_days as f64;
+
+    // Run variant A with a total budget over all days
+    let simulation_converge_a = prepare_simulationconverge(ConvergeTarget::TOTAL_BUDGET { target_total_budget: total_budget });
+    let stats_a = simulation_converge_a.run_variant("Running with total budget over all days", scenario_name, "total", 100, logger)?;
+
+    // Run variant B with a daily budget
+    let simulation_converge_b = prepare_simulationconverge(ConvergeTarget::DAILY_BUDGET { target_daily_budget: DAILY_BUDGET, num_days });
+    let stats_b = simulation_converge_b.run_variant("Running with daily budget", scenario_name, "daily", 100, logger)?;
+
+    let spends_a = day_spends(&stats_a);
+    let spends_b = day_spends(&stats_b);
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Variant A (total budget) spend follows the traffic, deviating from an even split
+    let max_deviation_a = spends_a.iter().map(|spend| (spend - DAILY_BUDGET).abs() / DAILY_BUDGET).fold(0.0, f64::max);
+    assertions::assert_greater(
+        &format!("Variant A (total budget) spend per day follows the traffic: {}, largest deviation from {:.1}", format_spends(&spends_a), DAILY_BUDGET),
+        max_deviation_a,
+        0.1,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Variant B (daily budget) spends its budget on each day
+    for (day, spend) in spends_b.iter().enumerate() {
+        assertions::assert_within_pct(&format!("Variant B (daily budget) spend on day {}", day), *spend, DAILY_BUDGET, 1.0, logger, &mut errors);
+    }
+
+    // Check: Both variants spend the total budget
+    assertions::assert_within_pct("Variant A (total budget) total spend", spends_a.iter().sum(), total_budget, 1.0, logger, &mut errors);
+    assertions::assert_within_pct("Variant B (daily budget) total spend", spends_b.iter().sum(), total_budget, 1.0, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
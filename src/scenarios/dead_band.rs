@@ -0,0 +1,52 @@
+//! This scenario compares the default PD controller with and without a dead band.
+//!
+//! - Variant A: Max margin campaign targeting total budget, paced by ControllerProportionalDerivative (the baseline)
+//!
+//! - Variant B: Same campaign, with the PD controller wrapped in a 1% ControllerDeadBand
+//!
+//! The PD controller's tolerance is 0.2%, so it keeps making small adjustments until spend is very close
+//! to the budget. With the dead band it stops as soon as spend is within 1%, in fewer iterations.
+
+use crate::sellers::SellerType;
+use crate::seller_builder::SellerBuilder;
+use crate::campaigns::{CampaignType, ConvergeTarget, CampaignGeneral};
+use crate::controllers::{ControllerDeadBand, ControllerProportionalDerivative};
+use crate::scenarios::builder::Scenario;
+use crate::scenarios::assertions;
+use crate::register_scenario;
+
+// Register this scenario in the catalog
+register_scenario!("dead_band", scenario, &["controllers"]);
+
+const TARGET_TOTAL_BUDGET: f64 = 40.0;
+const DEAD_BAND_FRACTION: f64 = 0.01;
+
+/// Variant A with the default PD controller as the baseline, and variant B with the PD controller inside a dead band
+fn scenario(scenario_name: &str) -> Scenario {
+    Scenario::new(scenario_name)
+        .campaign("Campaign 0", CampaignType::MAX_MARGIN, vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_TOTAL_BUDGET }])
+        .seller(SellerBuilder::new("HB", SellerType::FIRST_PRICE, 10000).build())
+        // Leave the PD controller's small adjustments room to converge
+        .max_iterations(200)
+        .variant("dead-band", |campaigns, _| {
+            let campaign_general = campaigns.campaigns[0].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+            campaign_general.converge_controllers[0] = Box::new(ControllerDeadBand::new(Box::new(ControllerProportionalDerivative::new()), DEAD_BAND_FRACTION));
+        })
+        // Check: PD controller needs more iterations to converge than the dead band controller
+        .assert(|result, logger, errors| assertions::assert_greater(
+            "Variant A (PD) needs more iterations to converge than variant B (dead band)",
+            result.baseline.convergence_iterations as f64,
+            result.variant("dead-band").convergence_iterations as f64,
+            logger,
+            errors,
+        ))
+        // Check: Dead band controller spends within the dead band
+        .assert(|result, logger, errors| assertions::assert_within_pct(
+            "Variant B (dead band) spends its budget",
+            result.variant("dead-band").campaign_stats[0].total_buyer_charge,
+            TARGET_TOTAL_BUDGET,
+            DEAD_BAND_FRACTION * 100.0,
+            logger,
+            errors,
+        ))
+}
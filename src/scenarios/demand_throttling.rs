@@ -0,0 +1,165 @@
+//! This scenario studies a seller throttling a campaign's bids (traffic shaping) and its effect on the campaigns.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller.
+//!
+//! - Variant A: Seller passes all bids through to its auctions
+//!
+//! - Variant B: Seller throttles half of the impressions campaign's bids
+//!
+//! - Variant C: Seller's controller tunes the throttling of the impressions campaign to receive a target
+//!   number of its bids
+//!
+//! A throttled campaign only gets a chance at part of the impressions, so the impressions campaign still
+//! meets its target but has to win a larger share of the auctions it gets through to, paying a higher CPM.
+//! In variant C the seller converges on the share of bids passed through while the campaigns converge
+//! their pacing.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "demand_throttling",
+    run,
+    tags: &["fast", "supply"],
+});
+
+/// Campaign throttled by the seller
+const THROTTLED_CAMPAIGN: usize = 0;
+/// Bids of the throttled campaign the seller's controller converges to in variant C
+const TARGET_BIDS: usize = 4000;
+
+/// How the seller throttles the impressions campaign
+enum Throttling {
+    None,
+    Static { throttle_probability: f64 },
+    Controlled { target_bids: usize },
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(throttling: Throttling) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    let seller_converge = match throttling {
+        Throttling::Controlled { target_bids } => SellerConvergeStrategy::THROTTLE { campaign_id: THROTTLED_CAMPAIGN, target_bids },
+        _ => SellerConvergeStrategy::NONE { default_value: 1.0 },
+    };
+    let seller_id = sellers.sellers.len();
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        seller_converge,  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+    if let Throttling::Static { throttle_probability } = throttling {
+        sellers.set_throttling(seller_id, THROTTLED_CAMPAIGN, throttle_probability);
+    }
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// CPM the throttled campaign pays
+fn throttled_campaign_cpm(stats: &SimulationStat) -> f64 {
+    let campaign_stat = &stats.campaign_stats[THROTTLED_CAMPAIGN];
+    campaign_stat.total_buyer_charge / campaign_stat.impressions_obtained * 1000.0
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A without throttling
+    let simulation_converge_a = prepare_simulationconverge(Throttling::None);
+    let stats_a = simulation_converge_a.run_variant("Running without throttling", scenario_name, "unthrottled", 100, logger)?;
+
+    // Run variant B with half of the impressions campaign's bids throttled
+    let simulation_converge_b = prepare_simulationconverge(Throttling::Static { throttle_probability: 0.5 });
+    let stats_b = simulation_converge_b.run_variant("Running with half of campaign 0's bids throttled", scenario_name, "static", 100, logger)?;
+
+    // Run variant C with the throttling converged to a target number of the impressions campaign's bids
+    let simulation_converge_c = prepare_simulationconverge(Throttling::Controlled { target_bids: TARGET_BIDS });
+    let stats_c = simulation_converge_c.run_variant("Running with campaign 0's throttling converged to a target number of bids", scenario_name, "controlled", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (unthrottled)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (static)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (controlled)", &stats_c, logger, &mut errors);
+
+    let seller_a = &stats_a.seller_stats[0];
+    let seller_b = &stats_b.seller_stats[0];
+    let seller_c = &stats_c.seller_stats[0];
+
+    // Check: Only the throttled campaign loses bids, about half of them with static throttling
+    let throttled_share_b = seller_b.throttled_bids[THROTTLED_CAMPAIGN] as f64 / (seller_b.campaign_bids[THROTTLED_CAMPAIGN] + seller_b.throttled_bids[THROTTLED_CAMPAIGN]) as f64;
+    validation::check(
+        seller_a.throttled_bids.iter().all(|throttled_bids| *throttled_bids == 0) && seller_b.throttled_bids[1] == 0,
+        "Variant B (static) throttles only campaign 0 (variant A throttles none)".to_string(),
+        logger,
+        &mut errors,
+    );
+    assertions::assert_between("Variant B (static) share of campaign 0's bids throttled", throttled_share_b, 0.45, 0.55, logger, &mut errors);
+
+    // Check: Seller's controller converges the bids received from the throttled campaign to the target
+    let campaign_bids_c = seller_c.campaign_bids[THROTTLED_CAMPAIGN];
+    assertions::assert_within_pct(
+        "Variant C (controlled) receives the target number of campaign 0's bids",
+        campaign_bids_c as f64,
+        TARGET_BIDS as f64,
+        1.0,
+        logger,
+        &mut errors,
+    );
+    assertions::assert_greater(
+        "Variant C (controlled) throttles some of campaign 0's bids",
+        seller_c.throttled_bids[THROTTLED_CAMPAIGN] as f64,
+        0.0,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Throttled campaign pays more to meet its target on the impressions it gets a chance at
+    let cpm_a = throttled_campaign_cpm(&stats_a);
+    let cpm_b = throttled_campaign_cpm(&stats_b);
+    let cpm_c = throttled_campaign_cpm(&stats_c);
+    assertions::assert_greater("Campaign 0 pays a higher CPM throttled statically (variant B) than unthrottled (variant A)", cpm_b, cpm_a, logger, &mut errors);
+    assertions::assert_greater("Campaign 0 pays a higher CPM throttled by the controller (variant C) than statically (variant B)", cpm_c, cpm_b, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
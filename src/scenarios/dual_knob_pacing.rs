@@ -0,0 +1,114 @@
+//! This scenario shows a campaign hitting a budget and an impressions target at the same time.
+//!
+//! A single pacing multiplier can only converge to one target. With a second knob, an additive
+//! boost on top of the multiplied value, the campaign can trade impression value for volume:
+//! the additive boost raises bids relatively more on cheap, low value impressions.
+//!
+//! - Variant A: Multiplicative pacing converging to the budget only
+//!
+//! - Variant B: Multiplier converging to the budget and additive boost converging to the impressions target
+//!
+//! The impressions target is set above what variant A obtains with the same budget.
+//! Variant B should hit both targets, while variant A stays below the impressions target.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "dual_knob_pacing",
+    run,
+    tags: &["fast"],
+});
+
+const TARGET_TOTAL_BUDGET: f64 = 20.0;
+const TARGET_TOTAL_IMPRESSIONS: i32 = 2500;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(campaign_type: CampaignType, converge_targets: Vec<ConvergeTarget>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        campaign_type,  // campaign_type - single or dual knob pacing
+        converge_targets,  // converge_targets
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with multiplicative pacing converging to the budget only
+    let simulation_converge_a = prepare_simulationconverge(
+        CampaignType::MULTIPLICATIVE_PACING,
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_TOTAL_BUDGET }],
+    );
+    let stats_a = simulation_converge_a.run_variant("Running with multiplicative pacing (budget target)", scenario_name, "multiplicative", 100, logger)?;
+
+    // Run variant B with dual knob pacing converging to budget and impressions
+    let simulation_converge_b = prepare_simulationconverge(
+        CampaignType::MULTIPLICATIVE_ADDITIVE_DOUBLE_TARGET,
+        vec![
+            ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_TOTAL_BUDGET },
+            ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: TARGET_TOTAL_IMPRESSIONS },
+        ],
+    );
+    let stats_b = simulation_converge_b.run_variant("Running with multiplicative + additive pacing (budget and impressions targets)", scenario_name, "dual-knob", 200, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+    let campaign_a = &stats_a.campaign_stats[0];
+    let campaign_b = &stats_b.campaign_stats[0];
+    let target_impressions = TARGET_TOTAL_IMPRESSIONS as f64;
+
+    // Check: Variant A stays below the impressions target with the same budget
+    assertions::assert_greater(
+        "Variant A (multiplicative) obtains fewer impressions than the target, 95% of the target",
+        target_impressions * 0.95,
+        campaign_a.impressions_obtained,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Variant B hits the budget target (within 2%)
+    assertions::assert_within_pct("Variant B (dual knob) spends the budget target", campaign_b.total_buyer_charge, TARGET_TOTAL_BUDGET, 2.0, logger, &mut errors);
+
+    // Check: Variant B hits the impressions target (within 2%)
+    assertions::assert_within_pct("Variant B (dual knob) obtains the impressions target", campaign_b.impressions_obtained, target_impressions, 2.0, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,135 @@
+//! This scenario demonstrates floors that adapt to demand across convergence iterations.
+//!
+//! Two budget campaigns buy from a single HB seller without competing demand, so floors alone set the
+//! prices and the number of impressions the budgets buy.
+//!
+//! - Variant A: Static lognormal floors
+//!
+//! - Variant B: Controlled floors converging to a lower sell-through than variant A reaches
+//!
+//! - Variant C: Controlled floors converging to a higher sell-through than variant A reaches
+//!
+//! The seller's control variable scales its floors. To sell fewer impressions variant B raises its
+//! floors above the static ones, and to sell more variant C lowers them. Campaigns still meet their targets.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers, SellerBuilder};
+use crate::seller_builder::{CompetitionConfig, FloorConfig};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::{SimulationConverge, SellerControllerStates};
+use crate::impressions::ImpressionsParam;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "dynamic_floors",
+    run,
+    tags: &["fast", "floors", "supply"],
+});
+
+/// Number of impressions the HB seller has on offer
+const IMPRESSIONS_ON_OFFER: usize = 10000;
+/// Sell-through targets of variants B and C
+const LOW_SELL_THROUGH: f64 = 0.8;
+const HIGH_SELL_THROUGH: f64 = 0.95;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(seller_converge: SellerConvergeStrategy, floors: FloorConfig) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    SellerBuilder::new("HB", SellerType::FIRST_PRICE, IMPRESSIONS_ON_OFFER)
+        .with_converge(seller_converge)
+        .with_competition(CompetitionConfig::NONE)
+        .with_floors(floors)
+        .add_to(&mut sellers);
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Share of the HB seller's impressions that were sold
+fn sell_through(stats: &SimulationStat) -> f64 {
+    stats.seller_stats[0].fill_rate()
+}
+
+/// Scale of the HB seller's floors
+fn floor_scale(simulation_converge: &SimulationConverge, seller_controller_states: &SellerControllerStates) -> f64 {
+    simulation_converge.marketplace.sellers.sellers[0].get_control_variable(seller_controller_states.seller_controller_states[0][0].as_ref())
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with static floors
+    let simulation_converge_a = prepare_simulationconverge(SellerConvergeStrategy::NONE { default_value: 1.0 }, FloorConfig::LOG_NORMAL { relative_to_impression_value: 0.2, stddev: 3.0 });
+    let stats_a = simulation_converge_a.run_variant("Running with static floors", scenario_name, "static", 100, logger)?;
+
+    // Run variant B with floors converging to a low sell-through
+    let simulation_converge_b = prepare_simulationconverge(SellerConvergeStrategy::SELL_THROUGH { target_sell_through: LOW_SELL_THROUGH }, FloorConfig::CONTROLLED { relative_to_impression_value: 0.2, stddev: 3.0 });
+    let (stats_b, _, seller_controller_states_b) = simulation_converge_b.run_variant_with_states("Running with floors controlled to a low sell-through", scenario_name, "low_sell_through", 100, logger)?;
+
+    // Run variant C with floors converging to a high sell-through
+    let simulation_converge_c = prepare_simulationconverge(SellerConvergeStrategy::SELL_THROUGH { target_sell_through: HIGH_SELL_THROUGH }, FloorConfig::CONTROLLED { relative_to_impression_value: 0.2, stddev: 3.0 });
+    let (stats_c, _, seller_controller_states_c) = simulation_converge_c.run_variant_with_states("Running with floors controlled to a high sell-through", scenario_name, "high_sell_through", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let sell_through_a = sell_through(&stats_a);
+    let sell_through_b = sell_through(&stats_b);
+    let sell_through_c = sell_through(&stats_c);
+    let floor_scale_b = floor_scale(&simulation_converge_b, &seller_controller_states_b);
+    let floor_scale_c = floor_scale(&simulation_converge_c, &seller_controller_states_c);
+
+    // Check: Targets are between variant A's sell-through, so that floors have to move both ways
+    assertions::assert_between(
+        "Variant A (static) sells between the targets",
+        sell_through_a,
+        LOW_SELL_THROUGH,
+        HIGH_SELL_THROUGH,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Variant B raises floors to sell less
+    assertions::assert_within_pct("Variant B (low sell-through) meets its target", sell_through_b, LOW_SELL_THROUGH, 1.0, logger, &mut errors);
+    assertions::assert_greater("Variant B (low sell-through) raises floors, floor scale", floor_scale_b, 1.0, logger, &mut errors);
+
+    // Check: Variant C lowers floors to sell more
+    assertions::assert_within_pct("Variant C (high sell-through) meets its target", sell_through_c, HIGH_SELL_THROUGH, 1.0, logger, &mut errors);
+    assertions::assert_greater("Variant C (high sell-through) lowers floors, unscaled floors above its floor scale", 1.0, floor_scale_c, logger, &mut errors);
+
+    // Check: Campaigns meet their targets whatever the floors
+    validation::assert_targets_met("Variant A (static)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (low sell-through)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (high sell-through)", &stats_c, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
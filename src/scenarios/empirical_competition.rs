@@ -0,0 +1,122 @@
+//! This scenario demonstrates replaying an empirical competitive landscape loaded from a CSV file.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller.
+//!
+//! - Variant A: Synthetic competition, competing bids around each impression's base value (CompetitionGeneratorLogNormal)
+//!
+//! - Variant B: Empirical competition, competing bids sampled from data/competition_bids.csv (CompetitionGeneratorEmpirical)
+//!
+//! The sample file has a bimodal landscape of mid-tier and premium demand. In variant B every competing
+//! bid is one of the samples, their mean matches the file's, and the campaigns still meet their targets.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::{CompetitionGeneratorTrait, CompetitionGeneratorLogNormal, CompetitionGeneratorEmpirical};
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::collections::HashSet;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "empirical_competition",
+    run,
+    tags: &["fast"],
+});
+
+/// Competing bid samples replayed in variant B
+const COMPETITION_SAMPLES_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/competition_bids.csv");
+
+/// Prepare simulation converge instance with campaign and seller setup, the seller using the given competition generator
+fn prepare_simulationconverge(competition_generator: Box<dyn CompetitionGeneratorTrait>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        competition_generator,  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Competing bids of all impressions
+fn competing_bids(simulation_converge: &SimulationConverge) -> Vec<f64> {
+    simulation_converge.marketplace.impressions.impressions.iter()
+        .filter_map(|impression| impression.competition.as_ref().map(|competition| competition.bid_cpm))
+        .collect()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with synthetic competition
+    let simulation_converge_a = prepare_simulationconverge(CompetitionGeneratorLogNormal::new(10.0));
+    let stats_a = simulation_converge_a.run_variant("Running with synthetic lognormal competition", scenario_name, "synthetic", 100, logger)?;
+
+    // Run variant B with competition replayed from the samples
+    let samples: Vec<f64> = CompetitionGeneratorEmpirical::from_csv(COMPETITION_SAMPLES_PATH)?.bid_samples().collect();
+    let simulation_converge_b = prepare_simulationconverge(CompetitionGeneratorEmpirical::from_csv(COMPETITION_SAMPLES_PATH)?);
+    let stats_b = simulation_converge_b.run_variant("Running with empirical competition", scenario_name, "empirical", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (synthetic)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (empirical)", &stats_b, logger, &mut errors);
+
+    // Check: Every competing bid of variant B is one of the samples
+    let sample_bits: HashSet<u64> = samples.iter().map(|bid_cpm| bid_cpm.to_bits()).collect();
+    let bids_b = competing_bids(&simulation_converge_b);
+    let replayed = bids_b.iter().filter(|bid_cpm| sample_bits.contains(&bid_cpm.to_bits())).count();
+    validation::check(
+        !bids_b.is_empty() && replayed == bids_b.len(),
+        format!("Variant B (empirical) replays the samples: {} of {} competing bids are samples", replayed, bids_b.len()),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Replayed bids follow the samples' distribution
+    let mean_samples = mean(&samples);
+    let mean_b = mean(&bids_b);
+    assertions::assert_within_pct("Variant B (empirical) mean competing bid matches the samples", mean_b, mean_samples, 5.0, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
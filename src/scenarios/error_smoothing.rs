@@ -0,0 +1,186 @@
+//! This scenario compares a PD controller with and without error smoothing under noisy observations.
+//!
+//! - Variant A: Max margin campaign targeting total budget, paced by a fast-reacting PD controller
+//!
+//! - Variant B: Same campaign, with the PD controller wrapped in a ControllerErrorSmoothing
+//!
+//! Runs in this simulator are deterministic, so the observation noise that stochastic impression generation
+//! would cause is simulated by a budget target that reports spend with multiplicative lognormal noise.
+//! The tolerance is tighter than the noise, so the variants run up to a fixed number of iterations and are
+//! allowed not to converge. The PD controller chases the noise, while the smoothed error should keep the
+//! actual spend closer to the budget.
+
+use crate::simulationrun::{Marketplace, SimulationType, CampaignStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignGeneral, CampaignTargetTrait};
+use crate::controllers::{ControllerProportionalDerivative, ControllerErrorSmoothing, ControllerTrait};
+use crate::converge::SimulationConverge;
+use crate::hooks::AuctionOutcome;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::{Distribution, LogNormal};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "error_smoothing",
+    run,
+    tags: &["controllers"],
+});
+
+const TARGET_TOTAL_BUDGET: f64 = 40.0;
+const MAX_ITERATIONS: usize = 60;
+/// Iterations skipped before measuring, so both variants have reached the budget
+const WARMUP_ITERATIONS: usize = 20;
+
+/// Total budget target reporting spend with multiplicative lognormal noise (seeded, a new draw every iteration)
+struct CampaignTargetNoisyBudget {
+    total_budget_target: f64,
+    noise_dist: LogNormal<f64>,
+    rng: RefCell<StdRng>,
+}
+
+impl CampaignTargetTrait for CampaignTargetNoisyBudget {
+    fn get_actual_and_target(&self, campaign_stat: &CampaignStat) -> (f64, f64) {
+        let noise = self.noise_dist.sample(&mut *self.rng.borrow_mut());
+        (campaign_stat.total_buyer_charge * noise, self.total_budget_target)
+    }
+
+    fn get_target_value(&self) -> f64 {
+        self.total_budget_target
+    }
+
+    fn converge_target_string(&self) -> String {
+        format!("Noisy budget: {:.2}", self.total_budget_target)
+    }
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the campaign's actual (noiseless) spend in every iteration
+fn prepare_simulationconverge(smoothed: bool) -> (SimulationConverge, Rc<RefCell<Vec<f64>>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_TOTAL_BUDGET }],  // converge_target
+    );
+    // Fast-reacting PD controller, which passes the noise on to pacing
+    let pd_controller = ControllerProportionalDerivative::new_advanced(
+        0.002, // tolerance_fraction
+        0.3,   // max_adjustment_factor
+        0.5,   // proportional_gain
+        0.1,   // derivative_gain
+        true,  // rescaling
+    );
+    let converge_controller: Box<dyn ControllerTrait> = if smoothed {
+        Box::new(ControllerErrorSmoothing::new(Box::new(pd_controller), 0.3))
+    } else {
+        Box::new(pd_controller)
+    };
+    let campaign_general = campaigns.campaigns[campaign_id].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+    campaign_general.converge_controllers[0] = converge_controller;
+    // Same noise sequence for both variants
+    campaign_general.converge_targets[0] = Box::new(CampaignTargetNoisyBudget {
+        total_budget_target: TARGET_TOTAL_BUDGET,
+        noise_dist: utils::lognormal_dist(1.0, 0.05),
+        rng: RefCell::new(StdRng::seed_from_u64(4242)),
+    });
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Record the campaign's actual spend in every iteration
+    let spend_per_iteration = Rc::new(RefCell::new(Vec::new()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let spend_start = spend_per_iteration.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            spend_start.borrow_mut().push(0.0);
+        });
+        let spend_update = spend_per_iteration.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                if let Winner::Campaign { buyer_charge, .. } = result.winner {
+                    *spend_update.borrow_mut().last_mut().unwrap() += buyer_charge;
+                }
+            }
+        });
+    }
+
+    (simulation_converge, spend_per_iteration)
+}
+
+/// Root mean square of the relative spend error after the warmup iterations
+/// A variant that converged early keeps spending its final spend for the remaining iterations
+fn rms_spend_error(spend_per_iteration: &[f64]) -> f64 {
+    let final_spend = *spend_per_iteration.last().unwrap();
+    let sum_squares: f64 = (WARMUP_ITERATIONS..MAX_ITERATIONS)
+        .map(|iteration| spend_per_iteration.get(iteration).copied().unwrap_or(final_spend))
+        .map(|spend| ((spend - TARGET_TOTAL_BUDGET) / TARGET_TOTAL_BUDGET).powi(2))
+        .sum();
+    (sum_squares / (MAX_ITERATIONS - WARMUP_ITERATIONS) as f64).sqrt()
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run both variants for a fixed number of iterations, they are allowed not to converge under noise
+    let (simulation_converge_a, spend_a) = prepare_simulationconverge(false);
+    if let Err(error) = simulation_converge_a.run_variant("Running with PD controller on noisy spend", scenario_name, "pd", MAX_ITERATIONS, logger) {
+        logln!(logger, LogEvent::Scenario, "Variant A (PD): {}", error);
+    }
+
+    let (simulation_converge_b, spend_b) = prepare_simulationconverge(true);
+    if let Err(error) = simulation_converge_b.run_variant("Running with error smoothing PD controller on noisy spend", scenario_name, "smoothed", MAX_ITERATIONS, logger) {
+        logln!(logger, LogEvent::Scenario, "Variant B (smoothed): {}", error);
+    }
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Smoothing keeps the actual spend closer to the budget
+    let rms_a = rms_spend_error(&spend_a.borrow());
+    let rms_b = rms_spend_error(&spend_b.borrow());
+    assertions::assert_greater(
+        &format!("Variant A (PD) RMS spend error after {} iterations is larger than variant B (smoothed)", WARMUP_ITERATIONS),
+        rms_a,
+        rms_b,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Smoothed spend stays within 3% of the budget on average
+    assertions::assert_between("Variant B (smoothed) RMS spend error is within 3%", rms_b, 0.0, 0.03, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
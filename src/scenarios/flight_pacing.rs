@@ -0,0 +1,128 @@
+//! This scenario demonstrates pacing a flight day by day in a multi-period run.
+//!
+//! Impressions are spread over a 14-day flight with lower traffic on weekends, and two campaigns (impressions
+//! and budget targets for the whole flight) are paced one day after another, their controllers updated once a day:
+//!
+//! - Variant A: What a day spends and obtains beyond or short of its share rolls over to the remaining days
+//!
+//! - Variant B: Without carryover, every day is paced to its share of the flight's targets by traffic
+//!
+//! Pacing starts from the controllers' initial states, so the first days overshoot their share. With carryover
+//! the remaining days make up for it and the flight meets its targets, without carryover the overshoot stays.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::{SimulationConverge, PeriodsStat};
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "flight_pacing",
+    run,
+    tags: &["fast", "multi_day"],
+});
+
+/// Relative traffic on each day of the flight (two weeks, weekends at 60%)
+const DAY_WEIGHTS: [f64; 14] = [1.0, 1.0, 1.0, 1.0, 1.0, 0.6, 0.6, 1.0, 1.0, 1.0, 1.0, 1.0, 0.6, 0.6];
+/// Impressions target of campaign 0 over the flight
+const FLIGHT_IMPRESSIONS: f64 = 2000.0;
+/// Budget of campaign 1 over the flight
+const FLIGHT_BUDGET: f64 = 40.0;
+/// Tolerance of the flight's targets with carryover, pacing isn't converged as within a single period
+const FLIGHT_TOLERANCE: f64 = 0.03;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(period_carryover: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: FLIGHT_IMPRESSIONS as i32 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: FLIGHT_BUDGET }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        20000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, spreading impressions over the days of the flight
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_days(DAY_WEIGHTS.to_vec());
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace).with_period_carryover(period_carryover)
+}
+
+/// Relative deviation of each campaign's results over the flight from its target
+fn flight_deviations(stats: &PeriodsStat) -> Vec<f64> {
+    stats.campaign_targets.iter().map(|targets| (targets[0].actual / targets[0].target - 1.0).abs()).collect()
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with carryover
+    let mut simulation_converge_a = prepare_simulationconverge(true);
+    let stats_a = simulation_converge_a.run_periods_variant("Running the flight with carryover", scenario_name, "carryover", logger);
+
+    // Run variant B without carryover
+    let mut simulation_converge_b = prepare_simulationconverge(false);
+    let stats_b = simulation_converge_b.run_periods_variant("Running the flight without carryover", scenario_name, "no-carryover", logger);
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let deviations_a = flight_deviations(&stats_a);
+    let deviations_b = flight_deviations(&stats_b);
+    for (campaign_id, (targets_a, targets_b)) in stats_a.campaign_targets.iter().zip(&stats_b.campaign_targets).enumerate() {
+        let (target_a, target_b) = (&targets_a[0], &targets_b[0]);
+
+        // Check: With carryover the flight meets its target
+        assertions::assert_within_pct(
+            &format!("Variant A (carryover) campaign {} meets its flight target [{}]", campaign_id, target_a.name),
+            target_a.actual,
+            target_a.target,
+            FLIGHT_TOLERANCE * 100.0,
+            logger,
+            &mut errors,
+        );
+
+        // Check: Without carryover the flight misses its target by more
+        assertions::assert_greater(
+            &format!("Variant B (no carryover) campaign {} misses its flight target [{}] by more than variant A (carryover): {:.2} vs. {:.2} of {:.2}, relative deviation",
+                campaign_id, target_b.name, target_b.actual, target_a.actual, target_b.target),
+            deviations_b[campaign_id],
+            deviations_a[campaign_id],
+            logger,
+            &mut errors,
+        );
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,105 @@
+//! This scenario compares max margin bidding with an untruncated and a floor-aware prediction sigmoid.
+//!
+//! Floors are set high relative to impression value, so many auctions are decided near the floor.
+//! The untruncated prediction sigmoid assumes a bid just above the floor wins with the full sigmoid
+//! probability, while the floor-aware one sets probability to zero below the floor and renormalizes above it.
+//!
+//! - Variant A: Max margin bidding with untruncated prediction sigmoid
+//!
+//! - Variant B: Max margin bidding with floor-aware prediction sigmoid
+//!
+//! Both campaigns spend the same budget, the floor-aware one should obtain more value per unit of spend.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "floor_truncation",
+    run,
+    tags: &["floors"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(campaign_type: CampaignType) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        campaign_type,  // campaign_type - untruncated or floor-aware max margin
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    // Floors are centered around the impression value, so they are binding for a large share of impressions
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.8, 2.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with untruncated prediction sigmoid
+    let simulation_converge_a = prepare_simulationconverge(CampaignType::MAX_MARGIN);
+    let stats_a = simulation_converge_a.run_variant("Running with untruncated prediction sigmoid", scenario_name, "max-margin", 100, logger)?;
+
+    // Run variant B with floor-aware prediction sigmoid
+    let simulation_converge_b = prepare_simulationconverge(CampaignType::MAX_MARGIN_FLOOR_AWARE);
+    let stats_b = simulation_converge_b.run_variant("Running with floor-aware prediction sigmoid", scenario_name, "max-margin-floor-aware", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Variant B obtains more value per spend than variant A
+    let campaign_a = &stats_a.campaign_stats[0];
+    let campaign_b = &stats_b.campaign_stats[0];
+    let value_per_spend_a = if campaign_a.total_buyer_charge > 0.0 {
+        campaign_a.total_value / campaign_a.total_buyer_charge
+    } else {
+        0.0
+    };
+    let value_per_spend_b = if campaign_b.total_buyer_charge > 0.0 {
+        campaign_b.total_value / campaign_b.total_buyer_charge
+    } else {
+        0.0
+    };
+    assertions::assert_greater(
+        "Variant B (floor-aware) has higher value per spend than variant A (untruncated)",
+        value_per_spend_b,
+        value_per_spend_a,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,75 @@
+//! This scenario shows a per-campaign frequency cap limiting the impressions won per user.
+//!
+//! Impressions are shown to 2000 users, about five impressions per user. The campaign converges on
+//! an impressions target.
+//!
+//! - Variant A: No frequency cap (the baseline)
+//!
+//! - Variant B: Frequency cap of one impression per user
+//!
+//! Both variants should reach the target. Variant A wins several impressions of some users, while
+//! variant B wins at most one impression per user: its bids on users it already reached are dropped,
+//! so it has to bid on more of the remaining supply and pays more per impression.
+
+use crate::simulationrun::CampaignStat;
+use crate::sellers::SellerType;
+use crate::seller_builder::SellerBuilder;
+use crate::campaigns::{CampaignType, ConvergeTarget};
+use crate::impressions::ImpressionsParam;
+use crate::utils;
+use crate::scenarios::builder::Scenario;
+use crate::scenarios::assertions;
+use crate::register_scenario;
+
+// Register this scenario in the catalog
+register_scenario!("frequency_cap", scenario, &["fast"]);
+
+/// Number of users the impressions are shown to
+const NUM_USERS: usize = 2000;
+/// Impressions target of the capped campaign
+const TARGET_IMPRESSIONS: i32 = 1500;
+
+/// Cost of each impression obtained by the campaign
+fn cost_per_impression(campaign_stat: &CampaignStat) -> f64 {
+    campaign_stat.total_buyer_charge / campaign_stat.impressions_obtained
+}
+
+/// Variant A without a frequency cap as the baseline, and variant B capped at one impression per user
+fn scenario(scenario_name: &str) -> Scenario {
+    Scenario::new(scenario_name)
+        .campaign("Campaign 0", CampaignType::MULTIPLICATIVE_PACING, vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: TARGET_IMPRESSIONS }])
+        .campaign("Campaign 1", CampaignType::MULTIPLICATIVE_PACING, vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }])
+        .seller(SellerBuilder::new("HB", SellerType::FIRST_PRICE, 10000).build())
+        // Show impressions to users
+        .impressions(ImpressionsParam::new(
+            utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+            utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+        ).with_users(NUM_USERS))
+        .variant("capped", |campaigns, _| campaigns.set_frequency_cap(0, 1))
+        .validate_targets_met()
+        // Check: Without the cap the campaign wins several impressions of some users
+        .assert(|result, logger, errors| assertions::assert_greater(
+            "Variant A (uncapped) wins more than one impression per reached user on average",
+            result.baseline.campaign_stats[0].average_frequency(),
+            1.0,
+            logger,
+            errors,
+        ))
+        // Check: The cap limits the campaign to one impression per user, dropping bids on reached users
+        .validate(|result| {
+            let campaign_b = &result.variant("capped").campaign_stats[0];
+            (
+                campaign_b.impressions_obtained == campaign_b.reach as f64 && campaign_b.frequency_capped_auctions > 0,
+                format!("Variant B (capped) wins at most one impression per user: {:.0} impressions of {} users reached, {} auctions dropped by the cap (eligible impressions {:.0})",
+                    campaign_b.impressions_obtained, campaign_b.reach, campaign_b.frequency_capped_auctions, campaign_b.eligible_impressions.unwrap_or(0.0)),
+            )
+        })
+        // Check: With part of the supply out of reach, the capped campaign pays more per impression
+        .assert(|result, logger, errors| assertions::assert_greater(
+            "Variant B (capped) pays more per impression than variant A (uncapped)",
+            cost_per_impression(&result.variant("capped").campaign_stats[0]),
+            cost_per_impression(&result.baseline.campaign_stats[0]),
+            logger,
+            errors,
+        ))
+}
@@ -0,0 +1,144 @@
+//! This scenario demonstrates tuning controller gains with the black-box tuner.
+//!
+//! Two campaigns (impressions and budget targets) are paced by PD controllers allowed to adjust by up to 50%
+//! an iteration. The tuner searches the proportional and derivative gains of both controllers for the fewest
+//! iterations to converge meeting the targets, starting from the default gains:
+//!
+//! - Variant A: Default gains (0.1 and 0.05)
+//!
+//! - Variant B: Gains found by the tuner (separable CMA-ES, 8 generations of 6 configurations)
+//!
+//! The default gains take small steps towards the targets, so the tuned gains should converge in fewer
+//! iterations while meeting the same targets.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, ControllerConfig};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::tuning::Tuner;
+use crate::logger::{Logger, LogEvent, sanitize_filename};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "gain_tuning",
+    run,
+    tags: &["slow", "controllers", "analysis"],
+});
+
+/// Default proportional and derivative gains of the PD controller
+const DEFAULT_GAINS: [f64; 2] = [0.1, 0.05];
+/// Maximum iterations of a run
+const MAX_ITERATIONS: usize = 200;
+/// Generations and configurations per generation of the search
+const GENERATIONS: usize = 8;
+const POPULATION_SIZE: usize = 6;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(proportional_gain: f64, derivative_gain: f64) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index), paced with the given gains
+    let controller_config = ControllerConfig::new().with_max_adjustment_factor(0.5).with_gains(proportional_gain, derivative_gain).with_bounds(0.01, 10.0);
+    campaigns.add_configured(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+        vec![controller_config.clone()],  // controller_configs
+    );
+    campaigns.add_configured(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+        vec![controller_config],  // controller_configs
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Objective of the search: the fewer iterations to converge the better, with runs that converge without
+/// meeting their targets (e.g. pacing stuck at a bound) ranked below all others
+fn objective(stats: &SimulationStat) -> f64 {
+    if stats.campaign_targets.iter().flatten().all(|target| target.is_met()) {
+        -(stats.convergence_iterations as f64)
+    } else {
+        -(MAX_ITERATIONS as f64) - 1.0
+    }
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with the default gains
+    let simulation_converge_a = prepare_simulationconverge(DEFAULT_GAINS[0], DEFAULT_GAINS[1]);
+    let stats_a = simulation_converge_a.run_variant("Running with default gains", scenario_name, "default-gains", MAX_ITERATIONS, logger)?;
+
+    // Run variant B, searching the gains converging in the fewest iterations
+    let tuner = Tuner::new("gains")
+        .with_parameter("proportional_gain", 0.02, 1.0)
+        .with_parameter("derivative_gain", 0.0, 0.5)
+        .with_initial_values(DEFAULT_GAINS.to_vec())
+        .with_budget(GENERATIONS, POPULATION_SIZE);
+    let result = tuner.run(
+        |values| prepare_simulationconverge(values[0], values[1]),
+        objective,
+        scenario_name,
+        MAX_ITERATIONS,
+        logger,
+    )?;
+    let stats_b = &result.best_stats;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (default gains)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (tuned gains)", stats_b, logger, &mut errors);
+
+    // Check: The tuned gains converge in fewer iterations
+    let tuned_gains: Vec<String> = result.parameter_names.iter().zip(&result.best.parameter_values).map(|(name, value)| format!("{} {:.4}", name, value)).collect();
+    assertions::assert_greater(
+        &format!("Variant A (default gains) needs more iterations to converge than variant B (tuned gains: {})", tuned_gains.join(", ")),
+        stats_a.convergence_iterations as f64,
+        stats_b.convergence_iterations as f64,
+        logger,
+        &mut errors,
+    );
+
+    // Check: The trace has every evaluated configuration, also written as a CSV with a header
+    let csv = std::fs::read_to_string(logger.log_path(&format!("{}/tuning-gains.csv", sanitize_filename(scenario_name))))?;
+    validation::check(
+        result.trace.len() == GENERATIONS * POPULATION_SIZE && csv.lines().count() == result.trace.len() + 1,
+        format!("Trace has {} evaluations ({} generations of {}), with a CSV line each: {} lines", result.trace.len(), GENERATIONS, POPULATION_SIZE, csv.lines().count()),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
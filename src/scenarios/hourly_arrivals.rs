@@ -0,0 +1,141 @@
+//! This scenario demonstrates impressions arriving over the day following hourly arrival rates.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, with impressions arriving:
+//!
+//! - Variant A: Uniformly over the day
+//!
+//! - Variant B: As a Poisson process with hourly rates, low at night and peaking in the evening
+//!
+//! The hourly breakdowns of the statistics should follow the arrival rates: the impressions offered per
+//! hour match the rate curve, and with evening traffic peaking the campaigns buy more of their
+//! impressions in the evening.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, HourlyArrivalRates};
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "hourly_arrivals",
+    run,
+    tags: &["fast"],
+});
+
+/// Relative arrival rates of the hours of the day: low at night, rising over the day, peaking in the evening
+const HOURLY_RATES: [f64; 24] = [
+    0.4, 0.3, 0.2, 0.2, 0.2, 0.3, 0.5, 0.8, 1.0, 1.0, 1.0, 1.1,
+    1.2, 1.1, 1.0, 1.0, 1.1, 1.3, 1.6, 2.0, 2.2, 2.0, 1.4, 0.8,
+];
+/// Evening peak hours [from, to)
+const EVENING_HOURS: std::ops::Range<usize> = 18..22;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(hourly_rates: Option<Vec<f64>>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, arriving uniformly unless hourly rates are given
+    let mut impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+    if let Some(hourly_rates) = hourly_rates {
+        impressions_params = impressions_params.with_hourly_arrival_rates(hourly_rates);
+    }
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Total variation distance between the hourly shares of offered impressions and the expected shares
+fn hourly_share_distance(stats: &SimulationStat, expected_share: impl Fn(usize) -> f64) -> f64 {
+    let total: usize = stats.hour_impressions_offered.iter().sum();
+    stats.hour_impressions_offered.iter().enumerate()
+        .map(|(hour, &offered)| (offered as f64 / total as f64 - expected_share(hour)).abs())
+        .sum::<f64>() / 2.0
+}
+
+/// Share of the impressions campaign's impressions bought in the evening peak hours
+fn evening_share(stats: &SimulationStat) -> f64 {
+    let campaign = &stats.campaign_stats[0];
+    let evening: f64 = campaign.hour_breakdown[EVENING_HOURS].iter().map(|hour_stat| hour_stat.impressions_obtained).sum();
+    evening / campaign.impressions_obtained
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with uniform arrivals
+    let simulation_converge_a = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_variant("Running with uniform arrivals", scenario_name, "uniform", 100, logger)?;
+
+    // Run variant B with Poisson arrivals following the hourly rates
+    let simulation_converge_b = prepare_simulationconverge(Some(HOURLY_RATES.to_vec()));
+    let stats_b = simulation_converge_b.run_variant("Running with hourly arrival rates", scenario_name, "hourly_rates", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (uniform)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (hourly rates)", &stats_b, logger, &mut errors);
+
+    // Check: Impressions offered per hour follow the arrival rates
+    let arrival_rates = HourlyArrivalRates::new(HOURLY_RATES.to_vec());
+    let distance_a = hourly_share_distance(&stats_a, |_| 1.0 / HOURLY_RATES.len() as f64);
+    let distance_b = hourly_share_distance(&stats_b, |hour| arrival_rates.hour_share(hour));
+    for (label, distance) in [("A (uniform)", distance_a), ("B (hourly rates)", distance_b)] {
+        assertions::assert_between(
+            &format!("Variant {} hourly shares of offered impressions match the arrival rates, total variation distance", label),
+            distance,
+            0.0,
+            0.03,
+            logger,
+            &mut errors,
+        );
+    }
+
+    // Check: With evening traffic peaking, more impressions are bought in the evening
+    assertions::assert_greater(
+        "Variant B (hourly rates) buys a larger share of impressions in the evening than variant A (uniform)",
+        evening_share(&stats_b),
+        evening_share(&stats_a),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
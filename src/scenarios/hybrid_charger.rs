@@ -0,0 +1,128 @@
+//! This scenario demonstrates a seller with a hybrid contract: a fixed fee per impression plus a
+//! revenue share of the clearing price.
+//!
+//! Two campaigns buy from a first price HB seller and from an SSP seller.
+//!
+//! - Variant A: The SSP is paid the clearing price (first price)
+//!
+//! - Variant B: The SSP is paid a hybrid of 1.0 CPM per impression on offer plus 80% of the clearing price
+//!
+//! Buyers see the same first price auction on the SSP in both variants, only the supply cost differs.
+//! In variant A the supply cost of the SSP equals its buyer charge. In variant B it should be the fixed
+//! fee on every impression on offer plus the revenue share of the buyer charge.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "hybrid_charger",
+    run,
+    tags: &["fast", "supply"],
+});
+
+/// Hybrid contract of the SSP in variant B
+const FIXED_FEE_CPM: f64 = 1.0;
+const REVENUE_SHARE: f64 = 0.8;
+/// Number of impressions the SSP has on offer
+const SSP_IMPRESSIONS_ON_OFFER: usize = 5000;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(ssp_seller_type: SellerType) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add HB seller
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Add SSP seller
+    sellers.add(
+        "SSP".to_string(),  // seller_name
+        ssp_seller_type,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        SSP_IMPRESSIONS_ON_OFFER,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with a first price SSP
+    let simulation_converge_a = prepare_simulationconverge(SellerType::FIRST_PRICE);
+    let stats_a = simulation_converge_a.run_variant("Running with a first price SSP", scenario_name, "first_price", 100, logger)?;
+
+    // Run variant B with a hybrid SSP contract
+    let simulation_converge_b = prepare_simulationconverge(SellerType::HYBRID { fixed_fee_cpm: FIXED_FEE_CPM, revenue_share: REVENUE_SHARE });
+    let stats_b = simulation_converge_b.run_variant("Running with a hybrid fee and revenue share SSP", scenario_name, "hybrid", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Campaigns reach their targets in both variants
+    validation::assert_targets_met("Variant A (first price)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (hybrid)", &stats_b, logger, &mut errors);
+
+    // Check: First price SSP is paid what buyers are charged
+    let ssp_a = &stats_a.seller_stats[1];
+    validation::check(
+        (ssp_a.total_supply_cost - ssp_a.total_buyer_charge).abs() < 1e-9,
+        format!("Variant A (first price) pays the SSP its buyer charge: {:.4} == {:.4}", ssp_a.total_supply_cost, ssp_a.total_buyer_charge),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Hybrid SSP is paid the fixed fee on every impression on offer plus the revenue share
+    let ssp_b = &stats_b.seller_stats[1];
+    let expected_supply_cost_b = FIXED_FEE_CPM * SSP_IMPRESSIONS_ON_OFFER as f64 / 1000.0 + REVENUE_SHARE * ssp_b.total_buyer_charge;
+    validation::check(
+        (ssp_b.total_supply_cost - expected_supply_cost_b).abs() < 1e-6,
+        format!("Variant B (hybrid) pays the SSP fee plus revenue share: {:.4} == {:.2} × {} / 1000 + {:.2} × {:.4}",
+            ssp_b.total_supply_cost, FIXED_FEE_CPM, SSP_IMPRESSIONS_ON_OFFER, REVENUE_SHARE, ssp_b.total_buyer_charge),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,133 @@
+//! This scenario demonstrates saving generated impressions to a file and loading them for another run.
+//!
+//! Two campaigns (impressions and budget targets, the budget campaign targeted at one of two audience
+//! segments) buy from a single seller, with impressions shown to 2000 users:
+//!
+//! - Variant A: Impressions generated as usual, then saved to a file
+//!
+//! - Variant B: Impressions loaded from the file (Impressions::load and Marketplace::new_shared)
+//!
+//! The loaded impressions are exactly the saved ones, so both variants converge to exactly the same results.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{Impressions, ImpressionsParam};
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "impressions_file",
+    run,
+    tags: &["fast"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Offers the given impressions, or generates them when there are none
+fn prepare_simulationconverge(impressions: Option<Rc<Impressions>>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    let targeted_campaign_id = campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+    campaigns.set_segment_targeting(targeted_campaign_id, vec![1]);
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions (loaded or generated)
+    let marketplace = match impressions {
+        Some(impressions) => Marketplace::new_shared(campaigns, sellers, impressions, SimulationType::Standard),
+        None => {
+            let impressions_params = ImpressionsParam::new(
+                utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+                utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+            )
+            .with_users(2000)
+            .with_segments(vec![1.0, 3.0]);
+            Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard)
+        }
+    };
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Impressions, spend and value of every campaign
+fn campaign_results(stats: &SimulationStat) -> Vec<(f64, f64, f64)> {
+    stats.campaign_stats.iter()
+        .map(|campaign| (campaign.impressions_obtained, campaign.total_buyer_charge, campaign.total_value))
+        .collect()
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with generated impressions, saving them to a file
+    let simulation_converge_a = prepare_simulationconverge(None);
+    let path = std::env::temp_dir().join(format!("marrakesh_{}_{}.impressions", scenario_name, std::process::id()));
+    let path = path.to_str().ok_or("Temporary directory path is not valid UTF-8")?;
+    simulation_converge_a.marketplace.impressions.save(path)?;
+    let file_size = std::fs::metadata(path)?.len();
+    let stats_a = simulation_converge_a.run_variant("Running with generated impressions", scenario_name, "generated", 100, logger)?;
+
+    // Run variant B with the impressions loaded from the file
+    let loaded = Impressions::load(path);
+    std::fs::remove_file(path)?;
+    let simulation_converge_b = prepare_simulationconverge(Some(Rc::new(loaded?)));
+    let stats_b = simulation_converge_b.run_variant("Running with impressions loaded from a file", scenario_name, "loaded", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (generated)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (loaded)", &stats_b, logger, &mut errors);
+
+    let num_impressions = simulation_converge_a.marketplace.impressions.impressions.len();
+    logln!(logger, LogEvent::Scenario, "Saved {} impressions in {} bytes ({:.1} bytes per impression)",
+        num_impressions, file_size, file_size as f64 / num_impressions as f64);
+
+    // Check: All impressions are loaded, with their users and segments
+    let impressions_a = &simulation_converge_a.marketplace.impressions;
+    let impressions_b = &simulation_converge_b.marketplace.impressions;
+    validation::check(
+        impressions_b.impressions.len() == num_impressions && impressions_b.num_segments == impressions_a.num_segments
+            && impressions_b.impressions.iter().zip(&impressions_a.impressions).all(|(b, a)| b.user_id == a.user_id && b.segment == a.segment),
+        format!("Variant B (loaded) offers the {} saved impressions with their users and {} segments", impressions_b.impressions.len(), impressions_b.num_segments),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Loaded impressions give exactly the same results
+    validation::check(
+        campaign_results(&stats_a) == campaign_results(&stats_b),
+        "Variant B (loaded) campaigns obtain exactly the impressions, spend and value of variant A (generated)".to_string(),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
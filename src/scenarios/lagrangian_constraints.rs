@@ -0,0 +1,127 @@
+//! This scenario demonstrates a campaign with three constraints handled by dual decomposition.
+//!
+//! - Variant A: Max margin campaign converging to a budget with a PD controller
+//!
+//! - Variant B: Lagrangian max margin campaign with a maximum budget, a minimum number of impressions
+//!   and a minimum ROAS, each constraint with a dual variable updated by subgradient steps
+//!
+//! Spending the budget on max margin bidding obtains fewer impressions than required. Variant B should
+//! satisfy all three constraints at once. The ROAS constraint has slack, so its dual should stay at zero.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignLagrangian, LagrangianConstraint};
+use crate::converge::SimulationConverge;
+use crate::controllers::ControllerStateDual;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "lagrangian_constraints",
+    run,
+    tags: &["slow"],
+});
+
+const MAX_BUDGET: f64 = 40.0;
+const MIN_IMPRESSIONS: i32 = 4200;
+const MIN_ROAS: f64 = 1.03;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(lagrangian: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    if lagrangian {
+        campaigns.add_advanced(Box::new(CampaignLagrangian::new(
+            "Campaign 0".to_string(),  // campaign_name
+            vec![
+                LagrangianConstraint::MAX_BUDGET { budget: MAX_BUDGET },
+                LagrangianConstraint::MIN_IMPRESSIONS { impressions: MIN_IMPRESSIONS },
+                LagrangianConstraint::MIN_ROAS { roas: MIN_ROAS },
+            ],  // constraints
+        )));
+    } else {
+        campaigns.add(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MAX_MARGIN,  // campaign_type
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: MAX_BUDGET }],  // converge_target
+        );
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with max margin bidding to a budget
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running max margin with a budget", scenario_name, "budget-only", 100, logger)?;
+
+    // Run variant B with the Lagrangian campaign
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let (stats_b, campaign_states_b, _) = simulation_converge_b.run_variant_with_states("Running Lagrangian max margin with budget, impressions and ROAS constraints", scenario_name, "lagrangian", 500, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+    let min_impressions = MIN_IMPRESSIONS as f64;
+
+    // Check: Budget-only max margin misses the impressions constraint
+    let impressions_a = stats_a.campaign_stats[0].impressions_obtained;
+    let roas_a = stats_a.campaign_stats[0].total_value / 1000.0 / stats_a.campaign_stats[0].total_buyer_charge;
+    assertions::assert_greater(
+        &format!("Variant A (budget only) obtains fewer impressions than required (ROAS {:.2}), impressions required", roas_a),
+        min_impressions,
+        impressions_a,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Lagrangian campaign satisfies all constraints (within 1%)
+    let stat_b = &stats_b.campaign_stats[0];
+    let roas_b = stat_b.total_value / 1000.0 / stat_b.total_buyer_charge;
+    let constraint_checks = [
+        (format!("spend {:.4} <= {:.1}", stat_b.total_buyer_charge, MAX_BUDGET), stat_b.total_buyer_charge <= MAX_BUDGET * 1.01),
+        (format!("impressions {:.0} >= {}", stat_b.impressions_obtained, MIN_IMPRESSIONS), stat_b.impressions_obtained >= min_impressions * 0.99),
+        (format!("ROAS {:.4} >= {:.2}", roas_b, MIN_ROAS), roas_b >= MIN_ROAS * 0.99),
+    ];
+    for (description, satisfied) in constraint_checks {
+        let msg = format!("Variant B (Lagrangian) satisfies constraint: {}", description);
+        validation::check(satisfied, msg, logger, &mut errors);
+    }
+
+    // Check: Only binding constraints have non-zero duals
+    let duals = &campaign_states_b.campaign_controller_states[0][0].as_any().downcast_ref::<ControllerStateDual>().unwrap().duals;
+    let msg = format!("Variant B (Lagrangian) has zero dual on the slack ROAS constraint and positive duals on budget and impressions: {:.4?}", duals);
+    validation::check(duals[0] > 0.0 && duals[1] > 0.0 && duals[2] == 0.0, msg, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
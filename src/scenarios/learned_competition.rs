@@ -0,0 +1,142 @@
+//! This scenario compares a bidder that learns its own win rate model with bidders relying on the provided one.
+//!
+//! All variants spend the same budget:
+//!
+//! - Variant A: Multiplicative pacing, bidding truthfully (no win rate model)
+//!
+//! - Variant B: Max margin bidding using the provided win rate prediction
+//!
+//! - Variant C: Max margin bidding using a win rate model fitted on the campaign's own wins and losses,
+//!   accumulated across convergence iterations
+//!
+//! The learned model only sees censored outcomes of its own bids, yet it should still capture enough
+//! of the competition to obtain more value per spend than truthful bidding.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignLearnedCompetition, CampaignTargetTotalBudget};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "learned_competition",
+    run,
+    tags: &[],
+});
+
+/// Bidding strategy of the single campaign
+enum Bidder {
+    Truthful,
+    ProvidedModel,
+    LearnedModel,
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(bidder: Bidder) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    match bidder {
+        Bidder::Truthful => {
+            campaigns.add(
+                "Campaign 0".to_string(),  // campaign_name
+                CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+                vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+            );
+        }
+        Bidder::ProvidedModel => {
+            campaigns.add(
+                "Campaign 0".to_string(),  // campaign_name
+                CampaignType::MAX_MARGIN,  // campaign_type
+                vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+            );
+        }
+        Bidder::LearnedModel => {
+            campaigns.add_advanced(Box::new(CampaignLearnedCompetition::new(
+                "Campaign 0".to_string(),  // campaign_name
+                Box::new(CampaignTargetTotalBudget { total_budget_target: 20.0 }),  // converge_target
+            )));
+        }
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Value obtained per unit of spend by the single campaign
+fn value_per_spend(stats: &SimulationStat) -> f64 {
+    let campaign = &stats.campaign_stats[0];
+    if campaign.total_buyer_charge > 0.0 {
+        campaign.total_value / campaign.total_buyer_charge
+    } else {
+        0.0
+    }
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with truthful bidding
+    let simulation_converge_a = prepare_simulationconverge(Bidder::Truthful);
+    let stats_a = simulation_converge_a.run_variant("Running with truthful bidding", scenario_name, "truthful", 100, logger)?;
+
+    // Run variant B with max margin bidding on the provided win rate prediction
+    let simulation_converge_b = prepare_simulationconverge(Bidder::ProvidedModel);
+    let stats_b = simulation_converge_b.run_variant("Running with provided win rate prediction", scenario_name, "provided", 100, logger)?;
+
+    // Run variant C with max margin bidding on the learned win rate model
+    let simulation_converge_c = prepare_simulationconverge(Bidder::LearnedModel);
+    let stats_c = simulation_converge_c.run_variant("Running with learned win rate model", scenario_name, "learned", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+    let value_per_spend_a = value_per_spend(&stats_a);
+    let value_per_spend_b = value_per_spend(&stats_b);
+    let value_per_spend_c = value_per_spend(&stats_c);
+
+    logln!(logger, LogEvent::Scenario, "Value per spend: truthful {:.4}, provided model {:.4}, learned model {:.4}",
+        value_per_spend_a, value_per_spend_b, value_per_spend_c);
+
+    // Check: Learned model obtains more value per spend than truthful bidding
+    assertions::assert_greater(
+        "Variant C (learned model) has higher value per spend than variant A (truthful)",
+        value_per_spend_c,
+        value_per_spend_a,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Learned campaign spends its budget like the others
+    let spend_c = stats_c.campaign_stats[0].total_buyer_charge;
+    assertions::assert_within_pct("Variant C (learned model) spends its budget", spend_c, 20.0, 5.0, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
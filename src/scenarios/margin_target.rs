@@ -0,0 +1,103 @@
+//! This scenario converges a max margin campaign on a margin target.
+//!
+//! The campaign's margin is (obtained value - spend) / obtained value. Pacing controls it: bidding
+//! more aggressively buys more impressions at a lower margin.
+//!
+//! - Variant A: Margin target of 0.2
+//!
+//! - Variant B: Margin target of 0.4
+//!
+//! Both variants should hit their margin. The higher margin needs less aggressive bidding, so
+//! variant B obtains fewer impressions and spends less than variant A.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "margin_target",
+    run,
+    tags: &[],
+});
+
+/// Margin targets of variants A and B
+const TARGET_MARGIN_A: f64 = 0.2;
+const TARGET_MARGIN_B: f64 = 0.4;
+/// Allowed absolute deviation of the margin from its target
+const MARGIN_TOLERANCE: f64 = 0.005;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(target_margin: f64) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::MARGIN { target_margin }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with the lower margin target
+    let simulation_converge_a = prepare_simulationconverge(TARGET_MARGIN_A);
+    let stats_a = simulation_converge_a.run_variant("Running with margin target 0.2", scenario_name, "margin_02", 100, logger)?;
+
+    // Run variant B with the higher margin target
+    let simulation_converge_b = prepare_simulationconverge(TARGET_MARGIN_B);
+    let stats_b = simulation_converge_b.run_variant("Running with margin target 0.4", scenario_name, "margin_04", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::check_margin("Variant A (margin 0.2)", &stats_a.campaign_stats[0], TARGET_MARGIN_A, MARGIN_TOLERANCE, logger, &mut errors);
+    validation::check_margin("Variant B (margin 0.4)", &stats_b.campaign_stats[0], TARGET_MARGIN_B, MARGIN_TOLERANCE, logger, &mut errors);
+
+    // Check: The higher margin is bought with fewer impressions and less spend
+    let (impressions_a, impressions_b) = (stats_a.campaign_stats[0].impressions_obtained, stats_b.campaign_stats[0].impressions_obtained);
+    let (spend_a, spend_b) = (stats_a.campaign_stats[0].total_buyer_charge, stats_b.campaign_stats[0].total_buyer_charge);
+    assertions::assert_greater("Variant A (margin 0.2) obtains more impressions than variant B (margin 0.4)", impressions_a, impressions_b, logger, &mut errors);
+    assertions::assert_greater("Variant A (margin 0.2) spends more than variant B (margin 0.4)", spend_a, spend_b, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -22,12 +22,14 @@ use crate::floors;
 use crate::utils;
 use crate::logger::{Logger, LogEvent};
 use crate::logln;
-use crate::errln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
 
 // Register this scenario in the catalog
 inventory::submit!(crate::scenarios::ScenarioEntry {
     short_name: "median_bidder",
     run,
+    tags: &["fractional"],
 });
 
 /// Prepare simulation converge instance with campaign and seller setup
@@ -166,62 +168,42 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     
     // Low impressions validations
     // Validation 1: Low impressions (5000) - Median Bidding should work worse than multiplicative
-    let msg = format!(
-        "Low impressions (5000): Multiplicative pacing obtained value > Median Bidding obtained value: {:.2} > {:.2}",
+    assertions::assert_greater(
+        "Low impressions (5000): Multiplicative pacing obtained value > Median Bidding obtained value",
         stats_mult_low.overall_stat.total_value,
-        stats_median_low.overall_stat.total_value
+        stats_median_low.overall_stat.total_value,
+        logger,
+        &mut errors,
     );
-    if stats_mult_low.overall_stat.total_value > stats_median_low.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
     
     // Validation 2: Low impressions - Max margin should capture more value than Median Bidding
-    let msg = format!(
-        "Low impressions (5000): Max margin obtained value > Median Bidding obtained value: {:.2} > {:.2}",
+    assertions::assert_greater(
+        "Low impressions (5000): Max margin obtained value > Median Bidding obtained value",
         stats_maxmargin_low.overall_stat.total_value,
-        stats_median_low.overall_stat.total_value
+        stats_median_low.overall_stat.total_value,
+        logger,
+        &mut errors,
     );
-    if stats_maxmargin_low.overall_stat.total_value > stats_median_low.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
     
     // High impressions validations
     // Validation 3: High impressions (50000) - Median Bidding should work better than multiplicative
-    let msg = format!(
-        "High impressions (50000): Median Bidding obtained value > Multiplicative pacing obtained value: {:.2} > {:.2}",
+    assertions::assert_greater(
+        "High impressions (50000): Median Bidding obtained value > Multiplicative pacing obtained value",
         stats_median_high.overall_stat.total_value,
-        stats_mult_high.overall_stat.total_value
+        stats_mult_high.overall_stat.total_value,
+        logger,
+        &mut errors,
     );
-    if stats_median_high.overall_stat.total_value > stats_mult_high.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
     
     // Validation 4: High impressions - Max margin should capture more value than Median Bidding
-    let msg = format!(
-        "High impressions (50000): Max margin obtained value > Median Bidding obtained value: {:.2} > {:.2}",
+    assertions::assert_greater(
+        "High impressions (50000): Max margin obtained value > Median Bidding obtained value",
         stats_maxmargin_high.overall_stat.total_value,
-        stats_median_high.overall_stat.total_value
+        stats_median_high.overall_stat.total_value,
+        logger,
+        &mut errors,
     );
-    if stats_maxmargin_high.overall_stat.total_value > stats_median_high.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
-    
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(format!("Scenario '{}' validation failed:\n{}", scenario_name, errors.join("\n")).into())
-    }
+    
+    validation::scenario_result(scenario_name, errors)
 }
 
@@ -0,0 +1,170 @@
+//! This scenario shows a minimum margin per impression constraint on a max margin bidder.
+//!
+//! Arbitrage-style campaigns only buy impressions they can resell with a profit, so each impression
+//! must leave a minimum margin between value and price. Both campaigns bid with fixed pacing.
+//!
+//! - Variant A: Max margin bidding without a margin constraint
+//!
+//! - Variant B: Max margin bidding that abstains when the margin is below 2.0 CPM
+//!
+//! - Variant C: Max margin bidding that abstains when the margin is below 20% of the value
+//!
+//! Max margin bids leave thin margins on impressions with strong competition. The constrained
+//! campaigns skip those, so every impression variant B wins has a margin of at least 2.0 CPM and
+//! every impression variant C wins a margin of at least 20% of its value.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, MinimumMargin};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::hooks::AuctionOutcome;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "minimum_margin",
+    run,
+    tags: &["fast"],
+});
+
+/// Minimum margin in CPM required by variant B
+const MINIMUM_MARGIN_CPM: f64 = 2.0;
+/// Minimum margin as a fraction of value required by variant C
+const MINIMUM_MARGIN_FRACTION: f64 = 0.2;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the smallest margin (in CPM and as a fraction of value) of a won impression
+/// in the last iteration
+fn prepare_simulationconverge(minimum_margin: Option<MinimumMargin>) -> (SimulationConverge, Rc<Cell<f64>>, Rc<Cell<f64>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::NONE { default_pacing: 1.0 }],  // converge_target
+    );
+    if let Some(minimum_margin) = minimum_margin {
+        campaigns.set_minimum_margin(campaign_id, minimum_margin);
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Track the smallest margin (value minus price) of a won impression, in CPM and as a fraction of value
+    let smallest_margin = Rc::new(Cell::new(f64::INFINITY));
+    let smallest_margin_fraction = Rc::new(Cell::new(f64::INFINITY));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let smallest_margin_reset = smallest_margin.clone();
+        let smallest_margin_fraction_reset = smallest_margin_fraction.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            smallest_margin_reset.set(f64::INFINITY);
+            smallest_margin_fraction_reset.set(f64::INFINITY);
+        });
+        let smallest_margin_update = smallest_margin.clone();
+        let smallest_margin_fraction_update = smallest_margin_fraction.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                if let Winner::Campaign { buyer_charge, .. } = result.winner {
+                    // Buyer charge is per impression, value and margin are in CPM
+                    let value = event.impression.value_to_campaign_group[0];
+                    let margin = value - buyer_charge * 1000.0;
+                    smallest_margin_update.set(smallest_margin_update.get().min(margin));
+                    smallest_margin_fraction_update.set(smallest_margin_fraction_update.get().min(margin / value));
+                }
+            }
+        });
+    }
+
+    (simulation_converge, smallest_margin, smallest_margin_fraction)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A without a margin constraint
+    let (simulation_converge_a, smallest_margin_a, smallest_margin_fraction_a) = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_variant("Running max margin without minimum margin", scenario_name, "unconstrained", 100, logger)?;
+
+    // Run variant B with a minimum absolute margin
+    let (simulation_converge_b, smallest_margin_b, _) = prepare_simulationconverge(Some(MinimumMargin::ABSOLUTE(MINIMUM_MARGIN_CPM)));
+    let stats_b = simulation_converge_b.run_variant("Running max margin with minimum margin of 2.0 CPM", scenario_name, "minimum-margin", 100, logger)?;
+
+    // Run variant C with a minimum margin relative to value
+    let (simulation_converge_c, _, smallest_margin_fraction_c) = prepare_simulationconverge(Some(MinimumMargin::RELATIVE(MINIMUM_MARGIN_FRACTION)));
+    let stats_c = simulation_converge_c.run_variant("Running max margin with minimum margin of 20% of value", scenario_name, "minimum-margin-relative", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+    let campaign_a = &stats_a.campaign_stats[0];
+    let campaign_b = &stats_b.campaign_stats[0];
+
+    // Check: Every impression won by variant B has at least the minimum margin, variant A wins some below it
+    let msg = format!(
+        "Variant B (minimum margin) wins only impressions with margin of at least {:.2}, variant A (unconstrained) also below it: {:.4} >= {:.2}, {:.4} < {:.2}",
+        MINIMUM_MARGIN_CPM,
+        smallest_margin_b.get(),
+        MINIMUM_MARGIN_CPM,
+        smallest_margin_a.get(),
+        MINIMUM_MARGIN_CPM
+    );
+    validation::check(smallest_margin_b.get() >= MINIMUM_MARGIN_CPM - 1e-9 && smallest_margin_a.get() < MINIMUM_MARGIN_CPM, msg, logger, &mut errors);
+
+    // Check: Variant B has a higher average margin (in CPM) per won impression than variant A
+    let avg_margin_a = if campaign_a.impressions_obtained > 0.0 { (campaign_a.total_value - campaign_a.total_buyer_charge * 1000.0) / campaign_a.impressions_obtained } else { 0.0 };
+    let avg_margin_b = if campaign_b.impressions_obtained > 0.0 { (campaign_b.total_value - campaign_b.total_buyer_charge * 1000.0) / campaign_b.impressions_obtained } else { 0.0 };
+    assertions::assert_greater(
+        "Variant B (minimum margin) has higher average margin per impression than variant A (unconstrained)",
+        avg_margin_b,
+        avg_margin_a,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Every impression won by variant C has at least the minimum margin relative to value, variant A wins
+    // some below it
+    let msg = format!(
+        "Variant C (relative minimum margin) wins only impressions with margin of at least {:.0}% of value, variant A (unconstrained) also below it: {:.4} >= {:.2}, {:.4} < {:.2}",
+        MINIMUM_MARGIN_FRACTION * 100.0,
+        smallest_margin_fraction_c.get(),
+        MINIMUM_MARGIN_FRACTION,
+        smallest_margin_fraction_a.get(),
+        MINIMUM_MARGIN_FRACTION
+    );
+    validation::check(smallest_margin_fraction_c.get() >= MINIMUM_MARGIN_FRACTION - 1e-9 && smallest_margin_fraction_a.get() < MINIMUM_MARGIN_FRACTION, msg, logger, &mut errors);
+
+    // Check: Variant C still wins impressions
+    assertions::assert_greater("Variant C (relative minimum margin) wins impressions", stats_c.campaign_stats[0].impressions_obtained, 0.0, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
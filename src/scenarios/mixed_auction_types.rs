@@ -0,0 +1,144 @@
+//! This scenario demonstrates a marketplace mixing auction mechanisms across its sellers.
+//!
+//! Two campaigns buy from three sellers: a first price seller, a second price seller and a third seller
+//! whose auction type is varied.
+//!
+//! - Variant A: All sellers run the marketplace's Standard auctions
+//!
+//! - Variant B: The third seller overrides the marketplace's auction type with fractional auctions
+//!
+//! In variant B the third seller's impressions are split fractionally between the campaigns, while the
+//! other sellers keep selling whole impressions, the second price seller charging below the winning bids.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "mixed_auction_types",
+    run,
+    tags: &["fast", "fractional", "supply"],
+});
+
+/// Seller IDs
+const FIRST_PRICE_SELLER: usize = 0;
+const SECOND_PRICE_SELLER: usize = 1;
+const FRACTIONAL_SELLER: usize = 2;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(fractional_seller: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 3000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 40.0 }],  // converge_target
+    );
+
+    // Add sellers (IDs are automatically set to match Vec index)
+    sellers.add(
+        "First price".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+    sellers.add(
+        "Second price".to_string(),  // seller_name
+        SellerType::SECOND_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+    sellers.add(
+        "Fractional".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let mut marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+    if fractional_seller {
+        marketplace = marketplace.with_seller_simulation_type(FRACTIONAL_SELLER, SimulationType::FractionalInternalAuction { softmax_temperature: 0.5 });
+    }
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Whether some campaign obtained a fraction of an impression from the seller
+fn sells_fractions(stats: &SimulationStat, seller_id: usize) -> bool {
+    stats.campaign_stats.iter().any(|campaign_stat| campaign_stat.seller_breakdown[seller_id].impressions_obtained.fract() > 1e-9)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with Standard auctions on all sellers
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running with Standard auctions on all sellers", scenario_name, "standard", 100, logger)?;
+
+    // Run variant B with fractional auctions on the third seller
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with fractional auctions on the third seller", scenario_name, "mixed", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (standard)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (mixed)", &stats_b, logger, &mut errors);
+
+    // Check: Only the seller with fractional auctions sells fractions of impressions
+    validation::check(
+        !sells_fractions(&stats_a, FRACTIONAL_SELLER) && sells_fractions(&stats_b, FRACTIONAL_SELLER),
+        "Only variant B (mixed) splits impressions of the third seller between campaigns".to_string(),
+        logger,
+        &mut errors,
+    );
+    validation::check(
+        !sells_fractions(&stats_b, FIRST_PRICE_SELLER) && !sells_fractions(&stats_b, SECOND_PRICE_SELLER),
+        "Variant B (mixed) keeps selling whole impressions on the first and second price sellers".to_string(),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Second price seller charges below the winning bids, the first price seller charges them
+    let first_price = &stats_b.seller_stats[FIRST_PRICE_SELLER];
+    let second_price = &stats_b.seller_stats[SECOND_PRICE_SELLER];
+    validation::check(
+        (first_price.total_buyer_charge - first_price.total_virtual_cost).abs() < 1e-9 && second_price.total_buyer_charge < second_price.total_virtual_cost,
+        format!("Variant B (mixed) charges first price {:.4} == {:.4} and second price {:.4} < {:.4} (buyer charge vs. winning bids)",
+            first_price.total_buyer_charge, first_price.total_virtual_cost, second_price.total_buyer_charge, second_price.total_virtual_cost),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -4,16 +4,51 @@ use crate::logger::Logger;
 /// Function type for scenario entry functions
 pub type ScenarioFn = fn(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn Error>>;
 
+/// Tags scenarios can have, with their meaning, for selecting scenarios to run (`--tag` and `--exclude`)
+pub const SCENARIO_TAGS: &[(&str, &str)] = &[
+    ("fast", "runs in under 0.2 seconds in a release build"),
+    ("slow", "runs for a second or more in a release build"),
+    ("floors", "studies floors and how bidders deal with them"),
+    ("fractional", "uses fractional internal auctions"),
+    ("supply", "studies sellers: boosts, pricing models, fees and supply paths"),
+    ("controllers", "studies the controllers converging pacing and boosts"),
+    ("multi_day", "runs impressions over several days"),
+    ("viewability", "values or measures viewability"),
+    ("analysis", "uses an analysis tool such as sweeps, seeds aggregation or counterfactuals"),
+];
+
 /// Entry in the scenario catalog
 #[derive(Clone)]
 pub struct ScenarioEntry {
     pub short_name: &'static str,
     pub run: ScenarioFn,
+    /// Tags of the scenario, from SCENARIO_TAGS
+    pub tags: &'static [&'static str],
+}
+
+impl ScenarioEntry {
+    /// Whether the scenario has the tag
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag)
+    }
 }
 
 // Create an inventory collection for scenario entries
 inventory::collect!(ScenarioEntry);
 
+/// Whether the tag is one of SCENARIO_TAGS
+pub fn is_known_tag(tag: &str) -> bool {
+    SCENARIO_TAGS.iter().any(|(known_tag, _)| *known_tag == tag)
+}
+
+/// Select the scenarios having any of the tags (all scenarios when there are none) and none of the excluded tags
+pub fn filter_by_tags(scenarios: Vec<ScenarioEntry>, tags: &[String], excluded_tags: &[String]) -> Vec<ScenarioEntry> {
+    scenarios.into_iter()
+        .filter(|scenario| tags.is_empty() || tags.iter().any(|tag| scenario.has_tag(tag)))
+        .filter(|scenario| !excluded_tags.iter().any(|tag| scenario.has_tag(tag)))
+        .collect()
+}
+
 /// Get all registered scenarios from the catalog
 pub fn get_scenario_catalog() -> Vec<ScenarioEntry> {
     inventory::iter::<ScenarioEntry>
@@ -27,7 +62,15 @@ pub fn get_scenario_catalog() -> Vec<ScenarioEntry> {
 // inventory::submit!(scenarios::ScenarioEntry {
 //     short_name: "name",
 //     run: function,
+//     tags: &["fast"],
 // });
+// or, for scenarios built with builder::Scenario, using register_scenario!("name", function, &["fast"])
+
+// Helpers shared by scenarios
+pub mod validation;
+pub mod assertions;
+pub mod builder;
+pub mod variant_chain;
 
 // Scenario modules
 pub mod scarcity_and_abundance;
@@ -37,5 +80,91 @@ pub mod supply_controlled_boost;
 pub mod supply_controlled_boost_2;
 pub mod median_bidder;
 pub mod viewability;
+pub mod viewability_bidder;
+pub mod floor_truncation;
+pub mod bid_cap;
+pub mod portfolio_bidder;
 pub mod value_groups;
 
+pub mod win_rate_miscalibration;
+pub mod soft_floor_bidder;
+pub mod cheater_detection;
+pub mod dual_knob_pacing;
+pub mod learned_competition;
+pub mod time_based_pacing;
+pub mod value_split;
+pub mod minimum_margin;
+pub mod clearing_price_bidder;
+pub mod weighted_value_bidder;
+pub mod pacing_bounds;
+pub mod adaptive_gain;
+pub mod bisection_controller;
+pub mod robbins_monro;
+pub mod dead_band;
+pub mod slew_rate_limit;
+pub mod warm_start;
+pub mod coupled_double_target;
+pub mod lagrangian_constraints;
+pub mod oscillation_detection;
+pub mod error_smoothing;
+pub mod response_curve_controller;
+pub mod seller_boost_bounds;
+pub mod perturbation_recovery;
+pub mod daily_budget;
+pub mod cpa_target;
+pub mod viewability_constraint;
+pub mod margin_target;
+pub mod frequency_cap;
+pub mod cpm_ceiling;
+pub mod win_rate_target;
+pub mod target_priorities;
+pub mod soft_targets;
+pub mod seller_spend_share;
+pub mod value_per_spend_target;
+pub mod second_price_seller;
+pub mod hybrid_charger;
+pub mod dynamic_floors;
+pub mod take_rate_optimization;
+pub mod premium_remnant_sellers;
+pub mod supply_paths;
+pub mod mixed_auction_types;
+pub mod bid_caching;
+pub mod volume_discount;
+pub mod demand_throttling;
+pub mod boost_schedule;
+pub mod adapter_fee;
+pub mod empirical_competition;
+pub mod value_correlated_competition;
+pub mod time_varying_competition;
+pub mod competition_prediction_error;
+pub mod censored_competition;
+pub mod competing_bid_distribution;
+pub mod competition_composition;
+pub mod reactive_competitor;
+pub mod hourly_arrivals;
+pub mod user_activity;
+pub mod audience_segments;
+pub mod attribute_arbitrage;
+pub mod value_group_correlation;
+pub mod value_oversampling;
+pub mod common_random_numbers;
+pub mod impressions_file;
+pub mod value_distributions;
+pub mod conversion_values;
+pub mod viewability_measurement;
+pub mod flight_pacing;
+pub mod online_pacing;
+pub mod budget_sweep;
+pub mod checkpoint_resume;
+pub mod convergence_criteria;
+pub mod seed_aggregation;
+pub mod parameter_sweep;
+pub mod gain_tuning;
+pub mod ab_experiment;
+pub mod sensitivity_analysis;
+pub mod simulation_observers;
+pub mod wall_clock_budget;
+pub mod convergence_trajectory;
+pub mod counterfactual_replay;
+pub mod budget_depletion;
+pub mod campaign_entry_exit;
@@ -0,0 +1,137 @@
+//! This scenario demonstrates online mini-batch convergence, updating pacing within each run.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, their pacing updated:
+//!
+//! - Variant A: Only between runs
+//!
+//! - Variant B: Also every 500 impressions within each run, as real-time pacing systems do
+//!
+//! Both start from the same pacing, which overdelivers. Updating pacing while the impressions come in corrects
+//! it within the first run already, so its results are much closer to the targets than without online updates.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::hooks::AuctionOutcome;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "online_pacing",
+    run,
+    tags: &["fast"],
+});
+
+/// Impressions between online updates in variant B
+const ONLINE_BATCH_SIZE: usize = 500;
+/// Impressions target of campaign 0 and budget of campaign 1
+const TARGET_IMPRESSIONS: f64 = 1000.0;
+const TARGET_BUDGET: f64 = 20.0;
+
+/// Impressions and spend of each campaign in the first run (indexed by campaign_id)
+type FirstRunResults = Vec<(f64, f64)>;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(online_batch_size: Option<usize>) -> (SimulationConverge, Rc<RefCell<FirstRunResults>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: TARGET_IMPRESSIONS as i32 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_BUDGET }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let mut simulation_converge = SimulationConverge::new(marketplace);
+    if let Some(batch_size) = online_batch_size {
+        simulation_converge = simulation_converge.with_online_updates(batch_size);
+    }
+
+    // Sum the campaigns' impressions and spend in the first run
+    let first_run_results = Rc::new(RefCell::new(vec![(0.0, 0.0); 2]));
+    {
+        let first_run_results_sum = first_run_results.clone();
+        simulation_converge.hooks.borrow_mut().on_auction_result(move |event, _logger| {
+            if let (1, AuctionOutcome::Standard(result)) = (event.iteration, &event.outcome) {
+                if let Winner::Campaign { campaign_id, buyer_charge, .. } = result.winner {
+                    let (impressions, spend) = &mut first_run_results_sum.borrow_mut()[campaign_id];
+                    *impressions += 1.0;
+                    *spend += buyer_charge;
+                }
+            }
+        });
+    }
+
+    (simulation_converge, first_run_results)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with pacing updated between runs only
+    let (simulation_converge_a, first_run_a) = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_variant("Running with pacing updated between runs", scenario_name, "between-runs", 100, logger)?;
+
+    // Run variant B with online pacing updates
+    let (simulation_converge_b, first_run_b) = prepare_simulationconverge(Some(ONLINE_BATCH_SIZE));
+    let stats_b = simulation_converge_b.run_variant("Running with online pacing updates", scenario_name, "online", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (between runs)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (online)", &stats_b, logger, &mut errors);
+
+    // Check: Online updates bring the first run's results closer to the targets
+    let (first_run_a, first_run_b) = (first_run_a.borrow(), first_run_b.borrow());
+    for (label, actual_a, actual_b, target) in [
+        ("Campaign 0 impressions", first_run_a[0].0, first_run_b[0].0, TARGET_IMPRESSIONS),
+        ("Campaign 1 spend", first_run_a[1].1, first_run_b[1].1, TARGET_BUDGET),
+    ] {
+        assertions::assert_greater(
+            &format!("{} in the first run is closer to its target of {:.2} with online updates ({:.2}) than between runs ({:.2}), distance between runs", label, target, actual_b, actual_a),
+            (actual_a - target).abs(),
+            (actual_b - target).abs(),
+            logger,
+            &mut errors,
+        );
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
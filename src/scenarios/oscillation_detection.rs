@@ -0,0 +1,110 @@
+//! This scenario checks the oscillation diagnostics of the convergence loop.
+//!
+//! - Variant A: Max margin campaign paced by ControllerProportionalDerivative with high gains
+//!
+//! - Variant B: Max margin campaign paced by the default ControllerProportionalDerivative
+//!
+//! Both campaigns converge to the same budget. The high gains of variant A overshoot the budget on
+//! every iteration before settling, so its pacing should be reported as oscillating (with the amplitude
+//! of the swings) in SimulationStat. The default gains approach the budget from one side and should
+//! be reported as stable.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, ControllerConfig};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "oscillation_detection",
+    run,
+    tags: &["controllers"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(high_gain: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let controller_config = if high_gain {
+        ControllerConfig::new().with_max_adjustment_factor(0.3).with_gains(0.6, 0.2)
+    } else {
+        ControllerConfig::new()
+    };
+    campaigns.add_configured(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 40.0 }],  // converge_target
+        vec![controller_config],  // controller_configs
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with the high-gain PD controller
+    let simulation_converge_a = prepare_simulationconverge(true);
+    let stats_a = simulation_converge_a.run_variant("Running with high-gain PD controller", scenario_name, "high-gain", 200, logger)?;
+
+    // Run variant B with the default PD controller
+    let simulation_converge_b = prepare_simulationconverge(false);
+    let stats_b = simulation_converge_b.run_variant("Running with default PD controller", scenario_name, "default-gain", 200, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Oscillation of the high-gain controller's pacing is detected
+    let oscillations_a = &stats_a.campaign_oscillations[0];
+    let msg = match oscillations_a.first() {
+        Some(oscillation) => format!(
+            "Variant A (high gain) pacing oscillation detected: control variable {}, from iteration {}, {} alternating changes, amplitude {:.4}",
+            oscillation.control_variable_index, oscillation.detected_at_iteration, oscillation.alternating_changes, oscillation.amplitude
+        ),
+        None => "Variant A (high gain) pacing oscillation detected: none reported".to_string(),
+    };
+    validation::check(oscillations_a.iter().any(|oscillation| oscillation.control_variable_index == 0 && oscillation.amplitude > 0.0), msg, logger, &mut errors);
+
+    // Check: The default controller's pacing is stable
+    let oscillations_b = stats_b.campaign_oscillations[0].len() + stats_b.seller_oscillations[0].len();
+    let msg = format!("Variant B (default gain) converges without sustained oscillation: {} oscillations reported", oscillations_b);
+    validation::check(oscillations_b == 0, msg, logger, &mut errors);
+
+    // Check: Both variants spend their budget
+    for (variant, stats) in [("A (high gain)", &stats_a), ("B (default gain)", &stats_b)] {
+        let spend = stats.campaign_stats[0].total_buyer_charge;
+        assertions::assert_within_pct(&format!("Variant {} spends its budget", variant), spend, 40.0, 1.0, logger, &mut errors);
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,102 @@
+//! This scenario shows bounded pacing with anti-windup on a campaign with an unreachable target.
+//!
+//! The campaign wants more impressions than the marketplace has on offer, so the PD controller keeps
+//! raising pacing after every iteration.
+//!
+//! - Variant A: Unbounded pacing
+//!
+//! - Variant B: Pacing bounded to [0.0, 3.0]
+//!
+//! Unbounded pacing runs away to infinity, together with bids and spend. Bounded pacing saturates
+//! at its upper bound, stops changing and converges, obtaining as many impressions as pacing 3.0 allows.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignGeneral};
+use crate::controllers::ControllerProportionalDerivative;
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "pacing_bounds",
+    run,
+    tags: &["fast", "controllers"],
+});
+
+/// Upper bound of pacing in variant B
+const MAX_PACING: f64 = 3.0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(bounded: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    // Target is above the 10000 impressions on offer
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 12000 }],  // converge_target
+    );
+    if bounded {
+        let campaign_general = campaigns.campaigns[campaign_id].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+        campaign_general.converge_controllers[0] = Box::new(ControllerProportionalDerivative::new().with_output_bounds(0.0, MAX_PACING));
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with unbounded pacing, which is expected to run away (or not converge at all)
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let result_a = simulation_converge_a.run_variant("Running with unbounded pacing", scenario_name, "unbounded", 100, logger);
+
+    // Run variant B with bounded pacing
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with pacing bounded to [0.0, 3.0]", scenario_name, "bounded", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Variant A (unbounded) runs away to infinite spend or fails to converge
+    let spend_a = result_a.as_ref().map_or(f64::INFINITY, |stats_a| stats_a.campaign_stats[0].total_buyer_charge);
+    let msg = format!("Variant A (unbounded) runs away with an unreachable target: spend {:.4} is not finite", spend_a);
+    validation::check(!spend_a.is_finite(), msg, logger, &mut errors);
+
+    // Check: Variant B (bounded) converges below the unreachable target with finite spend
+    let impressions_b = stats_b.campaign_stats[0].impressions_obtained;
+    let spend_b = stats_b.campaign_stats[0].total_buyer_charge;
+    let msg = format!("Variant B (bounded) converges with impressions below the unreachable target and finite spend: {:.0} < 12000, spend {:.4}", impressions_b, spend_b);
+    validation::check(impressions_b < 12000.0 && spend_b.is_finite(), msg, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,148 @@
+//! This scenario demonstrates a parameter sweep over the softmax temperature and the seller's boost.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller in fractional internal auctions,
+//! with every combination of softmax temperature 0.25, 0.5, 1.0 and 2.0 and seller boost 0.8, 1.0 and 1.25
+//! run as a variant of the sweep, its results written to a CSV and a heatmap.
+//!
+//! The boost multiplies the campaigns' bids, which their controllers compensate for, so the campaigns obtain
+//! the same results whatever the boost. A higher temperature spreads each impression over more of the bids,
+//! so campaign 0 obtains its impressions target with shares of impressions won at lower bids and pays less.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::convergence_criteria::{ConvergenceCriterionAny, ConvergenceCriterionControllersUnchanged, ConvergenceCriterionRelativeError};
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::sweep::{Sweep, SweepResult};
+use crate::logger::{Logger, LogEvent, sanitize_filename};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "parameter_sweep",
+    run,
+    tags: &["fractional", "analysis"],
+});
+
+/// Softmax temperatures swept over
+const SOFTMAX_TEMPERATURES: [f64; 4] = [0.25, 0.5, 1.0, 2.0];
+/// Seller boosts swept over
+const BOOSTS: [f64; 3] = [0.8, 1.0, 1.25];
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(softmax_temperature: f64, boost: f64) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index), boosting bids by the swept boost
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: boost },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::FractionalInternalAuction { softmax_temperature });
+
+    // Create simulation converge instance (initializes campaign and seller converges internally), some
+    // combinations settle into a small limit cycle of the two campaigns' controllers, converged once within the targets' tolerance
+    SimulationConverge::new(marketplace).with_convergence_criterion(ConvergenceCriterionAny::new(vec![
+        ConvergenceCriterionControllersUnchanged::new(),
+        ConvergenceCriterionRelativeError::new(0.01, 3),
+    ]))
+}
+
+/// Largest relative difference and mean of a metric over the boosts at a softmax temperature
+fn boost_spread_and_mean(result: &SweepResult, softmax_temperature: f64, metric_name: &str) -> (f64, f64) {
+    let values: Vec<f64> = BOOSTS.iter().filter_map(|&boost| result.metric(&[softmax_temperature, boost], metric_name)).collect();
+    let (min, max) = values.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| (min.min(value), max.max(value)));
+    ((max - min) / min, values.iter().sum::<f64>() / values.len() as f64)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run a variant for every combination of softmax temperature and boost
+    let sweep = Sweep::new("grid")
+        .with_parameter("softmax_temperature", SOFTMAX_TEMPERATURES.to_vec())
+        .with_parameter("boost", BOOSTS.to_vec())
+        .with_metric("campaign_0_buyer_charge", |stats| stats.campaign_stats[0].total_buyer_charge)
+        .with_metric("campaign_1_impressions", |stats| stats.campaign_stats[1].impressions_obtained)
+        .with_heatmap("campaign_0_buyer_charge");
+    let result = sweep.run(|values| prepare_simulationconverge(values[0], values[1]), scenario_name, 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Every combination converges and meets its targets
+    validation::check(
+        result.failed_points().is_empty(),
+        format!("All {} combinations converge ({} failed)", result.points.len(), result.failed_points().len()),
+        logger,
+        &mut errors,
+    );
+    for point in result.points.iter().filter(|point| point.stats.is_some()) {
+        validation::assert_targets_met(&format!("softmax_temperature={}, boost={}", point.parameter_values[0], point.parameter_values[1]), point.stats.as_ref().unwrap(), logger, &mut errors);
+    }
+
+    // Check: The results are written as a CSV with a header and a row per combination
+    let csv = std::fs::read_to_string(logger.log_path(&format!("{}/sweep-grid.csv", sanitize_filename(scenario_name))))?;
+    validation::check(
+        csv.lines().count() == result.points.len() + 1,
+        format!("Sweep CSV has a header and {} rows: {} lines", result.points.len(), csv.lines().count()),
+        logger,
+        &mut errors,
+    );
+
+    let mut buyer_charges = Vec::new();
+    for &softmax_temperature in &SOFTMAX_TEMPERATURES {
+        // Check: The campaigns' controllers compensate for the boost, up to the targets' tolerance
+        let (spread, mean) = boost_spread_and_mean(&result, softmax_temperature, "campaign_0_buyer_charge");
+        assertions::assert_between(
+            &format!("Campaign 0 buyer charge at softmax_temperature={} is about the same with every boost, spread", softmax_temperature),
+            spread,
+            0.0,
+            0.03,
+            logger,
+            &mut errors,
+        );
+        buyer_charges.push(mean);
+    }
+
+    // Check: Campaign 0 pays less for its impressions the higher the temperature
+    validation::check(
+        buyer_charges.windows(2).all(|pair| pair[1] < pair[0]),
+        format!("Campaign 0 buyer charge (mean over boosts) falls as the softmax temperature rises: {:?}", buyer_charges.iter().map(|charge| format!("{:.4}", charge)).collect::<Vec<_>>()),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,106 @@
+//! This scenario tests robustness of convergence by perturbing a converged campaign.
+//!
+//! Two campaigns (budget and impressions targets) converge with default PD controllers. After the
+//! first convergence, pacing of the budget campaign is multiplied by a factor and convergence continues.
+//!
+//! - Variant A: Pacing doubled (factor 2.0)
+//!
+//! - Variant B: Pacing halved (factor 0.5)
+//!
+//! Both variants are expected to recover back to their targets, reporting the iterations the
+//! recovery took.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::scenarios::validation;
+use crate::logln;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "perturbation_recovery",
+    run,
+    tags: &["fast", "controllers"],
+});
+
+/// Budget of the perturbed campaign
+const TARGET_BUDGET: f64 = 20.0;
+/// Impressions target of the other campaign
+const TARGET_IMPRESSIONS: i32 = 1000;
+
+/// Prepare simulation converge instance with campaign and seller setup, perturbing campaign 0 by `factor`
+fn prepare_simulationconverge(factor: f64) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_BUDGET }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: TARGET_IMPRESSIONS }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance, perturbing the budget campaign after the first convergence
+    SimulationConverge::new(marketplace).with_perturbation(campaign_id, factor)
+}
+
+/// Validate that a variant recovered from the perturbation back to both targets (within the default 1%)
+fn validate_recovery(variant: &str, stats: &SimulationStat, logger: &mut Logger, errors: &mut Vec<String>) {
+    let msg = match stats.perturbation_recovery_iterations {
+        Some(recovery_iterations) => format!("{}: Recovered from the perturbation in {} iterations (of {} in total)", variant, recovery_iterations, stats.convergence_iterations),
+        None => format!("{}: Recovered from the perturbation (no recovery reported)", variant),
+    };
+    validation::check(stats.perturbation_recovery_iterations.is_some_and(|recovery_iterations| recovery_iterations > 0), msg, logger, errors);
+
+    validation::assert_targets_met(variant, stats, logger, errors);
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with pacing doubled after convergence
+    let simulation_converge_a = prepare_simulationconverge(2.0);
+    let stats_a = simulation_converge_a.run_variant("Running with pacing of the budget campaign doubled after convergence", scenario_name, "doubled", 100, logger)?;
+
+    // Run variant B with pacing halved after convergence
+    let simulation_converge_b = prepare_simulationconverge(0.5);
+    let stats_b = simulation_converge_b.run_variant("Running with pacing of the budget campaign halved after convergence", scenario_name, "halved", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validate_recovery("Variant A (doubled)", &stats_a, logger, &mut errors);
+    validate_recovery("Variant B (halved)", &stats_b, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,123 @@
+//! This scenario compares a campaign with a single pacing and a portfolio campaign with per-seller pacing multipliers.
+//!
+//! There are two sellers with the same kind of impressions, but one of them sets much higher floors.
+//! Both campaigns bid truthfully, so the pacing decides how much of the surplus is given away on each seller.
+//!
+//! - Variant A: Multiplicative pacing with one campaign-wide pacing
+//!
+//! - Variant B: Multiplicative pacing with campaign-wide pacing and per-seller multipliers (portfolio)
+//!
+//! Both campaigns spend the same budget, the portfolio campaign should obtain more value per spend
+//! by equalizing marginal value per spend across sellers.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignPortfolio, CampaignTargetTotalBudget, BidOptimizerTrutful};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "portfolio_bidder",
+    run,
+    tags: &["floors"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(portfolio: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add seller with low floors (ID is automatically set to match Vec index)
+    sellers.add(
+        "Low floors".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Add seller with high floors
+    sellers.add(
+        "High floors".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(1.0, 2.0),  // floor_generator
+    );
+
+    // Add campaign (ID is automatically set to match Vec index)
+    if portfolio {
+        campaigns.add_advanced(Box::new(CampaignPortfolio::new(
+            "Campaign 0".to_string(),  // campaign_name
+            Box::new(CampaignTargetTotalBudget { total_budget_target: 30.0 }),  // converge_target
+            Box::new(BidOptimizerTrutful),  // bid_optimizer
+            sellers.sellers.len(),  // num_sellers
+        )));
+    } else {
+        campaigns.add(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 30.0 }],  // converge_target
+        );
+    }
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with single pacing
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running with single pacing", scenario_name, "single-pacing", 100, logger)?;
+
+    // Run variant B with per-seller pacing multipliers
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with per-seller pacing multipliers", scenario_name, "portfolio", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Variant B obtains more value per spend than variant A
+    let campaign_a = &stats_a.campaign_stats[0];
+    let campaign_b = &stats_b.campaign_stats[0];
+    let value_per_spend_a = if campaign_a.total_buyer_charge > 0.0 {
+        campaign_a.total_value / campaign_a.total_buyer_charge
+    } else {
+        0.0
+    };
+    let value_per_spend_b = if campaign_b.total_buyer_charge > 0.0 {
+        campaign_b.total_value / campaign_b.total_buyer_charge
+    } else {
+        0.0
+    };
+    assertions::assert_greater(
+        "Variant B (portfolio) has higher value per spend than variant A (single pacing)",
+        value_per_spend_b,
+        value_per_spend_a,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,172 @@
+//! This scenario demonstrates sellers with their own impression quality: a premium and a remnant seller.
+//!
+//! Two campaigns buy from both sellers.
+//!
+//! - Variant A: Impressions of both sellers are sampled from the global distributions
+//!   (base value around 10 CPM, viewability from Beta(3, 2))
+//!
+//! - Variant B: Premium seller overrides them with valuable and viewable impressions (base value around 20 CPM,
+//!   viewability from Beta(9, 1)), remnant seller with cheap and poorly viewable ones (base value around 5 CPM,
+//!   viewability from Beta(2, 3))
+//!
+//! In variant B impressions of each seller follow its own distributions, and premium impressions sell at
+//! a higher CPM than remnant ones.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, SellerImpressionsParam};
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "premium_remnant_sellers",
+    run,
+    tags: &["fast", "supply", "viewability"],
+});
+
+/// Seller IDs
+const PREMIUM: usize = 0;
+const REMNANT: usize = 1;
+/// Mean base impression values of the sellers in variant B
+const PREMIUM_BASE_VALUE: f64 = 20.0;
+const REMNANT_BASE_VALUE: f64 = 5.0;
+/// Mean viewability of the sellers in variant B (means of Beta(9, 1) and Beta(2, 3))
+const PREMIUM_VIEWABILITY: f64 = 0.9;
+const REMNANT_VIEWABILITY: f64 = 0.4;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(seller_quality: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add sellers (IDs are automatically set to match Vec index)
+    sellers.add(
+        "Premium".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        5000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+    sellers.add(
+        "Remnant".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, with per-seller overrides in variant B
+    let mut impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_viewability(utils::beta_dist(3.0, 2.0));
+    if seller_quality {
+        impressions_params = impressions_params
+            .with_seller_params(PREMIUM, SellerImpressionsParam::new()
+                .with_base_impression_value(utils::lognormal_dist(PREMIUM_BASE_VALUE, 6.0))
+                .with_viewability(utils::beta_dist(9.0, 1.0)))
+            .with_seller_params(REMNANT, SellerImpressionsParam::new()
+                .with_base_impression_value(utils::lognormal_dist(REMNANT_BASE_VALUE, 1.5))
+                .with_viewability(utils::beta_dist(2.0, 3.0)));
+    }
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Mean base impression value and mean viewability of a seller's impressions
+fn seller_means(simulation_converge: &SimulationConverge, seller_id: usize) -> (f64, f64) {
+    let impressions: Vec<_> = simulation_converge.marketplace.impressions.impressions.iter().filter(|impression| impression.seller_id == seller_id).collect();
+    let count = impressions.len() as f64;
+    (
+        impressions.iter().map(|impression| impression.base_impression_value).sum::<f64>() / count,
+        impressions.iter().map(|impression| impression.viewability).sum::<f64>() / count,
+    )
+}
+
+/// Average CPM buyers paid for a seller's impressions
+fn seller_cpm(stats: &SimulationStat, seller_id: usize) -> f64 {
+    stats.seller_stats[seller_id].rcpm_sold()
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with global impression distributions
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running with the same impression distributions for both sellers", scenario_name, "global", 100, logger)?;
+
+    // Run variant B with per-seller impression distributions
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with premium and remnant impression distributions", scenario_name, "per_seller", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Without overrides both sellers' impressions come from the global distributions
+    let (premium_value_a, premium_viewability_a) = seller_means(&simulation_converge_a, PREMIUM);
+    let (remnant_value_a, remnant_viewability_a) = seller_means(&simulation_converge_a, REMNANT);
+    assertions::assert_within_pct("Variant A (global) samples both sellers alike, premium base value", premium_value_a, remnant_value_a, 5.0, logger, &mut errors);
+    assertions::assert_between(
+        "Variant A (global) samples both sellers alike, premium viewability",
+        premium_viewability_a,
+        remnant_viewability_a - 0.02,
+        remnant_viewability_a + 0.02,
+        logger,
+        &mut errors,
+    );
+
+    // Check: With overrides each seller's impressions follow its own distributions
+    for (seller_name, seller_id, base_value, viewability) in [("Premium", PREMIUM, PREMIUM_BASE_VALUE, PREMIUM_VIEWABILITY), ("Remnant", REMNANT, REMNANT_BASE_VALUE, REMNANT_VIEWABILITY)] {
+        let (mean_value, mean_viewability) = seller_means(&simulation_converge_b, seller_id);
+        assertions::assert_within_pct(&format!("Variant B (per seller) samples {} impressions from its own distributions, base value", seller_name), mean_value, base_value, 5.0, logger, &mut errors);
+        assertions::assert_between(
+            &format!("Variant B (per seller) samples {} impressions from its own distributions, viewability", seller_name),
+            mean_viewability,
+            viewability - 0.02,
+            viewability + 0.02,
+            logger,
+            &mut errors,
+        );
+    }
+
+    // Check: Premium impressions sell at a higher CPM
+    assertions::assert_greater(
+        "Variant B (per seller) sells premium impressions at a higher CPM than remnant ones",
+        seller_cpm(&stats_b, PREMIUM),
+        seller_cpm(&stats_b, REMNANT),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Campaigns meet their targets in both variants
+    validation::assert_targets_met("Variant A (global)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (per seller)", &stats_b, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
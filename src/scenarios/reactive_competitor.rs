@@ -0,0 +1,130 @@
+//! This scenario demonstrates an external competitor adapting its bid level to the campaigns.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, whose competition is:
+//!
+//! - Variant A: A static competitor (lognormal competition)
+//!
+//! - Variant B: A reactive competitor aiming to win 75% of the seller's auctions
+//!
+//! - Variant C: A reactive competitor aiming to win 40% of the seller's auctions
+//!
+//! Reactive competitors scale their competing bids across iterations while the campaigns pace, until both
+//! reach their targets. An aggressive competitor makes the campaigns pay more for their impressions, a
+//! passive one lets them buy cheaper.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers, SellerTargetCompetitorWinRate};
+use crate::controllers::ControllerConfig;
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "reactive_competitor",
+    run,
+    tags: &["fast"],
+});
+
+/// Target win rates of the aggressive and passive reactive competitors
+const AGGRESSIVE_TARGET_WIN_RATE: f64 = 0.75;
+const PASSIVE_TARGET_WIN_RATE: f64 = 0.4;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(competitor_target_win_rate: Option<f64>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+    if let Some(target_win_rate) = competitor_target_win_rate {
+        sellers.set_reactive_competitor(0, target_win_rate, ControllerConfig::new().with_tolerance_fraction(0.02));
+    }
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Average CPM the impressions campaign pays
+fn impressions_campaign_cpm(stats: &SimulationStat) -> f64 {
+    let campaign = &stats.campaign_stats[0];
+    campaign.total_buyer_charge / campaign.impressions_obtained * 1000.0
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with a static competitor
+    let simulation_converge_a = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_variant("Running with a static competitor", scenario_name, "static", 100, logger)?;
+
+    // Run variant B with an aggressive reactive competitor
+    let simulation_converge_b = prepare_simulationconverge(Some(AGGRESSIVE_TARGET_WIN_RATE));
+    let stats_b = simulation_converge_b.run_variant("Running with an aggressive reactive competitor", scenario_name, "aggressive", 100, logger)?;
+
+    // Run variant C with a passive reactive competitor
+    let simulation_converge_c = prepare_simulationconverge(Some(PASSIVE_TARGET_WIN_RATE));
+    let stats_c = simulation_converge_c.run_variant("Running with a passive reactive competitor", scenario_name, "passive", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (static)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (aggressive)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (passive)", &stats_c, logger, &mut errors);
+
+    let win_rate_a = SellerTargetCompetitorWinRate::win_rate(&stats_a.seller_stats[0]);
+    let win_rate_b = SellerTargetCompetitorWinRate::win_rate(&stats_b.seller_stats[0]);
+    let win_rate_c = SellerTargetCompetitorWinRate::win_rate(&stats_c.seller_stats[0]);
+    logln!(logger, LogEvent::Scenario, "Competitor win rate: static {:.1}%, aggressive {:.1}%, passive {:.1}%",
+        win_rate_a * 100.0, win_rate_b * 100.0, win_rate_c * 100.0);
+
+    // Check: Reactive competitors reach their target win rates alongside the campaigns' targets
+    for (label, win_rate, target_win_rate) in [("Variant B (aggressive)", win_rate_b, AGGRESSIVE_TARGET_WIN_RATE), ("Variant C (passive)", win_rate_c, PASSIVE_TARGET_WIN_RATE)] {
+        assertions::assert_within_pct(&format!("{} competitor meets its target win rate", label), win_rate, target_win_rate, 3.0, logger, &mut errors);
+    }
+
+    // Check: An aggressive competitor makes impressions more expensive, a passive one cheaper
+    let cpm_a = impressions_campaign_cpm(&stats_a);
+    let cpm_b = impressions_campaign_cpm(&stats_b);
+    let cpm_c = impressions_campaign_cpm(&stats_c);
+    assertions::assert_greater("Impressions campaign CPM is higher with the aggressive (variant B) than the static competitor (variant A)", cpm_b, cpm_a, logger, &mut errors);
+    assertions::assert_greater("Impressions campaign CPM is higher with the static (variant A) than the passive competitor (variant C)", cpm_a, cpm_c, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
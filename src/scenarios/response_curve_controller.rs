@@ -0,0 +1,134 @@
+//! This scenario compares the default PD controller with the model-based response curve controller.
+//!
+//! - Variant A: Max margin campaign targeting total budget, paced by ControllerProportionalDerivative
+//!
+//! - Variant B: Max margin campaign targeting total budget, paced by ControllerResponseCurve
+//!
+//! - Variant C: Max margin campaign targeting total impressions, paced by ControllerProportionalDerivative
+//!
+//! - Variant D: Max margin campaign targeting total impressions, paced by ControllerResponseCurve
+//!
+//! Spend and impressions are smooth functions of pacing, so fitting a response curve to the observations
+//! and jumping to its root should need far fewer iterations than proportional steps: at most half of them.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignGeneral};
+use crate::controllers::{ControllerResponseCurve, ControllerTrait};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "response_curve_controller",
+    run,
+    tags: &["controllers"],
+});
+
+const TARGET_TOTAL_BUDGET: f64 = 40.0;
+const TARGET_TOTAL_IMPRESSIONS: i32 = 1500;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the number of iterations run
+fn prepare_simulationconverge(converge_target: ConvergeTarget, response_curve: bool) -> (SimulationConverge, Rc<Cell<usize>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![converge_target],  // converge_target
+    );
+    if response_curve {
+        let campaign_general = campaigns.campaigns[campaign_id].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+        let converge_controller: Box<dyn ControllerTrait> = Box::new(ControllerResponseCurve::new());
+        campaign_general.converge_controllers[0] = converge_controller;
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Count iterations until convergence
+    let iterations = Rc::new(Cell::new(0usize));
+    {
+        let iterations_count = iterations.clone();
+        simulation_converge.hooks.borrow_mut().on_iteration_start(move |iteration, _logger| {
+            iterations_count.set(iteration);
+        });
+    }
+
+    (simulation_converge, iterations)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    let targets = [
+        ("budget", ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_TOTAL_BUDGET }, ["A", "B"]),
+        ("impressions", ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: TARGET_TOTAL_IMPRESSIONS }, ["C", "D"]),
+    ];
+    for (target_name, converge_target, [variant_pd, variant_response_curve]) in targets {
+        // Run the PD variant
+        let (simulation_converge_pd, iterations_pd) = prepare_simulationconverge(converge_target.clone(), false);
+        let stats_pd = simulation_converge_pd.run_variant(&format!("Running {} target with PD controller", target_name), scenario_name, &format!("{}-pd", target_name), 200, logger)?;
+
+        // Run the response curve variant
+        let (simulation_converge_response_curve, iterations_response_curve) = prepare_simulationconverge(converge_target.clone(), true);
+        let stats_response_curve = simulation_converge_response_curve.run_variant(&format!("Running {} target with response curve controller", target_name), scenario_name, &format!("{}-response-curve", target_name), 200, logger)?;
+
+        logln!(logger, LogEvent::Scenario, "");
+
+        // Check: Response curve controller needs at most half the iterations of PD
+        let msg = format!(
+            "Variant {} (response curve, {}) converges in at most half the iterations of variant {} (PD): {} vs {}",
+            variant_response_curve,
+            target_name,
+            variant_pd,
+            iterations_response_curve.get(),
+            iterations_pd.get()
+        );
+        validation::check(iterations_response_curve.get() * 2 <= iterations_pd.get(), msg, logger, &mut errors);
+
+        // Check: Both controllers reach the target
+        for (variant, stats) in [(format!("{} (PD)", variant_pd), &stats_pd), (format!("{} (response curve)", variant_response_curve), &stats_response_curve)] {
+            let (actual, target) = match converge_target {
+                ConvergeTarget::TOTAL_BUDGET { target_total_budget } => (stats.campaign_stats[0].total_buyer_charge, target_total_budget),
+                ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions } => (stats.campaign_stats[0].impressions_obtained, target_total_impressions as f64),
+                _ => unreachable!(),
+            };
+            assertions::assert_within_pct(&format!("Variant {} reaches its {} target", variant, target_name), actual, target, 1.0, logger, &mut errors);
+        }
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
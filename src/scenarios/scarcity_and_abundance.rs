@@ -18,12 +18,13 @@ use crate::floors;
 use crate::utils;
 use crate::logger::{Logger, LogEvent};
 use crate::logln;
-use crate::errln;
+use crate::scenarios::{validation, assertions};
 
 // Register this scenario in the catalog
 inventory::submit!(crate::scenarios::ScenarioEntry {
     short_name: "scarcity_and_abundance",
     run,
+    tags: &["fast"],
 });
 
 /// Prepare simulation converge instance with campaign and seller setup
@@ -106,60 +107,16 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     let mut errors = Vec::new();
     
     // Check: Variant A has higher total cost charged to buyers
-    let msg = format!(
-        "Variant A (Scarce HB) has higher total buyer charge than variant B (Abundant HB): {:.2} > {:.2}",
-        stats_a.overall_stat.total_buyer_charge,
-        stats_b.overall_stat.total_buyer_charge
-    );
-    if stats_a.overall_stat.total_buyer_charge > stats_b.overall_stat.total_buyer_charge {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_greater("Variant A (Scarce HB) has higher total buyer charge than variant B (Abundant HB)", stats_a.overall_stat.total_buyer_charge, stats_b.overall_stat.total_buyer_charge, logger, &mut errors);
     
     // Check: Variant A has lower total value
-    let msg = format!(
-        "Variant A (Scarce HB) has lower total value than variant B (Abundant HB): {:.2} < {:.2}",
-        stats_a.overall_stat.total_value,
-        stats_b.overall_stat.total_value
-    );
-    if stats_a.overall_stat.total_value < stats_b.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_greater("Variant B (Abundant HB) has higher total value than variant A (Scarce HB)", stats_b.overall_stat.total_value, stats_a.overall_stat.total_value, logger, &mut errors);
     
     // Check: In variant A, cost of inventory is lower than cost charged to buyers
-    let msg = format!(
-        "Variant A (Scarce HB) is profitable (supply_cost < buyer_charge): {:.2} < {:.2}",
-        stats_a.overall_stat.total_supply_cost,
-        stats_a.overall_stat.total_buyer_charge
-    );
-    if stats_a.overall_stat.total_supply_cost < stats_a.overall_stat.total_buyer_charge {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_greater("Variant A (Scarce HB) is profitable (buyer_charge > supply_cost)", stats_a.overall_stat.total_buyer_charge, stats_a.overall_stat.total_supply_cost, logger, &mut errors);
     
     // Check: In variant B, cost of inventory is higher than cost charged to buyers
-    let msg = format!(
-        "Variant B (Abundant HB) is unprofitable (supply_cost > buyer_charge): {:.2} > {:.2}",
-        stats_b.overall_stat.total_supply_cost,
-        stats_b.overall_stat.total_buyer_charge
-    );
-    if stats_b.overall_stat.total_supply_cost > stats_b.overall_stat.total_buyer_charge {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_greater("Variant B (Abundant HB) is unprofitable (supply_cost > buyer_charge)", stats_b.overall_stat.total_supply_cost, stats_b.overall_stat.total_buyer_charge, logger, &mut errors);
     
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(format!("Scenario '{}' validation failed:\n{}", scenario_name, errors.join("\n")).into())
-    }
+    validation::scenario_result(scenario_name, errors)
 }
@@ -0,0 +1,125 @@
+//! This scenario mixes a first price and a second price seller in one marketplace.
+//!
+//! Both sellers offer the same kind of impressions with the same competition and floors. Two budget
+//! campaigns bid truthfully on both sellers.
+//!
+//! - Variant A: Both sellers run first price auctions
+//!
+//! - Variant B: The second seller runs second price auctions
+//!
+//! On the second price seller winners pay the highest of the next campaign bid, competing bid and
+//! floor, so its buyer charge should be below the virtual cost (the submitted winning bids), while
+//! the first price seller charges the bids. Paying less per impression there, the campaigns should
+//! buy more impressions on the second price seller in variant B than in variant A.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "second_price_seller",
+    run,
+    tags: &["fast", "supply"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(second_seller_type: SellerType) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add first price seller
+    sellers.add(
+        "FP".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Add second seller with the same supply
+    sellers.add(
+        "SP".to_string(),  // seller_name
+        second_seller_type,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with two first price sellers
+    let simulation_converge_a = prepare_simulationconverge(SellerType::FIRST_PRICE);
+    let stats_a = simulation_converge_a.run_variant("Running with two first price sellers", scenario_name, "first_price", 100, logger)?;
+
+    // Run variant B with a first price and a second price seller
+    let simulation_converge_b = prepare_simulationconverge(SellerType::SECOND_PRICE);
+    let stats_b = simulation_converge_b.run_variant("Running with a first price and a second price seller", scenario_name, "second_price", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Both variants spend the budgets
+    validation::assert_targets_met("Variant A (first price)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (second price)", &stats_b, logger, &mut errors);
+
+    // Check: First price seller charges the submitted bids, second price seller less than them
+    let (first_price_b, second_price_b) = (&stats_b.seller_stats[0], &stats_b.seller_stats[1]);
+    validation::check(
+        first_price_b.total_buyer_charge == first_price_b.total_virtual_cost && second_price_b.total_buyer_charge < second_price_b.total_virtual_cost,
+        format!("Variant B (second price) charges below the submitted bids only on the second price seller: FP {:.4} == {:.4}, SP {:.4} < {:.4}",
+            first_price_b.total_buyer_charge, first_price_b.total_virtual_cost, second_price_b.total_buyer_charge, second_price_b.total_virtual_cost),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Cheaper second price impressions draw the campaigns' impressions to the second seller
+    let impressions_a = stats_a.seller_stats[1].impressions_sold;
+    let impressions_b = second_price_b.impressions_sold;
+    assertions::assert_greater(
+        "Variant B (second price) sells more impressions on the second seller than variant A (first price)",
+        impressions_b as f64,
+        impressions_a as f64,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
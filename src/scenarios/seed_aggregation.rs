@@ -0,0 +1,114 @@
+//! This scenario demonstrates comparing variants on statistics aggregated across seeds.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, each variant run with 8 seeds, its
+//! marketplace drawn anew with each seed:
+//!
+//! - Variant A: Budget campaign with budget 20
+//!
+//! - Variant B: Budget campaign with budget 30
+//!
+//! Both variants meet their targets on average across seeds. The comparison relies on the bootstrap confidence
+//! intervals of the aggregated metrics: with more budget, the budget campaign clearly obtains more impressions,
+//! and the impressions campaign clearly pays a higher CPM, outbid by the budget campaign.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::seed_aggregation::run_variant_seeds;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "seed_aggregation",
+    run,
+    tags: &["analysis"],
+});
+
+/// Seeds each variant runs with
+const SEEDS: usize = 8;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(budget: f64) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: budget }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// CPM the impressions campaign (campaign 0) pays
+fn impressions_campaign_cpm(stats: &SimulationStat) -> f64 {
+    stats.campaign_stats[0].total_buyer_charge * 1000.0 / stats.campaign_stats[0].impressions_obtained
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with budget 20 across seeds
+    let stats_a = run_variant_seeds(|| prepare_simulationconverge(20.0), SEEDS, "Running with budget 20", scenario_name, "budget20", 100, logger)?;
+
+    // Run variant B with budget 30 across seeds
+    let stats_b = run_variant_seeds(|| prepare_simulationconverge(30.0), SEEDS, "Running with budget 30", scenario_name, "budget30", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met_aggregated("Variant A (budget 20)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met_aggregated("Variant B (budget 30)", &stats_b, logger, &mut errors);
+
+    // Check: With more budget, the budget campaign obtains more impressions
+    validation::check_ci_above(
+        "Variant B (budget 30) budget campaign impressions", &stats_b.campaign_stats[1].impressions_obtained,
+        "variant A (budget 20)", &stats_a.campaign_stats[1].impressions_obtained,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Outbid by the budget campaign, the impressions campaign pays a higher CPM
+    validation::check_ci_above(
+        "Variant B (budget 30) impressions campaign CPM", &stats_b.metric(impressions_campaign_cpm),
+        "variant A (budget 20)", &stats_a.metric(impressions_campaign_cpm),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,133 @@
+//! This scenario shows a hard bound on a seller's boost factor, modelling a contractual constraint.
+//!
+//! The MRG seller sells guaranteed impressions at a high fixed price and converges its boost factor
+//! so that the demand side pays as much as the guaranteed supply costs.
+//!
+//! - Variant A: Unbounded boost
+//!
+//! - Variant B: Boost bounded to [0.5, 2.0] through the seller's controller configuration
+//!
+//! Covering the supply cost needs a boost above 2.0, which the unbounded seller reaches. The bounded
+//! boost saturates at its upper bound (with a Convergence warning that the bound binds), stops changing
+//! and converges, recovering less of the supply cost.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers, SellerBuilder};
+use crate::seller_builder::{CompetitionConfig, FloorConfig};
+use crate::controllers::ControllerConfig;
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "seller_boost_bounds",
+    run,
+    tags: &["fast", "supply", "controllers"],
+});
+
+/// Contractual bounds of the MRG seller's boost factor in variant B
+const MIN_BOOST: f64 = 0.5;
+const MAX_BOOST: f64 = 2.0;
+/// Fixed price of the MRG seller's guaranteed impressions
+const MRG_FIXED_COST_CPM: f64 = 20.0;
+/// Number of impressions the MRG seller has on offer
+const MRG_IMPRESSIONS_ON_OFFER: usize = 1000;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(bounded: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add MRG seller, converging its boost so that the demand side covers the guaranteed supply cost
+    let controller_config = if bounded {
+        ControllerConfig::new().with_bounds(MIN_BOOST, MAX_BOOST)
+    } else {
+        ControllerConfig::new()
+    };
+    SellerBuilder::new("MRG", SellerType::FIXED_PRICE { fixed_cost_cpm: MRG_FIXED_COST_CPM }, MRG_IMPRESSIONS_ON_OFFER)
+        .with_converge(SellerConvergeStrategy::TOTAL_COST { target_total_cost: MRG_IMPRESSIONS_ON_OFFER as f64 * MRG_FIXED_COST_CPM / 1000.0 })
+        .with_competition(CompetitionConfig::NONE)
+        .with_floors(FloorConfig::FIXED { value: 0.0 })
+        .with_controller_config(controller_config)
+        .add_to(&mut sellers);
+
+    // Add HB seller
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with unbounded boost
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let (stats_a, _campaign_controller_states_a, seller_controller_states_a) = simulation_converge_a.run_variant_with_states("Running with unbounded MRG boost", scenario_name, "unbounded", 100, logger)?;
+
+    // Run variant B with boost bounded to [MIN_BOOST, MAX_BOOST]
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let (stats_b, _campaign_controller_states_b, seller_controller_states_b) = simulation_converge_b.run_variant_with_states("Running with MRG boost bounded to [0.5, 2.0]", scenario_name, "bounded", 100, logger)?;
+
+    let boost_a = simulation_converge_a.marketplace.sellers.sellers[0].get_control_variable(seller_controller_states_a.seller_controller_states[0][0].as_ref());
+    let boost_b = simulation_converge_b.marketplace.sellers.sellers[0].get_control_variable(seller_controller_states_b.seller_controller_states[0][0].as_ref());
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Variant A (unbounded) needs a boost above the contractual bound
+    assertions::assert_greater("Variant A (unbounded) converges MRG boost above the bound", boost_a, MAX_BOOST, logger, &mut errors);
+
+    // Check: Variant B (bounded) converges with the boost held at the upper bound
+    let msg = format!("Variant B (bounded) converges MRG boost at the upper bound: {:.4} == {:.1}", boost_b, MAX_BOOST);
+    validation::check(boost_b == MAX_BOOST, msg, logger, &mut errors);
+
+    // Check: With the boost held at the bound, the demand side covers less of the MRG supply cost
+    let virtual_cost_a = stats_a.seller_stats[0].total_virtual_cost;
+    let virtual_cost_b = stats_b.seller_stats[0].total_virtual_cost;
+    assertions::assert_greater(
+        "Variant A (unbounded) recovers more of the MRG supply cost than variant B (bounded)",
+        virtual_cost_a,
+        virtual_cost_b,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
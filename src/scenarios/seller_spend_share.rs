@@ -0,0 +1,135 @@
+//! This scenario caps the share of a campaign's spend going to the fixed price MRG seller.
+//!
+//! The MRG seller sells impressions at a fixed price below their typical value, so a budget campaign
+//! bidding on value alone spends much of its budget there.
+//!
+//! - Variant A: Campaign 0 bids truthfully (multiplicative pacing) on both sellers
+//!
+//! - Variant B: Campaign 0 keeps at most 40% of its spend on the MRG seller with a bid multiplier on
+//!   the MRG seller's impressions
+//!
+//! Variant A should spend more than 40% on MRG. Variant B should spend its budget with the MRG share
+//! at 40%, moving the rest of the spend to the HB seller.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::{CompetitionGeneratorLogNormal, CompetitionGeneratorNone};
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "seller_spend_share",
+    run,
+    tags: &["fast"],
+});
+
+const TARGET_BUDGET: f64 = 20.0;
+/// Maximum share of campaign 0's spend on the MRG seller in variant B
+const MAX_MRG_SPEND_SHARE: f64 = 0.4;
+/// Seller ID of the MRG seller (added first)
+const MRG_SELLER_ID: usize = 0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(capped: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    if capped {
+        campaigns.add(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MULTIPLICATIVE_SELLER_SPEND_SHARE,  // campaign_type
+            vec![
+                ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_BUDGET },
+                ConvergeTarget::MAX_SELLER_SPEND_SHARE { seller_id: MRG_SELLER_ID, max_spend_share: MAX_MRG_SPEND_SHARE },
+            ],  // converge_targets
+        );
+    } else {
+        campaigns.add(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_BUDGET }],  // converge_target
+        );
+    }
+
+    // Add MRG seller with guaranteed impressions at a fixed price
+    sellers.add(
+        "MRG".to_string(),  // seller_name
+        SellerType::FIXED_PRICE { fixed_cost_cpm: 5.0 },  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        2000,  // impressions_on_offer
+        CompetitionGeneratorNone::new(),  // competition_generator
+        floors::FloorGeneratorFixed::new(0.0),  // floor_generator
+    );
+
+    // Add HB seller
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Share of campaign 0's spend on the MRG seller
+fn mrg_spend_share(stats: &SimulationStat) -> f64 {
+    let campaign_stat = &stats.campaign_stats[0];
+    campaign_stat.seller_breakdown[MRG_SELLER_ID].total_buyer_charge / campaign_stat.total_buyer_charge
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A without a spend share cap
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running without MRG spend share cap", scenario_name, "uncapped", 100, logger)?;
+
+    // Run variant B with MRG spend share capped
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with MRG spend share capped at 40%", scenario_name, "capped", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Bidding on value alone spends more than the cap on MRG
+    let share_a = mrg_spend_share(&stats_a);
+    assertions::assert_greater("Variant A (uncapped) spends more than the cap on MRG", share_a, MAX_MRG_SPEND_SHARE, logger, &mut errors);
+
+    // Check: Capped campaign spends its budget with the MRG share at the cap
+    validation::assert_targets_met("Variant B (capped)", &stats_b, logger, &mut errors);
+
+    // Check: Spend capped on MRG moves to HB
+    let hb_spend_a = stats_a.campaign_stats[0].seller_breakdown[1].total_buyer_charge;
+    let hb_spend_b = stats_b.campaign_stats[0].seller_breakdown[1].total_buyer_charge;
+    assertions::assert_greater(
+        "Variant B (capped) spends more on HB than variant A (uncapped)",
+        hb_spend_b,
+        hb_spend_a,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,142 @@
+//! This scenario demonstrates a sensitivity analysis of the campaigns' KPIs to the marketplace's inputs.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller. Starting from the baseline,
+//! the base impression value, the floor level, the seller's boost, the impressions on offer and both
+//! campaigns' targets are perturbed by ±20% one at a time, and the parameters are ranked by how much they
+//! move campaign 0's CPM and campaign 1's impressions, reported as a table, a CSV and tornado charts.
+//!
+//! The base impression value sets the price level (competition is centered on it), so it is the most
+//! influential parameter on both KPIs, followed by its budget for the budget campaign's impressions. The boost
+//! multiplies the campaigns' bids, which their controllers compensate for, so it barely moves either KPI.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::sensitivity::SensitivityAnalysis;
+use crate::logger::{Logger, LogEvent, sanitize_filename};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "sensitivity_analysis",
+    run,
+    tags: &["analysis"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup from the analysis' parameter values
+/// (base impression value, floor level, boost, impressions on offer, campaign 0's impressions target and
+/// campaign 1's budget)
+fn prepare_simulationconverge(values: &[f64]) -> SimulationConverge {
+    let (base_impression_value, floor_level, boost, impressions_on_offer, target_impressions, target_budget) =
+        (values[0], values[1], values[2], values[3].round() as usize, values[4].round() as i32, values[5]);
+
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: target_impressions }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: target_budget }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: boost },  // seller_converge
+        impressions_on_offer,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(floor_level, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(base_impression_value, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run the baseline and every parameter perturbed by ±20%
+    let analysis = SensitivityAnalysis::new("inputs")
+        .with_parameter("base_impression_value", 10.0)
+        .with_parameter("floor_level", 0.2)
+        .with_parameter("boost", 1.0)
+        .with_parameter("impressions_on_offer", 10000.0)
+        .with_parameter("campaign_0_target_impressions", 1000.0)
+        .with_parameter("campaign_1_target_budget", 20.0)
+        .with_perturbation(0.2)
+        .with_metric("campaign_0_cpm", |stats| stats.campaign_stats[0].total_buyer_charge / stats.campaign_stats[0].impressions_obtained * 1000.0)
+        .with_metric("campaign_1_impressions", |stats| stats.campaign_stats[1].impressions_obtained);
+    let result = analysis.run(prepare_simulationconverge, scenario_name, 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: The baseline and every perturbation converge
+    validation::check(
+        result.failed_runs() == 0,
+        format!("All {} perturbations converge ({} failed)", 2 * result.parameter_names.len(), result.failed_runs()),
+        logger,
+        &mut errors,
+    );
+    validation::assert_targets_met("Baseline", &result.baseline_stats, logger, &mut errors);
+
+    // Check: The report is written as a CSV with a header and a row per parameter and KPI
+    let csv = std::fs::read_to_string(logger.log_path(&format!("{}/sensitivity-inputs.csv", sanitize_filename(scenario_name))))?;
+    validation::check(
+        csv.lines().count() == result.effects.len() + 1,
+        format!("Sensitivity CSV has a header and {} rows: {} lines", result.effects.len(), csv.lines().count()),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The price level is the most influential parameter on both KPIs
+    for metric_name in &result.metric_names {
+        let most_influential = result.ranking(metric_name)[0];
+        validation::check(
+            most_influential.parameter_name == "base_impression_value",
+            format!("Base impression value is the most influential parameter on {}: {} (swing {:.2}%)", metric_name, most_influential.parameter_name, most_influential.swing().unwrap_or(f64::NAN) * 100.0),
+            logger,
+            &mut errors,
+        );
+    }
+
+    // Check: Campaign 1's impressions are next most sensitive to its budget
+    let second_influential = result.ranking("campaign_1_impressions")[1];
+    validation::check(
+        second_influential.parameter_name == "campaign_1_target_budget",
+        format!("Campaign 1 impressions are next most sensitive to campaign_1_target_budget: {} (swing {:.2}%)", second_influential.parameter_name, second_influential.swing().unwrap_or(f64::NAN) * 100.0),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The controllers compensate for the boost, so it barely moves either KPI
+    for metric_name in &result.metric_names {
+        let swing = result.effect("boost", metric_name).swing().unwrap_or(f64::NAN);
+        assertions::assert_between(&format!("Boost barely moves {}, swing", metric_name), swing, 0.0, 0.02, logger, &mut errors);
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
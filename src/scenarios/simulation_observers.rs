@@ -0,0 +1,243 @@
+//! This scenario demonstrates observers attached to the simulation lifecycle.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, with two observers attached to the
+//! convergence loop: a win recorder, counting the impressions each campaign wins from the auction results of
+//! every iteration and comparing them with the iteration's statistics at its end, and a spend recorder, which
+//! doesn't use auction results and records the budget campaign's spend at the end of every iteration. Next
+//! to them a closure subscribed to the end of every iteration records the iterations it sees.
+//!
+//! - Variant A: Standard auctions
+//!
+//! - Variant B: Fractional internal auctions (impressions won fractionally)
+//!
+//! In both variants the observers and the closure should see every iteration, the observers the convergence
+//! once, the wins counted from the auction results should match the statistics, and the spend trajectory
+//! should end at the budget.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::hooks::{AuctionEvent, AuctionOutcome, SimulationObserverTrait};
+use crate::impressions::{FractionalWinners, ImpressionsParam, Winner};
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "simulation_observers",
+    run,
+    tags: &["fast", "fractional"],
+});
+
+/// Budget of campaign 1
+const TARGET_BUDGET: f64 = 20.0;
+
+/// Counts the impressions won by each campaign from the auction results and compares them with the statistics
+#[derive(Default)]
+struct WinRecorder {
+    /// Impressions won by each campaign in the current iteration
+    wins: Vec<f64>,
+    /// Iterations seen (on_iteration_end)
+    iterations: Vec<usize>,
+    /// Largest difference between the wins counted and a campaign's impressions obtained, over all iterations
+    max_difference: f64,
+    /// Iterations of the statistics the convergence was reported with
+    converged_iterations: Vec<usize>,
+}
+
+impl SimulationObserverTrait for WinRecorder {
+    fn on_iteration_start(&mut self, _iteration: usize, _logger: &mut Logger) {
+        self.wins.clear();
+    }
+
+    fn on_auction_result(&mut self, event: &AuctionEvent, _logger: &mut Logger) {
+        let mut add_win = |campaign_id: usize, win_fraction: f64| {
+            if self.wins.len() <= campaign_id {
+                self.wins.resize(campaign_id + 1, 0.0);
+            }
+            self.wins[campaign_id] += win_fraction * event.impression.weight;
+        };
+        match event.outcome {
+            AuctionOutcome::Standard(result) => {
+                if let Winner::Campaign { campaign_id, .. } = result.winner {
+                    add_win(campaign_id, 1.0);
+                }
+            }
+            AuctionOutcome::Fractional(result) => {
+                if let FractionalWinners::Campaigns { winners } = &result.winner {
+                    for winner in winners {
+                        add_win(winner.campaign_id, winner.win_fraction);
+                    }
+                }
+            }
+        }
+    }
+
+    fn on_iteration_end(&mut self, iteration: usize, stats: &SimulationStat, logger: &mut Logger) {
+        for (campaign_id, campaign_stat) in stats.campaign_stats.iter().enumerate() {
+            let difference = (self.wins.get(campaign_id).copied().unwrap_or(0.0) - campaign_stat.impressions_obtained).abs();
+            self.max_difference = self.max_difference.max(difference);
+        }
+        self.iterations.push(iteration);
+        logln!(logger, LogEvent::Simulation, "Win recorder: iteration {} wins {:?}", iteration, self.wins);
+    }
+
+    fn on_converged(&mut self, stats: &SimulationStat, _logger: &mut Logger) {
+        self.converged_iterations.push(stats.convergence_iterations);
+    }
+}
+
+/// Records the spend of a campaign at the end of every iteration
+struct SpendRecorder {
+    campaign_id: usize,
+    /// Spend in each iteration seen
+    spend: Vec<f64>,
+}
+
+impl SimulationObserverTrait for SpendRecorder {
+    fn on_iteration_end(&mut self, _iteration: usize, stats: &SimulationStat, _logger: &mut Logger) {
+        self.spend.push(stats.campaign_stats[self.campaign_id].total_buyer_charge);
+    }
+
+    fn wants_auction_results(&self) -> bool {
+        false
+    }
+}
+
+/// Simulation converge instance with handles to its observers and the iterations seen by its closure hook
+struct ObservedSimulation {
+    simulation_converge: SimulationConverge,
+    win_recorder: Rc<RefCell<WinRecorder>>,
+    spend_recorder: Rc<RefCell<SpendRecorder>>,
+    closure_iterations: Rc<RefCell<Vec<usize>>>,
+}
+
+/// Prepare simulation converge instance with campaign and seller setup and the observers attached
+fn prepare_simulationconverge(simulation_type: SimulationType) -> ObservedSimulation {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    let budget_campaign_id = campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_BUDGET }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, simulation_type);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally) with the
+    // observers attached, keeping handles to read their measurements
+    let win_recorder = Rc::new(RefCell::new(WinRecorder::default()));
+    let spend_recorder = Rc::new(RefCell::new(SpendRecorder { campaign_id: budget_campaign_id, spend: Vec::new() }));
+    let simulation_converge = SimulationConverge::new(marketplace)
+        .with_observer(win_recorder.clone())
+        .with_observer(spend_recorder.clone());
+
+    // Record the iterations seen by a closure subscribed to the end of every iteration
+    let closure_iterations = Rc::new(RefCell::new(Vec::new()));
+    let closure_iterations_end = closure_iterations.clone();
+    simulation_converge.hooks.borrow_mut().on_iteration_end(move |iteration, _stats, _logger| {
+        closure_iterations_end.borrow_mut().push(iteration);
+    });
+
+    ObservedSimulation { simulation_converge, win_recorder, spend_recorder, closure_iterations }
+}
+
+/// Check the observers' measurements of a converged variant
+fn check_observers(label: &str, stats: &SimulationStat, observed: &ObservedSimulation, logger: &mut Logger, errors: &mut Vec<String>) {
+    let win_recorder = observed.win_recorder.borrow();
+    let spend_recorder = observed.spend_recorder.borrow();
+    let closure_iterations = observed.closure_iterations.borrow();
+    // Check: The observers and the closure saw every iteration, the observers the convergence once with the final statistics
+    let expected_iterations: Vec<usize> = (1..=stats.convergence_iterations).collect();
+    validation::check(
+        win_recorder.iterations == expected_iterations && spend_recorder.spend.len() == stats.convergence_iterations,
+        format!("{}: Observers saw all {} iterations (win recorder {}, spend recorder {})", label, stats.convergence_iterations, win_recorder.iterations.len(), spend_recorder.spend.len()),
+        logger,
+        errors,
+    );
+    validation::check(
+        *closure_iterations == expected_iterations,
+        format!("{}: Iteration end closure saw all {} iterations ({})", label, stats.convergence_iterations, closure_iterations.len()),
+        logger,
+        errors,
+    );
+    validation::check(
+        win_recorder.converged_iterations == vec![stats.convergence_iterations],
+        format!("{}: Convergence was reported once, at iteration {}: {:?}", label, stats.convergence_iterations, win_recorder.converged_iterations),
+        logger,
+        errors,
+    );
+
+    // Check: The wins counted from the auction results match the statistics in every iteration
+    validation::check(
+        win_recorder.max_difference < 1e-6,
+        format!("{}: Wins counted from auction results match impressions obtained (largest difference {:.2e})", label, win_recorder.max_difference),
+        logger,
+        errors,
+    );
+
+    // Check: The spend trajectory ends at the budget
+    let final_spend = spend_recorder.spend.last().copied().unwrap_or(0.0);
+    assertions::assert_within_pct(
+        &format!("{}: Spend trajectory ends at the budget (first iteration {:.4})", label, spend_recorder.spend.first().copied().unwrap_or(0.0)),
+        final_spend,
+        TARGET_BUDGET,
+        1.0,
+        logger,
+        errors,
+    );
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with standard auctions
+    let observed_a = prepare_simulationconverge(SimulationType::Standard);
+    let stats_a = observed_a.simulation_converge.run_variant("Running with observers, standard auctions", scenario_name, "standard", 100, logger)?;
+
+    // Run variant B with fractional internal auctions
+    let observed_b = prepare_simulationconverge(SimulationType::FractionalInternalAuction { softmax_temperature: 0.5 });
+    let stats_b = observed_b.simulation_converge.run_variant("Running with observers, fractional auctions", scenario_name, "fractional", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B", &stats_b, logger, &mut errors);
+    check_observers("Variant A", &stats_a, &observed_a, logger, &mut errors);
+    check_observers("Variant B", &stats_b, &observed_b, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
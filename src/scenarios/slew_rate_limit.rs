@@ -0,0 +1,145 @@
+//! This scenario compares the PD controller with and without slew rate limiting.
+//!
+//! - Variant A: Max margin campaign targeting total budget, paced by a fast-reacting PD controller
+//!
+//! - Variant B: Same campaign, with the PD controller wrapped in a ControllerSlewRateLimited
+//!
+//! The budget is far below what the campaign spends at the initial pacing, so the first iteration's stats
+//! are wildly off. The PD controller reacts with large pacing changes and overshoots the budget in later
+//! iterations. Limiting the change per iteration should keep the overshoot smaller.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignGeneral};
+use crate::controllers::{ControllerProportionalDerivative, ControllerSlewRateLimited, ControllerTrait};
+use crate::converge::SimulationConverge;
+use crate::hooks::AuctionOutcome;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "slew_rate_limit",
+    run,
+    tags: &["controllers"],
+});
+
+const TARGET_TOTAL_BUDGET: f64 = 10.0;
+const MAX_CHANGE_FRACTION: f64 = 0.1;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the campaign's spend in every iteration
+fn prepare_simulationconverge(slew_rate_limited: bool) -> (SimulationConverge, Rc<RefCell<Vec<f64>>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_TOTAL_BUDGET }],  // converge_target
+    );
+    // Fast-reacting PD controller that overshoots when the first iteration is far off
+    let pd_controller = ControllerProportionalDerivative::new_advanced(
+        0.002, // tolerance_fraction
+        0.5,   // max_adjustment_factor
+        0.2,   // proportional_gain
+        0.05,  // derivative_gain
+        true,  // rescaling
+    );
+    let converge_controller: Box<dyn ControllerTrait> = if slew_rate_limited {
+        Box::new(ControllerSlewRateLimited::new(Box::new(pd_controller), MAX_CHANGE_FRACTION))
+    } else {
+        Box::new(pd_controller)
+    };
+    let campaign_general = campaigns.campaigns[campaign_id].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+    campaign_general.converge_controllers[0] = converge_controller;
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Record the campaign's spend in every iteration
+    let spend_per_iteration = Rc::new(RefCell::new(Vec::new()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let spend_start = spend_per_iteration.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            spend_start.borrow_mut().push(0.0);
+        });
+        let spend_update = spend_per_iteration.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                if let Winner::Campaign { buyer_charge, .. } = result.winner {
+                    *spend_update.borrow_mut().last_mut().unwrap() += buyer_charge;
+                }
+            }
+        });
+    }
+
+    (simulation_converge, spend_per_iteration)
+}
+
+/// Largest overshoot past the budget, as a fraction of the budget
+/// The first iteration spends above the budget, so overshooting means spending below it
+fn largest_overshoot(spend_per_iteration: &[f64]) -> f64 {
+    spend_per_iteration.iter().map(|spend| (TARGET_TOTAL_BUDGET - spend) / TARGET_TOTAL_BUDGET).fold(0.0, f64::max)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with the PD controller
+    let (simulation_converge_a, spend_a) = prepare_simulationconverge(false);
+    simulation_converge_a.run_variant("Running with PD controller", scenario_name, "pd", 200, logger)?;
+
+    // Run variant B with the slew rate limited PD controller
+    let (simulation_converge_b, spend_b) = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with slew rate limited PD controller", scenario_name, "slew-rate-limited", 200, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Slew rate limiting reduces the overshoot
+    let overshoot_a = largest_overshoot(&spend_a.borrow());
+    let overshoot_b = largest_overshoot(&spend_b.borrow());
+    assertions::assert_greater(
+        "Variant A (PD) overshoots the budget more than variant B (slew rate limited)",
+        overshoot_a,
+        overshoot_b,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Slew rate limited controller still converges to the budget
+    let spend = stats_b.campaign_stats[0].total_buyer_charge;
+    assertions::assert_within_pct("Variant B (slew rate limited) spends its budget", spend, TARGET_TOTAL_BUDGET, 1.0, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,105 @@
+//! This scenario compares max margin bidding with and without awareness of soft floors.
+//!
+//! The seller uses a soft floor: winning bids below it pay second price, bids at or above it pay
+//! their bid. Max margin bidding shades bids as if every auction was first price, while the
+//! soft-floor-aware bidder bids truthfully below the soft floor when that gives higher expected margin.
+//!
+//! - Variant A: Max margin bidding, ignoring the soft floor
+//!
+//! - Variant B: Soft-floor-aware max margin bidding
+//!
+//! Both campaigns spend the same budget, the soft-floor-aware one should obtain more value per unit of spend.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "soft_floor_bidder",
+    run,
+    tags: &["fast", "floors"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(campaign_type: CampaignType) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        campaign_type,  // campaign_type - soft-floor-blind or soft-floor-aware max margin
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    // Soft floor is set around the typical impression value, so both price rules matter
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE_SOFT_FLOOR { soft_floor_cpm: 10.0 },  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with soft-floor-blind max margin bidding
+    let simulation_converge_a = prepare_simulationconverge(CampaignType::MAX_MARGIN);
+    let stats_a = simulation_converge_a.run_variant("Running with soft-floor-blind max margin bidding", scenario_name, "max-margin", 100, logger)?;
+
+    // Run variant B with soft-floor-aware max margin bidding
+    let simulation_converge_b = prepare_simulationconverge(CampaignType::MAX_MARGIN_SOFT_FLOOR);
+    let stats_b = simulation_converge_b.run_variant("Running with soft-floor-aware max margin bidding", scenario_name, "max-margin-soft-floor", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Variant B obtains more value per spend than variant A
+    let campaign_a = &stats_a.campaign_stats[0];
+    let campaign_b = &stats_b.campaign_stats[0];
+    let value_per_spend_a = if campaign_a.total_buyer_charge > 0.0 {
+        campaign_a.total_value / campaign_a.total_buyer_charge
+    } else {
+        0.0
+    };
+    let value_per_spend_b = if campaign_b.total_buyer_charge > 0.0 {
+        campaign_b.total_value / campaign_b.total_buyer_charge
+    } else {
+        0.0
+    };
+    assertions::assert_greater(
+        "Variant B (soft-floor-aware) has higher value per spend than variant A (soft-floor-blind)",
+        value_per_spend_b,
+        value_per_spend_a,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,166 @@
+//! This scenario compares a hard impressions constraint with soft ones, whose misses are penalized
+//! in the objective instead of being met at any price.
+//!
+//! A Lagrangian max margin campaign has a hard budget and needs more impressions than max margin
+//! bidding on its budget obtains.
+//!
+//! - Variant A: Hard minimum impressions
+//!
+//! - Variant B: Soft minimum impressions with a linear penalty, cheaper per thousand missed impressions
+//!   than the price variant A pays to meet the constraint
+//!
+//! - Variant C: Soft minimum impressions with a quadratic penalty
+//!
+//! Variant A meets the impressions constraint. Variant B gives up on it: its dual stops at the penalty
+//! weight, and it misses impressions but ends with a higher objective net of the penalty. Variant C
+//! prices the impressions at the marginal penalty of its miss, obtaining impressions in between.
+
+use crate::simulationrun::{Marketplace, SimulationType, CampaignStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{Campaigns, CampaignLagrangian, LagrangianConstraint, PenaltyFunction};
+use crate::converge::{SimulationConverge, CampaignControllerStates};
+use crate::controllers::ControllerStateDual;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "soft_targets",
+    run,
+    tags: &["slow"],
+});
+
+const MAX_BUDGET: f64 = 40.0;
+const MIN_IMPRESSIONS: i32 = 4200;
+/// Penalty per thousand missed impressions of variant B (in CPM, like the impressions dual)
+const LINEAR_PENALTY_WEIGHT: f64 = 2.0;
+/// Quadratic penalty weight of variant C (marginal penalty per thousand missed impressions, per thousand missed)
+const QUADRATIC_PENALTY_WEIGHT: f64 = 20.0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(impressions_constraint: LagrangianConstraint) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    campaigns.add_advanced(Box::new(CampaignLagrangian::new(
+        "Campaign 0".to_string(),  // campaign_name
+        vec![
+            LagrangianConstraint::MAX_BUDGET { budget: MAX_BUDGET },
+            impressions_constraint,
+        ],  // constraints
+    )));
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Soft impressions constraint with the given penalty
+fn soft_impressions(penalty: PenaltyFunction) -> LagrangianConstraint {
+    LagrangianConstraint::SOFT {
+        constraint: Box::new(LagrangianConstraint::MIN_IMPRESSIONS { impressions: MIN_IMPRESSIONS }),
+        penalty,
+    }
+}
+
+/// Dual of the impressions constraint
+fn impressions_dual(campaign_controller_states: &CampaignControllerStates) -> f64 {
+    campaign_controller_states.campaign_controller_states[0][0].as_any().downcast_ref::<ControllerStateDual>().unwrap().duals[1]
+}
+
+/// Objective of the campaign: value (in units of spend) - spend - penalty of soft targets
+fn objective(campaign_stat: &CampaignStat) -> f64 {
+    campaign_stat.total_value / 1000.0 - campaign_stat.total_buyer_charge - campaign_stat.soft_target_penalty.unwrap_or(0.0)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with a hard impressions constraint
+    let simulation_converge_a = prepare_simulationconverge(LagrangianConstraint::MIN_IMPRESSIONS { impressions: MIN_IMPRESSIONS });
+    let (stats_a, campaign_states_a, _) = simulation_converge_a.run_variant_with_states("Running with a hard impressions constraint", scenario_name, "hard", 500, logger)?;
+
+    // Run variant B with a linearly penalized impressions constraint
+    let simulation_converge_b = prepare_simulationconverge(soft_impressions(PenaltyFunction::LINEAR { weight: LINEAR_PENALTY_WEIGHT }));
+    let (stats_b, campaign_states_b, _) = simulation_converge_b.run_variant_with_states("Running with a soft impressions constraint (linear penalty)", scenario_name, "linear", 500, logger)?;
+
+    // Run variant C with a quadratically penalized impressions constraint
+    let simulation_converge_c = prepare_simulationconverge(soft_impressions(PenaltyFunction::QUADRATIC { weight: QUADRATIC_PENALTY_WEIGHT }));
+    let (stats_c, campaign_states_c, _) = simulation_converge_c.run_variant_with_states("Running with a soft impressions constraint (quadratic penalty)", scenario_name, "quadratic", 500, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let stat_a = &stats_a.campaign_stats[0];
+    let stat_b = &stats_b.campaign_stats[0];
+    let stat_c = &stats_c.campaign_stats[0];
+    let dual_a = impressions_dual(&campaign_states_a);
+    let dual_b = impressions_dual(&campaign_states_b);
+    let dual_c = impressions_dual(&campaign_states_c);
+
+    // Check: Hard constraint is met, at a higher price than variant B's penalty weight
+    validation::check(
+        stat_a.impressions_obtained >= MIN_IMPRESSIONS as f64 * 0.99 && stat_a.soft_target_penalty.is_none() && dual_a > LINEAR_PENALTY_WEIGHT,
+        format!("Variant A (hard) meets the impressions constraint: {:.0} >= {}, paying {:.4} > {:.1} CPM per impression", stat_a.impressions_obtained, MIN_IMPRESSIONS, dual_a, LINEAR_PENALTY_WEIGHT),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Linear penalty bounds the price, so the constraint is missed and penalized
+    let missed_b = (MIN_IMPRESSIONS as f64 - stat_b.impressions_obtained) / 1000.0;
+    let penalty_b = stat_b.soft_target_penalty.unwrap_or(0.0);
+    validation::check(
+        dual_b == LINEAR_PENALTY_WEIGHT && missed_b > 0.0 && (penalty_b - LINEAR_PENALTY_WEIGHT * missed_b).abs() < 1e-9,
+        format!("Variant B (linear) stops paying at the penalty weight: dual {:.4} == {:.1}, {:.0} impressions missed, penalty {:.4}", dual_b, LINEAR_PENALTY_WEIGHT, missed_b * 1000.0, penalty_b),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Missing the cheaply penalized constraint is worth it
+    assertions::assert_greater(
+        "Variant B (linear) has a higher objective net of the penalty than variant A (hard)",
+        objective(stat_b),
+        objective(stat_a),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Quadratic penalty prices impressions at the marginal penalty of the miss, i.e. the miss
+    // is the one whose marginal penalty is the dual (within 1% of the constraint)
+    let missed_c = MIN_IMPRESSIONS as f64 - stat_c.impressions_obtained;
+    let priced_miss_c = dual_c / QUADRATIC_PENALTY_WEIGHT * 1000.0;
+    validation::check(
+        (missed_c - priced_miss_c).abs() <= MIN_IMPRESSIONS as f64 * 0.01 && stat_c.impressions_obtained > stat_b.impressions_obtained && stat_c.impressions_obtained < stat_a.impressions_obtained,
+        format!("Variant C (quadratic) misses the impressions priced by its dual: {:.0} ≈ {:.0} (dual {:.4}), obtaining impressions between variants B and A: {:.0} < {:.0} < {:.0}",
+            missed_c, priced_miss_c, dual_c, stat_b.impressions_obtained, stat_c.impressions_obtained, stat_a.impressions_obtained),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -22,7 +22,8 @@ use crate::floors;
 use crate::utils;
 use crate::logger::{Logger, LogEvent};
 use crate::logln;
-use crate::errln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
 use crate::seller_targets::{SellerTargetNone, SellerTargetTotalCost};
 use crate::seller_chargers::{SellerChargerFirstPrice, SellerChargerFixedPrice};
 
@@ -30,6 +31,7 @@ use crate::seller_chargers::{SellerChargerFirstPrice, SellerChargerFixedPrice};
 inventory::submit!(crate::scenarios::ScenarioEntry {
     short_name: "supply_controlled_boost",
     run,
+    tags: &["fast", "supply"],
 });
 
 /// Prepare simulation converge instance with campaign and seller setup
@@ -102,6 +104,7 @@ fn prepare_variant(dynamic_boost: bool, campaign_type: CampaignType) -> Simulati
         seller_charger: Box::new(SellerChargerFixedPrice {
             fixed_cost_cpm,
         }),
+        soft_floor_cpm: None,
     });
     sellers.add_advanced(seller_mrg);
 
@@ -115,6 +118,7 @@ fn prepare_variant(dynamic_boost: bool, campaign_type: CampaignType) -> Simulati
         competition_generator: CompetitionGeneratorLogNormal::new(10.0),
         floor_generator: floors::FloorGeneratorLogNormal::new(0.2, 3.0),
         seller_charger: Box::new(SellerChargerFirstPrice),
+        soft_floor_cpm: None,
     });
     sellers.add_advanced(seller_hb);
 
@@ -156,63 +160,33 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     let mut errors: Vec<String> = Vec::new();
     
     // Check: Variant A (no boost) - seller 0 should not be profitable (supply_cost > virtual_cost)
-    let msg = format!(
-        "Variant A (no boost) - Seller 0 (MRG) is not profitable (supply_cost > virtual_cost): {:.2} > {:.2}",
+    assertions::assert_greater(
+        "Variant A (no boost) - Seller 0 (MRG) is not profitable, supply cost above virtual cost",
         stats_a.seller_stats[0].total_supply_cost,
-        stats_a.seller_stats[0].total_virtual_cost
+        stats_a.seller_stats[0].total_virtual_cost,
+        logger,
+        &mut errors,
     );
-    if stats_a.seller_stats[0].total_supply_cost > stats_a.seller_stats[0].total_virtual_cost {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
     
     // Check: Variant B (dynamic boost, Multiplicative) - total overall supply and virtual cost should be nearly equal (max 1% off)
     let supply_cost = stats_b.overall_stat.total_supply_cost;
     let virtual_cost = stats_b.overall_stat.total_virtual_cost;
-    let diff = (supply_cost - virtual_cost).abs();
-    let max_diff = supply_cost.max(virtual_cost) * 0.01; // 1% of the larger value
-    let msg = format!(
-        "Variant B (dynamic boost, Multiplicative) - Total overall supply and virtual cost are nearly equal (within 1%): supply={:.2}, virtual={:.2}, diff={:.2}, max_diff={:.2}",
-        supply_cost, virtual_cost, diff, max_diff
-    );
-    if diff <= max_diff {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_within_pct("Variant B (dynamic boost, Multiplicative) - Total overall virtual cost matches supply cost", virtual_cost, supply_cost, 1.0, logger, &mut errors);
     
 
     // Check: Variant C (dynamic boost with MULTIPLICATIVE_ADDITIVE) - total overall supply and virtual cost should be nearly equal (max 1% off)
     let supply_cost_c = stats_c.overall_stat.total_supply_cost;
     let virtual_cost_c = stats_c.overall_stat.total_virtual_cost;
-    let diff_c = (supply_cost_c - virtual_cost_c).abs();
-    let max_diff_c = supply_cost_c.max(virtual_cost_c) * 0.01; // 1% of the larger value
-    let msg = format!(
-        "Variant C (dynamic boost, Multiplicative Additive) - Total overall supply and virtual cost are nearly equal (within 1%): supply={:.2}, virtual={:.2}, diff={:.2}, max_diff={:.2}",
-        supply_cost_c, virtual_cost_c, diff_c, max_diff_c
-    );
-    if diff_c <= max_diff_c {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_within_pct("Variant C (dynamic boost, Multiplicative Additive) - Total overall virtual cost matches supply cost", virtual_cost_c, supply_cost_c, 1.0, logger, &mut errors);
 
     // Check: Variant B (dynamic boost, Multiplicative) total supply cost should be lower than variant A (no boost)
-    let msg = format!(
-        "Variant B (dynamic boost, Multiplicative) total supply cost is lower than variant A (no boost): {:.2} < {:.2}",
+    assertions::assert_greater(
+        "Variant A (no boost) total supply cost is higher than variant B (dynamic boost, Multiplicative)",
+        stats_a.overall_stat.total_supply_cost,
         stats_b.overall_stat.total_supply_cost,
-        stats_a.overall_stat.total_supply_cost
+        logger,
+        &mut errors,
     );
-    if stats_b.overall_stat.total_supply_cost < stats_a.overall_stat.total_supply_cost {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
     // Check: Variant B (dynamic boost, Multiplicative) should have higher value-to-cost ratio than Variant C (additive)
     let supply_cost_b = stats_b.overall_stat.total_supply_cost;
     let value_b = stats_b.overall_stat.total_value;
@@ -222,29 +196,21 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     if supply_cost_b > 0.0 && supply_cost_c_check > 0.0 {
         let ratio_b = value_b / supply_cost_b;
         let ratio_c = value_c / supply_cost_c_check;
-        let msg = format!(
-            "Variant B (dynamic boost, Multiplicative) has higher value-to-cost ratio than Variant C (additive): {:.4} > {:.4}",
-            ratio_b, ratio_c
+        assertions::assert_greater(
+            "Variant B (dynamic boost, Multiplicative) has higher value-to-cost ratio than Variant C (additive)",
+            ratio_b,
+            ratio_c,
+            logger,
+            &mut errors,
         );
-        if ratio_b > ratio_c {
-            logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-        } else {
-            errors.push(msg.clone());
-            errln!(logger, LogEvent::Scenario, "{}", msg);
-        }
     } else {
         let msg = format!(
             "Cannot compare value-to-cost ratios: Variant B supply_cost={:.2}, Variant C supply_cost={:.2}",
             supply_cost_b, supply_cost_c_check
         );
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
+        validation::check(false, msg, logger, &mut errors);
     }
     
     
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(format!("Scenario '{}' validation failed:\n{}", scenario_name, errors.join("\n")).into())
-    }
+    validation::scenario_result(scenario_name, errors)
 }
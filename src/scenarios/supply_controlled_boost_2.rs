@@ -22,7 +22,8 @@ use crate::floors;
 use crate::utils;
 use crate::logger::{Logger, LogEvent};
 use crate::logln;
-use crate::errln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
 use crate::seller_targets::{SellerTargetNone, SellerTargetTotalCost};
 use crate::seller_chargers::{SellerChargerFirstPrice, SellerChargerFixedPrice};
 
@@ -30,6 +31,7 @@ use crate::seller_chargers::{SellerChargerFirstPrice, SellerChargerFixedPrice};
 inventory::submit!(crate::scenarios::ScenarioEntry {
     short_name: "supply_controlled_boost_2",
     run,
+    tags: &["slow", "supply"],
 });
 
 /// Prepare simulation converge instance with campaign and seller setup
@@ -90,6 +92,7 @@ fn prepare_variant(campaign_type: CampaignType) -> SimulationConverge {
         seller_charger: Box::new(SellerChargerFixedPrice {
             fixed_cost_cpm,
         }),
+        soft_floor_cpm: None,
     });
     sellers.add_advanced(seller_mrg);
 
@@ -103,6 +106,7 @@ fn prepare_variant(campaign_type: CampaignType) -> SimulationConverge {
         competition_generator: CompetitionGeneratorLogNormal::new(10.0),
         floor_generator: floors::FloorGeneratorLogNormal::new(0.2, 3.0),
         seller_charger: Box::new(SellerChargerFirstPrice),
+        soft_floor_cpm: None,
     });
     sellers.add_advanced(seller_hb);
 
@@ -149,50 +153,17 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     // Check: Variant A (MAX_MARGIN) - total overall supply and virtual cost should be nearly equal (max 1% off)
     let supply_cost_a = stats_a.overall_stat.total_supply_cost;
     let virtual_cost_a = stats_a.overall_stat.total_virtual_cost;
-    let diff_a = (supply_cost_a - virtual_cost_a).abs();
-    let max_diff_a = supply_cost_a.max(virtual_cost_a) * 0.01; // 1% of the larger value
-    let msg = format!(
-        "Variant A (MAX_MARGIN) - Total overall supply and virtual cost are nearly equal (within 1%): supply={:.2}, virtual={:.2}, diff={:.2}, max_diff={:.2}",
-        supply_cost_a, virtual_cost_a, diff_a, max_diff_a
-    );
-    if diff_a <= max_diff_a {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_within_pct("Variant A (MAX_MARGIN) - Total overall virtual cost matches supply cost", virtual_cost_a, supply_cost_a, 1.0, logger, &mut errors);
     
     // Check: Variant B (MAX_MARGIN_ADDITIVE_SUPPLY) - total overall supply and virtual cost should be nearly equal (max 1% off)
     let supply_cost_b = stats_b.overall_stat.total_supply_cost;
     let virtual_cost_b = stats_b.overall_stat.total_virtual_cost;
-    let diff_b = (supply_cost_b - virtual_cost_b).abs();
-    let max_diff_b = supply_cost_b.max(virtual_cost_b) * 0.01; // 1% of the larger value
-    let msg = format!(
-        "Variant B (MAX_MARGIN_ADDITIVE_SUPPLY) - Total overall supply and virtual cost are nearly equal (within 1%): supply={:.2}, virtual={:.2}, diff={:.2}, max_diff={:.2}",
-        supply_cost_b, virtual_cost_b, diff_b, max_diff_b
-    );
-    if diff_b <= max_diff_b {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_within_pct("Variant B (MAX_MARGIN_ADDITIVE_SUPPLY) - Total overall virtual cost matches supply cost", virtual_cost_b, supply_cost_b, 1.0, logger, &mut errors);
     
     // Check: Variant C (MAX_MARGIN_EXPONENTIAL_SUPPLY) - total overall supply and virtual cost should be nearly equal (max 1% off)
     let supply_cost_c = stats_c.overall_stat.total_supply_cost;
     let virtual_cost_c = stats_c.overall_stat.total_virtual_cost;
-    let diff_c = (supply_cost_c - virtual_cost_c).abs();
-    let max_diff_c = supply_cost_c.max(virtual_cost_c) * 0.01; // 1% of the larger value
-    let msg = format!(
-        "Variant C (MAX_MARGIN_EXPONENTIAL_SUPPLY) - Total overall supply and virtual cost are nearly equal (within 1%): supply={:.2}, virtual={:.2}, diff={:.2}, max_diff={:.2}",
-        supply_cost_c, virtual_cost_c, diff_c, max_diff_c
-    );
-    if diff_c <= max_diff_c {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_within_pct("Variant C (MAX_MARGIN_EXPONENTIAL_SUPPLY) - Total overall virtual cost matches supply cost", virtual_cost_c, supply_cost_c, 1.0, logger, &mut errors);
 
     // Check: Variant A (MAX_MARGIN) should have better value-to-cost ratio than Variant B (MAX_MARGIN_ADDITIVE_SUPPLY)
     if supply_cost_a > 0.0 && supply_cost_b > 0.0 {
@@ -200,38 +171,31 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
         let value_b = stats_b.overall_stat.total_value;
         let ratio_a = value_a / supply_cost_a;
         let ratio_b = value_b / supply_cost_b;
-        let msg = format!(
-            "Variant A (MAX_MARGIN) has better value-to-cost ratio than Variant B (MAX_MARGIN_ADDITIVE_SUPPLY): {:.4} > {:.4}",
-            ratio_a, ratio_b
+        assertions::assert_greater(
+            "Variant A (MAX_MARGIN) has better value-to-cost ratio than Variant B (MAX_MARGIN_ADDITIVE_SUPPLY)",
+            ratio_a,
+            ratio_b,
+            logger,
+            &mut errors,
         );
-        if ratio_a > ratio_b {
-            logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-        } else {
-            errors.push(msg.clone());
-            errln!(logger, LogEvent::Scenario, "{}", msg);
-        }
     } else {
         let msg = format!(
             "Cannot compare value-to-cost ratios: Variant A supply_cost={:.2}, Variant B supply_cost={:.2}",
             supply_cost_a, supply_cost_b
         );
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
+        validation::check(false, msg, logger, &mut errors);
     }
 
     // Check: Variant A (MAX_MARGIN) should have higher total value than Variant B (MAX_MARGIN_ADDITIVE_SUPPLY)
     let value_a = stats_a.overall_stat.total_value;
     let value_b = stats_b.overall_stat.total_value;
-    let msg = format!(
-        "Variant A (MAX_MARGIN) has higher total value than Variant B (MAX_MARGIN_ADDITIVE_SUPPLY): {:.2} > {:.2}",
-        value_a, value_b
+    assertions::assert_greater(
+        "Variant A (MAX_MARGIN) has higher total value than Variant B (MAX_MARGIN_ADDITIVE_SUPPLY)",
+        value_a,
+        value_b,
+        logger,
+        &mut errors,
     );
-    if value_a > value_b {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
     
     // Check: Variant C (MAX_MARGIN_EXPONENTIAL_SUPPLY) - compare value-to-cost ratio with other variants
     if supply_cost_c > 0.0 {
@@ -244,9 +208,5 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
         logln!(logger, LogEvent::Scenario, "✓ {}", msg);
     }
     
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(format!("Scenario '{}' validation failed:\n{}", scenario_name, errors.join("\n")).into())
-    }
+    validation::scenario_result(scenario_name, errors)
 }
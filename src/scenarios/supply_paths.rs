@@ -0,0 +1,161 @@
+//! This scenario demonstrates duplicate supply paths: the same impression offered through several sellers.
+//!
+//! Two campaigns buy the impressions of a publisher's direct seller.
+//!
+//! - Variant A: The impressions are only offered through the direct seller
+//!
+//! - Variant B: A reseller re-offers the same impressions as a second supply path, with its own competition,
+//!   lower floors and a revenue share taken from the buyer charge. Paths of an impression are auctioned
+//!   in random order, and once the impression is sold through one path the other is withdrawn
+//!
+//! In variant B no impression is sold twice, each path has impressions withdrawn because the other path
+//! sold them, and the reseller keeps its share of the spend bought through it. With a second chance at
+//! every impression, the impressions campaign meets its target at a lower CPM than in variant A.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "supply_paths",
+    run,
+    tags: &["fast", "supply"],
+});
+
+/// Seller IDs
+const DIRECT: usize = 0;
+const RESELLER: usize = 1;
+/// Impressions of the publisher, all of them re-offered by the reseller in variant B
+const IMPRESSIONS_ON_OFFER: usize = 10000;
+/// Share of the buyer charge the reseller passes on to the publisher
+const RESELLER_REVENUE_SHARE: f64 = 0.8;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(with_reseller: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add sellers (IDs are automatically set to match Vec index)
+    sellers.add(
+        "Direct".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        IMPRESSIONS_ON_OFFER,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+    if with_reseller {
+        sellers.add(
+            "Reseller".to_string(),  // seller_name
+            SellerType::HYBRID { fixed_fee_cpm: 0.0, revenue_share: RESELLER_REVENUE_SHARE },  // seller_type
+            SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+            IMPRESSIONS_ON_OFFER,  // impressions_on_offer
+            CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+            floors::FloorGeneratorLogNormal::new(0.1, 3.0),  // floor_generator
+        );
+        sellers.set_supply_path(RESELLER, DIRECT);
+    }
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// CPM the impressions campaign pays
+fn impressions_campaign_cpm(stats: &SimulationStat) -> f64 {
+    let campaign_stat = &stats.campaign_stats[0];
+    campaign_stat.total_buyer_charge / campaign_stat.impressions_obtained * 1000.0
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with the direct seller only
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running with the direct supply path only", scenario_name, "direct", 100, logger)?;
+
+    // Run variant B with the reseller as a second supply path
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with a reseller as a second supply path", scenario_name, "reseller", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (direct)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (reseller)", &stats_b, logger, &mut errors);
+
+    // Check: No impression is sold through both paths, and every withdrawn path belongs to an impression sold through the other
+    let direct = &stats_b.seller_stats[DIRECT];
+    let reseller = &stats_b.seller_stats[RESELLER];
+    validation::check(
+        direct.impressions_sold + reseller.impressions_sold <= IMPRESSIONS_ON_OFFER
+            && direct.impressions_withdrawn <= reseller.impressions_sold
+            && reseller.impressions_withdrawn <= direct.impressions_sold,
+        format!("Variant B (reseller) sells each impression at most once: {} + {} <= {} sold, withdrawn {} (direct) <= {} and {} (reseller) <= {}",
+            direct.impressions_sold, reseller.impressions_sold, IMPRESSIONS_ON_OFFER,
+            direct.impressions_withdrawn, reseller.impressions_sold, reseller.impressions_withdrawn, direct.impressions_sold),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Paths are auctioned in random order, so both paths have impressions withdrawn
+    validation::check(
+        direct.impressions_withdrawn > 0 && reseller.impressions_withdrawn > 0,
+        format!("Variant B (reseller) withdraws impressions on both paths: {} (direct) and {} (reseller)", direct.impressions_withdrawn, reseller.impressions_withdrawn),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Reseller keeps its share of the spend bought through it
+    let reseller_margin = reseller.total_buyer_charge - reseller.total_supply_cost;
+    let expected_margin = reseller.total_buyer_charge * (1.0 - RESELLER_REVENUE_SHARE);
+    validation::check(
+        reseller.total_buyer_charge > 0.0 && (reseller_margin - expected_margin).abs() < 1e-9,
+        format!("Variant B (reseller) reseller keeps {:.0}% of its buyer charge: {:.4} of {:.4}", (1.0 - RESELLER_REVENUE_SHARE) * 100.0, reseller_margin, reseller.total_buyer_charge),
+        logger,
+        &mut errors,
+    );
+
+    // Check: A second chance at every impression makes the impressions target cheaper
+    let cpm_a = impressions_campaign_cpm(&stats_a);
+    let cpm_b = impressions_campaign_cpm(&stats_b);
+    assertions::assert_greater(
+        "Variant A (direct) impressions campaign pays a higher CPM than variant B (reseller)",
+        cpm_a,
+        cpm_b,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
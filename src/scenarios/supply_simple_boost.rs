@@ -16,12 +16,13 @@ use crate::floors;
 use crate::utils;
 use crate::logger::{Logger, LogEvent};
 use crate::logln;
-use crate::errln;
+use crate::scenarios::{validation, assertions};
 
 // Register this scenario in the catalog
 inventory::submit!(crate::scenarios::ScenarioEntry {
     short_name: "supply_simple_boost",
     run,
+    tags: &["fast", "supply"],
 });
 
 /// Prepare simulation converge instance with campaign and seller setup
@@ -108,86 +109,22 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     let mut errors: Vec<String> = Vec::new();
     
     // Check: Variant A is unprofitable (overall)
-    let msg = format!(
-        "Variant A (MRG boost 1.0) is unprofitable (supply_cost > buyer_charge): {:.2} > {:.2}",
-        stats_a.overall_stat.total_supply_cost,
-        stats_a.overall_stat.total_buyer_charge
-    );
-    if stats_a.overall_stat.total_supply_cost > stats_a.overall_stat.total_buyer_charge {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_greater("Variant A (MRG boost 1.0) is unprofitable (supply_cost > buyer_charge)", stats_a.overall_stat.total_supply_cost, stats_a.overall_stat.total_buyer_charge, logger, &mut errors);
     
     // Check: Variant B is profitable (overall)
-    let msg = format!(
-        "Variant B (MRG boost 2.0) is profitable (supply_cost < buyer_charge): {:.2} < {:.2}",
-        stats_b.overall_stat.total_supply_cost,
-        stats_b.overall_stat.total_buyer_charge
-    );
-    if stats_b.overall_stat.total_supply_cost < stats_b.overall_stat.total_buyer_charge {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_greater("Variant B (MRG boost 2.0) is profitable (buyer_charge > supply_cost)", stats_b.overall_stat.total_buyer_charge, stats_b.overall_stat.total_supply_cost, logger, &mut errors);
     
     // Check: Seller 0 (MRG) is unprofitable in variant A
-    let msg = format!(
-        "Seller 0 (MRG) in variant A (MRG boost 1.0) is unprofitable (supply_cost > buyer_charge): {:.2} > {:.2}",
-        stats_a.seller_stats[0].total_supply_cost,
-        stats_a.seller_stats[0].total_buyer_charge
-    );
-    if stats_a.seller_stats[0].total_supply_cost > stats_a.seller_stats[0].total_buyer_charge {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_greater("Seller 0 (MRG) in variant A (MRG boost 1.0) is unprofitable (supply_cost > buyer_charge)", stats_a.seller_stats[0].total_supply_cost, stats_a.seller_stats[0].total_buyer_charge, logger, &mut errors);
     
     // Check: Seller 0 (MRG) is profitable in variant B
-    let msg = format!(
-        "Seller 0 (MRG) in variant B (MRG boost 2.0) is profitable (supply_cost < buyer_charge): {:.2} < {:.2}",
-        stats_b.seller_stats[0].total_supply_cost,
-        stats_b.seller_stats[0].total_buyer_charge
-    );
-    if stats_b.seller_stats[0].total_supply_cost < stats_b.seller_stats[0].total_buyer_charge {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_greater("Seller 0 (MRG) in variant B (MRG boost 2.0) is profitable (buyer_charge > supply_cost)", stats_b.seller_stats[0].total_buyer_charge, stats_b.seller_stats[0].total_supply_cost, logger, &mut errors);
     
     // Check: Variant A has more total value than variant B
-    let msg = format!(
-        "Variant A (MRG boost 1.0) has more total value than variant B (MRG boost 2.0): {:.2} > {:.2}",
-        stats_a.overall_stat.total_value,
-        stats_b.overall_stat.total_value
-    );
-    if stats_a.overall_stat.total_value > stats_b.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_greater("Variant A (MRG boost 1.0) has more total value than variant B (MRG boost 2.0)", stats_a.overall_stat.total_value, stats_b.overall_stat.total_value, logger, &mut errors);
     
     // Check: Variant A has lower total cost than variant B
-    let msg = format!(
-        "Variant A (MRG boost 1.0) has lower total cost than variant B (MRG boost 2.0): {:.2} < {:.2}",
-        stats_a.overall_stat.total_buyer_charge,
-        stats_b.overall_stat.total_buyer_charge
-    );
-    if stats_a.overall_stat.total_buyer_charge < stats_b.overall_stat.total_buyer_charge {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "{}", msg);
-    }
+    assertions::assert_greater("Variant B (MRG boost 2.0) has higher total cost than variant A (MRG boost 1.0)", stats_b.overall_stat.total_buyer_charge, stats_a.overall_stat.total_buyer_charge, logger, &mut errors);
     
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(format!("Scenario '{}' validation failed:\n{}", scenario_name, errors.join("\n")).into())
-    }
+    validation::scenario_result(scenario_name, errors)
 }
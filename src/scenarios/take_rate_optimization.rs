@@ -0,0 +1,126 @@
+//! This scenario demonstrates a seller tuning its take rate to maximize its profit.
+//!
+//! Two campaigns bid their value (without pacing) on a single seller without competing demand. The seller is
+//! paid a revenue share of the clearing price, and its floors hold for its share: the lower the share
+//! (the higher the take rate), the higher the floors bidders see and the fewer impressions are sold.
+//!
+//! - Variant A: Fixed revenue share of 80%
+//!
+//! - Variant B: Revenue share tuned by a line search to maximize profit (buyer charge - supply cost), starting at 80%
+//!
+//! - Variants C and D: Fixed revenue shares just below and just above the one variant B finds
+//!
+//! Variant B is expected to earn more than variant A, and at least as much as variants C and D, i.e. to
+//! end at a (local) maximum of profit.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers, SellerTargetMaxProfit};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorNone;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "take_rate_optimization",
+    run,
+    tags: &["fast", "floors", "supply"],
+});
+
+/// Revenue share of variant A, and the starting point of variant B
+const INITIAL_REVENUE_SHARE: f64 = 0.8;
+/// Distance of variants C and D from the revenue share variant B finds
+const NEIGHBOUR_DISTANCE: f64 = 0.02;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(revenue_share: f64, seller_converge: SellerConvergeStrategy) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns bidding their value (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::NONE { default_pacing: 1.0 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::NONE { default_pacing: 1.0 }],  // converge_target
+    );
+
+    // Add seller paid a revenue share of the clearing price (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::HYBRID { fixed_fee_cpm: 0.0, revenue_share },  // seller_type
+        seller_converge,  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorNone::new(),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.5, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Profit of the seller
+fn profit(stats: &SimulationStat) -> f64 {
+    SellerTargetMaxProfit::profit(&stats.seller_stats[0])
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with a fixed revenue share
+    let simulation_converge_a = prepare_simulationconverge(INITIAL_REVENUE_SHARE, SellerConvergeStrategy::NONE { default_value: 1.0 });
+    let stats_a = simulation_converge_a.run_variant("Running with a fixed revenue share", scenario_name, "fixed", 100, logger)?;
+
+    // Run variant B with the revenue share tuned to maximize profit
+    let simulation_converge_b = prepare_simulationconverge(INITIAL_REVENUE_SHARE, SellerConvergeStrategy::MAX_PROFIT);
+    let (stats_b, _, seller_controller_states_b) = simulation_converge_b.run_variant_with_states("Running with the revenue share maximizing profit", scenario_name, "max_profit", 100, logger)?;
+    let revenue_share_b = simulation_converge_b.marketplace.sellers.sellers[0].get_control_variable(seller_controller_states_b.seller_controller_states[0][0].as_ref());
+
+    // Run variants C and D with fixed revenue shares around the one variant B found
+    let simulation_converge_c = prepare_simulationconverge(revenue_share_b - NEIGHBOUR_DISTANCE, SellerConvergeStrategy::NONE { default_value: 1.0 });
+    let stats_c = simulation_converge_c.run_variant("Running with a fixed revenue share below the one maximizing profit", scenario_name, "below", 100, logger)?;
+    let simulation_converge_d = prepare_simulationconverge(revenue_share_b + NEIGHBOUR_DISTANCE, SellerConvergeStrategy::NONE { default_value: 1.0 });
+    let stats_d = simulation_converge_d.run_variant("Running with a fixed revenue share above the one maximizing profit", scenario_name, "above", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Tuning the revenue share earns more than the fixed one
+    assertions::assert_greater(
+        &format!("Variant B (max profit) earns more than variant A (fixed) by moving the revenue share from {:.2} to {:.4}", INITIAL_REVENUE_SHARE, revenue_share_b),
+        profit(&stats_b),
+        profit(&stats_a),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The revenue share found is a local maximum of profit
+    validation::check(
+        profit(&stats_b) >= profit(&stats_c) && profit(&stats_b) >= profit(&stats_d),
+        format!("Variant B (max profit) earns at least as much as revenue shares {:.4} and {:.4} around it: {:.4} >= {:.4}, {:.4}",
+            revenue_share_b - NEIGHBOUR_DISTANCE, revenue_share_b + NEIGHBOUR_DISTANCE, profit(&stats_b), profit(&stats_c), profit(&stats_d)),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
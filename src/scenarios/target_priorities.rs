@@ -0,0 +1,112 @@
+//! This scenario converges a double target campaign with prioritized targets.
+//!
+//! A dual knob campaign converges its multiplier on a budget and its additive boost on an impressions
+//! target (as in the dual knob pacing scenario). Both knobs move both spend and impressions, and the
+//! additive boost controller is tuned aggressively (gains 2.0 / 1.0).
+//!
+//! - Variant A: Symmetric targets, both controllers adjust in every iteration
+//!
+//! - Variant B: Budget prioritized over impressions: the additive boost only adjusts in iterations
+//!   where the multiplier settled (lexicographic convergence)
+//!
+//! In variant A the controllers fight each other, each undoing the other's adjustments, and don't
+//! converge. In variant B only one knob moves at a time, so it should converge on both targets.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, ControllerConfig};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "target_priorities",
+    run,
+    tags: &["controllers"],
+});
+
+const TARGET_TOTAL_BUDGET: f64 = 20.0;
+const TARGET_TOTAL_IMPRESSIONS: i32 = 2500;
+/// Iterations the variants get to converge
+const MAX_ITERATIONS: usize = 300;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(prioritized: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add_configured(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_ADDITIVE_DOUBLE_TARGET,  // campaign_type
+        vec![
+            ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_TOTAL_BUDGET },
+            ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: TARGET_TOTAL_IMPRESSIONS },
+        ],  // converge_targets
+        vec![
+            ControllerConfig::new(),
+            // Aggressive additive boost controller
+            ControllerConfig::new().with_gains(2.0, 1.0),
+        ],  // controller_configs
+    );
+    if prioritized {
+        campaigns.set_target_priorities(campaign_id, vec![0, 1]);
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with symmetric targets, which is expected not to converge
+    let simulation_converge_a = prepare_simulationconverge(false);
+    let converged_a = simulation_converge_a.run_variant("Running with symmetric targets", scenario_name, "symmetric", MAX_ITERATIONS, logger).is_ok();
+
+    // Run variant B with the budget prioritized
+    let simulation_converge_b = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with budget prioritized over impressions", scenario_name, "prioritized", MAX_ITERATIONS, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Symmetric controllers fight each other and don't converge
+    validation::check(
+        !converged_a,
+        format!("Variant A (symmetric) doesn't converge within {} iterations", MAX_ITERATIONS),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Prioritized controllers converge on both targets (within the default 1%)
+    logln!(logger, LogEvent::Scenario, "Variant B (prioritized) converged in {} iterations", stats_b.convergence_iterations);
+    validation::assert_targets_met("Variant B (prioritized)", &stats_b, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,166 @@
+//! This scenario compares pacing between convergence iterations with pacing updated within the day.
+//!
+//! Traffic is not uniform over the day, most impressions arrive in the evening.
+//!
+//! - Variant A: Multiplicative pacing to the budget, updated only between convergence iterations
+//!
+//! - Variant B: Time paced campaign, base pacing updated between iterations and an intra-run
+//!   multiplier updated every hour by a PID controller targeting even delivery across the day
+//!
+//! Both campaigns spend the same budget. Variant A spends it following the traffic, while variant B
+//! should deliver it smoothly, staying close to the linear plan throughout the day.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignTimePaced, BidOptimizerTrutful};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, Winner, HOURS_PER_DAY};
+use crate::hooks::AuctionOutcome;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "time_based_pacing",
+    run,
+    tags: &["fast"],
+});
+
+const TOTAL_BUDGET: f64 = 20.0;
+const HOURS: usize = 24;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the hourly spend of the campaign in the latest iteration
+fn prepare_simulationconverge(time_paced: bool) -> (SimulationConverge, Rc<RefCell<Vec<f64>>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    if time_paced {
+        campaigns.add_advanced(Box::new(CampaignTimePaced::new(
+            "Campaign 0".to_string(),  // campaign_name
+            TOTAL_BUDGET,  // total_budget
+            Box::new(BidOptimizerTrutful),  // bid_optimizer
+            96,  // num_time_buckets (every 15 minutes)
+        )));
+    } else {
+        campaigns.add(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TOTAL_BUDGET }],  // converge_target
+        );
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        20000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, traffic peaks in the evening
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_time_of_day(utils::normal_dist(18.0, 5.0));
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Measure hourly spend of the campaign, reset at the start of every iteration
+    let hourly_spend = Rc::new(RefCell::new(vec![0.0; HOURS]));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let hourly_spend_reset = hourly_spend.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            hourly_spend_reset.borrow_mut().iter_mut().for_each(|spend| *spend = 0.0);
+        });
+        let hourly_spend_sum = hourly_spend.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                if let Winner::Campaign { buyer_charge, .. } = result.winner {
+                    let hour = ((event.impression.timestamp / HOURS_PER_DAY * HOURS as f64) as usize).min(HOURS - 1);
+                    hourly_spend_sum.borrow_mut()[hour] += buyer_charge;
+                }
+            }
+        });
+    }
+
+    (simulation_converge, hourly_spend)
+}
+
+/// Largest deviation of cumulative spend from even delivery of the actual spend, as a fraction of spend
+fn max_delivery_deviation(hourly_spend: &[f64]) -> f64 {
+    let total_spend: f64 = hourly_spend.iter().sum();
+    let mut cumulative_spend = 0.0;
+    let mut max_deviation: f64 = 0.0;
+    for (hour, spend) in hourly_spend.iter().enumerate() {
+        cumulative_spend += spend;
+        let planned_spend = total_spend * (hour + 1) as f64 / hourly_spend.len() as f64;
+        max_deviation = max_deviation.max((cumulative_spend - planned_spend).abs());
+    }
+    max_deviation / total_spend
+}
+
+/// Log hourly spend of a variant
+fn log_hourly_spend(logger: &mut Logger, variant_name: &str, hourly_spend: &[f64]) {
+    let hourly: Vec<String> = hourly_spend.iter().map(|spend| format!("{:.2}", spend)).collect();
+    logln!(logger, LogEvent::Scenario, "Hourly spend of {}: {}", variant_name, hourly.join(" "));
+}
+
+/// Total spend of the single campaign
+fn total_spend(stats: &SimulationStat) -> f64 {
+    stats.campaign_stats[0].total_buyer_charge
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with pacing updated only between iterations
+    let (simulation_converge_a, hourly_spend_a) = prepare_simulationconverge(false);
+    let stats_a = simulation_converge_a.run_variant("Running with pacing between iterations", scenario_name, "iteration-pacing", 100, logger)?;
+
+    // Run variant B with intra-run PID pacing per hour
+    let (simulation_converge_b, hourly_spend_b) = prepare_simulationconverge(true);
+    let stats_b = simulation_converge_b.run_variant("Running with intra-run time-based pacing", scenario_name, "time-pacing", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let hourly_spend_a = hourly_spend_a.borrow();
+    let hourly_spend_b = hourly_spend_b.borrow();
+    log_hourly_spend(logger, "variant A", &hourly_spend_a);
+    log_hourly_spend(logger, "variant B", &hourly_spend_b);
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Variant B delivers more evenly than variant A
+    let deviation_a = max_delivery_deviation(&hourly_spend_a);
+    let deviation_b = max_delivery_deviation(&hourly_spend_b);
+    assertions::assert_greater(
+        "Variant A deviates more from even delivery than variant B (time paced), max deviation",
+        deviation_a,
+        deviation_b,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Both variants spend the budget
+    for (variant_name, stats) in [("A", &stats_a), ("B", &stats_b)] {
+        let spend = total_spend(stats);
+        assertions::assert_within_pct(&format!("Variant {} spends the budget", variant_name), spend, TOTAL_BUDGET, 5.0, logger, &mut errors);
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,199 @@
+//! This scenario demonstrates pacing under competition whose intensity varies over the day.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, with traffic spread evenly
+//! over the day.
+//!
+//! - Variant A: Constant competition over the day
+//!
+//! - Variant B: Sinusoidal competition, competing bids 50% above average at 8pm and 50% below at 8am
+//!
+//! - Variant C: Piecewise competition, competing bids at 1.6x in prime time (6pm to midnight) and at 0.8x
+//!   for the rest of the day
+//!
+//! Pacing controllers converge on the whole day, so with time-varying competition the campaigns keep
+//! bidding the same over the day and win a lower share of the prime time impressions, when competition
+//! is strongest.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::hooks::AuctionOutcome;
+use crate::competition::{CompetitionGeneratorTrait, CompetitionGeneratorLogNormal, CompetitionGeneratorTimeVarying, CompetitionIntensityCurve, IntensityPeriod};
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "time_varying_competition",
+    run,
+    tags: &["fast"],
+});
+
+/// Prime time starts at 6pm and lasts until midnight
+const PRIME_TIME_START_HOUR: f64 = 18.0;
+
+/// Competing bids and impressions won by the campaigns, in prime time and during the rest of the day
+#[derive(Default)]
+struct PrimeTimeSplit {
+    prime_time_offered: f64,
+    prime_time_competing_bids: f64,
+    prime_time_won: f64,
+    other_offered: f64,
+    other_competing_bids: f64,
+    other_won: f64,
+}
+
+impl PrimeTimeSplit {
+    /// Average competing bid in prime time relative to the rest of the day
+    fn competing_bid_ratio(&self) -> f64 {
+        (self.prime_time_competing_bids / self.prime_time_offered) / (self.other_competing_bids / self.other_offered)
+    }
+
+    fn prime_time_win_rate(&self) -> f64 {
+        self.prime_time_won / self.prime_time_offered
+    }
+
+    fn other_win_rate(&self) -> f64 {
+        self.other_won / self.other_offered
+    }
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the prime time split of the latest iteration
+fn prepare_simulationconverge(competition_generator: Box<dyn CompetitionGeneratorTrait>) -> (SimulationConverge, Rc<RefCell<PrimeTimeSplit>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        competition_generator,  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Measure competition and purchases in prime time and the rest of the day, reset at the start of every iteration
+    let prime_time_split = Rc::new(RefCell::new(PrimeTimeSplit::default()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let prime_time_split_reset = prime_time_split.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            *prime_time_split_reset.borrow_mut() = PrimeTimeSplit::default();
+        });
+        let prime_time_split_sum = prime_time_split.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                let mut prime_time_split = prime_time_split_sum.borrow_mut();
+                let competing_bid = event.impression.competition.as_ref().map_or(0.0, |competition| competition.bid_cpm);
+                let won = if matches!(result.winner, Winner::Campaign { .. }) { 1.0 } else { 0.0 };
+                if event.impression.time_of_day() >= PRIME_TIME_START_HOUR {
+                    prime_time_split.prime_time_offered += 1.0;
+                    prime_time_split.prime_time_competing_bids += competing_bid;
+                    prime_time_split.prime_time_won += won;
+                } else {
+                    prime_time_split.other_offered += 1.0;
+                    prime_time_split.other_competing_bids += competing_bid;
+                    prime_time_split.other_won += won;
+                }
+            }
+        });
+    }
+
+    (simulation_converge, prime_time_split)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with constant competition
+    let (simulation_converge_a, split_a) = prepare_simulationconverge(CompetitionGeneratorLogNormal::new(10.0));
+    let stats_a = simulation_converge_a.run_variant("Running with constant competition", scenario_name, "constant", 100, logger)?;
+
+    // Run variant B with competition following a sinusoid peaking at 8pm
+    let (simulation_converge_b, split_b) = prepare_simulationconverge(CompetitionGeneratorTimeVarying::new(
+        CompetitionGeneratorLogNormal::new(10.0),
+        CompetitionIntensityCurve::SINUSOIDAL { amplitude: 0.5, peak_hour: 20.0 },
+    ));
+    let stats_b = simulation_converge_b.run_variant("Running with sinusoidal competition", scenario_name, "sinusoidal", 100, logger)?;
+
+    // Run variant C with stronger competition in prime time
+    let (simulation_converge_c, split_c) = prepare_simulationconverge(CompetitionGeneratorTimeVarying::new(
+        CompetitionGeneratorLogNormal::new(10.0),
+        CompetitionIntensityCurve::PIECEWISE { periods: vec![
+            IntensityPeriod { from_hour: 0.0, intensity: 0.8 },
+            IntensityPeriod { from_hour: PRIME_TIME_START_HOUR, intensity: 1.6 },
+        ] },
+    ));
+    let stats_c = simulation_converge_c.run_variant("Running with piecewise competition", scenario_name, "piecewise", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (constant)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (sinusoidal)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (piecewise)", &stats_c, logger, &mut errors);
+
+    let split_a = split_a.borrow();
+    let split_b = split_b.borrow();
+    let split_c = split_c.borrow();
+
+    // Check: Competing bids follow the intensity curves, stronger in prime time than during the rest of the day
+    assertions::assert_within_pct("Variant A (constant) prime time competing bids relative to the rest of the day", split_a.competing_bid_ratio(), 1.0, 10.0, logger, &mut errors);
+    assertions::assert_greater("Variant B (sinusoidal) prime time competing bids relative to the rest of the day", split_b.competing_bid_ratio(), 1.5, logger, &mut errors);
+    assertions::assert_within_pct("Variant C (piecewise) prime time competing bids relative to the rest of the day", split_c.competing_bid_ratio(), 2.0, 10.0, logger, &mut errors);
+
+    // Check: Campaigns win a lower share of impressions in prime time only with time-varying competition
+    assertions::assert_between(
+        "Variant A (constant) wins a similar share of impressions in prime time and the rest of the day, prime time share",
+        split_a.prime_time_win_rate(),
+        split_a.other_win_rate() - 0.05,
+        split_a.other_win_rate() + 0.05,
+        logger,
+        &mut errors,
+    );
+    for (label, split) in [("Variant B (sinusoidal)", &split_b), ("Variant C (piecewise)", &split_c)] {
+        assertions::assert_greater(
+            &format!("{} wins a higher share of impressions during the rest of the day than in prime time", label),
+            split.other_win_rate(),
+            split.prime_time_win_rate(),
+            logger,
+            &mut errors,
+        );
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
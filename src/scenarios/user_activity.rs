@@ -0,0 +1,133 @@
+//! This scenario demonstrates reach and frequency under heavy-tailed user activity.
+//!
+//! Impressions are shown to 2000 users, about five impressions per user. Two campaigns (impressions and
+//! budget targets) buy from a single seller.
+//!
+//! - Variant A: Uniform user activity
+//!
+//! - Variant B: Heavy-tailed user activity (Zipf, exponent 1.0), a few users seeing most impressions
+//!
+//! - Variant C: Heavy-tailed user activity with the impressions campaign capped at one impression per user
+//!
+//! With heavy-tailed activity, impressions reach fewer unique users and the impressions campaign wins
+//! several impressions of the heavy users, so its average frequency rises. A frequency cap spreads its
+//! impressions over as many users as it wins impressions.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, UserActivity};
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "user_activity",
+    run,
+    tags: &["fast"],
+});
+
+/// Number of users the impressions are shown to
+const NUM_USERS: usize = 2000;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(user_activity: UserActivity, frequency_cap: Option<usize>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    if let Some(frequency_cap) = frequency_cap {
+        campaigns.set_frequency_cap(campaign_id, frequency_cap);
+    }
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, showing impressions to users with the given activity
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_user_activity(NUM_USERS, user_activity);
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with uniform user activity
+    let simulation_converge_a = prepare_simulationconverge(UserActivity::UNIFORM, None);
+    let stats_a = simulation_converge_a.run_variant("Running with uniform user activity", scenario_name, "uniform", 100, logger)?;
+
+    // Run variant B with heavy-tailed user activity
+    let simulation_converge_b = prepare_simulationconverge(UserActivity::ZIPF { exponent: 1.0 }, None);
+    let stats_b = simulation_converge_b.run_variant("Running with heavy-tailed user activity", scenario_name, "zipf", 100, logger)?;
+
+    // Run variant C with heavy-tailed user activity and a frequency cap
+    let simulation_converge_c = prepare_simulationconverge(UserActivity::ZIPF { exponent: 1.0 }, Some(1));
+    let stats_c = simulation_converge_c.run_variant("Running with heavy-tailed user activity and a frequency cap", scenario_name, "zipf_capped", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (uniform)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (zipf)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (zipf, capped)", &stats_c, logger, &mut errors);
+
+    let campaign_a = &stats_a.campaign_stats[0];
+    let campaign_b = &stats_b.campaign_stats[0];
+    let campaign_c = &stats_c.campaign_stats[0];
+    logln!(logger, LogEvent::Scenario, "Impressions campaign reach (average frequency): uniform {} ({:.3}), zipf {} ({:.3}), zipf capped {} ({:.3})",
+        campaign_a.reach, campaign_a.average_frequency(), campaign_b.reach, campaign_b.average_frequency(), campaign_c.reach, campaign_c.average_frequency());
+
+    // Check: Heavy-tailed activity concentrates the impressions on fewer users
+    assertions::assert_greater(
+        "Variant A (uniform) shows impressions to more unique users than variant B (zipf)",
+        stats_a.unique_users as f64,
+        stats_b.unique_users as f64,
+        logger,
+        &mut errors,
+    );
+
+    // Check: The impressions campaign wins more impressions per user of the heavy users
+    assertions::assert_greater(
+        "Variant B (zipf) has higher average frequency than variant A (uniform)",
+        campaign_b.average_frequency(),
+        campaign_a.average_frequency(),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The frequency cap reaches a user with every impression won
+    assertions::assert_between("Variant C (zipf, capped) has average frequency at most 1", campaign_c.average_frequency(), 0.0, 1.0, logger, &mut errors);
+    assertions::assert_greater("Variant C (zipf, capped) has higher reach than variant B (zipf)", campaign_c.reach as f64, campaign_b.reach as f64, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
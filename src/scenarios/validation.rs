@@ -0,0 +1,75 @@
+//! Validation helpers shared by scenarios
+//!
+//! Scenarios validate their results with a list of checks, each logged as ✓ or ✗ on the Scenario
+//! event. Messages of failed checks are collected and turned into the scenario's error at the end.
+
+use crate::campaigns::{CampaignTargetMargin, CampaignTargetValuePerSpend};
+use crate::simulationrun::{CampaignStat, SimulationStat};
+use crate::seed_aggregation::{AggregatedStat, MetricSummary};
+use crate::scenarios::assertions;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::errln;
+
+/// Log a check as passed (✓) or failed (✗), collecting the message of a failed check into errors
+pub fn check(passed: bool, msg: String, logger: &mut Logger, errors: &mut Vec<String>) {
+    if passed {
+        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
+    } else {
+        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
+        errors.push(msg);
+    }
+}
+
+/// Result of a scenario, failing with all collected messages when any check failed, one failed check per line
+pub fn scenario_result(scenario_name: &str, errors: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        let failed: Vec<String> = errors.iter().map(|msg| format!("  ✗ {}", msg)).collect();
+        Err(format!("Scenario '{}' validation failed ({} checks):\n{}", scenario_name, errors.len(), failed.join("\n")).into())
+    }
+}
+
+/// Check that a campaign's margin ((value - buyer charge) / value, see CampaignTargetMargin) is within tolerance (absolute) of the target margin
+pub fn check_margin(label: &str, campaign_stat: &CampaignStat, target_margin: f64, tolerance: f64, logger: &mut Logger, errors: &mut Vec<String>) {
+    let margin = CampaignTargetMargin::margin(campaign_stat);
+    let label = format!("{}: Margin hits the target (value {:.2}, spend {:.2})", label, campaign_stat.total_value, campaign_stat.total_buyer_charge);
+    assertions::assert_between(&label, margin, target_margin - tolerance, target_margin + tolerance, logger, errors);
+}
+
+/// Check that a campaign's value per spend (total_value / total_buyer_charge, see CampaignTargetValuePerSpend) is within tolerance_fraction (relative) of the target
+pub fn check_value_per_spend(label: &str, campaign_stat: &CampaignStat, target_value_per_spend: f64, tolerance_fraction: f64, logger: &mut Logger, errors: &mut Vec<String>) {
+    let value_per_spend = CampaignTargetValuePerSpend::value_per_spend(campaign_stat);
+    let label = format!("{}: Value per spend hits the target (value {:.2}, spend {:.2})", label, campaign_stat.total_value, campaign_stat.total_buyer_charge);
+    assertions::assert_within_pct(&label, value_per_spend, target_value_per_spend, tolerance_fraction * 100.0, logger, errors);
+}
+
+/// Check that every convergence target of every campaign is met within its tolerance band
+/// (TargetTolerance, set with Campaigns::set_target_tolerances), logging one check per target
+pub fn assert_targets_met(label: &str, stats: &SimulationStat, logger: &mut Logger, errors: &mut Vec<String>) {
+    for (campaign_id, targets) in stats.campaign_targets.iter().enumerate() {
+        for target in targets {
+            let msg = format!("{}: Campaign {} meets target [{}]: {:.4} ≈ {:.4} ({})", label, campaign_id, target.name, target.actual, target.target, target.tolerance.tolerance_string());
+            check(target.is_met(), msg, logger, errors);
+        }
+    }
+}
+
+/// Check that every convergence target of every campaign is met on average across seeds, logging one check per
+/// target (see seed_aggregation::run_variant_seeds)
+pub fn assert_targets_met_aggregated(label: &str, stats: &AggregatedStat, logger: &mut Logger, errors: &mut Vec<String>) {
+    for (campaign_id, targets) in stats.campaign_targets.iter().enumerate() {
+        for target in targets {
+            let msg = format!("{}: Campaign {} meets target [{}] over {} seeds: {} ≈ {:.4} ({})", label, campaign_id, target.name, stats.seeds.len(), target.actual.summary_string(), target.target, target.tolerance.tolerance_string());
+            check(target.is_met(), msg, logger, errors);
+        }
+    }
+}
+
+/// Check that a metric aggregated across seeds is clearly larger in one variant than in another: its confidence
+/// interval lies entirely above the other's
+pub fn check_ci_above(label: &str, metric: &MetricSummary, other_label: &str, other_metric: &MetricSummary, logger: &mut Logger, errors: &mut Vec<String>) {
+    let msg = format!("{} is above {}: {} > {}", label, other_label, metric.summary_string(), other_metric.summary_string());
+    check(metric.ci_above(other_metric), msg, logger, errors);
+}
@@ -0,0 +1,192 @@
+//! This scenario demonstrates competition correlated with the impression's base value.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller, whose competing bids follow
+//! the same lognormal distribution as the base impression values.
+//!
+//! - Variant A: Competing bids independent of the base value (correlation 0.0)
+//!
+//! - Variant B: Competing bids correlated with the base value (correlation 0.8)
+//!
+//! Over all impressions the competing bids are the same in both variants. In variant B valuable impressions
+//! meet higher competition, so the campaigns win a lower share of the valuable impressions and shift their
+//! purchases towards cheaper ones.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::hooks::AuctionOutcome;
+use crate::competition::CompetitionGeneratorCorrelated;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "value_correlated_competition",
+    run,
+    tags: &["fast"],
+});
+
+/// Mean and stddev of base impression values, also used for the competing bids
+const VALUE_MEAN: f64 = 10.0;
+const VALUE_STDDEV: f64 = 3.0;
+/// Impressions with a base value above this are considered valuable (roughly the top 15%)
+const VALUABLE_IMPRESSION_VALUE: f64 = 13.0;
+
+/// Impressions offered and won by the campaigns, split by value, and the log base values and competing bids
+#[derive(Default)]
+struct ValueSplit {
+    valuable_offered: f64,
+    valuable_won: f64,
+    other_offered: f64,
+    other_won: f64,
+    log_values_and_bids: Vec<(f64, f64)>,
+}
+
+impl ValueSplit {
+    fn valuable_win_rate(&self) -> f64 {
+        self.valuable_won / self.valuable_offered
+    }
+
+    fn other_win_rate(&self) -> f64 {
+        self.other_won / self.other_offered
+    }
+
+    /// Pearson correlation between the log base values and the log competing bids
+    fn log_correlation(&self) -> f64 {
+        let count = self.log_values_and_bids.len() as f64;
+        let mean_value = self.log_values_and_bids.iter().map(|(value, _)| value).sum::<f64>() / count;
+        let mean_bid = self.log_values_and_bids.iter().map(|(_, bid)| bid).sum::<f64>() / count;
+        let (mut covariance, mut variance_value, mut variance_bid) = (0.0, 0.0, 0.0);
+        for (value, bid) in &self.log_values_and_bids {
+            covariance += (value - mean_value) * (bid - mean_bid);
+            variance_value += (value - mean_value).powi(2);
+            variance_bid += (bid - mean_bid).powi(2);
+        }
+        covariance / (variance_value * variance_bid).sqrt()
+    }
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the campaigns' purchases by value in the latest iteration
+fn prepare_simulationconverge(correlation: f64) -> (SimulationConverge, Rc<RefCell<ValueSplit>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorCorrelated::new(VALUE_MEAN, VALUE_STDDEV, VALUE_MEAN, VALUE_STDDEV, correlation),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(VALUE_MEAN, VALUE_STDDEV),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Measure the campaigns' purchases by value, reset at the start of every iteration
+    let value_split = Rc::new(RefCell::new(ValueSplit::default()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let value_split_reset = value_split.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            *value_split_reset.borrow_mut() = ValueSplit::default();
+        });
+        let value_split_sum = value_split.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                let mut value_split = value_split_sum.borrow_mut();
+                let won = if matches!(result.winner, Winner::Campaign { .. }) { 1.0 } else { 0.0 };
+                if event.impression.base_impression_value > VALUABLE_IMPRESSION_VALUE {
+                    value_split.valuable_offered += 1.0;
+                    value_split.valuable_won += won;
+                } else {
+                    value_split.other_offered += 1.0;
+                    value_split.other_won += won;
+                }
+                if let Some(competition) = &event.impression.competition {
+                    value_split.log_values_and_bids.push((event.impression.base_impression_value.ln(), competition.bid_cpm.ln()));
+                }
+            }
+        });
+    }
+
+    (simulation_converge, value_split)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with competing bids independent of the base value
+    let (simulation_converge_a, value_split_a) = prepare_simulationconverge(0.0);
+    let stats_a = simulation_converge_a.run_variant("Running with independent competition", scenario_name, "independent", 100, logger)?;
+
+    // Run variant B with competing bids correlated with the base value
+    let (simulation_converge_b, value_split_b) = prepare_simulationconverge(0.8);
+    let stats_b = simulation_converge_b.run_variant("Running with value-correlated competition", scenario_name, "correlated", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (independent)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (correlated)", &stats_b, logger, &mut errors);
+
+    let value_split_a = value_split_a.borrow();
+    let value_split_b = value_split_b.borrow();
+
+    // Check: Competing bids follow the configured correlation with the base value
+    let correlation_a = value_split_a.log_correlation();
+    let correlation_b = value_split_b.log_correlation();
+    assertions::assert_between("Variant A (independent) log competing bids correlate with log base values as configured (0.0)", correlation_a, -0.05, 0.05, logger, &mut errors);
+    assertions::assert_between("Variant B (correlated) log competing bids correlate with log base values as configured (0.8)", correlation_b, 0.75, 0.85, logger, &mut errors);
+
+    // Check: Valuable impressions meet higher competition, so a lower share of them is won
+    assertions::assert_greater(
+        "Variant A (independent) wins a higher share of valuable impressions than variant B (correlated)",
+        value_split_a.valuable_win_rate(),
+        value_split_b.valuable_win_rate(),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Purchases shift towards the other impressions, which meet lower competition
+    assertions::assert_greater(
+        "Variant B (correlated) wins a higher share of other impressions than variant A (independent)",
+        value_split_b.other_win_rate(),
+        value_split_a.other_win_rate(),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
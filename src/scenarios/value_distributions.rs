@@ -0,0 +1,169 @@
+//! This scenario demonstrates base impression values drawn from different distributions.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller with log-normal competing bids
+//! independent of the base values, the base values of its impressions drawn from:
+//!
+//! - Variant A: Log-normal distribution (mean 10, stddev 3)
+//!
+//! - Variant B: Pareto distribution (minimum 6, tail index 2.5, mean 10)
+//!
+//! - Variant C: Weibull distribution (shape 1.5, mean 10)
+//!
+//! - Variant D: Normal distribution (mean 10, stddev 3) truncated to [5, 15]
+//!
+//! - Variant E: Empirical distribution of the samples in data/base_impression_values.csv
+//!
+//! All distributions have about the same mean, but the heavy-tailed Pareto distribution concentrates much
+//! more of the value in its most valuable impressions, and the truncated normal distribution much less.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorCorrelated;
+use crate::floors;
+use crate::utils::{self, EmpiricalDist};
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use rand_distr::Distribution;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "value_distributions",
+    run,
+    tags: &["fast"],
+});
+
+/// CSV file with the base value samples of variant E
+const BASE_VALUE_SAMPLES_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/base_impression_values.csv");
+
+/// Mean of the parametric distributions
+const VALUE_MEAN: f64 = 10.0;
+/// Weibull scale giving VALUE_MEAN with shape 1.5: VALUE_MEAN / Γ(1 + 1/1.5)
+const WEIBULL_SCALE: f64 = 11.0773;
+/// Truncation bounds of the truncated normal distribution
+const TRUNCATION_LOW: f64 = 5.0;
+const TRUNCATION_HIGH: f64 = 15.0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge<D: Distribution<f64> + 'static>(base_impression_value_dist: D) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    // Competing bids are independent of the base value, whose distribution differs by variant
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorCorrelated::new(VALUE_MEAN, 3.0, VALUE_MEAN, 3.0, 0.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        base_impression_value_dist,  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Base values of the impressions on offer
+fn base_values(simulation_converge: &SimulationConverge) -> Vec<f64> {
+    simulation_converge.marketplace.impressions.impressions.iter().map(|impression| impression.base_impression_value).collect()
+}
+
+/// Share of the total base value in the 1% most valuable impressions
+fn top_percent_share(base_values: &[f64]) -> f64 {
+    let mut sorted = base_values.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    sorted[..sorted.len() / 100].iter().sum::<f64>() / sorted.iter().sum::<f64>()
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with log-normal base values
+    let simulation_converge_a = prepare_simulationconverge(utils::lognormal_dist(VALUE_MEAN, 3.0));
+    let values_a = base_values(&simulation_converge_a);
+    let stats_a = simulation_converge_a.run_variant("Running with log-normal base values", scenario_name, "lognormal", 100, logger)?;
+
+    // Run variant B with Pareto base values
+    let simulation_converge_b = prepare_simulationconverge(utils::pareto_dist(6.0, 2.5));
+    let values_b = base_values(&simulation_converge_b);
+    let stats_b = simulation_converge_b.run_variant("Running with Pareto base values", scenario_name, "pareto", 100, logger)?;
+
+    // Run variant C with Weibull base values
+    let simulation_converge_c = prepare_simulationconverge(utils::weibull_dist(WEIBULL_SCALE, 1.5));
+    let values_c = base_values(&simulation_converge_c);
+    let stats_c = simulation_converge_c.run_variant("Running with Weibull base values", scenario_name, "weibull", 100, logger)?;
+
+    // Run variant D with truncated normal base values
+    let simulation_converge_d = prepare_simulationconverge(utils::truncated_normal_dist(VALUE_MEAN, 3.0, TRUNCATION_LOW, TRUNCATION_HIGH));
+    let values_d = base_values(&simulation_converge_d);
+    let stats_d = simulation_converge_d.run_variant("Running with truncated normal base values", scenario_name, "truncated_normal", 100, logger)?;
+
+    // Run variant E with empirical base values
+    let empirical = EmpiricalDist::from_csv(BASE_VALUE_SAMPLES_PATH, "base_impression_value")?;
+    let empirical_mean = empirical.mean();
+    let simulation_converge_e = prepare_simulationconverge(empirical);
+    let values_e = base_values(&simulation_converge_e);
+    let stats_e = simulation_converge_e.run_variant("Running with empirical base values", scenario_name, "empirical", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (lognormal)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (pareto)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (weibull)", &stats_c, logger, &mut errors);
+    validation::assert_targets_met("Variant D (truncated normal)", &stats_d, logger, &mut errors);
+    validation::assert_targets_met("Variant E (empirical)", &stats_e, logger, &mut errors);
+
+    // Check: Base values average to the mean of their distribution
+    for (label, values, expected_mean) in [
+        ("Variant A (lognormal)", &values_a, VALUE_MEAN),
+        ("Variant B (pareto)", &values_b, VALUE_MEAN),
+        ("Variant C (weibull)", &values_c, VALUE_MEAN),
+        ("Variant D (truncated normal)", &values_d, VALUE_MEAN),
+        ("Variant E (empirical)", &values_e, empirical_mean),
+    ] {
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        assertions::assert_within_pct(&format!("{} average base value matches the distribution's mean", label), mean, expected_mean, 5.0, logger, &mut errors);
+    }
+
+    // Check: Truncated normal base values stay within the truncation bounds
+    validation::check(
+        values_d.iter().all(|&value| (TRUNCATION_LOW..=TRUNCATION_HIGH).contains(&value)),
+        format!("Variant D (truncated normal) base values are all within [{}, {}]", TRUNCATION_LOW, TRUNCATION_HIGH),
+        logger,
+        &mut errors,
+    );
+
+    // Check: The heavier the tail, the more value is concentrated in the most valuable impressions
+    let (share_a, share_b, share_d) = (top_percent_share(&values_a), top_percent_share(&values_b), top_percent_share(&values_d));
+    assertions::assert_greater("Share of the total base value in the top 1% of impressions is higher for pareto than lognormal", share_b, share_a, logger, &mut errors);
+    assertions::assert_greater("Share of the total base value in the top 1% of impressions is higher for lognormal than truncated normal", share_a, share_d, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
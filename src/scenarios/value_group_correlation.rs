@@ -0,0 +1,160 @@
+//! This scenario demonstrates correlated values of campaigns with similar targeting.
+//!
+//! Two campaigns (impressions and budget targets) in separate value groups buy from a single seller, the
+//! value multipliers of the groups drawn from log-normal distributions (mean 1.0, stddev 0.5) with:
+//!
+//! - Variant A: Independent multipliers (correlation 0.0)
+//!
+//! - Variant B: Correlated multipliers (correlation 0.9), the campaigns valuing the same impressions
+//!
+//! - Variant C: Anti-correlated multipliers (correlation -0.5), the campaigns valuing different impressions
+//!
+//! The more the campaigns' values are correlated, the more they compete for the same impressions and the more
+//! the impressions campaign has to pay per impression.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "value_group_correlation",
+    run,
+    tags: &["fast"],
+});
+
+/// Log value multipliers of the two value groups on every impression
+type LogMultipliers = Vec<(f64, f64)>;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the log value multipliers of the latest iteration
+fn prepare_simulationconverge(correlation: f64) -> (SimulationConverge, Rc<RefCell<LogMultipliers>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index), each in its own value group
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, the value multipliers of the groups correlated
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_correlated_value_multipliers(1.0, 0.5, vec![
+        vec![1.0, correlation],
+        vec![correlation, 1.0],
+    ]);
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Record the log value multipliers, reset at the start of every iteration
+    let log_multipliers = Rc::new(RefCell::new(Vec::new()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let log_multipliers_reset = log_multipliers.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            log_multipliers_reset.borrow_mut().clear();
+        });
+        let log_multipliers_push = log_multipliers.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            let impression = event.impression;
+            let log_multiplier = |group: usize| (impression.value_to_campaign_group[group] / impression.base_impression_value).ln();
+            log_multipliers_push.borrow_mut().push((log_multiplier(0), log_multiplier(1)));
+        });
+    }
+
+    (simulation_converge, log_multipliers)
+}
+
+/// Pearson correlation between the log value multipliers of the two groups
+fn log_correlation(log_multipliers: &LogMultipliers) -> f64 {
+    let count = log_multipliers.len() as f64;
+    let mean_0 = log_multipliers.iter().map(|(multiplier_0, _)| multiplier_0).sum::<f64>() / count;
+    let mean_1 = log_multipliers.iter().map(|(_, multiplier_1)| multiplier_1).sum::<f64>() / count;
+    let (mut covariance, mut variance_0, mut variance_1) = (0.0, 0.0, 0.0);
+    for (multiplier_0, multiplier_1) in log_multipliers {
+        covariance += (multiplier_0 - mean_0) * (multiplier_1 - mean_1);
+        variance_0 += (multiplier_0 - mean_0).powi(2);
+        variance_1 += (multiplier_1 - mean_1).powi(2);
+    }
+    covariance / (variance_0 * variance_1).sqrt()
+}
+
+/// Average CPM paid by the impressions campaign
+fn impressions_campaign_cpm(stats: &SimulationStat) -> f64 {
+    let campaign = &stats.campaign_stats[0];
+    campaign.total_buyer_charge / campaign.impressions_obtained * 1000.0
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with independent value multipliers
+    let (simulation_converge_a, log_multipliers_a) = prepare_simulationconverge(0.0);
+    let stats_a = simulation_converge_a.run_variant("Running with independent value multipliers", scenario_name, "independent", 100, logger)?;
+
+    // Run variant B with correlated value multipliers
+    let (simulation_converge_b, log_multipliers_b) = prepare_simulationconverge(0.9);
+    let stats_b = simulation_converge_b.run_variant("Running with correlated value multipliers", scenario_name, "correlated", 100, logger)?;
+
+    // Run variant C with anti-correlated value multipliers
+    let (simulation_converge_c, log_multipliers_c) = prepare_simulationconverge(-0.5);
+    let stats_c = simulation_converge_c.run_variant("Running with anti-correlated value multipliers", scenario_name, "anti_correlated", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (independent)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (correlated)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (anti-correlated)", &stats_c, logger, &mut errors);
+
+    // Check: Log value multipliers follow the configured correlations
+    let correlation_a = log_correlation(&log_multipliers_a.borrow());
+    let correlation_b = log_correlation(&log_multipliers_b.borrow());
+    let correlation_c = log_correlation(&log_multipliers_c.borrow());
+    assertions::assert_between("Variant A (independent) log value multipliers correlate as configured (0.0)", correlation_a, -0.05, 0.05, logger, &mut errors);
+    assertions::assert_between("Variant B (correlated) log value multipliers correlate as configured (0.9)", correlation_b, 0.85, 0.95, logger, &mut errors);
+    assertions::assert_between("Variant C (anti-correlated) log value multipliers correlate as configured (-0.5)", correlation_c, -0.55, -0.45, logger, &mut errors);
+
+    // Check: The more correlated the values, the more the impressions campaign pays per impression
+    let cpm_a = impressions_campaign_cpm(&stats_a);
+    let cpm_b = impressions_campaign_cpm(&stats_b);
+    let cpm_c = impressions_campaign_cpm(&stats_c);
+    assertions::assert_greater("Impressions campaign CPM is higher with correlated (variant B) than independent values (variant A)", cpm_b, cpm_a, logger, &mut errors);
+    assertions::assert_greater("Impressions campaign CPM is higher with independent (variant A) than anti-correlated values (variant C)", cpm_a, cpm_c, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -20,12 +20,14 @@ use crate::floors;
 use crate::utils;
 use crate::logger::{Logger, LogEvent};
 use crate::logln;
-use crate::errln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
 
 // Register this scenario in the catalog
 inventory::submit!(crate::scenarios::ScenarioEntry {
     short_name: "value_groups",
     run,
+    tags: &["fast", "fractional"],
 });
 
 /// Variant configuration for the value behavior scenario
@@ -134,41 +136,24 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     let mut errors = Vec::new();
     
     // Check: Variant B has higher total value than variant A
-    let msg = format!(
-        "Variant B (two $10 campaigns) has higher total value than variant A (single $20 campaign): {:.2} > {:.2}",
+    assertions::assert_greater(
+        "Variant B (two $10 campaigns) has higher total value than variant A (single $20 campaign)",
         stats_b.overall_stat.total_value,
-        stats_a.overall_stat.total_value
+        stats_a.overall_stat.total_value,
+        logger,
+        &mut errors,
     );
-    if stats_b.overall_stat.total_value > stats_a.overall_stat.total_value {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
     
     // Check: Variant C has the same total value as variant A (within tolerance)
-    let value_diff = (stats_c.overall_stat.total_value - stats_a.overall_stat.total_value).abs();
-    let value_avg = (stats_c.overall_stat.total_value + stats_a.overall_stat.total_value) / 2.0;
-    let value_diff_pct = if value_avg > 0.0 { value_diff / value_avg * 100.0 } else { 0.0 };
-    let tolerance_pct = 1.0; // 1% tolerance
-    let msg = format!(
-        "Variant C (two $10 campaigns in value group) has same total value as variant A (single $20 campaign): {:.2} ≈ {:.2} (diff: {:.2}%, tolerance: {:.0}%)",
+    assertions::assert_within_pct(
+        "Variant C (two $10 campaigns in value group) has same total value as variant A (single $20 campaign)",
         stats_c.overall_stat.total_value,
         stats_a.overall_stat.total_value,
-        value_diff_pct,
-        tolerance_pct
+        1.0,
+        logger,
+        &mut errors,
     );
-    if value_diff_pct < tolerance_pct {
-        logln!(logger, LogEvent::Scenario, "✓ {}", msg);
-    } else {
-        errors.push(msg.clone());
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-    }
     
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(format!("Scenario '{}' validation failed:\n{}", scenario_name, errors.join("\n")).into())
-    }
+    validation::scenario_result(scenario_name, errors)
 }
 
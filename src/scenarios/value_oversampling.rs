@@ -0,0 +1,204 @@
+//! This scenario demonstrates importance sampling of rare high-value impressions.
+//!
+//! Two campaigns (impressions and budget targets) buy from a single seller. The value the campaigns obtain
+//! from the top 1% of impressions by base value is a tail statistic estimated from only about a hundred
+//! impressions.
+//!
+//! - Variant A: Impressions sampled as they occur
+//!
+//! - Variant B: The top 1% of impressions oversampled ten times, with weights correcting for it
+//!
+//! With the same number of impressions, variant B samples ten times more tail impressions. Weighted by the
+//! correcting weights, statistics estimate the same quantities as in variant A, the tail statistic with a
+//! much smaller standard error.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::hooks::AuctionOutcome;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "value_oversampling",
+    run,
+    tags: &["fast"],
+});
+
+/// Share of impressions with the highest base values in the tail
+const TAIL_SHARE: f64 = 0.01;
+/// Mean and stddev of base impression values
+const VALUE_MEAN: f64 = 10.0;
+const VALUE_STDDEV: f64 = 3.0;
+
+/// Tail impressions offered, and the weighted value the campaigns obtained from them with its squares
+#[derive(Default)]
+struct TailEstimate {
+    offered: usize,
+    value: f64,
+    squared_values: f64,
+}
+
+impl TailEstimate {
+    /// Standard error of the tail value estimate relative to the estimate
+    fn relative_standard_error(&self) -> f64 {
+        self.squared_values.sqrt() / self.value
+    }
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the tail estimate of the latest iteration
+fn prepare_simulationconverge(oversampling_factor: Option<f64>) -> (SimulationConverge, Rc<RefCell<TailEstimate>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, oversampling the tail when requested
+    let mut impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(VALUE_MEAN, VALUE_STDDEV),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+    if let Some(oversampling_factor) = oversampling_factor {
+        impressions_params = impressions_params.with_value_oversampling(TAIL_SHARE, oversampling_factor);
+    }
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Base value above which impressions are in the tail
+    let (mu, sigma) = utils::lognormal_from_mean_stddev(VALUE_MEAN, VALUE_STDDEV);
+    let tail_threshold = (mu + sigma * utils::standard_normal_quantile(1.0 - TAIL_SHARE)).exp();
+
+    // Estimate the value obtained from the tail, reset at the start of every iteration
+    let tail_estimate = Rc::new(RefCell::new(TailEstimate::default()));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let tail_estimate_reset = tail_estimate.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            *tail_estimate_reset.borrow_mut() = TailEstimate::default();
+        });
+        let tail_estimate_sum = tail_estimate.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if event.impression.base_impression_value <= tail_threshold {
+                return;
+            }
+            let mut tail_estimate = tail_estimate_sum.borrow_mut();
+            tail_estimate.offered += 1;
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                // Campaigns are in their own value groups, so the group of a campaign is its ID
+                if let Winner::Campaign { campaign_id, .. } = result.winner {
+                    let weighted_value = event.impression.value_to_campaign_group[campaign_id] * event.impression.weight;
+                    tail_estimate.value += weighted_value;
+                    tail_estimate.squared_values += weighted_value * weighted_value;
+                }
+            }
+        });
+    }
+
+    (simulation_converge, tail_estimate)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with impressions sampled as they occur
+    let (simulation_converge_a, tail_estimate_a) = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_variant("Running without oversampling", scenario_name, "plain", 100, logger)?;
+
+    // Run variant B with the tail oversampled ten times
+    let (simulation_converge_b, tail_estimate_b) = prepare_simulationconverge(Some(10.0));
+    let stats_b = simulation_converge_b.run_variant("Running with the tail oversampled", scenario_name, "oversampled", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (plain)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (oversampled)", &stats_b, logger, &mut errors);
+
+    let tail_estimate_a = tail_estimate_a.borrow();
+    let tail_estimate_b = tail_estimate_b.borrow();
+    logln!(logger, LogEvent::Scenario, "Tail value obtained: {:.1} ± {:.1} (plain, {} tail impressions), {:.1} ± {:.1} (oversampled, {} tail impressions)",
+        tail_estimate_a.value, tail_estimate_a.value * tail_estimate_a.relative_standard_error(), tail_estimate_a.offered,
+        tail_estimate_b.value, tail_estimate_b.value * tail_estimate_b.relative_standard_error(), tail_estimate_b.offered);
+
+    // Check: Oversampling samples about ten times more tail impressions
+    let tail_ratio = tail_estimate_b.offered as f64 / tail_estimate_a.offered as f64;
+    assertions::assert_between(
+        &format!("Variant B (oversampled) samples about ten times more tail impressions than variant A (plain): {} vs {}, ratio", tail_estimate_b.offered, tail_estimate_a.offered),
+        tail_ratio,
+        7.0,
+        13.0,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Weights correct for the oversampling, summing to the impressions on offer
+    let seller_b = &stats_b.seller_stats[0];
+    assertions::assert_within_pct(
+        "Variant B (oversampled) weights sum to the impressions on offer",
+        seller_b.weighted_impressions_on_offer,
+        seller_b.impressions_on_offer as f64,
+        2.0,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Weighted estimates of both variants agree, the tail values within three combined standard errors
+    let cpm_a = stats_a.campaign_stats[0].total_buyer_charge / stats_a.campaign_stats[0].impressions_obtained * 1000.0;
+    let cpm_b = stats_b.campaign_stats[0].total_buyer_charge / stats_b.campaign_stats[0].impressions_obtained * 1000.0;
+    let combined_standard_error = (tail_estimate_a.squared_values + tail_estimate_b.squared_values).sqrt();
+    assertions::assert_within_pct("Variant B (oversampled) impressions campaign CPM agrees with variant A (plain)", cpm_b, cpm_a, 10.0, logger, &mut errors);
+    assertions::assert_between(
+        "Variant B (oversampled) tail value agrees with variant A (plain) within three combined standard errors",
+        tail_estimate_b.value,
+        tail_estimate_a.value - 3.0 * combined_standard_error,
+        tail_estimate_a.value + 3.0 * combined_standard_error,
+        logger,
+        &mut errors,
+    );
+
+    // Check: The tail statistic is estimated with a smaller standard error
+    assertions::assert_greater(
+        "Half the relative standard error of variant A's (plain) tail value is above variant B's (oversampled)",
+        tail_estimate_a.relative_standard_error() / 2.0,
+        tail_estimate_b.relative_standard_error(),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,102 @@
+//! This scenario converges campaigns on value efficiency: obtained value per spend.
+//!
+//! Campaign 0 bids truthfully and converges on a value per spend (value is in CPM, so 1000 is one unit
+//! of value per unit of spend), next to a budget campaign competing for the same impressions.
+//!
+//! - Variant A: Value per spend of 1250
+//!
+//! - Variant B: Value per spend of 1500
+//!
+//! Both variants should reach their value per spend. Truthful first price bidding pays its whole bid,
+//! so efficiency is bought by lower pacing: variant B obtains fewer impressions and spends less.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "value_per_spend_target",
+    run,
+    tags: &["fast"],
+});
+
+/// Value per spend targets of variants A and B
+const TARGET_VALUE_PER_SPEND_A: f64 = 1250.0;
+const TARGET_VALUE_PER_SPEND_B: f64 = 1500.0;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(target_value_per_spend: f64) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::VALUE_PER_SPEND { target_value_per_spend }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with the lower value per spend target
+    let simulation_converge_a = prepare_simulationconverge(TARGET_VALUE_PER_SPEND_A);
+    let stats_a = simulation_converge_a.run_variant("Running with value per spend target 1250", scenario_name, "value_per_spend_1250", 100, logger)?;
+
+    // Run variant B with the higher value per spend target
+    let simulation_converge_b = prepare_simulationconverge(TARGET_VALUE_PER_SPEND_B);
+    let stats_b = simulation_converge_b.run_variant("Running with value per spend target 1500", scenario_name, "value_per_spend_1500", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Both variants reach their value per spend (within 1%)
+    validation::check_value_per_spend("Variant A (1250)", &stats_a.campaign_stats[0], TARGET_VALUE_PER_SPEND_A, 0.01, logger, &mut errors);
+    validation::check_value_per_spend("Variant B (1500)", &stats_b.campaign_stats[0], TARGET_VALUE_PER_SPEND_B, 0.01, logger, &mut errors);
+
+    // Check: The higher efficiency is bought with fewer impressions and less spend
+    let (impressions_a, impressions_b) = (stats_a.campaign_stats[0].impressions_obtained, stats_b.campaign_stats[0].impressions_obtained);
+    let (spend_a, spend_b) = (stats_a.campaign_stats[0].total_buyer_charge, stats_b.campaign_stats[0].total_buyer_charge);
+    assertions::assert_greater("Variant A (1250) obtains more impressions than variant B (1500)", impressions_a, impressions_b, logger, &mut errors);
+    assertions::assert_greater("Variant A (1250) spends more than variant B (1500)", spend_a, spend_b, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
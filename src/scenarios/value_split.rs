@@ -0,0 +1,120 @@
+//! This scenario tests splitting the group value among campaigns of a value group.
+//!
+//! Two campaigns with fixed pacing share a value group, so both value every impression the same.
+//! The group can win every impression only once, so bidding the full value with both campaigns
+//! overstates what each of them contributes.
+//!
+//! - Variant A: Both campaigns bid on the full group value
+//!
+//! - Variant B: Both campaigns bid on their Shapley share of the group value (equal weights, half each)
+//!
+//! - Variant C: Weighted Shapley split (weights 3:1)
+//!
+//! Expected behavior:
+//! - With split values the group bids less, so it spends less and obtains more value per spend
+//! - The weighted split bids on the larger share (3/4), so it spends between variants A and B
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, ValueSplitRule};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "value_split",
+    run,
+    tags: &["fast"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(split_rule: ValueSplitRule) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add two campaigns with fixed pacing (ID is automatically set to match Vec index)
+    for campaign_index in 0..2 {
+        campaigns.add(
+            format!("Campaign {}", campaign_index),  // campaign_name
+            CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+            vec![ConvergeTarget::NONE { default_pacing: 0.8 }],  // converge_target
+        );
+    }
+    // Both campaigns share one value group
+    campaigns.create_value_group_with_split(vec![0, 1], split_rule);
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Value obtained per unit of spend by the whole marketplace (both campaigns of the group)
+fn value_per_spend(stats: &SimulationStat) -> f64 {
+    if stats.overall_stat.total_buyer_charge > 0.0 {
+        stats.overall_stat.total_value / stats.overall_stat.total_buyer_charge
+    } else {
+        0.0
+    }
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with full group value
+    let simulation_converge_a = prepare_simulationconverge(ValueSplitRule::FULL);
+    let stats_a = simulation_converge_a.run_variant("Running with full group value", scenario_name, "full", 100, logger)?;
+
+    // Run variant B with equal Shapley split
+    let simulation_converge_b = prepare_simulationconverge(ValueSplitRule::SHAPLEY { weights: vec![1.0, 1.0] });
+    let stats_b = simulation_converge_b.run_variant("Running with equal Shapley split", scenario_name, "shapley-equal", 100, logger)?;
+
+    // Run variant C with weighted Shapley split
+    let simulation_converge_c = prepare_simulationconverge(ValueSplitRule::SHAPLEY { weights: vec![3.0, 1.0] });
+    let stats_c = simulation_converge_c.run_variant("Running with weighted Shapley split", scenario_name, "shapley-weighted", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+    let spend_a = stats_a.overall_stat.total_buyer_charge;
+    let spend_b = stats_b.overall_stat.total_buyer_charge;
+    let spend_c = stats_c.overall_stat.total_buyer_charge;
+
+    // Check: Split values lead to less spend, the weighted split in between
+    assertions::assert_greater("Group spend is higher with the weighted split (variant C) than the equal split (variant B)", spend_c, spend_b, logger, &mut errors);
+    assertions::assert_greater("Group spend is higher with full value (variant A) than the weighted split (variant C)", spend_a, spend_c, logger, &mut errors);
+
+    // Check: Equal split obtains more value per spend than full value
+    assertions::assert_greater(
+        "Variant B (equal split) has higher value per spend than variant A (full value)",
+        value_per_spend(&stats_b),
+        value_per_spend(&stats_a),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
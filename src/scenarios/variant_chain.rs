@@ -0,0 +1,37 @@
+//! Helper for chaining variants of a scenario
+//!
+//! Scenarios often run near-identical variants, each converging from scratch. A chain warm starts every
+//! variant from the final controller states of the previous one (see SimulationConverge::with_warm_start),
+//! so only the first variant of the chain converges from the controllers' initial states.
+
+use crate::converge::{SimulationConverge, CampaignControllerStates, SellerControllerStates};
+use crate::simulationrun::SimulationStat;
+use crate::logger::Logger;
+
+/// Runs variants one after another, each warm started from the final controller states of the previous one
+#[derive(Default)]
+pub struct VariantChain {
+    /// Final controller states of the last variant run, None before the first variant
+    states: Option<(CampaignControllerStates, SellerControllerStates)>,
+}
+
+impl VariantChain {
+    /// Run a variant like SimulationConverge::run_variant, warm started from the previous variant of the chain
+    pub fn run_variant(
+        &mut self,
+        simulation_converge: SimulationConverge,
+        variant_description: &str,
+        scenario_name: &str,
+        variant_name: &str,
+        max_iterations: usize,
+        logger: &mut Logger,
+    ) -> Result<SimulationStat, Box<dyn std::error::Error>> {
+        let simulation_converge = match &self.states {
+            Some((campaign_controller_states, seller_controller_states)) => simulation_converge.with_warm_start(campaign_controller_states, seller_controller_states),
+            None => simulation_converge,
+        };
+        let (stats, campaign_controller_states, seller_controller_states) = simulation_converge.run_variant_with_states(variant_description, scenario_name, variant_name, max_iterations, logger)?;
+        self.states = Some((campaign_controller_states, seller_controller_states));
+        Ok(stats)
+    }
+}
@@ -24,12 +24,13 @@ use crate::floors;
 use crate::utils;
 use crate::logger::{Logger, LogEvent};
 use crate::logln;
-use crate::errln;
+use crate::scenarios::{validation, assertions};
 
 // Register this scenario in the catalog
 inventory::submit!(crate::scenarios::ScenarioEntry {
     short_name: "viewability",
     run,
+    tags: &["viewability"],
 });
 
 /// Prepare simulation converge instance with campaign and seller setup
@@ -81,6 +82,10 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
         converge_controllers: vec![Box::new(crate::controllers::ControllerProportionalDerivative::new())],
         bid_valuer: Box::new(BidValuerMultiplicative),
         bid_optimizer: Box::new(BidOptimizerMaximumMargin) as Box<dyn BidOptimizerTrait>,
+        max_bid_cpm: None,
+        win_rate_miscalibration: None,
+        censored_competition: false,
+        target_priorities: None,
     });
     let simulation_converge_a = prepare_simulationconverge(num_impressions, campaign_a);
     let stats_a = simulation_converge_a.run_variant(&format!("Running with max margin bidding ({} impressions)", TARGET_IMPRESSIONS), scenario_name, "max-margin-impressions", 100, logger)?;
@@ -105,6 +110,10 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
              ))],
         bid_valuer: Box::new(BidValuerDualTarget),
         bid_optimizer: Box::new(BidOptimizerMaximumMargin) as Box<dyn BidOptimizerTrait>,
+        max_bid_cpm: None,
+        win_rate_miscalibration: None,
+        censored_competition: false,
+        target_priorities: None,
     });
     let simulation_converge_b = prepare_simulationconverge(num_impressions, campaign_b);
     let stats_b = simulation_converge_b.run_variant(&format!("Running with max margin double target ({} impressions, avg value {})", TARGET_IMPRESSIONS, TARGET_AVG_VALUE), scenario_name, "max-margin-double", 1000, logger)?;
@@ -118,25 +127,8 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     let impressions_a = stats_a.campaign_stats[0].impressions_obtained;
     let impressions_b = stats_b.campaign_stats[0].impressions_obtained;
     let impressions_target = TARGET_IMPRESSIONS as f64;
-    
-    let impressions_a_diff = (impressions_a - impressions_target).abs();
-    let impressions_b_diff = (impressions_b - impressions_target).abs();
-    
-    if impressions_a_diff < 50.0 {
-        logln!(logger, LogEvent::Scenario, "✓ Variant A achieved roughly {} impressions: {:.0}", TARGET_IMPRESSIONS, impressions_a);
-    } else {
-        let msg = format!("Variant A did NOT achieve roughly {} impressions: {:.0}", TARGET_IMPRESSIONS, impressions_a);
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-        errors.push(msg);
-    }
-    
-    if impressions_b_diff < 50.0 {
-        logln!(logger, LogEvent::Scenario, "✓ Variant B achieved roughly {} impressions: {:.0}", TARGET_IMPRESSIONS, impressions_b);
-    } else {
-        let msg = format!("Variant B did NOT achieve roughly {} impressions: {:.0}", TARGET_IMPRESSIONS, impressions_b);
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-        errors.push(msg);
-    }
+    assertions::assert_between("Variant A impressions", impressions_a, impressions_target - 50.0, impressions_target + 50.0, logger, &mut errors);
+    assertions::assert_between("Variant B impressions", impressions_b, impressions_target - 50.0, impressions_target + 50.0, logger, &mut errors);
     
     // Check that variant B achieved roughly TARGET_AVG_VALUE avg value
     let avg_value_b = if impressions_b > 0.0 {
@@ -144,16 +136,7 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     } else {
         0.0
     };
-    let avg_value_target = TARGET_AVG_VALUE;
-    let avg_value_diff = (avg_value_b - avg_value_target).abs();
-    
-    if avg_value_diff < 0.05 {
-        logln!(logger, LogEvent::Scenario, "✓ Variant B achieved roughly {} avg value: {:.4}", TARGET_AVG_VALUE, avg_value_b);
-    } else {
-        let msg = format!("Variant B did NOT achieve roughly {} avg value: {:.4}", TARGET_AVG_VALUE, avg_value_b);
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-        errors.push(msg);
-    }
+    assertions::assert_between("Variant B avg value", avg_value_b, TARGET_AVG_VALUE - 0.05, TARGET_AVG_VALUE + 0.05, logger, &mut errors);
     
     // Check that variant A had lower avg value than variant B
     let avg_value_a = if impressions_a > 0.0 {
@@ -161,31 +144,13 @@ pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::
     } else {
         0.0
     };
-    
-    if avg_value_a < avg_value_b {
-        logln!(logger, LogEvent::Scenario, "✓ Variant A had lower avg value ({:.4}) than variant B ({:.4})", avg_value_a, avg_value_b);
-    } else {
-        let msg = format!("Variant A did NOT have lower avg value than variant B: A={:.4}, B={:.4}", avg_value_a, avg_value_b);
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-        errors.push(msg);
-    }
+    assertions::assert_greater("Variant B has higher avg value than variant A", avg_value_b, avg_value_a, logger, &mut errors);
     
     // Check that variant A spend was lower than variant B spend
     let spend_a = stats_a.campaign_stats[0].total_buyer_charge;
     let spend_b = stats_b.campaign_stats[0].total_buyer_charge;
+    assertions::assert_greater("Variant B has higher spend than variant A", spend_b, spend_a, logger, &mut errors);
     
-    if spend_a < spend_b {
-        logln!(logger, LogEvent::Scenario, "✓ Variant A had lower spend ({:.2}) than variant B ({:.2})", spend_a, spend_b);
-    } else {
-        let msg = format!("Variant A did NOT have lower spend than variant B: A={:.2}, B={:.2}", spend_a, spend_b);
-        errln!(logger, LogEvent::Scenario, "✗ {}", msg);
-        errors.push(msg);
-    }
-    
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(format!("Scenario '{}' validation failed:\n{}", scenario_name, errors.join("\n")).into())
-    }
+    validation::scenario_result(scenario_name, errors)
 }
 
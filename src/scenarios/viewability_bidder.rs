@@ -0,0 +1,147 @@
+//! This scenario compares a viewability-blind and a viewability-aware max margin bidder.
+//!
+//! Every impression carries a viewability probability. Campaigns only get value from
+//! impressions that are actually seen, so the value that matters is value * viewability.
+//!
+//! - Variant A: Max margin bidding on full impression value (viewability-blind)
+//!
+//! - Variant B: Max margin bidding on viewable value (value * viewability)
+//!
+//! Both campaigns spend the same budget, the viewability-aware one should obtain more
+//! viewable value per unit of spend.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::hooks::AuctionOutcome;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "viewability_bidder",
+    run,
+    tags: &["viewability"],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(campaign_type: CampaignType) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        campaign_type,  // campaign_type - viewability-blind or viewability-aware max margin
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 10.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    // Viewability is drawn from a wide beta distribution (mean ~0.6) so that it matters which impressions are bought
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_viewability(utils::beta_dist(3.0, 2.0));
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Measure how selective the bidder is: share of auctions with a bid that were won, in the converged iteration
+    let bids_made = Rc::new(Cell::new(0usize));
+    let auctions_won = Rc::new(Cell::new(0usize));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let (bids_made_reset, auctions_won_reset) = (bids_made.clone(), auctions_won.clone());
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            bids_made_reset.set(0);
+            auctions_won_reset.set(0);
+        });
+        let (bids_made_count, auctions_won_count) = (bids_made.clone(), auctions_won.clone());
+        hooks.on_auction_result(move |event, _logger| {
+            if !event.bids.is_empty() {
+                bids_made_count.set(bids_made_count.get() + 1);
+            }
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                if let Winner::Campaign { .. } = result.winner {
+                    auctions_won_count.set(auctions_won_count.get() + 1);
+                }
+            }
+        });
+        hooks.on_converged(move |_stats, logger| {
+            logln!(logger, LogEvent::Variant, "Auctions won: {} out of {} with a bid", auctions_won.get(), bids_made.get());
+        });
+    }
+
+    simulation_converge
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with viewability-blind max margin bidding
+    let simulation_converge_a = prepare_simulationconverge(CampaignType::MAX_MARGIN);
+    let stats_a = simulation_converge_a.run_variant("Running with viewability-blind max margin bidding", scenario_name, "max-margin", 100, logger)?;
+
+    // Run variant B with viewability-aware max margin bidding
+    let simulation_converge_b = prepare_simulationconverge(CampaignType::MAX_MARGIN_VIEWABILITY);
+    let stats_b = simulation_converge_b.run_variant("Running with viewability-aware max margin bidding", scenario_name, "max-margin-viewability", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Variant B obtains more viewable value per spend than variant A
+    let campaign_a = &stats_a.campaign_stats[0];
+    let campaign_b = &stats_b.campaign_stats[0];
+    let viewable_value_per_spend_a = if campaign_a.total_buyer_charge > 0.0 {
+        campaign_a.total_viewable_value / campaign_a.total_buyer_charge
+    } else {
+        0.0
+    };
+    let viewable_value_per_spend_b = if campaign_b.total_buyer_charge > 0.0 {
+        campaign_b.total_viewable_value / campaign_b.total_buyer_charge
+    } else {
+        0.0
+    };
+    assertions::assert_greater(
+        "Variant B (viewability-aware) has higher viewable value per spend than variant A (viewability-blind)",
+        viewable_value_per_spend_b,
+        viewable_value_per_spend_a,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Variant B won impressions with higher average viewability
+    let avg_viewability_a = if campaign_a.total_value > 0.0 { campaign_a.total_viewable_value / campaign_a.total_value } else { 0.0 };
+    let avg_viewability_b = if campaign_b.total_value > 0.0 { campaign_b.total_viewable_value / campaign_b.total_value } else { 0.0 };
+    assertions::assert_greater(
+        "Variant B (viewability-aware) bought more viewable impressions than variant A (viewability-blind)",
+        avg_viewability_b,
+        avg_viewability_a,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,148 @@
+//! This scenario constrains the average viewability of won impressions with a second control variable.
+//!
+//! Impressions carry a viewability probability (beta distributed, mean 0.6). A budget campaign bidding
+//! on plain value buys impressions regardless of their viewability.
+//!
+//! - Variant A: Multiplicative pacing on the budget only
+//!
+//! - Variant B: Budget with a minimum average viewability of 0.7, kept by the viewability penalty
+//!   down-weighting low viewability impressions
+//!
+//! - Variant C: Budget with a minimum average viewability of 0.5, which plain value bidding already meets
+//!
+//! Variant B should meet its threshold while still spending the budget. In variant C the constraint has
+//! slack, so the penalty rests at 1.0 and bidding is the same as in variant A.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::{SimulationConverge, CampaignControllerStates};
+use crate::controllers::ControllerStateTrait;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "viewability_constraint",
+    run,
+    tags: &["fast", "viewability"],
+});
+
+/// Budget of the campaign in all variants
+const TARGET_BUDGET: f64 = 10.0;
+/// Binding minimum average viewability of variant B
+const MIN_AVG_VIEWABILITY_BINDING: f64 = 0.7;
+/// Minimum average viewability of variant C, met without any down-weighting
+const MIN_AVG_VIEWABILITY_SLACK: f64 = 0.5;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// The campaign is constrained to a minimum average viewability when one is given
+fn prepare_simulationconverge(min_avg_viewability: Option<f64>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    match min_avg_viewability {
+        Some(min_avg_viewability) => campaigns.add(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MULTIPLICATIVE_VIEWABILITY_CONSTRAINED,  // campaign_type
+            vec![
+                ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_BUDGET },
+                ConvergeTarget::MIN_AVG_VIEWABILITY { min_avg_viewability },
+            ],  // converge_targets
+        ),
+        None => campaigns.add(
+            "Campaign 0".to_string(),  // campaign_name
+            CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+            vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: TARGET_BUDGET }],  // converge_target
+        ),
+    };
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, with viewability independent of value
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_viewability(utils::beta_dist(3.0, 2.0));
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Average viewability of the impressions the campaign won
+fn avg_viewability(stats: &SimulationStat) -> f64 {
+    stats.campaign_stats[0].total_viewability / stats.campaign_stats[0].impressions_obtained
+}
+
+/// Converged viewability penalty (second control variable) of a constrained campaign
+fn viewability_penalty(simulation_converge: &SimulationConverge, campaign_controller_states: &CampaignControllerStates) -> f64 {
+    let controller_states: Vec<&dyn ControllerStateTrait> = campaign_controller_states.campaign_controller_states[0].iter().map(|cs| cs.as_ref()).collect();
+    simulation_converge.marketplace.campaigns.campaigns[0].get_control_variables(&controller_states)[1]
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A without a viewability constraint
+    let simulation_converge_a = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_variant("Running with budget only", scenario_name, "unconstrained", 100, logger)?;
+
+    // Run variant B with a binding viewability constraint
+    let simulation_converge_b = prepare_simulationconverge(Some(MIN_AVG_VIEWABILITY_BINDING));
+    let (stats_b, campaign_controller_states_b, _seller_controller_states_b) = simulation_converge_b.run_variant_with_states("Running with minimum average viewability 0.7", scenario_name, "binding", 100, logger)?;
+
+    // Run variant C with a viewability constraint that has slack
+    let simulation_converge_c = prepare_simulationconverge(Some(MIN_AVG_VIEWABILITY_SLACK));
+    let (stats_c, campaign_controller_states_c, _seller_controller_states_c) = simulation_converge_c.run_variant_with_states("Running with minimum average viewability 0.5", scenario_name, "slack", 100, logger)?;
+
+    let penalty_b = viewability_penalty(&simulation_converge_b, &campaign_controller_states_b);
+    let penalty_c = viewability_penalty(&simulation_converge_c, &campaign_controller_states_c);
+
+    let viewability_a = avg_viewability(&stats_a);
+    let viewability_b = avg_viewability(&stats_b);
+    let viewability_c = avg_viewability(&stats_c);
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Variant A (unconstrained) falls short of the binding threshold
+    assertions::assert_greater(
+        "Variant A (unconstrained) average viewability is below the threshold of variant B, threshold",
+        MIN_AVG_VIEWABILITY_BINDING,
+        viewability_a,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Variant B (binding) meets its threshold (within controller tolerance) by down-weighting low viewability
+    let msg = format!("Variant B (binding) meets the minimum average viewability: {:.4} ≥ {:.2} (viewability penalty {:.4})", viewability_b, MIN_AVG_VIEWABILITY_BINDING, penalty_b);
+    validation::check(viewability_b >= MIN_AVG_VIEWABILITY_BINDING * 0.995 && penalty_b > 1.0, msg, logger, &mut errors);
+
+    // Check: Variant B (binding) still spends its budget
+    let spend_b = stats_b.campaign_stats[0].total_buyer_charge;
+    assertions::assert_within_pct("Variant B (binding) spends the budget", spend_b, TARGET_BUDGET, 1.0, logger, &mut errors);
+
+    // Check: Variant C (slack) leaves the penalty at rest and bids like variant A
+    let msg = format!("Variant C (slack) doesn't down-weight: penalty {:.4} == 1.0, average viewability {:.4} ≈ {:.4} (variant A)", penalty_c, viewability_c, viewability_a);
+    validation::check(penalty_c == 1.0 && (viewability_c - viewability_a).abs() <= 0.005, msg, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
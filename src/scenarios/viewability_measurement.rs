@@ -0,0 +1,138 @@
+//! This scenario demonstrates a minimum average viewability constraint kept on measured viewability.
+//!
+//! Impressions carry a viewability probability (beta distributed, mean 0.6), while bidders and the
+//! viewability target only observe the viewability measured for them. A budget campaign is constrained to a
+//! minimum average viewability of 0.75 with viewability measured:
+//!
+//! - Variant A: Exactly
+//!
+//! - Variant B: With unbiased noise (stddev 0.2)
+//!
+//! - Variant C: Overstated by 0.1
+//!
+//! All variants meet the constraint on measured viewability, but only with exact measurement does the actual
+//! viewability meet it. Noisy measurement favors impressions whose viewability is overstated by the noise,
+//! and overstated measurement shifts the actual viewability down by the bias.
+
+use crate::simulationrun::{Marketplace, SimulationType, CampaignStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::viewability::ViewabilityGeneratorDistribution;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "viewability_measurement",
+    run,
+    tags: &["fast", "viewability"],
+});
+
+/// Minimum average viewability of the campaign in all variants
+const MIN_AVG_VIEWABILITY: f64 = 0.75;
+/// Bias of overstated measurement in variant C
+const MEASUREMENT_BIAS: f64 = 0.1;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(measurement_bias: f64, measurement_noise_stddev: f64) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_VIEWABILITY_CONSTRAINED,  // campaign_type
+        vec![
+            ConvergeTarget::TOTAL_BUDGET { target_total_budget: 10.0 },
+            ConvergeTarget::MIN_AVG_VIEWABILITY { min_avg_viewability: MIN_AVG_VIEWABILITY },
+        ],  // converge_targets
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters, with viewability independent of value and measured with the given error
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_viewability_generator(
+        ViewabilityGeneratorDistribution::new(utils::beta_dist(3.0, 2.0)).with_measurement_error(measurement_bias, measurement_noise_stddev),
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Actual and measured average viewability of the impressions the campaign won
+fn avg_viewability(campaign_stat: &CampaignStat) -> (f64, f64) {
+    (campaign_stat.total_viewability / campaign_stat.impressions_obtained, campaign_stat.total_measured_viewability / campaign_stat.impressions_obtained)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with exactly measured viewability
+    let simulation_converge_a = prepare_simulationconverge(0.0, 0.0);
+    let stats_a = simulation_converge_a.run_variant("Running with exact viewability measurement", scenario_name, "exact", 100, logger)?;
+
+    // Run variant B with noisy viewability measurement
+    let simulation_converge_b = prepare_simulationconverge(0.0, 0.2);
+    let stats_b = simulation_converge_b.run_variant("Running with noisy viewability measurement", scenario_name, "noisy", 100, logger)?;
+
+    // Run variant C with overstated viewability measurement
+    let simulation_converge_c = prepare_simulationconverge(MEASUREMENT_BIAS, 0.0);
+    let stats_c = simulation_converge_c.run_variant("Running with overstated viewability measurement", scenario_name, "overstated", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (exact)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (noisy)", &stats_b, logger, &mut errors);
+    validation::assert_targets_met("Variant C (overstated)", &stats_c, logger, &mut errors);
+
+    let (actual_a, measured_a) = avg_viewability(&stats_a.campaign_stats[0]);
+    let (actual_b, measured_b) = avg_viewability(&stats_b.campaign_stats[0]);
+    let (actual_c, measured_c) = avg_viewability(&stats_c.campaign_stats[0]);
+
+    // Check: Exactly measured viewability is the actual viewability, which meets the constraint
+    validation::check(
+        actual_a == measured_a && actual_a >= MIN_AVG_VIEWABILITY * 0.995,
+        format!("Variant A (exact) actual average viewability equals the measured one and meets the constraint: {:.4} = {:.4} ≥ {:.2}", actual_a, measured_a, MIN_AVG_VIEWABILITY),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Noisy measurement overstates the viewability of the won impressions, the actual falls short
+    validation::check(
+        actual_b < measured_b && actual_b < MIN_AVG_VIEWABILITY,
+        format!("Variant B (noisy) actual average viewability is below the measured one and the constraint: {:.4} < {:.4}, {:.2}", actual_b, measured_b, MIN_AVG_VIEWABILITY),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Overstated measurement shifts the actual viewability down by about the bias
+    validation::check(
+        (measured_c - actual_c - MEASUREMENT_BIAS).abs() < 0.02 && actual_c < MIN_AVG_VIEWABILITY,
+        format!("Variant C (overstated) actual average viewability is about {} below the measured one and below the constraint: {:.4} vs. {:.4}", MEASUREMENT_BIAS, actual_c, measured_c),
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,154 @@
+//! This scenario demonstrates a seller with a volume discount on its supply cost.
+//!
+//! Two campaigns buy from a seller paid per impression sold, at a CPM set by the seller's volume tiers.
+//!
+//! - Variant A: A single tier, 10 CPM for every impression sold
+//!
+//! - Variant B: Volume discount, 10 CPM for the first 1000 impressions sold in the run, 8 CPM for the
+//!   next 1000 and 6 CPM from 2000 impressions on
+//!
+//! Supply cost doesn't change the bidding, so both variants sell the same impressions. In variant B the
+//! supply cost follows the tier curve over the volume sold, lowering the average supply CPM.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers, VolumeTier};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "volume_discount",
+    run,
+    tags: &["fast", "supply"],
+});
+
+/// Price of the first tier, the only one in variant A
+const BASE_COST_CPM: f64 = 10.0;
+
+/// Volume tiers of the seller in variant B
+fn discount_tiers() -> Vec<VolumeTier> {
+    vec![
+        VolumeTier { from_volume: 0, cost_cpm: BASE_COST_CPM },
+        VolumeTier { from_volume: 1000, cost_cpm: 8.0 },
+        VolumeTier { from_volume: 2000, cost_cpm: 6.0 },
+    ]
+}
+
+/// Supply cost of selling `volume` impressions, each at the CPM of the tier the volume sold before it is in
+fn tiered_supply_cost(tiers: &[VolumeTier], volume: usize) -> f64 {
+    tiers.iter().enumerate()
+        .map(|(index, tier)| {
+            let tier_end = tiers.get(index + 1).map_or(usize::MAX, |next_tier| next_tier.from_volume);
+            let tier_volume = volume.min(tier_end).saturating_sub(tier.from_volume);
+            tier_volume as f64 * tier.cost_cpm / 1000.0
+        })
+        .sum()
+}
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(tiers: Vec<VolumeTier>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: 1000 }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "Tiered".to_string(),  // seller_name
+        SellerType::VOLUME_TIERED { tiers },  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with a single tier
+    let simulation_converge_a = prepare_simulationconverge(vec![VolumeTier { from_volume: 0, cost_cpm: BASE_COST_CPM }]);
+    let stats_a = simulation_converge_a.run_variant("Running with a flat supply CPM", scenario_name, "flat", 100, logger)?;
+
+    // Run variant B with the volume discount
+    let simulation_converge_b = prepare_simulationconverge(discount_tiers());
+    let stats_b = simulation_converge_b.run_variant("Running with a volume discount", scenario_name, "discount", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    validation::assert_targets_met("Variant A (flat)", &stats_a, logger, &mut errors);
+    validation::assert_targets_met("Variant B (discount)", &stats_b, logger, &mut errors);
+
+    let seller_a = &stats_a.seller_stats[0];
+    let seller_b = &stats_b.seller_stats[0];
+
+    // Check: Supply cost doesn't change the bidding, so both variants sell the same impressions
+    validation::check(
+        seller_a.impressions_sold == seller_b.impressions_sold,
+        format!("Both variants sell the same impressions: {} == {}", seller_a.impressions_sold, seller_b.impressions_sold),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Flat supply cost is the base CPM on every impression sold, unsold ones cost nothing
+    let expected_a = seller_a.impressions_sold as f64 * BASE_COST_CPM / 1000.0;
+    validation::check(
+        (seller_a.total_supply_cost - expected_a).abs() < 1e-6,
+        format!("Variant A (flat) supply cost is {:.1} CPM on {} impressions sold: {:.4} == {:.4}", BASE_COST_CPM, seller_a.impressions_sold, seller_a.total_supply_cost, expected_a),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Discounted supply cost follows the tier curve over the volume sold
+    let expected_b = tiered_supply_cost(&discount_tiers(), seller_b.impressions_sold);
+    validation::check(
+        seller_b.impressions_sold > 2000 && (seller_b.total_supply_cost - expected_b).abs() < 1e-6,
+        format!("Variant B (discount) supply cost follows the tiers over {} impressions sold: {:.4} == {:.4}", seller_b.impressions_sold, seller_b.total_supply_cost, expected_b),
+        logger,
+        &mut errors,
+    );
+
+    // Check: Volume discount lowers the average supply CPM
+    let average_cpm_a = seller_a.total_supply_cost / seller_a.impressions_sold as f64 * 1000.0;
+    let average_cpm_b = seller_b.total_supply_cost / seller_b.impressions_sold as f64 * 1000.0;
+    assertions::assert_greater(
+        "Variant A (flat) has a higher average supply CPM than variant B (discount)",
+        average_cpm_a,
+        average_cpm_b,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
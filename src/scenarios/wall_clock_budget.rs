@@ -0,0 +1,150 @@
+//! This scenario demonstrates wall-clock limits of variants and scenarios.
+//!
+//! A campaign targets a total budget. Paced by its default controller it converges within a few iterations,
+//! paced by a PD controller with zero tolerance it keeps adjusting pacing to ever smaller errors and never
+//! converges, so without a limit it would run on to maximum iterations.
+//!
+//! - Variant A: Converging campaign with a generous variant timeout
+//!
+//! - Variant B: Non-converging campaign with a variant timeout of 0.2s
+//!
+//! - Variant C: Non-converging campaign within a scenario timeout of 0.2s, followed by a converging one
+//!
+//! Variant A converges within its limit. Variant B is aborted with a variant timeout error long before maximum
+//! iterations. In variant C the scenario runs out of time, which aborts the running variant and keeps the
+//! next one from starting.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, CampaignGeneral};
+use crate::controllers::{ControllerProportionalDerivative, ControllerTrait};
+use crate::converge::SimulationConverge;
+use crate::timeouts::{TimeoutError, TimeoutScope, start_scenario_clock, restore_scenario_clock};
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use std::time::Duration;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "wall_clock_budget",
+    run,
+    tags: &[],
+});
+
+/// Maximum iterations of every variant, far more than the short limits allow
+const MAX_ITERATIONS: usize = 100000;
+/// Wall-clock limit of variant B and of the scenario in variant C
+const SHORT_LIMIT: Duration = Duration::from_millis(200);
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// The campaign is paced with zero tolerance (never converging) if `converging` is false
+fn prepare_simulationconverge(converging: bool) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+    if !converging {
+        let campaign_general = campaigns.campaigns[campaign_id].as_any_mut().downcast_mut::<CampaignGeneral>().unwrap();
+        let converge_controller: Box<dyn ControllerTrait> = Box::new(ControllerProportionalDerivative::new_advanced(0.0, 0.2, 0.1, 0.05, true));
+        campaign_general.converge_controllers[0] = converge_controller;
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Check that a variant was aborted with a timeout error of the given scope, and return the error
+fn check_timeout(label: &str, result: Result<SimulationStat, Box<dyn std::error::Error>>, scope: TimeoutScope, logger: &mut Logger, errors: &mut Vec<String>) -> Option<TimeoutError> {
+    let timeout_error = result.err().and_then(|error| error.downcast::<TimeoutError>().ok()).map(|timeout_error| *timeout_error);
+    validation::check(
+        timeout_error.as_ref().is_some_and(|timeout_error| timeout_error.scope == scope),
+        format!("{}: Aborted with a {:?} timeout: {}", label, scope, timeout_error.as_ref().map_or("no timeout".to_string(), |timeout_error| timeout_error.to_string())),
+        logger,
+        errors,
+    );
+    timeout_error
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A, a converging campaign well within its limit
+    let simulation_converge_a = prepare_simulationconverge(true).with_timeout(Duration::from_secs(60));
+    let stats_a = simulation_converge_a.run_variant("Running converging campaign with a 60s timeout", scenario_name, "converging", MAX_ITERATIONS, logger)?;
+
+    // Run variant B, a non-converging campaign with a short variant timeout
+    let simulation_converge_b = prepare_simulationconverge(false).with_timeout(SHORT_LIMIT);
+    let result_b = simulation_converge_b.run_variant("Running non-converging campaign with a 0.2s timeout", scenario_name, "variant_timeout", MAX_ITERATIONS, logger);
+
+    // Run variant C on a short scenario clock, restoring the scenario's own clock afterwards
+    let scenario_clock = start_scenario_clock(scenario_name, Some(SHORT_LIMIT));
+    let result_c = prepare_simulationconverge(false).run_variant("Running non-converging campaign with a 0.2s scenario timeout", scenario_name, "scenario_timeout", MAX_ITERATIONS, logger);
+    let result_c_next = prepare_simulationconverge(true).run_variant("Running converging campaign after the scenario timed out", scenario_name, "scenario_timeout_next", MAX_ITERATIONS, logger);
+    restore_scenario_clock(scenario_clock);
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Variant A converges within its limit
+    validation::assert_targets_met("Variant A", &stats_a, logger, &mut errors);
+
+    // Check: Variant B is aborted by its limit, after running at least as long as the limit but long before
+    // maximum iterations
+    if let Some(timeout_error) = check_timeout("Variant B", result_b, TimeoutScope::VARIANT, logger, &mut errors) {
+        validation::check(
+            timeout_error.elapsed >= SHORT_LIMIT && timeout_error.iterations > 0 && timeout_error.iterations < MAX_ITERATIONS,
+            format!("Variant B: Ran {:.2}s ≥ {:.2}s and stopped at iteration {} of {}", timeout_error.elapsed.as_secs_f64(), SHORT_LIMIT.as_secs_f64(), timeout_error.iterations, MAX_ITERATIONS),
+            logger,
+            &mut errors,
+        );
+    }
+
+    // Check: In variant C the scenario timeout aborts the running variant, and the next one doesn't start
+    if let Some(timeout_error) = check_timeout("Variant C", result_c, TimeoutScope::SCENARIO, logger, &mut errors) {
+        validation::check(
+            timeout_error.scenario_name == scenario_name && timeout_error.iterations > 0 && timeout_error.iterations < MAX_ITERATIONS,
+            format!("Variant C: Scenario '{}' stopped at iteration {} of {}", timeout_error.scenario_name, timeout_error.iterations, MAX_ITERATIONS),
+            logger,
+            &mut errors,
+        );
+    }
+    if let Some(timeout_error) = check_timeout("Variant C (next)", result_c_next, TimeoutScope::SCENARIO, logger, &mut errors) {
+        validation::check(
+            timeout_error.iterations == 0,
+            format!("Variant C (next): Didn't start after the scenario timed out (iterations {})", timeout_error.iterations),
+            logger,
+            &mut errors,
+        );
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
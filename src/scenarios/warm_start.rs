@@ -0,0 +1,135 @@
+//! This scenario demonstrates warm starts from saved controller states.
+//!
+//! - Variant A: Two max margin campaigns (budget and impressions targets) converged from scratch.
+//!   The converged controller states are saved to a file.
+//!
+//! - Variant B: Follow-up with a 10% higher budget, starting from the states loaded from the file
+//!
+//! - Variant C: The same follow-up converged from scratch
+//!
+//! Starting close to the solution, variant B should converge in fewer iterations than variant C
+//! and reach the same targets.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns};
+use crate::converge::{SimulationConverge, CampaignControllerStates, SellerControllerStates};
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent, sanitize_filename};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "warm_start",
+    run,
+    tags: &["slow", "controllers"],
+});
+
+const TARGET_TOTAL_BUDGET: f64 = 40.0;
+const TARGET_TOTAL_IMPRESSIONS: i32 = 1000;
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the number of iterations run
+fn prepare_simulationconverge(target_total_budget: f64) -> (SimulationConverge, Rc<Cell<usize>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (ID is automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget }],  // converge_target
+    );
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::TOTAL_IMPRESSIONS { target_total_impressions: TARGET_TOTAL_IMPRESSIONS }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Count iterations until convergence
+    let iterations = Rc::new(Cell::new(0usize));
+    {
+        let iterations_count = iterations.clone();
+        simulation_converge.hooks.borrow_mut().on_iteration_start(move |iteration, _logger| {
+            iterations_count.set(iteration);
+        });
+    }
+
+    (simulation_converge, iterations)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A from scratch and save its converged controller states
+    let (simulation_converge_a, _iterations_a) = prepare_simulationconverge(TARGET_TOTAL_BUDGET);
+    let (_stats_a, campaign_states_a, seller_states_a) = simulation_converge_a.run_variant_with_states("Running initial variant from scratch", scenario_name, "initial", 100, logger)?;
+    let states_path = logger.log_path(&format!("{}/controller_states.txt", sanitize_filename(scenario_name)));
+    std::fs::write(&states_path, format!("{}\n#\n{}", campaign_states_a.save(), seller_states_a.save()))?;
+
+    // Run variant B warm started from the saved controller states
+    let follow_up_budget = TARGET_TOTAL_BUDGET * 1.1;
+    let (simulation_converge_b, iterations_b) = prepare_simulationconverge(follow_up_budget);
+    let saved = std::fs::read_to_string(&states_path)?;
+    let (saved_campaign_states, saved_seller_states) = saved.split_once("\n#\n").ok_or("Saved controller states are missing the seller section")?;
+    let campaign_states_b = CampaignControllerStates::load(&simulation_converge_b.marketplace.campaigns, saved_campaign_states)?;
+    let seller_states_b = SellerControllerStates::load(&simulation_converge_b.marketplace.sellers, saved_seller_states)?;
+    let simulation_converge_b = simulation_converge_b.with_initial_states(campaign_states_b, seller_states_b);
+    let stats_b = simulation_converge_b.run_variant("Running follow-up variant warm started from saved states", scenario_name, "warm-start", 100, logger)?;
+
+    // Run variant C from scratch
+    let (simulation_converge_c, iterations_c) = prepare_simulationconverge(follow_up_budget);
+    let stats_c = simulation_converge_c.run_variant("Running follow-up variant from scratch", scenario_name, "cold-start", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Warm start converges in fewer iterations
+    assertions::assert_greater(
+        "Variant C (cold start) needs more iterations to converge than variant B (warm start)",
+        iterations_c.get() as f64,
+        iterations_b.get() as f64,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Warm and cold start reach the same targets
+    for (variant, stats) in [("B (warm start)", &stats_b), ("C (cold start)", &stats_c)] {
+        let spend = stats.campaign_stats[0].total_buyer_charge;
+        let impressions = stats.campaign_stats[1].impressions_obtained;
+        let target_impressions = TARGET_TOTAL_IMPRESSIONS as f64;
+        assertions::assert_within_pct(&format!("Variant {} reaches the budget", variant), spend, follow_up_budget, 1.0, logger, &mut errors);
+        assertions::assert_within_pct(&format!("Variant {} reaches the impressions target", variant), impressions, target_impressions, 1.0, logger, &mut errors);
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
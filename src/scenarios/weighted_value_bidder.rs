@@ -0,0 +1,132 @@
+//! This scenario compares max margin bidding on plain value with bidding on a weighted sum of value components.
+//!
+//! Every impression carries a viewability probability and a quality score. The campaign cares about a
+//! mix of value components: plain value, viewable value (value * viewability) and quality value
+//! (value * quality score), weighted 0.2, 0.3 and 0.5.
+//!
+//! - Variant A: Max margin bidding on plain value
+//!
+//! - Variant B: Max margin bidding on the weighted sum of value components
+//!
+//! Both campaigns spend the same budget, the weighted value bidder should obtain more weighted value
+//! per unit of spend.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, NUM_VALUE_COMPONENTS};
+use crate::converge::SimulationConverge;
+use crate::impressions::{ImpressionsParam, Winner};
+use crate::hooks::AuctionOutcome;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+use std::cell::Cell;
+use std::rc::Rc;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "weighted_value_bidder",
+    run,
+    tags: &["viewability"],
+});
+
+/// Weights of value components (value, viewable value, quality value) the campaign cares about
+const VALUE_WEIGHTS: [f64; NUM_VALUE_COMPONENTS] = [0.2, 0.3, 0.5];
+
+/// Prepare simulation converge instance with campaign and seller setup
+/// Returns the simulation converge and the weighted value obtained in the last iteration
+fn prepare_simulationconverge(campaign_type: CampaignType) -> (SimulationConverge, Rc<Cell<f64>>) {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        campaign_type,  // campaign_type - plain value or weighted value max margin
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 10.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    // Viewability and quality score are drawn from wide beta distributions so that it matters which impressions are bought
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    ).with_viewability(utils::beta_dist(3.0, 2.0))
+     .with_quality_score(utils::beta_dist(2.0, 2.0));
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    let simulation_converge = SimulationConverge::new(marketplace);
+
+    // Sum the weighted value of won impressions in each iteration
+    let weighted_value = Rc::new(Cell::new(0.0));
+    {
+        let mut hooks = simulation_converge.hooks.borrow_mut();
+        let weighted_value_reset = weighted_value.clone();
+        hooks.on_iteration_start(move |_iteration, _logger| {
+            weighted_value_reset.set(0.0);
+        });
+        let weighted_value_sum = weighted_value.clone();
+        hooks.on_auction_result(move |event, _logger| {
+            if let AuctionOutcome::Standard(result) = event.outcome {
+                if let Winner::Campaign { .. } = result.winner {
+                    let components = event.impression.value_components(event.impression.value_to_campaign_group[0]);
+                    let value: f64 = VALUE_WEIGHTS.iter().zip(components).map(|(weight, component)| weight * component).sum();
+                    weighted_value_sum.set(weighted_value_sum.get() + value);
+                }
+            }
+        });
+    }
+
+    (simulation_converge, weighted_value)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with max margin bidding on plain value
+    let (simulation_converge_a, weighted_value_a) = prepare_simulationconverge(CampaignType::MAX_MARGIN);
+    let stats_a = simulation_converge_a.run_variant("Running with max margin bidding on plain value", scenario_name, "max-margin", 100, logger)?;
+
+    // Run variant B with max margin bidding on weighted value
+    let (simulation_converge_b, weighted_value_b) = prepare_simulationconverge(CampaignType::MAX_MARGIN_WEIGHTED_VALUE { weights: VALUE_WEIGHTS });
+    let stats_b = simulation_converge_b.run_variant("Running with max margin bidding on weighted value", scenario_name, "max-margin-weighted", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+
+    // Check: Variant B obtains more weighted value per spend than variant A
+    let spend_a = stats_a.campaign_stats[0].total_buyer_charge;
+    let spend_b = stats_b.campaign_stats[0].total_buyer_charge;
+    let weighted_value_per_spend_a = if spend_a > 0.0 { weighted_value_a.get() / spend_a } else { 0.0 };
+    let weighted_value_per_spend_b = if spend_b > 0.0 { weighted_value_b.get() / spend_b } else { 0.0 };
+    assertions::assert_greater(
+        "Variant B (weighted value) has higher weighted value per spend than variant A (plain value)",
+        weighted_value_per_spend_b,
+        weighted_value_per_spend_a,
+        logger,
+        &mut errors,
+    );
+
+    // Check: Both variants spend the same budget
+    assertions::assert_within_pct("Variant A (plain value) spends its budget", spend_a, 10.0, 5.0, logger, &mut errors);
+    assertions::assert_within_pct("Variant B (weighted value) spends its budget", spend_b, 10.0, 5.0, logger, &mut errors);
+
+    validation::scenario_result(scenario_name, errors)
+}
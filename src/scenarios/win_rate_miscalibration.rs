@@ -0,0 +1,114 @@
+//! This scenario studies how robust the max margin bidder is to errors of the win rate model.
+//!
+//! All variants use the same max margin bidder and spend the same budget, only the win rate
+//! prediction seen by the bidder differs:
+//!
+//! - Variant A: Calibrated win rate prediction
+//!
+//! - Variant B: Biased win rate prediction (competition predicted to be 30% cheaper)
+//!
+//! - Variant C: Noisy win rate prediction (unbiased, with per-impression noise on offset and scale)
+//!
+//! The calibrated bidder should obtain the most value per unit of spend.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, WinRateMiscalibration};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "win_rate_miscalibration",
+    run,
+    tags: &[],
+});
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(win_rate_miscalibration: Option<WinRateMiscalibration>) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaign (ID is automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        CampaignType::MAX_MARGIN,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+    if let Some(win_rate_miscalibration) = win_rate_miscalibration {
+        campaigns.set_win_rate_miscalibration(campaign_id, win_rate_miscalibration);
+    }
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+/// Value obtained per unit of spend by the single campaign
+fn value_per_spend(stats: &SimulationStat) -> f64 {
+    let campaign = &stats.campaign_stats[0];
+    if campaign.total_buyer_charge > 0.0 {
+        campaign.total_value / campaign.total_buyer_charge
+    } else {
+        0.0
+    }
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    // Run variant A with calibrated win rate prediction
+    let simulation_converge_a = prepare_simulationconverge(None);
+    let stats_a = simulation_converge_a.run_variant("Running with calibrated win rate prediction", scenario_name, "calibrated", 100, logger)?;
+
+    // Run variant B with win rate prediction biased towards more expensive competition
+    let simulation_converge_b = prepare_simulationconverge(Some(WinRateMiscalibration::new(0.7, 1.0, 0.0, 0.0)));
+    let stats_b = simulation_converge_b.run_variant("Running with biased win rate prediction", scenario_name, "biased", 100, logger)?;
+
+    // Run variant C with noisy win rate prediction
+    let simulation_converge_c = prepare_simulationconverge(Some(WinRateMiscalibration::new(1.0, 1.0, 0.3, 0.3)));
+    let stats_c = simulation_converge_c.run_variant("Running with noisy win rate prediction", scenario_name, "noisy", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    let mut errors: Vec<String> = Vec::new();
+    let value_per_spend_a = value_per_spend(&stats_a);
+
+    // Check: Calibrated variant obtains more value per spend than each miscalibrated variant
+    for (variant_name, stats) in [("B (biased)", &stats_b), ("C (noisy)", &stats_c)] {
+        let value_per_spend_other = value_per_spend(stats);
+        assertions::assert_greater(
+            &format!("Variant A (calibrated) has higher value per spend than variant {}", variant_name),
+            value_per_spend_a,
+            value_per_spend_other,
+            logger,
+            &mut errors,
+        );
+    }
+
+    validation::scenario_result(scenario_name, errors)
+}
@@ -0,0 +1,118 @@
+//! This scenario converges campaigns on a win rate: the share of the auctions they bid in that they win.
+//!
+//! Campaign 0 converges on a win rate of 0.15, next to a budget campaign competing for the same impressions.
+//!
+//! - Variant A: Campaign 0 bids truthfully (multiplicative pacing)
+//!
+//! - Variant B: Campaign 0 bids max margin
+//!
+//! Both variants should reach the win rate. The truthful campaign bids in every auction, while max margin
+//! bidding skips auctions where its paced value is below the floor. The win rate only counts the
+//! auctions a campaign bid in, so the max margin campaign wins fewer impressions at the same win rate.
+
+use crate::simulationrun::{Marketplace, SimulationType};
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers};
+use crate::campaigns::{CampaignType, ConvergeTarget, Campaigns, TargetTolerance};
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::competition::CompetitionGeneratorLogNormal;
+use crate::floors;
+use crate::utils;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+use crate::scenarios::validation;
+use crate::scenarios::assertions;
+
+// Register this scenario in the catalog
+inventory::submit!(crate::scenarios::ScenarioEntry {
+    short_name: "win_rate_target",
+    run,
+    tags: &["fast"],
+});
+
+/// Win rate target of campaign 0
+const TARGET_WIN_RATE: f64 = 0.15;
+/// Absolute band around the win rate within which the target counts as met
+const WIN_RATE_TOLERANCE: f64 = 0.002;
+
+/// Prepare simulation converge instance with campaign and seller setup
+fn prepare_simulationconverge(campaign_type: CampaignType) -> SimulationConverge {
+    // Initialize containers for campaigns and sellers
+    let mut campaigns = Campaigns::new();
+    let mut sellers = Sellers::new();
+
+    // Add campaigns (IDs are automatically set to match Vec index)
+    let campaign_id = campaigns.add(
+        "Campaign 0".to_string(),  // campaign_name
+        campaign_type,  // campaign_type
+        vec![ConvergeTarget::WIN_RATE { target_win_rate: TARGET_WIN_RATE }],  // converge_target
+    );
+    campaigns.set_target_tolerances(campaign_id, vec![TargetTolerance::ABSOLUTE(WIN_RATE_TOLERANCE)]);
+    campaigns.add(
+        "Campaign 1".to_string(),  // campaign_name
+        CampaignType::MULTIPLICATIVE_PACING,  // campaign_type
+        vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }],  // converge_target
+    );
+
+    // Add seller (ID is automatically set to match Vec index)
+    sellers.add(
+        "HB".to_string(),  // seller_name
+        SellerType::FIRST_PRICE,  // seller_type
+        SellerConvergeStrategy::NONE { default_value: 1.0 },  // seller_converge
+        10000,  // impressions_on_offer
+        CompetitionGeneratorLogNormal::new(10.0),  // competition_generator
+        floors::FloorGeneratorLogNormal::new(0.2, 3.0),  // floor_generator
+    );
+
+    // Create impressions parameters
+    let impressions_params = ImpressionsParam::new(
+        utils::lognormal_dist(10.0, 3.0),  // base_impression_value_dist
+        utils::lognormal_dist(1.0, 0.2),   // value_to_campaign_multiplier_dist
+    );
+
+    // Create marketplace containing campaigns, sellers, and impressions
+    let marketplace = Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard);
+
+    // Create simulation converge instance (initializes campaign and seller converges internally)
+    SimulationConverge::new(marketplace)
+}
+
+pub fn run(scenario_name: &str, logger: &mut Logger) -> Result<(), Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    // Run variant A with truthful bidding
+    let simulation_converge_a = prepare_simulationconverge(CampaignType::MULTIPLICATIVE_PACING);
+    let stats_a = simulation_converge_a.run_variant("Running with win rate target and truthful bidding", scenario_name, "truthful", 100, logger)?;
+
+    // Run variant B with max margin bidding
+    let simulation_converge_b = prepare_simulationconverge(CampaignType::MAX_MARGIN);
+    let stats_b = simulation_converge_b.run_variant("Running with win rate target and max margin bidding", scenario_name, "max_margin", 100, logger)?;
+
+    logln!(logger, LogEvent::Scenario, "");
+
+    // Check: Both variants reach the win rate (within ±0.002) and the budget
+    for (variant, stats) in [("Variant A (truthful)", &stats_a), ("Variant B (max margin)", &stats_b)] {
+        logln!(logger, LogEvent::Scenario, "{}: Campaign won {:.0} impressions of {} auctions participated", variant, stats.campaign_stats[0].impressions_obtained, stats.campaign_stats[0].auctions_participated);
+        validation::assert_targets_met(variant, stats, logger, &mut errors);
+    }
+
+    // Check: Max margin bidding skips auctions, so the same win rate wins fewer impressions
+    let auctions_participated_a = stats_a.campaign_stats[0].auctions_participated;
+    let auctions_participated_b = stats_b.campaign_stats[0].auctions_participated;
+    assertions::assert_greater(
+        "Variant A (truthful) bids in more auctions than variant B (max margin)",
+        auctions_participated_a as f64,
+        auctions_participated_b as f64,
+        logger,
+        &mut errors,
+    );
+    assertions::assert_greater(
+        "Variant A (truthful) wins more impressions than variant B (max margin)",
+        stats_a.campaign_stats[0].impressions_obtained,
+        stats_b.campaign_stats[0].impressions_obtained,
+        logger,
+        &mut errors,
+    );
+
+    validation::scenario_result(scenario_name, errors)
+}
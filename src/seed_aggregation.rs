@@ -0,0 +1,260 @@
+//! Aggregation of a variant's results across seeds
+//!
+//! A single run of a variant is one draw of the impressions, so differences between variants can be noise.
+//! run_variant_seeds runs a variant with a number of seeds, each on a marketplace drawn with its seed, and
+//! aggregates the converged statistics into the mean, standard deviation and a bootstrap confidence interval
+//! of the mean of every metric (MetricSummary), which scenarios can validate instead of a single run's values.
+
+use crate::simulationrun::SimulationStat;
+use crate::campaign_targets::TargetTolerance;
+use crate::campaigns::Campaigns;
+use crate::sellers::Sellers;
+use crate::converge::SimulationConverge;
+use crate::utils::{get_seed, RAND_SEED};
+use crate::logger::{Logger, LogEvent, FileReceiver, sanitize_filename};
+use crate::logln;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Confidence level of the confidence intervals
+pub const CONFIDENCE_LEVEL: f64 = 0.95;
+/// Number of bootstrap resamples the confidence intervals are estimated from
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+
+/// Mean, standard deviation and confidence interval of the mean of a metric across seeds
+#[derive(Debug, Clone)]
+pub struct MetricSummary {
+    pub mean: f64,
+    /// Sample standard deviation (0.0 with a single seed)
+    pub std: f64,
+    /// Bounds of the percentile bootstrap confidence interval of the mean (CONFIDENCE_LEVEL)
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+impl MetricSummary {
+    /// Summarize the values of a metric across seeds
+    /// The bootstrap draws from an RNG seeded with RAND_SEED, so summaries are reproducible
+    pub fn new(samples: Vec<f64>) -> Self {
+        assert!(!samples.is_empty(), "A metric summary needs at least one sample");
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let std = if samples.len() > 1 {
+            (samples.iter().map(|sample| (sample - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+
+        let mut rng = StdRng::seed_from_u64(get_seed(23232));
+        let mut resample_means: Vec<f64> = (0..BOOTSTRAP_RESAMPLES)
+            .map(|_| (0..samples.len()).map(|_| samples[rng.gen_range(0..samples.len())]).sum::<f64>() / n)
+            .collect();
+        resample_means.sort_by(|a, b| a.total_cmp(b));
+        let quantile = |q: f64| resample_means[((BOOTSTRAP_RESAMPLES - 1) as f64 * q).round() as usize];
+
+        Self {
+            mean,
+            std,
+            ci_low: quantile((1.0 - CONFIDENCE_LEVEL) / 2.0),
+            ci_high: quantile((1.0 + CONFIDENCE_LEVEL) / 2.0),
+        }
+    }
+
+    /// Whether the confidence interval lies entirely above the other's, i.e. the metric is clearly larger
+    pub fn ci_above(&self, other: &MetricSummary) -> bool {
+        self.ci_low > other.ci_high
+    }
+
+    /// Get a string representation, e.g. "20.01 ± 0.12 (95% CI [19.95, 20.07])"
+    pub fn summary_string(&self) -> String {
+        format!("{:.4} ± {:.4} ({:.0}% CI [{:.4}, {:.4}])", self.mean, self.std, CONFIDENCE_LEVEL * 100.0, self.ci_low, self.ci_high)
+    }
+}
+
+/// A campaign's statistics aggregated across seeds
+pub struct AggregatedCampaignStat {
+    pub impressions_obtained: MetricSummary,
+    pub total_buyer_charge: MetricSummary,
+    pub total_value: MetricSummary,
+}
+
+/// A seller's statistics aggregated across seeds
+pub struct AggregatedSellerStat {
+    pub impressions_sold: MetricSummary,
+    pub total_buyer_charge: MetricSummary,
+}
+
+/// A campaign convergence target aggregated across seeds
+pub struct AggregatedTargetStat {
+    /// Description of the target (see CampaignTrait::get_targets_actual_and_target)
+    pub name: String,
+    pub actual: MetricSummary,
+    /// Target value, the mean across seeds for targets depending on the draws (e.g., capped by eligible impressions)
+    pub target: f64,
+    pub tolerance: TargetTolerance,
+}
+
+impl AggregatedTargetStat {
+    /// Whether the mean actual value is within the tolerance band around the target
+    pub fn is_met(&self) -> bool {
+        self.tolerance.is_met(self.actual.mean, self.target)
+    }
+}
+
+/// Statistics of a variant aggregated across seeds (see run_variant_seeds)
+pub struct AggregatedStat {
+    /// Seeds the variant ran with
+    pub seeds: Vec<u64>,
+    /// Statistics of each seed's converged run, in order of seeds
+    pub seed_stats: Vec<SimulationStat>,
+    pub convergence_iterations: MetricSummary,
+    /// Aggregated statistics of each campaign (indexed by campaign_id)
+    pub campaign_stats: Vec<AggregatedCampaignStat>,
+    /// Aggregated statistics of each seller (indexed by seller_id)
+    pub seller_stats: Vec<AggregatedSellerStat>,
+    /// Aggregated convergence targets, per campaign
+    pub campaign_targets: Vec<Vec<AggregatedTargetStat>>,
+}
+
+impl AggregatedStat {
+    /// Aggregate the statistics of a variant's runs with the given seeds
+    pub fn new(seeds: Vec<u64>, seed_stats: Vec<SimulationStat>) -> Self {
+        assert!(!seed_stats.is_empty(), "Aggregated statistics need at least one seed");
+        let summary = |metric: &dyn Fn(&SimulationStat) -> f64| MetricSummary::new(seed_stats.iter().map(metric).collect());
+
+        let campaign_stats = (0..seed_stats[0].campaign_stats.len())
+            .map(|index| AggregatedCampaignStat {
+                impressions_obtained: summary(&|stats| stats.campaign_stats[index].impressions_obtained),
+                total_buyer_charge: summary(&|stats| stats.campaign_stats[index].total_buyer_charge),
+                total_value: summary(&|stats| stats.campaign_stats[index].total_value),
+            })
+            .collect();
+        let seller_stats = (0..seed_stats[0].seller_stats.len())
+            .map(|index| AggregatedSellerStat {
+                impressions_sold: summary(&|stats| stats.seller_stats[index].impressions_sold as f64),
+                total_buyer_charge: summary(&|stats| stats.seller_stats[index].total_buyer_charge),
+            })
+            .collect();
+        let campaign_targets = seed_stats[0].campaign_targets.iter().enumerate()
+            .map(|(index, targets)| targets.iter().enumerate()
+                .map(|(target_index, target)| AggregatedTargetStat {
+                    name: target.name.clone(),
+                    actual: summary(&|stats| stats.campaign_targets[index][target_index].actual),
+                    target: summary(&|stats| stats.campaign_targets[index][target_index].target).mean,
+                    tolerance: target.tolerance,
+                })
+                .collect())
+            .collect();
+
+        Self {
+            convergence_iterations: summary(&|stats| stats.convergence_iterations as f64),
+            campaign_stats,
+            seller_stats,
+            campaign_targets,
+            seeds,
+            seed_stats,
+        }
+    }
+
+    /// Summarize any metric of the seeds' statistics, e.g. a campaign's CPM
+    pub fn metric<F: Fn(&SimulationStat) -> f64>(&self, metric: F) -> MetricSummary {
+        MetricSummary::new(self.seed_stats.iter().map(metric).collect())
+    }
+
+    /// Output the aggregated campaign, seller and target statistics
+    pub fn printout(&self, campaigns: &Campaigns, sellers: &Sellers, logger: &mut Logger) {
+        logln!(logger, LogEvent::Variant, "\n=== Statistics over {} Seeds ===", self.seeds.len());
+        logln!(logger, LogEvent::Variant, "Convergence Iterations: {}", self.convergence_iterations.summary_string());
+        for (index, campaign_stat) in self.campaign_stats.iter().enumerate() {
+            logln!(logger, LogEvent::Variant, "\nCampaign {} ({})", index, campaigns.campaigns[index].campaign_name());
+            logln!(logger, LogEvent::Variant, "  Impressions Obtained: {}", campaign_stat.impressions_obtained.summary_string());
+            logln!(logger, LogEvent::Variant, "  Buyer Charge: {}", campaign_stat.total_buyer_charge.summary_string());
+            logln!(logger, LogEvent::Variant, "  Obtained Value: {}", campaign_stat.total_value.summary_string());
+            for target in &self.campaign_targets[index] {
+                logln!(logger, LogEvent::Variant, "  Target [{}]: {} vs. {:.4} ({})", target.name, target.actual.summary_string(), target.target, target.tolerance.tolerance_string());
+            }
+        }
+        for (index, seller_stat) in self.seller_stats.iter().enumerate() {
+            logln!(logger, LogEvent::Variant, "\nSeller {} ({})", index, sellers.sellers[index].seller_name());
+            logln!(logger, LogEvent::Variant, "  Impressions Sold: {}", seller_stat.impressions_sold.summary_string());
+            logln!(logger, LogEvent::Variant, "  Buyer Charge: {}", seller_stat.total_buyer_charge.summary_string());
+        }
+    }
+}
+
+/// Run a variant with `seeds` seeds and aggregate its statistics
+///
+/// The marketplace is drawn from RAND_SEED, so `prepare` is called once per seed with RAND_SEED set to it, and
+/// RAND_SEED is restored afterwards. Seeds are RAND_SEED * seeds + 0..seeds, so iterations of a scenario
+/// (each with its own RAND_SEED) aggregate over distinct seeds. Each seed's run logs as variant
+/// `<variant_name>-seed<seed>`, the aggregated statistics go to the variant log of `variant_name`.
+///
+/// # Returns
+/// Returns a Result with the AggregatedStat, or an error if the variant failed to converge with any seed
+pub fn run_variant_seeds<F: Fn() -> SimulationConverge>(
+    prepare: F,
+    seeds: usize,
+    variant_description: &str,
+    scenario_name: &str,
+    variant_name: &str,
+    max_iterations: usize,
+    logger: &mut Logger,
+) -> Result<AggregatedStat, Box<dyn std::error::Error>> {
+    assert!(seeds > 0, "A variant needs at least one seed");
+    let rand_seed = RAND_SEED.get();
+    let seed_list: Vec<u64> = (0..seeds as u64).map(|index| rand_seed.wrapping_mul(seeds as u64).wrapping_add(index)).collect();
+
+    let mut seed_stats = Vec::with_capacity(seeds);
+    let mut simulation_converge = None;
+    for &seed in &seed_list {
+        RAND_SEED.set(seed);
+        let seed_simulation_converge = prepare();
+        let result = seed_simulation_converge.run_variant(&format!("{} (seed {})", variant_description, seed), scenario_name, &format!("{}-seed{}", variant_name, seed), max_iterations, logger);
+        RAND_SEED.set(rand_seed);
+        seed_stats.push(result?);
+        simulation_converge = Some(seed_simulation_converge);
+    }
+    let stats = AggregatedStat::new(seed_list, seed_stats);
+
+    let simulation_converge = simulation_converge.unwrap();
+    let variant_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/variant-{}.log", sanitize_filename(scenario_name), sanitize_filename(variant_name))), vec![LogEvent::Variant]));
+    logln!(logger, LogEvent::Variant, "\n=== {} ===", variant_description);
+    stats.printout(&simulation_converge.marketplace.campaigns, &simulation_converge.marketplace.sellers, logger);
+    logger.remove_receiver(variant_receiver_id);
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulationrun::{Marketplace, SimulationType};
+    use crate::campaigns::{CampaignType, ConvergeTarget};
+    use crate::sellers::{SellerType, SellerBuilder};
+    use crate::impressions::ImpressionsParam;
+    use crate::utils;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_run_variant_seeds_derives_and_restores_seeds() {
+        RAND_SEED.set(3);
+        let prepared_seeds = RefCell::new(Vec::new());
+        let prepare = || {
+            prepared_seeds.borrow_mut().push(RAND_SEED.get());
+            let mut campaigns = Campaigns::new();
+            campaigns.add("Campaign 0".to_string(), CampaignType::MULTIPLICATIVE_PACING, vec![ConvergeTarget::TOTAL_BUDGET { target_total_budget: 20.0 }]);
+            let mut sellers = Sellers::new();
+            SellerBuilder::new("HB", SellerType::FIRST_PRICE, 10000).add_to(&mut sellers);
+            let impressions_params = ImpressionsParam::new(utils::lognormal_dist(10.0, 3.0), utils::lognormal_dist(1.0, 0.2));
+            SimulationConverge::new(Marketplace::new(campaigns, sellers, &impressions_params, SimulationType::Standard))
+        };
+        let mut logger = Logger::new().with_log_dir(std::env::temp_dir().join("marrakesh-test-seed-aggregation"));
+
+        // Seeds are RAND_SEED * seeds + 0..seeds, each marketplace prepared with its seed
+        let stats = run_variant_seeds(prepare, 4, "Test variant", "test", "test", 100, &mut logger).unwrap();
+        assert_eq!(stats.seeds, vec![12, 13, 14, 15]);
+        assert_eq!(*prepared_seeds.borrow(), vec![12, 13, 14, 15]);
+        assert_eq!(stats.seed_stats.len(), 4);
+        // RAND_SEED is restored afterwards
+        assert_eq!(RAND_SEED.get(), 3);
+    }
+}
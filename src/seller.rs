@@ -6,6 +6,20 @@ use crate::seller_chargers::SellerCharger;
 use rand::rngs::StdRng;
 use std::any::Any;
 
+/// What the control variable of a seller's first controller acts on
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SellerControl {
+    /// Boost factor multiplying campaign bids on the seller
+    BOOST,
+    /// Scale of the seller's floors (see FloorGeneratorControlled)
+    FLOOR_SCALE,
+    /// Revenue share of the seller's charger, with floors grossed up so that they hold for the seller's share
+    REVENUE_SHARE,
+    /// Share of a campaign's bids the seller passes through to its auctions, the rest being throttled
+    THROTTLE { campaign_id: usize },
+}
+
 /// Trait for sellers participating in auctions
 pub trait SellerTrait: Any {
     /// Get the seller ID
@@ -17,18 +31,41 @@ pub trait SellerTrait: Any {
     /// Get the number of impressions on offer
     fn get_impressions_on_offer(&self) -> usize;
     
-    /// Get the supply cost in CPM for a given buyer winning bid CPM
-    fn get_supply_cost_cpm(&self, buyer_win_cpm: f64) -> f64;
+    /// Get the supply cost in CPM of a sold impression for a given buyer winning bid CPM
+    /// `control_variable` is the seller's control variable, used when it sets the revenue share
+    /// `volume` is the number of impressions the seller sold earlier in the run, used by volume tiered chargers
+    fn get_supply_cost_cpm(&self, buyer_win_cpm: f64, control_variable: f64, volume: usize) -> f64;
+    
+    /// Get the supply cost in CPM of an impression that wasn't sold (lost, without demand or withdrawn)
+    fn get_unsold_supply_cost_cpm(&self) -> f64;
+    
+    /// Get the soft floor CPM of the seller (None = no soft floor)
+    /// Winning bids below the soft floor are charged second price, bids above it first price
+    fn soft_floor_cpm(&self) -> Option<f64>;
+    
+    /// Whether the seller runs second price auctions: the winner is charged the highest of the next
+    /// campaign bid, competing bid and floor, while its bid is still recorded as virtual cost
+    fn is_second_price(&self) -> bool;
+    
+    /// What the seller's control variable acts on
+    fn seller_control(&self) -> SellerControl;
+    
+    /// Whether bidders see floors other than the generated ones (see buyer_floor_cpm)
+    fn sets_buyer_floors(&self) -> bool;
+    
+    /// Floor CPM bidders have to beat, for a generated floor and the seller's control variable
+    fn buyer_floor_cpm(&self, floor_cpm: f64, control_variable: f64) -> f64;
     
     /// Generate impression parameters (Option<ImpressionCompetition>, floor_cpm) using the provided distributions
     /// 
     /// # Arguments
     /// * `base_value` - Base value parameter for floor generation
+    /// * `time_of_day` - Time of day in hours the impression is offered at, for time-varying competition
     /// * `rng` - Random number generator
     /// 
     /// # Returns
     /// Tuple of (Option<ImpressionCompetition>, floor_cpm)
-    fn generate_impression(&self, base_value: f64, rng_competition: &mut StdRng, rng_floor: &mut StdRng) -> (Option<ImpressionCompetition>, f64);
+    fn generate_impression(&self, base_value: f64, time_of_day: f64, rng_competition: &mut StdRng, rng_floor: &mut StdRng) -> (Option<ImpressionCompetition>, f64);
     
     /// Get a string representation of the seller type and convergence for logging
     fn type_target_and_controller_state_string(&self, controller_states: &[&dyn crate::controllers::ControllerStateTrait]) -> String;
@@ -57,6 +94,15 @@ pub trait SellerTrait: Any {
     /// The control variable value (boost factor)
     fn get_control_variable(&self, controller_state: &dyn crate::controllers::ControllerStateTrait) -> f64;
     
+    /// Factor on the competing bids of the seller's impressions set by a reactive competitor (1.0 = none)
+    fn competitor_bid_factor(&self, controller_states: &[Box<dyn crate::controllers::ControllerStateTrait>]) -> f64;
+    
+    /// Get (target description, actual, target) of each convergence target from the statistics of an iteration
+    fn get_targets_actual_and_target(&self, seller_stat: &crate::simulationrun::SellerStat) -> Vec<(String, f64, f64)>;
+    
+    /// Get (target index, bound) of each convergence target whose control variable is held at a hard bound
+    fn binding_bounds(&self, controller_states: &[&dyn crate::controllers::ControllerStateTrait]) -> Vec<(usize, f64)>;
+    
     /// Get reference to Any for downcasting
     fn as_any(&self) -> &dyn Any;
     
@@ -75,6 +121,8 @@ pub struct SellerGeneral {
     pub competition_generator: Box<dyn CompetitionGeneratorTrait>,
     pub floor_generator: Box<dyn FloorGeneratorTrait>,
     pub seller_charger: Box<dyn SellerCharger>,
+    /// Optional soft floor CPM, below which winning bids are charged second price
+    pub soft_floor_cpm: Option<f64>,
 }
 
 impl SellerTrait for SellerGeneral {
@@ -82,12 +130,51 @@ impl SellerTrait for SellerGeneral {
     fn seller_name(&self) -> &str { &self.seller_name }
     fn get_impressions_on_offer(&self) -> usize { self.impressions_on_offer }
     
-    fn get_supply_cost_cpm(&self, buyer_win_cpm: f64) -> f64 {
-        self.seller_charger.get_supply_cost_cpm(buyer_win_cpm)
+    fn get_supply_cost_cpm(&self, buyer_win_cpm: f64, control_variable: f64, volume: usize) -> f64 {
+        match self.seller_control() {
+            SellerControl::REVENUE_SHARE => self.seller_charger.get_supply_cost_cpm_at_revenue_share(buyer_win_cpm, control_variable),
+            _ => self.seller_charger.get_supply_cost_cpm_at_volume(buyer_win_cpm, volume),
+        }
+    }
+    
+    fn get_unsold_supply_cost_cpm(&self) -> f64 {
+        self.seller_charger.get_unsold_supply_cost_cpm()
+    }
+    
+    fn soft_floor_cpm(&self) -> Option<f64> {
+        self.soft_floor_cpm
     }
     
-    fn generate_impression(&self, base_value: f64, rng_competition: &mut StdRng, rng_floor: &mut StdRng) -> (Option<ImpressionCompetition>, f64) {
-        let competition = self.competition_generator.generate_competition(base_value, rng_competition);
+    fn is_second_price(&self) -> bool {
+        self.seller_charger.is_second_price()
+    }
+    
+    fn seller_control(&self) -> SellerControl {
+        if self.converge_targets.first().is_some_and(|converge_target| converge_target.controls_revenue_share()) {
+            SellerControl::REVENUE_SHARE
+        } else if let Some(campaign_id) = self.converge_targets.first().and_then(|converge_target| converge_target.throttled_campaign()) {
+            SellerControl::THROTTLE { campaign_id }
+        } else if self.floor_generator.is_controlled() {
+            SellerControl::FLOOR_SCALE
+        } else {
+            SellerControl::BOOST
+        }
+    }
+    
+    fn sets_buyer_floors(&self) -> bool {
+        matches!(self.seller_control(), SellerControl::FLOOR_SCALE | SellerControl::REVENUE_SHARE) || self.seller_charger.revenue_share().is_some()
+    }
+    
+    fn buyer_floor_cpm(&self, floor_cpm: f64, control_variable: f64) -> f64 {
+        match self.seller_control() {
+            SellerControl::BOOST | SellerControl::THROTTLE { .. } => self.seller_charger.gross_floor_cpm(floor_cpm, None),
+            SellerControl::FLOOR_SCALE => self.seller_charger.gross_floor_cpm(floor_cpm * control_variable, None),
+            SellerControl::REVENUE_SHARE => self.seller_charger.gross_floor_cpm(floor_cpm, Some(control_variable)),
+        }
+    }
+    
+    fn generate_impression(&self, base_value: f64, time_of_day: f64, rng_competition: &mut StdRng, rng_floor: &mut StdRng) -> (Option<ImpressionCompetition>, f64) {
+        let competition = self.competition_generator.generate_competition_at(base_value, time_of_day, rng_competition);
         let floor_cpm = self.floor_generator.generate_floor(base_value, rng_floor);
         (competition, floor_cpm)
     }
@@ -101,7 +188,11 @@ impl SellerTrait for SellerGeneral {
                 converge_controller.controller_string(controller_states[index])
             ));
         }
-        format!("{} ({})", self.seller_charger.get_charging_type(), parts.join(", "))
+        let soft_floor_string = match self.soft_floor_cpm {
+            Some(soft_floor_cpm) => format!(", soft floor {:.2} CPM", soft_floor_cpm),
+            None => String::new(),
+        };
+        format!("{}{} ({})", self.seller_charger.get_charging_type(), soft_floor_string, parts.join(", "))
     }
     
     fn create_controller_state(&self) -> Vec<Box<dyn crate::controllers::ControllerStateTrait>> {
@@ -124,6 +215,29 @@ impl SellerTrait for SellerGeneral {
         self.converge_controllers[0].get_control_variable(controller_state)
     }
     
+    fn competitor_bid_factor(&self, controller_states: &[Box<dyn crate::controllers::ControllerStateTrait>]) -> f64 {
+        self.converge_targets.iter()
+            .position(|converge_target| converge_target.scales_competition())
+            .map_or(1.0, |index| self.converge_controllers[index].get_control_variable(controller_states[index].as_ref()))
+    }
+    
+    fn get_targets_actual_and_target(&self, seller_stat: &crate::simulationrun::SellerStat) -> Vec<(String, f64, f64)> {
+        self.converge_targets.iter()
+            .map(|converge_target| {
+                let (actual, target) = converge_target.get_actual_and_target(seller_stat);
+                (converge_target.converge_target_string(), actual, target)
+            })
+            .collect()
+    }
+    
+    fn binding_bounds(&self, controller_states: &[&dyn crate::controllers::ControllerStateTrait]) -> Vec<(usize, f64)> {
+        self.converge_controllers.iter()
+            .zip(controller_states.iter())
+            .enumerate()
+            .filter_map(|(index, (converge_controller, controller_state))| converge_controller.binding_bound(*controller_state).map(|bound| (index, bound)))
+            .collect()
+    }
+    
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -0,0 +1,180 @@
+use crate::competition::{CompetitionGeneratorTrait, CompetitionGeneratorNone, CompetitionGeneratorLogNormal, CompetitionGeneratorCorrelated, CompetitionGeneratorMax, CompetitionGeneratorSum, CompetitionGeneratorWeighted};
+use crate::floors::{FloorGeneratorTrait, FloorGeneratorFixed, FloorGeneratorLogNormal, FloorGeneratorControlled};
+use crate::controllers::ControllerConfig;
+use crate::sellers::{SellerType, SellerConvergeStrategy, Sellers, BoostPeriod};
+
+/// Competition generator of a configured seller
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum CompetitionConfig {
+    /// No competing bids (CompetitionGeneratorNone)
+    NONE,
+    /// Competing bids around the impression's base value (CompetitionGeneratorLogNormal)
+    #[default]
+    LOG_NORMAL,
+    /// Competing bids correlated with the impression's base value (CompetitionGeneratorCorrelated)
+    CORRELATED { value_mean: f64, value_stddev: f64, bid_mean: f64, bid_stddev: f64, correlation: f64 },
+    /// Highest competing bid of independent bidder populations (CompetitionGeneratorMax)
+    MAX { competitions: Vec<CompetitionConfig> },
+    /// Sum of the competing bids of independent components (CompetitionGeneratorSum)
+    SUM { competitions: Vec<CompetitionConfig> },
+    /// Competing bid of one population per impression, chosen by weight (CompetitionGeneratorWeighted)
+    WEIGHTED { competitions: Vec<(f64, CompetitionConfig)> },
+}
+
+impl CompetitionConfig {
+    /// Create the competition generator
+    pub fn build(&self) -> Box<dyn CompetitionGeneratorTrait> {
+        match self {
+            CompetitionConfig::NONE => CompetitionGeneratorNone::new(),
+            CompetitionConfig::LOG_NORMAL => CompetitionGeneratorLogNormal::new(10.0),
+            CompetitionConfig::CORRELATED { value_mean, value_stddev, bid_mean, bid_stddev, correlation } =>
+                CompetitionGeneratorCorrelated::new(*value_mean, *value_stddev, *bid_mean, *bid_stddev, *correlation),
+            CompetitionConfig::MAX { competitions } =>
+                CompetitionGeneratorMax::new(competitions.iter().map(CompetitionConfig::build).collect()),
+            CompetitionConfig::SUM { competitions } =>
+                CompetitionGeneratorSum::new(competitions.iter().map(CompetitionConfig::build).collect()),
+            CompetitionConfig::WEIGHTED { competitions } =>
+                CompetitionGeneratorWeighted::new(competitions.iter().map(|(weight, competition)| (*weight, competition.build())).collect()),
+        }
+    }
+}
+
+/// Floor generator of a configured seller
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum FloorConfig {
+    /// Same floor on every impression (FloorGeneratorFixed)
+    FIXED { value: f64 },
+    /// Floors around a share of the impression's base value (FloorGeneratorLogNormal)
+    LOG_NORMAL { relative_to_impression_value: f64, stddev: f64 },
+    /// Floors like LOG_NORMAL, scaled by the seller's control variable (FloorGeneratorControlled)
+    CONTROLLED { relative_to_impression_value: f64, stddev: f64 },
+}
+
+impl Default for FloorConfig {
+    fn default() -> Self {
+        FloorConfig::LOG_NORMAL { relative_to_impression_value: 0.2, stddev: 3.0 }
+    }
+}
+
+impl FloorConfig {
+    /// Create the floor generator
+    pub fn build(&self) -> Box<dyn FloorGeneratorTrait> {
+        match *self {
+            FloorConfig::FIXED { value } => FloorGeneratorFixed::new(value),
+            FloorConfig::LOG_NORMAL { relative_to_impression_value, stddev } => FloorGeneratorLogNormal::new(relative_to_impression_value, stddev),
+            FloorConfig::CONTROLLED { relative_to_impression_value, stddev } => FloorGeneratorControlled::new(relative_to_impression_value, stddev),
+        }
+    }
+}
+
+/// Declarative description of a seller, added with Sellers::add_from_config
+///
+/// Covers the arguments of Sellers::add_configured and the per-seller settings that don't refer to other
+/// sellers or campaigns (adapter fee, bid caching, boost schedule). Supply paths and throttling are set on
+/// Sellers once the sellers and campaigns they refer to exist.
+/// With the `serde` feature the config can be deserialized, optional fields falling back to their defaults.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct SellerConfig {
+    pub seller_name: String,
+    pub seller_type: SellerType,
+    pub seller_converge: SellerConvergeStrategy,
+    pub impressions_on_offer: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub competition: CompetitionConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub floors: FloorConfig,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub controller_config: ControllerConfig,
+    /// See Sellers::set_adapter_fee
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub adapter_fee: Option<f64>,
+    /// See Sellers::set_bid_caching
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stale_bid_probability: Option<f64>,
+    /// See Sellers::set_boost_schedule
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub boost_schedule: Option<Vec<BoostPeriod>>,
+}
+
+/// Builder of a SellerConfig, starting from the most common setup: no convergence (boost 1.0), lognormal
+/// competition and lognormal floors at 0.2 of the impression's base value
+pub struct SellerBuilder {
+    config: SellerConfig,
+}
+
+impl SellerBuilder {
+    /// Create a builder for a seller of the given type offering impressions_on_offer impressions
+    pub fn new(seller_name: &str, seller_type: SellerType, impressions_on_offer: usize) -> Self {
+        Self {
+            config: SellerConfig {
+                seller_name: seller_name.to_string(),
+                seller_type,
+                seller_converge: SellerConvergeStrategy::NONE { default_value: 1.0 },
+                impressions_on_offer,
+                competition: CompetitionConfig::default(),
+                floors: FloorConfig::default(),
+                controller_config: ControllerConfig::default(),
+                adapter_fee: None,
+                stale_bid_probability: None,
+                boost_schedule: None,
+            },
+        }
+    }
+
+    /// Set the convergence strategy
+    pub fn with_converge(mut self, seller_converge: SellerConvergeStrategy) -> Self {
+        self.config.seller_converge = seller_converge;
+        self
+    }
+
+    /// Set the competition generator
+    pub fn with_competition(mut self, competition: CompetitionConfig) -> Self {
+        self.config.competition = competition;
+        self
+    }
+
+    /// Set the floor generator
+    pub fn with_floors(mut self, floors: FloorConfig) -> Self {
+        self.config.floors = floors;
+        self
+    }
+
+    /// Set the parameters of the convergence strategy's controller
+    pub fn with_controller_config(mut self, controller_config: ControllerConfig) -> Self {
+        self.config.controller_config = controller_config;
+        self
+    }
+
+    /// Set the adapter fee (see Sellers::set_adapter_fee)
+    pub fn with_adapter_fee(mut self, adapter_fee: f64) -> Self {
+        self.config.adapter_fee = Some(adapter_fee);
+        self
+    }
+
+    /// Cache bids with the given probability (see Sellers::set_bid_caching)
+    pub fn with_bid_caching(mut self, stale_bid_probability: f64) -> Self {
+        self.config.stale_bid_probability = Some(stale_bid_probability);
+        self
+    }
+
+    /// Set the boost schedule over the day (see Sellers::set_boost_schedule)
+    pub fn with_boost_schedule(mut self, boost_schedule: Vec<BoostPeriod>) -> Self {
+        self.config.boost_schedule = Some(boost_schedule);
+        self
+    }
+
+    /// Get the built configuration
+    pub fn build(self) -> SellerConfig {
+        self.config
+    }
+
+    /// Add the seller to the collection, returning its ID
+    pub fn add_to(self, sellers: &mut Sellers) -> usize {
+        sellers.add_from_config(self.config)
+    }
+}
@@ -2,9 +2,9 @@
 // vast majority of strategies require just one parameter (buyer_win_cpm or fixed_cost_cpm), so if one needs more complex state
 // one can implement a full SellerTrait.
 //
-// Note: If we want to have second price auctions at some point, we would rename this to SellerAuction 
-// and move the Impression::run_auction code under seller, so that the auction mechanism (first price, second price, etc.)
-// would be determined by the seller's auction type rather than being hardcoded in the impression.
+// Note: Second price is flagged by the charger (is_second_price) and applied in Impression::run_auction. If auction
+// mechanisms grow beyond that, we would rename this to SellerAuction and move the Impression::run_auction code
+// under seller, so that the auction mechanism would be determined by the seller rather than the impression.
 
 /// Trait for seller charging strategies
 pub trait SellerCharger {
@@ -13,8 +13,43 @@ pub trait SellerCharger {
     /// For first price sellers, returns the buyer_win_cpm
     fn get_supply_cost_cpm(&self, buyer_win_cpm: f64) -> f64;
     
+    /// Get the supply cost in CPM of an impression sold after the seller sold `volume` impressions earlier in the run
+    /// Chargers without volume tiers don't depend on the volume
+    fn get_supply_cost_cpm_at_volume(&self, buyer_win_cpm: f64, _volume: usize) -> f64 {
+        self.get_supply_cost_cpm(buyer_win_cpm)
+    }
+    
+    /// Get the supply cost in CPM of an impression that wasn't sold
+    /// (0.0 for first price, fixed_cost_cpm for fixed price, the fixed fee for hybrid)
+    fn get_unsold_supply_cost_cpm(&self) -> f64 {
+        self.get_supply_cost_cpm(0.0)
+    }
+    
     /// Get a string representation of the charging type
     fn get_charging_type(&self) -> String;
+    
+    /// Whether the winner is charged the second price (highest of the next campaign bid, competing bid and floor)
+    /// instead of its bid
+    fn is_second_price(&self) -> bool {
+        false
+    }
+    
+    /// Share of the clearing price going to the seller (None for chargers without a revenue share)
+    fn revenue_share(&self) -> Option<f64> {
+        None
+    }
+    
+    /// Get the supply cost in CPM with the given revenue share instead of the charger's own
+    /// Used when the seller's controller tunes the revenue share (see SellerControl::REVENUE_SHARE)
+    fn get_supply_cost_cpm_at_revenue_share(&self, buyer_win_cpm: f64, _revenue_share: f64) -> f64 {
+        self.get_supply_cost_cpm(buyer_win_cpm)
+    }
+    
+    /// Lowest clearing price CPM that pays the seller at least `floor_cpm`, at the given revenue share
+    /// (None = the charger's own), so that floors hold for what the seller is paid
+    fn gross_floor_cpm(&self, floor_cpm: f64, _revenue_share: Option<f64>) -> f64 {
+        floor_cpm
+    }
 }
 
 /// Charger for first price auction
@@ -30,6 +65,23 @@ impl SellerCharger for SellerChargerFirstPrice {
     }
 }
 
+/// Charger for second price auction, the seller gets the clearing price the winner is charged
+pub struct SellerChargerSecondPrice;
+
+impl SellerCharger for SellerChargerSecondPrice {
+    fn get_supply_cost_cpm(&self, buyer_win_cpm: f64) -> f64 {
+        buyer_win_cpm
+    }
+    
+    fn get_charging_type(&self) -> String {
+        "Second price".to_string()
+    }
+    
+    fn is_second_price(&self) -> bool {
+        true
+    }
+}
+
 /// Charger for fixed price (cost per mille)
 pub struct SellerChargerFixedPrice {
     pub fixed_cost_cpm: f64,
@@ -45,3 +97,74 @@ impl SellerCharger for SellerChargerFixedPrice {
     }
 }
 
+
+/// Charger for a hybrid contract: a fixed fee per impression plus a revenue share of the clearing price
+/// (cost = fixed_fee_cpm + revenue_share × buyer_win_cpm)
+/// Like with fixed price, the fixed fee is paid on every impression on offer, sold or not
+/// Floors hold for what the seller is paid, so bidders see them grossed up by the revenue share
+pub struct SellerChargerHybrid {
+    pub fixed_fee_cpm: f64,
+    /// Share of the clearing price going to the seller (e.g., 0.8 = 80%)
+    pub revenue_share: f64,
+}
+
+impl SellerCharger for SellerChargerHybrid {
+    fn get_supply_cost_cpm(&self, buyer_win_cpm: f64) -> f64 {
+        self.get_supply_cost_cpm_at_revenue_share(buyer_win_cpm, self.revenue_share)
+    }
+    
+    fn get_charging_type(&self) -> String {
+        format!("Hybrid fee CPM: {:.2} + revenue share: {:.0}%", self.fixed_fee_cpm, self.revenue_share * 100.0)
+    }
+    
+    fn revenue_share(&self) -> Option<f64> {
+        Some(self.revenue_share)
+    }
+    
+    fn get_supply_cost_cpm_at_revenue_share(&self, buyer_win_cpm: f64, revenue_share: f64) -> f64 {
+        self.fixed_fee_cpm + revenue_share * buyer_win_cpm
+    }
+    
+    fn gross_floor_cpm(&self, floor_cpm: f64, revenue_share: Option<f64>) -> f64 {
+        // The fixed fee is paid anyway, so the revenue share has to cover the rest of the floor
+        ((floor_cpm - self.fixed_fee_cpm) / revenue_share.unwrap_or(self.revenue_share)).max(0.0)
+    }
+}
+
+/// Price tier of a volume tiered seller: impressions sold once the seller sold `from_volume` impressions
+/// in the run cost `cost_cpm` (until the next tier)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct VolumeTier {
+    pub from_volume: usize,
+    pub cost_cpm: f64,
+}
+
+/// Charger for a volume discount: the seller is paid per impression sold, at the CPM of the tier the
+/// seller's volume sold so far in the run is in, so impressions get cheaper past each volume threshold
+/// Unlike with fixed price, unsold impressions cost nothing
+/// Tiers are ordered by volume, the first one starting at volume 0
+pub struct SellerChargerVolumeTiered {
+    pub tiers: Vec<VolumeTier>,
+}
+
+impl SellerCharger for SellerChargerVolumeTiered {
+    fn get_supply_cost_cpm(&self, buyer_win_cpm: f64) -> f64 {
+        self.get_supply_cost_cpm_at_volume(buyer_win_cpm, 0)
+    }
+    
+    fn get_supply_cost_cpm_at_volume(&self, _buyer_win_cpm: f64, volume: usize) -> f64 {
+        self.tiers.iter().rev()
+            .find(|tier| volume >= tier.from_volume)
+            .map_or(0.0, |tier| tier.cost_cpm)
+    }
+    
+    fn get_unsold_supply_cost_cpm(&self) -> f64 {
+        0.0
+    }
+    
+    fn get_charging_type(&self) -> String {
+        let tiers: Vec<String> = self.tiers.iter().map(|tier| format!("{:.2} from {}", tier.cost_cpm, tier.from_volume)).collect();
+        format!("Volume tiered CPM: {}", tiers.join(" / "))
+    }
+}
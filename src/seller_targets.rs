@@ -19,6 +19,22 @@ pub trait SellerTargetTrait {
     
     /// Get a string representation of the convergence target
     fn converge_target_string(&self) -> String;
+    
+    /// Whether the target is converged by tuning the revenue share of the seller's charger
+    fn controls_revenue_share(&self) -> bool {
+        false
+    }
+    
+    /// Campaign whose bids the target is converged by throttling (None = the target doesn't throttle)
+    fn throttled_campaign(&self) -> Option<usize> {
+        None
+    }
+    
+    /// Whether the target is converged by scaling the competing bids on the seller's impressions (a reactive
+    /// competitor, see Sellers::set_reactive_competitor)
+    fn scales_competition(&self) -> bool {
+        false
+    }
 }
 
 /// Convergence strategy for sellers that don't converge (no boost adjustment)
@@ -60,3 +76,128 @@ impl SellerTargetTrait for SellerTargetTotalCost {
     }
 }
 
+/// Convergence strategy for sellers that converge floors to sell a target share of their impressions
+/// Raising floors lowers sell-through, so the unsold share is converged: the controller raises the floor
+/// scale while fewer impressions than 1 - target_sell_through remain unsold
+/// target_sell_through is below 1.0, as controllers normalize the error by the target unsold share
+pub struct SellerTargetSellThrough {
+    pub target_sell_through: f64,
+    pub impressions_on_offer: usize,
+}
+
+impl SellerTargetSellThrough {
+    /// Share of the impressions on offer that were sold
+    pub fn sell_through(&self, seller_stat: &crate::simulationrun::SellerStat) -> f64 {
+        seller_stat.impressions_sold as f64 / self.impressions_on_offer as f64
+    }
+}
+
+impl SellerTargetTrait for SellerTargetSellThrough {
+    fn get_actual_and_target(&self, seller_stat: &crate::simulationrun::SellerStat) -> (f64, f64) {
+        let actual = 1.0 - self.sell_through(seller_stat);
+        let target = 1.0 - self.target_sell_through;
+        (actual, target)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        self.target_sell_through
+    }
+    
+    fn converge_target_string(&self) -> String {
+        format!("Converge target sell-through: {:.1}% (unsold share)", self.target_sell_through * 100.0)
+    }
+}
+
+/// Convergence strategy for sellers that tune the revenue share of their charger to maximize their profit
+/// (buyer charge - supply cost). There is no target value, the profit is maximized by a line search
+/// (ControllerLineSearch) rather than converged to a target
+pub struct SellerTargetMaxProfit;
+
+impl SellerTargetMaxProfit {
+    /// Profit of the seller: what buyers are charged minus what the seller is paid
+    pub fn profit(seller_stat: &crate::simulationrun::SellerStat) -> f64 {
+        seller_stat.total_buyer_charge - seller_stat.total_supply_cost
+    }
+}
+
+impl SellerTargetTrait for SellerTargetMaxProfit {
+    fn get_actual_and_target(&self, seller_stat: &crate::simulationrun::SellerStat) -> (f64, f64) {
+        (Self::profit(seller_stat), 0.0)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        0.0
+    }
+    
+    fn converge_target_string(&self) -> String {
+        "Maximize profit".to_string()
+    }
+    
+    fn controls_revenue_share(&self) -> bool {
+        true
+    }
+}
+
+/// Convergence strategy for sellers that throttle a campaign to receive a target number of its bids.
+/// The control variable is the share of the campaign's bids passed through to the auctions, raised while
+/// fewer bids than the target get through
+pub struct SellerTargetThrottle {
+    pub campaign_id: usize,
+    pub target_bids: usize,
+}
+
+impl SellerTargetTrait for SellerTargetThrottle {
+    fn get_actual_and_target(&self, seller_stat: &crate::simulationrun::SellerStat) -> (f64, f64) {
+        let actual = seller_stat.campaign_bids[self.campaign_id] as f64;
+        let target = self.target_bids as f64;
+        (actual, target)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        self.target_bids as f64
+    }
+    
+    fn converge_target_string(&self) -> String {
+        format!("Converge target bids of campaign {}: {} (throttling)", self.campaign_id, self.target_bids)
+    }
+    
+    fn throttled_campaign(&self) -> Option<usize> {
+        Some(self.campaign_id)
+    }
+}
+
+/// Convergence strategy of a reactive competitor on the seller, scaling its competing bids to win a target
+/// share of the seller's auctions. The control variable is the factor on the competing bids, raised while
+/// the competitor wins less than the target
+pub struct SellerTargetCompetitorWinRate {
+    pub target_win_rate: f64,
+}
+
+impl SellerTargetCompetitorWinRate {
+    /// Share of the seller's auctions won by the competitor
+    pub fn win_rate(seller_stat: &crate::simulationrun::SellerStat) -> f64 {
+        let auctions = seller_stat.impressions_on_offer - seller_stat.impressions_withdrawn;
+        if auctions == 0 {
+            return 0.0;
+        }
+        seller_stat.competitor_wins as f64 / auctions as f64
+    }
+}
+
+impl SellerTargetTrait for SellerTargetCompetitorWinRate {
+    fn get_actual_and_target(&self, seller_stat: &crate::simulationrun::SellerStat) -> (f64, f64) {
+        (Self::win_rate(seller_stat), self.target_win_rate)
+    }
+    
+    fn get_target_value(&self) -> f64 {
+        self.target_win_rate
+    }
+    
+    fn converge_target_string(&self) -> String {
+        format!("Converge competitor win rate: {:.1}% (scaling competing bids)", self.target_win_rate * 100.0)
+    }
+    
+    fn scales_competition(&self) -> bool {
+        true
+    }
+}
@@ -1,41 +1,103 @@
 use crate::competition::CompetitionGeneratorTrait;
 use crate::floors::FloorGeneratorTrait;
-use crate::controllers::ControllerTrait;
+use crate::controllers::{ControllerTrait, ControllerConfig};
+use crate::impressions::HOURS_PER_DAY;
 pub use crate::seller::SellerTrait;
 pub use crate::seller::SellerGeneral;
+pub use crate::seller::SellerControl;
 pub use crate::seller_targets::SellerTargetTrait;
 
 /// Seller type for different pricing models
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub enum SellerType {
     FIRST_PRICE,
+    /// First price above the soft floor, second price below it
+    FIRST_PRICE_SOFT_FLOOR { soft_floor_cpm: f64 },
+    /// Winner is charged the highest of the next campaign bid, competing bid and floor
+    SECOND_PRICE,
     FIXED_PRICE { fixed_cost_cpm: f64 },
+    /// First price auction with a hybrid contract: a fixed fee per impression on offer plus a revenue share
+    /// of the clearing price (supply cost = fixed_fee_cpm + revenue_share × clearing price)
+    HYBRID { fixed_fee_cpm: f64, revenue_share: f64 },
+    /// First price auction with a volume discount: each sold impression costs the CPM of the tier the seller's
+    /// volume sold so far in the run is in (tiers ordered by volume, the first one from volume 0)
+    VOLUME_TIERED { tiers: Vec<VolumeTier> },
 }
 
+/// Period of a seller's boost schedule: impressions offered from `from_hour` of the day (until the next
+/// period) have campaign bids on the seller multiplied by `boost_factor`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct BoostPeriod {
+    pub from_hour: f64,
+    pub boost_factor: f64,
+}
+
+/// Lowest floor scale of sellers converging their floors, unless their controller configuration sets one
+const MIN_FLOOR_SCALE: f64 = 0.01;
+/// Lowest revenue share and first step of the line search of sellers maximizing profit, unless their
+/// controller configuration sets a lower bound
+const MIN_REVENUE_SHARE: f64 = 0.01;
+const REVENUE_SHARE_STEP: f64 = 0.1;
+/// Lowest share of a throttled campaign's bids passed through by sellers converging their throttling, unless
+/// their controller configuration sets one
+const MIN_PASS_THROUGH: f64 = 0.01;
+/// Lowest factor on the competing bids of reactive competitors, unless their controller configuration sets one
+const MIN_COMPETITOR_BID_FACTOR: f64 = 0.01;
+
 /// Convergence strategy for sellers
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub enum SellerConvergeStrategy {
     NONE { default_value: f64 },
     TOTAL_COST { target_total_cost: f64 },
+    /// Share of impressions on offer to sell, converged by scaling floors (needs FloorGeneratorControlled)
+    /// Must be in [0, 1): the controller converges the unsold share 1 - target_sell_through, which can't be 0
+    SELL_THROUGH { target_sell_through: f64 },
+    /// Tune the revenue share of the charger to maximize profit (buyer charge - supply cost), starting from
+    /// the seller type's revenue share (needs HYBRID). Floors hold for the seller's share, so the buyer
+    /// facing floors rise as the share falls
+    MAX_PROFIT,
+    /// Number of a campaign's bids to receive, converged by throttling the campaign's bids (the control
+    /// variable is the share of its bids passed through, starting with no throttling)
+    THROTTLE { campaign_id: usize, target_bids: usize },
 }
 
 // Re-export convergence target types for convenience
-pub use crate::seller_targets::{SellerTargetNone, SellerTargetTotalCost};
+pub use crate::seller_targets::{SellerTargetNone, SellerTargetTotalCost, SellerTargetSellThrough, SellerTargetMaxProfit, SellerTargetThrottle, SellerTargetCompetitorWinRate};
+// Re-export builder types for convenience
+pub use crate::seller_builder::{SellerBuilder, SellerConfig};
 // Re-export charger types for convenience
-pub use crate::seller_chargers::{SellerCharger, SellerChargerFirstPrice, SellerChargerFixedPrice};
+pub use crate::seller_chargers::{SellerCharger, SellerChargerFirstPrice, SellerChargerSecondPrice, SellerChargerFixedPrice, SellerChargerHybrid, SellerChargerVolumeTiered, VolumeTier};
 
 /// Container for sellers with methods to add sellers
 /// Uses trait objects to support different seller types
 pub struct Sellers {
     pub sellers: Vec<Box<dyn SellerTrait>>,
+    /// Seller whose impressions each seller re-offers as a supply path (indexed by seller_id, missing or None = own impressions)
+    pub supply_path_sources: Vec<Option<usize>>,
+    /// Probability that a seller serves a campaign's bid from the previous iteration (indexed by seller_id, missing or None = fresh bids)
+    pub stale_bid_probabilities: Vec<Option<f64>>,
+    /// Probability that a seller drops a campaign's bid (indexed by seller_id, then campaign_id, missing = never)
+    pub throttle_probabilities: Vec<Vec<f64>>,
+    /// Boost schedule of each seller over the day (indexed by seller_id, missing or None = no schedule)
+    pub boost_schedules: Vec<Option<Vec<BoostPeriod>>>,
+    /// Share of campaign bids each seller's header bidding adapter takes (indexed by seller_id, missing or None = no fee)
+    pub adapter_fees: Vec<Option<f64>>,
 }
 
 impl Sellers {
     pub fn new() -> Self {
         Self {
             sellers: Vec::new(),
+            supply_path_sources: Vec::new(),
+            stale_bid_probabilities: Vec::new(),
+            throttle_probabilities: Vec::new(),
+            boost_schedules: Vec::new(),
+            adapter_fees: Vec::new(),
         }
     }
 
@@ -43,12 +105,21 @@ impl Sellers {
     /// 
     /// # Arguments
     /// * `seller_name` - Name of the seller
-    /// * `seller_type` - Seller type (FIRST_PRICE or FIXED_PRICE)
-    /// * `seller_converge` - Convergence strategy (NONE or TOTAL_COST)
+    /// * `seller_type` - Seller type (FIRST_PRICE, FIRST_PRICE_SOFT_FLOOR, SECOND_PRICE, FIXED_PRICE, HYBRID or VOLUME_TIERED)
+    /// * `seller_converge` - Convergence strategy (NONE, TOTAL_COST, SELL_THROUGH, MAX_PROFIT or THROTTLE)
     /// * `impressions_on_offer` - Number of impressions this seller will offer
     /// * `competition_generator` - Generator for impression competition data
     /// * `floor_generator` - Generator for floor CPM values
     pub fn add(&mut self, seller_name: String, seller_type: SellerType, seller_converge: SellerConvergeStrategy, impressions_on_offer: usize, competition_generator: Box<dyn CompetitionGeneratorTrait>, floor_generator: Box<dyn FloorGeneratorTrait>) {
+        self.add_configured(seller_name, seller_type, seller_converge, impressions_on_offer, competition_generator, floor_generator, ControllerConfig::default());
+    }
+
+    /// Add a seller to the collection, configuring the controller of its convergence strategy
+    /// 
+    /// Arguments are the same as for `add`, with `controller_config` setting the parameters of the
+    /// TOTAL_COST, SELL_THROUGH and THROTTLE controllers and the bounds of the MAX_PROFIT line search (ignored by NONE)
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_configured(&mut self, seller_name: String, seller_type: SellerType, seller_converge: SellerConvergeStrategy, impressions_on_offer: usize, competition_generator: Box<dyn CompetitionGeneratorTrait>, floor_generator: Box<dyn FloorGeneratorTrait>, controller_config: ControllerConfig) {
         let seller_id = self.sellers.len();
         
         // Create converge_targets and converge_controllers based on seller_converge
@@ -64,7 +135,48 @@ impl Sellers {
                     Box::new(SellerTargetTotalCost {
                         target_cost: target_total_cost,
                     }),
-                    Box::new(crate::controllers::ControllerProportionalDerivative::new())
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(&controller_config))
+                )
+            }
+            SellerConvergeStrategy::SELL_THROUGH { target_sell_through } => {
+                assert!(floor_generator.is_controlled(), "Seller '{}' converges sell-through, which needs a controlled floor generator (FloorGeneratorControlled)", seller_name);
+                assert!((0.0..1.0).contains(&target_sell_through), "Target sell-through must be in [0, 1), got {}", target_sell_through);
+                // Controllers adjust the scale relative to itself, so it is kept above zero to keep floors adjustable
+                let mut floor_config = controller_config.clone();
+                floor_config.min_control_variable = floor_config.min_control_variable.or(Some(MIN_FLOOR_SCALE));
+                (
+                    Box::new(SellerTargetSellThrough {
+                        target_sell_through,
+                        impressions_on_offer,
+                    }),
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(&floor_config))
+                )
+            }
+            SellerConvergeStrategy::MAX_PROFIT => {
+                let revenue_share = match &seller_type {
+                    SellerType::HYBRID { revenue_share, .. } => *revenue_share,
+                    _ => panic!("Seller '{}' maximizes profit by tuning its revenue share, which needs the HYBRID seller type", seller_name),
+                };
+                // A zero revenue share would gross floors up without bound, and the seller can't be paid more than the clearing price
+                let mut share_config = controller_config.clone();
+                share_config.min_control_variable = share_config.min_control_variable.or(Some(MIN_REVENUE_SHARE));
+                share_config.max_control_variable = share_config.max_control_variable.or(Some(1.0));
+                (
+                    Box::new(SellerTargetMaxProfit),
+                    Box::new(crate::controllers::ControllerLineSearch::new(revenue_share, REVENUE_SHARE_STEP).with_config(&share_config))
+                )
+            }
+            SellerConvergeStrategy::THROTTLE { campaign_id, target_bids } => {
+                // The pass-through share can't exceed 1, and is kept above zero so that some bids get through to adjust on
+                let mut throttle_config = controller_config.clone();
+                throttle_config.min_control_variable = throttle_config.min_control_variable.or(Some(MIN_PASS_THROUGH));
+                throttle_config.max_control_variable = throttle_config.max_control_variable.or(Some(1.0));
+                (
+                    Box::new(SellerTargetThrottle {
+                        campaign_id,
+                        target_bids,
+                    }),
+                    Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(&throttle_config))
                 )
             }
         };
@@ -82,6 +194,35 @@ impl Sellers {
                     competition_generator,
                     floor_generator,
                     seller_charger,
+                    soft_floor_cpm: None,
+                }));
+            }
+            SellerType::FIRST_PRICE_SOFT_FLOOR { soft_floor_cpm } => {
+                let seller_charger = Box::new(SellerChargerFirstPrice) as Box<dyn SellerCharger>;
+                self.sellers.push(Box::new(SellerGeneral {
+                    seller_id,
+                    seller_name,
+                    impressions_on_offer,
+                    converge_targets: vec![converge_target],
+                    converge_controllers: vec![converge_controller],
+                    competition_generator,
+                    floor_generator,
+                    seller_charger,
+                    soft_floor_cpm: Some(soft_floor_cpm),
+                }));
+            }
+            SellerType::SECOND_PRICE => {
+                let seller_charger = Box::new(SellerChargerSecondPrice) as Box<dyn SellerCharger>;
+                self.sellers.push(Box::new(SellerGeneral {
+                    seller_id,
+                    seller_name,
+                    impressions_on_offer,
+                    converge_targets: vec![converge_target],
+                    converge_controllers: vec![converge_controller],
+                    competition_generator,
+                    floor_generator,
+                    seller_charger,
+                    soft_floor_cpm: None,
                 }));
             }
             SellerType::FIXED_PRICE { fixed_cost_cpm } => {
@@ -97,11 +238,66 @@ impl Sellers {
                     competition_generator,
                     floor_generator,
                     seller_charger,
+                    soft_floor_cpm: None,
+                }));
+            }
+            SellerType::HYBRID { fixed_fee_cpm, revenue_share } => {
+                assert!((0.0..=1.0).contains(&revenue_share), "Revenue share must be in [0, 1], got {}", revenue_share);
+                let seller_charger = Box::new(SellerChargerHybrid {
+                    fixed_fee_cpm,
+                    revenue_share,
+                }) as Box<dyn SellerCharger>;
+                self.sellers.push(Box::new(SellerGeneral {
+                    seller_id,
+                    seller_name,
+                    impressions_on_offer,
+                    converge_targets: vec![converge_target],
+                    converge_controllers: vec![converge_controller],
+                    competition_generator,
+                    floor_generator,
+                    seller_charger,
+                    soft_floor_cpm: None,
+                }));
+            }
+            SellerType::VOLUME_TIERED { tiers } => {
+                assert!(tiers.first().is_some_and(|tier| tier.from_volume == 0), "Volume tiers of seller '{}' must start at volume 0", seller_name);
+                assert!(tiers.windows(2).all(|pair| pair[0].from_volume < pair[1].from_volume), "Volume tiers of seller '{}' must be ordered by increasing volume", seller_name);
+                let seller_charger = Box::new(SellerChargerVolumeTiered {
+                    tiers,
+                }) as Box<dyn SellerCharger>;
+                self.sellers.push(Box::new(SellerGeneral {
+                    seller_id,
+                    seller_name,
+                    impressions_on_offer,
+                    converge_targets: vec![converge_target],
+                    converge_controllers: vec![converge_controller],
+                    competition_generator,
+                    floor_generator,
+                    seller_charger,
+                    soft_floor_cpm: None,
                 }));
             }
         }
     }
 
+    /// Add a seller described by a configuration (see SellerBuilder), returning its ID
+    pub fn add_from_config(&mut self, config: SellerConfig) -> usize {
+        let seller_id = self.sellers.len();
+        let competition_generator = config.competition.build();
+        let floor_generator = config.floors.build();
+        self.add_configured(config.seller_name, config.seller_type, config.seller_converge, config.impressions_on_offer, competition_generator, floor_generator, config.controller_config);
+        if let Some(adapter_fee) = config.adapter_fee {
+            self.set_adapter_fee(seller_id, adapter_fee);
+        }
+        if let Some(stale_bid_probability) = config.stale_bid_probability {
+            self.set_bid_caching(seller_id, stale_bid_probability);
+        }
+        if let Some(boost_schedule) = config.boost_schedule {
+            self.set_boost_schedule(seller_id, boost_schedule);
+        }
+        seller_id
+    }
+
     /// Add a seller using an advanced method that accepts a pre-constructed SellerTrait
     /// 
     /// # Arguments
@@ -116,4 +312,150 @@ impl Sellers {
         
         self.sellers.push(seller);
     }
+
+    /// Make a seller a supply path of another seller's impressions
+    /// The path seller offers the first impressions_on_offer impressions of the source seller again, with
+    /// its own competition, floors and charging. Once the impression is sold through one of its paths,
+    /// the others are withdrawn
+    /// 
+    /// # Arguments
+    /// * `path_seller_id` - ID of the seller re-offering the impressions (added after the source seller)
+    /// * `source_seller_id` - ID of the seller whose impressions are re-offered
+    pub fn set_supply_path(&mut self, path_seller_id: usize, source_seller_id: usize) {
+        assert!(path_seller_id < self.sellers.len(), "No seller with ID {}", path_seller_id);
+        assert!(source_seller_id < path_seller_id, "Supply path seller {} must be added after its source seller {}", path_seller_id, source_seller_id);
+        assert!(self.supply_path_source(source_seller_id).is_none(), "Seller {} is itself a supply path, make seller {} a path of its source seller instead", source_seller_id, path_seller_id);
+        assert!(!self.supply_path_sources.contains(&Some(path_seller_id)), "Seller {} is the source of other supply paths and can't be a supply path itself", path_seller_id);
+        assert!(self.sellers[path_seller_id].get_impressions_on_offer() <= self.sellers[source_seller_id].get_impressions_on_offer(),
+            "Supply path seller '{}' offers more impressions than its source seller '{}' has", self.sellers[path_seller_id].seller_name(), self.sellers[source_seller_id].seller_name());
+        if self.supply_path_sources.len() <= path_seller_id {
+            self.supply_path_sources.resize(path_seller_id + 1, None);
+        }
+        self.supply_path_sources[path_seller_id] = Some(source_seller_id);
+    }
+
+    /// Get the seller whose impressions a seller re-offers (None = the seller offers its own impressions)
+    pub fn supply_path_source(&self, seller_id: usize) -> Option<usize> {
+        self.supply_path_sources.get(seller_id).copied().flatten()
+    }
+
+    /// Make a seller cache bids, modelling bid responses that arrive too late for the auction
+    /// On each of the seller's impressions, each campaign's bid is with probability stale_bid_probability replaced by
+    /// its bid on the same impression in the previous iteration (fresh bids are used in the first iteration)
+    /// 
+    /// # Arguments
+    /// * `seller_id` - ID of the seller caching bids
+    /// * `stale_bid_probability` - Probability in [0, 1] that a bid is served from the cache
+    pub fn set_bid_caching(&mut self, seller_id: usize, stale_bid_probability: f64) {
+        assert!(seller_id < self.sellers.len(), "No seller with ID {}", seller_id);
+        assert!((0.0..=1.0).contains(&stale_bid_probability), "Stale bid probability must be in [0, 1], got {}", stale_bid_probability);
+        if self.stale_bid_probabilities.len() <= seller_id {
+            self.stale_bid_probabilities.resize(seller_id + 1, None);
+        }
+        self.stale_bid_probabilities[seller_id] = Some(stale_bid_probability);
+    }
+
+    /// Get the probability that a seller serves cached bids (None = the seller uses fresh bids)
+    pub fn stale_bid_probability(&self, seller_id: usize) -> Option<f64> {
+        self.stale_bid_probabilities.get(seller_id).copied().flatten()
+    }
+
+    /// Make a seller throttle a campaign, modelling traffic shaping by the seller
+    /// On each of the seller's impressions, the campaign's bid is dropped with probability throttle_probability
+    /// (the draw is fixed per impression and campaign, so the same bids are throttled in every iteration)
+    /// 
+    /// # Arguments
+    /// * `seller_id` - ID of the seller throttling the campaign
+    /// * `campaign_id` - ID of the throttled campaign
+    /// * `throttle_probability` - Probability in [0, 1] that the campaign's bid is dropped
+    pub fn set_throttling(&mut self, seller_id: usize, campaign_id: usize, throttle_probability: f64) {
+        assert!(seller_id < self.sellers.len(), "No seller with ID {}", seller_id);
+        assert!((0.0..=1.0).contains(&throttle_probability), "Throttle probability must be in [0, 1], got {}", throttle_probability);
+        if self.throttle_probabilities.len() <= seller_id {
+            self.throttle_probabilities.resize(seller_id + 1, Vec::new());
+        }
+        let campaign_throttle_probabilities = &mut self.throttle_probabilities[seller_id];
+        if campaign_throttle_probabilities.len() <= campaign_id {
+            campaign_throttle_probabilities.resize(campaign_id + 1, 0.0);
+        }
+        campaign_throttle_probabilities[campaign_id] = throttle_probability;
+    }
+
+    /// Get the probability that a seller drops a campaign's bid set by set_throttling (0 = not throttled)
+    pub fn throttle_probability(&self, seller_id: usize, campaign_id: usize) -> f64 {
+        self.throttle_probabilities.get(seller_id).and_then(|campaign_throttle_probabilities| campaign_throttle_probabilities.get(campaign_id)).copied().unwrap_or(0.0)
+    }
+
+    /// Make a seller's boost follow a schedule over the day, e.g. cheaper in off-peak hours
+    /// Campaign bids on the seller's impressions are multiplied by the boost factor of the period the impression's
+    /// time of day is in, on top of the seller's control variable when it boosts bids (SellerControl::BOOST)
+    /// 
+    /// # Arguments
+    /// * `seller_id` - ID of the seller
+    /// * `schedule` - Periods ordered by increasing from_hour, the first one from hour 0, the last one lasting until the end of the day
+    pub fn set_boost_schedule(&mut self, seller_id: usize, schedule: Vec<BoostPeriod>) {
+        assert!(seller_id < self.sellers.len(), "No seller with ID {}", seller_id);
+        assert!(schedule.first().is_some_and(|period| period.from_hour == 0.0), "Boost schedule of seller {} must start at hour 0", seller_id);
+        assert!(schedule.windows(2).all(|pair| pair[0].from_hour < pair[1].from_hour), "Boost schedule of seller {} must be ordered by increasing hour", seller_id);
+        assert!(schedule.iter().all(|period| period.from_hour < HOURS_PER_DAY && period.boost_factor > 0.0), "Boost schedule of seller {} must have periods within the day and positive boost factors", seller_id);
+        if self.boost_schedules.len() <= seller_id {
+            self.boost_schedules.resize(seller_id + 1, None);
+        }
+        self.boost_schedules[seller_id] = Some(schedule);
+    }
+
+    /// Get the scheduled boost factor of a seller at the given time of day in hours (1.0 = no schedule)
+    pub fn scheduled_boost(&self, seller_id: usize, time_of_day: f64) -> f64 {
+        match self.boost_schedules.get(seller_id) {
+            Some(Some(schedule)) => schedule.iter().rev()
+                .find(|period| period.from_hour <= time_of_day)
+                .map_or(1.0, |period| period.boost_factor),
+            _ => 1.0,
+        }
+    }
+
+    /// Make a seller charge an adapter fee, modelling the cost of the header bidding wrapper the seller is reached through
+    /// Campaign bids on the seller's impressions are reduced by the fee before they are compared with the floor, the
+    /// competing bid and each other, so bids routed through expensive sellers are less competitive. Winners are charged
+    /// their bids (or the second price) grossed up by the fee, which goes to the adapter (see SellerStat::total_adapter_fees)
+    /// 
+    /// # Arguments
+    /// * `seller_id` - ID of the seller
+    /// * `adapter_fee` - Share in [0, 1) of the bids the adapter takes
+    pub fn set_adapter_fee(&mut self, seller_id: usize, adapter_fee: f64) {
+        assert!(seller_id < self.sellers.len(), "No seller with ID {}", seller_id);
+        assert!((0.0..1.0).contains(&adapter_fee), "Adapter fee must be in [0, 1), got {}", adapter_fee);
+        if self.adapter_fees.len() <= seller_id {
+            self.adapter_fees.resize(seller_id + 1, None);
+        }
+        self.adapter_fees[seller_id] = Some(adapter_fee);
+    }
+
+    /// Get the adapter fee of a seller (None = the seller charges no adapter fee)
+    pub fn adapter_fee(&self, seller_id: usize) -> Option<f64> {
+        self.adapter_fees.get(seller_id).copied().flatten()
+    }
+
+    /// Make the competition on a seller's impressions a reactive competitor, adapting its bid level across
+    /// iterations to win a target share of the seller's auctions
+    /// The competing bids generated for the seller's impressions are multiplied by the control variable of an
+    /// additional converge target of the seller (SellerTargetCompetitorWinRate), raised while the competitor
+    /// wins less than the target, so the competitor and the campaigns converge to an equilibrium
+    /// 
+    /// # Arguments
+    /// * `seller_id` - ID of the seller (a SellerGeneral, as added by `add`)
+    /// * `target_win_rate` - Share in (0, 1) of the seller's auctions the competitor aims to win
+    /// * `controller_config` - Parameters of the competitor's controller
+    pub fn set_reactive_competitor(&mut self, seller_id: usize, target_win_rate: f64, controller_config: ControllerConfig) {
+        assert!(seller_id < self.sellers.len(), "No seller with ID {}", seller_id);
+        assert!(target_win_rate > 0.0 && target_win_rate < 1.0, "Competitor target win rate must be in (0, 1), got {}", target_win_rate);
+        let seller_general = self.sellers[seller_id].as_any_mut().downcast_mut::<SellerGeneral>()
+            .unwrap_or_else(|| panic!("Reactive competitors need a SellerGeneral seller, seller {} isn't one", seller_id));
+        assert!(!seller_general.converge_targets.iter().any(|converge_target| converge_target.scales_competition()), "Seller {} already has a reactive competitor", seller_id);
+        // Controllers adjust the factor relative to itself, so it is kept above zero to keep the competitor adjustable
+        let mut competitor_config = controller_config;
+        competitor_config.min_control_variable = competitor_config.min_control_variable.or(Some(MIN_COMPETITOR_BID_FACTOR));
+        seller_general.converge_targets.push(Box::new(SellerTargetCompetitorWinRate { target_win_rate }));
+        seller_general.converge_controllers.push(Box::new(crate::controllers::ControllerProportionalDerivative::new().with_config(&competitor_config)));
+    }
 }
@@ -0,0 +1,299 @@
+//! Sensitivity analysis of KPIs to the marketplace's input parameters
+//!
+//! A sensitivity analysis declares the input parameters of a scenario's marketplace with their baseline values
+//! and the KPIs (metrics of the converged statistics) to report on. It runs the baseline, then perturbs one
+//! parameter at a time down and up by a relative perturbation (all others at their baseline), each parameter
+//! as a one parameter sweep (see sweep::Sweep). The report ranks the parameters by how much they move each KPI,
+//! as a table in the scenario log, a CSV and a tornado chart per KPI.
+
+use crate::simulationrun::SimulationStat;
+use crate::converge::SimulationConverge;
+use crate::sweep::Sweep;
+use crate::logger::{Logger, LogEvent, FileReceiver, sanitize_filename};
+use crate::logln;
+use plotters::prelude::*;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Relative perturbation of the parameters unless set (±10%)
+const DEFAULT_PERTURBATION: f64 = 0.1;
+
+/// KPI reported on by a sensitivity analysis
+type SensitivityMetric = Rc<dyn Fn(&SimulationStat) -> f64>;
+
+/// An input parameter and its baseline value
+pub struct SensitivityParameter {
+    pub name: String,
+    pub baseline: f64,
+}
+
+/// Effect of perturbing one parameter on one KPI
+pub struct SensitivityEffect {
+    pub parameter_name: String,
+    pub metric_name: String,
+    /// KPI with the parameter perturbed down and up, None if the run failed to converge
+    pub low: Option<f64>,
+    pub high: Option<f64>,
+    /// KPI at the baseline
+    pub baseline: f64,
+}
+
+impl SensitivityEffect {
+    /// Relative change of the KPI from the baseline with the parameter perturbed down and up
+    pub fn relative_changes(&self) -> (Option<f64>, Option<f64>) {
+        let change = |value: Option<f64>| value.map(|value| value / self.baseline - 1.0);
+        (change(self.low), change(self.high))
+    }
+
+    /// Relative range the KPI spans between the two perturbations (the bar length in the tornado chart),
+    /// None if either perturbation failed to converge
+    pub fn swing(&self) -> Option<f64> {
+        match (self.low, self.high) {
+            (Some(low), Some(high)) => Some((high - low).abs() / self.baseline.abs()),
+            _ => None,
+        }
+    }
+}
+
+/// Results of a sensitivity analysis
+pub struct SensitivityResult {
+    pub parameter_names: Vec<String>,
+    pub metric_names: Vec<String>,
+    /// Relative perturbation of the parameters
+    pub perturbation: f64,
+    /// Statistics of the baseline run
+    pub baseline_stats: SimulationStat,
+    /// Effect of every parameter on every KPI (in order of the parameters, then of the KPIs)
+    pub effects: Vec<SensitivityEffect>,
+}
+
+impl SensitivityResult {
+    /// Effect of a parameter on a KPI
+    /// Panics if the analysis has no such parameter or KPI
+    pub fn effect(&self, parameter_name: &str, metric_name: &str) -> &SensitivityEffect {
+        self.effects.iter()
+            .find(|effect| effect.parameter_name == parameter_name && effect.metric_name == metric_name)
+            .unwrap_or_else(|| panic!("No sensitivity effect of parameter '{}' on '{}'", parameter_name, metric_name))
+    }
+
+    /// Effects of all parameters on a KPI, the most influential first (parameters failing to converge last)
+    pub fn ranking(&self, metric_name: &str) -> Vec<&SensitivityEffect> {
+        assert!(self.metric_names.iter().any(|name| name == metric_name), "No sensitivity metric '{}'", metric_name);
+        let mut effects: Vec<&SensitivityEffect> = self.effects.iter().filter(|effect| effect.metric_name == metric_name).collect();
+        effects.sort_by(|a, b| b.swing().unwrap_or(f64::NEG_INFINITY).total_cmp(&a.swing().unwrap_or(f64::NEG_INFINITY)));
+        effects
+    }
+
+    /// Perturbations of any parameter that failed to converge
+    pub fn failed_runs(&self) -> usize {
+        self.effects.iter()
+            .filter(|effect| effect.metric_name == self.metric_names[0])
+            .map(|effect| effect.low.is_none() as usize + effect.high.is_none() as usize)
+            .sum()
+    }
+}
+
+/// One-at-a-time sensitivity analysis around a baseline
+pub struct SensitivityAnalysis {
+    pub name: String,
+    pub parameters: Vec<SensitivityParameter>,
+    /// Relative perturbation of the parameters, e.g. 0.1 runs each at 90% and 110% of its baseline
+    pub perturbation: f64,
+    metrics: Vec<(String, SensitivityMetric)>,
+}
+
+impl SensitivityAnalysis {
+    /// Create a new sensitivity analysis, `name` names its variants and output files
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters: Vec::new(),
+            perturbation: DEFAULT_PERTURBATION,
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Add an input parameter with its baseline value (non-zero, perturbations are relative to it)
+    pub fn with_parameter(mut self, name: &str, baseline: f64) -> Self {
+        assert!(baseline != 0.0, "Sensitivity parameter '{}' needs a non-zero baseline", name);
+        self.parameters.push(SensitivityParameter { name: name.to_string(), baseline });
+        self
+    }
+
+    /// Set the relative perturbation of the parameters, e.g. 0.2 for ±20%
+    pub fn with_perturbation(mut self, perturbation: f64) -> Self {
+        assert!(perturbation > 0.0 && perturbation < 1.0, "Perturbation of sensitivity analysis '{}' must be within (0, 1)", self.name);
+        self.perturbation = perturbation;
+        self
+    }
+
+    /// Report on a KPI of the converged statistics
+    pub fn with_metric<F: Fn(&SimulationStat) -> f64 + 'static>(mut self, name: &str, metric: F) -> Self {
+        self.metrics.push((name.to_string(), Rc::new(metric)));
+        self
+    }
+
+    /// Run the baseline and the perturbations of every parameter, and write the report
+    ///
+    /// `prepare` creates the simulation from the values of all parameters (in order of the parameters). The
+    /// baseline runs as variant `<name>-baseline`, the perturbations of a parameter as the sweep
+    /// `<name>-<parameter>` (written to `sweep-<name>-<parameter>.csv`). The report goes to the scenario log,
+    /// `sensitivity-<name>.csv` and a tornado chart per KPI, `sensitivity-<name>-<metric>.png`.
+    ///
+    /// # Returns
+    /// Returns a Result with the SensitivityResult, or an error if the baseline failed to converge or a chart
+    /// couldn't be drawn
+    pub fn run<F: Fn(&[f64]) -> SimulationConverge>(
+        &self,
+        prepare: F,
+        scenario_name: &str,
+        max_iterations: usize,
+        logger: &mut Logger,
+    ) -> Result<SensitivityResult, Box<dyn std::error::Error>> {
+        assert!(!self.parameters.is_empty(), "Sensitivity analysis '{}' has no parameters", self.name);
+        assert!(!self.metrics.is_empty(), "Sensitivity analysis '{}' has no metrics", self.name);
+        let baseline_values: Vec<f64> = self.parameters.iter().map(|parameter| parameter.baseline).collect();
+
+        let baseline_stats = prepare(&baseline_values).run_variant(&format!("Running {} baseline", self.name), scenario_name, &format!("{}-baseline", self.name), max_iterations, logger)?;
+        let baseline_metrics: Vec<f64> = self.metrics.iter().map(|(_, metric)| metric(&baseline_stats)).collect();
+
+        let mut effects = Vec::new();
+        for (parameter_index, parameter) in self.parameters.iter().enumerate() {
+            let low = parameter.baseline * (1.0 - self.perturbation);
+            let high = parameter.baseline * (1.0 + self.perturbation);
+            let sweep = self.metrics.iter().fold(
+                Sweep::new(&format!("{}-{}", self.name, parameter.name)).with_parameter(&parameter.name, vec![low, high]),
+                |sweep, (metric_name, metric)| {
+                    let metric = Rc::clone(metric);
+                    sweep.with_metric(metric_name, move |stats| metric(stats))
+                },
+            );
+            let sweep_result = sweep.run(|values| {
+                let mut parameter_values = baseline_values.clone();
+                parameter_values[parameter_index] = values[0];
+                prepare(&parameter_values)
+            }, scenario_name, max_iterations, logger)?;
+
+            for (metric_index, (metric_name, _)) in self.metrics.iter().enumerate() {
+                effects.push(SensitivityEffect {
+                    parameter_name: parameter.name.clone(),
+                    metric_name: metric_name.clone(),
+                    low: sweep_result.point(&[low]).metric_values.get(metric_index).copied(),
+                    high: sweep_result.point(&[high]).metric_values.get(metric_index).copied(),
+                    baseline: baseline_metrics[metric_index],
+                });
+            }
+        }
+        let result = SensitivityResult {
+            parameter_names: self.parameters.iter().map(|parameter| parameter.name.clone()).collect(),
+            metric_names: self.metrics.iter().map(|(name, _)| name.clone()).collect(),
+            perturbation: self.perturbation,
+            baseline_stats,
+            effects,
+        };
+
+        self.printout(&result, logger);
+        self.write_csv(&result, scenario_name, logger);
+        for metric_name in &result.metric_names {
+            let path = logger.log_path(&format!("{}/sensitivity-{}-{}.png", sanitize_filename(scenario_name), sanitize_filename(&self.name), sanitize_filename(metric_name)));
+            self.draw_tornado(&result, metric_name, &path)?;
+            logln!(logger, LogEvent::Scenario, "Sensitivity {}: tornado chart of {} written to {}", self.name, metric_name, path.display());
+        }
+        Ok(result)
+    }
+
+    /// Output the parameters' effects on every KPI, the most influential first
+    fn printout(&self, result: &SensitivityResult, logger: &mut Logger) {
+        let percent = |change: Option<f64>| change.map_or("not converged".to_string(), |change| format!("{:+.2}%", change * 100.0));
+        logln!(logger, LogEvent::Scenario, "\n=== Sensitivity {} (parameters ±{:.0}%) ===", self.name, result.perturbation * 100.0);
+        for metric_name in &result.metric_names {
+            let ranking = result.ranking(metric_name);
+            logln!(logger, LogEvent::Scenario, "{} (baseline {:.4}):", metric_name, ranking[0].baseline);
+            for effect in ranking {
+                let (low_change, high_change) = effect.relative_changes();
+                logln!(logger, LogEvent::Scenario, "  {:<30} -{:.0}%: {:>10}  +{:.0}%: {:>10}  swing: {}",
+                    effect.parameter_name, result.perturbation * 100.0, percent(low_change), result.perturbation * 100.0, percent(high_change), percent(effect.swing()));
+            }
+        }
+    }
+
+    /// Write the effects as CSV, a row per parameter and KPI with the KPI at the baseline, at both
+    /// perturbations (empty if the run failed to converge) and the swing
+    fn write_csv(&self, result: &SensitivityResult, scenario_name: &str, logger: &mut Logger) {
+        let optional = |value: Option<f64>| value.map_or(String::new(), |value| value.to_string());
+        let sensitivity_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/sensitivity-{}.csv", sanitize_filename(scenario_name), sanitize_filename(&self.name))), vec![LogEvent::Sweep]));
+        logln!(logger, LogEvent::Sweep, "parameter,baseline_value,low_value,high_value,metric,metric_baseline,metric_low,metric_high,swing");
+        for effect in &result.effects {
+            let parameter = self.parameters.iter().find(|parameter| parameter.name == effect.parameter_name).unwrap();
+            logln!(logger, LogEvent::Sweep, "{},{},{},{},{},{},{},{},{}",
+                effect.parameter_name, parameter.baseline, parameter.baseline * (1.0 - result.perturbation), parameter.baseline * (1.0 + result.perturbation),
+                effect.metric_name, effect.baseline, optional(effect.low), optional(effect.high), optional(effect.swing()));
+        }
+        logger.remove_receiver(sensitivity_receiver_id);
+    }
+
+    /// Draw a tornado chart of a KPI: a row per parameter, the most influential on top, with bars of the KPI's
+    /// relative change with the parameter perturbed down (blue) and up (red)
+    fn draw_tornado(&self, result: &SensitivityResult, metric_name: &str, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        // Most influential parameter in the top row
+        let ranking: Vec<&SensitivityEffect> = result.ranking(metric_name).into_iter().rev().collect();
+        let count = ranking.len();
+        let extent = ranking.iter()
+            .flat_map(|effect| {
+                let (low_change, high_change) = effect.relative_changes();
+                [low_change, high_change]
+            })
+            .flatten()
+            .fold(0.0_f64, |extent, change| extent.max(change.abs() * 100.0));
+        // Avoid an empty axis when no parameter moves the KPI
+        let extent = if extent > 0.0 { extent * 1.1 } else { 1.0 };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let root = BitMapBackend::new(path, (1600, 200 + 100 * count as u32)).into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(format!("{}: {} (parameters ±{:.0}%)", self.name, metric_name, result.perturbation * 100.0), ("sans-serif", 30))
+            .margin(20)
+            .x_label_area_size(60)
+            .y_label_area_size(300)
+            .build_cartesian_2d(-extent..extent, (0..count - 1).into_segmented())?;
+        let segment_end = |index: usize| if index + 1 < count { SegmentValue::Exact(index + 1) } else { SegmentValue::Last };
+
+        chart.configure_mesh()
+            .disable_y_mesh()
+            .label_style(("sans-serif", 20))
+            .axis_desc_style(("sans-serif", 25))
+            .x_desc(format!("Change of {} (%)", metric_name))
+            .y_labels(count)
+            .y_label_formatter(&|segment| match segment {
+                SegmentValue::CenterOf(index) => ranking.get(*index).map_or(String::new(), |effect| effect.parameter_name.clone()),
+                _ => String::new(),
+            })
+            .draw()?;
+
+        // Each bar spans its parameter's row, from no change to the change with the perturbation
+        for (color, sign, perturbed_down) in [(BLUE, '-', true), (RED, '+', false)] {
+            chart.draw_series(ranking.iter().enumerate().filter_map(|(index, effect)| {
+                let (low_change, high_change) = effect.relative_changes();
+                let change = if perturbed_down { low_change } else { high_change }?;
+                Some(Rectangle::new(
+                    [(0.0, SegmentValue::Exact(index)), (change * 100.0, segment_end(index))],
+                    color.mix(0.6).filled(),
+                ))
+            }))?
+            .label(format!("Parameter {}{:.0}%", sign, result.perturbation * 100.0))
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], color.mix(0.6).filled()));
+        }
+        chart.configure_series_labels()
+            .label_font(("sans-serif", 20))
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        root.present()?;
+        Ok(())
+    }
+}
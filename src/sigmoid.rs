@@ -231,6 +231,21 @@ impl Sigmoid {
         Some((min_x + max_x) / 2.0)
     }
 
+    /// Get the probability at x of the sigmoid truncated at floor
+    /// Probability is zero below floor and renormalized above it:
+    /// (get_probability(x) - get_probability(floor)) / (1 - get_probability(floor))
+    #[allow(dead_code)]
+    pub fn get_probability_floor_truncated(&self, x: f64, floor: f64) -> f64 {
+        if x < floor {
+            return 0.0;
+        }
+        let floor_probability = self.get_probability(floor);
+        if 1.0 - floor_probability < 1e-15 {
+            return 1.0;
+        }
+        (self.get_probability(x) - floor_probability) / (1.0 - floor_probability)
+    }
+
     /// Find the bid that maximizes margin = P(win) * (full_price - bid)
     /// Uses bisection to find where the derivative of margin is zero
     /// 
@@ -241,6 +256,25 @@ impl Sigmoid {
     /// # Returns
     /// The bid that maximizes margin, or None if max_bid <= min_bid
     pub fn max_margin_bid_bisection(&self, full_price: f64, min_bid: f64) -> Option<f64> {
+        self.max_margin_bid_bisection_truncated(full_price, min_bid, 0.0)
+    }
+
+    /// Find the bid that maximizes margin when P(win) is the sigmoid truncated at floor
+    /// (see get_probability_floor_truncated)
+    /// 
+    /// # Arguments
+    /// * `full_price` - Maximum price we're willing to pay (also used as max_bid)
+    /// * `floor` - Floor price, below which the win probability is zero
+    /// 
+    /// # Returns
+    /// The bid that maximizes margin, or None if max_bid <= floor
+    pub fn max_margin_bid_bisection_floor_truncated(&self, full_price: f64, floor: f64) -> Option<f64> {
+        self.max_margin_bid_bisection_truncated(full_price, floor, self.get_probability(floor))
+    }
+
+    /// Shared bisection for max margin bidding
+    /// `truncation_probability` is the untruncated probability at min_bid that is cut off by truncation (0.0 = no truncation)
+    fn max_margin_bid_bisection_truncated(&self, full_price: f64, min_bid: f64, truncation_probability: f64) -> Option<f64> {
         const MAX_ITERATIONS: usize = 100;
         const TOLERANCE: f64 = 1e-6;
         
@@ -262,11 +296,15 @@ impl Sigmoid {
         //                 = prob(bid) * [scale * (1 - prob(bid)) * (full_price - bid) - 1]
         //
         // We'll find the root of: f(bid) = scale * (1 - prob(bid)) * (full_price - bid) - 1
+        //
+        // With truncation, P(win) = (prob(bid) - p_t) / (1 - p_t), where p_t = truncation_probability.
+        // The constant denominator does not move the maximum, and the derivative becomes
+        // prob(bid) * [scale * (1 - prob(bid)) * (full_price - bid) - 1 + p_t / prob(bid)]
         
         // Helper function to compute the derivative of margin
         let margin_derivative = |bid: f64| -> f64 {
             let prob = self.get_probability(bid);
-            self.scale * (1.0 - prob) * (full_price - bid) - 1.0
+            self.scale * (1.0 - prob) * (full_price - bid) - 1.0 + truncation_probability / prob
         };
         
         // Check if we have opposite signs at the bounds
@@ -310,6 +348,72 @@ impl Sigmoid {
         Some((low + high) / 2.0)
     }
 
+    /// Integral of get_probability from -infinity to x
+    /// Returns: ln(1 + exp((x - offset) * scale)) / scale (computed in a numerically stable way)
+    pub fn get_probability_integral(&self, x: f64) -> f64 {
+        let z = (x - self.offset) * self.scale;
+        (z.max(0.0) + (-z.abs()).exp().ln_1p()) / self.scale
+    }
+
+    /// Expected margin of a bid under first price: P(win) * (full_price - bid)
+    pub fn expected_margin_first_price(&self, full_price: f64, bid: f64) -> f64 {
+        self.get_probability(bid) * (full_price - bid)
+    }
+
+    /// Expected margin of a bid under second price, where the winner pays max(competing bid, floor)
+    /// 
+    /// With the competing bid distributed by the sigmoid:
+    /// E[margin] = integral of (full_price - max(x, floor)) dP(x) from -infinity to bid
+    ///           = P(bid) * (full_price - bid) + integral of P(x) dx from floor to bid
+    /// 
+    /// Returns 0.0 when the bid is below the floor
+    pub fn expected_margin_second_price(&self, full_price: f64, bid: f64, floor: f64) -> f64 {
+        if bid < floor {
+            return 0.0;
+        }
+        self.expected_margin_first_price(full_price, bid) + self.get_probability_integral(bid) - self.get_probability_integral(floor)
+    }
+
+    /// Find the bid that maximizes expected margin under a soft floor price rule
+    /// (second price for winning bids below the soft floor, first price at or above it)
+    /// 
+    /// Both regimes are optimized separately and the one with higher expected margin wins:
+    /// - Below the soft floor truthful bidding is optimal, so the bid is full_price capped just below the soft floor
+    /// - At or above the soft floor it is max margin bidding with the soft floor as minimum bid
+    /// 
+    /// # Arguments
+    /// * `full_price` - Maximum price we're willing to pay
+    /// * `floor` - Hard floor price
+    /// * `soft_floor` - Soft floor price
+    /// 
+    /// # Returns
+    /// The bid that maximizes expected margin, or None if full_price <= floor
+    pub fn max_margin_bid_soft_floor(&self, full_price: f64, floor: f64, soft_floor: f64) -> Option<f64> {
+        // Second price regime (only exists when the soft floor is above the hard floor)
+        let second_price_bid = full_price.min(soft_floor - 0.00001);
+        let second_price = if second_price_bid > floor {
+            Some((second_price_bid, self.expected_margin_second_price(full_price, second_price_bid, floor)))
+        } else {
+            None
+        };
+
+        // First price regime
+        let first_price = self.max_margin_bid_bisection(full_price, floor.max(soft_floor))
+            .map(|bid| (bid, self.expected_margin_first_price(full_price, bid)));
+
+        match (second_price, first_price) {
+            (Some((second_price_bid, second_price_margin)), Some((first_price_bid, first_price_margin))) => {
+                if second_price_margin >= first_price_margin {
+                    Some(second_price_bid)
+                } else {
+                    Some(first_price_bid)
+                }
+            }
+            (Some((bid, _)), None) | (None, Some((bid, _))) => Some(bid),
+            (None, None) => None,
+        }
+    }
+
     // The problem with this function is that it is not robust at  - better to use bisection
     #[allow(dead_code)]
     pub fn marginal_utility_of_spend_inverse(&self, y_target: f64) -> Option<f64> {
@@ -383,6 +487,46 @@ mod tests {
         assert!((prob - 0.5).abs() < 0.01);
     }
 
+    #[test]
+    fn test_floor_truncated_max_margin_bid() {
+        let sigmoid = Sigmoid::new(8.0, 0.5, 1.0);
+        // Zero below floor, renormalized to span [0, 1] above it
+        assert_eq!(sigmoid.get_probability_floor_truncated(5.0, 6.0), 0.0);
+        assert!(sigmoid.get_probability_floor_truncated(6.0, 6.0).abs() < 1e-12);
+        assert!(sigmoid.get_probability_floor_truncated(8.0, 6.0) > sigmoid.get_probability(8.0) - sigmoid.get_probability(6.0));
+        // Bidding at the floor wins nothing under truncation, so the optimal bid moves above the untruncated one
+        let untruncated = sigmoid.max_margin_bid_bisection(12.0, 6.0).unwrap();
+        let truncated = sigmoid.max_margin_bid_bisection_floor_truncated(12.0, 6.0).unwrap();
+        assert!(truncated > untruncated);
+        assert!(truncated > 6.0 && truncated < 12.0);
+        // Without a binding floor both agree
+        let sigmoid = Sigmoid::new(8.0, 2.0, 1.0);
+        let untruncated = sigmoid.max_margin_bid_bisection(12.0, 0.0).unwrap();
+        let truncated = sigmoid.max_margin_bid_bisection_floor_truncated(12.0, 0.0).unwrap();
+        assert!((truncated - untruncated).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_soft_floor_max_margin_bid() {
+        let sigmoid = Sigmoid::new(8.0, 2.0, 1.0);
+        // Second price margin matches numerical integration of (full_price - max(x, floor)) dP(x)
+        let (full_price, bid, floor) = (12.0, 9.0, 7.0);
+        let steps = 100000;
+        let mut numeric = 0.0;
+        for i in 0..steps {
+            let x = bid * (i as f64 + 0.5) / steps as f64;
+            let dp = sigmoid.get_probability(bid * (i + 1) as f64 / steps as f64) - sigmoid.get_probability(bid * i as f64 / steps as f64);
+            numeric += (full_price - x.max(floor)) * dp;
+        }
+        assert!((sigmoid.expected_margin_second_price(full_price, bid, floor) - numeric).abs() < 0.001);
+        // Soft floor above the value: bid truthfully under second price
+        assert_eq!(sigmoid.max_margin_bid_soft_floor(12.0, 0.0, 20.0), Some(12.0));
+        // Soft floor at the hard floor: same as max margin bidding under first price
+        assert_eq!(sigmoid.max_margin_bid_soft_floor(12.0, 1.0, 1.0), sigmoid.max_margin_bid_bisection(12.0, 1.0));
+        // Can't afford the floor
+        assert_eq!(sigmoid.max_margin_bid_soft_floor(5.0, 6.0, 10.0), None);
+    }
+
     // #[test]
     // fn test_inverse() {
     //     let sigmoid = Sigmoid::new(8.0, 0.5, 1.0);
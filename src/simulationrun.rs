@@ -5,19 +5,31 @@
 /// - Standard: First price auction
 /// - Fractional auction: Fractional auction where multiple impressions that bid more than "generated competition" can win the auction fractionally
 ///     what fraction of auction they win is handled by softmax with its temperature
+///
+/// The auction type is set on the marketplace and can be overridden per seller, each impression being auctioned with the type of its seller
 
 
-use crate::impressions::{AuctionResult, FractionalAuctionResult, FractionalWinners, Winner, Impressions, ImpressionsParam};
-use crate::sellers::Sellers;
-use crate::campaigns::Campaigns;
+use crate::impressions::{AuctionResult, FractionalAuctionResult, FractionalWinners, Winner, Impression, Impressions, ImpressionsParam, HOURS_PER_DAY};
+use crate::sellers::{Sellers, SellerControl};
+use crate::oscillation::Oscillation;
+use crate::campaigns::{Campaigns, TargetTolerance};
 use crate::converge::{CampaignControllerStates, SellerControllerStates};
+use crate::experiments::{ExperimentArm, ExperimentStat};
+use crate::hooks::{AuctionEvent, AuctionOutcome, SimulationHooks};
 use crate::logger::{Logger, LogEvent};
 use crate::logln;
-use crate::utils::TOTAL_SIMULATION_RUNS;
+use crate::utils::{get_seed, TOTAL_SIMULATION_RUNS};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::sync::atomic::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Number of impressions campaigns bid on in one CampaignTrait::get_bids call (at most, batches end at online updates)
+const BID_BATCH_SIZE: usize = 64;
 
 /// Simulation type determining the auction mechanism
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub enum SimulationType {
     Standard,
     /// Fractional auction with temperature parameter for softmax
@@ -33,8 +45,14 @@ pub enum SimulationType {
 pub struct Marketplace {
     pub campaigns: crate::campaigns::Campaigns,
     pub sellers: crate::sellers::Sellers,
-    pub impressions: crate::impressions::Impressions,
+    /// Impressions on offer, shared with other marketplaces created with new_shared
+    pub impressions: Rc<Impressions>,
+    /// Auction mechanism of sellers without their own (see with_seller_simulation_type)
     pub simulation_type: SimulationType,
+    /// Auction mechanism overriding simulation_type per seller (indexed by seller_id, missing or None = simulation_type)
+    pub seller_simulation_types: Vec<Option<SimulationType>>,
+    /// Number of impressions each campaign can win under its frequency cap (indexed by campaign_id, None = no cap)
+    pub eligible_impressions: Vec<Option<f64>>,
 }
 
 impl Marketplace {
@@ -43,15 +61,117 @@ impl Marketplace {
     pub fn new(mut campaigns: Campaigns, sellers: Sellers, impressions_params: &ImpressionsParam, simulation_type: SimulationType) -> Self {
         // Finalize campaign groups before creating impressions
         campaigns.finalize_groups();
+        Self::check_throttling(&campaigns, &sellers);
         // Generally all simulations run perfectly well with fractional auctions...
         //        let simulation_type = SimulationType::FractionalInternalAuction { softmax_temperature: 0.5 };
-        let impressions = Impressions::new(&sellers, impressions_params, &campaigns);
+        let impressions = Rc::new(Impressions::new(&sellers, impressions_params, &campaigns));
+        Self::with_impressions(campaigns, sellers, impressions, simulation_type)
+    }
+
+    /// Create a new Marketplace offering impressions generated earlier (see shared_impressions), instead of
+    /// generating them anew
+    /// Variants of a scenario sharing the same impressions see the same values, competition and floors (common
+    /// random numbers), so their comparison isn't confounded by different random draws. The impressions stay
+    /// as generated, so sellers' competition and floor generators and the impressions parameters of the variant
+    /// creating them apply to all variants sharing them
+    /// 
+    /// # Panics
+    /// Panics if the impressions don't have a value for every value group of the campaigns, or a seller doesn't
+    /// offer as many impressions as the impressions hold of it
+    pub fn new_shared(mut campaigns: Campaigns, sellers: Sellers, impressions: Rc<Impressions>, simulation_type: SimulationType) -> Self {
+        campaigns.finalize_groups();
+        Self::check_throttling(&campaigns, &sellers);
+        let mut seller_impressions = vec![0; sellers.sellers.len()];
+        for impression in &impressions.impressions {
+            assert_eq!(impression.value_to_campaign_group.len(), campaigns.value_groups.len(),
+                "Shared impressions were generated for {} value groups, but the campaigns have {}", impression.value_to_campaign_group.len(), campaigns.value_groups.len());
+            assert!(impression.seller_id < sellers.sellers.len(), "Shared impressions are offered by seller {}, but there are only {} sellers", impression.seller_id, sellers.sellers.len());
+            seller_impressions[impression.seller_id] += 1;
+        }
+        for (seller, &count) in sellers.sellers.iter().zip(&seller_impressions) {
+            assert_eq!(seller.get_impressions_on_offer(), count,
+                "Seller '{}' offers {} impressions, but the shared impressions hold {} of it", seller.seller_name(), seller.get_impressions_on_offer(), count);
+        }
+        Self::with_impressions(campaigns, sellers, impressions, simulation_type)
+    }
+
+    /// Impressions of this marketplace, to create further marketplaces offering the same impressions with new_shared
+    pub fn shared_impressions(&self) -> Rc<Impressions> {
+        Rc::clone(&self.impressions)
+    }
+
+    /// Offer other impressions of the same sellers and value groups, e.g. the impressions of one period of a
+    /// multi-period run (see SimulationConverge::run_periods)
+    /// Returns the impressions offered before
+    pub fn replace_impressions(&mut self, impressions: Rc<Impressions>) -> Rc<Impressions> {
+        self.eligible_impressions = (0..self.campaigns.campaigns.len())
+            .map(|campaign_id| self.campaigns.frequency_cap(campaign_id).map(|frequency_cap| Self::frequency_capped_supply(&impressions, &self.campaigns, campaign_id, frequency_cap)))
+            .collect();
+        std::mem::replace(&mut self.impressions, impressions)
+    }
+
+    /// Sellers can only throttle campaigns that exist
+    fn check_throttling(campaigns: &Campaigns, sellers: &Sellers) {
+        for (seller_id, seller) in sellers.sellers.iter().enumerate() {
+            let statically_throttled = sellers.throttle_probabilities.get(seller_id).map_or(0, |campaign_throttle_probabilities| campaign_throttle_probabilities.len());
+            assert!(statically_throttled <= campaigns.campaigns.len(), "Seller '{}' throttles a campaign that doesn't exist", seller.seller_name());
+            if let SellerControl::THROTTLE { campaign_id } = seller.seller_control() {
+                assert!(campaign_id < campaigns.campaigns.len(), "Seller '{}' converges by throttling campaign {}, which doesn't exist", seller.seller_name(), campaign_id);
+            }
+        }
+    }
+
+    /// Assemble the marketplace from finalized campaigns, sellers and their impressions
+    fn with_impressions(campaigns: Campaigns, sellers: Sellers, impressions: Rc<Impressions>, simulation_type: SimulationType) -> Self {
+        // Campaigns can only be targeted at segments that exist
+        for campaign_id in 0..campaigns.campaigns.len() {
+            if let Some(segments) = campaigns.segment_targeting(campaign_id) {
+                assert!(segments.iter().all(|&segment| segment < impressions.num_segments),
+                    "Campaign {} is targeted at segments {:?}, but there are only {} segments (see ImpressionsParam::with_segments)", campaign_id, segments, impressions.num_segments);
+            }
+        }
+        let eligible_impressions = (0..campaigns.campaigns.len())
+            .map(|campaign_id| campaigns.frequency_cap(campaign_id).map(|frequency_cap| Self::frequency_capped_supply(&impressions, &campaigns, campaign_id, frequency_cap)))
+            .collect();
         Self {
             campaigns,
             sellers,
             impressions,
             simulation_type,
+            seller_simulation_types: Vec::new(),
+            eligible_impressions,
+        }
+    }
+
+    /// Set the auction mechanism of one seller's impressions, overriding the marketplace's simulation_type
+    /// Second price is charged by SellerType::SECOND_PRICE sellers, so their Standard auctions are second price auctions
+    /// 
+    /// # Arguments
+    /// * `seller_id` - ID of the seller
+    /// * `simulation_type` - Auction mechanism of the seller's impressions
+    pub fn with_seller_simulation_type(mut self, seller_id: usize, simulation_type: SimulationType) -> Self {
+        assert!(seller_id < self.sellers.sellers.len(), "No seller with ID {}", seller_id);
+        if self.seller_simulation_types.len() <= seller_id {
+            self.seller_simulation_types.resize(seller_id + 1, None);
+        }
+        self.seller_simulation_types[seller_id] = Some(simulation_type);
+        self
+    }
+
+    /// Get the auction mechanism of a seller's impressions
+    pub fn seller_simulation_type(&self, seller_id: usize) -> &SimulationType {
+        self.seller_simulation_types.get(seller_id).and_then(|simulation_type| simulation_type.as_ref()).unwrap_or(&self.simulation_type)
+    }
+
+    /// Number of impressions a campaign with the given frequency cap can win: per user and day,
+    /// the user's impressions on that day the campaign is eligible for, up to the cap
+    fn frequency_capped_supply(impressions: &Impressions, campaigns: &Campaigns, campaign_id: usize, frequency_cap: usize) -> f64 {
+        let mut impressions_per_user_day: HashMap<(usize, usize), usize> = HashMap::new();
+        for impression in impressions.impressions.iter().filter(|impression| campaigns.is_eligible(campaign_id, impression.segment)) {
+            let user_id = impression.user_id.expect("Frequency caps need users, see ImpressionsParam::with_users");
+            *impressions_per_user_day.entry((user_id, impression.day())).or_insert(0) += 1;
         }
+        impressions_per_user_day.values().map(|&count| count.min(frequency_cap) as f64).sum()
     }
     
     /// Print initialization information about the marketplace
@@ -64,38 +184,538 @@ impl Marketplace {
 }
 
 /// Container for auction results
-/// Note: SimulationRun results are matched to Impressions by index in the vectors
+/// Note: SimulationRun results are matched to Impressions by order: results holds the results of impressions
+/// auctioned with Standard auctions and results_fractional of those auctioned with fractional ones (see
+/// Marketplace::seller_simulation_type), each in the order of the impressions
 pub struct SimulationRun {
     pub results: Vec<AuctionResult>,
     pub results_fractional: Vec<FractionalAuctionResult>,
+    /// Number of bids per campaign that were capped at the campaign's max_bid_cpm (indexed by campaign_id)
+    pub capped_bids: Vec<usize>,
+    /// Number of auctions per campaign its bid was dropped from by its frequency cap (indexed by campaign_id)
+    pub frequency_capped_auctions: Vec<usize>,
+    /// Number of auctions per campaign its bid was dropped from as it had exhausted its budget cap (indexed by campaign_id)
+    pub budget_capped_auctions: Vec<usize>,
+    /// Timestamp of the impression whose charge exhausted each campaign's budget cap (indexed by campaign_id,
+    /// None = not exhausted or no cap)
+    pub budget_exhausted_at: Vec<Option<f64>>,
+    /// Number of auctions per campaign it submitted a bid to (indexed by campaign_id)
+    pub auctions_participated: Vec<usize>,
+    /// Auctions per campaign it submitted a bid to, weighted by Impression::weight (indexed by campaign_id)
+    pub weighted_auctions_participated: Vec<f64>,
+    /// Bid observations per campaign (indexed by campaign_id), only filled for campaigns that want bid history
+    pub bid_history: Vec<Vec<BidObservation>>,
+    /// Number of bids per campaign served from a seller's cache instead of fresh (indexed by campaign_id)
+    pub stale_bids: Vec<usize>,
+    /// Fresh bids per campaign on every impression (indexed by campaign_id, then impression index), kept for
+    /// sellers caching bids to serve in the next iteration (empty when no seller caches bids)
+    pub cached_bids: Vec<Vec<Option<f64>>>,
+    /// Number of bids per seller received from each campaign after throttling (indexed by seller_id, then campaign_id)
+    pub seller_campaign_bids: Vec<Vec<usize>>,
+    /// Number of bids per seller it throttled of each campaign (indexed by seller_id, then campaign_id)
+    pub seller_throttled_bids: Vec<Vec<usize>>,
+    /// Number of auctions per seller won by the competition (indexed by seller_id)
+    pub seller_competitor_wins: Vec<usize>,
+}
+
+/// Online updates of campaign controller states within a simulation run, mimicking real-time pacing systems
+/// that adjust pacing while traffic comes in instead of only between runs
+pub struct OnlineUpdates<'a> {
+    /// Number of impressions auctioned between updates
+    pub batch_size: usize,
+    /// Update of the campaign controller states campaigns bid with, given the run so far
+    pub update: &'a mut dyn FnMut(&SimulationRun, &mut CampaignControllerStates, &mut Logger),
+}
+
+/// Outcome of a single bid, as observed by the bidding campaign
+#[derive(Debug, Clone)]
+pub struct BidObservation {
+    pub seller_id: usize,
+    pub bid_cpm: f64,
+    pub floor_cpm: f64,
+    pub base_impression_value: f64,
+    /// Clearing price as seen by the campaign (loss notification): the minimum CPM that would have
+    /// won, i.e. the highest of the floor, the competing bid and the bids of other campaigns
+    pub clearing_price_cpm: f64,
+    /// Whether the campaign won the impression (or a fraction of it in fractional auctions)
+    pub won: bool,
+}
+
+impl BidObservation {
+    /// Observe a campaign's bid on an impression, given all campaign bids of the auction as (campaign_id, bid_cpm)
+    fn new(impression: &Impression, campaign_id: usize, bid_cpm: f64, bids: &[(usize, f64)], won: bool) -> Self {
+        let mut clearing_price_cpm = impression.floor_cpm;
+        if let Some(competition) = &impression.competition {
+            clearing_price_cpm = clearing_price_cpm.max(competition.bid_cpm);
+        }
+        for &(other_campaign_id, other_bid_cpm) in bids {
+            if other_campaign_id != campaign_id {
+                clearing_price_cpm = clearing_price_cpm.max(other_bid_cpm);
+            }
+        }
+        Self {
+            seller_id: impression.seller_id,
+            bid_cpm,
+            floor_cpm: impression.floor_cpm,
+            base_impression_value: impression.base_impression_value,
+            clearing_price_cpm,
+            won,
+        }
+    }
 }
 
 impl SimulationRun {
     /// Create a new SimulationRun container and run auctions for all impressions
-    pub fn new(marketplace: &Marketplace, campaign_controller_states: &CampaignControllerStates, seller_controller_states: &SellerControllerStates, logger: &mut Logger) -> Self {
-        let mut results = Vec::with_capacity(marketplace.impressions.impressions.len());
-        let mut results_fractional = Vec::with_capacity(marketplace.impressions.impressions.len());
+    /// Every auction result is dispatched to the auction result hooks, tagged with `iteration`
+    /// Sellers caching bids serve stale bids from `previous_bids` (cached_bids of the previous iteration, empty in the first one)
+    /// With `online_updates` campaigns bid with a copy of their controller states that is updated within the run
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(marketplace: &Marketplace, campaign_controller_states: &CampaignControllerStates, seller_controller_states: &SellerControllerStates, previous_bids: &[Vec<Option<f64>>], hooks: &mut SimulationHooks, mut online_updates: Option<OnlineUpdates>, iteration: usize, logger: &mut Logger) -> Self {
+        // Bid caps of campaigns, used to count capped bids
+        let max_bid_cpms: Vec<Option<f64>> = marketplace.campaigns.campaigns.iter().map(|c| c.max_bid_cpm()).collect();
+        
+        // Frequency caps of campaigns, with the wins of capped campaigns per (user, day) so far
+        let frequency_caps: Vec<Option<usize>> = (0..marketplace.campaigns.campaigns.len()).map(|campaign_id| marketplace.campaigns.frequency_cap(campaign_id)).collect();
+        let any_frequency_cap = frequency_caps.iter().any(|cap| cap.is_some());
+        let mut user_day_wins: Vec<HashMap<(usize, usize), f64>> = vec![HashMap::new(); marketplace.campaigns.campaigns.len()];
+        
+        // Campaigns targeted at segments only bid on impressions of those segments
+        let any_segment_targeting = (0..marketplace.campaigns.campaigns.len()).any(|campaign_id| marketplace.campaigns.segment_targeting(campaign_id).is_some());
+        let any_active_days = (0..marketplace.campaigns.campaigns.len()).any(|campaign_id| marketplace.campaigns.active_days(campaign_id).is_some());
+        let any_experiment_arms = (0..marketplace.campaigns.campaigns.len()).any(|campaign_id| marketplace.campaigns.experiment_arm(campaign_id).is_some());
+        let any_paused = (0..marketplace.campaigns.campaigns.len()).any(|campaign_id| marketplace.campaigns.is_paused(campaign_id));
+        
+        // Budget caps of campaigns, with each capped campaign's spend so far in the run
+        let budget_caps: Vec<Option<f64>> = (0..marketplace.campaigns.campaigns.len()).map(|campaign_id| marketplace.campaigns.budget_cap(campaign_id)).collect();
+        let any_budget_cap = budget_caps.iter().any(|cap| cap.is_some());
+        let mut campaign_spend = vec![0.0; marketplace.campaigns.campaigns.len()];
+        
+        // Campaigns learning from their own bids get their bid outcomes recorded
+        let wants_bid_history: Vec<bool> = marketplace.campaigns.campaigns.iter().map(|c| c.wants_bid_history()).collect();
         
-        // Create campaign_converge slices for all campaigns (once, outside the loop)
-        let campaign_converges: Vec<Vec<&dyn crate::controllers::ControllerStateTrait>> = campaign_controller_states.campaign_controller_states.iter()
-            .map(|campaign_states_vec| campaign_states_vec.iter().map(|cs| cs.as_ref()).collect())
+        // Bids are only collected when some hook wants to see them, when they need to be checked against caps
+        // or when some campaign wants its bid history
+        let collect_bids = hooks.wants_auction_results() || max_bid_cpms.iter().any(|cap| cap.is_some()) || wants_bid_history.iter().any(|wants| *wants);
+        let mut bids: Vec<(usize, f64)> = Vec::new();
+        
+        // Bids are computed per campaign for batches of impressions, to cut per-call overhead
+        // Campaigns whose intra-run updates change their bids (CampaignTrait::updates_within_run) bid per impression
+        // instead, right before its auction, so every update takes effect from the next auction
+        let updates_within_run: Vec<bool> = marketplace.campaigns.campaigns.iter().map(|c| c.updates_within_run()).collect();
+        let any_updates_within_run = updates_within_run.iter().any(|updates| *updates);
+        let num_campaigns = marketplace.campaigns.campaigns.len();
+        let mut seller_control_factors = vec![0.0; BID_BATCH_SIZE];
+        let mut values_to_campaign = vec![0.0; BID_BATCH_SIZE];
+        let mut batch_bids: Vec<Vec<Option<f64>>> = vec![vec![None; BID_BATCH_SIZE]; num_campaigns];
+        let mut campaign_bids: Vec<Option<f64>> = vec![None; num_campaigns];
+        
+        // Control variable of each seller (sellers use their first controller state), whether it boosts bids on
+        // the seller, and whether the seller sets the floors bidders see
+        let seller_control_variables: Vec<f64> = marketplace.sellers.sellers.iter().enumerate()
+            .map(|(seller_id, seller)| seller.get_control_variable(seller_controller_states.seller_controller_states[seller_id][0].as_ref()))
+            .collect();
+        let seller_boosts_bids: Vec<bool> = marketplace.sellers.sellers.iter().map(|seller| seller.seller_control() == SellerControl::BOOST).collect();
+        let seller_sets_floors: Vec<bool> = marketplace.sellers.sellers.iter().map(|seller| seller.sets_buyer_floors()).collect();
+        let any_seller_sets_floors = seller_sets_floors.iter().any(|sets_floors| *sets_floors);
+        // Factor of each seller's reactive competitor on the competing bids (1.0 = no reactive competitor)
+        let competitor_bid_factors: Vec<f64> = marketplace.sellers.sellers.iter().enumerate()
+            .map(|(seller_id, seller)| seller.competitor_bid_factor(&seller_controller_states.seller_controller_states[seller_id]))
+            .collect();
+        let any_reactive_competitor = competitor_bid_factors.iter().any(|factor| *factor != 1.0);
+        let seller_has_boost_schedule: Vec<bool> = (0..marketplace.sellers.sellers.len()).map(|seller_id| marketplace.sellers.boost_schedules.get(seller_id).is_some_and(|schedule| schedule.is_some())).collect();
+        // Impressions each seller sold so far in the run, for volume dependent supply costs
+        let mut seller_volumes = vec![0; marketplace.sellers.sellers.len()];
+        let mut buyer_floor_batch: Vec<Impression> = Vec::new();
+        
+        // Impressions offered through several supply paths that were sold through one of them (by supply_id)
+        let mut sold_supply_ids: HashSet<usize> = HashSet::new();
+        
+        // Sellers caching bids serve some bids from the previous iteration, so this iteration's fresh bids are kept
+        let stale_bid_probabilities: Vec<Option<f64>> = (0..marketplace.sellers.sellers.len()).map(|seller_id| marketplace.sellers.stale_bid_probability(seller_id)).collect();
+        let any_seller_caches_bids = stale_bid_probabilities.iter().any(|probability| probability.is_some());
+        // Stale bids are drawn anew in every iteration
+        let mut rng_stale_bids = StdRng::seed_from_u64(get_seed(15151 + iteration as u64));
+        
+        // Probability of each seller dropping each campaign's bid, set statically or by the seller's controller
+        // through the share of the campaign's bids it passes through
+        let throttle_probabilities: Vec<Vec<f64>> = marketplace.sellers.sellers.iter().enumerate()
+            .map(|(seller_id, seller)| (0..num_campaigns)
+                .map(|campaign_id| match seller.seller_control() {
+                    SellerControl::THROTTLE { campaign_id: throttled_campaign_id } if throttled_campaign_id == campaign_id => 1.0 - seller_control_variables[seller_id],
+                    _ => marketplace.sellers.throttle_probability(seller_id, campaign_id),
+                })
+                .collect())
             .collect();
+        let any_seller_throttles = throttle_probabilities.iter().flatten().any(|probability| *probability > 0.0);
         
-        for impression in &marketplace.impressions.impressions {
-            // Get the seller and seller_converge for this impression
-            let seller = marketplace.sellers.sellers[impression.seller_id].as_ref();
-            // For sellers, we typically use the first controller state
-            let seller_converge = seller_controller_states.seller_controller_states[seller.seller_id()][0].as_ref();
+        // Adapter fees of sellers, deducted from the bids their auctions compare
+        let adapter_fees: Vec<Option<f64>> = (0..marketplace.sellers.sellers.len()).map(|seller_id| marketplace.sellers.adapter_fee(seller_id)).collect();
+        
+        // Results and counters are accumulated in the run as auctions happen, so that online updates see the run so far
+        let mut run = Self {
+            results: Vec::with_capacity(marketplace.impressions.impressions.len()),
+            results_fractional: Vec::with_capacity(marketplace.impressions.impressions.len()),
+            capped_bids: vec![0; num_campaigns],
+            frequency_capped_auctions: vec![0; num_campaigns],
+            budget_capped_auctions: vec![0; num_campaigns],
+            budget_exhausted_at: vec![None; num_campaigns],
+            // Auctions each campaign submitted a bid to, the base of its win rate
+            auctions_participated: vec![0; num_campaigns],
+            weighted_auctions_participated: vec![0.0; num_campaigns],
+            bid_history: vec![Vec::new(); num_campaigns],
+            stale_bids: vec![0; num_campaigns],
+            cached_bids: if any_seller_caches_bids {
+                vec![Vec::with_capacity(marketplace.impressions.impressions.len()); num_campaigns]
+            } else {
+                Vec::new()
+            },
+            seller_campaign_bids: vec![vec![0; num_campaigns]; marketplace.sellers.sellers.len()],
+            seller_throttled_bids: vec![vec![0; num_campaigns]; marketplace.sellers.sellers.len()],
+            seller_competitor_wins: vec![0; marketplace.sellers.sellers.len()],
+        };
+        // Online updates change a copy of the campaign controller states, starting from the given ones in every run
+        let mut online_campaign_controller_states = online_updates.as_ref().map(|_| campaign_controller_states.clone());
+        // Impressions auctioned since the last online update
+        let mut impressions_since_update = 0;
+        
+        // Bid batches end at every online update, so that it takes effect from the next impression
+        let mut next_batch_start = 0;
+        while next_batch_start < marketplace.impressions.impressions.len() {
+            let batch_start = next_batch_start;
+            let mut batch_len = BID_BATCH_SIZE.min(marketplace.impressions.impressions.len() - batch_start);
+            if let Some(online_updates) = online_updates.as_ref() {
+                batch_len = batch_len.min(online_updates.batch_size - impressions_since_update);
+            }
+            next_batch_start += batch_len;
+            let batch = &marketplace.impressions.impressions[batch_start..next_batch_start];
             
-            // Check simulation type and call appropriate auction method
-            match marketplace.simulation_type {
-                SimulationType::Standard => {
-                    let result = impression.run_auction(&marketplace.campaigns, &campaign_converges, seller, seller_converge, logger);
-                    results.push(result);
+            // Controller state slices of all campaigns, taken for every batch as online updates change the states
+            let campaign_converges: Vec<Vec<&dyn crate::controllers::ControllerStateTrait>> = online_campaign_controller_states.as_ref().unwrap_or(campaign_controller_states).campaign_controller_states.iter()
+                .map(|campaign_states_vec| campaign_states_vec.iter().map(|cs| cs.as_ref()).collect())
+                .collect();
+            
+            // Bidders and auctions see the floors set by the sellers and the competing bids of reactive competitors,
+            // so the batch is copied with those floors and competing bids
+            let batch = if any_seller_sets_floors || any_reactive_competitor {
+                buyer_floor_batch.clear();
+                buyer_floor_batch.extend(batch.iter().map(|impression| {
+                    let mut impression = impression.clone();
+                    if seller_sets_floors[impression.seller_id] {
+                        let seller = marketplace.sellers.sellers[impression.seller_id].as_ref();
+                        impression.floor_cpm = seller.buyer_floor_cpm(impression.floor_cpm, seller_control_variables[impression.seller_id]);
+                    }
+                    let competitor_bid_factor = competitor_bid_factors[impression.seller_id];
+                    if competitor_bid_factor != 1.0 {
+                        impression.competition = impression.competition.map(|competition| competition.scaled(competitor_bid_factor));
+                    }
+                    impression
+                }));
+                &buyer_floor_batch[..]
+            } else {
+                batch
+            };
+            
+            // Seller control factor of each impression (1.0 for sellers whose control variable doesn't boost bids),
+            // times the boost the seller's schedule sets for the impression's time of day
+            for (seller_control_factor, impression) in seller_control_factors.iter_mut().zip(batch) {
+                *seller_control_factor = if seller_boosts_bids[impression.seller_id] {
+                    seller_control_variables[impression.seller_id]
+                } else {
+                    1.0
+                };
+                if seller_has_boost_schedule[impression.seller_id] {
+                    *seller_control_factor *= marketplace.sellers.scheduled_boost(impression.seller_id, impression.time_of_day());
+                }
+            }
+            
+            for campaign in marketplace.campaigns.campaigns.iter().filter(|campaign| !updates_within_run[campaign.campaign_id()]) {
+                let campaign_id = campaign.campaign_id();
+                // Resolve value_to_campaign using campaign's group ID and its share of the group value
+                let group_id = marketplace.campaigns.campaign_to_value_group_mapping[campaign_id];
+                let value_share = marketplace.campaigns.campaign_value_share[campaign_id];
+                for (value_to_campaign, impression) in values_to_campaign.iter_mut().zip(batch) {
+                    *value_to_campaign = impression.value_to_campaign_group[group_id] * value_share;
+                }
+                campaign.get_bids(batch, &campaign_converges[campaign_id], &seller_control_factors[..batch.len()], &values_to_campaign[..batch.len()], &mut batch_bids[campaign_id][..batch.len()], logger);
+            }
+            
+            for (index_in_batch, impression) in batch.iter().enumerate() {
+                let impression_index = batch_start + index_in_batch;
+                let seller = marketplace.sellers.sellers[impression.seller_id].as_ref();
+                
+                if any_updates_within_run {
+                    for campaign in marketplace.campaigns.campaigns.iter().filter(|campaign| updates_within_run[campaign.campaign_id()]) {
+                        let campaign_id = campaign.campaign_id();
+                        let value_to_campaign = impression.value_to_campaign_group[marketplace.campaigns.campaign_to_value_group_mapping[campaign_id]] * marketplace.campaigns.campaign_value_share[campaign_id];
+                        batch_bids[campaign_id][index_in_batch] = campaign.get_bid(impression, &campaign_converges[campaign_id], seller_control_factors[index_in_batch], value_to_campaign, logger);
+                    }
+                }
+                
+                // Once an impression is sold through one supply path, its other paths are withdrawn without an auction
+                if impression.supply_id.is_some_and(|supply_id| sold_supply_ids.contains(&supply_id)) {
+                    let supply_cost = seller.get_unsold_supply_cost_cpm() / 1000.0;
+                    match marketplace.seller_simulation_type(impression.seller_id) {
+                        SimulationType::Standard => run.results.push(AuctionResult { winner: Winner::WITHDRAWN, supply_cost }),
+                        SimulationType::FractionalInternalAuction { .. } => run.results_fractional.push(FractionalAuctionResult { winner: FractionalWinners::WITHDRAWN, supply_cost }),
+                    }
+                    continue;
+                }
+                
+                for (campaign_bid, campaign_batch_bids) in campaign_bids.iter_mut().zip(&batch_bids) {
+                    *campaign_bid = campaign_batch_bids[index_in_batch];
+                }
+                
+                // Sellers caching bids replace some fresh bids with the campaign's bid on the impression in the previous iteration
+                if let Some(stale_bid_probability) = stale_bid_probabilities[impression.seller_id].filter(|_| !previous_bids.is_empty()) {
+                    for (campaign_id, campaign_bid) in campaign_bids.iter_mut().enumerate() {
+                        if rng_stale_bids.gen_bool(stale_bid_probability) {
+                            *campaign_bid = previous_bids[campaign_id][impression_index];
+                            run.stale_bids[campaign_id] += 1;
+                        }
+                    }
+                }
+                
+                // Drop bids of campaigns not eligible for the impression's segment
+                if any_segment_targeting {
+                    for (campaign_id, campaign_bid) in campaign_bids.iter_mut().enumerate() {
+                        if !marketplace.campaigns.is_eligible(campaign_id, impression.segment) {
+                            *campaign_bid = None;
+                        }
+                    }
+                }
+                
+                // Drop bids of campaigns not active on the impression's day
+                if any_active_days {
+                    let day = impression.day();
+                    for (campaign_id, campaign_bid) in campaign_bids.iter_mut().enumerate() {
+                        if !marketplace.campaigns.is_active(campaign_id, day) {
+                            *campaign_bid = None;
+                        }
+                    }
+                }
+                
+                // Drop bids of campaigns in an experiment arm other than the impression's bucket
+                if any_experiment_arms {
+                    let buckets: Vec<ExperimentArm> = marketplace.campaigns.experiments.iter().map(|experiment| experiment.bucket(impression).0).collect();
+                    for (campaign_id, campaign_bid) in campaign_bids.iter_mut().enumerate() {
+                        if let Some((experiment_id, arm)) = marketplace.campaigns.experiment_arm(campaign_id) {
+                            if buckets[experiment_id] != arm {
+                                *campaign_bid = None;
+                            }
+                        }
+                    }
+                }
+                
+                // Drop bids of paused campaigns
+                if any_paused {
+                    for (campaign_id, campaign_bid) in campaign_bids.iter_mut().enumerate() {
+                        if marketplace.campaigns.is_paused(campaign_id) {
+                            *campaign_bid = None;
+                        }
+                    }
+                }
+                
+                // Drop bids of campaigns that exhausted their budget cap earlier in the run
+                if any_budget_cap {
+                    for (campaign_id, campaign_bid) in campaign_bids.iter_mut().enumerate() {
+                        if campaign_bid.is_some() && run.budget_exhausted_at[campaign_id].is_some() {
+                            *campaign_bid = None;
+                            run.budget_capped_auctions[campaign_id] += 1;
+                        }
+                    }
+                }
+                
+                // Throttling sellers drop campaign bids, drawn per impression and campaign so that the throttled bids
+                // only change with the throttle probability
+                if any_seller_throttles {
+                    for (campaign_id, campaign_bid) in campaign_bids.iter_mut().enumerate() {
+                        let throttle_probability = throttle_probabilities[impression.seller_id][campaign_id];
+                        if campaign_bid.is_some() && throttle_probability > 0.0 {
+                            let seed = impression.seed(16161 ^ (campaign_id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+                            let draw = (seed >> 11) as f64 / (1u64 << 53) as f64;
+                            if draw < throttle_probability {
+                                *campaign_bid = None;
+                                run.seller_throttled_bids[impression.seller_id][campaign_id] += 1;
+                            }
+                        }
+                    }
+                }
+                for (campaign_id, campaign_bid) in campaign_bids.iter().enumerate() {
+                    if campaign_bid.is_some() {
+                        run.seller_campaign_bids[impression.seller_id][campaign_id] += 1;
+                    }
+                }
+                
+                // Drop bids of campaigns that reached their frequency cap on the impression's user and day
+                let user_day = if any_frequency_cap {
+                    let user_day = (impression.user_id.expect("Frequency caps need users, see ImpressionsParam::with_users"), impression.day());
+                    for (campaign_id, campaign_bid) in campaign_bids.iter_mut().enumerate() {
+                        if let Some(frequency_cap) = frequency_caps[campaign_id] {
+                            let wins = user_day_wins[campaign_id].get(&user_day).copied().unwrap_or(0.0);
+                            if campaign_bid.is_some() && wins >= frequency_cap as f64 {
+                                *campaign_bid = None;
+                                run.frequency_capped_auctions[campaign_id] += 1;
+                            }
+                        }
+                    }
+                    Some(user_day)
+                } else {
+                    None
+                };
+                
+                for (campaign_id, campaign_bid) in campaign_bids.iter().enumerate() {
+                    if campaign_bid.is_some() {
+                        run.auctions_participated[campaign_id] += 1;
+                        run.weighted_auctions_participated[campaign_id] += impression.weight;
+                    }
+                }
+            
+                // Sellers with an adapter fee auction the bids net of the fee, the results and collected bids are
+                // grossed up again below so that campaigns and hooks see what the campaigns bid and pay
+                let adapter_fee = adapter_fees[impression.seller_id];
+                if let Some(adapter_fee) = adapter_fee {
+                    for campaign_bid in campaign_bids.iter_mut().flatten() {
+                        *campaign_bid *= 1.0 - adapter_fee;
+                    }
+                }
+                
+                bids.clear();
+                let bids_sink = if collect_bids { Some(&mut bids) } else { None };
+            
+                // Check simulation type of the impression's seller and call appropriate auction method
+                match *marketplace.seller_simulation_type(impression.seller_id) {
+                    SimulationType::Standard => {
+                        let mut result = impression.run_auction(&campaign_bids, seller, seller_control_variables[impression.seller_id], seller_volumes[impression.seller_id], bids_sink, logger);
+                        if let Some(adapter_fee) = adapter_fee {
+                            result.gross_up_adapter_fee(adapter_fee);
+                            for (_, bid) in bids.iter_mut() {
+                                *bid /= 1.0 - adapter_fee;
+                            }
+                        }
+                        if let Winner::Campaign { .. } = &result.winner {
+                            seller_volumes[impression.seller_id] += 1;
+                            if let Some(supply_id) = impression.supply_id {
+                                sold_supply_ids.insert(supply_id);
+                            }
+                        }
+                        if let (Some(user_day), Winner::Campaign { campaign_id, .. }) = (user_day, &result.winner) {
+                            if frequency_caps[*campaign_id].is_some() {
+                                *user_day_wins[*campaign_id].entry(user_day).or_insert(0.0) += 1.0;
+                            }
+                        }
+                        for campaign in &marketplace.campaigns.campaigns {
+                            let campaign_id = campaign.campaign_id();
+                            let buyer_charge = match result.winner {
+                                Winner::Campaign { campaign_id: winner_id, buyer_charge, .. } if winner_id == campaign_id => buyer_charge * impression.weight,
+                                _ => 0.0,
+                            };
+                            campaign.intra_run_update(impression, &campaign_converges[campaign_id], buyer_charge);
+                            if let Some(budget_cap) = budget_caps[campaign_id] {
+                                campaign_spend[campaign_id] += buyer_charge;
+                                if campaign_spend[campaign_id] >= budget_cap && run.budget_exhausted_at[campaign_id].is_none() {
+                                    run.budget_exhausted_at[campaign_id] = Some(impression.timestamp);
+                                }
+                            }
+                        }
+                        for &(campaign_id, bid) in &bids {
+                            if wants_bid_history[campaign_id] {
+                                let won = matches!(result.winner, Winner::Campaign { campaign_id: winner_id, .. } if winner_id == campaign_id);
+                                run.bid_history[campaign_id].push(BidObservation::new(impression, campaign_id, bid, &bids, won));
+                            }
+                        }
+                        if collect_bids {
+                            hooks.auction_result(&AuctionEvent {
+                                iteration,
+                                impression,
+                                bids: &bids,
+                                outcome: AuctionOutcome::Standard(&result),
+                            }, logger);
+                        }
+                        // The competition buys impressions no campaign won when its bid clears the floor
+                        if matches!(result.winner, Winner::LOST | Winner::NO_DEMAND) && impression.competition.as_ref().is_some_and(|competition| competition.bid_cpm >= impression.floor_cpm) {
+                            run.seller_competitor_wins[impression.seller_id] += 1;
+                        }
+                        run.results.push(result);
+                    }
+                    SimulationType::FractionalInternalAuction { softmax_temperature } => {
+                        let mut result_fractional = impression.run_fractional_auction(&campaign_bids, seller, softmax_temperature, seller_control_variables[impression.seller_id], seller_volumes[impression.seller_id], bids_sink, logger);
+                        if let Some(adapter_fee) = adapter_fee {
+                            result_fractional.gross_up_adapter_fee(adapter_fee);
+                            for (_, bid) in bids.iter_mut() {
+                                *bid /= 1.0 - adapter_fee;
+                            }
+                        }
+                        if let FractionalWinners::Campaigns { .. } = &result_fractional.winner {
+                            seller_volumes[impression.seller_id] += 1;
+                            if let Some(supply_id) = impression.supply_id {
+                                sold_supply_ids.insert(supply_id);
+                            }
+                        }
+                        if let (Some(user_day), FractionalWinners::Campaigns { winners }) = (user_day, &result_fractional.winner) {
+                            for winner in winners.iter().filter(|winner| frequency_caps[winner.campaign_id].is_some()) {
+                                *user_day_wins[winner.campaign_id].entry(user_day).or_insert(0.0) += winner.win_fraction;
+                            }
+                        }
+                        for campaign in &marketplace.campaigns.campaigns {
+                            let campaign_id = campaign.campaign_id();
+                            let buyer_charge = match &result_fractional.winner {
+                                FractionalWinners::Campaigns { winners } => winners.iter()
+                                    .filter(|w| w.campaign_id == campaign_id)
+                                    .map(|w| w.buyer_charge * w.win_fraction * impression.weight)
+                                    .sum(),
+                                _ => 0.0,
+                            };
+                            campaign.intra_run_update(impression, &campaign_converges[campaign_id], buyer_charge);
+                            if let Some(budget_cap) = budget_caps[campaign_id] {
+                                campaign_spend[campaign_id] += buyer_charge;
+                                if campaign_spend[campaign_id] >= budget_cap && run.budget_exhausted_at[campaign_id].is_none() {
+                                    run.budget_exhausted_at[campaign_id] = Some(impression.timestamp);
+                                }
+                            }
+                        }
+                        for &(campaign_id, bid) in &bids {
+                            if wants_bid_history[campaign_id] {
+                                let won = matches!(&result_fractional.winner, FractionalWinners::Campaigns { winners } if winners.iter().any(|w| w.campaign_id == campaign_id));
+                                run.bid_history[campaign_id].push(BidObservation::new(impression, campaign_id, bid, &bids, won));
+                            }
+                        }
+                        if collect_bids {
+                            hooks.auction_result(&AuctionEvent {
+                                iteration,
+                                impression,
+                                bids: &bids,
+                                outcome: AuctionOutcome::Fractional(&result_fractional),
+                            }, logger);
+                        }
+                        if matches!(result_fractional.winner, FractionalWinners::LOST | FractionalWinners::NO_DEMAND) && impression.competition.as_ref().is_some_and(|competition| competition.bid_cpm >= impression.floor_cpm) {
+                            run.seller_competitor_wins[impression.seller_id] += 1;
+                        }
+                        run.results_fractional.push(result_fractional);
+                    }
                 }
-                SimulationType::FractionalInternalAuction { softmax_temperature } => {
-                    let result_fractional = impression.run_fractional_auction(&marketplace.campaigns, &campaign_converges, seller, seller_converge, softmax_temperature, logger);
-                    results_fractional.push(result_fractional);
+            
+                // Bids are capped in get_bid, so a bid at the cap is counted as capped
+                for &(campaign_id, bid) in &bids {
+                    if let Some(max_bid_cpm) = max_bid_cpms[campaign_id] {
+                        if bid >= max_bid_cpm {
+                            run.capped_bids[campaign_id] += 1;
+                        }
+                    }
+                }
+            }
+            
+            if any_seller_caches_bids {
+                for (campaign_cached_bids, campaign_batch_bids) in run.cached_bids.iter_mut().zip(&batch_bids) {
+                    campaign_cached_bids.extend_from_slice(&campaign_batch_bids[..batch.len()]);
+                }
+            }
+            
+            // Online updates change the campaign controller states every batch_size impressions, taking effect from the next impression
+            if let (Some(online_updates), Some(online_campaign_controller_states)) = (online_updates.as_mut(), online_campaign_controller_states.as_mut()) {
+                impressions_since_update += batch.len();
+                if impressions_since_update >= online_updates.batch_size && run.impressions_auctioned() < marketplace.impressions.impressions.len() {
+                    impressions_since_update = 0;
+                    (online_updates.update)(&run, online_campaign_controller_states, logger);
                 }
             }
         }
@@ -103,11 +723,17 @@ impl SimulationRun {
         // Increment global counter for completed simulation run
         TOTAL_SIMULATION_RUNS.fetch_add(1, Ordering::Relaxed);
         
-        Self { results, results_fractional }
+        run
+    }
+    
+    /// Number of impressions auctioned so far (all impressions once the run is complete), counting withdrawn ones
+    pub fn impressions_auctioned(&self) -> usize {
+        self.results.len() + self.results_fractional.len()
     }
 }
 
 /// Statistics for a single campaign
+#[derive(Clone)]
 pub struct CampaignStat {
     /// Number of impressions obtained (f64 to support fractional impressions in FractionalInternalAuction)
     pub impressions_obtained: f64,
@@ -115,21 +741,253 @@ pub struct CampaignStat {
     pub total_virtual_cost: f64,
     pub total_buyer_charge: f64,
     pub total_value: f64,
+    /// Value weighted by impression viewability (equals total_value when viewability is not modeled)
+    pub total_viewable_value: f64,
+    /// Sum of viewability of won impressions (average viewability is total_viewability / impressions_obtained)
+    pub total_viewability: f64,
+    /// Sum of measured viewability of won impressions, the average viewability campaigns observe
+    /// (equals total_viewability unless viewability is measured with error)
+    pub total_measured_viewability: f64,
+    /// Number of won impressions that converted (fractional in FractionalInternalAuction)
+    pub total_conversions: f64,
+    /// Value of the won impressions' conversions (ROAS is total_conversion_value / total_buyer_charge)
+    pub total_conversion_value: f64,
+    /// Number of bids capped at the campaign's max_bid_cpm
+    pub capped_bids: usize,
+    /// Number of bids served from a seller's cache, i.e. the campaign's bid of the previous iteration
+    pub stale_bids: usize,
+    /// Number of auctions the campaign's bid was dropped from by its frequency cap
+    pub frequency_capped_auctions: usize,
+    /// Number of auctions the campaign's bid was dropped from as it had exhausted its budget cap
+    pub budget_capped_auctions: usize,
+    /// Timestamp (hours) at which the campaign exhausted its budget cap (None = not exhausted or no cap)
+    pub budget_exhausted_at: Option<f64>,
+    /// Number of impressions the campaign can win under its frequency cap (None = no cap)
+    pub eligible_impressions: Option<f64>,
+    /// Number of auctions the campaign submitted a bid to (win rate is impressions_obtained / auctions_participated)
+    pub auctions_participated: usize,
+    /// Auctions the campaign submitted a bid to weighted by Impression::weight (equal to auctions_participated
+    /// unless impressions are oversampled), the base of its win rate
+    pub weighted_auctions_participated: f64,
+    /// Number of unique users the campaign won impressions of (0 when users are not modeled)
+    pub reach: usize,
+    /// Penalty of the campaign's violated soft targets, in units of spend (None = no soft targets)
+    pub soft_target_penalty: Option<f64>,
+    /// Breakdown of the campaign's statistics per seller (indexed by seller_id)
+    pub seller_breakdown: Vec<CampaignSellerStat>,
+    /// Breakdown of the campaign's statistics per simulated day (indexed by day)
+    pub day_breakdown: Vec<CampaignDayStat>,
+    /// Breakdown of the campaign's statistics per hour of the day, over all days (indexed by hour)
+    pub hour_breakdown: Vec<CampaignHourStat>,
+    /// Outcomes of the campaign's bids (empty unless the campaign wants bid history)
+    pub bid_history: Vec<BidObservation>,
+}
+
+impl CampaignStat {
+    /// Average number of impressions won per user reached (0.0 without reach)
+    pub fn average_frequency(&self) -> f64 {
+        if self.reach == 0 {
+            return 0.0;
+        }
+        self.impressions_obtained / self.reach as f64
+    }
+
+    /// Add the statistics of another simulation run of the campaign, e.g. of a further period
+    /// Reach is summed, so users reached in both runs are counted twice
+    pub fn add(&mut self, other: &CampaignStat) {
+        self.impressions_obtained += other.impressions_obtained;
+        self.total_supply_cost += other.total_supply_cost;
+        self.total_virtual_cost += other.total_virtual_cost;
+        self.total_buyer_charge += other.total_buyer_charge;
+        self.total_value += other.total_value;
+        self.total_viewable_value += other.total_viewable_value;
+        self.total_viewability += other.total_viewability;
+        self.total_measured_viewability += other.total_measured_viewability;
+        self.total_conversions += other.total_conversions;
+        self.total_conversion_value += other.total_conversion_value;
+        self.capped_bids += other.capped_bids;
+        self.stale_bids += other.stale_bids;
+        self.frequency_capped_auctions += other.frequency_capped_auctions;
+        self.budget_capped_auctions += other.budget_capped_auctions;
+        self.budget_exhausted_at = self.budget_exhausted_at.or(other.budget_exhausted_at);
+        self.eligible_impressions = match (self.eligible_impressions, other.eligible_impressions) {
+            (Some(eligible), Some(other_eligible)) => Some(eligible + other_eligible),
+            (eligible, other_eligible) => eligible.or(other_eligible),
+        };
+        self.auctions_participated += other.auctions_participated;
+        self.weighted_auctions_participated += other.weighted_auctions_participated;
+        self.reach += other.reach;
+        self.soft_target_penalty = match (self.soft_target_penalty, other.soft_target_penalty) {
+            (Some(penalty), Some(other_penalty)) => Some(penalty + other_penalty),
+            (penalty, other_penalty) => penalty.or(other_penalty),
+        };
+        for (seller_stat, other_seller_stat) in self.seller_breakdown.iter_mut().zip(&other.seller_breakdown) {
+            seller_stat.impressions_obtained += other_seller_stat.impressions_obtained;
+            seller_stat.total_buyer_charge += other_seller_stat.total_buyer_charge;
+            seller_stat.total_value += other_seller_stat.total_value;
+        }
+        for (day_stat, other_day_stat) in self.day_breakdown.iter_mut().zip(&other.day_breakdown) {
+            day_stat.impressions_obtained += other_day_stat.impressions_obtained;
+            day_stat.total_buyer_charge += other_day_stat.total_buyer_charge;
+            day_stat.total_value += other_day_stat.total_value;
+        }
+        for (hour_stat, other_hour_stat) in self.hour_breakdown.iter_mut().zip(&other.hour_breakdown) {
+            hour_stat.impressions_obtained += other_hour_stat.impressions_obtained;
+            hour_stat.total_buyer_charge += other_hour_stat.total_buyer_charge;
+            hour_stat.total_value += other_hour_stat.total_value;
+        }
+        self.bid_history.extend(other.bid_history.iter().cloned());
+    }
+
+    /// Scale the statistics by a factor, e.g. to extrapolate one period's statistics over several periods
+    /// Counts are rounded to the nearest integer, averages (sums divided by counts) stay about the same
+    pub fn scale(&mut self, factor: f64) {
+        let scale_count = |count: usize| (count as f64 * factor).round() as usize;
+        self.impressions_obtained *= factor;
+        self.total_supply_cost *= factor;
+        self.total_virtual_cost *= factor;
+        self.total_buyer_charge *= factor;
+        self.total_value *= factor;
+        self.total_viewable_value *= factor;
+        self.total_viewability *= factor;
+        self.total_measured_viewability *= factor;
+        self.total_conversions *= factor;
+        self.total_conversion_value *= factor;
+        self.capped_bids = scale_count(self.capped_bids);
+        self.stale_bids = scale_count(self.stale_bids);
+        self.frequency_capped_auctions = scale_count(self.frequency_capped_auctions);
+        self.budget_capped_auctions = scale_count(self.budget_capped_auctions);
+        self.eligible_impressions = self.eligible_impressions.map(|eligible| eligible * factor);
+        self.auctions_participated = scale_count(self.auctions_participated);
+        self.weighted_auctions_participated *= factor;
+        self.reach = scale_count(self.reach);
+        self.soft_target_penalty = self.soft_target_penalty.map(|penalty| penalty * factor);
+        for seller_stat in self.seller_breakdown.iter_mut() {
+            seller_stat.impressions_obtained *= factor;
+            seller_stat.total_buyer_charge *= factor;
+            seller_stat.total_value *= factor;
+        }
+        for day_stat in self.day_breakdown.iter_mut() {
+            day_stat.impressions_obtained *= factor;
+            day_stat.total_buyer_charge *= factor;
+            day_stat.total_value *= factor;
+        }
+        for hour_stat in self.hour_breakdown.iter_mut() {
+            hour_stat.impressions_obtained *= factor;
+            hour_stat.total_buyer_charge *= factor;
+            hour_stat.total_value *= factor;
+        }
+    }
+}
+
+/// A campaign's convergence target as achieved in a simulation run
+#[derive(Debug, Clone)]
+pub struct TargetStat {
+    /// Description of the target (see CampaignTrait::get_targets_actual_and_target)
+    pub name: String,
+    pub actual: f64,
+    pub target: f64,
+    /// Band around the target within which it counts as met (see Campaigns::set_target_tolerances)
+    pub tolerance: TargetTolerance,
+}
+
+impl TargetStat {
+    /// Whether the actual value is within the tolerance band around the target
+    pub fn is_met(&self) -> bool {
+        self.tolerance.is_met(self.actual, self.target)
+    }
+}
+
+/// Statistics of a single campaign on a single seller
+#[derive(Clone)]
+pub struct CampaignSellerStat {
+    pub impressions_obtained: f64,
+    pub total_buyer_charge: f64,
+    pub total_value: f64,
+}
+
+/// Statistics of a single campaign on a single simulated day
+#[derive(Clone)]
+pub struct CampaignDayStat {
+    pub impressions_obtained: f64,
+    pub total_buyer_charge: f64,
+    pub total_value: f64,
+}
+
+/// Statistics of a single campaign in a single hour of the day
+#[derive(Clone)]
+pub struct CampaignHourStat {
+    pub impressions_obtained: f64,
+    pub total_buyer_charge: f64,
+    pub total_value: f64,
 }
 
 /// Statistics for a single seller
+#[derive(Clone)]
 pub struct SellerStat {
+    pub impressions_on_offer: usize,
     pub impressions_sold: usize,
+    /// Impressions on offer and sold weighted by Impression::weight (equal to the counts unless impressions are
+    /// oversampled), the base of the seller's rates
+    pub weighted_impressions_on_offer: f64,
+    pub weighted_impressions_sold: f64,
+    /// Impressions withdrawn without an auction because they were sold through another supply path
+    pub impressions_withdrawn: usize,
+    /// Number of bids received from each campaign, after throttling (indexed by campaign_id)
+    pub campaign_bids: Vec<usize>,
+    /// Number of bids of each campaign the seller throttled (indexed by campaign_id)
+    pub throttled_bids: Vec<usize>,
     pub total_supply_cost: f64,
     pub total_virtual_cost: f64,
     pub total_buyer_charge: f64,
+    /// Part of the buyer charge taken by the seller's adapter fee (see Sellers::set_adapter_fee)
+    pub total_adapter_fees: f64,
     pub total_provided_value: f64,
+    /// Number of auctions won by the competition: no campaign won and the competing bid cleared the floor
+    pub competitor_wins: usize,
+}
+
+impl SellerStat {
+    /// Share of the impressions on offer that were sold
+    pub fn fill_rate(&self) -> f64 {
+        if self.impressions_on_offer == 0 {
+            return 0.0;
+        }
+        self.weighted_impressions_sold / self.weighted_impressions_on_offer
+    }
+
+    /// Revenue (buyer charge) per mille of impressions sold
+    pub fn rcpm_sold(&self) -> f64 {
+        if self.impressions_sold == 0 {
+            return 0.0;
+        }
+        self.total_buyer_charge / self.weighted_impressions_sold * 1000.0
+    }
+
+    /// Revenue (buyer charge) per mille of impressions on offer
+    pub fn rcpm_offered(&self) -> f64 {
+        if self.impressions_on_offer == 0 {
+            return 0.0;
+        }
+        self.total_buyer_charge / self.weighted_impressions_on_offer * 1000.0
+    }
+
+    /// Share of the buyer charge kept after paying the supply cost, (buyer charge - supply cost) / buyer charge
+    pub fn take_rate(&self) -> f64 {
+        if self.total_buyer_charge == 0.0 {
+            return 0.0;
+        }
+        (self.total_buyer_charge - self.total_supply_cost) / self.total_buyer_charge
+    }
 }
 
 /// Overall statistics for the simulation
+#[derive(Clone)]
 pub struct OverallStat {
     pub lost_count: usize,
     pub no_bids_count: usize,
+    /// Supply paths withdrawn because their impression was sold through another path
+    pub withdrawn_count: usize,
     pub total_supply_cost: f64,
     pub total_virtual_cost: f64,
     pub total_buyer_charge: f64,
@@ -137,11 +995,31 @@ pub struct OverallStat {
 }
 
 /// Complete simulation statistics
+#[derive(Clone)]
 pub struct SimulationStat {
     pub campaign_stats: Vec<CampaignStat>,
     pub seller_stats: Vec<SellerStat>,
     pub overall_stat: OverallStat,
     pub convergence_iterations: usize,
+    /// Sustained oscillations of control variables detected up to this iteration, per campaign
+    /// (filled in by the convergence loop, empty when the stats are generated)
+    pub campaign_oscillations: Vec<Vec<Oscillation>>,
+    /// Sustained oscillations of control variables detected up to this iteration, per seller
+    pub seller_oscillations: Vec<Vec<Oscillation>>,
+    /// Iterations needed to converge again after perturbing a campaign's converged controller state
+    /// (filled in by the convergence loop when running with a perturbation, None otherwise)
+    pub perturbation_recovery_iterations: Option<usize>,
+    /// Actual value, target value and tolerance of every convergence target, per campaign
+    /// (filled in by the convergence loop, empty when the stats are generated)
+    pub campaign_targets: Vec<Vec<TargetStat>>,
+    /// Impressions offered per hour of the day, over all days and sellers (indexed by hour)
+    pub hour_impressions_offered: Vec<usize>,
+    /// Impressions offered in each audience segment (indexed by segment, empty when segments are not modeled)
+    pub segment_impressions_offered: Vec<usize>,
+    /// Number of unique users shown at least one impression (0 when users are not modeled)
+    pub unique_users: usize,
+    /// Results of the arms of every experiment (indexed by experiment_id, empty without experiments)
+    pub experiment_stats: Vec<ExperimentStat>,
 }
 
 impl SimulationStat {
@@ -149,30 +1027,60 @@ impl SimulationStat {
     /// 
     /// # Arguments
     /// * `marketplace` - The marketplace containing campaigns, sellers, and impressions
-    /// * `simulation_run` - The simulation run results
+    /// * `simulation_run` - The simulation run results (covering the impressions auctioned so far, for a run in progress)
     /// * `convergence_iterations` - Number of iterations it took to converge (1-indexed)
     pub fn new(marketplace: &Marketplace, simulation_run: &SimulationRun, convergence_iterations: usize) -> Self {
+        // Impressions the run has auctioned, all of them unless the run is still in progress
+        let impressions = &marketplace.impressions.impressions[..simulation_run.impressions_auctioned()];
+        
         // Initialize campaign statistics
         let num_campaigns = marketplace.campaigns.campaigns.len();
+        let num_sellers = marketplace.sellers.sellers.len();
         let mut campaign_stats: Vec<CampaignStat> = (0..num_campaigns)
-            .map(|_| CampaignStat {
+            .map(|campaign_id| CampaignStat {
                 impressions_obtained: 0.0,
                 total_supply_cost: 0.0,
                 total_virtual_cost: 0.0,
                 total_buyer_charge: 0.0,
                 total_value: 0.0,
+                total_viewable_value: 0.0,
+                total_viewability: 0.0,
+                total_measured_viewability: 0.0,
+                total_conversions: 0.0,
+                total_conversion_value: 0.0,
+                capped_bids: simulation_run.capped_bids[campaign_id],
+                stale_bids: simulation_run.stale_bids[campaign_id],
+                frequency_capped_auctions: simulation_run.frequency_capped_auctions[campaign_id],
+                budget_capped_auctions: simulation_run.budget_capped_auctions[campaign_id],
+                budget_exhausted_at: simulation_run.budget_exhausted_at[campaign_id],
+                eligible_impressions: marketplace.eligible_impressions[campaign_id],
+                auctions_participated: simulation_run.auctions_participated[campaign_id],
+                weighted_auctions_participated: simulation_run.weighted_auctions_participated[campaign_id],
+                reach: 0,
+                soft_target_penalty: None,
+                seller_breakdown: vec![CampaignSellerStat { impressions_obtained: 0.0, total_buyer_charge: 0.0, total_value: 0.0 }; num_sellers],
+                day_breakdown: vec![CampaignDayStat { impressions_obtained: 0.0, total_buyer_charge: 0.0, total_value: 0.0 }; marketplace.impressions.num_days],
+                hour_breakdown: vec![CampaignHourStat { impressions_obtained: 0.0, total_buyer_charge: 0.0, total_value: 0.0 }; HOURS_PER_DAY as usize],
+                bid_history: simulation_run.bid_history[campaign_id].clone(),
             })
             .collect();
 
         // Initialize seller statistics
-        let num_sellers = marketplace.sellers.sellers.len();
         let mut seller_stats: Vec<SellerStat> = (0..num_sellers)
-            .map(|_| SellerStat {
+            .map(|seller_id| SellerStat {
+                impressions_on_offer: 0,
                 impressions_sold: 0,
+                weighted_impressions_on_offer: 0.0,
+                weighted_impressions_sold: 0.0,
+                impressions_withdrawn: 0,
+                campaign_bids: simulation_run.seller_campaign_bids[seller_id].clone(),
+                throttled_bids: simulation_run.seller_throttled_bids[seller_id].clone(),
                 total_supply_cost: 0.0,
                 total_virtual_cost: 0.0,
                 total_buyer_charge: 0.0,
+                total_adapter_fees: 0.0,
                 total_provided_value: 0.0,
+                competitor_wins: simulation_run.seller_competitor_wins[seller_id],
             })
             .collect();
 
@@ -180,87 +1088,143 @@ impl SimulationStat {
         let mut overall_stat = OverallStat {
             lost_count: 0,
             no_bids_count: 0,
+            withdrawn_count: 0,
             total_supply_cost: 0.0,
             total_virtual_cost: 0.0,
             total_buyer_charge: 0.0,
             total_value: 0.0,
         };
 
-        // Iterate through impressions once and accumulate all statistics
-        for (index, impression) in marketplace.impressions.impressions.iter().enumerate() {
+        // Users each campaign won impressions of, for its reach
+        let mut campaign_users: Vec<HashSet<usize>> = vec![HashSet::new(); num_campaigns];
+
+        // Iterate through impressions once and accumulate all statistics, taking each impression's result
+        // from the results of its seller's simulation type
+        let mut results = simulation_run.results.iter();
+        let mut results_fractional = simulation_run.results_fractional.iter();
+        for impression in impressions {
             let seller_id = impression.seller_id;
+            // Sums are weighted by the impression's importance sampling weight, counts are not
+            let weight = impression.weight;
+            seller_stats[seller_id].impressions_on_offer += 1;
+            seller_stats[seller_id].weighted_impressions_on_offer += weight;
 
             // Condition on simulation type to handle different auction result types
-            match marketplace.simulation_type {
+            match marketplace.seller_simulation_type(seller_id) {
                 SimulationType::Standard => {
-                    let result = &simulation_run.results[index];
+                    let result = results.next().expect("Standard auction result for every impression auctioned with Standard auctions");
+                    let supply_cost = result.supply_cost * weight;
 
                     // Update overall statistics based on winner
                     match result.winner {
                         Winner::LOST => {
                             overall_stat.lost_count += 1;
                             // Even when impression is not sold, count supply cost (0.0 for first price, fixed_cost_cpm for fixed price)
-                            overall_stat.total_supply_cost += result.supply_cost;
+                            overall_stat.total_supply_cost += supply_cost;
                             // Update seller statistics
                             let seller_stat = &mut seller_stats[seller_id];
-                            seller_stat.total_supply_cost += result.supply_cost;
+                            seller_stat.total_supply_cost += supply_cost;
                         }
                         Winner::NO_DEMAND => {
                             overall_stat.no_bids_count += 1;
                             // Even when there's no demand, count supply cost (0.0 for first price, fixed_cost_cpm for fixed price)
-                            overall_stat.total_supply_cost += result.supply_cost;
+                            overall_stat.total_supply_cost += supply_cost;
+                            // Update seller statistics
+                            let seller_stat = &mut seller_stats[seller_id];
+                            seller_stat.total_supply_cost += supply_cost;
+                        }
+                        Winner::WITHDRAWN => {
+                            overall_stat.withdrawn_count += 1;
+                            // Withdrawn paths still count their supply cost, like unsold impressions
+                            overall_stat.total_supply_cost += supply_cost;
                             // Update seller statistics
                             let seller_stat = &mut seller_stats[seller_id];
-                            seller_stat.total_supply_cost += result.supply_cost;
+                            seller_stat.impressions_withdrawn += 1;
+                            seller_stat.total_supply_cost += supply_cost;
                         }
                         Winner::Campaign { campaign_id, virtual_cost, buyer_charge, .. } => {
+                            let (virtual_cost, buyer_charge) = (virtual_cost * weight, buyer_charge * weight);
+                            let group_id = marketplace.campaigns.campaign_to_value_group_mapping[campaign_id];
+                            let value = impression.value_to_campaign_group[group_id] * weight;
+
                             // Update overall statistics
-                            overall_stat.total_supply_cost += result.supply_cost;
+                            overall_stat.total_supply_cost += supply_cost;
                             overall_stat.total_virtual_cost += virtual_cost;
                             overall_stat.total_buyer_charge += buyer_charge;
-                            let group_id = marketplace.campaigns.campaign_to_value_group_mapping[campaign_id];
-                            overall_stat.total_value += impression.value_to_campaign_group[group_id];
+                            overall_stat.total_value += value;
 
                             // Update seller statistics
                             let seller_stat = &mut seller_stats[seller_id];
                             seller_stat.impressions_sold += 1;
-                            seller_stat.total_supply_cost += result.supply_cost;
+                            seller_stat.weighted_impressions_sold += weight;
+                            seller_stat.total_supply_cost += supply_cost;
                             seller_stat.total_virtual_cost += virtual_cost;
                             seller_stat.total_buyer_charge += buyer_charge;
-                            let group_id = marketplace.campaigns.campaign_to_value_group_mapping[campaign_id];
-                            seller_stat.total_provided_value += impression.value_to_campaign_group[group_id];
+                            seller_stat.total_adapter_fees += buyer_charge * marketplace.sellers.adapter_fee(seller_id).unwrap_or(0.0);
+                            seller_stat.total_provided_value += value;
 
                             // Update campaign statistics
                             let campaign_stat = &mut campaign_stats[campaign_id];
-                            campaign_stat.impressions_obtained += 1.0;
-                            campaign_stat.total_supply_cost += result.supply_cost;
+                            campaign_stat.impressions_obtained += weight;
+                            if let Some(user_id) = impression.user_id {
+                                campaign_users[campaign_id].insert(user_id);
+                            }
+                            campaign_stat.total_supply_cost += supply_cost;
                             campaign_stat.total_virtual_cost += virtual_cost;
                             campaign_stat.total_buyer_charge += buyer_charge;
-                            let group_id = marketplace.campaigns.campaign_to_value_group_mapping[campaign_id];
-                            campaign_stat.total_value += impression.value_to_campaign_group[group_id];
+                            campaign_stat.total_value += value;
+                            campaign_stat.total_viewable_value += value * impression.viewability;
+                            campaign_stat.total_viewability += impression.viewability * weight;
+                            campaign_stat.total_measured_viewability += impression.measured_viewability * weight;
+                            if impression.converted {
+                                campaign_stat.total_conversions += weight;
+                                campaign_stat.total_conversion_value += impression.conversion_value * weight;
+                            }
+                            let campaign_seller_stat = &mut campaign_stat.seller_breakdown[seller_id];
+                            campaign_seller_stat.impressions_obtained += weight;
+                            campaign_seller_stat.total_buyer_charge += buyer_charge;
+                            campaign_seller_stat.total_value += value;
+                            let campaign_day_stat = &mut campaign_stat.day_breakdown[impression.day()];
+                            campaign_day_stat.impressions_obtained += weight;
+                            campaign_day_stat.total_buyer_charge += buyer_charge;
+                            campaign_day_stat.total_value += value;
+                            let campaign_hour_stat = &mut campaign_stat.hour_breakdown[impression.hour_of_day()];
+                            campaign_hour_stat.impressions_obtained += weight;
+                            campaign_hour_stat.total_buyer_charge += buyer_charge;
+                            campaign_hour_stat.total_value += value;
                         }
                     }
                 }
                 SimulationType::FractionalInternalAuction { .. } => {
-                    let result_fractional = &simulation_run.results_fractional[index];
+                    let result_fractional = results_fractional.next().expect("Fractional auction result for every impression auctioned with fractional auctions");
+                    let supply_cost = result_fractional.supply_cost * weight;
 
                     // Update overall statistics based on fractional winners
                     match &result_fractional.winner {
                         FractionalWinners::LOST => {
                             overall_stat.lost_count += 1;
                             // Even when impression is not sold, count supply cost (0.0 for first price, fixed_cost_cpm for fixed price)
-                            overall_stat.total_supply_cost += result_fractional.supply_cost;
+                            overall_stat.total_supply_cost += supply_cost;
                             // Update seller statistics
                             let seller_stat = &mut seller_stats[seller_id];
-                            seller_stat.total_supply_cost += result_fractional.supply_cost;
+                            seller_stat.total_supply_cost += supply_cost;
                         }
                         FractionalWinners::NO_DEMAND => {
                             overall_stat.no_bids_count += 1;
                             // Even when there's no demand, count supply cost (0.0 for first price, fixed_cost_cpm for fixed price)
-                            overall_stat.total_supply_cost += result_fractional.supply_cost;
+                            overall_stat.total_supply_cost += supply_cost;
                             // Update seller statistics
                             let seller_stat = &mut seller_stats[seller_id];
-                            seller_stat.total_supply_cost += result_fractional.supply_cost;
+                            seller_stat.total_supply_cost += supply_cost;
+                        }
+                        FractionalWinners::WITHDRAWN => {
+                            overall_stat.withdrawn_count += 1;
+                            // Withdrawn paths still count their supply cost, like unsold impressions
+                            overall_stat.total_supply_cost += supply_cost;
+                            // Update seller statistics
+                            let seller_stat = &mut seller_stats[seller_id];
+                            seller_stat.impressions_withdrawn += 1;
+                            seller_stat.total_supply_cost += supply_cost;
                         }
                         FractionalWinners::Campaigns { winners } => {
                             // Calculate total supply cost from fractional winners (weighted by win_fraction)
@@ -269,11 +1233,13 @@ impl SimulationStat {
                             // Update seller statistics once per impression (impressions_sold is usize, not fractional)
                             let seller_stat = &mut seller_stats[seller_id];
                             seller_stat.impressions_sold += 1;
+                            seller_stat.weighted_impressions_sold += weight;
                             
                             // Process each fractional winner
                             for fractional_winner in winners {
                                 let campaign_id = fractional_winner.campaign_id;
-                                let win_fraction = fractional_winner.win_fraction;
+                                // Fractions won count with the impression's weight
+                                let win_fraction = fractional_winner.win_fraction * weight;
                                 
                                 // Accumulate supply cost (weighted by win_fraction)
                                 total_supply_cost += fractional_winner.supply_cost * win_fraction;
@@ -287,17 +1253,40 @@ impl SimulationStat {
                                 // Update seller statistics (weighted by win_fraction)
                                 seller_stat.total_virtual_cost += fractional_winner.virtual_cost * win_fraction;
                                 seller_stat.total_buyer_charge += fractional_winner.buyer_charge * win_fraction;
+                                seller_stat.total_adapter_fees += fractional_winner.buyer_charge * win_fraction * marketplace.sellers.adapter_fee(seller_id).unwrap_or(0.0);
                                 let group_id = marketplace.campaigns.campaign_to_value_group_mapping[campaign_id];
                                 seller_stat.total_provided_value += impression.value_to_campaign_group[group_id] * win_fraction;
 
                                 // Update campaign statistics (weighted by win_fraction - fractional counting on buy side)
                                 let campaign_stat = &mut campaign_stats[campaign_id];
                                 campaign_stat.impressions_obtained += win_fraction;
+                                if let Some(user_id) = impression.user_id {
+                                    campaign_users[campaign_id].insert(user_id);
+                                }
                                 campaign_stat.total_supply_cost += fractional_winner.supply_cost * win_fraction;
                                 campaign_stat.total_virtual_cost += fractional_winner.virtual_cost * win_fraction;
                                 campaign_stat.total_buyer_charge += fractional_winner.buyer_charge * win_fraction;
                                 let group_id = marketplace.campaigns.campaign_to_value_group_mapping[campaign_id];
                                 campaign_stat.total_value += impression.value_to_campaign_group[group_id] * win_fraction;
+                                campaign_stat.total_viewable_value += impression.value_to_campaign_group[group_id] * impression.viewability * win_fraction;
+                                campaign_stat.total_viewability += impression.viewability * win_fraction;
+                                campaign_stat.total_measured_viewability += impression.measured_viewability * win_fraction;
+                                if impression.converted {
+                                    campaign_stat.total_conversions += win_fraction;
+                                    campaign_stat.total_conversion_value += impression.conversion_value * win_fraction;
+                                }
+                                let campaign_seller_stat = &mut campaign_stat.seller_breakdown[seller_id];
+                                campaign_seller_stat.impressions_obtained += win_fraction;
+                                campaign_seller_stat.total_buyer_charge += fractional_winner.buyer_charge * win_fraction;
+                                campaign_seller_stat.total_value += impression.value_to_campaign_group[group_id] * win_fraction;
+                                let campaign_day_stat = &mut campaign_stat.day_breakdown[impression.day()];
+                                campaign_day_stat.impressions_obtained += win_fraction;
+                                campaign_day_stat.total_buyer_charge += fractional_winner.buyer_charge * win_fraction;
+                                campaign_day_stat.total_value += impression.value_to_campaign_group[group_id] * win_fraction;
+                                let campaign_hour_stat = &mut campaign_stat.hour_breakdown[impression.hour_of_day()];
+                                campaign_hour_stat.impressions_obtained += win_fraction;
+                                campaign_hour_stat.total_buyer_charge += fractional_winner.buyer_charge * win_fraction;
+                                campaign_hour_stat.total_value += impression.value_to_campaign_group[group_id] * win_fraction;
                             }
                             
                             // Update overall supply cost (once per impression)
@@ -309,11 +1298,42 @@ impl SimulationStat {
             }
         }
 
+        for (campaign_stat, users) in campaign_stats.iter_mut().zip(&campaign_users) {
+            campaign_stat.reach = users.len();
+        }
+
+        // Impressions offered per hour, the base of the hourly breakdowns, per segment, and users shown any impression
+        let mut hour_impressions_offered = vec![0; HOURS_PER_DAY as usize];
+        let mut segment_impressions_offered = vec![0; marketplace.impressions.num_segments];
+        let mut users = HashSet::new();
+        for impression in impressions {
+            hour_impressions_offered[impression.hour_of_day()] += 1;
+            if let Some(segment) = impression.segment {
+                segment_impressions_offered[segment] += 1;
+            }
+            if let Some(user_id) = impression.user_id {
+                users.insert(user_id);
+            }
+        }
+
+        // Penalize soft targets on the aggregated statistics
+        for (campaign_stat, campaign) in campaign_stats.iter_mut().zip(marketplace.campaigns.campaigns.iter()) {
+            campaign_stat.soft_target_penalty = campaign.soft_target_penalty(campaign_stat);
+        }
+
         Self {
             campaign_stats,
             seller_stats,
             overall_stat,
             convergence_iterations,
+            campaign_oscillations: vec![Vec::new(); num_campaigns],
+            seller_oscillations: vec![Vec::new(); num_sellers],
+            perturbation_recovery_iterations: None,
+            campaign_targets: Vec::new(),
+            hour_impressions_offered,
+            segment_impressions_offered,
+            unique_users: users.len(),
+            experiment_stats: ExperimentStat::from_run(marketplace, simulation_run),
         }
     }
 
@@ -345,6 +1365,43 @@ impl SimulationStat {
                 0.0
             };
             logln!(logger, event, "  Obtained Value: {:.2} (per spend: {:.4}, per impression: {:.4})", campaign_stat.total_value, value_per_spend, avg_value_per_impression);
+            let win_rate = if campaign_stat.auctions_participated > 0 {
+                campaign_stat.impressions_obtained / campaign_stat.weighted_auctions_participated
+            } else {
+                0.0
+            };
+            logln!(logger, event, "  Auctions Participated: {} (win rate: {:.4})", campaign_stat.auctions_participated, win_rate);
+            if let Some(soft_target_penalty) = campaign_stat.soft_target_penalty {
+                let margin = campaign_stat.total_value / 1000.0 - campaign_stat.total_buyer_charge;
+                logln!(logger, event, "  Soft Target Penalty: {:.4} (value - spend: {:.4}, net of penalty: {:.4})", soft_target_penalty, margin, margin - soft_target_penalty);
+            }
+            if campaign.max_bid_cpm().is_some() {
+                logln!(logger, event, "  Capped Bids: {}", campaign_stat.capped_bids);
+            }
+            if campaign_stat.stale_bids > 0 {
+                logln!(logger, event, "  Stale Bids (served from seller caches): {}", campaign_stat.stale_bids);
+            }
+            if let Some(eligible_impressions) = campaign_stat.eligible_impressions {
+                logln!(logger, event, "  Frequency Capped Auctions: {} (eligible impressions: {:.0})", campaign_stat.frequency_capped_auctions, eligible_impressions);
+            }
+            if let Some(budget_cap) = campaigns.budget_cap(index) {
+                match campaign_stat.budget_exhausted_at {
+                    Some(budget_exhausted_at) => logln!(logger, event, "  Budget Cap: {:.2}, exhausted at hour {:.2} (budget capped auctions: {})", budget_cap, budget_exhausted_at, campaign_stat.budget_capped_auctions),
+                    None => logln!(logger, event, "  Budget Cap: {:.2}, not exhausted", budget_cap),
+                }
+            }
+            if campaign_stat.total_conversions > 0.0 {
+                logln!(logger, event, "  Conversions: {:.2} (CPA: {:.4}), Conversion Value: {:.2} (ROAS: {:.4})", campaign_stat.total_conversions, campaign_stat.total_buyer_charge / campaign_stat.total_conversions,
+                    campaign_stat.total_conversion_value, campaign_stat.total_conversion_value / campaign_stat.total_buyer_charge);
+            }
+            if campaign_stat.seller_breakdown.len() > 1 && campaign_stat.total_buyer_charge > 0.0 {
+                let seller_spend_shares: Vec<String> = campaign_stat.seller_breakdown.iter().map(|seller_stat| format!("{:.2} ({:.1}%)", seller_stat.total_buyer_charge, seller_stat.total_buyer_charge / campaign_stat.total_buyer_charge * 100.0)).collect();
+                logln!(logger, event, "  Spend per Seller: {}", seller_spend_shares.join(" / "));
+            }
+            if campaign_stat.day_breakdown.len() > 1 {
+                let day_spends: Vec<String> = campaign_stat.day_breakdown.iter().map(|day_stat| format!("{:.2}", day_stat.total_buyer_charge)).collect();
+                logln!(logger, event, "  Spend per Day: {}", day_spends.join(" / "));
+            }
         }
     }
 
@@ -359,11 +1416,35 @@ impl SimulationStat {
             let converged_status = if seller_controller_states.converged[index] { " [CONVERGED]" } else { " [NOT CONVERGED]" };
             logln!(logger, event, "\nSeller {} ({}) - {}{}", 
                      seller.seller_id(), seller.seller_name(), type_target_and_controller_string, converged_status);
-            logln!(logger, event, "  Impressions (sold/on offer): {} / {}", seller_stat.impressions_sold, seller.get_impressions_on_offer());
+            logln!(logger, event, "  Impressions (sold/on offer): {} / {}", seller_stat.impressions_sold, seller_stat.impressions_on_offer);
+            if let Some(source_seller_id) = sellers.supply_path_source(index) {
+                logln!(logger, event, "  Supply Path of Seller {} ({})", source_seller_id, sellers.sellers[source_seller_id].seller_name());
+            }
+            if let Some(Some(schedule)) = sellers.boost_schedules.get(index) {
+                let periods: Vec<String> = schedule.iter().map(|period| format!("from {:.1}h x{:.2}", period.from_hour, period.boost_factor)).collect();
+                logln!(logger, event, "  Boost Schedule: {}", periods.join(", "));
+            }
+            if seller_stat.impressions_withdrawn > 0 {
+                logln!(logger, event, "  Impressions Withdrawn (sold through another supply path): {}", seller_stat.impressions_withdrawn);
+            }
+            if seller_stat.throttled_bids.iter().any(|throttled_bids| *throttled_bids > 0) {
+                let campaign_bids: Vec<String> = seller_stat.campaign_bids.iter().zip(&seller_stat.throttled_bids)
+                    .map(|(campaign_bids, throttled_bids)| format!("{}/{}", campaign_bids, throttled_bids))
+                    .collect();
+                logln!(logger, event, "  Bids per Campaign (received/throttled): {}", campaign_bids.join(", "));
+            }
             logln!(logger, event, "  Total Costs (supply/virtual/buyer): {:.2} / {:.2} / {:.2}", 
                      seller_stat.total_supply_cost, 
                      seller_stat.total_virtual_cost, 
                      seller_stat.total_buyer_charge);
+            if seller_stat.total_adapter_fees > 0.0 {
+                logln!(logger, event, "  Adapter Fees (of buyer charge): {:.2}", seller_stat.total_adapter_fees);
+            }
+            logln!(logger, event, "  Fill Rate: {:.2}%, rCPM (sold/offered): {:.4} / {:.4}, Take Rate: {:.2}%",
+                     seller_stat.fill_rate() * 100.0,
+                     seller_stat.rcpm_sold(),
+                     seller_stat.rcpm_offered(),
+                     seller_stat.take_rate() * 100.0);
             logln!(logger, event, "  Total Provided Value: {:.2}", seller_stat.total_provided_value);
         }
     }
@@ -379,6 +1460,14 @@ impl SimulationStat {
         logln!(logger, LogEvent::Variant, "\n=== Seller Statistics ===");
         self.printout_sellers(sellers, seller_controller_states, logger, LogEvent::Variant);
 
+        // Output experiment statistics
+        if !self.experiment_stats.is_empty() {
+            logln!(logger, LogEvent::Variant, "\n=== Experiment Statistics ===");
+            for experiment_stat in &self.experiment_stats {
+                experiment_stat.printout(logger, LogEvent::Variant);
+            }
+        }
+
         // Output overall statistics
         self.printout_overall(logger);
     }
@@ -388,9 +1477,19 @@ impl SimulationStat {
         
         logln!(logger, LogEvent::Variant, "\n=== Overall Statistics ===");
         logln!(logger, LogEvent::Variant, "Convergence: {} iterations", self.convergence_iterations);
+        let oscillations = self.campaign_oscillations.iter().chain(self.seller_oscillations.iter()).map(|oscillations| oscillations.len()).sum::<usize>();
+        if oscillations > 0 {
+            logln!(logger, LogEvent::Variant, "Oscillations detected: {}", oscillations);
+        }
+        if let Some(recovery_iterations) = self.perturbation_recovery_iterations {
+            logln!(logger, LogEvent::Variant, "Recovery after perturbation: {} iterations", recovery_iterations);
+        }
         logln!(logger, LogEvent::Variant, "Impressions (lost/no bids): {} / {}", 
                  self.overall_stat.lost_count,
                  self.overall_stat.no_bids_count);
+        if self.overall_stat.withdrawn_count > 0 {
+            logln!(logger, LogEvent::Variant, "Supply Paths Withdrawn: {}", self.overall_stat.withdrawn_count);
+        }
         logln!(logger, LogEvent::Variant, "Total Costs (supply/virtual/buyer): {:.2} / {:.2} / {:.2}", 
                  self.overall_stat.total_supply_cost, 
                  self.overall_stat.total_virtual_cost, 
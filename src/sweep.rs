@@ -0,0 +1,264 @@
+//! Parameter sweeps over a grid of values
+//!
+//! A sweep declares one or two parameters with the values to try and the metrics to record, and runs a
+//! variant for every combination of the values, prepared by the scenario from them. Results are written as
+//! a tidy CSV (a row per combination, a column per parameter and metric), and for two parameters optionally
+//! as a heatmap of a metric over the grid, so scenarios don't repeat a variant block per parameter value.
+
+use crate::simulationrun::SimulationStat;
+use crate::converge::SimulationConverge;
+use crate::timeouts::is_scenario_timeout;
+use crate::logger::{Logger, LogEvent, FileReceiver, sanitize_filename};
+use crate::logln;
+use crate::warnln;
+use plotters::prelude::*;
+use std::path::Path;
+
+/// Metric recorded for every combination of a sweep
+type SweepMetric = Box<dyn Fn(&SimulationStat) -> f64>;
+
+/// A swept parameter and the values it takes
+pub struct SweepParameter {
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+/// Result of one combination of a sweep's parameter values
+pub struct SweepPoint {
+    /// Value of each parameter (in order of the sweep's parameters)
+    pub parameter_values: Vec<f64>,
+    /// Statistics of the converged run, None if it failed to converge
+    pub stats: Option<SimulationStat>,
+    /// Value of each metric (in order of the sweep's metrics), empty if the run failed to converge
+    pub metric_values: Vec<f64>,
+}
+
+/// Results of all combinations of a sweep, in the order they ran (the last parameter varying fastest)
+pub struct SweepResult {
+    pub parameter_names: Vec<String>,
+    pub metric_names: Vec<String>,
+    pub points: Vec<SweepPoint>,
+}
+
+impl SweepResult {
+    /// Result of the combination of the given parameter values
+    /// Panics if the combination isn't on the sweep's grid
+    pub fn point(&self, parameter_values: &[f64]) -> &SweepPoint {
+        self.points.iter()
+            .find(|point| point.parameter_values == parameter_values)
+            .unwrap_or_else(|| panic!("No sweep point for parameter values {:?}", parameter_values))
+    }
+
+    /// Value of a metric for the combination of the given parameter values, None if its run failed to converge
+    pub fn metric(&self, parameter_values: &[f64], metric_name: &str) -> Option<f64> {
+        let metric_index = self.metric_names.iter().position(|name| name == metric_name)
+            .unwrap_or_else(|| panic!("No sweep metric '{}'", metric_name));
+        self.point(parameter_values).metric_values.get(metric_index).copied()
+    }
+
+    /// Combinations whose runs failed to converge
+    pub fn failed_points(&self) -> Vec<&SweepPoint> {
+        self.points.iter().filter(|point| point.stats.is_none()).collect()
+    }
+}
+
+/// A sweep over one or two parameters
+pub struct Sweep {
+    pub name: String,
+    pub parameters: Vec<SweepParameter>,
+    metrics: Vec<(String, SweepMetric)>,
+    /// Metric to draw a heatmap of over the grid (two parameter sweeps only)
+    heatmap_metric: Option<String>,
+}
+
+impl Sweep {
+    /// Create a new sweep, `name` names its variants and output files
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters: Vec::new(),
+            metrics: Vec::new(),
+            heatmap_metric: None,
+        }
+    }
+
+    /// Sweep a parameter over the given values (at most two parameters)
+    pub fn with_parameter(mut self, name: &str, values: Vec<f64>) -> Self {
+        assert!(self.parameters.len() < 2, "Sweep '{}' can sweep at most two parameters", self.name);
+        assert!(!values.is_empty(), "Sweep parameter '{}' needs at least one value", name);
+        self.parameters.push(SweepParameter { name: name.to_string(), values });
+        self
+    }
+
+    /// Record a metric of the converged statistics for every combination
+    pub fn with_metric<F: Fn(&SimulationStat) -> f64 + 'static>(mut self, name: &str, metric: F) -> Self {
+        self.metrics.push((name.to_string(), Box::new(metric)));
+        self
+    }
+
+    /// Draw a heatmap of a (previously added) metric over the grid of a two parameter sweep
+    pub fn with_heatmap(mut self, metric_name: &str) -> Self {
+        assert!(self.metrics.iter().any(|(name, _)| name == metric_name), "Sweep '{}' has no metric '{}'", self.name, metric_name);
+        self.heatmap_metric = Some(metric_name.to_string());
+        self
+    }
+
+    /// All combinations of the parameter values, the last parameter varying fastest
+    fn combinations(&self) -> Vec<Vec<f64>> {
+        self.parameters.iter().fold(vec![Vec::new()], |combinations, parameter| {
+            combinations.iter()
+                .flat_map(|combination| parameter.values.iter().map(move |&value| {
+                    let mut combination = combination.clone();
+                    combination.push(value);
+                    combination
+                }))
+                .collect()
+        })
+    }
+
+    /// Description of a combination, e.g. "softmax_temperature=0.5, boost=1.2"
+    fn combination_string(&self, parameter_values: &[f64], separator: &str) -> String {
+        self.parameters.iter().zip(parameter_values)
+            .map(|(parameter, value)| format!("{}={}", parameter.name, value))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    /// Run a variant for every combination of the parameter values
+    ///
+    /// `prepare` creates the simulation of a combination from its parameter values (in order of the
+    /// parameters). Each combination runs as variant `<sweep name>-<parameter>=<value>-...`, and a combination
+    /// failing to converge (or timing out) is recorded as failed rather than ending the sweep, unless the
+    /// scenario runs out of time (see timeouts.rs). The results are written to
+    /// `sweep-<sweep name>.csv` in the scenario's log directory, and the heatmap (if any) to
+    /// `sweep-<sweep name>-<metric>.png` next to it.
+    ///
+    /// # Returns
+    /// Returns a Result with the SweepResult, or an error if the scenario timed out or the heatmap couldn't be drawn
+    pub fn run<F: Fn(&[f64]) -> SimulationConverge>(
+        &self,
+        prepare: F,
+        scenario_name: &str,
+        max_iterations: usize,
+        logger: &mut Logger,
+    ) -> Result<SweepResult, Box<dyn std::error::Error>> {
+        assert!(!self.parameters.is_empty(), "Sweep '{}' has no parameters", self.name);
+        assert!(self.heatmap_metric.is_none() || self.parameters.len() == 2, "Heatmap of sweep '{}' needs two parameters", self.name);
+
+        let mut points = Vec::new();
+        for parameter_values in self.combinations() {
+            let simulation_converge = prepare(&parameter_values);
+            let variant_name = format!("{}-{}", self.name, self.combination_string(&parameter_values, "-"));
+            let variant_description = format!("Running {} with {}", self.name, self.combination_string(&parameter_values, ", "));
+            match simulation_converge.run_variant(&variant_description, scenario_name, &variant_name, max_iterations, logger) {
+                Ok(stats) => {
+                    let metric_values = self.metrics.iter().map(|(_, metric)| metric(&stats)).collect();
+                    points.push(SweepPoint { parameter_values, stats: Some(stats), metric_values });
+                }
+                Err(error) if is_scenario_timeout(error.as_ref()) => return Err(error),
+                Err(error) => {
+                    warnln!(logger, LogEvent::Scenario, "{}", error);
+                    points.push(SweepPoint { parameter_values, stats: None, metric_values: Vec::new() });
+                }
+            }
+        }
+        let result = SweepResult {
+            parameter_names: self.parameters.iter().map(|parameter| parameter.name.clone()).collect(),
+            metric_names: self.metrics.iter().map(|(name, _)| name.clone()).collect(),
+            points,
+        };
+
+        self.write_csv(&result, scenario_name, logger);
+        if let Some(heatmap_metric) = &self.heatmap_metric {
+            let path = logger.log_path(&format!("{}/sweep-{}-{}.png", sanitize_filename(scenario_name), sanitize_filename(&self.name), sanitize_filename(heatmap_metric)));
+            self.draw_heatmap(&result, heatmap_metric, &path)?;
+            logln!(logger, LogEvent::Scenario, "Sweep {}: heatmap of {} written to {}", self.name, heatmap_metric, path.display());
+        }
+        Ok(result)
+    }
+
+    /// Write the results as CSV, a row per combination with its parameter values, whether it converged,
+    /// the convergence iterations and the metrics (empty if it failed to converge)
+    fn write_csv(&self, result: &SweepResult, scenario_name: &str, logger: &mut Logger) {
+        let sweep_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/sweep-{}.csv", sanitize_filename(scenario_name), sanitize_filename(&self.name))), vec![LogEvent::Sweep]));
+        logln!(logger, LogEvent::Sweep, "{},converged,convergence_iterations,{}", result.parameter_names.join(","), result.metric_names.join(","));
+        for point in &result.points {
+            let parameter_values: Vec<String> = point.parameter_values.iter().map(|value| value.to_string()).collect();
+            let metric_values: Vec<String> = match &point.stats {
+                Some(_) => point.metric_values.iter().map(|value| value.to_string()).collect(),
+                None => vec![String::new(); result.metric_names.len()],
+            };
+            let convergence_iterations = point.stats.as_ref().map_or(String::new(), |stats| stats.convergence_iterations.to_string());
+            logln!(logger, LogEvent::Sweep, "{},{},{},{}", parameter_values.join(","), point.stats.is_some(), convergence_iterations, metric_values.join(","));
+        }
+        logger.remove_receiver(sweep_receiver_id);
+    }
+
+    /// Draw a heatmap of a metric over the grid of the two parameters, with the metric's value in every cell
+    /// (cells of combinations that failed to converge are gray)
+    fn draw_heatmap(&self, result: &SweepResult, metric_name: &str, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let metric_index = result.metric_names.iter().position(|name| name == metric_name).unwrap();
+        let (x_parameter, y_parameter) = (&self.parameters[0], &self.parameters[1]);
+        let (x_count, y_count) = (x_parameter.values.len(), y_parameter.values.len());
+        let (min, max) = result.points.iter()
+            .filter_map(|point| point.metric_values.get(metric_index))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| (min.min(value), max.max(value)));
+        // Avoid normalizing by an empty range when all values are equal
+        let max = if max > min { max } else { min + 1.0 };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let root = BitMapBackend::new(path, (1600, 1200)).into_drawing_area();
+        root.fill(&WHITE)?;
+
+        // Cell i of an axis spans from Exact(i) to Exact(i + 1), the last one up to Last
+        let mut chart = ChartBuilder::on(&root)
+            .caption(format!("{}: {}", self.name, metric_name), ("sans-serif", 30))
+            .margin(20)
+            .x_label_area_size(60)
+            .y_label_area_size(80)
+            .build_cartesian_2d((0..x_count - 1).into_segmented(), (0..y_count - 1).into_segmented())?;
+        let segment_end = |index: usize, count: usize| if index + 1 < count { SegmentValue::Exact(index + 1) } else { SegmentValue::Last };
+        let label = |segment: &SegmentValue<usize>, values: &[f64]| match segment {
+            SegmentValue::CenterOf(index) => values.get(*index).map_or(String::new(), |value| value.to_string()),
+            _ => String::new(),
+        };
+
+        chart.configure_mesh()
+            .disable_mesh()
+            .label_style(("sans-serif", 20))
+            .axis_desc_style(("sans-serif", 25))
+            .x_desc(&x_parameter.name)
+            .y_desc(&y_parameter.name)
+            .x_labels(x_count)
+            .y_labels(y_count)
+            .x_label_formatter(&|segment| label(segment, &x_parameter.values))
+            .y_label_formatter(&|segment| label(segment, &y_parameter.values))
+            .draw()?;
+
+        let cells: Vec<(usize, usize, Option<f64>)> = result.points.iter()
+            .map(|point| {
+                let x_index = x_parameter.values.iter().position(|&value| value == point.parameter_values[0]).unwrap();
+                let y_index = y_parameter.values.iter().position(|&value| value == point.parameter_values[1]).unwrap();
+                (x_index, y_index, point.metric_values.get(metric_index).copied())
+            })
+            .collect();
+        chart.draw_series(cells.iter().map(|&(x_index, y_index, value)| {
+            let color = value.map_or(RGBColor(200, 200, 200), |value| ViridisRGB::get_color_normalized(value, min, max));
+            Rectangle::new(
+                [(SegmentValue::Exact(x_index), SegmentValue::Exact(y_index)), (segment_end(x_index, x_count), segment_end(y_index, y_count))],
+                color.filled(),
+            )
+        }))?;
+        chart.draw_series(cells.iter().map(|&(x_index, y_index, value)| {
+            let text = value.map_or("not converged".to_string(), |value| format!("{:.4}", value));
+            // Light text on the dark lower half of the color map
+            let text_color = if value.is_some_and(|value| (value - min) / (max - min) < 0.5) { WHITE } else { BLACK };
+            Text::new(text, (SegmentValue::CenterOf(x_index), SegmentValue::CenterOf(y_index)), ("sans-serif", 25).into_font().color(&text_color))
+        }))?;
+
+        root.present()?;
+        Ok(())
+    }
+}
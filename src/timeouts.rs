@@ -0,0 +1,126 @@
+//! Wall-clock limits of variants and scenarios
+//!
+//! A variant's convergence run can be limited to a maximum wall-clock duration (SimulationConverge::with_timeout,
+//! or for all variants VARIANT_TIMEOUT_MS, set by --variant-timeout), and a scenario with one seed to a maximum
+//! duration over all its variants (SCENARIO_TIMEOUT_MS, set by --scenario-timeout). Limits are checked after
+//! every iteration and before a variant starts; a run exceeding one is aborted with a TimeoutError, which
+//! scenarios pass on like any other error and which is reported as a timeout in the validation output, so
+//! runs over many scenarios and seeds finish in predictable time.
+
+use crate::utils::{VARIANT_TIMEOUT_MS, SCENARIO_TIMEOUT_MS};
+use std::cell::RefCell;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+/// What exceeded its wall-clock limit
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeoutScope {
+    VARIANT,
+    SCENARIO,
+}
+
+/// Error of a variant aborted because it or its scenario exceeded its wall-clock limit
+#[derive(Debug, Clone)]
+pub struct TimeoutError {
+    pub scope: TimeoutScope,
+    pub scenario_name: String,
+    pub variant_name: String,
+    pub limit: Duration,
+    /// Time the variant (VARIANT) or scenario (SCENARIO) had run when it was aborted
+    pub elapsed: Duration,
+    /// Iterations the variant completed before it was aborted (0 if it didn't start)
+    pub iterations: usize,
+}
+
+impl std::fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.scope {
+            TimeoutScope::VARIANT => write!(f, "Variant '{}' timed out after {:.2}s (limit {:.2}s) at iteration {}",
+                self.variant_name, self.elapsed.as_secs_f64(), self.limit.as_secs_f64(), self.iterations),
+            TimeoutScope::SCENARIO => write!(f, "Scenario '{}' timed out after {:.2}s (limit {:.2}s) in variant '{}' at iteration {}",
+                self.scenario_name, self.elapsed.as_secs_f64(), self.limit.as_secs_f64(), self.variant_name, self.iterations),
+        }
+    }
+}
+
+impl std::error::Error for TimeoutError {}
+
+/// Whether an error (e.g. returned by a scenario) is a timeout
+pub fn is_timeout(error: &(dyn std::error::Error + 'static)) -> bool {
+    error.is::<TimeoutError>()
+}
+
+/// Whether an error is a timeout of the scenario, which ends it rather than only the variant
+pub fn is_scenario_timeout(error: &(dyn std::error::Error + 'static)) -> bool {
+    error.downcast_ref::<TimeoutError>().is_some_and(|timeout_error| timeout_error.scope == TimeoutScope::SCENARIO)
+}
+
+/// Wall-clock limit of the scenario running on a thread
+#[derive(Debug, Clone)]
+pub struct ScenarioClock {
+    pub scenario_name: String,
+    pub started: Instant,
+    pub limit: Duration,
+}
+
+thread_local! {
+    /// Wall-clock limit of the scenario running on the current thread, None for no limit
+    /// Thread-local so that scenarios running in parallel each have their own clock
+    static SCENARIO_CLOCK: RefCell<Option<ScenarioClock>> = const { RefCell::new(None) };
+}
+
+/// Global limit of variants' convergence runs (VARIANT_TIMEOUT_MS), None for no limit
+pub fn variant_timeout() -> Option<Duration> {
+    match VARIANT_TIMEOUT_MS.load(Ordering::Relaxed) {
+        0 => None,
+        milliseconds => Some(Duration::from_millis(milliseconds)),
+    }
+}
+
+/// Global limit of scenarios (SCENARIO_TIMEOUT_MS), None for no limit
+pub fn scenario_timeout() -> Option<Duration> {
+    match SCENARIO_TIMEOUT_MS.load(Ordering::Relaxed) {
+        0 => None,
+        milliseconds => Some(Duration::from_millis(milliseconds)),
+    }
+}
+
+/// Start the clock of a scenario on the current thread, with the given limit (None for no limit)
+/// Returns the clock running before, to restore with restore_scenario_clock
+pub fn start_scenario_clock(scenario_name: &str, limit: Option<Duration>) -> Option<ScenarioClock> {
+    let clock = limit.map(|limit| ScenarioClock { scenario_name: scenario_name.to_string(), started: Instant::now(), limit });
+    SCENARIO_CLOCK.with(|scenario_clock| scenario_clock.replace(clock))
+}
+
+/// Restore the clock running before start_scenario_clock
+pub fn restore_scenario_clock(clock: Option<ScenarioClock>) {
+    SCENARIO_CLOCK.with(|scenario_clock| scenario_clock.replace(clock));
+}
+
+/// Check whether a variant's run started at `variant_started` has exceeded `variant_limit`, or the scenario
+/// running on the current thread its limit (the variant's limit is checked first)
+///
+/// # Returns
+/// Returns the TimeoutError of the exceeded limit, or None within both limits
+pub fn check_timeouts(variant_name: &str, variant_started: Instant, variant_limit: Option<Duration>, iterations: usize) -> Option<TimeoutError> {
+    if let Some(limit) = variant_limit {
+        let elapsed = variant_started.elapsed();
+        if elapsed >= limit {
+            let scenario_name = SCENARIO_CLOCK.with(|scenario_clock| scenario_clock.borrow().as_ref().map_or(String::new(), |clock| clock.scenario_name.clone()));
+            return Some(TimeoutError { scope: TimeoutScope::VARIANT, scenario_name, variant_name: variant_name.to_string(), limit, elapsed, iterations });
+        }
+    }
+    SCENARIO_CLOCK.with(|scenario_clock| {
+        scenario_clock.borrow().as_ref()
+            .filter(|clock| clock.started.elapsed() >= clock.limit)
+            .map(|clock| TimeoutError {
+                scope: TimeoutScope::SCENARIO,
+                scenario_name: clock.scenario_name.clone(),
+                variant_name: variant_name.to_string(),
+                limit: clock.limit,
+                elapsed: clock.started.elapsed(),
+                iterations,
+            })
+    })
+}
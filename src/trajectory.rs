@@ -0,0 +1,145 @@
+//! Convergence trajectories
+//!
+//! A Trajectory follows a value over the iterations of a convergence run, taken from the statistics of every
+//! iteration (SimulationConverge::history), optionally with the target it converges to. Scenarios check how a
+//! value approaches its target (monotone approach, overshoot, final error) and draw trajectories as line
+//! charts, one series per trajectory with its target as a dashed line.
+
+use crate::simulationrun::SimulationStat;
+use plotters::prelude::*;
+use std::path::Path;
+
+/// Value over the iterations of a convergence run
+#[derive(Debug, Clone)]
+pub struct Trajectory {
+    pub name: String,
+    /// Value in each iteration (index 0 is iteration 1)
+    pub values: Vec<f64>,
+    /// Value the trajectory converges to, None if it has no target
+    pub target: Option<f64>,
+}
+
+impl Trajectory {
+    /// Trajectory of a metric of the statistics of every iteration
+    pub fn from_history(name: &str, history: &[SimulationStat], metric: impl Fn(&SimulationStat) -> f64) -> Self {
+        Self {
+            name: name.to_string(),
+            values: history.iter().map(metric).collect(),
+            target: None,
+        }
+    }
+
+    /// Trajectory of the actual value of a campaign's convergence target, with the target value
+    /// (the target of the last iteration, targets that roll over between periods can change)
+    pub fn campaign_target(history: &[SimulationStat], campaign_id: usize, target_index: usize) -> Self {
+        let target_stats: Vec<_> = history.iter().map(|stats| &stats.campaign_targets[campaign_id][target_index]).collect();
+        Self {
+            name: format!("Campaign {} {}", campaign_id, target_stats.last().map_or("", |target_stat| target_stat.name.as_str())),
+            values: target_stats.iter().map(|target_stat| target_stat.actual).collect(),
+            target: target_stats.last().map(|target_stat| target_stat.target),
+        }
+    }
+
+    /// Set the value the trajectory converges to
+    pub fn with_target(mut self, target: f64) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    fn target_value(&self) -> f64 {
+        self.target.unwrap_or_else(|| panic!("Trajectory '{}' has no target", self.name))
+    }
+
+    /// Error relative to the target in each iteration, (value - target) / target
+    pub fn relative_errors(&self) -> Vec<f64> {
+        let target = self.target_value();
+        self.values.iter().map(|value| (value - target) / target).collect()
+    }
+
+    /// Relative error of the last iteration (absolute)
+    pub fn final_error(&self) -> f64 {
+        self.relative_errors().last().map_or(f64::NAN, |error| error.abs())
+    }
+
+    /// Largest relative excursion past the target, on the opposite side of where the first iteration was
+    /// (0 if the trajectory never crosses the target)
+    pub fn overshoot(&self) -> f64 {
+        let errors = self.relative_errors();
+        let first_sign = errors.first().map_or(0.0, |error| error.signum());
+        errors.iter().map(|error| -error * first_sign).fold(0.0, f64::max)
+    }
+
+    /// Whether the trajectory approaches its target monotonically: the distance to the target never grows by
+    /// more than `tolerance` (relative to the target) from one iteration to the next
+    pub fn is_monotone_approach(&self, tolerance: f64) -> bool {
+        self.relative_errors().windows(2).all(|errors| errors[1].abs() <= errors[0].abs() + tolerance)
+    }
+
+    /// First iteration (1-indexed) from which the trajectory stays within `tolerance` of its target, None if
+    /// it doesn't settle
+    pub fn settling_iteration(&self, tolerance: f64) -> Option<usize> {
+        let errors = self.relative_errors();
+        let unsettled = errors.iter().rposition(|error| error.abs() > tolerance);
+        match unsettled {
+            Some(index) if index + 1 == errors.len() => None,
+            Some(index) => Some(index + 2),
+            None => Some(1),
+        }
+    }
+}
+
+/// Draw trajectories as a line chart, each target as a dashed line in its trajectory's color
+pub fn draw_trajectories(path: &Path, title: &str, y_label: &str, trajectories: &[Trajectory]) -> Result<(), Box<dyn std::error::Error>> {
+    let iterations = trajectories.iter().map(|trajectory| trajectory.values.len()).max().unwrap_or(0).max(2);
+    let values = trajectories.iter().flat_map(|trajectory| trajectory.values.iter().chain(trajectory.target.iter()));
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| (min.min(value), max.max(value)));
+    // Avoid an empty axis for constant trajectories
+    let (min, max) = if min < max { (min, max) } else if min.is_finite() { (min - 1.0, max + 1.0) } else { (0.0, 1.0) };
+    let margin = (max - min) * 0.05;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let root = BitMapBackend::new(path, (1600, 900)).into_drawing_area();
+    root.fill(&WHITE)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 30))
+        .margin(20)
+        .x_label_area_size(60)
+        .y_label_area_size(100)
+        .build_cartesian_2d(1..iterations, (min - margin)..(max + margin))?;
+
+    chart.configure_mesh()
+        .label_style(("sans-serif", 20))
+        .axis_desc_style(("sans-serif", 25))
+        .x_desc("Iteration")
+        .y_desc(y_label)
+        .draw()?;
+
+    for (index, trajectory) in trajectories.iter().enumerate() {
+        let color = Palette99::pick(index).to_rgba();
+        chart.draw_series(LineSeries::new(
+            trajectory.values.iter().enumerate().map(|(iteration, &value)| (iteration + 1, value)),
+            color.stroke_width(3),
+        ))?
+        .label(trajectory.name.clone())
+        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(3)));
+        if let Some(target) = trajectory.target {
+            chart.draw_series(DashedLineSeries::new(
+                [(1, target), (iterations, target)],
+                10,
+                5,
+                color.stroke_width(2),
+            ))?;
+        }
+    }
+    chart.configure_series_labels()
+        .label_font(("sans-serif", 20))
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+
+    root.present()?;
+    Ok(())
+}
@@ -0,0 +1,239 @@
+//! Black-box tuning of simulation parameters
+//!
+//! A Tuner searches bounded parameters (e.g. controller gains or seller parameters) for the configuration
+//! maximizing a scenario-defined objective of the converged statistics, running a variant per evaluated
+//! configuration. The search is a separable CMA-ES (covariance matrix adaptation evolution strategy with a
+//! diagonal covariance): every generation samples a population around the mean, moves the mean towards the
+//! best of them, and adapts the step size and the per-parameter spread from the steps that paid off.
+//! Parameters are searched normalized to [0, 1] within their bounds, so they may have different scales.
+
+use crate::simulationrun::SimulationStat;
+use crate::converge::SimulationConverge;
+use crate::timeouts::is_scenario_timeout;
+use crate::utils::get_seed;
+use crate::logger::{Logger, LogEvent, FileReceiver, sanitize_filename};
+use crate::logln;
+use rand::{rngs::StdRng, SeedableRng};
+use rand_distr::{Distribution, StandardNormal};
+
+/// Initial step size of the search, as a fraction of the parameters' ranges
+const INITIAL_STEP_SIZE: f64 = 0.3;
+
+/// A tuned parameter and the bounds it's searched within
+pub struct TuningParameter {
+    pub name: String,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl TuningParameter {
+    /// Value of the parameter at a normalized position within its bounds (0.0 = min, 1.0 = max)
+    fn denormalize(&self, normalized: f64) -> f64 {
+        self.min + normalized * (self.max - self.min)
+    }
+
+    /// Normalized position of a value within the parameter's bounds
+    fn normalize(&self, value: f64) -> f64 {
+        (value - self.min) / (self.max - self.min)
+    }
+}
+
+/// A configuration evaluated during the search
+#[derive(Debug, Clone)]
+pub struct TuningEvaluation {
+    /// Generation of the search (0-indexed)
+    pub generation: usize,
+    /// Value of each parameter (in order of the tuner's parameters)
+    pub parameter_values: Vec<f64>,
+    /// Objective of the converged run, None if it failed to converge
+    pub objective: Option<f64>,
+}
+
+/// Outcome of a search
+pub struct TuningResult {
+    pub parameter_names: Vec<String>,
+    /// Best evaluated configuration, the first one of those with the highest objective
+    pub best: TuningEvaluation,
+    /// Statistics of the best configuration's run
+    pub best_stats: SimulationStat,
+    /// Every evaluated configuration, in order of evaluation
+    pub trace: Vec<TuningEvaluation>,
+}
+
+/// Black-box tuner maximizing an objective over bounded parameters (separable CMA-ES)
+pub struct Tuner {
+    pub name: String,
+    pub parameters: Vec<TuningParameter>,
+    /// Normalized starting point of the search, the middle of the bounds if None
+    initial_values: Option<Vec<f64>>,
+    pub generations: usize,
+    /// Configurations evaluated per generation, None for the CMA-ES default 4 + 3 ln(parameters)
+    population_size: Option<usize>,
+}
+
+impl Tuner {
+    /// Create a new tuner, `name` names its variants and output files
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters: Vec::new(),
+            initial_values: None,
+            generations: 10,
+            population_size: None,
+        }
+    }
+
+    /// Tune a parameter within [min, max]
+    pub fn with_parameter(mut self, name: &str, min: f64, max: f64) -> Self {
+        assert!(min < max, "Tuning parameter '{}' needs min < max", name);
+        self.parameters.push(TuningParameter { name: name.to_string(), min, max });
+        self
+    }
+
+    /// Start the search from the given parameter values (e.g. the current configuration), one per parameter added before
+    pub fn with_initial_values(mut self, values: Vec<f64>) -> Self {
+        assert!(values.len() == self.parameters.len(), "Tuner '{}' needs an initial value per parameter", self.name);
+        self.initial_values = Some(self.parameters.iter().zip(&values).map(|(parameter, &value)| parameter.normalize(value).clamp(0.0, 1.0)).collect());
+        self
+    }
+
+    /// Set the number of generations and configurations evaluated per generation
+    pub fn with_budget(mut self, generations: usize, population_size: usize) -> Self {
+        assert!(generations > 0 && population_size >= 2, "Tuner '{}' needs at least one generation of two configurations", self.name);
+        self.generations = generations;
+        self.population_size = Some(population_size);
+        self
+    }
+
+    /// Search the configuration maximizing the objective
+    ///
+    /// `prepare` creates the simulation of a configuration from its parameter values (in order of the
+    /// parameters) and `objective` scores its converged statistics. Each configuration runs as variant
+    /// `<tuner name>-gen<generation>-<index>`, configurations failing to converge rank below all others.
+    /// Random draws are seeded from RAND_SEED, so a search is reproducible. The trace is written to
+    /// `tuning-<tuner name>.csv` in the scenario's log directory.
+    ///
+    /// # Returns
+    /// Returns a Result with the TuningResult, or an error if no configuration converged or the scenario timed out
+    pub fn run<P: Fn(&[f64]) -> SimulationConverge, O: Fn(&SimulationStat) -> f64>(
+        &self,
+        prepare: P,
+        objective: O,
+        scenario_name: &str,
+        max_iterations: usize,
+        logger: &mut Logger,
+    ) -> Result<TuningResult, Box<dyn std::error::Error>> {
+        assert!(!self.parameters.is_empty(), "Tuner '{}' has no parameters", self.name);
+        let n = self.parameters.len();
+        let nf = n as f64;
+        let lambda = self.population_size.unwrap_or(4 + (3.0 * nf.ln()).floor() as usize);
+        let mu = lambda / 2;
+
+        // Recombination weights of the best mu configurations and strategy constants (Hansen's defaults,
+        // covariance learning rates scaled up for the diagonal covariance)
+        let raw_weights: Vec<f64> = (1..=mu).map(|rank| (mu as f64 + 0.5).ln() - (rank as f64).ln()).collect();
+        let weights_sum: f64 = raw_weights.iter().sum();
+        let weights: Vec<f64> = raw_weights.iter().map(|weight| weight / weights_sum).collect();
+        let mu_eff = 1.0 / weights.iter().map(|weight| weight * weight).sum::<f64>();
+        let c_sigma = (mu_eff + 2.0) / (nf + mu_eff + 5.0);
+        let d_sigma = 1.0 + 2.0 * (((mu_eff - 1.0) / (nf + 1.0)).sqrt() - 1.0).max(0.0) + c_sigma;
+        let c_c = (4.0 + mu_eff / nf) / (nf + 4.0 + 2.0 * mu_eff / nf);
+        let c_1 = (2.0 / ((nf + 1.3).powi(2) + mu_eff) * (nf + 2.0) / 3.0).min(1.0);
+        let c_mu = (2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((nf + 2.0).powi(2) + mu_eff) * (nf + 2.0) / 3.0).min(1.0 - c_1);
+        let expected_norm = nf.sqrt() * (1.0 - 1.0 / (4.0 * nf) + 1.0 / (21.0 * nf * nf));
+
+        let mut mean = self.initial_values.clone().unwrap_or_else(|| vec![0.5; n]);
+        let mut sigma = INITIAL_STEP_SIZE;
+        let mut variances: Vec<f64> = vec![1.0; n];
+        let mut path_sigma = vec![0.0; n];
+        let mut path_c = vec![0.0; n];
+        let mut rng = StdRng::seed_from_u64(get_seed(34343));
+
+        let mut trace: Vec<TuningEvaluation> = Vec::new();
+        let mut best: Option<(TuningEvaluation, SimulationStat)> = None;
+        for generation in 0..self.generations {
+            // Sample the population, repairing samples outside the bounds onto them
+            let mut population: Vec<(Vec<f64>, Option<f64>)> = Vec::with_capacity(lambda);
+            for index in 0..lambda {
+                let normalized: Vec<f64> = (0..n)
+                    .map(|i| {
+                        let z: f64 = StandardNormal.sample(&mut rng);
+                        (mean[i] + sigma * variances[i].sqrt() * z).clamp(0.0, 1.0)
+                    })
+                    .collect();
+                let parameter_values: Vec<f64> = self.parameters.iter().zip(&normalized).map(|(parameter, &value)| parameter.denormalize(value)).collect();
+
+                let simulation_converge = prepare(&parameter_values);
+                let variant_description = format!("Running {} generation {} with {}", self.name, generation, self.configuration_string(&parameter_values));
+                let result = match simulation_converge.run_variant(&variant_description, scenario_name, &format!("{}-gen{}-{}", self.name, generation, index), max_iterations, logger) {
+                    Err(error) if is_scenario_timeout(error.as_ref()) => return Err(error),
+                    result => result,
+                };
+                let evaluation = TuningEvaluation { generation, parameter_values, objective: result.as_ref().ok().map(&objective) };
+                if let (Ok(stats), Some(objective)) = (result, evaluation.objective) {
+                    if best.as_ref().is_none_or(|(best, _)| objective > best.objective.unwrap()) {
+                        best = Some((evaluation.clone(), stats));
+                    }
+                }
+                population.push((normalized, evaluation.objective));
+                trace.push(evaluation);
+            }
+
+            // Rank the population by objective, configurations failing to converge last
+            population.sort_by(|(_, a), (_, b)| b.unwrap_or(f64::NEG_INFINITY).total_cmp(&a.unwrap_or(f64::NEG_INFINITY)));
+            let steps: Vec<Vec<f64>> = population.iter().take(mu)
+                .map(|(normalized, _)| (0..n).map(|i| (normalized[i] - mean[i]) / sigma).collect())
+                .collect();
+            let weighted_step: Vec<f64> = (0..n).map(|i| weights.iter().zip(&steps).map(|(weight, step)| weight * step[i]).sum()).collect();
+
+            // Move the mean and update the evolution paths, step size and variances
+            for i in 0..n {
+                mean[i] = (mean[i] + sigma * weighted_step[i]).clamp(0.0, 1.0);
+                path_sigma[i] = (1.0 - c_sigma) * path_sigma[i] + (c_sigma * (2.0 - c_sigma) * mu_eff).sqrt() * weighted_step[i] / variances[i].sqrt();
+            }
+            let path_sigma_norm = path_sigma.iter().map(|value| value * value).sum::<f64>().sqrt();
+            let h_sigma = path_sigma_norm / (1.0 - (1.0 - c_sigma).powi(2 * (generation as i32 + 1))).sqrt() < (1.4 + 2.0 / (nf + 1.0)) * expected_norm;
+            for i in 0..n {
+                path_c[i] = (1.0 - c_c) * path_c[i] + if h_sigma { (c_c * (2.0 - c_c) * mu_eff).sqrt() * weighted_step[i] } else { 0.0 };
+                let rank_mu: f64 = weights.iter().zip(&steps).map(|(weight, step)| weight * step[i] * step[i]).sum();
+                let correction = if h_sigma { 0.0 } else { c_c * (2.0 - c_c) * variances[i] };
+                variances[i] = (1.0 - c_1 - c_mu) * variances[i] + c_1 * (path_c[i] * path_c[i] + correction) + c_mu * rank_mu;
+            }
+            sigma *= ((c_sigma / d_sigma) * (path_sigma_norm / expected_norm - 1.0)).exp();
+
+            if let Some((best, _)) = &best {
+                logln!(logger, LogEvent::Scenario, "Tuning {}: generation {} best objective {:.4} with {} (step size {:.4})", self.name, generation, best.objective.unwrap(), self.configuration_string(&best.parameter_values), sigma);
+            }
+        }
+
+        self.write_trace(&trace, scenario_name, logger);
+        let (best, best_stats) = best.ok_or_else(|| format!("Tuner '{}' found no configuration that converges", self.name))?;
+        Ok(TuningResult {
+            parameter_names: self.parameters.iter().map(|parameter| parameter.name.clone()).collect(),
+            best,
+            best_stats,
+            trace,
+        })
+    }
+
+    /// Description of a configuration, e.g. "proportional_gain=0.1, derivative_gain=0.05"
+    fn configuration_string(&self, parameter_values: &[f64]) -> String {
+        self.parameters.iter().zip(parameter_values)
+            .map(|(parameter, value)| format!("{}={:.4}", parameter.name, value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Write the trace as CSV, a row per evaluated configuration with its generation, parameter values,
+    /// whether it converged and its objective (empty if it failed to converge)
+    fn write_trace(&self, trace: &[TuningEvaluation], scenario_name: &str, logger: &mut Logger) {
+        let trace_receiver_id = logger.add_receiver(FileReceiver::new(&logger.log_path(&format!("{}/tuning-{}.csv", sanitize_filename(scenario_name), sanitize_filename(&self.name))), vec![LogEvent::Sweep]));
+        let parameter_names: Vec<&str> = self.parameters.iter().map(|parameter| parameter.name.as_str()).collect();
+        logln!(logger, LogEvent::Sweep, "generation,{},converged,objective", parameter_names.join(","));
+        for evaluation in trace {
+            let parameter_values: Vec<String> = evaluation.parameter_values.iter().map(|value| value.to_string()).collect();
+            logln!(logger, LogEvent::Sweep, "{},{},{},{}", evaluation.generation, parameter_values.join(","), evaluation.objective.is_some(), evaluation.objective.map_or(String::new(), |objective| objective.to_string()));
+        }
+        logger.remove_receiver(trace_receiver_id);
+    }
+}
@@ -1,21 +1,37 @@
-use rand_distr::{LogNormal, Beta};
+use rand_distr::{LogNormal, Beta, Normal, Pareto, Weibull, StandardNormal, Distribution};
 use rand::Rng;
-use std::sync::atomic::{AtomicU64, AtomicBool, Ordering};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, AtomicBool, AtomicUsize};
 
-/// Global random seed that can be modified to change all random number generation
-/// This seed is XORed into all local seeds to ensure reproducible simulations
-pub static RAND_SEED: AtomicU64 = AtomicU64::new(0);
+thread_local! {
+    /// Random seed that can be modified to change all random number generation
+    /// This seed is XORed into all local seeds to ensure reproducible simulations
+    /// Thread-local so that scenarios running in parallel each use their own seed
+    pub static RAND_SEED: Cell<u64> = const { Cell::new(0) };
+}
 
 /// Global flag for verbose logging of auction data
 pub static VERBOSE_AUCTION: AtomicBool = AtomicBool::new(false);
 
+/// Global number of convergence iterations between checkpoints of convergence runs, 0 = no checkpoints
+/// (see checkpoint.rs)
+pub static CHECKPOINT_INTERVAL: AtomicUsize = AtomicUsize::new(0);
+
+/// Global maximum wall-clock duration of a variant's convergence run in milliseconds, 0 = no limit
+/// (see timeouts.rs)
+pub static VARIANT_TIMEOUT_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Global maximum wall-clock duration of a scenario (with one seed) in milliseconds, 0 = no limit
+/// (see timeouts.rs)
+pub static SCENARIO_TIMEOUT_MS: AtomicU64 = AtomicU64::new(0);
+
 /// Global counter for total simulation runs completed
 /// This tracks the number of completed simulation iterations across all scenarios
 pub static TOTAL_SIMULATION_RUNS: AtomicU64 = AtomicU64::new(0);
 
-/// Get a seed value by XORing the global RAND_SEED with a local seed
+/// Get a seed value by XORing the current thread's RAND_SEED with a local seed
 pub fn get_seed(local_seed: u64) -> u64 {
-    RAND_SEED.load(Ordering::Relaxed) ^ local_seed
+    RAND_SEED.get() ^ local_seed
 }
 
 /// Convert mean and standard deviation to log-normal distribution parameters
@@ -28,7 +44,7 @@ pub fn get_seed(local_seed: u64) -> u64 {
 /// To convert from mean (m) and stddev (s):
 /// - σ = sqrt(ln(1 + s²/m²))
 /// - μ = ln(m) - σ²/2
-fn lognormal_from_mean_stddev(mean: f64, stddev: f64) -> (f64, f64) {
+pub fn lognormal_from_mean_stddev(mean: f64, stddev: f64) -> (f64, f64) {
     let variance = stddev * stddev;
     let sigma_squared = (1.0 + variance / (mean * mean)).ln();
     let sigma = sigma_squared.sqrt();
@@ -43,12 +59,230 @@ pub fn lognormal_dist(mean: f64, stddev: f64) -> LogNormal<f64> {
     LogNormal::new(mu, sigma).unwrap()
 }
 
+/// Cholesky decomposition of a symmetric positive definite matrix
+/// Returns the lower triangular L with L × Lᵀ = matrix, or None when the matrix is not positive definite
+pub fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut lower = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| lower[i][k] * lower[j][k]).sum();
+            if i == j {
+                let diagonal = matrix[i][i] - sum;
+                if diagonal <= 0.0 {
+                    return None;
+                }
+                lower[i][j] = diagonal.sqrt();
+            } else {
+                lower[i][j] = (matrix[i][j] - sum) / lower[j][j];
+            }
+        }
+    }
+    Some(lower)
+}
+
+/// Sampler of correlated log-normal variables, each with the same mean and standard deviation
+/// 
+/// The logs of the variables are normal with the given correlation matrix, sampled by multiplying
+/// independent standard normals with the matrix's Cholesky factor
+#[derive(Debug, Clone)]
+pub struct CorrelatedLogNormal {
+    mu: f64,
+    sigma: f64,
+    /// Cholesky factor of the correlation matrix
+    cholesky_factor: Vec<Vec<f64>>,
+}
+
+impl CorrelatedLogNormal {
+    /// Create the sampler from mean, standard deviation and the correlation matrix of the logs
+    /// 
+    /// # Panics
+    /// Panics if the correlation matrix is not square and symmetric, with unit diagonal and positive definite
+    pub fn new(mean: f64, stddev: f64, correlation: Vec<Vec<f64>>) -> Self {
+        let n = correlation.len();
+        assert!(n > 0, "Correlation matrix must not be empty");
+        for (i, row) in correlation.iter().enumerate() {
+            assert_eq!(row.len(), n, "Correlation matrix must be square, row {} has {} entries instead of {}", i, row.len(), n);
+            assert!((row[i] - 1.0).abs() < 1e-12, "Correlation matrix must have a unit diagonal, got {} at ({}, {})", row[i], i, i);
+            for (j, &value) in row.iter().enumerate() {
+                assert!((value - correlation[j][i]).abs() < 1e-12, "Correlation matrix must be symmetric, ({}, {}) differs from ({}, {})", i, j, j, i);
+            }
+        }
+        let cholesky_factor = cholesky(&correlation).expect("Correlation matrix must be positive definite");
+        let (mu, sigma) = lognormal_from_mean_stddev(mean, stddev);
+        Self { mu, sigma, cholesky_factor }
+    }
+
+    /// Number of correlated variables
+    pub fn dimension(&self) -> usize {
+        self.cholesky_factor.len()
+    }
+
+    /// Sample all variables at once into samples (of length dimension())
+    pub fn sample_into<R: Rng + ?Sized>(&self, rng: &mut R, samples: &mut [f64]) {
+        let standard_normals: Vec<f64> = (0..self.dimension()).map(|_| rng.sample(StandardNormal)).collect();
+        for (sample, row) in samples.iter_mut().zip(&self.cholesky_factor) {
+            let correlated_normal: f64 = row.iter().zip(&standard_normals).map(|(l, z)| l * z).sum();
+            *sample = (self.mu + self.sigma * correlated_normal).exp();
+        }
+    }
+}
+
 /// Create a beta distribution with given alpha and beta parameters
 /// Returns values in the range [0, 1]
 pub fn beta_dist(alpha: f64, beta: f64) -> Beta<f64> {
     Beta::new(alpha, beta).unwrap()
 }
 
+/// Create a normal distribution with given mean and standard deviation
+pub fn normal_dist(mean: f64, stddev: f64) -> Normal<f64> {
+    Normal::new(mean, stddev).unwrap()
+}
+
+/// Create a Pareto distribution with minimum value scale and tail index shape
+/// Heavy-tailed: the mean scale × shape / (shape - 1) only exists for shape > 1, the variance for shape > 2
+pub fn pareto_dist(scale: f64, shape: f64) -> Pareto<f64> {
+    Pareto::new(scale, shape).unwrap()
+}
+
+/// Create a Weibull distribution with given scale and shape parameters
+/// The mean is scale × Γ(1 + 1/shape); shape below 1 gives a heavier tail than the exponential distribution
+pub fn weibull_dist(scale: f64, shape: f64) -> Weibull<f64> {
+    Weibull::new(scale, shape).unwrap()
+}
+
+/// Normal distribution restricted to [low, high], sampled by inverting its CDF
+#[derive(Debug, Clone)]
+pub struct TruncatedNormal {
+    mean: f64,
+    stddev: f64,
+    low: f64,
+    high: f64,
+    /// Standard normal CDF at low and high
+    cdf_low: f64,
+    cdf_high: f64,
+}
+
+impl Distribution<f64> for TruncatedNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let probability = rng.gen_range(self.cdf_low..=self.cdf_high);
+        (self.mean + self.stddev * standard_normal_quantile(probability)).clamp(self.low, self.high)
+    }
+}
+
+/// Create a normal distribution with given mean and standard deviation, truncated to [low, high]
+/// (infinite bounds leave that side untruncated)
+/// 
+/// # Panics
+/// Panics if stddev is not positive, low is not below high, or [low, high] holds no probability mass
+pub fn truncated_normal_dist(mean: f64, stddev: f64, low: f64, high: f64) -> TruncatedNormal {
+    assert!(stddev > 0.0, "Standard deviation of a truncated normal distribution must be positive");
+    assert!(low < high, "Truncated normal distribution needs low {} below high {}", low, high);
+    let cdf_low = standard_normal_cdf((low - mean) / stddev);
+    let cdf_high = standard_normal_cdf((high - mean) / stddev);
+    assert!(cdf_low < cdf_high, "Truncated normal distribution has no probability mass in [{}, {}]", low, high);
+    TruncatedNormal { mean, stddev, low, high, cdf_low, cdf_high }
+}
+
+/// Empirical distribution, drawing uniformly from a set of samples
+#[derive(Debug, Clone)]
+pub struct EmpiricalDist {
+    samples: Vec<f64>,
+}
+
+impl EmpiricalDist {
+    /// Create the distribution from its samples
+    /// 
+    /// # Panics
+    /// Panics if there are no samples or any is not finite
+    pub fn new(samples: Vec<f64>) -> Self {
+        assert!(!samples.is_empty(), "Empirical distribution needs at least one sample");
+        assert!(samples.iter().all(|sample| sample.is_finite()), "Empirical distribution samples must be finite");
+        Self { samples }
+    }
+
+    /// Load the samples from the given column of a CSV file
+    /// The header row names the columns; lines starting with `#` are comments
+    pub fn from_csv(path: &str, column: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path).map_err(|error| format!("Can't read samples from '{}': {}", path, error))?;
+        Self::from_csv_str(&contents, column).map_err(|error| format!("Invalid samples in '{}': {}", path, error).into())
+    }
+
+    /// Load the samples from the given column of the contents of a CSV file
+    pub fn from_csv_str(contents: &str, column: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut lines = contents.lines().enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let (_, header) = lines.next().ok_or("No header row")?;
+        let column_index = header.split(',').position(|name| name.trim() == column).ok_or_else(|| format!("No {} column", column))?;
+
+        let mut samples = Vec::new();
+        for (line_number, line) in lines {
+            let text = line.split(',').nth(column_index).ok_or_else(|| format!("Line {}: missing column {}", line_number, column))?.trim();
+            let sample = text.parse::<f64>().ok().filter(|sample| sample.is_finite())
+                .ok_or_else(|| format!("Line {}: invalid {} '{}'", line_number, column, text))?;
+            samples.push(sample);
+        }
+        if samples.is_empty() {
+            return Err("No samples".into());
+        }
+        Ok(Self::new(samples))
+    }
+
+    /// Mean of the samples
+    pub fn mean(&self) -> f64 {
+        self.samples.iter().sum::<f64>() / self.samples.len() as f64
+    }
+}
+
+impl Distribution<f64> for EmpiricalDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.samples[rng.gen_range(0..self.samples.len())]
+    }
+}
+
+
+/// Cumulative distribution function of the standard normal distribution
+/// 
+/// Uses the complementary error function approximation of Numerical Recipes (erfcc),
+/// with relative error below 1.2e-7 everywhere
+pub fn standard_normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let erfc = t * (-z * z - 1.26551223 + t * (1.00002368 + t * (0.37409196 + t * (0.09678418
+        + t * (-0.18628806 + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587
+        + t * (-0.82215223 + t * 0.17087277))))))))).exp();
+    if x >= 0.0 { 1.0 - 0.5 * erfc } else { 0.5 * erfc }
+}
+
+/// Quantile function (inverse CDF) of the standard normal distribution
+/// 
+/// Uses Acklam's rational approximation, with relative error below 1.2e-9
+pub fn standard_normal_quantile(probability: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e1, 2.209460984245205e2, -2.759285104469687e2, 1.38357751867269e2, -3.066479806614716e1, 2.506628277459239];
+    const B: [f64; 5] = [-5.447609879822406e1, 1.615858368580409e2, -1.556989798598866e2, 6.680131188771972e1, -1.328068155288572e1];
+    const C: [f64; 6] = [-7.784894002430293e-3, -3.223964580411365e-1, -2.400758277161838, -2.549732539343734, 4.374664141464968, 2.938163982698783];
+    const D: [f64; 4] = [7.784695709041462e-3, 3.224671290700398e-1, 2.445134137142996, 3.754408661907416];
+    const LOW: f64 = 0.02425;
+    
+    if probability <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if probability >= 1.0 {
+        return f64::INFINITY;
+    }
+    if probability < LOW {
+        let q = (-2.0 * probability.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if probability <= 1.0 - LOW {
+        let q = probability - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -standard_normal_quantile(1.0 - probability)
+    }
+}
 
 /// Sample a bid from a logistic distribution with given sigmoid parameters
 /// 
@@ -0,0 +1,129 @@
+//! Variants of a base marketplace differing by small deltas
+//!
+//! Most scenarios run variants of one marketplace that differ by a single change, e.g. one campaign's bidder or
+//! bid cap. run_variants builds the base campaigns and sellers anew for every variant, applies the variant's
+//! delta to them and runs it on the impressions of the baseline (see Marketplace::new_shared), so variants only
+//! differ by their deltas and scenarios don't repeat the marketplace setup per variant. The baseline and every
+//! variant are reported side by side in a comparison table.
+
+use crate::simulationrun::{Marketplace, SimulationType, SimulationStat};
+use crate::campaigns::Campaigns;
+use crate::sellers::Sellers;
+use crate::converge::SimulationConverge;
+use crate::impressions::ImpressionsParam;
+use crate::logger::{Logger, LogEvent};
+use crate::logln;
+
+/// Name of the variant running the base marketplace unchanged
+pub const BASELINE_NAME: &str = "baseline";
+
+/// Change of the base campaigns and sellers
+type VariantDelta = Box<dyn Fn(&mut Campaigns, &mut Sellers)>;
+
+/// Named change of the base marketplace
+pub struct Variant {
+    pub name: String,
+    delta: VariantDelta,
+}
+
+impl Variant {
+    /// Create a new variant, `delta` changes the base campaigns and sellers (e.g. sets a campaign's bid cap)
+    /// The variant offers the baseline's impressions, so the delta must keep the value groups of the campaigns
+    /// and the number of impressions each seller offers
+    pub fn new<F: Fn(&mut Campaigns, &mut Sellers) + 'static>(name: &str, delta: F) -> Self {
+        Self { name: name.to_string(), delta: Box::new(delta) }
+    }
+}
+
+/// Converged statistics of the baseline and every variant
+pub struct VariantsResult {
+    /// Names of the campaigns and sellers of the base marketplace, for the comparison table
+    pub campaign_names: Vec<String>,
+    pub seller_names: Vec<String>,
+    /// Statistics of the unchanged base marketplace
+    pub baseline: SimulationStat,
+    /// Name and statistics of every variant, in order
+    pub variants: Vec<(String, SimulationStat)>,
+}
+
+impl VariantsResult {
+    /// Statistics of a variant
+    pub fn variant(&self, name: &str) -> &SimulationStat {
+        self.variants.iter()
+            .find(|(variant_name, _)| variant_name == name)
+            .map(|(_, stats)| stats)
+            .unwrap_or_else(|| panic!("No variant '{}'", name))
+    }
+
+    /// Output the baseline and every variant side by side, with the change from the baseline
+    pub fn printout(&self, logger: &mut Logger, event: LogEvent) {
+        printout_comparison("Variants", &self.campaign_names, &self.seller_names, &self.baseline, &self.variants, logger, event);
+    }
+}
+
+/// Run the base marketplace and every variant of it to convergence
+///
+/// `base` creates the campaigns and sellers of the base marketplace, anew for every variant. The baseline runs
+/// first as variant "baseline", generating the impressions from `impressions_params`, then every variant runs
+/// on the same impressions with its delta applied, as variant `<variant name>`.
+///
+/// # Returns
+/// Returns a Result with the VariantsResult, or the error of the first variant that fails to converge
+pub fn run_variants<F: Fn() -> (Campaigns, Sellers)>(
+    base: F,
+    impressions_params: &ImpressionsParam,
+    simulation_type: SimulationType,
+    variants: &[Variant],
+    scenario_name: &str,
+    max_iterations: usize,
+    logger: &mut Logger,
+) -> Result<VariantsResult, Box<dyn std::error::Error>> {
+    let (campaigns, sellers) = base();
+    let campaign_names = campaigns.campaigns.iter().map(|campaign| campaign.campaign_name().to_string()).collect();
+    let seller_names = sellers.sellers.iter().map(|seller| seller.seller_name().to_string()).collect();
+    let marketplace = Marketplace::new(campaigns, sellers, impressions_params, simulation_type.clone());
+    let impressions = marketplace.shared_impressions();
+    let baseline = SimulationConverge::new(marketplace).run_variant("Running baseline", scenario_name, BASELINE_NAME, max_iterations, logger)?;
+
+    let mut variant_stats = Vec::with_capacity(variants.len());
+    for variant in variants {
+        let (mut campaigns, mut sellers) = base();
+        (variant.delta)(&mut campaigns, &mut sellers);
+        let marketplace = Marketplace::new_shared(campaigns, sellers, impressions.clone(), simulation_type.clone());
+        let stats = SimulationConverge::new(marketplace).run_variant(&format!("Running variant {}", variant.name), scenario_name, &variant.name, max_iterations, logger)?;
+        variant_stats.push((variant.name.clone(), stats));
+    }
+    Ok(VariantsResult { campaign_names, seller_names, baseline, variants: variant_stats })
+}
+
+/// Output a comparison table of a baseline and other statistics of the same marketplace side by side, with the
+/// change of every metric from the baseline
+pub fn printout_comparison(title: &str, campaign_names: &[String], seller_names: &[String], baseline: &SimulationStat, others: &[(String, SimulationStat)], logger: &mut Logger, event: LogEvent) {
+    logln!(logger, event, "\n=== {} ===", title);
+    let mut header = format!("{:<36} {:>12}", "", "baseline");
+    for (name, _) in others {
+        header.push_str(&format!(" {:>16} {:>9}", name, "change"));
+    }
+    logln!(logger, event, "{}", header);
+
+    let mut row = |label: String, metric: &dyn Fn(&SimulationStat) -> f64| {
+        let baseline = metric(baseline);
+        let mut line = format!("{:<36} {:>12.4}", label, baseline);
+        for (_, stats) in others {
+            let value = metric(stats);
+            let change = if baseline != 0.0 { format!("{:+.2}%", (value / baseline - 1.0) * 100.0) } else { "-".to_string() };
+            line.push_str(&format!(" {:>16.4} {:>9}", value, change));
+        }
+        logln!(logger, event, "{}", line);
+    };
+    for (campaign_id, campaign_name) in campaign_names.iter().enumerate() {
+        row(format!("{} impressions", campaign_name), &|stats| stats.campaign_stats[campaign_id].impressions_obtained);
+        row(format!("{} spend", campaign_name), &|stats| stats.campaign_stats[campaign_id].total_buyer_charge);
+        row(format!("{} value", campaign_name), &|stats| stats.campaign_stats[campaign_id].total_value);
+    }
+    for (seller_id, seller_name) in seller_names.iter().enumerate() {
+        row(format!("{} impressions sold", seller_name), &|stats| stats.seller_stats[seller_id].impressions_sold as f64);
+        row(format!("{} revenue", seller_name), &|stats| stats.seller_stats[seller_id].total_buyer_charge);
+    }
+    row("Total value".to_string(), &|stats| stats.overall_stat.total_value);
+}
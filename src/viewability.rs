@@ -0,0 +1,64 @@
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, Normal};
+use crate::impressions::DistributionF64;
+
+/// Trait for generating the viewability of impressions
+pub trait ViewabilityGeneratorTrait {
+    /// Generate the probability that an impression will be viewable (ground truth)
+    ///
+    /// # Returns
+    /// Viewability probability in [0, 1]
+    fn generate_viewability(&self, rng: &mut StdRng) -> f64;
+
+    /// Generate the viewability measured for an impression, which is what bidders observe
+    /// Defaults to the actual viewability (exact measurement)
+    ///
+    /// # Arguments
+    /// * `viewability` - Actual viewability probability of the impression
+    /// * `rng` - Random number generator, separate from the one generating viewability
+    ///
+    /// # Returns
+    /// Measured viewability in [0, 1]
+    fn measure_viewability(&self, viewability: f64, _rng: &mut StdRng) -> f64 {
+        viewability
+    }
+}
+
+/// Viewability generator sampling the viewability probability from a distribution (samples are clamped
+/// to [0, 1]), measured exactly unless a measurement error is set
+pub struct ViewabilityGeneratorDistribution {
+    viewability_dist: Box<dyn DistributionF64>,
+    /// Bias added to measured viewability (> 0.0 = viewability is overstated)
+    measurement_bias: f64,
+    /// Distribution of zero-mean noise added to measured viewability, None for no noise
+    measurement_noise_dist: Option<Normal<f64>>,
+}
+
+impl ViewabilityGeneratorDistribution {
+    /// Create a new ViewabilityGeneratorDistribution measuring viewability exactly
+    pub fn new<D>(viewability_dist: D) -> Box<Self>
+    where
+        D: Distribution<f64> + 'static,
+    {
+        Box::new(Self { viewability_dist: Box::new(viewability_dist), measurement_bias: 0.0, measurement_noise_dist: None })
+    }
+
+    /// Measure viewability as viewability + bias + normal noise with the given stddev, clamped to [0, 1]
+    pub fn with_measurement_error(mut self: Box<Self>, bias: f64, noise_stddev: f64) -> Box<Self> {
+        assert!(noise_stddev >= 0.0, "Viewability measurement noise stddev must not be negative");
+        self.measurement_bias = bias;
+        self.measurement_noise_dist = (noise_stddev > 0.0).then(|| Normal::new(0.0, noise_stddev).unwrap());
+        self
+    }
+}
+
+impl ViewabilityGeneratorTrait for ViewabilityGeneratorDistribution {
+    fn generate_viewability(&self, rng: &mut StdRng) -> f64 {
+        self.viewability_dist.sample(rng).clamp(0.0, 1.0)
+    }
+
+    fn measure_viewability(&self, viewability: f64, rng: &mut StdRng) -> f64 {
+        let noise = self.measurement_noise_dist.as_ref().map_or(0.0, |noise_dist| Distribution::sample(noise_dist, rng));
+        (viewability + self.measurement_bias + noise).clamp(0.0, 1.0)
+    }
+}